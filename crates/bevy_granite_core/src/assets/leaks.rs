@@ -0,0 +1,194 @@
+use super::AvailableEditableMaterials;
+use crate::{
+    events::{RequestAssetLeakScanEvent, RequestPurgeUnusedAssetsEvent},
+    shared::EditorCapabilities,
+};
+use bevy::{
+    asset::{AssetId, AssetServer, Assets, UntypedAssetId},
+    ecs::system::SystemParam,
+    mesh::{Mesh, Mesh3d},
+    pbr::{MeshMaterial3d, StandardMaterial},
+    platform::collections::{HashMap, HashSet},
+    prelude::{Image, MessageReader, Query, Res, ResMut, Resource},
+};
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+
+// leaks.rs
+// Finds assets that are still alive but no entity or material definition uses, so long edit sessions can free them
+// Only assets Granite can vouch for are reported: tracked material textures, materials built from them and meshes loaded from files
+// Runtime assets without a file (gizmo and icon materials, procedural meshes) belong to whoever created them and are left alone
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LeakedAsset {
+    pub id: UntypedAssetId,
+    /// Asset path, or the texture path for materials
+    pub label: String,
+}
+
+/// Result of the last leak scan
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct AssetLeakReport {
+    /// Materials with Granite textures that no entity or material definition uses
+    pub materials: Vec<LeakedAsset>,
+    /// File meshes that no entity uses
+    pub meshes: Vec<LeakedAsset>,
+    /// Textures kept alive only by `AvailableEditableMaterials.image_paths`
+    pub images: Vec<LeakedAsset>,
+    pub scanned: bool,
+}
+
+impl AssetLeakReport {
+    pub fn total(&self) -> usize {
+        self.materials.len() + self.meshes.len() + self.images.len()
+    }
+}
+
+#[derive(SystemParam)]
+pub struct AssetLeakScanner<'w, 's> {
+    materials: ResMut<'w, Assets<StandardMaterial>>,
+    meshes: ResMut<'w, Assets<Mesh>>,
+    asset_server: Res<'w, AssetServer>,
+    material_users: Query<'w, 's, &'static MeshMaterial3d<StandardMaterial>>,
+    mesh_users: Query<'w, 's, &'static Mesh3d>,
+}
+
+impl AssetLeakScanner<'_, '_> {
+    fn scan(&self, available: &AvailableEditableMaterials) -> AssetLeakReport {
+        let tracked: HashMap<AssetId<Image>, &String> = available
+            .image_paths
+            .iter()
+            .map(|(handle, path)| (handle.id(), path))
+            .collect();
+
+        let mut used_materials: HashSet<AssetId<StandardMaterial>> = self
+            .material_users
+            .iter()
+            .map(|material| material.0.id())
+            .collect();
+        if let Some(definitions) = available.materials() {
+            used_materials.extend(
+                definitions
+                    .iter()
+                    .filter_map(|definition| definition.handle.as_ref().map(|handle| handle.id())),
+            );
+        }
+
+        let mut report = AssetLeakReport {
+            scanned: true,
+            ..Default::default()
+        };
+        let mut used_images: HashSet<AssetId<Image>> = HashSet::new();
+        for (id, material) in self.materials.iter() {
+            let textures = material_textures(material);
+            if used_materials.contains(&id) {
+                used_images.extend(textures);
+            } else if let Some(path) = textures.iter().find_map(|texture| tracked.get(texture)) {
+                report.materials.push(LeakedAsset {
+                    id: id.untyped(),
+                    label: format!("Material using {}", path),
+                });
+            }
+        }
+
+        let used_meshes: HashSet<AssetId<Mesh>> =
+            self.mesh_users.iter().map(|mesh| mesh.0.id()).collect();
+        for (id, _) in self.meshes.iter() {
+            if used_meshes.contains(&id) {
+                continue;
+            }
+            if let Some(path) = self.asset_server.get_path(id) {
+                report.meshes.push(LeakedAsset {
+                    id: id.untyped(),
+                    label: path.to_string(),
+                });
+            }
+        }
+
+        for (handle, path) in available.image_paths.iter() {
+            if !used_images.contains(&handle.id()) {
+                report.images.push(LeakedAsset {
+                    id: handle.id().untyped(),
+                    label: path.clone(),
+                });
+            }
+        }
+        report.images.sort_by(|a, b| a.label.cmp(&b.label));
+
+        report
+    }
+}
+
+/// Every texture slot a Granite material definition can fill
+fn material_textures(material: &StandardMaterial) -> Vec<AssetId<Image>> {
+    [
+        &material.base_color_texture,
+        &material.metallic_roughness_texture,
+        &material.emissive_texture,
+        &material.normal_map_texture,
+        &material.occlusion_texture,
+        &material.depth_map,
+    ]
+    .into_iter()
+    .flatten()
+    .map(|handle| handle.id())
+    .collect()
+}
+
+/// Scans on request. Purging removes the reported materials and meshes and drops the stale texture handles,
+/// which frees the textures once nothing else holds them
+pub fn asset_leak_system(
+    mut scan_reader: MessageReader<RequestAssetLeakScanEvent>,
+    mut purge_reader: MessageReader<RequestPurgeUnusedAssetsEvent>,
+    mut scanner: AssetLeakScanner,
+    mut available: ResMut<AvailableEditableMaterials>,
+    mut report: ResMut<AssetLeakReport>,
+    capabilities: Res<EditorCapabilities>,
+) {
+    let scan = scan_reader.read().count() > 0;
+    let purge = purge_reader.read().count() > 0 && capabilities.can_delete("unused assets");
+    if !scan && !purge {
+        return;
+    }
+
+    let found = scanner.scan(&available);
+    if !purge {
+        log!(
+            LogType::Game,
+            LogLevel::Info,
+            LogCategory::Asset,
+            "Leak scan found {} unused materials, {} meshes and {} textures",
+            found.materials.len(),
+            found.meshes.len(),
+            found.images.len()
+        );
+        *report = found;
+        return;
+    }
+
+    for leaked in found.materials.iter() {
+        if let Ok(id) = leaked.id.try_typed::<StandardMaterial>() {
+            scanner.materials.remove(id);
+        }
+    }
+    for leaked in found.meshes.iter() {
+        if let Ok(id) = leaked.id.try_typed::<Mesh>() {
+            scanner.meshes.remove(id);
+        }
+    }
+    let stale: HashSet<UntypedAssetId> = found.images.iter().map(|leaked| leaked.id).collect();
+    available
+        .image_paths
+        .retain(|handle, _| !stale.contains(&handle.id().untyped()));
+
+    log!(
+        LogType::Game,
+        LogLevel::OK,
+        LogCategory::Asset,
+        "Purged {} unused assets",
+        found.total()
+    );
+    *report = scanner.scan(&available);
+}
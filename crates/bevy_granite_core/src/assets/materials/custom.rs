@@ -0,0 +1,374 @@
+use super::{load_texture_with_repeat, AvailableEditableMaterials, MaterialOverride};
+use bevy::{
+    asset::{Asset, AssetId, AssetServer, Assets, Handle},
+    color::{Color, ColorToComponents, LinearRgba},
+    ecs::{
+        change_detection::DetectChanges,
+        component::Component,
+        entity::Entity,
+        query::Without,
+        resource::Resource,
+        system::{Commands, Query, Res, ResMut},
+    },
+    image::Image,
+    math::Vec4,
+    mesh::MeshVertexBufferLayoutRef,
+    pbr::{Material, MaterialPipeline, MaterialPipelineKey, MeshMaterial3d, StandardMaterial},
+    prelude::{AlphaMode, Ref},
+    reflect::Reflect,
+    render::render_resource::{
+        AsBindGroup, RenderPipelineDescriptor, ShaderType, SpecializedMeshPipelineError,
+    },
+    shader::{Shader, ShaderRef},
+    time::Time,
+};
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// custom.rs
+// Materials drawn by a WGSL fragment shader of the project's own, for looks StandardMaterial can't do
+// A .mat with a `custom` definition names the shader and a list of uniforms. Entities using it are moved onto a
+// CustomShaderMaterial built from it, like extended materials are, and back once the definition is removed
+// Uniforms fill fixed slots in declaration order, each kind counting on its own. custom.wgsl declares the bindings
+
+const CUSTOM_FALLBACK_SHADER: &str =
+    "embedded://bevy_granite_core/assets/materials/custom_fallback.wgsl";
+
+pub const CUSTOM_COLOR_SLOTS: usize = 4;
+pub const CUSTOM_FLOAT_SLOTS: usize = 8;
+pub const CUSTOM_TEXTURE_SLOTS: usize = 4;
+
+/// Value of a custom material uniform, its kind picks the slot it goes in
+#[derive(Reflect, Deserialize, Serialize, PartialEq, Debug, Clone)]
+pub enum CustomUniformValue {
+    /// sRGB with alpha, passed to the shader as linear
+    Color((f32, f32, f32, f32)),
+    Float(f32),
+    /// Image path relative to assets/
+    Texture(String),
+}
+
+impl CustomUniformValue {
+    pub fn kind_name(&self) -> &'static str {
+        match self {
+            CustomUniformValue::Color(_) => "Color",
+            CustomUniformValue::Float(_) => "Float",
+            CustomUniformValue::Texture(_) => "Texture",
+        }
+    }
+}
+
+#[derive(Reflect, Deserialize, Serialize, PartialEq, Debug, Clone)]
+pub struct CustomUniform {
+    /// Only for reading the .mat, the shader sees slots
+    pub name: String,
+    pub value: CustomUniformValue,
+}
+
+/// A .mat drawn by its own WGSL shader instead of StandardMaterial
+#[derive(Reflect, Deserialize, Serialize, PartialEq, Debug, Clone, Default)]
+pub struct CustomMaterialDef {
+    /// Fragment shader path relative to assets/
+    pub shader: String,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub uniforms: Vec<CustomUniform>,
+
+    /// Updates `time` every frame. Off leaves the material alone between edits
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub animated: bool,
+
+    /// Alpha blended instead of opaque
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub blend: bool,
+}
+
+impl CustomMaterialDef {
+    /// Slot of each uniform in `uniforms`, None past the last slot of its kind
+    pub fn slots(&self) -> Vec<Option<usize>> {
+        let (mut colors, mut floats, mut textures) = (0, 0, 0);
+        self.uniforms
+            .iter()
+            .map(|uniform| {
+                let (next, limit) = match uniform.value {
+                    CustomUniformValue::Color(_) => (&mut colors, CUSTOM_COLOR_SLOTS),
+                    CustomUniformValue::Float(_) => (&mut floats, CUSTOM_FLOAT_SLOTS),
+                    CustomUniformValue::Texture(_) => (&mut textures, CUSTOM_TEXTURE_SLOTS),
+                };
+                let slot = (*next < limit).then_some(*next);
+                *next += 1;
+                slot
+            })
+            .collect()
+    }
+
+    fn write(&self, material: &mut CustomShaderMaterial, asset_server: &AssetServer) {
+        let shader: Handle<Shader> = asset_server.load(self.shader.clone());
+        material.shader = Some(shader);
+        material.alpha_mode = if self.blend {
+            AlphaMode::Blend
+        } else {
+            AlphaMode::Opaque
+        };
+
+        let params = &mut material.params;
+        params.colors = [Vec4::ZERO; CUSTOM_COLOR_SLOTS];
+        params.floats = [Vec4::ZERO; CUSTOM_FLOAT_SLOTS / 4];
+        let mut textures: [Option<Handle<Image>>; CUSTOM_TEXTURE_SLOTS] = Default::default();
+        for (uniform, slot) in self.uniforms.iter().zip(self.slots()) {
+            let Some(slot) = slot else {
+                log!(
+                    LogType::Game,
+                    LogLevel::Warning,
+                    LogCategory::Asset,
+                    "Custom material uniform '{}' has no {} slot left, it is skipped",
+                    uniform.name,
+                    uniform.value.kind_name()
+                );
+                continue;
+            };
+            match &uniform.value {
+                CustomUniformValue::Color((r, g, b, a)) => {
+                    params.colors[slot] = LinearRgba::from(Color::srgba(*r, *g, *b, *a)).to_vec4();
+                }
+                CustomUniformValue::Float(value) => {
+                    params.floats[slot / 4][slot % 4] = *value;
+                }
+                CustomUniformValue::Texture(path) if !path.is_empty() => {
+                    textures[slot] =
+                        Some(load_texture_with_repeat(asset_server, path.clone(), true));
+                }
+                CustomUniformValue::Texture(_) => {}
+            }
+        }
+        let [texture_0, texture_1, texture_2, texture_3] = textures;
+        material.texture_0 = texture_0;
+        material.texture_1 = texture_1;
+        material.texture_2 = texture_2;
+        material.texture_3 = texture_3;
+    }
+}
+
+/// Layout shared with custom.wgsl
+#[derive(ShaderType, Reflect, Debug, Clone, Default)]
+pub struct CustomMaterialUniform {
+    pub colors: [Vec4; CUSTOM_COLOR_SLOTS],
+    /// Four floats per vector, see `custom_float` in custom.wgsl
+    pub floats: [Vec4; CUSTOM_FLOAT_SLOTS / 4],
+    /// Seconds, only moving for animated materials
+    pub time: f32,
+}
+
+/// Pipelines are told apart by the shader, so every custom material gets its own
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CustomMaterialKey {
+    shader: Option<Handle<Shader>>,
+}
+
+impl From<&CustomShaderMaterial> for CustomMaterialKey {
+    fn from(material: &CustomShaderMaterial) -> Self {
+        Self {
+            shader: material.shader.clone(),
+        }
+    }
+}
+
+/// Material of entities using a CustomMaterialDef. The shader imports `bevy_granite::custom_material` and reads
+/// `material.colors[i]`, `custom_float(i)` and `texture_i`/`sampler_i`, filled from the .mat uniforms in slot order
+#[derive(Asset, AsBindGroup, Reflect, Debug, Clone, Default)]
+#[bind_group_data(CustomMaterialKey)]
+pub struct CustomShaderMaterial {
+    #[uniform(0)]
+    pub params: CustomMaterialUniform,
+    #[texture(1)]
+    #[sampler(2)]
+    pub texture_0: Option<Handle<Image>>,
+    #[texture(3)]
+    #[sampler(4)]
+    pub texture_1: Option<Handle<Image>>,
+    #[texture(5)]
+    #[sampler(6)]
+    pub texture_2: Option<Handle<Image>>,
+    #[texture(7)]
+    #[sampler(8)]
+    pub texture_3: Option<Handle<Image>>,
+    /// Fragment shader of the main pass, the fallback until one is set
+    pub shader: Option<Handle<Shader>>,
+    pub alpha_mode: AlphaMode,
+}
+
+impl Material for CustomShaderMaterial {
+    fn fragment_shader() -> ShaderRef {
+        CUSTOM_FALLBACK_SHADER.into()
+    }
+
+    fn alpha_mode(&self) -> AlphaMode {
+        self.alpha_mode
+    }
+
+    /// Swaps the fallback for the material's own shader. Prepasses keep their default fragment
+    fn specialize(
+        _pipeline: &MaterialPipeline,
+        descriptor: &mut RenderPipelineDescriptor,
+        _layout: &MeshVertexBufferLayoutRef,
+        key: MaterialPipelineKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        let (Some(fragment), Some(shader)) =
+            (descriptor.fragment.as_mut(), key.bind_group_data.shader)
+        else {
+            return Ok(());
+        };
+        if fragment
+            .shader
+            .path()
+            .is_some_and(|path| path.to_string() == CUSTOM_FALLBACK_SHADER)
+        {
+            fragment.shader = shader;
+        }
+        Ok(())
+    }
+}
+
+/// Runtime link between an entity on a CustomShaderMaterial and the shared .mat material it came from
+#[derive(Component, Debug, Clone)]
+pub struct CustomMaterialState {
+    pub shared: Handle<StandardMaterial>,
+}
+
+/// One CustomShaderMaterial per custom .mat, shared by every entity using it
+#[derive(Resource, Default)]
+pub struct CustomMaterials {
+    by_material:
+        HashMap<AssetId<StandardMaterial>, (CustomMaterialDef, Handle<CustomShaderMaterial>)>,
+}
+
+impl CustomMaterials {
+    pub fn get(&self, shared: AssetId<StandardMaterial>) -> Option<&Handle<CustomShaderMaterial>> {
+        self.by_material.get(&shared).map(|(_, handle)| handle)
+    }
+}
+
+type AssignedEntity = (
+    Entity,
+    Ref<'static, MeshMaterial3d<StandardMaterial>>,
+    Option<&'static CustomMaterialState>,
+);
+type CustomEntity = (
+    Entity,
+    &'static CustomMaterialState,
+    &'static MeshMaterial3d<CustomShaderMaterial>,
+);
+
+/// Keeps CustomShaderMaterials in step with the .mat custom definitions, and swaps entities onto or off them
+#[allow(clippy::too_many_arguments)]
+pub fn apply_custom_materials_system(
+    mut commands: Commands,
+    available: Res<AvailableEditableMaterials>,
+    mut custom_materials: ResMut<CustomMaterials>,
+    mut materials: ResMut<Assets<CustomShaderMaterial>>,
+    assigned: Query<AssignedEntity, Without<MaterialOverride>>,
+    custom: Query<CustomEntity>,
+    asset_server: Res<AssetServer>,
+    time: Res<Time>,
+) {
+    let mut rebuilt = false;
+    if available.is_changed() {
+        let mut defs = HashMap::new();
+        for material in available.materials().unwrap_or_default() {
+            let (Some(def), Some(handle)) = (available.resolved_def(material), &material.handle)
+            else {
+                continue;
+            };
+            if let Some(custom) = def
+                .custom
+                .as_ref()
+                .filter(|custom| !custom.shader.is_empty())
+            {
+                defs.insert(handle.id(), custom.clone());
+            }
+        }
+        let before = custom_materials.by_material.len();
+        custom_materials
+            .by_material
+            .retain(|id, _| defs.contains_key(id));
+        rebuilt = before != custom_materials.by_material.len();
+        for (id, def) in defs {
+            match custom_materials.by_material.get_mut(&id) {
+                Some((existing, handle)) => {
+                    if *existing != def {
+                        if let Some(material) = materials.get_mut(handle.id()) {
+                            def.write(material, &asset_server);
+                        }
+                        *existing = def;
+                    }
+                }
+                None => {
+                    let mut material = CustomShaderMaterial::default();
+                    def.write(&mut material, &asset_server);
+                    let handle = materials.add(material);
+                    custom_materials.by_material.insert(id, (def, handle));
+                    rebuilt = true;
+                }
+            }
+        }
+    }
+
+    // Newly assigned materials, including a custom entity given another material by the editor
+    for (entity, mesh_material, state) in assigned.iter() {
+        if !rebuilt && !mesh_material.is_changed() {
+            continue;
+        }
+        match custom_materials.get(mesh_material.0.id()) {
+            Some(handle) => {
+                commands
+                    .entity(entity)
+                    .remove::<MeshMaterial3d<StandardMaterial>>()
+                    .insert((
+                        MeshMaterial3d(handle.clone()),
+                        CustomMaterialState {
+                            shared: mesh_material.0.clone(),
+                        },
+                    ));
+            }
+            None if state.is_some() => {
+                commands
+                    .entity(entity)
+                    .remove::<(MeshMaterial3d<CustomShaderMaterial>, CustomMaterialState)>();
+            }
+            None => {}
+        }
+    }
+
+    if rebuilt {
+        for (entity, state, mesh_material) in custom.iter() {
+            match custom_materials.get(state.shared.id()) {
+                Some(handle) if *handle != mesh_material.0 => {
+                    commands
+                        .entity(entity)
+                        .insert(MeshMaterial3d(handle.clone()));
+                }
+                Some(_) => {}
+                None => {
+                    commands
+                        .entity(entity)
+                        .remove::<(MeshMaterial3d<CustomShaderMaterial>, CustomMaterialState)>()
+                        .insert(MeshMaterial3d(state.shared.clone()));
+                }
+            }
+        }
+    }
+
+    let elapsed = time.elapsed_secs_wrapped();
+    for (def, handle) in custom_materials.by_material.values() {
+        if !def.animated {
+            continue;
+        }
+        if let Some(material) = materials.get_mut(handle.id()) {
+            material.params.time = elapsed;
+        }
+    }
+}
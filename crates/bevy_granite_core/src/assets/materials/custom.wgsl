@@ -0,0 +1,25 @@
+// custom.wgsl
+// Bindings of CustomShaderMaterial, see custom.rs. Import them in a custom .mat shader
+
+#define_import_path bevy_granite::custom_material
+
+struct CustomMaterial {
+    colors: array<vec4<f32>, 4>,
+    floats: array<vec4<f32>, 2>,
+    time: f32,
+}
+
+@group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> material: CustomMaterial;
+@group(#{MATERIAL_BIND_GROUP}) @binding(1) var texture_0: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(2) var sampler_0: sampler;
+@group(#{MATERIAL_BIND_GROUP}) @binding(3) var texture_1: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(4) var sampler_1: sampler;
+@group(#{MATERIAL_BIND_GROUP}) @binding(5) var texture_2: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(6) var sampler_2: sampler;
+@group(#{MATERIAL_BIND_GROUP}) @binding(7) var texture_3: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(8) var sampler_3: sampler;
+
+// Float uniform by slot, in declaration order
+fn custom_float(slot: u32) -> f32 {
+    return material.floats[slot / 4u][slot % 4u];
+}
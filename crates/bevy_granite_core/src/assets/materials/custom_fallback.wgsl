@@ -0,0 +1,10 @@
+// custom_fallback.wgsl
+// Drawn by a CustomShaderMaterial until its own shader is set, see custom.rs. The first color, unlit
+
+#import bevy_pbr::forward_io::VertexOutput
+#import bevy_granite::custom_material::material
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    return material.colors[0];
+}
@@ -1,7 +1,7 @@
 use bevy::math::Affine2;
 use bevy::prelude::{
-    AlphaMode, AssetServer, Assets, Color, Handle, Image, Reflect, Res, ResMut, Resource,
-    StandardMaterial,
+    AlphaMode, AssetServer, Assets, Color, Entity, Handle, Image, ParallaxMappingMethod, Reflect,
+    Res, ResMut, Resource, StandardMaterial,
 };
 use bevy::render::render_resource::Face;
 use bevy_granite_logging::{
@@ -12,8 +12,9 @@ use ron::ser::to_string_pretty;
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, path::Path};
 
+use super::CustomMaterialDef;
 use crate::shared::rel_asset_to_absolute;
-use crate::{load_texture_with_repeat, material_from_path_into_scene};
+use crate::{load_texture_with_repeat, material_from_path_into_scene, GraniteType, IdentityData};
 
 // For types that require EditableMaterials, use this struct to hold necessary info
 // Path is basically the requestor for brand new entities as the current/last wont exist in a meaningful way
@@ -31,27 +32,255 @@ pub struct RequiredMaterialDataMut<'a> {
     pub path: &'a mut String,
 }
 
-#[derive(Resource, Default, Clone, PartialEq, Debug)]
+/// Every loaded material definition, in load order, with an index by path for lookups
+/// Changes are queued here and sent as `EditableMaterialChangedEvent` so UI lists can update incrementally
+#[derive(Resource, Default, Clone, Debug)]
 pub struct AvailableEditableMaterials {
+    /// Edits made straight to the list skip the change queue, prefer `add_material`, `update_material` and `remove_material`
     pub materials: Option<Vec<EditableMaterial>>,
+    by_path: HashMap<String, usize>,
+    pending_changes: Vec<(String, EditableMaterialChange)>,
     pub image_paths: HashMap<Handle<Image>, String>,
 }
 
+/// Only the materials are compared, the path index and queued changes are bookkeeping
+impl PartialEq for AvailableEditableMaterials {
+    fn eq(&self, other: &Self) -> bool {
+        self.materials == other.materials
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditableMaterialChange {
+    Added,
+    Removed,
+    Modified,
+}
+
 impl AvailableEditableMaterials {
+    /// All materials in load order. "None" and "default" come first
+    pub fn materials(&self) -> Option<&[EditableMaterial]> {
+        self.materials.as_deref()
+    }
+
     pub fn find_material_by_path(&self, path: &str) -> Option<&EditableMaterial> {
-        if let Some(existing_materials) = &self.materials {
-            existing_materials.iter().find(|m| m.path == path)
-        } else {
-            None
+        let index = self.index_of(path)?;
+        self.materials.as_ref()?.get(index)
+    }
+
+    /// Materials listed in `folder` or its subfolders, see [`EditableMaterial::folder`]
+    pub fn materials_in_folder(&self, folder: &str) -> Vec<&EditableMaterial> {
+        self.materials()
+            .unwrap_or_default()
+            .iter()
+            .filter(|material| material.in_folder(folder))
+            .collect()
+    }
+
+    /// `def` with the fields it leaves unset taken from its parent chain. Stops at missing parents and loops
+    pub fn resolve_def(&self, def: &StandardMaterialDef) -> StandardMaterialDef {
+        let mut resolved = def.clone();
+        let mut seen: Vec<&str> = Vec::new();
+        let mut parent = def.parent.as_deref();
+        while let Some(path) = parent {
+            if seen.contains(&path) {
+                break;
+            }
+            seen.push(path);
+            let Some(parent_def) = self
+                .find_material_by_path(path)
+                .and_then(|material| material.def.as_ref())
+            else {
+                break;
+            };
+            resolved = resolved.inherit(parent_def);
+            parent = parent_def.parent.as_deref();
+        }
+        resolved
+    }
+
+    /// Definition of a material with its parents' fields filled in
+    pub fn resolved_def(&self, material: &EditableMaterial) -> Option<StandardMaterialDef> {
+        material.def.as_ref().map(|def| self.resolve_def(def))
+    }
+
+    /// True if `path` has `ancestor` anywhere up its parent chain, or is it
+    pub fn inherits_from(&self, path: &str, ancestor: &str) -> bool {
+        let mut seen: Vec<&str> = Vec::new();
+        let mut current = Some(path);
+        while let Some(path) = current {
+            if path == ancestor {
+                return true;
+            }
+            if seen.contains(&path) {
+                return false;
+            }
+            seen.push(path);
+            current = self
+                .find_material_by_path(path)
+                .and_then(|material| material.def.as_ref())
+                .and_then(|def| def.parent.as_deref());
+        }
+        false
+    }
+
+    /// Paths of every material inheriting from `path`, parents before their children
+    pub fn descendants_of(&self, path: &str) -> Vec<String> {
+        let mut descendants: Vec<String> = Vec::new();
+        let mut index = 0;
+        let mut parent = path.to_string();
+        loop {
+            for material in self.materials().unwrap_or_default() {
+                let is_child = material.def.as_ref().and_then(|def| def.parent.as_deref())
+                    == Some(parent.as_str());
+                if is_child && material.path != path && !descendants.contains(&material.path) {
+                    descendants.push(material.path.clone());
+                }
+            }
+            let Some(next) = descendants.get(index) else {
+                break;
+            };
+            parent = next.clone();
+            index += 1;
+        }
+        descendants
+    }
+
+    /// Entities whose class uses the material at `path`, out of the given entities
+    /// Only lists loaded materials, an unknown path has no users
+    pub fn material_users<'a>(
+        &self,
+        path: &str,
+        entities: impl IntoIterator<Item = (Entity, &'a IdentityData)>,
+    ) -> Vec<Entity> {
+        if path.is_empty() || self.index_of(path).is_none() {
+            return vec![];
         }
+        entities
+            .into_iter()
+            .filter(|(_, identity)| {
+                identity
+                    .class
+                    .get_material_data()
+                    .is_some_and(|material| material.current.path == path)
+            })
+            .map(|(entity, _)| entity)
+            .collect()
     }
+
     pub fn contains_material(&self, material: &EditableMaterial) -> bool {
-        if let Some(materials) = &self.materials {
-            materials.iter().any(|m| m.path == material.path)
-        } else {
-            false
+        self.index_of(&material.path).is_some()
+    }
+
+    /// Append a material. Returns false when one with the same path is already there
+    pub fn add_material(&mut self, material: EditableMaterial) -> bool {
+        if self.index_of(&material.path).is_some() {
+            return false;
+        }
+        let materials = self.materials.get_or_insert_with(Vec::new);
+        self.by_path.insert(material.path.clone(), materials.len());
+        self.pending_changes
+            .push((material.path.clone(), EditableMaterialChange::Added));
+        materials.push(material);
+        true
+    }
+
+    /// Insert a material at a position, moving it there if its path is already loaded
+    pub fn insert_material(&mut self, index: usize, material: EditableMaterial) {
+        let path = material.path.clone();
+        let existed = self.remove_quiet(&path).is_some();
+        let materials = self.materials.get_or_insert_with(Vec::new);
+        materials.insert(index.min(materials.len()), material);
+        self.rebuild_index();
+        self.pending_changes.push((
+            path,
+            if existed {
+                EditableMaterialChange::Modified
+            } else {
+                EditableMaterialChange::Added
+            },
+        ));
+    }
+
+    /// Replace the material with the same path. Returns true if it changed
+    pub fn update_material(&mut self, material: &EditableMaterial) -> bool {
+        let Some(index) = self.index_of(&material.path) else {
+            return false;
+        };
+        let Some(existing) = self.materials.as_mut().and_then(|m| m.get_mut(index)) else {
+            return false;
+        };
+        if *existing == *material {
+            return false;
+        }
+        *existing = material.clone();
+        self.pending_changes
+            .push((material.path.clone(), EditableMaterialChange::Modified));
+        true
+    }
+
+    pub fn remove_material(&mut self, path: &str) -> Option<EditableMaterial> {
+        let removed = self.remove_quiet(path)?;
+        self.pending_changes
+            .push((path.to_string(), EditableMaterialChange::Removed));
+        Some(removed)
+    }
+
+    /// Rewrite material paths, e.g. after their files moved. `rewrite` returns the new path or None to keep it
+    pub fn rewrite_paths(&mut self, mut rewrite: impl FnMut(&str) -> Option<String>) {
+        let Some(materials) = self.materials.as_mut() else {
+            return;
+        };
+        let mut changed = false;
+        for material in materials.iter_mut() {
+            if let Some(path) = rewrite(&material.path) {
+                self.pending_changes
+                    .push((material.path.clone(), EditableMaterialChange::Removed));
+                self.pending_changes
+                    .push((path.clone(), EditableMaterialChange::Added));
+                material.update_path(path);
+                changed = true;
+            }
+        }
+        if changed {
+            self.rebuild_index();
+        }
+    }
+
+    /// Changes since the last call, oldest first
+    pub fn take_changes(&mut self) -> Vec<(String, EditableMaterialChange)> {
+        std::mem::take(&mut self.pending_changes)
+    }
+
+    pub fn has_changes(&self) -> bool {
+        !self.pending_changes.is_empty()
+    }
+
+    /// Position of a material in the list. The list is public, so the index is checked and rebuilt by a scan when stale
+    fn index_of(&self, path: &str) -> Option<usize> {
+        let materials = self.materials.as_ref()?;
+        match self.by_path.get(path) {
+            Some(&index) if materials.get(index).is_some_and(|m| m.path == path) => Some(index),
+            _ => materials.iter().position(|m| m.path == path),
         }
     }
+
+    fn remove_quiet(&mut self, path: &str) -> Option<EditableMaterial> {
+        let index = self.index_of(path)?;
+        let removed = self.materials.as_mut()?.remove(index);
+        self.rebuild_index();
+        Some(removed)
+    }
+
+    fn rebuild_index(&mut self) {
+        self.by_path = self
+            .materials
+            .iter()
+            .flatten()
+            .enumerate()
+            .map(|(index, material)| (material.path.clone(), index))
+            .collect();
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -88,9 +317,16 @@ pub enum EditableMaterialField {
     //NormalMap,
     NormalMapTexture,
     OcclusionMap,
+    DepthMap,
+    ParallaxDepthScale,
+    ParallaxMappingMethod,
+    MaxParallaxLayerCount,
     Thickness,
     AttenuationColor,
     AttenuationDistance,
+    SpecularTransmission,
+    DiffuseTransmission,
+    Ior,
     Clearcoat,
     ClearcoatPerceptualRoughness,
     AnisotropyStrength,
@@ -103,6 +339,18 @@ pub enum EditableMaterialField {
     DepthBias,
     CullMode,
     UvTransform,
+    WindStrength,
+    WindFrequency,
+    WindMask,
+    WindMaskHeight,
+    Triplanar,
+    TriplanarScale,
+    TriplanarSharpness,
+    DetailAlbedoTexture,
+    DetailNormalTexture,
+    DetailTiling,
+    DetailStrength,
+    CustomShader,
 }
 
 impl EditableMaterialField {
@@ -120,9 +368,16 @@ impl EditableMaterialField {
             //NormalMap,
             NormalMapTexture,
             OcclusionMap,
+            DepthMap,
+            ParallaxDepthScale,
+            ParallaxMappingMethod,
+            MaxParallaxLayerCount,
             Thickness,
             AttenuationColor,
             AttenuationDistance,
+            SpecularTransmission,
+            DiffuseTransmission,
+            Ior,
             Clearcoat,
             ClearcoatPerceptualRoughness,
             AnisotropyStrength,
@@ -135,6 +390,18 @@ impl EditableMaterialField {
             DepthBias,
             CullMode,
             UvTransform,
+            WindStrength,
+            WindFrequency,
+            WindMask,
+            WindMaskHeight,
+            Triplanar,
+            TriplanarScale,
+            TriplanarSharpness,
+            DetailAlbedoTexture,
+            DetailNormalTexture,
+            DetailTiling,
+            DetailStrength,
+            CustomShader,
         ]
     }
 }
@@ -211,40 +478,7 @@ impl EditableMaterial {
             let mut removed_fields = Vec::new();
 
             fields.retain(|field| {
-                let keep = match field {
-                    EditableMaterialField::BaseColor => def.base_color.is_some(),
-                    EditableMaterialField::BaseColorTexture => def.base_color_texture.is_some(),
-                    EditableMaterialField::Roughness => def.roughness.is_some(),
-                    EditableMaterialField::Metalness => def.metalness.is_some(),
-                    EditableMaterialField::MetallicRoughnessTexture => def.metallic_roughness_texture.is_some(),
-                    EditableMaterialField::Emissive => def.emissive.is_some(),
-                    EditableMaterialField::EmissiveTexture => def.emissive_texture.is_some(),
-                    EditableMaterialField::EmissiveExposureWeight => {
-                        def.emissive_exposure_weight.is_some()
-                    }
-                    //EditableMaterialField::NormalMap => def.normal_map.is_some(), <- same as normal map texture
-                    EditableMaterialField::NormalMapTexture => def.normal_map_texture.is_some(),
-                    EditableMaterialField::OcclusionMap => def.occlusion_map.is_some(),
-                    EditableMaterialField::Thickness => def.thickness.is_some(),
-                    EditableMaterialField::AttenuationColor => def.attenuation_color.is_some(),
-                    EditableMaterialField::AttenuationDistance => {
-                        def.attenuation_distance.is_some()
-                    }
-                    EditableMaterialField::Clearcoat => def.clearcoat.is_some(),
-                    EditableMaterialField::ClearcoatPerceptualRoughness => {
-                        def.clearcoat_perceptual_roughness.is_some()
-                    }
-                    EditableMaterialField::AnisotropyStrength => def.anisotropy_strength.is_some(),
-                    EditableMaterialField::AnisotropyRotation => def.anisotropy_rotation.is_some(),
-                    EditableMaterialField::AnisotropyChannel => false, // Not implemented
-                    EditableMaterialField::DoubleSided => def.double_sided.is_some(),
-                    EditableMaterialField::Unlit => def.unlit.is_some(),
-                    EditableMaterialField::FogEnabled => def.fog_enabled.is_some(),
-                    EditableMaterialField::AlphaMode => def.alpha_mode.is_some(),
-                    EditableMaterialField::DepthBias => def.depth_bias.is_some(),
-                    EditableMaterialField::CullMode => def.cull_mode.is_some(),
-                    EditableMaterialField::UvTransform => def.uv_transform.is_some(),
-                };
+                let keep = def.has_field(field);
 
                 if !keep {
                     removed_fields.push(format!("{:?}", field));
@@ -254,7 +488,9 @@ impl EditableMaterial {
                         EditableMaterialField::BaseColorTexture => def.base_color_texture = None,
                         EditableMaterialField::Roughness => def.roughness = None,
                         EditableMaterialField::Metalness => def.metalness = None,
-                        EditableMaterialField::MetallicRoughnessTexture => def.metallic_roughness_texture = None,
+                        EditableMaterialField::MetallicRoughnessTexture => {
+                            def.metallic_roughness_texture = None
+                        }
                         EditableMaterialField::Emissive => def.emissive = None,
                         EditableMaterialField::EmissiveTexture => def.emissive_texture = None,
                         EditableMaterialField::EmissiveExposureWeight => {
@@ -263,11 +499,28 @@ impl EditableMaterial {
                         //EditableMaterialField::NormalMap => def.normal_map = None, <- Same as normal map texture
                         EditableMaterialField::NormalMapTexture => def.normal_map_texture = None,
                         EditableMaterialField::OcclusionMap => def.occlusion_map = None,
+                        EditableMaterialField::DepthMap => def.depth_map = None,
+                        EditableMaterialField::ParallaxDepthScale => {
+                            def.parallax_depth_scale = None
+                        }
+                        EditableMaterialField::ParallaxMappingMethod => {
+                            def.parallax_mapping_method = None
+                        }
+                        EditableMaterialField::MaxParallaxLayerCount => {
+                            def.max_parallax_layer_count = None
+                        }
                         EditableMaterialField::Thickness => def.thickness = None,
                         EditableMaterialField::AttenuationColor => def.attenuation_color = None,
                         EditableMaterialField::AttenuationDistance => {
                             def.attenuation_distance = None
                         }
+                        EditableMaterialField::SpecularTransmission => {
+                            def.specular_transmission = None
+                        }
+                        EditableMaterialField::DiffuseTransmission => {
+                            def.diffuse_transmission = None
+                        }
+                        EditableMaterialField::Ior => def.ior = None,
                         EditableMaterialField::Clearcoat => def.clearcoat = None,
                         EditableMaterialField::ClearcoatPerceptualRoughness => {
                             def.clearcoat_perceptual_roughness = None
@@ -282,6 +535,22 @@ impl EditableMaterial {
                         EditableMaterialField::DepthBias => def.depth_bias = None,
                         EditableMaterialField::CullMode => def.cull_mode = None,
                         EditableMaterialField::UvTransform => def.uv_transform = None,
+                        EditableMaterialField::WindStrength => def.wind_strength = None,
+                        EditableMaterialField::WindFrequency => def.wind_frequency = None,
+                        EditableMaterialField::WindMask => def.wind_mask = None,
+                        EditableMaterialField::WindMaskHeight => def.wind_mask_height = None,
+                        EditableMaterialField::Triplanar => def.triplanar = None,
+                        EditableMaterialField::TriplanarScale => def.triplanar_scale = None,
+                        EditableMaterialField::TriplanarSharpness => def.triplanar_sharpness = None,
+                        EditableMaterialField::DetailAlbedoTexture => {
+                            def.detail_albedo_texture = None
+                        }
+                        EditableMaterialField::DetailNormalTexture => {
+                            def.detail_normal_texture = None
+                        }
+                        EditableMaterialField::DetailTiling => def.detail_tiling = None,
+                        EditableMaterialField::DetailStrength => def.detail_strength = None,
+                        EditableMaterialField::CustomShader => def.custom = None,
                     }
                 }
 
@@ -374,6 +643,7 @@ impl EditableMaterial {
         );
     }
 
+    /// Apply `def` to the material asset and save it if needed, then refresh the materials inheriting from it
     pub fn update_material_handle(
         &mut self,
         def: &StandardMaterialDef,
@@ -381,6 +651,30 @@ impl EditableMaterial {
         available_obj_materials: &mut ResMut<AvailableEditableMaterials>,
         asset_server: &Res<AssetServer>,
     ) {
+        self.apply_material_def(def, materials, available_obj_materials, asset_server);
+        for path in available_obj_materials.descendants_of(&self.path) {
+            let Some(mut child) = available_obj_materials
+                .find_material_by_path(&path)
+                .cloned()
+            else {
+                continue;
+            };
+            let Some(child_def) = child.def.clone() else {
+                continue;
+            };
+            child.apply_material_def(&child_def, materials, available_obj_materials, asset_server);
+        }
+    }
+
+    fn apply_material_def(
+        &mut self,
+        own_def: &StandardMaterialDef,
+        materials: &mut Assets<StandardMaterial>,
+        available_obj_materials: &mut ResMut<AvailableEditableMaterials>,
+        asset_server: &Res<AssetServer>,
+    ) {
+        // The asset gets the parents' fields too, the definition and field list keep only its own
+        let def = &available_obj_materials.resolve_def(own_def);
         let fields = self.fields.get_or_insert_with(Vec::new);
         let defaults = StandardMaterial::default();
 
@@ -557,6 +851,60 @@ impl EditableMaterial {
                     existing_material.occlusion_texture = None;
                 }
 
+                // Depth Map, parallax mapping
+                if let Some(path) = &def.depth_map {
+                    if !path.is_empty() {
+                        if !fields.contains(&EditableMaterialField::DepthMap) {
+                            fields.push(EditableMaterialField::DepthMap);
+                        }
+                        let handle = load_texture_with_repeat(asset_server, path.clone(), false);
+
+                        changed = true;
+                        existing_material.depth_map = Some(handle.clone());
+                        available_obj_materials
+                            .image_paths
+                            .insert(handle, path.clone());
+                    }
+                } else {
+                    existing_material.depth_map = None;
+                }
+
+                if let Some(scale) = def.parallax_depth_scale {
+                    if !fields.contains(&EditableMaterialField::ParallaxDepthScale) {
+                        fields.push(EditableMaterialField::ParallaxDepthScale);
+                    }
+
+                    changed = true;
+                    existing_material.parallax_depth_scale = scale;
+                } else {
+                    existing_material.parallax_depth_scale = defaults.parallax_depth_scale;
+                }
+
+                if let Some(method) = def.parallax_mapping_method.as_deref() {
+                    if !fields.contains(&EditableMaterialField::ParallaxMappingMethod) {
+                        fields.push(EditableMaterialField::ParallaxMappingMethod);
+                    }
+
+                    changed = true;
+                    existing_material.parallax_mapping_method = match method {
+                        "Relief" => ParallaxMappingMethod::Relief { max_steps: 5 },
+                        _ => ParallaxMappingMethod::Occlusion,
+                    };
+                } else {
+                    existing_material.parallax_mapping_method = defaults.parallax_mapping_method;
+                }
+
+                if let Some(count) = def.max_parallax_layer_count {
+                    if !fields.contains(&EditableMaterialField::MaxParallaxLayerCount) {
+                        fields.push(EditableMaterialField::MaxParallaxLayerCount);
+                    }
+
+                    changed = true;
+                    existing_material.max_parallax_layer_count = count;
+                } else {
+                    existing_material.max_parallax_layer_count = defaults.max_parallax_layer_count;
+                }
+
                 // Thickness
                 if let Some(thickness) = def.thickness {
                     if !fields.contains(&EditableMaterialField::Thickness) {
@@ -592,6 +940,40 @@ impl EditableMaterial {
                     existing_material.attenuation_distance = defaults.attenuation_distance;
                 }
 
+                // Transmission
+                if let Some(value) = def.specular_transmission {
+                    if !fields.contains(&EditableMaterialField::SpecularTransmission) {
+                        fields.push(EditableMaterialField::SpecularTransmission);
+                    }
+
+                    changed = true;
+                    existing_material.specular_transmission = value;
+                } else {
+                    existing_material.specular_transmission = defaults.specular_transmission;
+                }
+
+                if let Some(value) = def.diffuse_transmission {
+                    if !fields.contains(&EditableMaterialField::DiffuseTransmission) {
+                        fields.push(EditableMaterialField::DiffuseTransmission);
+                    }
+
+                    changed = true;
+                    existing_material.diffuse_transmission = value;
+                } else {
+                    existing_material.diffuse_transmission = defaults.diffuse_transmission;
+                }
+
+                if let Some(value) = def.ior {
+                    if !fields.contains(&EditableMaterialField::Ior) {
+                        fields.push(EditableMaterialField::Ior);
+                    }
+
+                    changed = true;
+                    existing_material.ior = value;
+                } else {
+                    existing_material.ior = defaults.ior;
+                }
+
                 // Clearcoat
                 if let Some(value) = def.clearcoat {
                     if !fields.contains(&EditableMaterialField::Clearcoat) {
@@ -739,10 +1121,62 @@ impl EditableMaterial {
                     existing_material.uv_transform = defaults.uv_transform;
                 }
 
+                // Wind, triplanar and detail maps, applied by swapping users of this material to a GraniteMaterial, see extension.rs
+                // Custom shaders likewise swap them to a CustomShaderMaterial, see custom.rs
+                for (field, set) in [
+                    (
+                        EditableMaterialField::WindStrength,
+                        def.wind_strength.is_some(),
+                    ),
+                    (
+                        EditableMaterialField::WindFrequency,
+                        def.wind_frequency.is_some(),
+                    ),
+                    (EditableMaterialField::WindMask, def.wind_mask.is_some()),
+                    (
+                        EditableMaterialField::WindMaskHeight,
+                        def.wind_mask_height.is_some(),
+                    ),
+                    (EditableMaterialField::Triplanar, def.triplanar.is_some()),
+                    (
+                        EditableMaterialField::TriplanarScale,
+                        def.triplanar_scale.is_some(),
+                    ),
+                    (
+                        EditableMaterialField::TriplanarSharpness,
+                        def.triplanar_sharpness.is_some(),
+                    ),
+                    (
+                        EditableMaterialField::DetailAlbedoTexture,
+                        def.detail_albedo_texture.is_some(),
+                    ),
+                    (
+                        EditableMaterialField::DetailNormalTexture,
+                        def.detail_normal_texture.is_some(),
+                    ),
+                    (
+                        EditableMaterialField::DetailTiling,
+                        def.detail_tiling.is_some(),
+                    ),
+                    (
+                        EditableMaterialField::DetailStrength,
+                        def.detail_strength.is_some(),
+                    ),
+                    (EditableMaterialField::CustomShader, def.custom.is_some()),
+                ] {
+                    if set {
+                        if !fields.contains(&field) {
+                            fields.push(field);
+                        }
+                        changed = true;
+                    }
+                }
+
+                fields.retain(|field| own_def.has_field(field));
                 self.version += 1;
             }
 
-            self.def = Some(def.clone());
+            self.def = Some(own_def.clone());
 
             let pre_clean = self.fields.clone();
             self.clean_fields();
@@ -761,21 +1195,14 @@ impl EditableMaterial {
             }
 
             if changed || self.new_material {
-                if let Some(materials_vec) = &mut available_obj_materials.materials {
-                    if let Some(material_to_update) =
-                        materials_vec.iter_mut().find(|mat| mat.path == self.path)
-                    {
-                        // Only update if the material has actually changed
-                        if *material_to_update != *self {
-                            *material_to_update = self.clone();
-                            log!(
-                                LogType::Editor,
-                                LogLevel::Info,
-                                LogCategory::System,
-                                "Updated 'Available Scene Materials' with new material handle",
-                            );
-                        }
-                    }
+                // Only update if the material has actually changed
+                if available_obj_materials.update_material(self) {
+                    log!(
+                        LogType::Editor,
+                        LogLevel::Info,
+                        LogCategory::System,
+                        "Updated 'Available Scene Materials' with new material handle",
+                    );
                 }
             }
         }
@@ -855,6 +1282,38 @@ impl EditableMaterial {
         self.path = path.clone();
     }
 
+    /// Folder this material is listed under, '/' separated. The category from its definition wins over
+    /// its directory under "materials/"
+    pub fn folder(&self) -> String {
+        if let Some(category) = self
+            .def
+            .as_ref()
+            .and_then(|def| def.category.as_deref())
+            .map(|category| category.trim_matches('/'))
+            .filter(|category| !category.is_empty())
+        {
+            return category.to_string();
+        }
+        if self.path.is_empty() || self.path == "None" {
+            return "internal".to_string();
+        }
+        let path = self.path.strip_prefix("materials/").unwrap_or(&self.path);
+        match path.rfind('/') {
+            Some(last_separator) => path[..last_separator].to_string(),
+            None => String::new(),
+        }
+    }
+
+    /// True if this material is listed in `folder` or one of its subfolders
+    pub fn in_folder(&self, folder: &str) -> bool {
+        let own = self.folder();
+        folder.is_empty()
+            || own == folder
+            || own
+                .strip_prefix(folder)
+                .is_some_and(|rest| rest.starts_with('/'))
+    }
+
     /// Delete this material from disk and remove from available materials list
     pub fn delete_from_disk_and_memory(
         &self,
@@ -913,8 +1372,7 @@ impl EditableMaterial {
         }
 
         // Remove from available materials list (even if file deletion failed)
-        if let Some(materials) = &mut available_materials.materials {
-            materials.retain(|m| m.path != self.path);
+        if available_materials.remove_material(&self.path).is_some() {
             log!(
                 LogType::Editor,
                 LogLevel::Info,
@@ -932,6 +1390,13 @@ impl EditableMaterial {
 pub struct StandardMaterialDef {
     pub friendly_name: String,
 
+    /// Folder the material is listed under, e.g. "Stone/Walls". Falls back to the file's directory
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    /// Material this one inherits every field it doesn't set from, e.g. "materials/stone.mat"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub base_color: Option<(f32, f32, f32, f32)>,
 
@@ -952,6 +1417,22 @@ pub struct StandardMaterialDef {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub occlusion_map: Option<String>,
 
+    /// Height map for parallax mapping, white is deepest. Needs UVs and tangents
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depth_map: Option<String>,
+
+    /// How deep the depth map reaches into the surface, in UV units
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parallax_depth_scale: Option<f32>,
+
+    /// "Occlusion" or "Relief", slower but sharper at steep angles
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parallax_mapping_method: Option<String>,
+
+    /// Depth map samples at grazing angles, more is smoother and slower
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_parallax_layer_count: Option<f32>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub thickness: Option<f32>,
 
@@ -961,6 +1442,18 @@ pub struct StandardMaterialDef {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub attenuation_distance: Option<f32>,
 
+    /// Light passing through the surface refracted, i.e. glass and water. Cameras need screen space transmission
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub specular_transmission: Option<f32>,
+
+    /// Light passing through the surface scattered, i.e. leaves and paper
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diffuse_transmission: Option<f32>,
+
+    /// Index of refraction, 1.5 for glass, 1.33 for water
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ior: Option<f32>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub clearcoat: Option<f32>,
 
@@ -1005,12 +1498,290 @@ pub struct StandardMaterialDef {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub normal_map_texture: Option<String>,
+
+    /// World units the tips sway. Setting it makes the material a GraniteMaterial
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wind_strength: Option<f32>,
+
+    /// Sways per second
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wind_frequency: Option<f32>,
+
+    /// "None", "VertexColor" (red channel) or "Height", what keeps the base of the plant still
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wind_mask: Option<String>,
+
+    /// Mesh height at which a "Height" mask sways fully
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wind_mask_height: Option<f32>,
+
+    /// Projects the base color texture from world position instead of UVs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub triplanar: Option<bool>,
+
+    /// Triplanar texture repeats per world unit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub triplanar_scale: Option<f32>,
+
+    /// How sharply the triplanar projections blend where the surface turns
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub triplanar_sharpness: Option<f32>,
+
+    /// Tiled albedo multiplied over the base color, mid gray leaves it unchanged
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail_albedo_texture: Option<String>,
+
+    /// Tiled normal map added over the base normals. Needs UVs and tangents
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail_normal_texture: Option<String>,
+
+    /// Detail repeats per base texture repeat
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail_tiling: Option<f32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail_strength: Option<f32>,
+
+    /// Draws the material with its own shader, the other fields are not used then
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub custom: Option<CustomMaterialDef>,
+}
+
+impl StandardMaterialDef {
+    /// True if this definition sets the field itself
+    pub fn has_field(&self, field: &EditableMaterialField) -> bool {
+        match field {
+            EditableMaterialField::BaseColor => self.base_color.is_some(),
+            EditableMaterialField::BaseColorTexture => self.base_color_texture.is_some(),
+            EditableMaterialField::Roughness => self.roughness.is_some(),
+            EditableMaterialField::Metalness => self.metalness.is_some(),
+            EditableMaterialField::MetallicRoughnessTexture => {
+                self.metallic_roughness_texture.is_some()
+            }
+            EditableMaterialField::Emissive => self.emissive.is_some(),
+            EditableMaterialField::EmissiveTexture => self.emissive_texture.is_some(),
+            EditableMaterialField::EmissiveExposureWeight => {
+                self.emissive_exposure_weight.is_some()
+            }
+            EditableMaterialField::NormalMapTexture => self.normal_map_texture.is_some(),
+            EditableMaterialField::OcclusionMap => self.occlusion_map.is_some(),
+            EditableMaterialField::DepthMap => self.depth_map.is_some(),
+            EditableMaterialField::ParallaxDepthScale => self.parallax_depth_scale.is_some(),
+            EditableMaterialField::ParallaxMappingMethod => self.parallax_mapping_method.is_some(),
+            EditableMaterialField::MaxParallaxLayerCount => self.max_parallax_layer_count.is_some(),
+            EditableMaterialField::Thickness => self.thickness.is_some(),
+            EditableMaterialField::AttenuationColor => self.attenuation_color.is_some(),
+            EditableMaterialField::AttenuationDistance => self.attenuation_distance.is_some(),
+            EditableMaterialField::SpecularTransmission => self.specular_transmission.is_some(),
+            EditableMaterialField::DiffuseTransmission => self.diffuse_transmission.is_some(),
+            EditableMaterialField::Ior => self.ior.is_some(),
+            EditableMaterialField::Clearcoat => self.clearcoat.is_some(),
+            EditableMaterialField::ClearcoatPerceptualRoughness => {
+                self.clearcoat_perceptual_roughness.is_some()
+            }
+            EditableMaterialField::AnisotropyStrength => self.anisotropy_strength.is_some(),
+            EditableMaterialField::AnisotropyRotation => self.anisotropy_rotation.is_some(),
+            EditableMaterialField::AnisotropyChannel => false, // Not implemented
+            EditableMaterialField::DoubleSided => self.double_sided.is_some(),
+            EditableMaterialField::Unlit => self.unlit.is_some(),
+            EditableMaterialField::FogEnabled => self.fog_enabled.is_some(),
+            EditableMaterialField::AlphaMode => self.alpha_mode.is_some(),
+            EditableMaterialField::DepthBias => self.depth_bias.is_some(),
+            EditableMaterialField::CullMode => self.cull_mode.is_some(),
+            EditableMaterialField::UvTransform => self.uv_transform.is_some(),
+            EditableMaterialField::WindStrength => self.wind_strength.is_some(),
+            EditableMaterialField::WindFrequency => self.wind_frequency.is_some(),
+            EditableMaterialField::WindMask => self.wind_mask.is_some(),
+            EditableMaterialField::WindMaskHeight => self.wind_mask_height.is_some(),
+            EditableMaterialField::Triplanar => self.triplanar.is_some(),
+            EditableMaterialField::TriplanarScale => self.triplanar_scale.is_some(),
+            EditableMaterialField::TriplanarSharpness => self.triplanar_sharpness.is_some(),
+            EditableMaterialField::DetailAlbedoTexture => self.detail_albedo_texture.is_some(),
+            EditableMaterialField::DetailNormalTexture => self.detail_normal_texture.is_some(),
+            EditableMaterialField::DetailTiling => self.detail_tiling.is_some(),
+            EditableMaterialField::DetailStrength => self.detail_strength.is_some(),
+            EditableMaterialField::CustomShader => self.custom.is_some(),
+        }
+    }
+
+    /// Set one field to its value in `from`, which clears it if `from` doesn't set it
+    pub fn copy_field(&mut self, field: &EditableMaterialField, from: &StandardMaterialDef) {
+        match field {
+            EditableMaterialField::BaseColor => self.base_color = from.base_color,
+            EditableMaterialField::BaseColorTexture => {
+                self.base_color_texture = from.base_color_texture.clone()
+            }
+            EditableMaterialField::Roughness => self.roughness = from.roughness,
+            EditableMaterialField::Metalness => self.metalness = from.metalness,
+            EditableMaterialField::MetallicRoughnessTexture => {
+                self.metallic_roughness_texture = from.metallic_roughness_texture.clone()
+            }
+            EditableMaterialField::Emissive => self.emissive = from.emissive,
+            EditableMaterialField::EmissiveTexture => {
+                self.emissive_texture = from.emissive_texture.clone()
+            }
+            EditableMaterialField::EmissiveExposureWeight => {
+                self.emissive_exposure_weight = from.emissive_exposure_weight
+            }
+            EditableMaterialField::NormalMapTexture => {
+                self.normal_map_texture = from.normal_map_texture.clone()
+            }
+            EditableMaterialField::OcclusionMap => self.occlusion_map = from.occlusion_map.clone(),
+            EditableMaterialField::DepthMap => self.depth_map = from.depth_map.clone(),
+            EditableMaterialField::ParallaxDepthScale => {
+                self.parallax_depth_scale = from.parallax_depth_scale
+            }
+            EditableMaterialField::ParallaxMappingMethod => {
+                self.parallax_mapping_method = from.parallax_mapping_method.clone()
+            }
+            EditableMaterialField::MaxParallaxLayerCount => {
+                self.max_parallax_layer_count = from.max_parallax_layer_count
+            }
+            EditableMaterialField::Thickness => self.thickness = from.thickness,
+            EditableMaterialField::AttenuationColor => {
+                self.attenuation_color = from.attenuation_color
+            }
+            EditableMaterialField::AttenuationDistance => {
+                self.attenuation_distance = from.attenuation_distance
+            }
+            EditableMaterialField::SpecularTransmission => {
+                self.specular_transmission = from.specular_transmission
+            }
+            EditableMaterialField::DiffuseTransmission => {
+                self.diffuse_transmission = from.diffuse_transmission
+            }
+            EditableMaterialField::Ior => self.ior = from.ior,
+            EditableMaterialField::Clearcoat => self.clearcoat = from.clearcoat,
+            EditableMaterialField::ClearcoatPerceptualRoughness => {
+                self.clearcoat_perceptual_roughness = from.clearcoat_perceptual_roughness
+            }
+            EditableMaterialField::AnisotropyStrength => {
+                self.anisotropy_strength = from.anisotropy_strength
+            }
+            EditableMaterialField::AnisotropyRotation => {
+                self.anisotropy_rotation = from.anisotropy_rotation
+            }
+            EditableMaterialField::AnisotropyChannel => {} // Not implemented
+            EditableMaterialField::DoubleSided => self.double_sided = from.double_sided,
+            EditableMaterialField::Unlit => self.unlit = from.unlit,
+            EditableMaterialField::FogEnabled => self.fog_enabled = from.fog_enabled,
+            EditableMaterialField::AlphaMode => self.alpha_mode = from.alpha_mode.clone(),
+            EditableMaterialField::DepthBias => self.depth_bias = from.depth_bias,
+            EditableMaterialField::CullMode => self.cull_mode = from.cull_mode.clone(),
+            EditableMaterialField::UvTransform => self.uv_transform = from.uv_transform,
+            EditableMaterialField::WindStrength => self.wind_strength = from.wind_strength,
+            EditableMaterialField::WindFrequency => self.wind_frequency = from.wind_frequency,
+            EditableMaterialField::WindMask => self.wind_mask = from.wind_mask.clone(),
+            EditableMaterialField::WindMaskHeight => self.wind_mask_height = from.wind_mask_height,
+            EditableMaterialField::Triplanar => self.triplanar = from.triplanar,
+            EditableMaterialField::TriplanarScale => self.triplanar_scale = from.triplanar_scale,
+            EditableMaterialField::TriplanarSharpness => {
+                self.triplanar_sharpness = from.triplanar_sharpness
+            }
+            EditableMaterialField::DetailAlbedoTexture => {
+                self.detail_albedo_texture = from.detail_albedo_texture.clone()
+            }
+            EditableMaterialField::DetailNormalTexture => {
+                self.detail_normal_texture = from.detail_normal_texture.clone()
+            }
+            EditableMaterialField::DetailTiling => self.detail_tiling = from.detail_tiling,
+            EditableMaterialField::DetailStrength => self.detail_strength = from.detail_strength,
+            EditableMaterialField::CustomShader => self.custom = from.custom.clone(),
+        }
+    }
+
+    /// This definition with the fields it doesn't set taken from `parent`. Name, category and parent stay its own
+    pub fn inherit(&self, parent: &StandardMaterialDef) -> StandardMaterialDef {
+        StandardMaterialDef {
+            friendly_name: self.friendly_name.clone(),
+            category: self.category.clone(),
+            parent: self.parent.clone(),
+            base_color: self.base_color.or(parent.base_color),
+            base_color_texture: self
+                .base_color_texture
+                .clone()
+                .or_else(|| parent.base_color_texture.clone()),
+            roughness: self.roughness.or(parent.roughness),
+            metalness: self.metalness.or(parent.metalness),
+            metallic_roughness_texture: self
+                .metallic_roughness_texture
+                .clone()
+                .or_else(|| parent.metallic_roughness_texture.clone()),
+            emissive: self.emissive.or(parent.emissive),
+            emissive_texture: self
+                .emissive_texture
+                .clone()
+                .or_else(|| parent.emissive_texture.clone()),
+            emissive_exposure_weight: self
+                .emissive_exposure_weight
+                .or(parent.emissive_exposure_weight),
+            normal_map_texture: self
+                .normal_map_texture
+                .clone()
+                .or_else(|| parent.normal_map_texture.clone()),
+            occlusion_map: self
+                .occlusion_map
+                .clone()
+                .or_else(|| parent.occlusion_map.clone()),
+            depth_map: self.depth_map.clone().or_else(|| parent.depth_map.clone()),
+            parallax_depth_scale: self.parallax_depth_scale.or(parent.parallax_depth_scale),
+            parallax_mapping_method: self
+                .parallax_mapping_method
+                .clone()
+                .or_else(|| parent.parallax_mapping_method.clone()),
+            max_parallax_layer_count: self
+                .max_parallax_layer_count
+                .or(parent.max_parallax_layer_count),
+            thickness: self.thickness.or(parent.thickness),
+            attenuation_color: self.attenuation_color.or(parent.attenuation_color),
+            attenuation_distance: self.attenuation_distance.or(parent.attenuation_distance),
+            specular_transmission: self.specular_transmission.or(parent.specular_transmission),
+            diffuse_transmission: self.diffuse_transmission.or(parent.diffuse_transmission),
+            ior: self.ior.or(parent.ior),
+            clearcoat: self.clearcoat.or(parent.clearcoat),
+            clearcoat_perceptual_roughness: self
+                .clearcoat_perceptual_roughness
+                .or(parent.clearcoat_perceptual_roughness),
+            anisotropy_strength: self.anisotropy_strength.or(parent.anisotropy_strength),
+            anisotropy_rotation: self.anisotropy_rotation.or(parent.anisotropy_rotation),
+            double_sided: self.double_sided.or(parent.double_sided),
+            unlit: self.unlit.or(parent.unlit),
+            fog_enabled: self.fog_enabled.or(parent.fog_enabled),
+            alpha_mode: self
+                .alpha_mode
+                .clone()
+                .or_else(|| parent.alpha_mode.clone()),
+            depth_bias: self.depth_bias.or(parent.depth_bias),
+            cull_mode: self.cull_mode.clone().or_else(|| parent.cull_mode.clone()),
+            uv_transform: self.uv_transform.or(parent.uv_transform),
+            wind_strength: self.wind_strength.or(parent.wind_strength),
+            wind_frequency: self.wind_frequency.or(parent.wind_frequency),
+            wind_mask: self.wind_mask.clone().or_else(|| parent.wind_mask.clone()),
+            wind_mask_height: self.wind_mask_height.or(parent.wind_mask_height),
+            triplanar: self.triplanar.or(parent.triplanar),
+            triplanar_scale: self.triplanar_scale.or(parent.triplanar_scale),
+            triplanar_sharpness: self.triplanar_sharpness.or(parent.triplanar_sharpness),
+            detail_albedo_texture: self
+                .detail_albedo_texture
+                .clone()
+                .or_else(|| parent.detail_albedo_texture.clone()),
+            detail_normal_texture: self
+                .detail_normal_texture
+                .clone()
+                .or_else(|| parent.detail_normal_texture.clone()),
+            detail_tiling: self.detail_tiling.or(parent.detail_tiling),
+            detail_strength: self.detail_strength.or(parent.detail_strength),
+            custom: self.custom.clone().or_else(|| parent.custom.clone()),
+        }
+    }
 }
 
 impl Default for StandardMaterialDef {
     fn default() -> Self {
         Self {
             friendly_name: "None".to_string(),
+            category: None,
+            parent: None,
             base_color: None,
             roughness: None,
             metalness: None,
@@ -1018,9 +1789,16 @@ impl Default for StandardMaterialDef {
             emissive_exposure_weight: None,
             //normal_map: None, <- same as normal map texture
             occlusion_map: None,
+            depth_map: None,
+            parallax_depth_scale: None,
+            parallax_mapping_method: None,
+            max_parallax_layer_count: None,
             thickness: None,
             attenuation_color: None,
             attenuation_distance: None,
+            specular_transmission: None,
+            diffuse_transmission: None,
+            ior: None,
             clearcoat: None,
             clearcoat_perceptual_roughness: None,
             anisotropy_strength: None,
@@ -1036,6 +1814,18 @@ impl Default for StandardMaterialDef {
             metallic_roughness_texture: None,
             emissive_texture: None,
             normal_map_texture: None,
+            wind_strength: None,
+            wind_frequency: None,
+            wind_mask: None,
+            wind_mask_height: None,
+            triplanar: None,
+            triplanar_scale: None,
+            triplanar_sharpness: None,
+            detail_albedo_texture: None,
+            detail_normal_texture: None,
+            detail_tiling: None,
+            detail_strength: None,
+            custom: None,
         }
     }
 }
@@ -0,0 +1,319 @@
+use super::{
+    load_texture_with_repeat, AvailableEditableMaterials, DetailSettings, MaterialOverride,
+    MaterialWind, StandardMaterialDef, TriplanarSettings, WindMask, WindSettings,
+};
+use bevy::{
+    asset::{Asset, AssetEvent, AssetId, AssetServer, Assets, Handle},
+    ecs::{
+        change_detection::DetectChanges,
+        component::Component,
+        entity::Entity,
+        message::MessageReader,
+        query::Without,
+        resource::Resource,
+        system::{Commands, Query, Res, ResMut},
+    },
+    image::Image,
+    math::Vec2,
+    pbr::{ExtendedMaterial, MaterialExtension, MeshMaterial3d, StandardMaterial},
+    prelude::Ref,
+    reflect::Reflect,
+    render::render_resource::{AsBindGroup, ShaderType},
+    shader::ShaderRef,
+    time::Time,
+};
+use std::collections::HashMap;
+
+// extension.rs
+// Material features on top of StandardMaterial, set up from extra fields of a .mat: wind sway, triplanar and detail maps
+// Entities using a .mat with any of them are moved onto a GraniteMaterial built from it, and back once they are all removed
+// Entities with a MaterialOverride keep their own composited material and go without
+
+const WIND_SHADER: &str = "embedded://bevy_granite_core/assets/materials/wind.wgsl";
+const SURFACE_SHADER: &str = "embedded://bevy_granite_core/assets/materials/surface.wgsl";
+
+const FLAG_WIND: u32 = 1;
+const FLAG_TRIPLANAR: u32 = 2;
+const FLAG_DETAIL_ALBEDO: u32 = 4;
+const FLAG_DETAIL_NORMAL: u32 = 8;
+
+/// Extension fields of a .mat. A material with none of them stays a plain StandardMaterial
+#[derive(Reflect, Debug, Clone, PartialEq, Default)]
+pub struct ExtensionSettings {
+    pub wind: Option<WindSettings>,
+    pub triplanar: Option<TriplanarSettings>,
+    pub detail: Option<DetailSettings>,
+}
+
+impl ExtensionSettings {
+    pub fn from_def(def: &StandardMaterialDef) -> Option<Self> {
+        // Custom shaders replace the whole material, see custom.rs
+        if def.custom.is_some() {
+            return None;
+        }
+        let settings = Self {
+            wind: WindSettings::from_def(def),
+            triplanar: TriplanarSettings::from_def(def),
+            detail: DetailSettings::from_def(def),
+        };
+        (settings != Self::default()).then_some(settings)
+    }
+
+    fn write(&self, extension: &mut GraniteExtension, asset_server: &AssetServer) {
+        let params = &mut extension.params;
+        params.flags = 0;
+        if let Some(wind) = &self.wind {
+            params.flags |= FLAG_WIND;
+            params.wind_strength = wind.strength;
+            params.wind_frequency = wind.frequency;
+            params.wind_mask = match wind.mask {
+                WindMask::None => 0,
+                WindMask::VertexColor => 1,
+                WindMask::Height => 2,
+            };
+            params.wind_mask_height = wind.mask_height;
+        }
+        if let Some(triplanar) = &self.triplanar {
+            params.flags |= FLAG_TRIPLANAR;
+            params.triplanar_scale = triplanar.scale;
+            params.triplanar_sharpness = triplanar.sharpness;
+        }
+
+        extension.detail_albedo = None;
+        extension.detail_normal = None;
+        if let Some(detail) = &self.detail {
+            params.detail_tiling = detail.tiling;
+            params.detail_strength = detail.strength;
+            if let Some(path) = &detail.albedo {
+                params.flags |= FLAG_DETAIL_ALBEDO;
+                extension.detail_albedo =
+                    Some(load_texture_with_repeat(asset_server, path.clone(), true));
+            }
+            if let Some(path) = &detail.normal {
+                params.flags |= FLAG_DETAIL_NORMAL;
+                extension.detail_normal =
+                    Some(load_texture_with_repeat(asset_server, path.clone(), false));
+            }
+        }
+    }
+}
+
+/// Layout shared with extension.wgsl
+#[derive(ShaderType, Reflect, Debug, Clone, Default)]
+pub struct GraniteExtensionUniform {
+    pub flags: u32,
+    pub wind_strength: f32,
+    pub wind_frequency: f32,
+    pub wind_mask: u32,
+    pub wind_mask_height: f32,
+    pub wind_direction: Vec2,
+    pub time: f32,
+    pub triplanar_scale: f32,
+    pub triplanar_sharpness: f32,
+    pub detail_tiling: f32,
+    pub detail_strength: f32,
+}
+
+/// Wind, triplanar and detail settings of a .mat drawn on top of StandardMaterial, any of them can be combined
+#[derive(Asset, AsBindGroup, Reflect, Debug, Clone, Default)]
+pub struct GraniteExtension {
+    /// Settings of the .mat, wind direction and time follow MaterialWind and Time
+    #[uniform(100)]
+    pub params: GraniteExtensionUniform,
+    #[texture(101)]
+    #[sampler(102)]
+    pub detail_albedo: Option<Handle<Image>>,
+    #[texture(103)]
+    #[sampler(104)]
+    pub detail_normal: Option<Handle<Image>>,
+}
+
+impl MaterialExtension for GraniteExtension {
+    fn vertex_shader() -> ShaderRef {
+        WIND_SHADER.into()
+    }
+
+    fn fragment_shader() -> ShaderRef {
+        SURFACE_SHADER.into()
+    }
+
+    fn prepass_vertex_shader() -> ShaderRef {
+        WIND_SHADER.into()
+    }
+
+    fn deferred_vertex_shader() -> ShaderRef {
+        WIND_SHADER.into()
+    }
+
+    fn deferred_fragment_shader() -> ShaderRef {
+        SURFACE_SHADER.into()
+    }
+}
+
+pub type GraniteMaterial = ExtendedMaterial<StandardMaterial, GraniteExtension>;
+
+/// Runtime link between an entity on a GraniteMaterial and the shared .mat material it came from
+#[derive(Component, Debug, Clone)]
+pub struct ExtendedMaterialState {
+    pub shared: Handle<StandardMaterial>,
+}
+
+/// One GraniteMaterial per extended .mat, shared by every entity using it
+#[derive(Resource, Default)]
+pub struct GraniteMaterials {
+    by_material: HashMap<AssetId<StandardMaterial>, (ExtensionSettings, Handle<GraniteMaterial>)>,
+}
+
+impl GraniteMaterials {
+    pub fn get(&self, shared: AssetId<StandardMaterial>) -> Option<&Handle<GraniteMaterial>> {
+        self.by_material.get(&shared).map(|(_, handle)| handle)
+    }
+}
+
+type AssignedEntity = (
+    Entity,
+    Ref<'static, MeshMaterial3d<StandardMaterial>>,
+    Option<&'static ExtendedMaterialState>,
+);
+type ExtendedEntity = (
+    Entity,
+    &'static ExtendedMaterialState,
+    &'static MeshMaterial3d<GraniteMaterial>,
+);
+
+/// Keeps GraniteMaterials in step with the .mat extension fields and base material, and swaps entities onto or off them
+#[allow(clippy::too_many_arguments)]
+pub fn apply_extended_materials_system(
+    mut commands: Commands,
+    available: Res<AvailableEditableMaterials>,
+    mut granite_materials: ResMut<GraniteMaterials>,
+    mut materials: ResMut<Assets<GraniteMaterial>>,
+    standard_materials: Res<Assets<StandardMaterial>>,
+    mut material_events: MessageReader<AssetEvent<StandardMaterial>>,
+    assigned: Query<AssignedEntity, Without<MaterialOverride>>,
+    extended: Query<ExtendedEntity>,
+    asset_server: Res<AssetServer>,
+    wind: Res<MaterialWind>,
+    time: Res<Time>,
+) {
+    let mut rebuilt = false;
+    if available.is_changed() {
+        let mut settings = HashMap::new();
+        for material in available.materials().unwrap_or_default() {
+            let (Some(def), Some(handle)) = (available.resolved_def(material), &material.handle)
+            else {
+                continue;
+            };
+            if let Some(extension) = ExtensionSettings::from_def(&def) {
+                settings.insert(handle.id(), extension);
+            }
+        }
+        let before = granite_materials.by_material.len();
+        granite_materials
+            .by_material
+            .retain(|id, _| settings.contains_key(id));
+        rebuilt = before != granite_materials.by_material.len();
+        for (id, extension) in settings {
+            match granite_materials.by_material.get_mut(&id) {
+                Some((existing, handle)) => {
+                    if *existing != extension {
+                        if let Some(material) = materials.get_mut(handle.id()) {
+                            extension.write(&mut material.extension, &asset_server);
+                        }
+                        *existing = extension;
+                    }
+                }
+                None => {
+                    let mut granite_extension = GraniteExtension::default();
+                    extension.write(&mut granite_extension, &asset_server);
+                    let handle = materials.add(GraniteMaterial {
+                        base: standard_materials.get(id).cloned().unwrap_or_default(),
+                        extension: granite_extension,
+                    });
+                    granite_materials
+                        .by_material
+                        .insert(id, (extension, handle));
+                    rebuilt = true;
+                }
+            }
+        }
+    }
+
+    // Base material edits and late loads carry over to the extended copy
+    for event in material_events.read() {
+        let (AssetEvent::Added { id }
+        | AssetEvent::Modified { id }
+        | AssetEvent::LoadedWithDependencies { id }) = event
+        else {
+            continue;
+        };
+        let Some((_, handle)) = granite_materials.by_material.get(id) else {
+            continue;
+        };
+        if let (Some(base), Some(material)) =
+            (standard_materials.get(*id), materials.get_mut(handle.id()))
+        {
+            material.base = base.clone();
+        }
+    }
+
+    // Newly assigned materials, including an extended entity given another material by the editor
+    for (entity, mesh_material, state) in assigned.iter() {
+        if !rebuilt && !mesh_material.is_changed() {
+            continue;
+        }
+        match granite_materials.get(mesh_material.0.id()) {
+            Some(handle) => {
+                commands
+                    .entity(entity)
+                    .remove::<MeshMaterial3d<StandardMaterial>>()
+                    .insert((
+                        MeshMaterial3d(handle.clone()),
+                        ExtendedMaterialState {
+                            shared: mesh_material.0.clone(),
+                        },
+                    ));
+            }
+            None if state.is_some() => {
+                commands
+                    .entity(entity)
+                    .remove::<(MeshMaterial3d<GraniteMaterial>, ExtendedMaterialState)>();
+            }
+            None => {}
+        }
+    }
+
+    if rebuilt {
+        for (entity, state, mesh_material) in extended.iter() {
+            let current = granite_materials.get(state.shared.id());
+            match current {
+                Some(handle) if *handle != mesh_material.0 => {
+                    commands
+                        .entity(entity)
+                        .insert(MeshMaterial3d(handle.clone()));
+                }
+                Some(_) => {}
+                None => {
+                    commands
+                        .entity(entity)
+                        .remove::<(MeshMaterial3d<GraniteMaterial>, ExtendedMaterialState)>()
+                        .insert(MeshMaterial3d(state.shared.clone()));
+                }
+            }
+        }
+    }
+
+    // Only swaying materials change every frame
+    let elapsed = time.elapsed_secs_wrapped();
+    let direction = wind.direction.try_normalize().unwrap_or(Vec2::X);
+    for (extension, handle) in granite_materials.by_material.values() {
+        let Some(wind_settings) = &extension.wind else {
+            continue;
+        };
+        if let Some(material) = materials.get_mut(handle.id()) {
+            material.extension.params.time = elapsed;
+            material.extension.params.wind_direction = direction;
+            material.extension.params.wind_strength = wind_settings.strength * wind.scale;
+        }
+    }
+}
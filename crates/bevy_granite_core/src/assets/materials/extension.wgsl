@@ -0,0 +1,30 @@
+// extension.wgsl
+// Bindings of GraniteExtension, see extension.rs. Imported by wind.wgsl and surface.wgsl
+
+#define_import_path bevy_granite::material_extension
+
+const FLAG_WIND: u32 = 1u;
+const FLAG_TRIPLANAR: u32 = 2u;
+const FLAG_DETAIL_ALBEDO: u32 = 4u;
+const FLAG_DETAIL_NORMAL: u32 = 8u;
+
+struct GraniteExtension {
+    flags: u32,
+    wind_strength: f32,
+    wind_frequency: f32,
+    // 0 none, 1 vertex color red, 2 height
+    wind_mask: u32,
+    wind_mask_height: f32,
+    wind_direction: vec2<f32>,
+    time: f32,
+    triplanar_scale: f32,
+    triplanar_sharpness: f32,
+    detail_tiling: f32,
+    detail_strength: f32,
+}
+
+@group(#{MATERIAL_BIND_GROUP}) @binding(100) var<uniform> extension: GraniteExtension;
+@group(#{MATERIAL_BIND_GROUP}) @binding(101) var detail_albedo_texture: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(102) var detail_albedo_sampler: sampler;
+@group(#{MATERIAL_BIND_GROUP}) @binding(103) var detail_normal_texture: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(104) var detail_normal_sampler: sampler;
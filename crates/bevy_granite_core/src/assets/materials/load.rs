@@ -3,11 +3,13 @@ use super::{
     StandardMaterialDef,
 };
 use bevy::image::{
-    ImageAddressMode, ImageFilterMode, ImageFormat, ImageFormatSetting, ImageLoaderSettings, ImageSampler, ImageSamplerDescriptor
+    ImageAddressMode, ImageFilterMode, ImageFormat, ImageFormatSetting, ImageLoaderSettings,
+    ImageSampler, ImageSamplerDescriptor,
 };
 use bevy::math::Affine2;
 use bevy::prelude::{
-    AlphaMode, AssetServer, Assets, Color, Handle, Image, Res, ResMut, StandardMaterial,
+    AlphaMode, AssetServer, Assets, Color, Handle, Image, ParallaxMappingMethod, Res, ResMut,
+    StandardMaterial,
 };
 use bevy::render::render_resource::{Face, TextureFormat};
 use bevy_granite_logging::{
@@ -18,17 +20,21 @@ use bevy_granite_logging::{
 // This was brutal to figure out and I CANNOT believe the is a .load_with_settings() method...
 /// Helper function to load textures with REPEAT address mode
 /// `is_srgb` should be true for color textures (base_color, emissive), false for data textures (normal, metallic, roughness, etc.)
-pub fn load_texture_with_repeat(asset_server: &AssetServer, path: String, is_srgb: bool) -> Handle<Image> {
+pub fn load_texture_with_repeat(
+    asset_server: &AssetServer,
+    path: String,
+    is_srgb: bool,
+) -> Handle<Image> {
     let path_clone = path.clone();
     asset_server.load_with_settings(path, move |settings: &mut ImageLoaderSettings| {
         settings.is_srgb = is_srgb;
 
         if let Some(ext) = path_clone.rsplit('.').next() {
             settings.format = ImageFormatSetting::Format(
-                ImageFormat::from_extension(ext).unwrap_or(ImageFormat::Png)
+                ImageFormat::from_extension(ext).unwrap_or(ImageFormat::Png),
             );
         }
-        
+
         settings.sampler = ImageSampler::Descriptor(ImageSamplerDescriptor {
             address_mode_u: ImageAddressMode::Repeat,
             address_mode_v: ImageAddressMode::Repeat,
@@ -43,11 +49,28 @@ pub fn load_texture_with_repeat(asset_server: &AssetServer, path: String, is_srg
 }
 
 /// Creates a EditableMaterial from a definition(wrapper) file and adds it to the asset system
+/// Its parent material is loaded first so the asset gets the inherited fields
 pub fn material_from_path_into_scene(
     path: &str,
     materials: &mut ResMut<Assets<StandardMaterial>>,
     available_materials: &mut ResMut<AvailableEditableMaterials>,
     asset_server: &Res<AssetServer>,
+) -> Option<EditableMaterial> {
+    load_material_file(
+        path,
+        materials,
+        available_materials,
+        asset_server,
+        &mut Vec::new(),
+    )
+}
+
+fn load_material_file(
+    path: &str,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+    available_materials: &mut ResMut<AvailableEditableMaterials>,
+    asset_server: &Res<AssetServer>,
+    loading: &mut Vec<String>,
 ) -> Option<EditableMaterial> {
     if let Some(existing) = available_materials.find_material_by_path(path) {
         //log!(
@@ -91,6 +114,42 @@ pub fn material_from_path_into_scene(
         }
     };
 
+    loading.push(path.to_string());
+    if let Some(parent) = mat_def.parent.as_deref() {
+        if loading.iter().any(|loading_path| loading_path == parent) {
+            log!(
+                LogType::Editor,
+                LogLevel::Warning,
+                LogCategory::Asset,
+                "Material {} inherits from itself through {}, ignoring the loop",
+                path,
+                parent
+            );
+        } else if load_material_file(
+            parent,
+            materials,
+            available_materials,
+            asset_server,
+            loading,
+        )
+        .is_none()
+        {
+            log!(
+                LogType::Editor,
+                LogLevel::Warning,
+                LogCategory::Asset,
+                "Parent material {} of {} could not be loaded",
+                parent,
+                path
+            );
+        }
+    }
+    loading.pop();
+
+    // The asset is built from the inherited fields too, the definition keeps only its own
+    let own_def = mat_def;
+    let mat_def = available_materials.resolve_def(&own_def);
+
     let mut found_fields: Vec<EditableMaterialField> = vec![];
     let mut mat = StandardMaterial::default();
 
@@ -178,6 +237,33 @@ pub fn material_from_path_into_scene(
         }
     }
 
+    // Depth Map, parallax mapping
+    if let Some(texture_path) = &mat_def.depth_map {
+        if !texture_path.is_empty() {
+            let handle = load_texture_with_repeat(asset_server, texture_path.clone(), false); // Linear for depth data
+            mat.depth_map = Some(handle.clone());
+            available_materials
+                .image_paths
+                .insert(handle, texture_path.clone());
+            found_fields.push(EditableMaterialField::DepthMap);
+        }
+    }
+    if let Some(scale) = mat_def.parallax_depth_scale {
+        mat.parallax_depth_scale = scale;
+        found_fields.push(EditableMaterialField::ParallaxDepthScale);
+    }
+    if let Some(method) = &mat_def.parallax_mapping_method {
+        mat.parallax_mapping_method = match method.as_str() {
+            "Relief" => ParallaxMappingMethod::Relief { max_steps: 5 },
+            _ => ParallaxMappingMethod::Occlusion,
+        };
+        found_fields.push(EditableMaterialField::ParallaxMappingMethod);
+    }
+    if let Some(count) = mat_def.max_parallax_layer_count {
+        mat.max_parallax_layer_count = count;
+        found_fields.push(EditableMaterialField::MaxParallaxLayerCount);
+    }
+
     // Thickness
     if let Some(thickness) = mat_def.thickness {
         mat.thickness = thickness;
@@ -194,6 +280,20 @@ pub fn material_from_path_into_scene(
         found_fields.push(EditableMaterialField::AttenuationDistance);
     }
 
+    // Transmission
+    if let Some(transmission) = mat_def.specular_transmission {
+        mat.specular_transmission = transmission;
+        found_fields.push(EditableMaterialField::SpecularTransmission);
+    }
+    if let Some(transmission) = mat_def.diffuse_transmission {
+        mat.diffuse_transmission = transmission;
+        found_fields.push(EditableMaterialField::DiffuseTransmission);
+    }
+    if let Some(ior) = mat_def.ior {
+        mat.ior = ior;
+        found_fields.push(EditableMaterialField::Ior);
+    }
+
     // Clearcoat
     if let Some(clearcoat) = mat_def.clearcoat {
         mat.clearcoat = clearcoat;
@@ -270,6 +370,61 @@ pub fn material_from_path_into_scene(
         found_fields.push(EditableMaterialField::UvTransform);
     }
 
+    // Wind, triplanar and detail maps, see extension.rs, and custom shaders, see custom.rs
+    for (field, set) in [
+        (
+            EditableMaterialField::WindStrength,
+            mat_def.wind_strength.is_some(),
+        ),
+        (
+            EditableMaterialField::WindFrequency,
+            mat_def.wind_frequency.is_some(),
+        ),
+        (EditableMaterialField::WindMask, mat_def.wind_mask.is_some()),
+        (
+            EditableMaterialField::WindMaskHeight,
+            mat_def.wind_mask_height.is_some(),
+        ),
+        (
+            EditableMaterialField::Triplanar,
+            mat_def.triplanar.is_some(),
+        ),
+        (
+            EditableMaterialField::TriplanarScale,
+            mat_def.triplanar_scale.is_some(),
+        ),
+        (
+            EditableMaterialField::TriplanarSharpness,
+            mat_def.triplanar_sharpness.is_some(),
+        ),
+        (
+            EditableMaterialField::DetailAlbedoTexture,
+            mat_def.detail_albedo_texture.is_some(),
+        ),
+        (
+            EditableMaterialField::DetailNormalTexture,
+            mat_def.detail_normal_texture.is_some(),
+        ),
+        (
+            EditableMaterialField::DetailTiling,
+            mat_def.detail_tiling.is_some(),
+        ),
+        (
+            EditableMaterialField::DetailStrength,
+            mat_def.detail_strength.is_some(),
+        ),
+        (
+            EditableMaterialField::CustomShader,
+            mat_def.custom.is_some(),
+        ),
+    ] {
+        if set {
+            found_fields.push(field);
+        }
+    }
+
+    found_fields.retain(|field| own_def.has_field(field));
+
     // Create the material handle
     let handle = materials.add(mat);
 
@@ -277,9 +432,9 @@ pub fn material_from_path_into_scene(
     let obj_material = EditableMaterial {
         path: path.to_string(),
         handle: Some(handle),
-        def: Some(mat_def.clone()),
+        def: Some(own_def.clone()),
         fields: Some(found_fields),
-        friendly_name: mat_def.friendly_name.clone(),
+        friendly_name: own_def.friendly_name.clone(),
         version: 0,
         new_material: false,
         error: EditableMaterialError::None,
@@ -287,13 +442,7 @@ pub fn material_from_path_into_scene(
     };
 
     // Add to available materials
-    if let Some(existing) = &mut available_materials.materials {
-        if !existing.contains(&obj_material) {
-            existing.push(obj_material.clone());
-        }
-    } else {
-        available_materials.materials = Some(vec![obj_material.clone()]);
-    }
+    available_materials.add_material(obj_material.clone());
 
     log!(
         LogType::Editor,
@@ -1,10 +1,20 @@
 use bevy::prelude::Reflect;
 use serde::{Deserialize, Serialize};
+pub mod custom;
 pub mod definition;
+pub mod extension;
 pub mod load;
+pub mod overrides;
+pub mod surface;
+pub mod wind;
 
+pub use custom::*;
 pub use definition::*;
+pub use extension::*;
 pub use load::*;
+pub use overrides::*;
+pub use surface::*;
+pub use wind::*;
 
 // Store the material path, the current material, and the last material
 #[derive(Serialize, Deserialize, Reflect, Debug, Clone, PartialEq)]
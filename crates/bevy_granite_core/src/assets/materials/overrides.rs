@@ -0,0 +1,192 @@
+use bevy::{
+    asset::{AssetEvent, Assets, Handle},
+    ecs::{
+        change_detection::DetectChanges,
+        component::Component,
+        entity::Entity,
+        lifecycle::RemovedComponents,
+        message::MessageReader,
+        query::Without,
+        system::{Commands, Query, ResMut},
+    },
+    math::{Affine2, Vec2},
+    pbr::{MeshMaterial3d, StandardMaterial},
+    prelude::{
+        Color, Ref, ReflectComponent, ReflectDefault, ReflectDeserialize, ReflectFromReflect,
+        ReflectSerialize,
+    },
+    reflect::Reflect,
+};
+use serde::{Deserialize, Serialize};
+
+// overrides.rs
+// Per entity tweaks on top of a shared .mat, i.e. color variants of one crate material
+// The entity gets its own copy of the shared material with the overrides applied, rebuilt whenever the shared one changes
+// The .mat file and every other entity using it are left untouched
+
+/// Material fields this entity overrides. Unset fields come from the shared material
+#[derive(Reflect, Serialize, Deserialize, Debug, Clone, Component, Default, PartialEq)]
+#[reflect(Component, Serialize, Deserialize, Default, FromReflect)]
+pub struct MaterialOverride {
+    /// Multiplied with the shared base color, so textures keep their detail
+    pub tint: Option<Color>,
+    pub base_color: Option<Color>,
+    pub roughness: Option<f32>,
+    pub metalness: Option<f32>,
+    pub emissive: Option<Color>,
+    /// Applied on top of the shared material's UV transform
+    pub uv_scale: Option<Vec2>,
+    pub uv_offset: Option<Vec2>,
+}
+
+impl MaterialOverride {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The shared material with these overrides applied
+    pub fn composite(&self, shared: &StandardMaterial) -> StandardMaterial {
+        let mut material = shared.clone();
+        if let Some(base_color) = self.base_color {
+            material.base_color = base_color;
+        }
+        if let Some(tint) = self.tint {
+            let tint = tint.to_linear();
+            let base = material.base_color.to_linear();
+            material.base_color = Color::linear_rgba(
+                base.red * tint.red,
+                base.green * tint.green,
+                base.blue * tint.blue,
+                base.alpha * tint.alpha,
+            );
+        }
+        if let Some(roughness) = self.roughness {
+            material.perceptual_roughness = roughness.clamp(0.0, 1.0);
+        }
+        if let Some(metalness) = self.metalness {
+            material.metallic = metalness.clamp(0.0, 1.0);
+        }
+        if let Some(emissive) = self.emissive {
+            material.emissive = emissive.to_linear();
+        }
+        if self.uv_scale.is_some() || self.uv_offset.is_some() {
+            let uv = Affine2::from_scale_angle_translation(
+                self.uv_scale.unwrap_or(Vec2::ONE),
+                0.0,
+                self.uv_offset.unwrap_or(Vec2::ZERO),
+            );
+            material.uv_transform = uv * material.uv_transform;
+        }
+        material
+    }
+}
+
+/// Runtime link between an overridden entity, its shared material and its own composited copy
+#[derive(Component, Debug, Clone)]
+pub struct MaterialOverrideState {
+    pub shared: Handle<StandardMaterial>,
+    pub composited: Option<Handle<StandardMaterial>>,
+}
+
+type OverriddenEntity = (
+    Entity,
+    Ref<'static, MaterialOverride>,
+    &'static mut MeshMaterial3d<StandardMaterial>,
+    Option<&'static MaterialOverrideState>,
+);
+
+/// Builds the composited material of overridden entities, and rebuilds it when the override or shared material changes
+/// Assigning another material to the entity makes that the new shared material
+pub fn apply_material_overrides_system(
+    mut commands: Commands,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut material_events: MessageReader<AssetEvent<StandardMaterial>>,
+    mut overridden: Query<OverriddenEntity>,
+    mut removed: RemovedComponents<MaterialOverride>,
+    mut restore: Query<
+        (
+            &mut MeshMaterial3d<StandardMaterial>,
+            &MaterialOverrideState,
+        ),
+        Without<MaterialOverride>,
+    >,
+) {
+    // Put the shared material back once the override is gone
+    for entity in removed.read() {
+        if let Ok((mut mesh_material, state)) = restore.get_mut(entity) {
+            if state.composited.as_ref() == Some(&mesh_material.0) {
+                mesh_material.0 = state.shared.clone();
+            }
+            commands.entity(entity).remove::<MaterialOverrideState>();
+        }
+    }
+
+    let shared_changed: Vec<_> = material_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Added { id }
+            | AssetEvent::Modified { id }
+            | AssetEvent::LoadedWithDependencies { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+
+    for (entity, material_override, mut mesh_material, state) in overridden.iter_mut() {
+        let shared_modified =
+            state.is_some_and(|state| shared_changed.contains(&state.shared.id()));
+        if !material_override.is_changed() && !mesh_material.is_changed() && !shared_modified {
+            continue;
+        }
+
+        // Our own copy being assigned is not a new shared material
+        let shared = match state {
+            Some(state) if state.composited.as_ref() == Some(&mesh_material.0) => {
+                state.shared.clone()
+            }
+            _ => mesh_material.0.clone(),
+        };
+        let reusable = state
+            .and_then(|state| state.composited.clone())
+            .filter(|composited| *composited != shared);
+
+        if material_override.is_empty() {
+            if mesh_material.0 != shared {
+                mesh_material.0 = shared.clone();
+            }
+            commands.entity(entity).insert(MaterialOverrideState {
+                shared,
+                composited: None,
+            });
+            continue;
+        }
+
+        // Not loaded yet, the load event brings us back here
+        let Some(composited_material) = materials
+            .get(&shared)
+            .map(|shared_material| material_override.composite(shared_material))
+        else {
+            commands.entity(entity).insert(MaterialOverrideState {
+                shared,
+                composited: reusable,
+            });
+            continue;
+        };
+
+        let composited = match reusable {
+            Some(handle) => {
+                if let Some(existing) = materials.get_mut(&handle) {
+                    *existing = composited_material;
+                }
+                handle
+            }
+            None => materials.add(composited_material),
+        };
+        if mesh_material.0 != composited {
+            mesh_material.0 = composited.clone();
+        }
+        commands.entity(entity).insert(MaterialOverrideState {
+            shared,
+            composited: Some(composited),
+        });
+    }
+}
@@ -0,0 +1,82 @@
+use super::StandardMaterialDef;
+use bevy::reflect::Reflect;
+
+// surface.rs
+// Triplanar projection and detail maps, set up from the fields of a .mat
+// Drawn by GraniteMaterial with surface.wgsl, see extension.rs
+
+/// Base color projected from world position instead of UVs, so blockout and terrain meshes need no unwrap
+#[derive(Reflect, Debug, Clone, Copy, PartialEq)]
+pub struct TriplanarSettings {
+    /// Texture repeats per world unit
+    pub scale: f32,
+    /// How sharply the three projections blend where the surface turns
+    pub sharpness: f32,
+}
+
+impl Default for TriplanarSettings {
+    fn default() -> Self {
+        Self {
+            scale: 0.5,
+            sharpness: 4.0,
+        }
+    }
+}
+
+impl TriplanarSettings {
+    /// None unless the material turns triplanar on
+    pub fn from_def(def: &StandardMaterialDef) -> Option<Self> {
+        if def.triplanar != Some(true) {
+            return None;
+        }
+        let defaults = Self::default();
+        Some(Self {
+            scale: def.triplanar_scale.unwrap_or(defaults.scale),
+            sharpness: def.triplanar_sharpness.unwrap_or(defaults.sharpness),
+        })
+    }
+}
+
+/// Tiled albedo and normal maps over the base textures, for close up detail on large surfaces
+#[derive(Reflect, Debug, Clone, PartialEq)]
+pub struct DetailSettings {
+    /// Albedo centered on mid gray, relative to assets
+    pub albedo: Option<String>,
+    /// Tangent space normal map, relative to assets
+    pub normal: Option<String>,
+    /// Detail repeats per base texture repeat, or per triplanar repeat
+    pub tiling: f32,
+    pub strength: f32,
+}
+
+impl Default for DetailSettings {
+    fn default() -> Self {
+        Self {
+            albedo: None,
+            normal: None,
+            tiling: 8.0,
+            strength: 1.0,
+        }
+    }
+}
+
+impl DetailSettings {
+    /// None when the material has no detail texture
+    pub fn from_def(def: &StandardMaterialDef) -> Option<Self> {
+        let texture = |path: &Option<String>| path.clone().filter(|path| !path.is_empty());
+        let (albedo, normal) = (
+            texture(&def.detail_albedo_texture),
+            texture(&def.detail_normal_texture),
+        );
+        if albedo.is_none() && normal.is_none() {
+            return None;
+        }
+        let defaults = Self::default();
+        Some(Self {
+            albedo,
+            normal,
+            tiling: def.detail_tiling.unwrap_or(defaults.tiling),
+            strength: def.detail_strength.unwrap_or(defaults.strength),
+        })
+    }
+}
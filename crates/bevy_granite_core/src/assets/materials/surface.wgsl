@@ -0,0 +1,117 @@
+// surface.wgsl
+// Fragment of GraniteMaterial, see surface.rs. Standard PBR with triplanar base color and detail maps on top
+
+#import bevy_pbr::{
+    pbr_bindings,
+    pbr_fragment::pbr_input_from_standard_material,
+    pbr_functions::{alpha_discard, calculate_tbn_mikktspace},
+    pbr_types::{STANDARD_MATERIAL_FLAGS_BASE_COLOR_TEXTURE_BIT, STANDARD_MATERIAL_FLAGS_UNLIT_BIT},
+}
+#import bevy_granite::material_extension::{
+    extension,
+    detail_albedo_texture,
+    detail_albedo_sampler,
+    detail_normal_texture,
+    detail_normal_sampler,
+    FLAG_TRIPLANAR,
+    FLAG_DETAIL_ALBEDO,
+    FLAG_DETAIL_NORMAL,
+}
+
+#ifdef PREPASS_PIPELINE
+#import bevy_pbr::{
+    prepass_io::{VertexOutput, FragmentOutput},
+    pbr_deferred_functions::deferred_output,
+}
+#else
+#import bevy_pbr::{
+    forward_io::{VertexOutput, FragmentOutput},
+    pbr_functions::{apply_pbr_lighting, main_pass_post_lighting_processing},
+}
+#endif
+
+// Samples along the three world axes, blended by how much the surface faces each one
+fn triplanar(
+    texture: texture_2d<f32>,
+    texture_sampler: sampler,
+    position: vec3<f32>,
+    normal: vec3<f32>,
+    scale: f32,
+) -> vec4<f32> {
+    var weights = pow(abs(normal), vec3<f32>(max(extension.triplanar_sharpness, 1.0)));
+    weights /= max(weights.x + weights.y + weights.z, 0.0001);
+    let p = position * scale;
+    return textureSample(texture, texture_sampler, p.zy) * weights.x
+        + textureSample(texture, texture_sampler, p.xz) * weights.y
+        + textureSample(texture, texture_sampler, p.xy) * weights.z;
+}
+
+@fragment
+fn fragment(in: VertexOutput, @builtin(front_facing) is_front: bool) -> FragmentOutput {
+    var pbr_input = pbr_input_from_standard_material(in, is_front);
+    let world_position = in.world_position.xyz;
+    let world_normal = normalize(pbr_input.world_normal);
+
+    // Base color from world position instead of UVs, for blockout and terrain
+    if (extension.flags & FLAG_TRIPLANAR) != 0u
+        && (pbr_bindings::material.flags & STANDARD_MATERIAL_FLAGS_BASE_COLOR_TEXTURE_BIT) != 0u {
+        var base_color = pbr_bindings::material.base_color;
+#ifdef VERTEX_COLORS
+        base_color *= in.color;
+#endif
+        pbr_input.material.base_color = base_color * triplanar(
+            pbr_bindings::base_color_texture,
+            pbr_bindings::base_color_sampler,
+            world_position,
+            world_normal,
+            extension.triplanar_scale,
+        );
+    }
+
+    // Detail albedo is centered on mid gray, lighter brightens and darker darkens
+    if (extension.flags & FLAG_DETAIL_ALBEDO) != 0u {
+        var detail = vec3<f32>(0.5);
+        if (extension.flags & FLAG_TRIPLANAR) != 0u {
+            detail = triplanar(
+                detail_albedo_texture,
+                detail_albedo_sampler,
+                world_position,
+                world_normal,
+                extension.triplanar_scale * extension.detail_tiling,
+            ).rgb;
+        } else {
+#ifdef VERTEX_UVS_A
+            detail = textureSample(detail_albedo_texture, detail_albedo_sampler, in.uv * extension.detail_tiling).rgb;
+#endif
+        }
+        let tint = mix(vec3<f32>(1.0), detail * 2.0, extension.detail_strength);
+        pbr_input.material.base_color = vec4<f32>(pbr_input.material.base_color.rgb * tint, pbr_input.material.base_color.a);
+    }
+
+    // Detail normals need UVs and tangents, they are skipped on triplanar surfaces
+#ifdef VERTEX_UVS_A
+#ifdef VERTEX_TANGENTS
+    if (extension.flags & FLAG_DETAIL_NORMAL) != 0u && (extension.flags & FLAG_TRIPLANAR) == 0u {
+        let tbn = calculate_tbn_mikktspace(in.world_normal, in.world_tangent);
+        let sampled = textureSample(detail_normal_texture, detail_normal_sampler, in.uv * extension.detail_tiling).rg;
+        let detail = (sampled * 2.0 - 1.0) * extension.detail_strength;
+        pbr_input.N = normalize(pbr_input.N + tbn[0] * detail.x + tbn[1] * detail.y);
+    }
+#endif
+#endif
+
+    pbr_input.material.base_color = alpha_discard(pbr_input.material, pbr_input.material.base_color);
+
+#ifdef PREPASS_PIPELINE
+    let out = deferred_output(in, pbr_input);
+#else
+    var out: FragmentOutput;
+    if (pbr_input.material.flags & STANDARD_MATERIAL_FLAGS_UNLIT_BIT) == 0u {
+        out.color = apply_pbr_lighting(pbr_input);
+    } else {
+        out.color = pbr_input.material.base_color;
+    }
+    out.color = main_pass_post_lighting_processing(pbr_input, out.color);
+#endif
+    return out;
+}
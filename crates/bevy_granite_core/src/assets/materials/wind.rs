@@ -0,0 +1,96 @@
+use super::StandardMaterialDef;
+use bevy::{ecs::resource::Resource, math::Vec2, reflect::Reflect};
+
+// wind.rs
+// Vertex sway for foliage, set up from the wind fields of a .mat
+// Drawn by GraniteMaterial with wind.wgsl, see extension.rs
+
+/// What keeps the base of a plant still
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WindMask {
+    /// The whole mesh sways
+    None,
+    /// Red vertex color, 0 still and 1 full sway
+    VertexColor,
+    /// Sways more the higher the vertex, from the mesh origin up to the mask height
+    #[default]
+    Height,
+}
+
+impl WindMask {
+    pub fn name(&self) -> &'static str {
+        match self {
+            WindMask::None => "None",
+            WindMask::VertexColor => "VertexColor",
+            WindMask::Height => "Height",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "None" => Some(WindMask::None),
+            "VertexColor" => Some(WindMask::VertexColor),
+            "Height" => Some(WindMask::Height),
+            _ => None,
+        }
+    }
+}
+
+/// Wind fields of a .mat, with defaults for the unset ones
+#[derive(Reflect, Debug, Clone, Copy, PartialEq)]
+pub struct WindSettings {
+    pub strength: f32,
+    pub frequency: f32,
+    pub mask: WindMask,
+    pub mask_height: f32,
+}
+
+impl Default for WindSettings {
+    fn default() -> Self {
+        Self {
+            strength: 0.1,
+            frequency: 0.5,
+            mask: WindMask::Height,
+            mask_height: 1.0,
+        }
+    }
+}
+
+impl WindSettings {
+    /// None when the material doesn't sway
+    pub fn from_def(def: &StandardMaterialDef) -> Option<Self> {
+        let strength = def.wind_strength?;
+        if strength <= 0.0 {
+            return None;
+        }
+        let defaults = Self::default();
+        Some(Self {
+            strength,
+            frequency: def.wind_frequency.unwrap_or(defaults.frequency),
+            mask: def
+                .wind_mask
+                .as_deref()
+                .and_then(WindMask::from_name)
+                .unwrap_or(defaults.mask),
+            mask_height: def.wind_mask_height.unwrap_or(defaults.mask_height),
+        })
+    }
+}
+
+/// World wide wind. Turn or calm it from game code, every swaying material follows
+#[derive(Resource, Reflect, Debug, Clone)]
+pub struct MaterialWind {
+    /// Horizontal direction on the XZ plane, X then Z
+    pub direction: Vec2,
+    /// Multiplies the strength of every material
+    pub scale: f32,
+}
+
+impl Default for MaterialWind {
+    fn default() -> Self {
+        Self {
+            direction: Vec2::X,
+            scale: 1.0,
+        }
+    }
+}
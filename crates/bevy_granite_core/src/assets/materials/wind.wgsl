@@ -0,0 +1,109 @@
+// wind.wgsl
+// Vertex sway of GraniteMaterial, see wind.rs. Shared by the main pass, prepass and shadows
+// Static meshes only, skinned and morphed meshes are drawn unswayed
+
+#import bevy_pbr::{
+    mesh_functions,
+    view_transformations::position_world_to_clip,
+}
+#import bevy_granite::material_extension::{extension, FLAG_WIND}
+
+#ifdef PREPASS_PIPELINE
+#import bevy_pbr::prepass_io::{Vertex, VertexOutput}
+#else
+#import bevy_pbr::forward_io::{Vertex, VertexOutput}
+#endif
+
+const TAU: f32 = 6.28318530718;
+
+fn mask_weight(vertex: Vertex) -> f32 {
+    if extension.wind_mask == 1u {
+#ifdef VERTEX_COLORS
+        return vertex.color.r;
+#else
+        return 1.0;
+#endif
+    }
+    if extension.wind_mask == 2u {
+        let height = clamp(vertex.position.y / max(extension.wind_mask_height, 0.0001), 0.0, 1.0);
+        return height * height;
+    }
+    return 1.0;
+}
+
+// World space offset, phased by position so neighbouring plants don't sway in step
+fn wind_offset(vertex: Vertex, world_position: vec3<f32>) -> vec3<f32> {
+    if (extension.flags & FLAG_WIND) == 0u {
+        return vec3<f32>(0.0);
+    }
+    let direction = vec3<f32>(extension.wind_direction.x, 0.0, extension.wind_direction.y);
+    let across = vec3<f32>(-extension.wind_direction.y, 0.0, extension.wind_direction.x);
+    let phase = dot(world_position.xz, vec2<f32>(0.37, 0.23));
+    let t = extension.time * extension.wind_frequency * TAU + phase;
+    let sway = sin(t) * 0.7 + sin(t * 2.3 + 1.7) * 0.3;
+    let flutter = sin(t * 3.1 + world_position.y) * 0.25;
+    return (direction * (sway + 0.5) + across * flutter) * extension.wind_strength * mask_weight(vertex);
+}
+
+@vertex
+fn vertex(vertex: Vertex) -> VertexOutput {
+    var out: VertexOutput;
+
+    let world_from_local = mesh_functions::get_world_from_local(vertex.instance_index);
+    let unswayed = mesh_functions::mesh_position_local_to_world(world_from_local, vec4<f32>(vertex.position, 1.0));
+    let offset = wind_offset(vertex, world_from_local[3].xyz);
+    out.world_position = vec4<f32>(unswayed.xyz + offset, unswayed.w);
+    out.position = position_world_to_clip(out.world_position.xyz);
+
+#ifdef UNCLIPPED_DEPTH_ORTHO_EMULATION
+    out.unclipped_depth = out.position.z;
+    out.position.z = min(out.position.z, 1.0);
+#endif
+
+#ifdef VERTEX_UVS_A
+    out.uv = vertex.uv;
+#endif
+#ifdef VERTEX_UVS_B
+    out.uv_b = vertex.uv_b;
+#endif
+
+#ifdef PREPASS_PIPELINE
+#ifdef NORMAL_PREPASS_OR_DEFERRED_PREPASS
+#ifdef VERTEX_NORMALS
+    out.world_normal = mesh_functions::mesh_normal_local_to_world(vertex.normal, vertex.instance_index);
+#endif
+#ifdef VERTEX_TANGENTS
+    out.world_tangent = mesh_functions::mesh_tangent_local_to_world(world_from_local, vertex.tangent, vertex.instance_index);
+#endif
+#endif
+#else
+#ifdef VERTEX_NORMALS
+    out.world_normal = mesh_functions::mesh_normal_local_to_world(vertex.normal, vertex.instance_index);
+#endif
+#ifdef VERTEX_TANGENTS
+    out.world_tangent = mesh_functions::mesh_tangent_local_to_world(world_from_local, vertex.tangent, vertex.instance_index);
+#endif
+#endif
+
+#ifdef VERTEX_COLORS
+    out.color = vertex.color;
+#endif
+
+#ifdef MOTION_VECTOR_PREPASS
+    // Sway is left out of motion vectors, only the mesh's own movement counts
+    let prev_world_from_local = mesh_functions::get_previous_world_from_local(vertex.instance_index);
+    let previous = mesh_functions::mesh_position_local_to_world(prev_world_from_local, vec4<f32>(vertex.position, 1.0));
+    out.previous_world_position = vec4<f32>(previous.xyz + offset, previous.w);
+#endif
+
+#ifdef VERTEX_OUTPUT_INSTANCE_INDEX
+    out.instance_index = vertex.instance_index;
+#endif
+
+#ifdef VISIBILITY_RANGE_DITHER
+    out.visibility_range_dither = mesh_functions::get_visibility_range_dither_level(
+        vertex.instance_index, world_from_local[3]);
+#endif
+
+    return out;
+}
@@ -0,0 +1,217 @@
+use crate::{
+    entities::{mesh_to_obj, GraniteTypes, IdentityData, OBJ},
+    events::RequestRecalculateMeshNormals,
+    shared::{rel_asset_to_absolute, EditorCapabilities},
+};
+use bevy::{
+    asset::Assets,
+    mesh::{Indices, Mesh, Mesh3d, PrimitiveTopology, VertexAttributeValues},
+    prelude::{MessageReader, Query, Res, ResMut},
+};
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+use std::fs;
+
+// mesh_stats.rs
+// Counts and sanity checks of a mesh, shown for the selected entity
+// The fix recalculates normals and tangents in memory, and writes the corrected mesh back to its .obj
+
+/// Triangles with less area than this are degenerate
+const DEGENERATE_AREA: f32 = 1e-12;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MeshStats {
+    pub vertices: usize,
+    pub triangles: usize,
+    pub triangle_list: bool,
+    pub indexed: bool,
+    pub has_normals: bool,
+    pub has_tangents: bool,
+    pub has_uv0: bool,
+    pub has_uv1: bool,
+    pub has_colors: bool,
+    /// Triangles with a repeated vertex or no area
+    pub degenerate_triangles: usize,
+    /// Normals that are zero length or not a number
+    pub invalid_normals: usize,
+}
+
+impl MeshStats {
+    pub fn from_mesh(mesh: &Mesh) -> Self {
+        let positions = match mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
+            Some(VertexAttributeValues::Float32x3(positions)) => positions.as_slice(),
+            _ => &[],
+        };
+        let indices: Vec<usize> = match mesh.indices() {
+            Some(Indices::U16(indices)) => indices.iter().map(|index| *index as usize).collect(),
+            Some(Indices::U32(indices)) => indices.iter().map(|index| *index as usize).collect(),
+            None => (0..positions.len()).collect(),
+        };
+        let triangle_list = mesh.primitive_topology() == PrimitiveTopology::TriangleList;
+
+        let mut stats = Self {
+            vertices: mesh.count_vertices(),
+            triangles: if triangle_list { indices.len() / 3 } else { 0 },
+            triangle_list,
+            indexed: mesh.indices().is_some(),
+            has_normals: mesh.attribute(Mesh::ATTRIBUTE_NORMAL).is_some(),
+            has_tangents: mesh.attribute(Mesh::ATTRIBUTE_TANGENT).is_some(),
+            has_uv0: mesh.attribute(Mesh::ATTRIBUTE_UV_0).is_some(),
+            has_uv1: mesh.attribute(Mesh::ATTRIBUTE_UV_1).is_some(),
+            has_colors: mesh.attribute(Mesh::ATTRIBUTE_COLOR).is_some(),
+            ..Default::default()
+        };
+
+        if triangle_list {
+            stats.degenerate_triangles = indices
+                .chunks_exact(3)
+                .filter(|triangle| {
+                    let (a, b, c) = (triangle[0], triangle[1], triangle[2]);
+                    if a == b || b == c || a == c {
+                        return true;
+                    }
+                    let (Some(a), Some(b), Some(c)) =
+                        (positions.get(a), positions.get(b), positions.get(c))
+                    else {
+                        return true;
+                    };
+                    let ab = [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+                    let ac = [c[0] - a[0], c[1] - a[1], c[2] - a[2]];
+                    let cross = [
+                        ab[1] * ac[2] - ab[2] * ac[1],
+                        ab[2] * ac[0] - ab[0] * ac[2],
+                        ab[0] * ac[1] - ab[1] * ac[0],
+                    ];
+                    cross[0] * cross[0] + cross[1] * cross[1] + cross[2] * cross[2]
+                        <= DEGENERATE_AREA
+                })
+                .count();
+        }
+
+        if let Some(VertexAttributeValues::Float32x3(normals)) =
+            mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
+        {
+            stats.invalid_normals = normals
+                .iter()
+                .filter(|normal| {
+                    let length =
+                        normal[0] * normal[0] + normal[1] * normal[1] + normal[2] * normal[2];
+                    !length.is_finite() || length <= f32::EPSILON
+                })
+                .count();
+        }
+        stats
+    }
+
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if !self.triangle_list {
+            warnings.push("Not a triangle list, stats are partial".to_string());
+        }
+        if !self.has_normals {
+            warnings.push("No normals, lighting will be wrong".to_string());
+        } else if self.invalid_normals > 0 {
+            warnings.push(format!(
+                "{} zero length or invalid normals",
+                self.invalid_normals
+            ));
+        }
+        if self.degenerate_triangles > 0 {
+            warnings.push(format!(
+                "{} degenerate triangles",
+                self.degenerate_triangles
+            ));
+        }
+        if !self.has_uv0 {
+            warnings.push("No UVs, textures can't be mapped".to_string());
+        } else if !self.has_tangents {
+            warnings.push("No tangents, normal maps won't work".to_string());
+        }
+        warnings
+    }
+
+    /// Normals and tangents can only be rebuilt for triangle lists
+    pub fn can_recalculate(&self) -> bool {
+        self.triangle_list && self.vertices > 0
+    }
+}
+
+/// Rebuilds the normals, smooth for indexed meshes and flat otherwise, then the tangents when the mesh has UVs
+pub fn recalculate_normals_and_tangents(mesh: &mut Mesh) -> Result<(), String> {
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+        return Err("only triangle lists are supported".to_string());
+    }
+    mesh.try_compute_normals().map_err(|e| e.to_string())?;
+    if mesh.attribute(Mesh::ATTRIBUTE_UV_0).is_some() {
+        mesh.generate_tangents().map_err(|e| e.to_string())?;
+    } else {
+        mesh.remove_attribute(Mesh::ATTRIBUTE_TANGENT);
+    }
+    Ok(())
+}
+
+/// Fixes the normals and tangents of an entity's mesh. Every entity sharing the mesh gets the fix
+/// Meshes loaded from an .obj have the corrected mesh written back to the file
+pub fn recalculate_mesh_normals_system(
+    mut requests: MessageReader<RequestRecalculateMeshNormals>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    capabilities: Res<EditorCapabilities>,
+    entities: Query<(&Mesh3d, Option<&IdentityData>)>,
+) {
+    for RequestRecalculateMeshNormals(entity) in requests.read() {
+        let Ok((mesh_handle, identity)) = entities.get(*entity) else {
+            continue;
+        };
+        let Some(mesh) = meshes.get_mut(&mesh_handle.0) else {
+            continue;
+        };
+        if let Err(e) = recalculate_normals_and_tangents(mesh) {
+            log!(
+                LogType::Editor,
+                LogLevel::Error,
+                LogCategory::Asset,
+                "Failed to recalculate normals: {}",
+                e
+            );
+            continue;
+        }
+
+        let Some(GraniteTypes::OBJ(obj)) = identity.map(|identity| &identity.class) else {
+            log!(
+                LogType::Editor,
+                LogLevel::OK,
+                LogCategory::Asset,
+                "Recalculated normals and tangents"
+            );
+            continue;
+        };
+        if obj.mesh_path.is_empty() || !capabilities.can_save(&obj.mesh_path) {
+            continue;
+        }
+        let path = rel_asset_to_absolute(&obj.mesh_path);
+        let material = OBJ::extract_first_usemtl_name(path.as_ref()).ok();
+        let name = identity.map_or("mesh", |identity| identity.name.as_str());
+        let Some(contents) = mesh_to_obj(mesh, name, material.as_deref()) else {
+            continue;
+        };
+        match fs::write(path.as_ref(), contents) {
+            Ok(()) => log!(
+                LogType::Editor,
+                LogLevel::OK,
+                LogCategory::Asset,
+                "Recalculated normals and tangents, written to: {}",
+                obj.mesh_path
+            ),
+            Err(e) => log!(
+                LogType::Editor,
+                LogLevel::Error,
+                LogCategory::Asset,
+                "Failed to write mesh '{}': {}",
+                path,
+                e
+            ),
+        }
+    }
+}
@@ -1,10 +1,26 @@
+pub mod leaks;
 pub mod materials;
+pub mod mesh_stats;
 pub mod plugin;
+pub mod references;
 
+pub use leaks::{asset_leak_system, AssetLeakReport, AssetLeakScanner, LeakedAsset};
 pub use materials::{
-    get_material_from_path, load_texture_with_repeat, material_from_path_into_scene,
-    materials_from_folder_into_scene, AvailableEditableMaterials, EditableMaterial,
-    EditableMaterialError, EditableMaterialField, MaterialData,
-    NewEditableMaterial, RequiredMaterialData, RequiredMaterialDataMut, StandardMaterialDef,
+    apply_custom_materials_system, apply_extended_materials_system,
+    apply_material_overrides_system, get_material_from_path, load_texture_with_repeat,
+    material_from_path_into_scene, materials_from_folder_into_scene, AvailableEditableMaterials,
+    CustomMaterialDef, CustomMaterialState, CustomMaterials, CustomShaderMaterial, CustomUniform,
+    CustomUniformValue, DetailSettings, EditableMaterial, EditableMaterialChange,
+    EditableMaterialError, EditableMaterialField, ExtendedMaterialState, ExtensionSettings,
+    GraniteExtension, GraniteMaterial, GraniteMaterials, MaterialData, MaterialOverride,
+    MaterialOverrideState, MaterialWind, NewEditableMaterial, RequiredMaterialData,
+    RequiredMaterialDataMut, StandardMaterialDef, TriplanarSettings, WindMask, WindSettings,
+};
+pub use mesh_stats::{
+    recalculate_mesh_normals_system, recalculate_normals_and_tangents, MeshStats,
 };
 pub use plugin::AssetPlugin;
+pub use references::{
+    asset_move_system, collect_reference_files, find_asset_references, material_replace_system,
+    move_asset_with_references,
+};
@@ -1,10 +1,21 @@
-use super::AvailableEditableMaterials;
-use crate::EditableMaterial;
+use super::{
+    apply_custom_materials_system, apply_extended_materials_system,
+    apply_material_overrides_system, asset_leak_system, asset_move_system, material_replace_system,
+    recalculate_mesh_normals_system, AssetLeakReport, AvailableEditableMaterials, CustomMaterials,
+    CustomShaderMaterial, GraniteMaterial, GraniteMaterials, MaterialOverride, MaterialWind,
+};
+use crate::{events::EditableMaterialChangedEvent, BridgeTag, EditableMaterial};
 use bevy::{
-    app::{App, Plugin, PreStartup},
-    asset::{AssetServer, Assets, Handle},
-    ecs::system::{Res, ResMut},
-    pbr::StandardMaterial,
+    app::{App, Plugin, PreStartup, Update},
+    asset::{embedded_asset, AssetServer, Assets, Handle},
+    ecs::{
+        change_detection::DetectChangesMut,
+        message::MessageWriter,
+        schedule::IntoScheduleConfigs,
+        system::{Res, ResMut},
+    },
+    pbr::{MaterialPlugin, StandardMaterial},
+    shader::load_shader_library,
 };
 
 fn preload_fallback_material(
@@ -31,25 +42,67 @@ fn preload_fallback_material(
         "",
     );
 
-    if let Some(ref mut materials) = available_materials.materials {
-        materials.insert(0, none_material);
-        materials.insert(1, white_editable);
-    } else {
-        available_materials.materials = Some(vec![none_material, white_editable]);
+    available_materials.insert_material(0, none_material);
+    available_materials.insert_material(1, white_editable);
+}
+
+/// Sends the queued material list changes. Draining the queue doesn't count as a change of the list
+fn material_changed_events_system(
+    mut available_materials: ResMut<AvailableEditableMaterials>,
+    mut changed_writer: MessageWriter<EditableMaterialChangedEvent>,
+) {
+    if !available_materials.has_changes() {
+        return;
+    }
+    for (path, change) in available_materials.bypass_change_detection().take_changes() {
+        changed_writer.write(EditableMaterialChangedEvent { path, change });
     }
 }
 
 pub struct AssetPlugin;
 impl Plugin for AssetPlugin {
     fn build(&self, app: &mut App) {
+        load_shader_library!(app, "materials/extension.wgsl");
+        embedded_asset!(app, "materials/wind.wgsl");
+        embedded_asset!(app, "materials/surface.wgsl");
+        load_shader_library!(app, "materials/custom.wgsl");
+        embedded_asset!(app, "materials/custom_fallback.wgsl");
         app
+            //
+            // Plugins
+            //
+            .add_plugins(MaterialPlugin::<GraniteMaterial>::default())
+            .add_plugins(MaterialPlugin::<CustomShaderMaterial>::default())
             //
             // Resources
             //
             .insert_resource(AvailableEditableMaterials::default())
+            .init_resource::<AssetLeakReport>()
+            .init_resource::<MaterialWind>()
+            .init_resource::<GraniteMaterials>()
+            .init_resource::<CustomMaterials>()
+            //
+            // Register types
+            //
+            .register_type::<MaterialOverride>()
+            .register_type_data::<MaterialOverride, BridgeTag>()
+            .register_type::<MaterialWind>()
             //
             // Schedule system
             //
-            .add_systems(PreStartup, preload_fallback_material);
+            .add_systems(PreStartup, preload_fallback_material)
+            .add_systems(
+                Update,
+                (
+                    asset_move_system,
+                    material_replace_system,
+                    asset_leak_system,
+                    recalculate_mesh_normals_system,
+                    material_changed_events_system,
+                    apply_material_overrides_system,
+                    apply_extended_materials_system.after(apply_material_overrides_system),
+                    apply_custom_materials_system.after(apply_material_overrides_system),
+                ),
+            );
     }
 }
@@ -0,0 +1,318 @@
+use crate::{
+    entities::{GraniteType, GraniteTypes, IdentityData, SpawnSource},
+    events::{
+        AssetMovedEvent, MaterialReplacedEvent, RequestAssetMoveEvent, RequestReplaceMaterialEvent,
+    },
+    material_from_path_into_scene,
+    shared::{
+        absolute_asset_to_rel, is_scene_file, read_scene_file_compressed, rel_asset_to_absolute,
+        write_scene_contents, EditorCapabilities,
+    },
+    world::{DirtyScenes, SaveLock},
+    AvailableEditableMaterials,
+};
+use bevy::{
+    asset::{AssetServer, Assets},
+    pbr::{MeshMaterial3d, StandardMaterial},
+    prelude::{Commands, Entity, MessageReader, MessageWriter, Query, Res, ResMut},
+};
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+use std::{fs, path::Path};
+
+/// File extensions that can hold asset references and get rewritten on a move, besides scenes
+const REFERENCE_FILE_EXTENSIONS: [&str; 1] = ["mat"];
+
+/// Relative paths of every scene and material file inside the assets folder
+pub fn collect_reference_files() -> Vec<String> {
+    let mut files = vec![];
+    collect_reference_files_recursive(Path::new(rel_asset_to_absolute("").as_ref()), &mut files);
+    files.sort();
+    files
+}
+
+fn collect_reference_files_recursive(dir: &Path, files: &mut Vec<String>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_reference_files_recursive(&path, files);
+        } else if is_scene_file(&path.to_string_lossy())
+            || path
+                .extension()
+                .map(|ext| REFERENCE_FILE_EXTENSIONS.contains(&ext.to_string_lossy().as_ref()))
+                .unwrap_or(false)
+        {
+            files.push(absolute_asset_to_rel(path.to_string_lossy().to_string()).to_string());
+        }
+    }
+}
+
+/// Preview of a move. Lists the scene and material files that reference the asset (or any asset inside the folder)
+pub fn find_asset_references(from: &str) -> Vec<String> {
+    let from = normalize(from);
+    let is_dir = Path::new(rel_asset_to_absolute(&from).as_ref()).is_dir();
+
+    collect_reference_files()
+        .into_iter()
+        .filter(|file| {
+            read_scene_file_compressed(rel_asset_to_absolute(file).as_ref())
+                .map(|(contents, _)| rewrite_references(&contents, &from, "", is_dir).is_some())
+                .unwrap_or(false)
+        })
+        .collect()
+}
+
+/// Move or rename a file or folder inside the assets folder, then rewrite every scene and material that referenced it
+/// Returns the files that were rewritten
+pub fn move_asset_with_references(from: &str, to: &str) -> std::io::Result<Vec<String>> {
+    let from = normalize(from);
+    let to = normalize(to);
+    let abs_from = rel_asset_to_absolute(&from).to_string();
+    let abs_to = rel_asset_to_absolute(&to).to_string();
+
+    if !Path::new(&abs_from).exists() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("'{}' does not exist", from),
+        ));
+    }
+    if Path::new(&abs_to).exists() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::AlreadyExists,
+            format!("'{}' already exists", to),
+        ));
+    }
+
+    let is_dir = Path::new(&abs_from).is_dir();
+    let affected = find_asset_references(&from);
+
+    if let Some(parent) = Path::new(&abs_to).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::rename(&abs_from, &abs_to)?;
+
+    // Reference files may have moved along with a folder
+    let mut rewritten = vec![];
+    for file in affected {
+        let file = rewrite_path(&file, &from, &to, is_dir).unwrap_or(file);
+        let abs_file = rel_asset_to_absolute(&file).to_string();
+        // Compressed scenes are written back with the compression they had
+        let (contents, compression) = read_scene_file_compressed(&abs_file)?;
+        if let Some(contents) = rewrite_references(&contents, &from, &to, is_dir) {
+            write_scene_contents(&abs_file, &contents, compression)?;
+            rewritten.push(file);
+        }
+    }
+
+    Ok(rewritten)
+}
+
+/// Handles move requests, and keeps already spawned entities and loaded materials pointing at the new paths
+pub fn asset_move_system(
+    mut move_reader: MessageReader<RequestAssetMoveEvent>,
+    mut moved_writer: MessageWriter<AssetMovedEvent>,
+    mut available_materials: ResMut<AvailableEditableMaterials>,
+    mut identity_query: Query<&mut IdentityData>,
+    save_lock: Res<SaveLock>,
+    capabilities: Res<EditorCapabilities>,
+) {
+    for RequestAssetMoveEvent(from, to) in move_reader.read() {
+        if let Some(reason) = &save_lock.reason {
+            log!(
+                LogType::Editor,
+                LogLevel::Warning,
+                LogCategory::Asset,
+                "Refused to move '{}': {}",
+                from,
+                reason
+            );
+            continue;
+        }
+        if !capabilities.can_use_files(from) {
+            continue;
+        }
+
+        let from = normalize(from);
+        let to = normalize(to);
+        let is_dir = Path::new(rel_asset_to_absolute(&from).as_ref()).is_dir();
+
+        let rewritten = match move_asset_with_references(&from, &to) {
+            Ok(rewritten) => rewritten,
+            Err(e) => {
+                log!(
+                    LogType::Editor,
+                    LogLevel::Error,
+                    LogCategory::Asset,
+                    "Failed to move '{}' to '{}': {}",
+                    from,
+                    to,
+                    e
+                );
+                continue;
+            }
+        };
+
+        available_materials.rewrite_paths(|path| rewrite_path(path, &from, &to, is_dir));
+
+        for mut identity in identity_query.iter_mut() {
+            if let Some(class) = rewrite_class(&identity.class, &from, &to, is_dir) {
+                identity.class = class;
+            }
+        }
+
+        log!(
+            LogType::Editor,
+            LogLevel::OK,
+            LogCategory::Asset,
+            "Moved '{}' to '{}'. Updated references in {} files",
+            from,
+            to,
+            rewritten.len()
+        );
+
+        moved_writer.write(AssetMovedEvent {
+            from,
+            to,
+            rewritten,
+        });
+    }
+}
+
+/// Swaps every loaded entity using one material to another, in one go
+/// Only the entities' material paths change, so their scenes are marked dirty and the material files are left alone
+pub fn material_replace_system(
+    mut replace_reader: MessageReader<RequestReplaceMaterialEvent>,
+    mut replaced_writer: MessageWriter<MaterialReplacedEvent>,
+    mut available_materials: ResMut<AvailableEditableMaterials>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut dirty_scenes: ResMut<DirtyScenes>,
+    mut identity_query: Query<(Entity, &mut IdentityData, &SpawnSource)>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    capabilities: Res<EditorCapabilities>,
+) {
+    for RequestReplaceMaterialEvent { from, to } in replace_reader.read() {
+        if from == to || !capabilities.can_edit_components(&format!("entities using '{}'", from)) {
+            continue;
+        }
+        let Some(material) = material_from_path_into_scene(
+            to,
+            &mut materials,
+            &mut available_materials,
+            &asset_server,
+        ) else {
+            log!(
+                LogType::Editor,
+                LogLevel::Error,
+                LogCategory::Asset,
+                "Could not replace '{}': material '{}' did not load",
+                from,
+                to
+            );
+            continue;
+        };
+
+        let mut entities = vec![];
+        for (entity, mut identity, source) in identity_query.iter_mut() {
+            let Some(material_data) = identity.class.get_mut_material_data() else {
+                continue;
+            };
+            if material_data.current.path != *from {
+                continue;
+            }
+            *material_data.last = material_data.current.clone();
+            *material_data.current = material.clone();
+            *material_data.path = material.path.clone();
+            if let Some(handle) = material.handle.clone() {
+                commands.entity(entity).insert(MeshMaterial3d(handle));
+            }
+            dirty_scenes.mark(source.str_ref());
+            entities.push(entity);
+        }
+
+        log!(
+            LogType::Editor,
+            LogLevel::OK,
+            LogCategory::Asset,
+            "Replaced material '{}' with '{}' on {} entities",
+            from,
+            to,
+            entities.len()
+        );
+        replaced_writer.write(MaterialReplacedEvent {
+            from: from.clone(),
+            to: to.clone(),
+            entities,
+        });
+    }
+}
+
+/// Round trip the class through RON so every path field is rewritten, not only the ones we know about
+/// Runtime material data is skipped by serde, so it is carried over by hand
+fn rewrite_class(class: &GraniteTypes, from: &str, to: &str, is_dir: bool) -> Option<GraniteTypes> {
+    let class_ron = ron::ser::to_string(class).ok()?;
+    let class_ron = rewrite_references(&class_ron, from, to, is_dir)?;
+    let mut new_class = ron::de::from_str::<GraniteTypes>(&class_ron).ok()?;
+
+    if let (Some(old), Some(new)) = (class.get_material_data(), new_class.get_mut_material_data()) {
+        *new.current = old.current.clone();
+        *new.last = old.last.clone();
+        if let Some(path) = rewrite_path(&new.current.path, from, to, is_dir) {
+            new.current.update_path(path);
+        }
+        if let Some(path) = rewrite_path(&new.last.path, from, to, is_dir) {
+            new.last.update_path(path);
+        }
+    }
+
+    Some(new_class)
+}
+
+fn normalize(path: &str) -> String {
+    path.replace('\\', "/").trim_matches('/').to_string()
+}
+
+/// New path for a single path, if the move touches it
+fn rewrite_path(path: &str, from: &str, to: &str, is_dir: bool) -> Option<String> {
+    if path == from {
+        Some(to.to_string())
+    } else if is_dir {
+        path.strip_prefix(&format!("{}/", from))
+            .map(|rest| format!("{}/{}", to, rest))
+    } else {
+        None
+    }
+}
+
+/// Rewrite every quoted reference to the moved asset inside RON text
+/// Component data is stored as nested RON strings, so escaped quotes are matched as well
+/// Returns None when nothing referenced it
+fn rewrite_references(text: &str, from: &str, to: &str, is_dir: bool) -> Option<String> {
+    let patterns: Vec<(String, String)> = if is_dir {
+        vec![
+            (format!("\"{}/", from), format!("\"{}/", to)),
+            (format!("\\\"{}/", from), format!("\\\"{}/", to)),
+        ]
+    } else {
+        vec![
+            (format!("\"{}\"", from), format!("\"{}\"", to)),
+            (format!("\\\"{}\\\"", from), format!("\\\"{}\\\"", to)),
+        ]
+    };
+
+    if !patterns.iter().any(|(pattern, _)| text.contains(pattern)) {
+        return None;
+    }
+
+    let mut text = text.to_string();
+    for (pattern, replacement) in patterns {
+        text = text.replace(&pattern, &replacement);
+    }
+    Some(text)
+}
@@ -4,7 +4,12 @@ use bevy::{
 };
 use bevy_granite_logging::{log, LogCategory, LogLevel, LogType};
 use serde::de::DeserializeSeed;
-use std::{any::Any, borrow::Cow, collections::HashMap};
+use std::{
+    any::Any,
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    time::{Duration, Instant},
+};
 
 // All structs defined by #[granite_component]
 // get this tag so we can easily filter in UI
@@ -127,9 +132,10 @@ impl ComponentEditor {
                                     reflected.reflect_kind(),
                                     registration.type_info()
                                 );
-                                
+
                                 // Try to get more details about what field is causing the issue
-                                if let bevy::reflect::ReflectKind::Struct = reflected.reflect_kind() {
+                                if let bevy::reflect::ReflectKind::Struct = reflected.reflect_kind()
+                                {
                                     if let Ok(struct_ref) = reflected.reflect_ref().as_struct() {
                                         log!(
                                             LogType::Editor,
@@ -140,7 +146,8 @@ impl ComponentEditor {
                                         );
                                         for i in 0..struct_ref.field_len() {
                                             if let Some(field) = struct_ref.field_at(i) {
-                                                let field_name = struct_ref.name_at(i).unwrap_or("unknown");
+                                                let field_name =
+                                                    struct_ref.name_at(i).unwrap_or("unknown");
                                                 if field.reflect_clone().is_err() {
                                                     log!(
                                                         LogType::Editor,
@@ -204,6 +211,15 @@ impl ComponentEditor {
         world: &World,
         entity: Entity,
     ) -> HashMap<String, String> {
+        self.serialize_entity_components_timed(world, entity).0
+    }
+
+    /// Same as serialize_entity_components, along with how long each component took, for save profiles
+    pub fn serialize_entity_components_timed(
+        &self,
+        world: &World,
+        entity: Entity,
+    ) -> (HashMap<String, String>, Vec<(String, Duration)>) {
         //log!(
         //    LogType::Game,
         //    LogLevel::Info,
@@ -211,6 +227,7 @@ impl ComponentEditor {
         //    "Serialize entity components called"
         //);
         let mut serialized_components = HashMap::new();
+        let mut timings = vec![];
         let type_registry = self.type_registry.read();
 
         let entity_ref = world.entity(entity);
@@ -229,12 +246,14 @@ impl ComponentEditor {
 
                     if let Some(reflect_component) = registration.data::<ReflectComponent>() {
                         if let Some(reflected_value) = reflect_component.reflect(entity_ref) {
+                            let started = Instant::now();
                             let serializer = bevy::reflect::serde::ReflectSerializer::new(
                                 reflected_value,
                                 &type_registry,
                             );
                             if let Ok(serialized) = ron::to_string(&serializer) {
                                 serialized_components.insert(type_name.to_string(), serialized);
+                                timings.push((type_name.to_string(), started.elapsed()));
                             }
                         }
                     }
@@ -242,28 +261,51 @@ impl ComponentEditor {
             }
         }
 
-        serialized_components
+        (serialized_components, timings)
+    }
+
+    /// Type paths of the components serialize_entity_components would save, without serializing them
+    pub fn saved_component_names(&self, world: &World, entity: Entity) -> HashSet<String> {
+        let type_registry = self.type_registry.read();
+        world
+            .entity(entity)
+            .archetype()
+            .components()
+            .iter()
+            .filter_map(|component_id| {
+                let type_id = world.components().get_info(*component_id)?.type_id()?;
+                let registration = type_registry.get(type_id)?;
+                registration.data::<ReflectComponent>()?;
+                (!self.should_skip_component(registration))
+                    .then(|| registration.type_info().type_path().to_string())
+            })
+            .collect()
     }
 
     /// Insert components from serialized data with proper error handling
+    /// Returns how long each component took to load, for load profiles
     pub fn load_components_from_scene_data(
         &self,
         world: &mut World,
         entity: Entity,
         serialized_components: HashMap<String, String>,
         type_registry: AppTypeRegistry,
-    ) {
+    ) -> Vec<(String, Duration)> {
         let mut success_count = 0;
         let mut error_count = 0;
+        let mut timings = vec![];
 
         for (component_name, serialized_data) in serialized_components {
-            match self.process_single_component(
+            let started = Instant::now();
+            let result = self.process_single_component(
                 world,
                 entity,
                 &component_name,
                 &serialized_data,
                 &type_registry,
-            ) {
+            );
+            timings.push((component_name.clone(), started.elapsed()));
+            match result {
                 Ok(()) => {
                     success_count += 1;
                 }
@@ -289,6 +331,7 @@ impl ComponentEditor {
             success_count,
             error_count
         );
+        timings
     }
 
     /// Process a single component with comprehensive error handling
@@ -335,7 +378,7 @@ impl ComponentEditor {
             component_name,
             serialized_data
         );
-        
+
         // First, try to parse the original data as RON to see if we can extract the component directly
         if let Some(extracted) = self.try_extract_ron_component(component_name, serialized_data) {
             log!(
@@ -346,7 +389,7 @@ impl ComponentEditor {
             );
             return Some(extracted);
         }
-        
+
         log!(
             LogType::Game,
             LogLevel::Info,
@@ -498,7 +541,7 @@ impl ComponentEditor {
             "try_extract_ron_component: Looking for component '{}'",
             component_name
         );
-        
+
         // The data format is: {"component_name": (struct_data)}
         // We need to extract just the (struct_data) part
         let search_pattern = format!("\"{}\":", component_name);
@@ -510,17 +553,17 @@ impl ComponentEditor {
                 "Found pattern at position {}",
                 start
             );
-            
+
             let after_colon = start + search_pattern.len();
             let remaining = &serialized_data[after_colon..].trim_start();
-            
+
             // The data should be like: (manufacturer:Some(...),components:[(unit:W,...)])
             // Just extract from the opening ( to the closing }
             if remaining.starts_with('(') {
                 // Find the matching closing paren
                 let mut depth = 0;
                 let mut end_pos = None;
-                
+
                 for (i, ch) in remaining.chars().enumerate() {
                     match ch {
                         '(' | '[' => depth += 1,
@@ -534,13 +577,13 @@ impl ComponentEditor {
                         _ => {}
                     }
                 }
-                
+
                 if let Some(end) = end_pos {
                     let extracted = &remaining[..end];
-                    
+
                     // Clean up Float() wrappers
                     let cleaned = self.clean_extracted_ron(extracted);
-                    
+
                     log!(
                         LogType::Game,
                         LogLevel::Info,
@@ -548,12 +591,12 @@ impl ComponentEditor {
                         "Successfully extracted RON data: {}",
                         &cleaned[..cleaned.len().min(200)]
                     );
-                    
+
                     return Some(cleaned);
                 }
             }
         }
-        
+
         log!(
             LogType::Game,
             LogLevel::Info,
@@ -562,11 +605,11 @@ impl ComponentEditor {
         );
         None
     }
-    
+
     /// Clean extracted RON data by removing Float() wrappers while preserving structure
     fn clean_extracted_ron(&self, ron_data: &str) -> String {
         let mut result = ron_data.to_string();
-        
+
         // Remove Float() wrappers
         while result.contains("Float(") {
             // Find Float( and its matching closing )
@@ -574,7 +617,7 @@ impl ComponentEditor {
                 // Find the matching closing paren
                 let after_float = start + 6; // length of "Float("
                 let remaining = &result[after_float..];
-                
+
                 // Simple approach: find the first closing paren (works for simple numbers)
                 if let Some(end_offset) = remaining.find(')') {
                     let number = &remaining[..end_offset];
@@ -588,7 +631,7 @@ impl ComponentEditor {
                 break;
             }
         }
-        
+
         result
     }
 
@@ -617,13 +660,13 @@ impl ComponentEditor {
             ron::Value::Seq(seq) => {
                 // Check if this is a numeric array (Vec2, Vec3, Vec4, etc.)
                 let is_numeric_array = seq.iter().all(|v| matches!(v, ron::Value::Number(_)));
-                
+
                 // Convert sequence elements recursively
                 let elements: Vec<String> = seq
                     .iter()
                     .map(|v| self.convert_ron_value_to_struct(v, original_data))
                     .collect();
-                
+
                 if is_numeric_array {
                     // Use tuple syntax for numeric arrays (Vec2, Vec3, etc.)
                     format!("({})", elements.join(","))
@@ -632,12 +675,13 @@ impl ComponentEditor {
                     format!("[{}]", elements.join(","))
                 }
             }
-            ron::Value::Option(opt) => {
-                match opt {
-                    Some(inner) => format!("Some({})", self.convert_ron_value_to_struct(inner, original_data)),
-                    None => "None".to_string()
-                }
-            }
+            ron::Value::Option(opt) => match opt {
+                Some(inner) => format!(
+                    "Some({})",
+                    self.convert_ron_value_to_struct(inner, original_data)
+                ),
+                None => "None".to_string(),
+            },
             ron::Value::Unit => {
                 // For Unit values, try to extract the original enum variant name from the source
                 // This is a workaround because ron::Value::Unit loses the variant name
@@ -0,0 +1,210 @@
+use super::{randomization::randomize_entities, EntitySaveReadyData};
+use bevy::{
+    ecs::{component::Component, resource::Resource},
+    prelude::{
+        ReflectComponent, ReflectDefault, ReflectDeserialize, ReflectFromReflect, ReflectSerialize,
+    },
+    reflect::Reflect,
+};
+use bevy_granite_logging::{log, LogCategory, LogLevel, LogType};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use uuid::Uuid;
+
+// conditions.rs
+// Platform and feature conditions on scene entities and components, so one scene serves several targets
+// SpawnConditions is saved with the entity like any other component. Scene loads check it against SceneTarget
+// and skip what doesn't match before anything spawns. The editor loads everything, or saving would lose it
+
+/// Platforms and features something needs. A leading "!" negates an entry, i.e. "!mobile"
+#[derive(Reflect, Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[reflect(Serialize, Deserialize, Default, FromReflect)]
+pub struct SpawnCondition {
+    /// Any of these, i.e. "desktop", "web", "mobile", "windows", "linux", "macos". Empty for every platform
+    pub platforms: Vec<String>,
+    /// All of these, i.e. "high_end"
+    pub features: Vec<String>,
+}
+
+/// Where an entity and its components are spawned
+#[derive(Component, Reflect, Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[reflect(Component, Serialize, Deserialize, Default, FromReflect)]
+pub struct SpawnConditions {
+    /// The whole entity, children included
+    pub entity: SpawnCondition,
+    /// Single components, by type path or bare type name
+    pub components: BTreeMap<String, SpawnCondition>,
+}
+
+/// Platform and features scene loads are for, and the seed SpawnRandomization rolls with
+/// Defaults to the platform being built for, no features and a new seed each run
+/// i.e. `app.insert_resource(SceneTarget::current().with_feature("high_end").with_seed(7))`
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct SceneTarget {
+    pub platforms: HashSet<String>,
+    pub features: HashSet<String>,
+    /// Off loads everything regardless of conditions and randomization, the editor turns it off
+    pub enforce: bool,
+    pub seed: u64,
+}
+
+impl Default for SceneTarget {
+    fn default() -> Self {
+        Self::current()
+    }
+}
+
+impl SceneTarget {
+    /// The OS name and "desktop", "mobile" or "web"
+    pub fn current() -> Self {
+        let family = if cfg!(target_arch = "wasm32") {
+            "web"
+        } else if cfg!(any(target_os = "android", target_os = "ios")) {
+            "mobile"
+        } else {
+            "desktop"
+        };
+        let platforms = [std::env::consts::OS, family]
+            .into_iter()
+            .filter(|platform| !platform.is_empty())
+            .map(str::to_string)
+            .collect();
+        let seed = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|since| since.as_nanos() as u64)
+            .unwrap_or_default();
+        Self {
+            platforms,
+            features: HashSet::new(),
+            enforce: true,
+            seed,
+        }
+    }
+
+    /// Conditions are ignored and everything loads
+    pub fn everything() -> Self {
+        Self {
+            enforce: false,
+            ..Self::current()
+        }
+    }
+
+    pub fn with_feature(mut self, feature: impl Into<String>) -> Self {
+        self.features.insert(feature.into());
+        self
+    }
+
+    /// Same seed, same rolls. i.e. a daily seed or one from a save game
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn matches(&self, condition: &SpawnCondition) -> bool {
+        if !self.enforce {
+            return true;
+        }
+        let has = |set: &HashSet<String>, entry: &str| match entry.strip_prefix('!') {
+            Some(entry) => !set.contains(entry),
+            None => set.contains(entry),
+        };
+        let platform = condition.platforms.is_empty()
+            || condition
+                .platforms
+                .iter()
+                .any(|platform| has(&self.platforms, platform));
+        platform
+            && condition
+                .features
+                .iter()
+                .all(|feature| has(&self.features, feature))
+    }
+
+    /// Which scene entities to spawn, in order. Entities that don't match skip their children too,
+    /// and components that don't match are removed from the ones that stay
+    /// SpawnRandomization is rolled for the entities that match, see randomization.rs
+    pub fn filter(&self, entities: &mut [EntitySaveReadyData]) -> Vec<bool> {
+        if !self.enforce {
+            return vec![true; entities.len()];
+        }
+        let mut skipped: HashSet<Uuid> = HashSet::new();
+        let mut keep = vec![true; entities.len()];
+        let by_uuid: HashMap<Uuid, Option<Uuid>> = entities
+            .iter()
+            .map(|entity| (entity.identity.uuid, entity.parent))
+            .collect();
+
+        for (index, entity) in entities.iter_mut().enumerate() {
+            let Some(conditions) = read_conditions(entity) else {
+                continue;
+            };
+            if !self.matches(&conditions.entity) {
+                keep[index] = false;
+                skipped.insert(entity.identity.uuid);
+                continue;
+            }
+            if let Some(components) = entity.components.as_mut() {
+                components.retain(|type_path, _| {
+                    let short_name = type_path.rsplit("::").next().unwrap_or(type_path);
+                    conditions
+                        .components
+                        .get(type_path.as_str())
+                        .or_else(|| conditions.components.get(short_name))
+                        .is_none_or(|condition| self.matches(condition))
+                });
+            }
+        }
+
+        randomize_entities(self.seed, entities, &mut keep, &mut skipped);
+
+        // Descendants of skipped entities, wherever they are in the file
+        if !skipped.is_empty() {
+            for (index, entity) in entities.iter().enumerate() {
+                let mut parent = entity.parent;
+                // Bounded, in case a broken file has a parent loop
+                for _ in 0..by_uuid.len() {
+                    let Some(uuid) = parent else {
+                        break;
+                    };
+                    if skipped.contains(&uuid) {
+                        keep[index] = false;
+                        break;
+                    }
+                    parent = by_uuid.get(&uuid).copied().flatten();
+                }
+            }
+            log!(
+                LogType::Game,
+                LogLevel::Info,
+                LogCategory::System,
+                "Skipped {} entities by spawn conditions and randomization",
+                keep.iter().filter(|keep| !**keep).count()
+            );
+        }
+        keep
+    }
+
+    /// filter, dropping the skipped entities
+    pub fn apply(&self, mut entities: Vec<EntitySaveReadyData>) -> Vec<EntitySaveReadyData> {
+        let keep = self.filter(&mut entities);
+        entities
+            .into_iter()
+            .zip(keep)
+            .filter_map(|(entity, keep)| keep.then_some(entity))
+            .collect()
+    }
+}
+
+/// SpawnConditions of saved entity data. Saved as `{"type::path":(..)}`, see ComponentEditor
+fn read_conditions(entity: &EntitySaveReadyData) -> Option<SpawnConditions> {
+    let serialized = entity
+        .components
+        .as_ref()?
+        .iter()
+        .find(|(type_path, _)| type_path.ends_with("::SpawnConditions"))
+        .map(|(_, serialized)| serialized)?;
+    ron::from_str::<HashMap<String, SpawnConditions>>(serialized)
+        .ok()?
+        .into_values()
+        .next()
+}
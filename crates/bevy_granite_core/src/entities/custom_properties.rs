@@ -0,0 +1,334 @@
+use bevy::{
+    ecs::component::Component,
+    prelude::{
+        Color, ReflectComponent, ReflectDefault, ReflectDeserialize, ReflectFromReflect,
+        ReflectSerialize,
+    },
+    reflect::Reflect,
+};
+use bevy_egui::egui;
+use bevy_inspector_egui::{
+    inspector_egui_impls::InspectorPrimitive, reflect_inspector::InspectorUi,
+};
+use serde::{Deserialize, Serialize};
+use std::{any::Any, collections::BTreeMap};
+
+// custom_properties.rs
+// Untyped key/value data on an entity, for game specific values that don't deserve their own component
+// Saved with the entity like any other component, and edited in the entity editor with its own table
+
+#[derive(Reflect, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[reflect(Serialize, Deserialize, Default, FromReflect)]
+pub enum PropertyValue {
+    String(String),
+    Number(f64),
+    Bool(bool),
+    Color(Color),
+}
+
+impl Default for PropertyValue {
+    fn default() -> Self {
+        Self::String(String::new())
+    }
+}
+
+impl PropertyValue {
+    pub fn kind_name(&self) -> &'static str {
+        match self {
+            Self::String(_) => "String",
+            Self::Number(_) => "Number",
+            Self::Bool(_) => "Bool",
+            Self::Color(_) => "Color",
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            Self::Number(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_color(&self) -> Option<Color> {
+        match self {
+            Self::Color(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Same value as another kind where it makes sense, the kind's default otherwise
+    fn converted(&self, kind: &PropertyValue) -> PropertyValue {
+        match (self, kind) {
+            (Self::Number(value), Self::String(_)) => Self::String(value.to_string()),
+            (Self::Bool(value), Self::String(_)) => Self::String(value.to_string()),
+            (Self::String(value), Self::Number(_)) => {
+                Self::Number(value.trim().parse().unwrap_or(0.0))
+            }
+            (Self::Bool(value), Self::Number(_)) => Self::Number(if *value { 1.0 } else { 0.0 }),
+            (Self::String(value), Self::Bool(_)) => Self::Bool(value.trim() == "true"),
+            (Self::Number(value), Self::Bool(_)) => Self::Bool(*value != 0.0),
+            _ => kind.clone(),
+        }
+    }
+}
+
+impl From<&str> for PropertyValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
+
+impl From<String> for PropertyValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<f64> for PropertyValue {
+    fn from(value: f64) -> Self {
+        Self::Number(value)
+    }
+}
+
+impl From<f32> for PropertyValue {
+    fn from(value: f32) -> Self {
+        Self::Number(value as f64)
+    }
+}
+
+impl From<i32> for PropertyValue {
+    fn from(value: i32) -> Self {
+        Self::Number(value as f64)
+    }
+}
+
+impl From<bool> for PropertyValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<Color> for PropertyValue {
+    fn from(value: Color) -> Self {
+        Self::Color(value)
+    }
+}
+
+/// Key/value bag of an entity, i.e. `"loot_table": "chest_rare"` or `"health": 250`
+/// Getters return None when the key is missing or holds another kind of value
+#[derive(Reflect, Serialize, Deserialize, Debug, Clone, Component, Default, PartialEq)]
+#[reflect(Component, Serialize, Deserialize, Default, FromReflect)]
+pub struct CustomProperties {
+    pub properties: BTreeMap<String, PropertyValue>,
+}
+
+impl CustomProperties {
+    pub fn with(mut self, key: impl Into<String>, value: impl Into<PropertyValue>) -> Self {
+        self.set(key, value);
+        self
+    }
+
+    pub fn set(&mut self, key: impl Into<String>, value: impl Into<PropertyValue>) {
+        self.properties.insert(key.into(), value.into());
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<PropertyValue> {
+        self.properties.remove(key)
+    }
+
+    pub fn get(&self, key: &str) -> Option<&PropertyValue> {
+        self.properties.get(key)
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.properties.contains_key(key)
+    }
+
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.get(key).and_then(PropertyValue::as_str)
+    }
+
+    pub fn get_number(&self, key: &str) -> Option<f64> {
+        self.get(key).and_then(PropertyValue::as_number)
+    }
+
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        self.get(key).and_then(PropertyValue::as_bool)
+    }
+
+    pub fn get_color(&self, key: &str) -> Option<Color> {
+        self.get(key).and_then(PropertyValue::as_color)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &PropertyValue)> {
+        self.properties.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.properties.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.properties.is_empty()
+    }
+
+    /// Key for a new property, "property", "property_1" and so on
+    fn unused_key(&self) -> String {
+        let mut key = "property".to_string();
+        let mut index = 0;
+        while self.contains(&key) {
+            index += 1;
+            key = format!("property_{}", index);
+        }
+        key
+    }
+}
+
+// Table in the entity editor instead of the generic map inspector, so keys can be renamed and kinds switched
+impl InspectorPrimitive for CustomProperties {
+    fn ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        _options: &dyn Any,
+        id: egui::Id,
+        _env: InspectorUi<'_, '_>,
+    ) -> bool {
+        let mut changed = false;
+        let mut renamed = None;
+        let mut removed = None;
+        let kinds = [
+            PropertyValue::String(String::new()),
+            PropertyValue::Number(0.0),
+            PropertyValue::Bool(false),
+            PropertyValue::Color(Color::WHITE),
+        ];
+
+        egui::Grid::new(id.with("custom_properties"))
+            .num_columns(4)
+            .striped(true)
+            .show(ui, |ui| {
+                for (index, (key, value)) in self.properties.iter_mut().enumerate() {
+                    // The key is renamed once editing ends, so rows don't reorder while typing
+                    let key_id = id.with(("key", key.as_str()));
+                    let mut new_key = ui
+                        .data(|data| data.get_temp::<String>(key_id))
+                        .unwrap_or_else(|| key.clone());
+                    let response = ui.add(
+                        egui::TextEdit::singleline(&mut new_key)
+                            .id(key_id)
+                            .desired_width(90.0),
+                    );
+                    if response.changed() {
+                        ui.data_mut(|data| data.insert_temp(key_id, new_key.clone()));
+                    }
+                    if response.lost_focus() {
+                        ui.data_mut(|data| data.remove::<String>(key_id));
+                        if new_key != *key {
+                            renamed = Some((key.clone(), new_key));
+                        }
+                    }
+
+                    egui::ComboBox::from_id_salt(id.with(("kind", index)))
+                        .width(70.0)
+                        .selected_text(value.kind_name())
+                        .show_ui(ui, |ui| {
+                            for kind in &kinds {
+                                let selected =
+                                    std::mem::discriminant(value) == std::mem::discriminant(kind);
+                                if ui.selectable_label(selected, kind.kind_name()).clicked()
+                                    && !selected
+                                {
+                                    *value = value.converted(kind);
+                                    changed = true;
+                                }
+                            }
+                        });
+
+                    changed |= match value {
+                        PropertyValue::String(text) => ui.text_edit_singleline(text).changed(),
+                        PropertyValue::Number(number) => {
+                            ui.add(egui::DragValue::new(number).speed(0.1)).changed()
+                        }
+                        PropertyValue::Bool(flag) => ui.checkbox(flag, "").changed(),
+                        PropertyValue::Color(color) => {
+                            let srgba = color.to_srgba();
+                            let mut rgba = [srgba.red, srgba.green, srgba.blue, srgba.alpha];
+                            let edited =
+                                ui.color_edit_button_rgba_unmultiplied(&mut rgba).changed();
+                            if edited {
+                                *color = Color::srgba(rgba[0], rgba[1], rgba[2], rgba[3]);
+                            }
+                            edited
+                        }
+                    };
+
+                    if ui
+                        .small_button("❌")
+                        .on_hover_text("Remove property")
+                        .clicked()
+                    {
+                        removed = Some(key.clone());
+                    }
+                    ui.end_row();
+                }
+            });
+
+        // Renaming onto an existing key would drop that property, so it waits for a free name
+        if let Some((old, new)) = renamed {
+            if !new.is_empty() && !self.contains(&new) {
+                if let Some(value) = self.properties.remove(&old) {
+                    self.properties.insert(new, value);
+                    changed = true;
+                }
+            }
+        }
+        if let Some(key) = removed {
+            self.properties.remove(&key);
+            changed = true;
+        }
+        if ui.button("Add Property").clicked() {
+            let key = self.unused_key();
+            self.properties.insert(key, PropertyValue::default());
+            changed = true;
+        }
+        changed
+    }
+
+    fn ui_readonly(
+        &self,
+        ui: &mut egui::Ui,
+        _options: &dyn Any,
+        id: egui::Id,
+        _env: InspectorUi<'_, '_>,
+    ) {
+        egui::Grid::new(id.with("custom_properties_readonly"))
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                for (key, value) in self.iter() {
+                    ui.label(key);
+                    ui.label(match value {
+                        PropertyValue::String(text) => text.clone(),
+                        PropertyValue::Number(number) => number.to_string(),
+                        PropertyValue::Bool(flag) => flag.to_string(),
+                        PropertyValue::Color(color) => format!("{:?}", color.to_srgba()),
+                    });
+                    ui.end_row();
+                }
+            });
+    }
+}
@@ -1,14 +1,23 @@
-use super::{ComponentEditor, EntitySaveReadyData, IdentityData, SceneData, SpawnSource};
+use super::{
+    run_granite_spawn_hooks, ComponentEditor, EntitySaveReadyData, IdentityData, SceneData,
+    SpawnSource,
+};
 use crate::{
-    absolute_asset_to_rel, entities::SaveSettings, materials_from_folder_into_scene,
-    rel_asset_to_absolute, shared::is_scene_version_compatible, AvailableEditableMaterials,
-    GraniteType, TransformData,
+    absolute_asset_to_rel,
+    entities::SaveSettings,
+    materials_from_folder_into_scene, read_binary_scene, read_scene_file, rel_asset_to_absolute,
+    shared::is_scene_version_compatible,
+    world::{PrefabMember, SceneProfile, SceneProfileKind, SceneProfileReport},
+    AvailableEditableMaterials, GraniteClassRegistry, GraniteType, GraniteTypes, SandboxReport,
+    SceneSandbox, SceneTarget, TransformData,
 };
 use bevy::{
     ecs::{entity::Entity, system::ResMut, world::World},
     mesh::Mesh,
     pbr::StandardMaterial,
-    prelude::{AppTypeRegistry, AssetServer, Assets, Commands, Component, Reflect, Res},
+    prelude::{
+        AppTypeRegistry, AssetServer, Assets, Commands, Component, Reflect, ReflectComponent, Res,
+    },
     transform::components::Transform,
 };
 use bevy_granite_logging::{
@@ -17,8 +26,11 @@ use bevy_granite_logging::{
 };
 use ron::de::from_str;
 use serde::{Deserialize, Serialize};
-use std::io::Read;
-use std::{borrow::Cow, fs::File};
+use std::{
+    borrow::Cow,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
 use uuid::Uuid;
 
 // Main component to tag all of our custom entity class types
@@ -26,11 +38,52 @@ use uuid::Uuid;
 #[derive(Component, Serialize, Reflect, Deserialize, Debug, Clone, Default, PartialEq)]
 pub struct GraniteEditorSerdeEntity;
 
+/// Position of the entity in its scene file. Queries don't keep file order, sort by this when setup order matters
+/// Saves write entities back in this order, new ones last
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[reflect(Component)]
+pub struct SceneOrder(pub usize);
+
+/// Reorder so parents always come before their children, otherwise keeping file order
+/// Pairs each entity with its index in the file
+pub fn order_parents_first(entities: &[EntitySaveReadyData]) -> Vec<(usize, &EntitySaveReadyData)> {
+    let index_by_uuid: std::collections::HashMap<Uuid, usize> = entities
+        .iter()
+        .enumerate()
+        .map(|(index, save_data)| (save_data.identity.uuid, index))
+        .collect();
+    let mut placed = vec![false; entities.len()];
+    let mut ordered = Vec::with_capacity(entities.len());
+
+    for index in 0..entities.len() {
+        // Walk up to the first ancestor not placed yet, the visited guard stops parent cycles
+        let mut chain = vec![];
+        let mut visited = std::collections::HashSet::new();
+        let mut current = Some(index);
+        while let Some(i) = current {
+            if placed[i] || !visited.insert(i) {
+                break;
+            }
+            chain.push(i);
+            current = entities[i]
+                .parent
+                .and_then(|parent| index_by_uuid.get(&parent).copied());
+        }
+        for i in chain.into_iter().rev() {
+            placed[i] = true;
+            ordered.push((i, &entities[i]));
+        }
+    }
+    ordered
+}
+
 // Basically we grab the file contents into save ready struct
 // Spawn all entities - (might be able to improve and just insert components this step?)
 // Insert all components with access to mut World after all entities are spawned
 
 /// Build materials and entities into the scene from the world path
+/// With a sandbox, entities are filtered first and the report of what was stripped is returned
+/// Entities and components whose SpawnConditions don't match the target are skipped
 pub fn deserialize_entities(
     asset_server: &Res<AssetServer>,
     commands: &mut Commands,
@@ -40,26 +93,156 @@ pub fn deserialize_entities(
     path: impl Into<Cow<'static, str>>, //absolute or rel
     save_settings: SaveSettings,
     transform_override: Option<Transform>,
-) {
-    let abs_path: Cow<'static, str> = rel_asset_to_absolute(&path.into());
+    sandbox: Option<&SceneSandbox>,
+    target: &SceneTarget,
+) -> Option<SandboxReport> {
+    let path: Cow<'static, str> = path.into();
+    let abs_path: Cow<'static, str> = rel_asset_to_absolute(&path);
+    let mut sandbox_report = None;
+    if let Some(sandbox) = sandbox {
+        // Check the path before reading anything from disk
+        let rel_path = absolute_asset_to_rel(path.to_string());
+        if !sandbox.is_path_allowed(&rel_path) {
+            return Some(sandbox.filter(&rel_path, vec![]).1);
+        }
+    }
     // Build materials from the folder and load them into the scene
     materials_from_folder_into_scene("materials", materials, available_materials, asset_server);
 
     // Gather file contents into a Vec<EntitySaveReadyData>
+    let read_started = Instant::now();
     let deserialized_data = gather_file_contents(
         asset_server,
         materials,
         available_materials,
         abs_path.as_ref(),
     );
+    let file_time = read_started.elapsed();
+    let deserialized_data = match sandbox {
+        Some(sandbox) => {
+            let (allowed, report) = sandbox.filter(
+                &absolute_asset_to_rel(abs_path.to_string()),
+                deserialized_data,
+            );
+            sandbox_report = Some(report);
+            allowed
+        }
+        None => deserialized_data,
+    };
+    let deserialized_data = target.apply(deserialized_data);
+
+    spawn_scene_entities(
+        asset_server,
+        commands,
+        materials,
+        available_materials,
+        meshes,
+        &deserialized_data,
+        &SceneSpawnTarget {
+            abs_path,
+            save_settings,
+            transform_override,
+            file_indices: None,
+            loaded: std::collections::HashMap::new(),
+            prefab_sources: None,
+            new_to_scene: false,
+            file_time,
+        },
+    );
+
+    sandbox_report
+}
+
+/// Where spawned scene entities come from and how they attach to what is already loaded
+pub(crate) struct SceneSpawnTarget {
+    pub abs_path: Cow<'static, str>,
+    pub save_settings: SaveSettings,
+    pub transform_override: Option<Transform>,
+    /// Index in the scene file of each entity, when only part of the file is spawned
+    pub file_indices: Option<Vec<usize>>,
+    /// Entities of the scene spawned earlier, that children in this batch can attach to
+    pub loaded: std::collections::HashMap<Uuid, Entity>,
+    /// Set when spawning prefab members: spawned uuid to uuid in the prefab file
+    /// Members are tagged with PrefabMember instead of a SpawnSource, so scene saves skip them
+    pub prefab_sources: Option<std::collections::HashMap<Uuid, Uuid>>,
+    /// Entities added to an already loaded scene, i.e. imports. They get no file order, so saves put them last
+    pub new_to_scene: bool,
+    /// Time spent reading and parsing the scene file, for the load profile
+    pub file_time: Duration,
+}
+
+/// Spawn deserialized entities with their hierarchy and components, tagged with their source
+pub(crate) fn spawn_scene_entities(
+    asset_server: &Res<AssetServer>,
+    commands: &mut Commands,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+    available_materials: &mut ResMut<AvailableEditableMaterials>,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    deserialized_data: &[EntitySaveReadyData],
+    target: &SceneSpawnTarget,
+) {
+    let started = Instant::now();
+    let abs_path = &target.abs_path;
+
+    // Warn about classes no plugin registered, they still spawn so their data survives a re-save
+    let classes: Vec<GraniteTypes> = deserialized_data
+        .iter()
+        .map(|save_data| save_data.identity.class.clone())
+        .collect();
+    let source = abs_path.to_string();
+    commands.queue(move |world: &mut World| {
+        let Some(class_registry) = world.get_resource::<GraniteClassRegistry>() else {
+            return;
+        };
+        let mut reported: Vec<String> = vec![];
+        for class in classes
+            .iter()
+            .filter(|class| !class_registry.is_registered(class))
+        {
+            let type_name = class.type_name();
+            if reported.contains(&type_name) {
+                continue;
+            }
+            log!(
+                LogType::Game,
+                LogLevel::Warning,
+                LogCategory::Entity,
+                "Scene '{}' uses class '{}' which is not registered",
+                source,
+                type_name
+            );
+            reported.push(type_name);
+        }
+    });
 
     // for id
     let mut uuid_to_entity_map: std::collections::HashMap<Uuid, Entity> =
         std::collections::HashMap::new();
-    let mut parent_relationships: Vec<(Entity, Uuid)> = Vec::new(); // (child_entity, parent_guid)
-
-    // Deserialized data is Vec<EntitySaveReadyData>
-    for save_data in &deserialized_data {
+    let mut parent_relationships: Vec<(usize, Entity, Uuid)> = Vec::new(); // (file_index, child_entity, parent_guid)
+    let mut component_loads: Vec<(
+        usize,
+        Entity,
+        Uuid,
+        std::collections::HashMap<String, String>,
+    )> = Vec::new();
+    let mut spawned: Vec<(usize, Entity)> = Vec::new();
+    let relative: Cow<'static, str> = absolute_asset_to_rel(abs_path.to_string());
+
+    // Prefab members are part of the scene that spawned their instance, they are not profiled on their own
+    let profile = target.prefab_sources.is_none().then(|| {
+        let mut profile = SceneProfile::new(SceneProfileKind::Load, relative.to_string());
+        profile.file = target.file_time;
+        Arc::new(Mutex::new(profile))
+    });
+
+    // Phase 1. Spawn every entity, parents first, and link the hierarchy
+    for (index, save_data) in order_parents_first(deserialized_data) {
+        let file_index = target
+            .file_indices
+            .as_ref()
+            .and_then(|indices| indices.get(index).copied())
+            .unwrap_or(index);
+        let spawn_started = Instant::now();
         let (entity, _final_identity) = spawn_entity_from_class_type(
             asset_server,
             commands,
@@ -67,21 +250,50 @@ pub fn deserialize_entities(
             available_materials,
             meshes,
             save_data,
-            transform_override,
+            target.transform_override,
         );
+        if let Some(profile) = profile.as_ref() {
+            let bytes = save_data
+                .components
+                .iter()
+                .flatten()
+                .map(|(type_path, data)| type_path.len() + data.len())
+                .sum();
+            profile.lock().unwrap().record_entity(
+                save_data.identity.uuid,
+                &save_data.identity.name,
+                &save_data.identity.class.type_name(),
+                spawn_started.elapsed(),
+                bytes,
+            );
+        }
 
         // Map the stored GUID to the new entity
         uuid_to_entity_map.insert(save_data.identity.uuid, entity);
-
-        // Tag entity with its source file
-        let relative: Cow<'static, str> = absolute_asset_to_rel(abs_path.to_string());
-        commands
-            .entity(entity)
-            .insert(SpawnSource::new(relative, save_settings.clone()));
+        spawned.push((file_index, entity));
+
+        // Tag entity with its source file and where it sits in it
+        if let Some(sources) = target.prefab_sources.as_ref() {
+            let source = sources
+                .get(&save_data.identity.uuid)
+                .copied()
+                .unwrap_or(save_data.identity.uuid);
+            commands.entity(entity).insert(PrefabMember { source });
+        } else if target.new_to_scene {
+            commands.entity(entity).insert(SpawnSource::new(
+                relative.clone(),
+                target.save_settings.clone(),
+            ));
+        } else {
+            commands.entity(entity).insert((
+                SpawnSource::new(relative.clone(), target.save_settings.clone()),
+                SceneOrder(file_index),
+            ));
+        }
 
         // Store parent relationships for second pass
         if let Some(parent_guid) = save_data.parent {
-            parent_relationships.push((entity, parent_guid));
+            parent_relationships.push((file_index, entity, parent_guid));
         }
 
         //
@@ -102,33 +314,24 @@ pub fn deserialize_entities(
         //);
         //
 
-        // Load components into the scene entities
+        // Queue components to load into the scene entities in phase 2
         if let Some(component_map) = save_data.components.as_ref() {
-            let component_map = component_map.clone();
-            let entity_copy = entity;
-
-            commands.queue(move |world: &mut World| {
-                // Get the current type registry from the world
-                let type_registry = world.resource::<AppTypeRegistry>().clone();
-
-                // Remove the resource to avoid borrowing errors
-                if let Some(component_editor) = world.remove_resource::<ComponentEditor>() {
-                    component_editor.load_components_from_scene_data(
-                        world,
-                        entity_copy,
-                        component_map,
-                        type_registry,
-                    );
-
-                    world.insert_resource(component_editor);
-                }
-            });
+            component_loads.push((
+                file_index,
+                entity,
+                save_data.identity.uuid,
+                component_map.clone(),
+            ));
         }
     }
 
-    // Apply relationships
-    for (child_entity, parent_guid) in parent_relationships {
-        if let Some(&parent_entity) = uuid_to_entity_map.get(&parent_guid) {
+    // Apply relationships. Children are added in file order, so Children keeps file order too
+    parent_relationships.sort_by_key(|(file_index, _, _)| *file_index);
+    for (_, child_entity, parent_guid) in parent_relationships {
+        if let Some(&parent_entity) = uuid_to_entity_map
+            .get(&parent_guid)
+            .or_else(|| target.loaded.get(&parent_guid))
+        {
             commands.entity(parent_entity).add_child(child_entity);
         } else {
             log!(
@@ -142,6 +345,57 @@ pub fn deserialize_entities(
         }
     }
 
+    // Phase 2. Load components once every entity exists, so references to other entities resolve
+    component_loads.sort_by_key(|(file_index, _, _, _)| *file_index);
+    for (_, entity, uuid, component_map) in component_loads {
+        let profile = profile.clone();
+        commands.queue(move |world: &mut World| {
+            // Get the current type registry from the world
+            let type_registry = world.resource::<AppTypeRegistry>().clone();
+
+            // Remove the resource to avoid borrowing errors
+            if let Some(component_editor) = world.remove_resource::<ComponentEditor>() {
+                let sizes: std::collections::HashMap<String, usize> = component_map
+                    .iter()
+                    .map(|(type_path, data)| (type_path.clone(), type_path.len() + data.len()))
+                    .collect();
+                let timings = component_editor.load_components_from_scene_data(
+                    world,
+                    entity,
+                    component_map,
+                    type_registry,
+                );
+                if let Some(profile) = profile {
+                    let mut profile = profile.lock().unwrap();
+                    for (type_path, time) in timings {
+                        let bytes = sizes.get(&type_path).copied().unwrap_or_default();
+                        profile.record_component(uuid, &type_path, time, bytes);
+                    }
+                }
+
+                world.insert_resource(component_editor);
+            }
+        });
+    }
+
+    // Everything is constructed, let game code set the entities up, in file order
+    spawned.sort_by_key(|(file_index, _)| *file_index);
+    let spawned: Vec<Entity> = spawned.into_iter().map(|(_, entity)| entity).collect();
+    commands.queue(move |world: &mut World| {
+        run_granite_spawn_hooks(world, &spawned);
+        let Some(profile) = profile else {
+            return;
+        };
+        let Ok(profile) = Arc::try_unwrap(profile) else {
+            return;
+        };
+        let mut profile = profile.into_inner().unwrap();
+        profile.total = profile.file + started.elapsed();
+        if let Some(mut report) = world.get_resource_mut::<SceneProfileReport>() {
+            report.finish(profile);
+        }
+    });
+
     log!(
         LogType::Game,
         LogLevel::OK,
@@ -182,121 +436,129 @@ fn gather_file_contents(
         "--------------------"
     );
 
-    let mut file = match File::open(&path) {
-        Ok(file) => file,
-        Err(e) => {
-            log!(
-                LogType::Game,
-                LogLevel::Error,
-                LogCategory::System,
-                "Failed to open file {}: {}. Are you sure it exists?",
-                path,
-                e
-            );
-            return vec![];
-        }
-    };
+    // Binary scenes skip RON and the text checks
+    let scene_data = match read_binary_scene(path).ok().flatten() {
+        Some(scene) => scene,
+        None => {
+            // Compressed scenes are recognized by their header and decompressed here
+            let file_contents = match read_scene_file(path) {
+                Ok(contents) => contents,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    log!(
+                        LogType::Game,
+                        LogLevel::Error,
+                        LogCategory::System,
+                        "Failed to open file {}: {}. Are you sure it exists?",
+                        path,
+                        e
+                    );
+                    return vec![];
+                }
+                Err(e) => {
+                    log!(
+                        LogType::Game,
+                        LogLevel::Error,
+                        LogCategory::System,
+                        "Failed to read file {}: {}",
+                        path,
+                        e
+                    );
+                    return vec![];
+                }
+            };
 
-    let mut file_contents = String::new();
-    if let Err(e) = file.read_to_string(&mut file_contents) {
-        log!(
-            LogType::Game,
-            LogLevel::Error,
-            LogCategory::System,
-            "Failed to read file {}: {}",
-            path,
-            e
-        );
-        return vec![];
-    }
+            // Handle empty file
+            if file_contents.is_empty() {
+                log!(
+                    LogType::Game,
+                    LogLevel::Warning,
+                    LogCategory::System,
+                    "No contents found in scene: {}",
+                    path
+                );
+                return vec![];
+            }
 
-    // Handle empty file
-    if file_contents.is_empty() {
-        log!(
-            LogType::Game,
-            LogLevel::Warning,
-            LogCategory::System,
-            "No contents found in scene: {}",
-            path
-        );
-        return vec![];
-    }
+            // Handle whitespace-only files
+            if file_contents.trim().is_empty() {
+                log!(
+                    LogType::Game,
+                    LogLevel::Warning,
+                    LogCategory::System,
+                    "Only whitespace found in scene: {}",
+                    path
+                );
+                return vec![];
+            }
 
-    // Handle whitespace-only files
-    if file_contents.trim().is_empty() {
-        log!(
-            LogType::Game,
-            LogLevel::Warning,
-            LogCategory::System,
-            "Only whitespace found in scene: {}",
-            path
-        );
-        return vec![];
-    }
+            // Handle empty JSON object or array
+            let trimmed = file_contents.trim();
+            if trimmed == "{}" || trimmed == "[]" {
+                log!(
+                    LogType::Game,
+                    LogLevel::Warning,
+                    LogCategory::System,
+                    "Empty JSON structure found in scene: {}, skipping entity creation",
+                    path
+                );
+                // Still create materials even if no entities to deserialize
+                materials_from_folder_into_scene(
+                    "materials",
+                    materials,
+                    available_materials,
+                    asset_server,
+                );
+                return vec![];
+            }
+
+            // Attempt to deserialize with proper error handling
+            let Ok(scene_data) = from_str::<SceneData>(&file_contents) else {
+                log!(
+                    LogType::Game,
+                    LogLevel::Error,
+                    LogCategory::System,
+                    "Failed to deserialize data from {} - invalid format",
+                    path
+                );
+                return vec![];
+            };
+            scene_data
+        }
+    };
 
-    // Handle empty JSON object or array
-    let trimmed = file_contents.trim();
-    if trimmed == "{}" || trimmed == "[]" {
+    log!(
+        LogType::Game,
+        LogLevel::Info,
+        LogCategory::System,
+        "Loading scene with metadata - Version: {}, Entities: {}",
+        scene_data.metadata.format_version,
+        scene_data.metadata.entity_count
+    );
+
+    // Check version compatibility
+    if !is_scene_version_compatible(scene_data.metadata.format_version) {
         log!(
             LogType::Game,
             LogLevel::Warning,
             LogCategory::System,
-            "Empty JSON structure found in scene: {}, skipping entity creation",
-            path
+            "Scene version {} may not be fully compatible with current version",
+            scene_data.metadata.format_version
         );
-        // Still create materials even if no entities to deserialize
-        materials_from_folder_into_scene("materials", materials, available_materials, asset_server);
-        return vec![];
     }
 
-    // Attempt to deserialize with proper error handling
-    // Try new format first (with metadata), fallback to old format (direct array)
-    let deserialized_data: Vec<EntitySaveReadyData> = if let Ok(scene_data) =
-        from_str::<SceneData>(&file_contents)
-    {
+    let e_count = scene_data.entities.len();
+    if e_count != scene_data.metadata.entity_count {
         log!(
-            LogType::Game,
-            LogLevel::Info,
-            LogCategory::System,
-            "Loading scene with metadata - Version: {}, Entities: {}",
-            scene_data.metadata.format_version,
-            scene_data.metadata.entity_count
-        );
-
-        // Check version compatibility
-        if !is_scene_version_compatible(scene_data.metadata.format_version) {
-            log!(
                 LogType::Game,
                 LogLevel::Warning,
                 LogCategory::System,
-                "Scene version {} may not be fully compatible with current version",
-                scene_data.metadata.format_version
+                "Entity count mismatch: expected {}, found {}. Not an error, but perhaps you manually edited the scene file?",
+                scene_data.metadata.entity_count,
+                e_count
             );
-        }
-
-        let e_count = scene_data.entities.len();
-        if e_count != scene_data.metadata.entity_count {
-            log!(
-                    LogType::Game,
-                    LogLevel::Warning,
-                    LogCategory::System,
-                    "Entity count mismatch: expected {}, found {}. Not an error, but perhaps you manually edited the scene file?",
-                    scene_data.metadata.entity_count,
-                    e_count
-                );
-        }
+    }
 
-        scene_data.entities
-    } else {
-        log!(
-            LogType::Game,
-            LogLevel::Error,
-            LogCategory::System,
-            "Failed to deserialize data from {} - invalid format",
-            path
-        );
-        return vec![];
-    };
+    let deserialized_data: Vec<EntitySaveReadyData> = scene_data.entities;
 
     // Handle case where deserialization succeeded but resulted in empty vector
     if deserialized_data.is_empty() {
@@ -379,3 +641,78 @@ fn offset_saved_transform(original: TransformData, offset: Transform) -> Transfo
         scale: new_transform.scale,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Entity `id` with an optional parent, uuids are the ids so failures read easily
+    fn entity(id: u128, parent: Option<u128>) -> EntitySaveReadyData {
+        EntitySaveReadyData {
+            identity: IdentityData {
+                uuid: Uuid::from_u128(id),
+                name: format!("entity_{}", id),
+                ..Default::default()
+            },
+            transform: TransformData::default(),
+            parent: parent.map(Uuid::from_u128),
+            components: None,
+        }
+    }
+
+    fn ordered_ids(entities: &[EntitySaveReadyData]) -> Vec<u128> {
+        order_parents_first(entities)
+            .into_iter()
+            .map(|(_, save_data)| save_data.identity.uuid.as_u128())
+            .collect()
+    }
+
+    #[test]
+    fn child_before_parent_is_moved_after_it() {
+        let entities = [entity(1, Some(2)), entity(2, None)];
+        assert_eq!(ordered_ids(&entities), vec![2, 1]);
+    }
+
+    #[test]
+    fn deep_chain_in_reverse_file_order() {
+        let entities = [entity(3, Some(2)), entity(2, Some(1)), entity(1, None)];
+        assert_eq!(ordered_ids(&entities), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn missing_parent_keeps_the_entity() {
+        let entities = [entity(1, Some(99)), entity(2, None)];
+        assert_eq!(ordered_ids(&entities), vec![1, 2]);
+    }
+
+    #[test]
+    fn parent_cycle_places_every_entity_once() {
+        let entities = [entity(1, Some(2)), entity(2, Some(1)), entity(3, None)];
+        let mut ids = ordered_ids(&entities);
+        assert_eq!(ids.len(), entities.len());
+        ids.sort();
+        assert_eq!(ids, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn roots_keep_file_order() {
+        let entities = [
+            entity(5, None),
+            entity(1, None),
+            entity(4, Some(3)),
+            entity(3, None),
+            entity(2, None),
+        ];
+        assert_eq!(ordered_ids(&entities), vec![5, 1, 3, 4, 2]);
+    }
+
+    #[test]
+    fn pairs_keep_file_index() {
+        let entities = [entity(1, Some(2)), entity(2, None)];
+        let indices: Vec<usize> = order_parents_first(&entities)
+            .into_iter()
+            .map(|(index, _)| index)
+            .collect();
+        assert_eq!(indices, vec![1, 0]);
+    }
+}
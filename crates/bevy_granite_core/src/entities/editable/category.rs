@@ -7,6 +7,8 @@ pub enum ClassCategory {
     Light,
     Empty,
     UI,
+    /// Project defined category, i.e. #[granite_class(category = "Vehicles")]
+    Custom(&'static str),
     Unknown,
 }
 
@@ -18,25 +20,30 @@ impl ClassCategory {
             Self::Light => "Light".to_string(),
             Self::Empty => "Empty".to_string(),
             Self::UI => "UI".to_string(),
+            Self::Custom(name) => name.to_string(),
             Self::Unknown => "Unknown".to_string(),
         }
     }
     // How each category should be ordered for UI elements when looping through all
-    fn order_value(&self) -> u8 {
+    // Projects can override this per category through GraniteClassRegistry::set_category_order
+    pub fn default_order(&self) -> i32 {
         match self {
             ClassCategory::Mesh => 0,
             ClassCategory::Light => 1,
             ClassCategory::Gameplay => 2,
             ClassCategory::Empty => 3,
             ClassCategory::UI => 4,
-            ClassCategory::Unknown => 5,
+            ClassCategory::Custom(_) => 100,
+            ClassCategory::Unknown => i32::MAX,
         }
     }
 }
 
 impl Ord for ClassCategory {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.order_value().cmp(&other.order_value())
+        self.default_order()
+            .cmp(&other.default_order())
+            .then_with(|| self.get_friendly_name().cmp(&other.get_friendly_name()))
     }
 }
 
@@ -0,0 +1,136 @@
+use super::{ClassCategory, GraniteType, GraniteTypes};
+use bevy::{
+    app::App,
+    asset::{AssetServer, Handle},
+    ecs::resource::Resource,
+    image::Image,
+};
+use std::{collections::HashMap, mem::discriminant};
+
+/// Every class the editor knows how to spawn
+/// Built in classes are registered by their plugins, game classes and class packs via register_granite_class
+/// Spawn menus, icons and the node tree read from here instead of a hardcoded list
+#[derive(Resource, Default, Clone)]
+pub struct GraniteClassRegistry {
+    classes: Vec<GraniteTypes>,
+    /// Per project category ordering, lower shows first. Falls back to ClassCategory::default_order
+    category_order: HashMap<ClassCategory, i32>,
+    /// Class type name -> icon asset path, used instead of the embedded icon
+    icon_overrides: HashMap<String, String>,
+}
+
+impl GraniteClassRegistry {
+    /// Add a class with its default data. Registering the same class again replaces it
+    pub fn register(&mut self, class: GraniteTypes) {
+        if let Some(existing) = self
+            .classes
+            .iter_mut()
+            .find(|existing| Self::same_class(existing, &class))
+        {
+            *existing = class;
+        } else {
+            self.classes.push(class);
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &GraniteTypes> {
+        self.classes.iter()
+    }
+
+    pub fn is_registered(&self, class: &GraniteTypes) -> bool {
+        self.classes
+            .iter()
+            .any(|existing| Self::same_class(existing, class))
+    }
+
+    /// Default data for every registered class in a category, in registration order
+    pub fn by_category(&self, category: ClassCategory) -> Vec<GraniteTypes> {
+        self.classes
+            .iter()
+            .filter(|class| class.category() == category)
+            .cloned()
+            .collect()
+    }
+
+    /// Categories that have at least one registered class, in UI order
+    pub fn categories(&self) -> Vec<ClassCategory> {
+        let mut categories: Vec<ClassCategory> =
+            self.classes.iter().map(|class| class.category()).collect();
+        categories.sort();
+        categories.dedup();
+        categories.sort_by_key(|category| self.category_order(*category));
+        categories
+    }
+
+    pub fn set_category_order(&mut self, category: ClassCategory, order: i32) {
+        self.category_order.insert(category, order);
+    }
+
+    pub fn category_order(&self, category: ClassCategory) -> i32 {
+        self.category_order
+            .get(&category)
+            .copied()
+            .unwrap_or_else(|| category.default_order())
+    }
+
+    /// Use an image from the assets folder as this class's icon, instead of its embedded one
+    pub fn set_icon_override(&mut self, class: &GraniteTypes, asset_path: impl Into<String>) {
+        self.icon_overrides
+            .insert(class.type_name(), asset_path.into());
+    }
+
+    pub fn icon_override(&self, class: &GraniteTypes) -> Option<&str> {
+        self.icon_overrides
+            .get(&class.type_name())
+            .map(|path| path.as_str())
+    }
+
+    /// Icon for the tree and viewport. The asset path override wins over embedded bytes
+    pub fn icon_handle(
+        &self,
+        class: &GraniteTypes,
+        asset_server: &AssetServer,
+    ) -> Option<Handle<Image>> {
+        match self.icon_override(class) {
+            Some(path) => Some(asset_server.load(path.to_string())),
+            None => class.get_icon_handle(),
+        }
+    }
+
+    /// Short type name for UI. Classes missing from the registry are flagged
+    pub fn type_abv(&self, class: &GraniteTypes) -> String {
+        if self.is_registered(class) {
+            class.type_abv()
+        } else {
+            format!("{} (unregistered)", class.type_abv())
+        }
+    }
+
+    fn same_class(a: &GraniteTypes, b: &GraniteTypes) -> bool {
+        match (a, b) {
+            (GraniteTypes::Custom(a), GraniteTypes::Custom(b)) => a.class_name == b.class_name,
+            _ => discriminant(a) == discriminant(b),
+        }
+    }
+}
+
+/// Register a class with its default data from a plugin's build
+pub fn register_class(app: &mut App, class: GraniteTypes) {
+    app.world_mut()
+        .get_resource_or_init::<GraniteClassRegistry>()
+        .register(class);
+}
+
+/// Order a category in the add entity menu, lower shows first
+pub fn register_class_category_order(app: &mut App, category: ClassCategory, order: i32) {
+    app.world_mut()
+        .get_resource_or_init::<GraniteClassRegistry>()
+        .set_category_order(category, order);
+}
+
+/// Give a class an icon from the assets folder, i.e. "icons/vehicle.png"
+pub fn register_class_icon(app: &mut App, class: GraniteTypes, asset_path: &str) {
+    app.world_mut()
+        .get_resource_or_init::<GraniteClassRegistry>()
+        .set_icon_override(&class, asset_path);
+}
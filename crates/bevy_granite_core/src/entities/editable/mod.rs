@@ -20,11 +20,15 @@ use serde::{Deserialize, Serialize};
 
 // Modules
 pub mod category;
+pub mod class_registry;
 pub mod definition;
 pub mod types;
 
 // Re-exports
 pub use category::ClassCategory;
+pub use class_registry::{
+    register_class, register_class_category_order, register_class_icon, GraniteClassRegistry,
+};
 pub use definition::GraniteType;
 pub use types::*;
 
@@ -40,13 +44,27 @@ pub struct RequestEntityUpdateFromClass<'w> {
     pub rectangle_brush: MessageWriter<'w, UserUpdatedRectBrushEvent>,
     pub obj: MessageWriter<'w, UserUpdatedOBJEvent>,
     pub empty: MessageWriter<'w, UserUpdatedEmptyEvent>,
+    pub anchor: MessageWriter<'w, UserUpdatedAnchorEvent>,
+    pub waypoint: MessageWriter<'w, UserUpdatedWaypointEvent>,
+    pub input_binding: MessageWriter<'w, UserUpdatedInputBindingEvent>,
+    pub force_volume: MessageWriter<'w, UserUpdatedForceVolumeEvent>,
+    pub ambience_zone: MessageWriter<'w, UserUpdatedAmbienceZoneEvent>,
+    pub audio_emitter: MessageWriter<'w, UserUpdatedAudioEmitter3DEvent>,
+    pub vfx_spawner: MessageWriter<'w, UserUpdatedVfxSpawnerEvent>,
+    pub camera_rail: MessageWriter<'w, UserUpdatedCameraRailEvent>,
+    pub weather: MessageWriter<'w, UserUpdatedWeatherEvent>,
+    pub world_environment: MessageWriter<'w, UserUpdatedWorldEnvironmentEvent>,
+    pub light_probe: MessageWriter<'w, UserUpdatedLightProbeEvent>,
+    pub spline_mesh: MessageWriter<'w, UserUpdatedSplineMeshEvent>,
+    pub spline_3d: MessageWriter<'w, UserUpdatedSpline3DEvent>,
+    pub custom: MessageWriter<'w, UserUpdatedCustomClassEvent>,
 }
 
 // ---------------------------------------------------------------------------------------
 
 // GraniteTypes represents all Granite entity "types" that are supported
 // This should be relatively trivial to expand with new types
-// If you add a new type ensure to add it under the enum and its all function
+// If you add a new type ensure to add it under the enum and register it with register_class in its plugin
 /// We use enum_dispatch for static polymorphism - i.e. all variants of our enum need same functions available to themselves, and exposed up a level - this saves us a tremendous amount of match arms in this enum
 #[enum_dispatch(GraniteType)]
 #[derive(Serialize, Reflect, Deserialize, PartialEq, Clone, Debug)]
@@ -57,37 +75,28 @@ pub enum GraniteTypes {
     SpotLightData(SpotLightData),
     DirLight(DirLight),
     Camera3D(Camera3D),
+    Weather(Weather),
+    WorldEnvironment(WorldEnvironment),
+    LightProbe(LightProbeData),
+    Anchor(Anchor),
+    Waypoint(Waypoint),
+    InputBinding(InputBinding),
+    ForceVolume(ForceVolume),
+    AmbienceZone(AmbienceZone),
+    AudioEmitter3D(AudioEmitter3D),
+    VfxSpawner(VfxSpawner),
+    CameraRail(CameraRail),
     RectBrush(RectBrush),
-    Unknown(Unknown), // Holds no real data
+    SplineMesh(SplineMesh),
+    Spline3D(Spline3D),
+    Custom(CustomClass), // Registered with #[derive(GraniteClass)]
+    Unknown(Unknown),    // Holds no real data
 }
 impl GraniteTypes {
-    // If you add a new custom type - add it here as well so its concretely known!!
-    // Used to get all available variants, not data
-    pub fn all() -> Vec<GraniteTypes> {
-        vec![
-            GraniteTypes::OBJ(Default::default()),
-            GraniteTypes::Empty(Default::default()),
-            GraniteTypes::PointLightData(Default::default()),
-            GraniteTypes::SpotLightData(Default::default()),
-            GraniteTypes::DirLight(Default::default()),
-            GraniteTypes::Camera3D(Default::default()),
-            GraniteTypes::RectBrush(Default::default()),
-            GraniteTypes::Unknown(Default::default()),
-        ]
-    }
-
     // Check if we are a known type - helpful for UI and what not
     pub fn is_known(&self) -> bool {
         !matches!(self, GraniteTypes::Unknown(_))
     }
-
-    // Return vector of all GraniteTypes variant categories. Used for UI
-    pub fn all_by_category(category: ClassCategory) -> Vec<GraniteTypes> {
-        Self::all()
-            .into_iter()
-            .filter(|class_type| class_type.category() == category)
-            .collect()
-    }
 }
 
 impl Default for GraniteTypes {
@@ -0,0 +1,221 @@
+use crate::{
+    entities::{
+        editable::{GraniteType, RequestEntityUpdateFromClass},
+        EntitySaveReadyData,
+    },
+    AvailableEditableMaterials, ClassCategory, PromptData,
+};
+use bevy::{
+    asset::{AssetServer, Assets},
+    ecs::{
+        entity::Entity,
+        message::Message,
+        system::{Commands, Res, ResMut},
+    },
+    math::Vec3,
+    mesh::Mesh,
+    pbr::StandardMaterial,
+    reflect::Reflect,
+    transform::components::{GlobalTransform, Transform},
+};
+use bevy_egui::egui;
+use serde::{Deserialize, Serialize};
+
+pub mod creation;
+pub mod plugin;
+pub mod ui;
+pub mod update_event;
+pub mod zones;
+
+pub use plugin::*;
+pub use update_event::*;
+pub use zones::*;
+
+/// Internal event thats called when user edits UI ambience zone variables
+#[derive(Message)]
+pub struct UserUpdatedAmbienceZoneEvent {
+    pub entity: Entity,
+    pub data: AmbienceZone,
+}
+
+#[derive(Serialize, Deserialize, Reflect, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AmbienceZoneShape {
+    #[default]
+    Sphere,
+    /// Turns with the entity
+    Box,
+}
+
+impl AmbienceZoneShape {
+    pub fn all() -> [AmbienceZoneShape; 2] {
+        [AmbienceZoneShape::Sphere, AmbienceZoneShape::Box]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            AmbienceZoneShape::Sphere => "Sphere",
+            AmbienceZoneShape::Box => "Box",
+        }
+    }
+}
+
+/// Reverb parameters for the audio backend, Granite only blends them
+#[derive(Serialize, Deserialize, Reflect, Debug, Clone, Copy, PartialEq)]
+pub struct ZoneReverb {
+    /// 0 to 1
+    pub room_size: f32,
+    /// 0 to 1, how fast high frequencies die out
+    pub damping: f32,
+    /// 0 to 1, reverb level against the dry sound
+    pub wet: f32,
+    /// Seconds
+    pub decay_time: f32,
+}
+
+impl Default for ZoneReverb {
+    fn default() -> Self {
+        Self {
+            room_size: 0.5,
+            damping: 0.5,
+            wet: 0.3,
+            decay_time: 1.5,
+        }
+    }
+}
+
+impl ZoneReverb {
+    /// No reverb at all
+    pub const DRY: ZoneReverb = ZoneReverb {
+        room_size: 0.0,
+        damping: 0.0,
+        wet: 0.0,
+        decay_time: 0.0,
+    };
+
+    pub fn lerp(&self, other: &ZoneReverb, t: f32) -> ZoneReverb {
+        let mix = |a: f32, b: f32| a + (b - a) * t;
+        ZoneReverb {
+            room_size: mix(self.room_size, other.room_size),
+            damping: mix(self.damping, other.damping),
+            wet: mix(self.wet, other.wet),
+            decay_time: mix(self.decay_time, other.decay_time),
+        }
+    }
+}
+
+/// Actual serialized class data thats stored inside IdentityData
+/// A volume with an ambient loop and reverb. Granite plays nothing itself,
+/// the audio backend reads the crossfaded mix from the AmbienceZones resource
+#[derive(Serialize, Deserialize, Reflect, Debug, Clone, PartialEq)]
+pub struct AmbienceZone {
+    pub shape: AmbienceZoneShape,
+    /// Sphere radius in world units, the entity scale doesn't change it
+    pub radius: f32,
+    /// Half size of the box in world units
+    pub half_extents: Vec3,
+    /// World units outside the shape over which the zone fades out
+    pub blend_distance: f32,
+    /// Seconds the zone takes to fade in or out once the listener enters or leaves
+    pub fade_time: f32,
+    /// Asset path of the looping ambient sound, empty for reverb only zones
+    pub ambient_sound: String,
+    pub volume: f32,
+    /// Where zones overlap, the reverb of the highest priority is used
+    pub priority: i32,
+    pub reverb: ZoneReverb,
+}
+
+impl Default for AmbienceZone {
+    fn default() -> Self {
+        Self {
+            shape: AmbienceZoneShape::Sphere,
+            radius: 10.0,
+            half_extents: Vec3::splat(5.0),
+            blend_distance: 2.0,
+            fade_time: 1.0,
+            ambient_sound: String::new(),
+            volume: 1.0,
+            priority: 0,
+            reverb: ZoneReverb::default(),
+        }
+    }
+}
+
+impl AmbienceZone {
+    /// World units from the shape's surface to a position, zero inside
+    pub fn distance_outside(&self, transform: &GlobalTransform, position: Vec3) -> f32 {
+        let (_, rotation, translation) = transform.to_scale_rotation_translation();
+        let local = rotation.inverse() * (position - translation);
+        match self.shape {
+            AmbienceZoneShape::Sphere => (local.length() - self.radius).max(0.0),
+            AmbienceZoneShape::Box => (local.abs() - self.half_extents).max(Vec3::ZERO).length(),
+        }
+    }
+
+    /// 1 inside the shape, fading to 0 across the blend distance
+    pub fn weight_at(&self, transform: &GlobalTransform, position: Vec3) -> f32 {
+        let distance = self.distance_outside(transform, position);
+        if distance <= 0.0 {
+            1.0
+        } else if self.blend_distance > 0.0 {
+            (1.0 - distance / self.blend_distance).max(0.0)
+        } else {
+            0.0
+        }
+    }
+}
+
+impl GraniteType for AmbienceZone {
+    fn type_name(&self) -> String {
+        "Ambience Zone".to_string()
+    }
+
+    fn type_abv(&self) -> String {
+        "Ambience".to_string()
+    }
+
+    fn category(&self) -> ClassCategory {
+        ClassCategory::Gameplay
+    }
+
+    fn get_embedded_icon_bytes(&self) -> Option<&'static [u8]> {
+        Some(include_bytes!("AmbienceZone.png"))
+    }
+
+    fn get_icon_filename(&self) -> Option<&'static str> {
+        Some("AmbienceZone.png")
+    }
+
+    fn spawn_from_new_identity(
+        &mut self,
+        commands: &mut Commands,
+        transform: Transform,
+        _standard_materials: ResMut<Assets<StandardMaterial>>,
+        _meshes: ResMut<Assets<Mesh>>,
+        _available_materials: ResMut<AvailableEditableMaterials>,
+        _asset_server: Res<AssetServer>,
+        _maybe_prompt_data: Option<PromptData>,
+    ) -> Entity {
+        AmbienceZone::spawn_from_new_identity(self, commands, transform)
+    }
+
+    fn spawn_from_save_data(
+        &self,
+        save_data: &EntitySaveReadyData,
+        commands: &mut Commands,
+        _standard_materials: &mut ResMut<Assets<StandardMaterial>>,
+        _meshes: &mut ResMut<Assets<Mesh>>,
+        _available_materials: &mut ResMut<AvailableEditableMaterials>,
+        _asset_server: &Res<AssetServer>,
+    ) -> Entity {
+        AmbienceZone::spawn_from_save_data(save_data, commands)
+    }
+
+    fn push_to_entity(&self, entity: Entity, request_update: &mut RequestEntityUpdateFromClass) {
+        self.push_to_entity(entity, request_update)
+    }
+
+    fn edit_via_ui(&mut self, ui: &mut egui::Ui, spacing: (f32, f32, f32)) -> bool {
+        self.edit_via_ui(ui, spacing)
+    }
+}
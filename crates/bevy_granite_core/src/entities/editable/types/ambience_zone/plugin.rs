@@ -0,0 +1,42 @@
+use super::{
+    update_ambience_zone_system, update_ambience_zones_system, AmbienceListener, AmbienceZone,
+    AmbienceZoneEntered, AmbienceZoneExited, AmbienceZones, UserUpdatedAmbienceZoneEvent,
+};
+use crate::{entities::BridgeTag, register_class, GraniteTypes};
+use bevy::app::{App, Plugin, PostUpdate, Update};
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::transform::TransformSystems;
+
+pub struct AmbienceZonePlugin;
+impl Plugin for AmbienceZonePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            //
+            // Event
+            //
+            .add_message::<UserUpdatedAmbienceZoneEvent>()
+            .add_message::<AmbienceZoneEntered>()
+            .add_message::<AmbienceZoneExited>()
+            //
+            // Register
+            //
+            .register_type::<AmbienceZone>()
+            .register_type::<AmbienceListener>()
+            .register_type_data::<AmbienceListener, BridgeTag>()
+            //
+            // Resources
+            //
+            .init_resource::<AmbienceZones>()
+            //
+            // Schedule system
+            //
+            .add_systems(Update, update_ambience_zone_system)
+            .add_systems(
+                PostUpdate,
+                update_ambience_zones_system.after(TransformSystems::Propagate),
+            );
+
+        // Class registry
+        register_class(app, GraniteTypes::AmbienceZone(Default::default()));
+    }
+}
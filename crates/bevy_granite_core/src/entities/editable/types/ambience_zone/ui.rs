@@ -0,0 +1,148 @@
+use super::{AmbienceZone, AmbienceZoneShape};
+use crate::{absolute_asset_to_rel, shared::asset_file_browser};
+use bevy_egui::egui;
+
+impl AmbienceZone {
+    /// Function to edit self's data via UI side panel
+    pub fn edit_via_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        // Small, Large, Normal
+        spacing: (f32, f32, f32),
+    ) -> bool {
+        let large_spacing = spacing.1;
+        let mut changed = false;
+        egui::Grid::new("ambience_zone_grid")
+            .num_columns(2)
+            .spacing([large_spacing, large_spacing])
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Shape:");
+                egui::ComboBox::from_id_salt("ambience_zone_shape")
+                    .selected_text(self.shape.name())
+                    .show_ui(ui, |ui| {
+                        for shape in AmbienceZoneShape::all() {
+                            changed |= ui
+                                .selectable_value(&mut self.shape, shape, shape.name())
+                                .changed();
+                        }
+                    });
+                ui.end_row();
+
+                match self.shape {
+                    AmbienceZoneShape::Sphere => {
+                        ui.label("Radius:");
+                        changed |= ui
+                            .add(
+                                egui::DragValue::new(&mut self.radius)
+                                    .speed(0.1)
+                                    .range(0.0..=f32::MAX),
+                            )
+                            .changed();
+                    }
+                    AmbienceZoneShape::Box => {
+                        ui.label("Half Extents:");
+                        ui.horizontal(|ui| {
+                            for value in [
+                                &mut self.half_extents.x,
+                                &mut self.half_extents.y,
+                                &mut self.half_extents.z,
+                            ] {
+                                changed |= ui
+                                    .add(
+                                        egui::DragValue::new(value)
+                                            .speed(0.1)
+                                            .range(0.0..=f32::MAX),
+                                    )
+                                    .changed();
+                            }
+                        });
+                    }
+                }
+                ui.end_row();
+
+                ui.label("Blend Distance:");
+                changed |= ui
+                    .add(
+                        egui::DragValue::new(&mut self.blend_distance)
+                            .speed(0.1)
+                            .range(0.0..=f32::MAX),
+                    )
+                    .on_hover_text("Distance outside the shape over which the zone fades out")
+                    .changed();
+                ui.end_row();
+
+                ui.label("Fade Time:");
+                changed |= ui
+                    .add(
+                        egui::DragValue::new(&mut self.fade_time)
+                            .speed(0.05)
+                            .range(0.0..=f32::MAX)
+                            .suffix(" s"),
+                    )
+                    .changed();
+                ui.end_row();
+
+                ui.label("Ambient Sound:");
+                ui.horizontal(|ui| {
+                    changed |= ui.text_edit_singleline(&mut self.ambient_sound).changed();
+                    if ui.button("📁").clicked() {
+                        if let Some(selected) = asset_file_browser(
+                            "audio".to_string(),
+                            vec!["ogg", "wav", "mp3", "flac"],
+                        ) {
+                            self.ambient_sound = absolute_asset_to_rel(selected).to_string();
+                            changed = true;
+                        }
+                    }
+                });
+                ui.end_row();
+
+                ui.label("Volume:");
+                changed |= ui
+                    .add(egui::Slider::new(&mut self.volume, 0.0..=2.0))
+                    .changed();
+                ui.end_row();
+
+                ui.label("Priority:");
+                changed |= ui
+                    .add(egui::DragValue::new(&mut self.priority))
+                    .on_hover_text("Where zones overlap, the highest priority reverb is used")
+                    .changed();
+                ui.end_row();
+            });
+
+        ui.add_space(large_spacing);
+        ui.collapsing("Reverb", |ui| {
+            let reverb = &mut self.reverb;
+            egui::Grid::new("ambience_zone_reverb_grid")
+                .num_columns(2)
+                .spacing([large_spacing, large_spacing])
+                .striped(true)
+                .show(ui, |ui| {
+                    for (label, value) in [
+                        ("Room Size:", &mut reverb.room_size),
+                        ("Damping:", &mut reverb.damping),
+                        ("Wet:", &mut reverb.wet),
+                    ] {
+                        ui.label(label);
+                        changed |= ui.add(egui::Slider::new(value, 0.0..=1.0)).changed();
+                        ui.end_row();
+                    }
+
+                    ui.label("Decay Time:");
+                    changed |= ui
+                        .add(
+                            egui::DragValue::new(&mut reverb.decay_time)
+                                .speed(0.05)
+                                .range(0.0..=30.0)
+                                .suffix(" s"),
+                        )
+                        .changed();
+                    ui.end_row();
+                });
+        });
+        ui.add_space(large_spacing);
+        changed
+    }
+}
@@ -0,0 +1,45 @@
+use crate::entities::editable::{RequestEntityUpdateFromClass, UserUpdatedAmbienceZoneEvent};
+use crate::entities::AmbienceZone;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::message::MessageReader;
+use bevy_granite_logging::{log, LogCategory, LogLevel, LogType};
+
+impl AmbienceZone {
+    pub fn push_to_entity(
+        &self,
+        entity: Entity,
+        request_update: &mut RequestEntityUpdateFromClass,
+    ) {
+        log!(
+            LogType::Editor,
+            LogLevel::Info,
+            LogCategory::Entity,
+            "Requesting ambience zone entity update"
+        );
+
+        request_update
+            .ambience_zone
+            .write(UserUpdatedAmbienceZoneEvent {
+                entity,
+                data: self.clone(),
+            });
+    }
+}
+
+/// The settings are read from IdentityData, so AmbienceZones picks edits up on its own
+pub fn update_ambience_zone_system(mut reader: MessageReader<UserUpdatedAmbienceZoneEvent>) {
+    for UserUpdatedAmbienceZoneEvent {
+        entity: requested_entity,
+        data: new,
+    } in reader.read()
+    {
+        log!(
+            LogType::Editor,
+            LogLevel::Info,
+            LogCategory::Entity,
+            "Heard ambience zone update event: {} is now a {} zone",
+            requested_entity,
+            new.shape.name()
+        );
+    }
+}
@@ -0,0 +1,185 @@
+use super::{AmbienceZone, ZoneReverb};
+use crate::{GraniteTypes, IdentityData};
+use bevy::{
+    camera::{Camera, Camera3d},
+    ecs::{
+        component::Component,
+        entity::Entity,
+        message::{Message, MessageWriter},
+        query::With,
+        resource::Resource,
+        system::{Query, Res, ResMut},
+    },
+    math::Vec3,
+    prelude::{
+        ReflectComponent, ReflectDefault, ReflectDeserialize, ReflectFromReflect, ReflectSerialize,
+    },
+    reflect::Reflect,
+    time::Time,
+    transform::components::GlobalTransform,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// zones.rs
+// Follows the listener through the ambience zones of the world and crossfades their weights over time
+// The audio backend plays the loops of AmbienceZones::mix at their volumes and applies AmbienceZones::reverb
+
+/// The entity ambience zones are heard from, usually the player or its camera
+/// Without one, the active 3D camera with the highest order listens
+#[derive(Reflect, Serialize, Deserialize, Debug, Clone, Component, Default, PartialEq)]
+#[reflect(Component, Serialize, Deserialize, Default, FromReflect)]
+pub struct AmbienceListener;
+
+/// Sent when the listener starts hearing a zone, on entering its blend distance
+#[derive(Message, Debug, Clone, Copy)]
+pub struct AmbienceZoneEntered(pub Entity);
+
+/// Sent when the listener leaves the blend distance of a zone, or the zone is removed while heard
+#[derive(Message, Debug, Clone, Copy)]
+pub struct AmbienceZoneExited(pub Entity);
+
+#[derive(Debug, Clone)]
+pub struct AmbienceZoneState {
+    pub entity: Entity,
+    pub zone: AmbienceZone,
+    pub transform: GlobalTransform,
+    /// Where the weight is fading to, from the listener position
+    pub target: f32,
+    /// Crossfaded weight, 0 to 1
+    pub weight: f32,
+}
+
+/// Every AmbienceZone entity in the world with its current weight, refreshed each frame after transforms propagate
+#[derive(Resource, Default, Debug, Clone)]
+pub struct AmbienceZones {
+    listener: Option<Vec3>,
+    zones: Vec<AmbienceZoneState>,
+}
+
+impl AmbienceZones {
+    pub fn listener(&self) -> Option<Vec3> {
+        self.listener
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &AmbienceZoneState> {
+        self.zones.iter()
+    }
+
+    pub fn weight(&self, entity: Entity) -> f32 {
+        self.zones
+            .iter()
+            .find(|state| state.entity == entity)
+            .map_or(0.0, |state| state.weight)
+    }
+
+    /// Zones with a sound that can be heard right now, with the volume to play it at
+    pub fn mix(&self) -> impl Iterator<Item = (Entity, &str, f32)> + '_ {
+        self.zones
+            .iter()
+            .filter(|state| state.weight > 0.0 && !state.zone.ambient_sound.is_empty())
+            .map(|state| {
+                (
+                    state.entity,
+                    state.zone.ambient_sound.as_str(),
+                    state.zone.volume * state.weight,
+                )
+            })
+    }
+
+    /// Reverb of the highest priority zones being heard, blended by weight and faded toward dry at their edges
+    pub fn reverb(&self) -> Option<ZoneReverb> {
+        let priority = self
+            .zones
+            .iter()
+            .filter(|state| state.weight > 0.0)
+            .map(|state| state.zone.priority)
+            .max()?;
+        let heard: Vec<&AmbienceZoneState> = self
+            .zones
+            .iter()
+            .filter(|state| state.weight > 0.0 && state.zone.priority == priority)
+            .collect();
+        let total: f32 = heard.iter().map(|state| state.weight).sum();
+        let mut reverb = ZoneReverb::DRY;
+        let mut blended = 0.0;
+        for state in heard {
+            blended += state.weight;
+            reverb = reverb.lerp(&state.zone.reverb, state.weight / blended);
+        }
+        Some(ZoneReverb::DRY.lerp(&reverb, total.min(1.0)))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.zones.is_empty()
+    }
+}
+
+pub fn update_ambience_zones_system(
+    time: Res<Time>,
+    mut ambience_zones: ResMut<AmbienceZones>,
+    listeners: Query<&GlobalTransform, With<AmbienceListener>>,
+    cameras: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    query: Query<(Entity, &IdentityData, &GlobalTransform)>,
+    mut entered: MessageWriter<AmbienceZoneEntered>,
+    mut exited: MessageWriter<AmbienceZoneExited>,
+) {
+    let listener = listeners
+        .iter()
+        .next()
+        .map(GlobalTransform::translation)
+        .or_else(|| {
+            cameras
+                .iter()
+                .filter(|(camera, _)| camera.is_active)
+                .max_by_key(|(camera, _)| camera.order)
+                .map(|(_, transform)| transform.translation())
+        });
+
+    let mut previous: HashMap<Entity, AmbienceZoneState> =
+        std::mem::take(&mut ambience_zones.zones)
+            .into_iter()
+            .map(|state| (state.entity, state))
+            .collect();
+    let mut zones: Vec<AmbienceZoneState> = query
+        .iter()
+        .filter_map(|(entity, identity, transform)| match &identity.class {
+            GraniteTypes::AmbienceZone(zone) => Some((entity, zone, transform)),
+            _ => None,
+        })
+        .map(|(entity, zone, transform)| {
+            let target = listener.map_or(0.0, |position| zone.weight_at(transform, position));
+            let (was_heard, weight) = previous
+                .remove(&entity)
+                .map_or((false, 0.0), |state| (state.target > 0.0, state.weight));
+            let heard = target > 0.0;
+            if heard && !was_heard {
+                entered.write(AmbienceZoneEntered(entity));
+            } else if !heard && was_heard {
+                exited.write(AmbienceZoneExited(entity));
+            }
+
+            let weight = if zone.fade_time > 0.0 {
+                let step = time.delta_secs() / zone.fade_time;
+                weight + (target - weight).clamp(-step, step)
+            } else {
+                target
+            };
+            AmbienceZoneState {
+                entity,
+                zone: zone.clone(),
+                transform: *transform,
+                target,
+                weight,
+            }
+        })
+        .collect();
+    for state in previous.into_values().filter(|state| state.target > 0.0) {
+        exited.write(AmbienceZoneExited(state.entity));
+    }
+    // Query order isn't stable, keep zones in a fixed order
+    zones.sort_by_key(|state| state.entity);
+
+    ambience_zones.listener = listener;
+    ambience_zones.zones = zones;
+}
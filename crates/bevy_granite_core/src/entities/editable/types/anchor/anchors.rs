@@ -0,0 +1,87 @@
+use crate::{GraniteTypes, IdentityData};
+use bevy::{
+    ecs::{
+        entity::Entity,
+        resource::Resource,
+        system::{Query, ResMut},
+    },
+    math::Vec3,
+    transform::components::GlobalTransform,
+};
+use std::collections::BTreeMap;
+
+/// Every Anchor entity in the world by anchor name, refreshed each frame after transforms propagate
+/// Names are not unique, i.e. several "patrol" anchors. `get` returns the first one
+#[derive(Resource, Default, Debug, Clone)]
+pub struct Anchors {
+    by_name: BTreeMap<String, Vec<(Entity, GlobalTransform)>>,
+}
+
+impl Anchors {
+    pub fn get(&self, name: &str) -> Option<Entity> {
+        self.get_all(name).first().map(|(entity, _)| *entity)
+    }
+
+    pub fn transform(&self, name: &str) -> Option<GlobalTransform> {
+        self.get_all(name).first().map(|(_, transform)| *transform)
+    }
+
+    pub fn position(&self, name: &str) -> Option<Vec3> {
+        self.transform(name)
+            .map(|transform| transform.translation())
+    }
+
+    /// Every anchor with this name
+    pub fn get_all(&self, name: &str) -> &[(Entity, GlobalTransform)] {
+        self.by_name
+            .get(name)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Anchors whose name starts with prefix, sorted by name. i.e. "patrol_" for patrol nodes
+    pub fn with_prefix<'a>(
+        &'a self,
+        prefix: &'a str,
+    ) -> impl Iterator<Item = (&'a str, Entity, GlobalTransform)> + 'a {
+        self.by_name
+            .range(prefix.to_string()..)
+            .take_while(move |(name, _)| name.starts_with(prefix))
+            .flat_map(|(name, anchors)| {
+                anchors
+                    .iter()
+                    .map(move |(entity, transform)| (name.as_str(), *entity, *transform))
+            })
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.by_name.keys().map(String::as_str)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.by_name.contains_key(name)
+    }
+}
+
+pub fn update_anchors_system(
+    mut anchors: ResMut<Anchors>,
+    query: Query<(Entity, &IdentityData, &GlobalTransform)>,
+) {
+    let mut by_name: BTreeMap<String, Vec<(Entity, GlobalTransform)>> = BTreeMap::new();
+    for (entity, identity, transform) in query.iter() {
+        if let GraniteTypes::Anchor(anchor) = &identity.class {
+            by_name
+                .entry(anchor.name.clone())
+                .or_default()
+                .push((entity, *transform));
+        }
+    }
+    // Query order isn't stable, keep duplicates in a fixed order
+    for anchors in by_name.values_mut() {
+        anchors.sort_by_key(|(entity, _)| *entity);
+    }
+
+    if anchors.by_name != by_name {
+        anchors.by_name = by_name;
+    }
+}
@@ -0,0 +1,114 @@
+use crate::{
+    entities::{
+        editable::{GraniteType, RequestEntityUpdateFromClass},
+        EntitySaveReadyData,
+    },
+    AvailableEditableMaterials, ClassCategory, PromptData,
+};
+use bevy::{
+    asset::{AssetServer, Assets},
+    ecs::{
+        entity::Entity,
+        message::Message,
+        system::{Commands, Res, ResMut},
+    },
+    mesh::Mesh,
+    pbr::StandardMaterial,
+    reflect::Reflect,
+    transform::components::Transform,
+};
+use bevy_egui::egui;
+use serde::{Deserialize, Serialize};
+
+pub mod anchors;
+pub mod creation;
+pub mod plugin;
+pub mod ui;
+pub mod update_event;
+
+pub use anchors::*;
+pub use plugin::*;
+pub use update_event::*;
+
+/// Internal event thats called when user edits UI anchor variable
+#[derive(Message)]
+pub struct UserUpdatedAnchorEvent {
+    pub entity: Entity,
+    pub data: Anchor,
+}
+
+/// Actual serialized class data thats stored inside IdentityData
+/// A named point game code can look up through the Anchors resource, i.e. "player_spawn"
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Reflect)]
+pub struct Anchor {
+    pub name: String,
+}
+
+impl Default for Anchor {
+    fn default() -> Self {
+        Self {
+            name: "anchor".to_string(),
+        }
+    }
+}
+
+impl Anchor {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into() }
+    }
+}
+
+impl GraniteType for Anchor {
+    fn type_name(&self) -> String {
+        "Anchor".to_string()
+    }
+
+    fn type_abv(&self) -> String {
+        "Anchor".to_string()
+    }
+
+    fn category(&self) -> ClassCategory {
+        ClassCategory::Gameplay
+    }
+
+    fn get_embedded_icon_bytes(&self) -> Option<&'static [u8]> {
+        Some(include_bytes!("Anchor.png"))
+    }
+
+    fn get_icon_filename(&self) -> Option<&'static str> {
+        Some("Anchor.png")
+    }
+
+    fn spawn_from_new_identity(
+        &mut self,
+        commands: &mut Commands,
+        transform: Transform,
+        _standard_materials: ResMut<Assets<StandardMaterial>>,
+        _meshes: ResMut<Assets<Mesh>>,
+        _available_materials: ResMut<AvailableEditableMaterials>,
+        _asset_server: Res<AssetServer>,
+        _maybe_prompt_data: Option<PromptData>,
+    ) -> Entity {
+        Anchor::spawn_from_new_identity(self, commands, transform)
+    }
+
+    fn spawn_from_save_data(
+        &self,
+        save_data: &EntitySaveReadyData,
+        commands: &mut Commands,
+        _standard_materials: &mut ResMut<Assets<StandardMaterial>>,
+        _meshes: &mut ResMut<Assets<Mesh>>,
+        _available_materials: &mut ResMut<AvailableEditableMaterials>,
+        _asset_server: &Res<AssetServer>,
+    ) -> Entity {
+        Anchor::spawn_from_save_data(save_data, commands)
+    }
+
+    fn push_to_entity(&self, entity: Entity, request_update: &mut RequestEntityUpdateFromClass) {
+        self.push_to_entity(entity, request_update)
+    }
+
+    fn edit_via_ui(&mut self, ui: &mut egui::Ui, spacing: (f32, f32, f32)) -> bool {
+        self.edit_via_ui(ui, spacing)
+    }
+}
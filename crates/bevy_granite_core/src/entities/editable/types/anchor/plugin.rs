@@ -0,0 +1,35 @@
+use super::{update_anchor_system, update_anchors_system, Anchor, Anchors, UserUpdatedAnchorEvent};
+use crate::{register_class, GraniteTypes};
+use bevy::app::{App, Plugin, PostUpdate, Update};
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::transform::TransformSystems;
+
+pub struct AnchorPlugin;
+impl Plugin for AnchorPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            //
+            // Event
+            //
+            .add_message::<UserUpdatedAnchorEvent>()
+            //
+            // Register
+            //
+            .register_type::<Anchor>()
+            //
+            // Resources
+            //
+            .init_resource::<Anchors>()
+            //
+            // Schedule system
+            //
+            .add_systems(Update, update_anchor_system)
+            .add_systems(
+                PostUpdate,
+                update_anchors_system.after(TransformSystems::Propagate),
+            );
+
+        // Class registry
+        register_class(app, GraniteTypes::Anchor(Default::default()));
+    }
+}
@@ -0,0 +1,26 @@
+use super::Anchor;
+use bevy_egui::egui;
+
+impl Anchor {
+    /// Function to edit self's data via UI side panel
+    pub fn edit_via_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        // Small, Large, Normal
+        spacing: (f32, f32, f32),
+    ) -> bool {
+        let large_spacing = spacing.1;
+        let mut changed = false;
+        egui::Grid::new("anchor_grid")
+            .num_columns(2)
+            .spacing([large_spacing, large_spacing])
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Anchor Name:");
+                changed |= ui.text_edit_singleline(&mut self.name).changed();
+                ui.end_row();
+            });
+        ui.add_space(large_spacing);
+        changed
+    }
+}
@@ -0,0 +1,43 @@
+use crate::entities::editable::{RequestEntityUpdateFromClass, UserUpdatedAnchorEvent};
+use crate::entities::Anchor;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::message::MessageReader;
+use bevy_granite_logging::{log, LogCategory, LogLevel, LogType};
+
+impl Anchor {
+    pub fn push_to_entity(
+        &self,
+        entity: Entity,
+        request_update: &mut RequestEntityUpdateFromClass,
+    ) {
+        log!(
+            LogType::Editor,
+            LogLevel::Info,
+            LogCategory::Entity,
+            "Requesting anchor entity update"
+        );
+
+        request_update.anchor.write(UserUpdatedAnchorEvent {
+            entity,
+            data: self.clone(),
+        });
+    }
+}
+
+/// The name is read from IdentityData, so Anchors picks renames up on its own
+pub fn update_anchor_system(mut reader: MessageReader<UserUpdatedAnchorEvent>) {
+    for UserUpdatedAnchorEvent {
+        entity: requested_entity,
+        data: new,
+    } in reader.read()
+    {
+        log!(
+            LogType::Editor,
+            LogLevel::Info,
+            LogCategory::Entity,
+            "Heard anchor update event: {} is now '{}'",
+            requested_entity,
+            new.name
+        );
+    }
+}
@@ -0,0 +1,240 @@
+use super::AudioEmitter3D;
+use crate::{AmbienceListener, GraniteTypes, IdentityData};
+use bevy::{
+    camera::{Camera, Camera3d},
+    ecs::{
+        entity::Entity,
+        message::{Message, MessageWriter},
+        query::With,
+        resource::Resource,
+        system::{Query, ResMut},
+    },
+    math::Vec3,
+    transform::components::GlobalTransform,
+};
+use std::collections::{HashMap, HashSet};
+
+// emitters.rs
+// Works out how loud every audio emitter is heard from the listener, after transforms propagate
+// The audio backend plays AudioEmitters::playing at their volumes, and AudioEmitterPreview starts and stops editor previews
+// AudioMonitor mutes and solos previews in the editor, a muted preview counts as stopped
+
+/// Sent when the preview of an emitter starts or stops in the editor, or a previewing emitter is removed
+#[derive(Message, Debug, Clone, Copy)]
+pub struct AudioEmitterPreview {
+    pub entity: Entity,
+    pub playing: bool,
+}
+
+/// Editor monitoring of audio previews, so one emitter can be auditioned without the rest of the mix
+/// Only previews are affected, sounds playing in game ignore it
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct AudioMonitor {
+    /// Silence every preview
+    pub mute_all: bool,
+    pub muted: HashSet<Entity>,
+    /// Only this emitter's preview is heard
+    pub solo: Option<Entity>,
+    /// Master preview volume, 0 to 1
+    pub volume: f32,
+}
+
+impl Default for AudioMonitor {
+    fn default() -> Self {
+        Self {
+            mute_all: false,
+            muted: HashSet::new(),
+            solo: None,
+            volume: 1.0,
+        }
+    }
+}
+
+impl AudioMonitor {
+    /// Whether this emitter's preview can be heard
+    pub fn is_audible(&self, entity: Entity) -> bool {
+        !self.mute_all
+            && !self.muted.contains(&entity)
+            && self.solo.is_none_or(|solo| solo == entity)
+    }
+
+    pub fn is_muted(&self, entity: Entity) -> bool {
+        self.muted.contains(&entity)
+    }
+
+    pub fn is_soloed(&self, entity: Entity) -> bool {
+        self.solo == Some(entity)
+    }
+
+    pub fn toggle_mute(&mut self, entity: Entity) {
+        if !self.muted.remove(&entity) {
+            self.muted.insert(entity);
+        }
+    }
+
+    /// Solo an emitter, or clear the solo if it already is
+    pub fn toggle_solo(&mut self, entity: Entity) {
+        self.solo = if self.is_soloed(entity) {
+            None
+        } else {
+            Some(entity)
+        };
+    }
+
+    /// Clears the mutes and the solo, the master volume is kept
+    pub fn reset(&mut self) {
+        self.mute_all = false;
+        self.muted.clear();
+        self.solo = None;
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AudioEmitterState {
+    pub entity: Entity,
+    pub emitter: AudioEmitter3D,
+    pub position: Vec3,
+    /// World units to the listener, None without one
+    pub distance: Option<f32>,
+    /// Falloff at the listener's distance, 0 to 1
+    pub gain: f32,
+    /// False while the AudioMonitor mutes its preview
+    pub audible: bool,
+}
+
+impl AudioEmitterState {
+    /// Volume to play the sound at right now
+    pub fn volume(&self) -> f32 {
+        self.emitter.volume * self.gain
+    }
+
+    /// Preview is playing and not muted in the editor
+    pub fn previewing(&self) -> bool {
+        self.emitter.previewing && self.audible
+    }
+}
+
+/// Every AudioEmitter3D entity in the world with how loud it is heard, refreshed each frame after transforms propagate
+/// Heard from the same listener as ambience zones
+#[derive(Resource, Default, Debug, Clone)]
+pub struct AudioEmitters {
+    listener: Option<Vec3>,
+    emitters: Vec<AudioEmitterState>,
+    preview_volume: f32,
+}
+
+impl AudioEmitters {
+    pub fn listener(&self) -> Option<Vec3> {
+        self.listener
+    }
+
+    /// Master volume of editor previews from the AudioMonitor, multiply it with `state.volume()` when previewing
+    pub fn preview_volume(&self) -> f32 {
+        self.preview_volume
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &AudioEmitterState> {
+        self.emitters.iter()
+    }
+
+    pub fn get(&self, entity: Entity) -> Option<&AudioEmitterState> {
+        self.emitters.iter().find(|state| state.entity == entity)
+    }
+
+    /// Emitters with a sound that should be playing, autoplay ones in game and previews the AudioMonitor lets through in the editor
+    pub fn playing(&self, in_editor: bool) -> impl Iterator<Item = &AudioEmitterState> + '_ {
+        self.emitters.iter().filter(move |state| {
+            let emitter = &state.emitter;
+            !emitter.sound.is_empty()
+                && if in_editor {
+                    state.previewing()
+                } else {
+                    emitter.autoplay
+                }
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.emitters.is_empty()
+    }
+}
+
+pub fn update_audio_emitters_system(
+    mut audio_emitters: ResMut<AudioEmitters>,
+    mut monitor: ResMut<AudioMonitor>,
+    listeners: Query<&GlobalTransform, With<AmbienceListener>>,
+    cameras: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    query: Query<(Entity, &IdentityData, &GlobalTransform)>,
+    mut preview: MessageWriter<AudioEmitterPreview>,
+) {
+    let listener = listeners
+        .iter()
+        .next()
+        .map(GlobalTransform::translation)
+        .or_else(|| {
+            cameras
+                .iter()
+                .filter(|(camera, _)| camera.is_active)
+                .max_by_key(|(camera, _)| camera.order)
+                .map(|(_, transform)| transform.translation())
+        });
+
+    let mut previous: HashMap<Entity, AudioEmitterState> =
+        std::mem::take(&mut audio_emitters.emitters)
+            .into_iter()
+            .map(|state| (state.entity, state))
+            .collect();
+    let mut emitters: Vec<AudioEmitterState> = query
+        .iter()
+        .filter_map(|(entity, identity, transform)| match &identity.class {
+            GraniteTypes::AudioEmitter3D(emitter) => Some((entity, emitter, transform)),
+            _ => None,
+        })
+        .map(|(entity, emitter, transform)| {
+            // Muting or soloing starts and stops previews like the Preview button does
+            let audible = monitor.is_audible(entity);
+            let previewing = emitter.previewing && audible;
+            let was_previewing = previous
+                .remove(&entity)
+                .is_some_and(|state| state.previewing());
+            if previewing != was_previewing {
+                preview.write(AudioEmitterPreview {
+                    entity,
+                    playing: previewing,
+                });
+            }
+
+            let position = transform.translation();
+            let distance = listener.map(|listener| listener.distance(position));
+            AudioEmitterState {
+                entity,
+                emitter: emitter.clone(),
+                position,
+                distance,
+                gain: distance.map_or(0.0, |distance| emitter.gain_at(distance)),
+                audible,
+            }
+        })
+        .collect();
+    for state in previous.into_values().filter(AudioEmitterState::previewing) {
+        preview.write(AudioEmitterPreview {
+            entity: state.entity,
+            playing: false,
+        });
+    }
+    // Query order isn't stable, keep emitters in a fixed order
+    emitters.sort_by_key(|state| state.entity);
+
+    // Forget removed emitters, only touching the monitor when something changed
+    let removed = |entity: &Entity| !emitters.iter().any(|state| state.entity == *entity);
+    if monitor.solo.as_ref().is_some_and(removed) {
+        monitor.solo = None;
+    }
+    if monitor.muted.iter().any(removed) {
+        monitor.muted.retain(|entity| !removed(entity));
+    }
+
+    audio_emitters.listener = listener;
+    audio_emitters.emitters = emitters;
+    audio_emitters.preview_volume = monitor.volume.clamp(0.0, 1.0);
+}
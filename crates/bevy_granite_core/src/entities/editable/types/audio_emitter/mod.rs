@@ -0,0 +1,179 @@
+use crate::{
+    entities::{
+        editable::{GraniteType, RequestEntityUpdateFromClass},
+        EntitySaveReadyData,
+    },
+    AvailableEditableMaterials, ClassCategory, PromptData,
+};
+use bevy::{
+    asset::{AssetServer, Assets},
+    ecs::{
+        entity::Entity,
+        message::Message,
+        system::{Commands, Res, ResMut},
+    },
+    mesh::Mesh,
+    pbr::StandardMaterial,
+    reflect::Reflect,
+    transform::components::Transform,
+};
+use bevy_egui::egui;
+use serde::{Deserialize, Serialize};
+
+pub mod creation;
+pub mod emitters;
+pub mod plugin;
+pub mod ui;
+pub mod update_event;
+
+pub use emitters::*;
+pub use plugin::*;
+pub use update_event::*;
+
+/// Internal event thats called when user edits UI audio emitter variables
+#[derive(Message)]
+pub struct UserUpdatedAudioEmitter3DEvent {
+    pub entity: Entity,
+    pub data: AudioEmitter3D,
+}
+
+/// How the volume drops between the min and max distance
+#[derive(Serialize, Deserialize, Reflect, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AudioRolloff {
+    /// Straight line down to silence at the max distance
+    #[default]
+    Linear,
+    /// Halves each time the distance doubles, like a real sound. Cut off at the max distance
+    Inverse,
+    /// Drops faster than inverse, for sounds that should stay close. Cut off at the max distance
+    Exponential,
+}
+
+impl AudioRolloff {
+    pub fn all() -> [AudioRolloff; 3] {
+        [
+            AudioRolloff::Linear,
+            AudioRolloff::Inverse,
+            AudioRolloff::Exponential,
+        ]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            AudioRolloff::Linear => "Linear",
+            AudioRolloff::Inverse => "Inverse",
+            AudioRolloff::Exponential => "Exponential",
+        }
+    }
+}
+
+/// Actual serialized class data thats stored inside IdentityData
+/// A positional sound. Granite plays nothing itself,
+/// the audio backend reads the volume each emitter is heard at from the AudioEmitters resource
+#[derive(Serialize, Deserialize, Reflect, Debug, Clone, PartialEq)]
+pub struct AudioEmitter3D {
+    /// Asset path of the sound
+    pub sound: String,
+    pub volume: f32,
+    pub looping: bool,
+    /// Start playing when spawned in game, the editor only plays previews
+    pub autoplay: bool,
+    pub rolloff: AudioRolloff,
+    /// Heard at full volume within this many world units
+    pub min_distance: f32,
+    /// Silent beyond this many world units
+    pub max_distance: f32,
+    /// Preview playing in the editor, never saved
+    #[serde(skip)]
+    pub previewing: bool,
+}
+
+impl Default for AudioEmitter3D {
+    fn default() -> Self {
+        Self {
+            sound: String::new(),
+            volume: 1.0,
+            looping: true,
+            autoplay: true,
+            rolloff: AudioRolloff::Linear,
+            min_distance: 1.0,
+            max_distance: 20.0,
+            previewing: false,
+        }
+    }
+}
+
+impl AudioEmitter3D {
+    /// 1 within the min distance, falling off to 0 at the max distance
+    pub fn gain_at(&self, distance: f32) -> f32 {
+        let min_distance = self.min_distance.max(f32::EPSILON);
+        if distance <= min_distance {
+            return 1.0;
+        }
+        if distance >= self.max_distance {
+            return 0.0;
+        }
+        match self.rolloff {
+            AudioRolloff::Linear => {
+                1.0 - (distance - min_distance) / (self.max_distance - min_distance)
+            }
+            AudioRolloff::Inverse => min_distance / distance,
+            AudioRolloff::Exponential => (min_distance / distance).powi(2),
+        }
+    }
+}
+
+impl GraniteType for AudioEmitter3D {
+    fn type_name(&self) -> String {
+        "Audio Emitter".to_string()
+    }
+
+    fn type_abv(&self) -> String {
+        "Audio".to_string()
+    }
+
+    fn category(&self) -> ClassCategory {
+        ClassCategory::Gameplay
+    }
+
+    fn get_embedded_icon_bytes(&self) -> Option<&'static [u8]> {
+        Some(include_bytes!("AudioEmitter3D.png"))
+    }
+
+    fn get_icon_filename(&self) -> Option<&'static str> {
+        Some("AudioEmitter3D.png")
+    }
+
+    fn spawn_from_new_identity(
+        &mut self,
+        commands: &mut Commands,
+        transform: Transform,
+        _standard_materials: ResMut<Assets<StandardMaterial>>,
+        _meshes: ResMut<Assets<Mesh>>,
+        _available_materials: ResMut<AvailableEditableMaterials>,
+        _asset_server: Res<AssetServer>,
+        _maybe_prompt_data: Option<PromptData>,
+    ) -> Entity {
+        AudioEmitter3D::spawn_from_new_identity(self, commands, transform)
+    }
+
+    fn spawn_from_save_data(
+        &self,
+        save_data: &EntitySaveReadyData,
+        commands: &mut Commands,
+        _standard_materials: &mut ResMut<Assets<StandardMaterial>>,
+        _meshes: &mut ResMut<Assets<Mesh>>,
+        _available_materials: &mut ResMut<AvailableEditableMaterials>,
+        _asset_server: &Res<AssetServer>,
+    ) -> Entity {
+        AudioEmitter3D::spawn_from_save_data(save_data, commands)
+    }
+
+    fn push_to_entity(&self, entity: Entity, request_update: &mut RequestEntityUpdateFromClass) {
+        self.push_to_entity(entity, request_update)
+    }
+
+    fn edit_via_ui(&mut self, ui: &mut egui::Ui, spacing: (f32, f32, f32)) -> bool {
+        self.edit_via_ui(ui, spacing)
+    }
+}
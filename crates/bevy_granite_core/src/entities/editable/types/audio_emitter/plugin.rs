@@ -0,0 +1,41 @@
+use super::{
+    update_audio_emitter_system, update_audio_emitters_system, AudioEmitter3D, AudioEmitterPreview,
+    AudioEmitters, AudioMonitor, AudioRolloff, UserUpdatedAudioEmitter3DEvent,
+};
+use crate::{register_class, GraniteTypes};
+use bevy::app::{App, Plugin, PostUpdate, Update};
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::transform::TransformSystems;
+
+pub struct AudioEmitter3DPlugin;
+impl Plugin for AudioEmitter3DPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            //
+            // Event
+            //
+            .add_message::<UserUpdatedAudioEmitter3DEvent>()
+            .add_message::<AudioEmitterPreview>()
+            //
+            // Register
+            //
+            .register_type::<AudioEmitter3D>()
+            .register_type::<AudioRolloff>()
+            //
+            // Resources
+            //
+            .init_resource::<AudioEmitters>()
+            .init_resource::<AudioMonitor>()
+            //
+            // Schedule system
+            //
+            .add_systems(Update, update_audio_emitter_system)
+            .add_systems(
+                PostUpdate,
+                update_audio_emitters_system.after(TransformSystems::Propagate),
+            );
+
+        // Class registry
+        register_class(app, GraniteTypes::AudioEmitter3D(Default::default()));
+    }
+}
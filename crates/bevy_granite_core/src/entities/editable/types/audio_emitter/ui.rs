@@ -0,0 +1,104 @@
+use super::{AudioEmitter3D, AudioRolloff};
+use crate::{absolute_asset_to_rel, shared::asset_file_browser};
+use bevy_egui::egui;
+
+impl AudioEmitter3D {
+    /// Function to edit self's data via UI side panel
+    pub fn edit_via_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        // Small, Large, Normal
+        spacing: (f32, f32, f32),
+    ) -> bool {
+        let large_spacing = spacing.1;
+        let mut changed = false;
+        egui::Grid::new("audio_emitter_grid")
+            .num_columns(2)
+            .spacing([large_spacing, large_spacing])
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Sound:");
+                ui.horizontal(|ui| {
+                    changed |= ui.text_edit_singleline(&mut self.sound).changed();
+                    if ui.button("📁").clicked() {
+                        if let Some(selected) = asset_file_browser(
+                            "audio".to_string(),
+                            vec!["ogg", "wav", "mp3", "flac"],
+                        ) {
+                            self.sound = absolute_asset_to_rel(selected).to_string();
+                            changed = true;
+                        }
+                    }
+                });
+                ui.end_row();
+
+                ui.label("Volume:");
+                changed |= ui
+                    .add(egui::Slider::new(&mut self.volume, 0.0..=2.0))
+                    .changed();
+                ui.end_row();
+
+                ui.label("Looping:");
+                changed |= ui.checkbox(&mut self.looping, "").changed();
+                ui.end_row();
+
+                ui.label("Autoplay:");
+                changed |= ui
+                    .checkbox(&mut self.autoplay, "")
+                    .on_hover_text("Start playing when spawned in game")
+                    .changed();
+                ui.end_row();
+
+                ui.label("Rolloff:");
+                egui::ComboBox::from_id_salt("audio_emitter_rolloff")
+                    .selected_text(self.rolloff.name())
+                    .show_ui(ui, |ui| {
+                        for rolloff in AudioRolloff::all() {
+                            changed |= ui
+                                .selectable_value(&mut self.rolloff, rolloff, rolloff.name())
+                                .changed();
+                        }
+                    });
+                ui.end_row();
+
+                ui.label("Min Distance:");
+                changed |= ui
+                    .add(
+                        egui::DragValue::new(&mut self.min_distance)
+                            .speed(0.1)
+                            .range(0.0..=self.max_distance),
+                    )
+                    .on_hover_text("Heard at full volume within this distance")
+                    .changed();
+                ui.end_row();
+
+                ui.label("Max Distance:");
+                changed |= ui
+                    .add(
+                        egui::DragValue::new(&mut self.max_distance)
+                            .speed(0.1)
+                            .range(self.min_distance..=f32::MAX),
+                    )
+                    .on_hover_text("Silent beyond this distance")
+                    .changed();
+                ui.end_row();
+            });
+
+        ui.add_space(large_spacing);
+        let label = if self.previewing {
+            "⏹ Stop Preview"
+        } else {
+            "▶ Preview"
+        };
+        if ui
+            .add_enabled(!self.sound.is_empty(), egui::Button::new(label))
+            .on_hover_text("Play the sound in the editor, heard from the viewport camera")
+            .clicked()
+        {
+            self.previewing = !self.previewing;
+            changed = true;
+        }
+        ui.add_space(large_spacing);
+        changed
+    }
+}
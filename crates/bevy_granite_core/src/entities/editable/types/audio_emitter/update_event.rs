@@ -0,0 +1,45 @@
+use crate::entities::editable::{RequestEntityUpdateFromClass, UserUpdatedAudioEmitter3DEvent};
+use crate::entities::AudioEmitter3D;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::message::MessageReader;
+use bevy_granite_logging::{log, LogCategory, LogLevel, LogType};
+
+impl AudioEmitter3D {
+    pub fn push_to_entity(
+        &self,
+        entity: Entity,
+        request_update: &mut RequestEntityUpdateFromClass,
+    ) {
+        log!(
+            LogType::Editor,
+            LogLevel::Info,
+            LogCategory::Entity,
+            "Requesting audio emitter entity update"
+        );
+
+        request_update
+            .audio_emitter
+            .write(UserUpdatedAudioEmitter3DEvent {
+                entity,
+                data: self.clone(),
+            });
+    }
+}
+
+/// The settings are read from IdentityData, so AudioEmitters picks edits and previews up on its own
+pub fn update_audio_emitter_system(mut reader: MessageReader<UserUpdatedAudioEmitter3DEvent>) {
+    for UserUpdatedAudioEmitter3DEvent {
+        entity: requested_entity,
+        data: new,
+    } in reader.read()
+    {
+        log!(
+            LogType::Editor,
+            LogLevel::Info,
+            LogCategory::Entity,
+            "Heard audio emitter update event: {} plays '{}'",
+            requested_entity,
+            new.sound
+        );
+    }
+}
@@ -22,6 +22,7 @@ use serde::{Deserialize, Serialize};
 
 pub mod creation;
 pub mod plugin;
+pub mod quality;
 pub mod ui;
 pub mod update_event;
 
@@ -42,8 +43,8 @@ pub struct UserUpdatedCamera3DEvent {
 #[derive(Serialize, Deserialize, Reflect, Debug, Clone, PartialEq)]
 pub struct Camera3D {
     pub is_active: bool,
-    pub order: isize, // Camera render order - higher values render on top
-    pub dither: bool, // Enable dithering to reduce banding artifacts
+    pub order: isize,    // Camera render order - higher values render on top
+    pub dither: bool,    // Enable dithering to reduce banding artifacts
     pub has_bloom: bool, // Enable bloom effect for HDR lighting
     pub has_volumetric_fog: bool, // if true, our next update even will insert volumetric fog settings
     pub has_atmosphere: bool,     // if true, our next update event will insert atmosphere settings
@@ -1,5 +1,9 @@
-use super::{update_camera_3d_system, UserUpdatedCamera3DEvent, AtmosphereSettings};
-use crate::Camera3D;
+use super::{
+    quality::camera_3d_quality_rule, update_camera_3d_system, AtmosphereSettings,
+    UserUpdatedCamera3DEvent,
+};
+use crate::{entities::quality::QualityRuleAppExt, Camera3D};
+use crate::{register_class, GraniteTypes};
 use bevy::app::{App, Plugin, Update};
 
 pub struct Camera3DPlugin;
@@ -18,6 +22,13 @@ impl Plugin for Camera3DPlugin {
             //
             // Schedule system
             //
-            .add_systems(Update, update_camera_3d_system);
+            .add_systems(Update, update_camera_3d_system)
+            //
+            // Quality
+            //
+            .add_quality_rule("Camera 3D", camera_3d_quality_rule);
+
+        // Class registry
+        register_class(app, GraniteTypes::Camera3D(Default::default()));
     }
-}
\ No newline at end of file
+}
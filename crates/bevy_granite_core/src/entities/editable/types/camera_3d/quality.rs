@@ -0,0 +1,75 @@
+use super::{BloomCompositeMode, BloomSettings};
+use crate::{entities::quality::QualityLevels, GraniteTypes, IdentityData};
+use bevy::{
+    ecs::{entity::Entity, world::World},
+    light::VolumetricFog as VolumetricFogSettings,
+    math::{UVec2, UVec3},
+    pbr::AtmosphereSettings as BevyAtmosphereSettings,
+    post_process::bloom::{Bloom, BloomCompositeMode as BevyBloomCompositeMode},
+};
+
+// quality.rs
+// Quality rule of Camera 3D: bloom intensity, volumetric fog steps and atmosphere lookup texture sizes
+// Everything is rebuilt from the authored class data, so the scaled values never compound or end up saved
+
+fn scale_2d(size: (u32, u32), scale: f32) -> UVec2 {
+    UVec2::new(
+        QualityLevels::scale(size.0, scale),
+        QualityLevels::scale(size.1, scale),
+    )
+}
+
+fn authored_bloom(settings: Option<&BloomSettings>) -> Bloom {
+    let Some(settings) = settings else {
+        return Bloom::default();
+    };
+    Bloom {
+        intensity: settings.intensity,
+        low_frequency_boost: settings.low_frequency_boost,
+        low_frequency_boost_curvature: settings.low_frequency_boost_curvature,
+        high_pass_frequency: settings.high_pass_frequency,
+        composite_mode: match settings.composite_mode {
+            BloomCompositeMode::EnergyConserving => BevyBloomCompositeMode::EnergyConserving,
+            BloomCompositeMode::Additive => BevyBloomCompositeMode::Additive,
+        },
+        ..Default::default()
+    }
+}
+
+pub fn camera_3d_quality_rule(world: &mut World, entity: Entity, levels: &QualityLevels) {
+    let Some(GraniteTypes::Camera3D(camera)) = world
+        .get::<IdentityData>(entity)
+        .map(|identity| identity.class.clone())
+    else {
+        return;
+    };
+    let mut entity = world.entity_mut(entity);
+
+    // Bloom comes back when the quality goes up again
+    if camera.has_bloom && levels.bloom > 0.0 {
+        let mut bloom = authored_bloom(camera.bloom_settings.as_ref());
+        bloom.intensity *= levels.bloom;
+        entity.insert(bloom);
+    } else {
+        entity.remove::<Bloom>();
+    }
+
+    if let Some(mut fog) = entity.get_mut::<VolumetricFogSettings>() {
+        let authored = camera.volumetric_fog_settings.unwrap_or_default();
+        fog.step_count = QualityLevels::scale(authored.step_count, levels.fog_steps);
+    }
+
+    if let Some(mut settings) = entity.get_mut::<BevyAtmosphereSettings>() {
+        let authored = camera.atmosphere_settings.unwrap_or_default();
+        let scale = levels.atmosphere_lut;
+        settings.transmittance_lut_size = scale_2d(authored.transmittance_lut_size, scale);
+        settings.multiscattering_lut_size = scale_2d(authored.multiscattering_lut_size, scale);
+        settings.sky_view_lut_size = scale_2d(authored.sky_view_lut_size, scale);
+        let (x, y, z) = authored.aerial_view_lut_size;
+        settings.aerial_view_lut_size = UVec3::new(
+            QualityLevels::scale(x, scale),
+            QualityLevels::scale(y, scale),
+            QualityLevels::scale(z, scale),
+        );
+    }
+}
@@ -0,0 +1,339 @@
+use super::spline_mesh::{sample_spline, SplinePoint};
+use crate::{
+    entities::{
+        editable::{GraniteType, RequestEntityUpdateFromClass},
+        EntitySaveReadyData,
+    },
+    AvailableEditableMaterials, ClassCategory, PromptData,
+};
+use bevy::{
+    asset::{AssetServer, Assets},
+    ecs::{
+        entity::Entity,
+        message::Message,
+        system::{Commands, Res, ResMut},
+    },
+    math::Vec3,
+    mesh::Mesh,
+    pbr::StandardMaterial,
+    reflect::Reflect,
+    transform::components::{GlobalTransform, Transform},
+};
+use bevy_egui::egui;
+use serde::{Deserialize, Serialize};
+
+pub mod creation;
+pub mod player;
+pub mod plugin;
+pub mod ui;
+pub mod update_event;
+
+pub use player::*;
+pub use plugin::*;
+pub use update_event::*;
+
+/// Internal event thats called when user edits UI camera rail variables
+#[derive(Message)]
+pub struct UserUpdatedCameraRailEvent {
+    pub entity: Entity,
+    pub data: CameraRail,
+}
+
+/// Control point of a camera rail, relative to the entity
+#[derive(Serialize, Deserialize, Reflect, Debug, Clone, Copy, PartialEq)]
+pub struct CameraRailPoint {
+    pub position: Vec3,
+    /// Where the camera looks when passing this point, relative to the entity. None looks along the rail
+    pub look_at: Option<Vec3>,
+}
+
+impl CameraRailPoint {
+    pub fn new(position: Vec3) -> Self {
+        Self {
+            position,
+            look_at: None,
+        }
+    }
+
+    pub fn looking_at(mut self, target: Vec3) -> Self {
+        self.look_at = Some(target);
+        self
+    }
+}
+
+#[derive(Serialize, Deserialize, Reflect, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RailEasing {
+    Linear,
+    EaseIn,
+    EaseOut,
+    #[default]
+    EaseInOut,
+}
+
+impl RailEasing {
+    pub fn all() -> [RailEasing; 4] {
+        [
+            RailEasing::Linear,
+            RailEasing::EaseIn,
+            RailEasing::EaseOut,
+            RailEasing::EaseInOut,
+        ]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            RailEasing::Linear => "Linear",
+            RailEasing::EaseIn => "Ease In",
+            RailEasing::EaseOut => "Ease Out",
+            RailEasing::EaseInOut => "Ease In Out",
+        }
+    }
+
+    /// Maps time progress to distance progress, both 0 to 1
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            RailEasing::Linear => t,
+            RailEasing::EaseIn => t * t,
+            RailEasing::EaseOut => t * (2.0 - t),
+            RailEasing::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+/// Actual serialized class data thats stored inside IdentityData
+/// A Catmull-Rom path for a camera to travel, with a look at target per point
+/// Nothing moves on its own, game code starts the move with CameraRailPlayer
+#[derive(Serialize, Deserialize, Reflect, Debug, Clone, PartialEq)]
+pub struct CameraRail {
+    pub points: Vec<CameraRailPoint>,
+    /// Connect the last point back to the first
+    pub closed: bool,
+    /// World units per second, the easing speeds up and slows down around it
+    pub speed: f32,
+    pub easing: RailEasing,
+    /// Start over once the end is reached, instead of finishing
+    pub looping: bool,
+    /// Samples between two control points, the camera moves at an even speed across them
+    pub resolution: u32,
+}
+
+impl Default for CameraRail {
+    fn default() -> Self {
+        Self {
+            points: vec![
+                CameraRailPoint::new(Vec3::new(0.0, 2.0, 0.0)),
+                CameraRailPoint::new(Vec3::new(0.0, 2.0, -10.0))
+                    .looking_at(Vec3::new(5.0, 0.0, -15.0)),
+                CameraRailPoint::new(Vec3::new(5.0, 3.0, -20.0)),
+            ],
+            closed: false,
+            speed: 4.0,
+            easing: RailEasing::EaseInOut,
+            looping: false,
+            resolution: 16,
+        }
+    }
+}
+
+/// Rail sampled into even steps, in the space of the control points
+#[derive(Debug, Clone, Default)]
+pub struct RailPath {
+    samples: Vec<RailSample>,
+    length: f32,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RailSample {
+    position: Vec3,
+    /// Distance along the rail from the first point
+    distance: f32,
+    /// Control points this sample lies between and how far from the first one, 0 to 1
+    from: usize,
+    to: usize,
+    t: f32,
+}
+
+impl RailPath {
+    pub fn length(&self) -> f32 {
+        self.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.len() < 2
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Vec3> + '_ {
+        self.samples.iter().map(|sample| sample.position)
+    }
+
+    /// Position at a distance along the rail, with the control points around it and how far between them
+    fn at_distance(&self, distance: f32) -> (Vec3, usize, usize, f32) {
+        let distance = distance.clamp(0.0, self.length);
+        let index = self
+            .samples
+            .partition_point(|sample| sample.distance <= distance)
+            .clamp(1, self.samples.len() - 1);
+        let (a, b) = (self.samples[index - 1], self.samples[index]);
+        let span = b.distance - a.distance;
+        let blend = if span > 0.0 {
+            (distance - a.distance) / span
+        } else {
+            0.0
+        };
+        // The last sample of a segment ends at t 1 rather than the next segment's 0
+        let b_t = if b.from == a.from { b.t } else { 1.0 };
+        (
+            a.position.lerp(b.position, blend),
+            a.from,
+            a.to,
+            a.t + (b_t - a.t) * blend,
+        )
+    }
+}
+
+impl CameraRail {
+    /// Sample the rail so it can be travelled at an even speed
+    pub fn path(&self) -> RailPath {
+        let count = self.points.len();
+        let spline: Vec<SplinePoint> = self
+            .points
+            .iter()
+            .map(|point| SplinePoint::new(point.position, 0.0))
+            .collect();
+        let resolution = self.resolution.max(1) as usize;
+        let positions = sample_spline(&spline, self.closed, self.resolution);
+
+        let mut samples = Vec::with_capacity(positions.len());
+        let mut distance = 0.0;
+        for (index, sample) in positions.iter().enumerate() {
+            if let Some(previous) = samples.last().map(|sample: &RailSample| sample.position) {
+                distance += previous.distance(sample.position);
+            }
+            let segment = index / resolution;
+            let (from, t) = if index == positions.len() - 1 {
+                // Closing sample sits on the last point, or back on the first one
+                let last = if self.closed {
+                    count - 1
+                } else {
+                    count.saturating_sub(2)
+                };
+                (last, 1.0)
+            } else {
+                (segment, (index % resolution) as f32 / resolution as f32)
+            };
+            samples.push(RailSample {
+                position: sample.position,
+                distance,
+                from,
+                to: (from + 1) % count.max(1),
+                t,
+            });
+        }
+        RailPath {
+            samples,
+            length: distance,
+        }
+    }
+
+    /// Seconds to travel the whole rail at its speed
+    pub fn duration(&self, path: &RailPath) -> f32 {
+        if self.speed > 0.0 {
+            path.length() / self.speed
+        } else {
+            0.0
+        }
+    }
+
+    /// World transform of the camera once `progress` (0 to 1) of the travel time has passed
+    /// The camera turns from one point's look at target to the next over the segment between them
+    pub fn pose(&self, path: &RailPath, transform: &GlobalTransform, progress: f32) -> Transform {
+        if path.is_empty() {
+            return transform.compute_transform();
+        }
+        let distance = self.easing.apply(progress) * path.length();
+        let (position, from, to, t) = path.at_distance(distance);
+
+        // Looking along the rail follows the tangent of the current spot
+        let step = (path.length() * 0.001).max(0.001);
+        let ahead = if distance + step <= path.length() {
+            path.at_distance(distance + step).0 - position
+        } else {
+            position - path.at_distance(distance - step).0
+        };
+        let look = |point: &CameraRailPoint| match point.look_at {
+            Some(target) => target - position,
+            None => ahead,
+        };
+
+        let world = transform.affine();
+        let world_position = world.transform_point3(position);
+        let direction_from = world.transform_vector3(look(&self.points[from]));
+        let direction_to = world.transform_vector3(look(&self.points[to]));
+        let rotation_from = Transform::IDENTITY
+            .looking_to(direction_from, Vec3::Y)
+            .rotation;
+        let rotation_to = Transform::IDENTITY
+            .looking_to(direction_to, Vec3::Y)
+            .rotation;
+        let blend = t * t * (3.0 - 2.0 * t);
+
+        Transform::from_translation(world_position)
+            .with_rotation(rotation_from.slerp(rotation_to, blend))
+    }
+}
+
+impl GraniteType for CameraRail {
+    fn type_name(&self) -> String {
+        "Camera Rail".to_string()
+    }
+
+    fn type_abv(&self) -> String {
+        "Rail".to_string()
+    }
+
+    fn category(&self) -> ClassCategory {
+        ClassCategory::Gameplay
+    }
+
+    fn get_embedded_icon_bytes(&self) -> Option<&'static [u8]> {
+        Some(include_bytes!("CameraRail.png"))
+    }
+
+    fn get_icon_filename(&self) -> Option<&'static str> {
+        Some("CameraRail.png")
+    }
+
+    fn spawn_from_new_identity(
+        &mut self,
+        commands: &mut Commands,
+        transform: Transform,
+        _standard_materials: ResMut<Assets<StandardMaterial>>,
+        _meshes: ResMut<Assets<Mesh>>,
+        _available_materials: ResMut<AvailableEditableMaterials>,
+        _asset_server: Res<AssetServer>,
+        _maybe_prompt_data: Option<PromptData>,
+    ) -> Entity {
+        CameraRail::spawn_from_new_identity(self, commands, transform)
+    }
+
+    fn spawn_from_save_data(
+        &self,
+        save_data: &EntitySaveReadyData,
+        commands: &mut Commands,
+        _standard_materials: &mut ResMut<Assets<StandardMaterial>>,
+        _meshes: &mut ResMut<Assets<Mesh>>,
+        _available_materials: &mut ResMut<AvailableEditableMaterials>,
+        _asset_server: &Res<AssetServer>,
+    ) -> Entity {
+        CameraRail::spawn_from_save_data(save_data, commands)
+    }
+
+    fn push_to_entity(&self, entity: Entity, request_update: &mut RequestEntityUpdateFromClass) {
+        self.push_to_entity(entity, request_update)
+    }
+
+    fn edit_via_ui(&mut self, ui: &mut egui::Ui, spacing: (f32, f32, f32)) -> bool {
+        self.edit_via_ui(ui, spacing)
+    }
+}
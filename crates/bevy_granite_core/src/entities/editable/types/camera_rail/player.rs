@@ -0,0 +1,171 @@
+use crate::{GraniteTypes, IdentityData};
+use bevy::{
+    ecs::{
+        entity::Entity,
+        message::{Message, MessageWriter},
+        resource::Resource,
+        system::{Query, Res, ResMut},
+    },
+    time::Time,
+    transform::components::{GlobalTransform, Transform},
+};
+use bevy_granite_logging::{log, LogCategory, LogLevel, LogType};
+
+// player.rs
+// Moves a camera along a CameraRail entity, for intros and cutscenes
+// Rails are read from IdentityData every frame so edits in the editor show on the next frame
+
+/// Sent when a rail that doesn't loop reaches its end. The camera is left on the last pose
+#[derive(Message, Debug, Clone, Copy)]
+pub struct CameraRailFinished {
+    pub rail: Entity,
+    pub camera: Entity,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RailPlayback {
+    rail: Entity,
+    camera: Entity,
+    elapsed: f32,
+    duration: f32,
+    paused: bool,
+}
+
+/// Runtime control of camera rails. One rail plays at a time, playing another replaces it
+/// i.e. `player.play(rail, camera)` from a trigger, then wait for CameraRailFinished to hand control back
+#[derive(Resource, Default, Debug, Clone)]
+pub struct CameraRailPlayer {
+    playback: Option<RailPlayback>,
+}
+
+impl CameraRailPlayer {
+    /// Start moving the camera along the rail entity from its first point
+    /// The camera's Transform is set in world space, so it should have no parent
+    pub fn play(&mut self, rail: Entity, camera: Entity) {
+        self.playback = Some(RailPlayback {
+            rail,
+            camera,
+            elapsed: 0.0,
+            duration: 0.0,
+            paused: false,
+        });
+    }
+
+    /// Let go of the camera where it is, without sending CameraRailFinished
+    pub fn stop(&mut self) {
+        self.playback = None;
+    }
+
+    pub fn pause(&mut self) {
+        if let Some(playback) = self.playback.as_mut() {
+            playback.paused = true;
+        }
+    }
+
+    pub fn resume(&mut self) {
+        if let Some(playback) = self.playback.as_mut() {
+            playback.paused = false;
+        }
+    }
+
+    /// A rail has the camera, paused or not
+    pub fn is_playing(&self) -> bool {
+        self.playback.is_some()
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.playback.is_some_and(|playback| playback.paused)
+    }
+
+    pub fn rail(&self) -> Option<Entity> {
+        self.playback.map(|playback| playback.rail)
+    }
+
+    pub fn camera(&self) -> Option<Entity> {
+        self.playback.map(|playback| playback.camera)
+    }
+
+    /// 0 to 1 of the travel time, before easing. 0 when nothing plays
+    pub fn progress(&self) -> f32 {
+        match self.playback {
+            Some(playback) if playback.duration > 0.0 => {
+                (playback.elapsed / playback.duration).min(1.0)
+            }
+            _ => 0.0,
+        }
+    }
+
+    /// Jump to a point of the travel time, 0 to 1
+    pub fn seek(&mut self, progress: f32) {
+        if let Some(playback) = self.playback.as_mut() {
+            playback.elapsed = progress.clamp(0.0, 1.0) * playback.duration;
+        }
+    }
+}
+
+/// Advances the playing rail and writes the camera's Transform, before transforms propagate
+pub fn play_camera_rails_system(
+    time: Res<Time>,
+    mut player: ResMut<CameraRailPlayer>,
+    rails: Query<(&IdentityData, &GlobalTransform)>,
+    mut cameras: Query<&mut Transform>,
+    mut finished: MessageWriter<CameraRailFinished>,
+) {
+    let Some(playback) = player.playback.as_mut() else {
+        return;
+    };
+    let rail = match rails.get(playback.rail) {
+        Ok((identity, transform)) => match &identity.class {
+            GraniteTypes::CameraRail(rail) => Some((rail, transform)),
+            _ => None,
+        },
+        Err(_) => None,
+    };
+    let Some((rail, rail_transform)) = rail else {
+        log!(
+            LogType::Game,
+            LogLevel::Warning,
+            LogCategory::Entity,
+            "Camera rail {} is gone or isn't a camera rail, stopping it",
+            playback.rail
+        );
+        player.playback = None;
+        return;
+    };
+    let Ok(mut camera_transform) = cameras.get_mut(playback.camera) else {
+        log!(
+            LogType::Game,
+            LogLevel::Warning,
+            LogCategory::Entity,
+            "Camera {} of the playing rail is gone, stopping it",
+            playback.camera
+        );
+        player.playback = None;
+        return;
+    };
+
+    let path = rail.path();
+    playback.duration = rail.duration(&path);
+    if !playback.paused {
+        playback.elapsed += time.delta_secs();
+    }
+
+    let done = playback.elapsed >= playback.duration;
+    if done && rail.looping && playback.duration > 0.0 {
+        playback.elapsed %= playback.duration;
+    }
+    let progress = if playback.duration > 0.0 {
+        (playback.elapsed / playback.duration).min(1.0)
+    } else {
+        1.0
+    };
+    *camera_transform = rail.pose(&path, rail_transform, progress);
+
+    if done && !rail.looping {
+        finished.write(CameraRailFinished {
+            rail: playback.rail,
+            camera: playback.camera,
+        });
+        player.playback = None;
+    }
+}
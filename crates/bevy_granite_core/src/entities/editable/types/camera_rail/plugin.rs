@@ -0,0 +1,39 @@
+use super::{
+    play_camera_rails_system, update_camera_rail_system, CameraRail, CameraRailFinished,
+    CameraRailPlayer, UserUpdatedCameraRailEvent,
+};
+use crate::{register_class, GraniteTypes};
+use bevy::app::{App, Plugin, PostUpdate, Update};
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::transform::TransformSystems;
+
+pub struct CameraRailPlugin;
+impl Plugin for CameraRailPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            //
+            // Event
+            //
+            .add_message::<UserUpdatedCameraRailEvent>()
+            .add_message::<CameraRailFinished>()
+            //
+            // Register
+            //
+            .register_type::<CameraRail>()
+            //
+            // Resources
+            //
+            .init_resource::<CameraRailPlayer>()
+            //
+            // Schedule system
+            //
+            .add_systems(Update, update_camera_rail_system)
+            .add_systems(
+                PostUpdate,
+                play_camera_rails_system.before(TransformSystems::Propagate),
+            );
+
+        // Class registry
+        register_class(app, GraniteTypes::CameraRail(Default::default()));
+    }
+}
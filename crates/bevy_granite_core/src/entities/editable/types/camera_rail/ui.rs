@@ -0,0 +1,135 @@
+use super::{CameraRail, CameraRailPoint, RailEasing};
+use bevy::math::Vec3;
+use bevy_egui::egui;
+
+impl CameraRail {
+    /// Function to edit self's data via UI side panel
+    /// The viewport previews the dolly along the rail at its speed and easing
+    pub fn edit_via_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        // Small, Large, Normal
+        spacing: (f32, f32, f32),
+    ) -> bool {
+        let large_spacing = spacing.1;
+        let mut changed = false;
+
+        ui.label(egui::RichText::new("Camera Rail").italics());
+        ui.add_space(large_spacing);
+
+        egui::Grid::new("camera_rail_grid")
+            .num_columns(2)
+            .spacing([large_spacing, large_spacing])
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Speed:");
+                changed |= ui
+                    .add(
+                        egui::DragValue::new(&mut self.speed)
+                            .speed(0.1)
+                            .range(0.0..=f32::MAX),
+                    )
+                    .on_hover_text("World units per second")
+                    .changed();
+                ui.end_row();
+
+                ui.label("Easing:");
+                egui::ComboBox::from_id_salt("camera_rail_easing")
+                    .selected_text(self.easing.name())
+                    .show_ui(ui, |ui| {
+                        for easing in RailEasing::all() {
+                            changed |= ui
+                                .selectable_value(&mut self.easing, easing, easing.name())
+                                .changed();
+                        }
+                    });
+                ui.end_row();
+
+                ui.label("Closed:");
+                changed |= ui.checkbox(&mut self.closed, "").changed();
+                ui.end_row();
+
+                ui.label("Looping:");
+                changed |= ui.checkbox(&mut self.looping, "").changed();
+                ui.end_row();
+
+                ui.label("Resolution:");
+                changed |= ui
+                    .add(egui::DragValue::new(&mut self.resolution).range(1..=64))
+                    .changed();
+                ui.end_row();
+            });
+        ui.add_space(large_spacing);
+
+        let path = self.path();
+        ui.label(
+            egui::RichText::new(format!(
+                "Length {:.1}, {:.1} s",
+                path.length(),
+                self.duration(&path)
+            ))
+            .weak(),
+        );
+        ui.add_space(large_spacing);
+
+        ui.label(format!("Points ({})", self.points.len()));
+        let mut remove = None;
+        for (index, point) in self.points.iter_mut().enumerate() {
+            ui.push_id(index, |ui| {
+                ui.horizontal(|ui| {
+                    ui.monospace(format!("{:>2}", index));
+                    for axis in [
+                        &mut point.position.x,
+                        &mut point.position.y,
+                        &mut point.position.z,
+                    ] {
+                        changed |= ui
+                            .add(egui::DragValue::new(axis).speed(0.1).fixed_decimals(2))
+                            .changed();
+                    }
+                    if ui.small_button("Remove").clicked() {
+                        remove = Some(index);
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.monospace("  ");
+                    let mut has_target = point.look_at.is_some();
+                    if ui
+                        .checkbox(&mut has_target, "Look At")
+                        .on_hover_text("Unchecked looks along the rail")
+                        .changed()
+                    {
+                        // Start the target a little ahead of the point
+                        point.look_at = has_target.then(|| point.position + Vec3::NEG_Z * 5.0);
+                        changed = true;
+                    }
+                    if let Some(target) = point.look_at.as_mut() {
+                        for axis in [&mut target.x, &mut target.y, &mut target.z] {
+                            changed |= ui
+                                .add(egui::DragValue::new(axis).speed(0.1).fixed_decimals(2))
+                                .changed();
+                        }
+                    }
+                });
+            });
+        }
+        if let Some(index) = remove {
+            self.points.remove(index);
+            changed = true;
+        }
+        if ui.button("Add Point").clicked() {
+            // Continue in the direction of the last segment
+            let point = match self.points.as_slice() {
+                [.., before, last] => {
+                    CameraRailPoint::new(last.position + (last.position - before.position))
+                }
+                [last] => CameraRailPoint::new(last.position + Vec3::NEG_Z * 10.0),
+                [] => CameraRailPoint::new(Vec3::ZERO),
+            };
+            self.points.push(point);
+            changed = true;
+        }
+        ui.add_space(large_spacing);
+        changed
+    }
+}
@@ -0,0 +1,45 @@
+use crate::entities::editable::{RequestEntityUpdateFromClass, UserUpdatedCameraRailEvent};
+use crate::entities::CameraRail;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::message::MessageReader;
+use bevy_granite_logging::{log, LogCategory, LogLevel, LogType};
+
+impl CameraRail {
+    pub fn push_to_entity(
+        &self,
+        entity: Entity,
+        request_update: &mut RequestEntityUpdateFromClass,
+    ) {
+        log!(
+            LogType::Editor,
+            LogLevel::Info,
+            LogCategory::Entity,
+            "Requesting camera rail entity update"
+        );
+
+        request_update
+            .camera_rail
+            .write(UserUpdatedCameraRailEvent {
+                entity,
+                data: self.clone(),
+            });
+    }
+}
+
+/// Playing rails are read from IdentityData every frame, so edits apply on their own
+pub fn update_camera_rail_system(mut reader: MessageReader<UserUpdatedCameraRailEvent>) {
+    for UserUpdatedCameraRailEvent {
+        entity: requested_entity,
+        data: new,
+    } in reader.read()
+    {
+        log!(
+            LogType::Editor,
+            LogLevel::Info,
+            LogCategory::Entity,
+            "Heard camera rail update event: {} now has {} points",
+            requested_entity,
+            new.points.len()
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use super::CustomClass;
+use crate::{
+    entities::EntitySaveReadyData, GraniteEditorSerdeEntity, GraniteType, GraniteTypes,
+    HasRuntimeData, IdentityData,
+};
+use bevy::{
+    camera::visibility::Visibility,
+    ecs::{bundle::Bundle, entity::Entity, system::Commands},
+    prelude::Name,
+    transform::components::Transform,
+};
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+use uuid::Uuid;
+
+impl CustomClass {
+    /// Extract needed info to spawn this entity via save data
+    pub fn spawn_from_save_data(
+        save_data: &EntitySaveReadyData,
+        commands: &mut Commands,
+    ) -> Entity {
+        let identity = &save_data.identity;
+        let save_transform = &save_data.transform;
+
+        Self::spawn_from_identity(commands, identity, save_transform.to_bevy())
+    }
+
+    /// Take the name and class from identity to spawn
+    pub fn spawn_from_identity(
+        commands: &mut Commands,
+        identity: &IdentityData,
+        transform: Transform,
+    ) -> Entity {
+        let class = Self::extract_class(identity);
+
+        class.spawn(identity, commands, transform)
+    }
+
+    /// Generally to be used from UI popups as it gives default name
+    pub fn spawn_from_new_identity(&self, commands: &mut Commands, transform: Transform) -> Entity {
+        let identity = IdentityData {
+            name: self.type_name(),
+            uuid: Uuid::new_v4(),
+            class: GraniteTypes::Custom(self.clone()),
+        };
+        self.spawn(&identity, commands, transform)
+    }
+
+    /// Private core logic
+    /// Spawns a spatial entity and inserts the registered struct as a component on top
+    fn spawn(
+        &self,
+        identity: &IdentityData,
+        commands: &mut Commands,
+        transform: Transform,
+    ) -> Entity {
+        let mut entity_commands = commands.spawn(Self::get_bundle(identity.clone(), transform));
+
+        match self.info() {
+            Some(info) => (info.insert_component)(&mut entity_commands, &self.data),
+            None => log!(
+                LogType::Game,
+                LogLevel::Warning,
+                LogCategory::Entity,
+                "Class '{}' is not registered, spawning '{}' without its component",
+                self.class_name,
+                identity.name
+            ),
+        }
+
+        entity_commands.id()
+    }
+
+    fn get_bundle(identity: IdentityData, transform: Transform) -> impl Bundle {
+        (
+            Visibility::default(),
+            transform,
+            Name::new(identity.name.clone()),
+            HasRuntimeData,
+            GraniteEditorSerdeEntity,
+            identity,
+        )
+    }
+
+    fn extract_class(identity: &IdentityData) -> CustomClass {
+        match &identity.class {
+            GraniteTypes::Custom(custom_data) => custom_data.clone(),
+            _ => panic!("Expected Custom class data, got different type from save data"),
+        }
+    }
+}
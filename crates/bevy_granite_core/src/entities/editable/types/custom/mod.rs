@@ -0,0 +1,122 @@
+use crate::{
+    entities::{
+        editable::{GraniteType, RequestEntityUpdateFromClass},
+        EntitySaveReadyData,
+    },
+    AvailableEditableMaterials, ClassCategory, PromptData,
+};
+use bevy::{
+    asset::{AssetServer, Assets},
+    ecs::{
+        entity::Entity,
+        message::Message,
+        system::{Commands, Res, ResMut},
+    },
+    mesh::Mesh,
+    pbr::StandardMaterial,
+    reflect::Reflect,
+    transform::components::Transform,
+};
+use bevy_egui::egui;
+use serde::{Deserialize, Serialize};
+
+pub mod creation;
+pub mod plugin;
+pub mod registry;
+pub mod ui;
+pub mod update_event;
+
+pub use plugin::*;
+pub use registry::*;
+pub use update_event::*;
+
+/// Internal event thats called when user edits a custom class via the UI
+#[derive(Message)]
+pub struct UserUpdatedCustomClassEvent {
+    pub entity: Entity,
+    pub data: CustomClass,
+}
+
+/// Class data for classes defined outside of granite with #[derive(GraniteClass)]
+/// The struct itself is kept as RON so scenes stay loadable even if the game no longer registers the class
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Reflect)]
+pub struct CustomClass {
+    /// Registered class name, the struct name of the derived type
+    pub class_name: String,
+    /// RON serialized struct data
+    pub data: String,
+}
+
+impl CustomClass {
+    pub fn new<T: GraniteClass>(value: &T) -> Self {
+        Self {
+            class_name: T::CLASS_NAME.to_string(),
+            data: ron::ser::to_string(value).unwrap_or_default(),
+        }
+    }
+
+    pub fn info(&self) -> Option<CustomClassInfo> {
+        get_custom_class_info(&self.class_name)
+    }
+}
+
+impl GraniteType for CustomClass {
+    fn category(&self) -> ClassCategory {
+        self.info()
+            .map(|info| info.category)
+            .unwrap_or(ClassCategory::Unknown)
+    }
+
+    fn type_name(&self) -> String {
+        self.info()
+            .map(|info| info.type_name.to_string())
+            .unwrap_or_else(|| self.class_name.clone())
+    }
+
+    fn type_abv(&self) -> String {
+        self.info()
+            .map(|info| info.type_abv.to_string())
+            .unwrap_or_else(|| self.class_name.clone())
+    }
+
+    fn get_embedded_icon_bytes(&self) -> Option<&'static [u8]> {
+        self.info()?.icon.map(|(_, bytes)| bytes)
+    }
+
+    fn get_icon_filename(&self) -> Option<&'static str> {
+        self.info()?.icon.map(|(filename, _)| filename)
+    }
+
+    fn spawn_from_new_identity(
+        &mut self,
+        commands: &mut Commands,
+        transform: Transform,
+        _standard_materials: ResMut<Assets<StandardMaterial>>,
+        _meshes: ResMut<Assets<Mesh>>,
+        _available_materials: ResMut<AvailableEditableMaterials>,
+        _asset_server: Res<AssetServer>,
+        _maybe_prompt_data: Option<PromptData>,
+    ) -> Entity {
+        CustomClass::spawn_from_new_identity(self, commands, transform)
+    }
+
+    fn spawn_from_save_data(
+        &self,
+        save_data: &EntitySaveReadyData,
+        commands: &mut Commands,
+        _standard_materials: &mut ResMut<Assets<StandardMaterial>>,
+        _meshes: &mut ResMut<Assets<Mesh>>,
+        _available_materials: &mut ResMut<AvailableEditableMaterials>,
+        _asset_server: &Res<AssetServer>,
+    ) -> Entity {
+        CustomClass::spawn_from_save_data(save_data, commands)
+    }
+
+    fn push_to_entity(&self, entity: Entity, request_update: &mut RequestEntityUpdateFromClass) {
+        self.push_to_entity(entity, request_update)
+    }
+
+    fn edit_via_ui(&mut self, ui: &mut egui::Ui, spacing: (f32, f32, f32)) -> bool {
+        self.edit_via_ui(ui, spacing)
+    }
+}
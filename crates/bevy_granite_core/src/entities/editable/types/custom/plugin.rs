@@ -0,0 +1,21 @@
+use super::{update_custom_class_system, CustomClass, UserUpdatedCustomClassEvent};
+use bevy::app::{App, Plugin, Update};
+
+pub struct CustomClassPlugin;
+impl Plugin for CustomClassPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            //
+            // Event
+            //
+            .add_message::<UserUpdatedCustomClassEvent>()
+            //
+            // Register
+            //
+            .register_type::<CustomClass>()
+            //
+            // Schedule system
+            //
+            .add_systems(Update, update_custom_class_system);
+    }
+}
@@ -0,0 +1,132 @@
+use super::CustomClass;
+use crate::{register_class, register_class_icon, ClassCategory, GraniteTypes};
+use bevy::{
+    app::App,
+    ecs::{component::Component, system::EntityCommands},
+    reflect::{GetTypeRegistration, Reflect, TypeRegistry},
+};
+use bevy_egui::egui;
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, RwLock},
+};
+
+/// Implemented by #[derive(GraniteClass)]. Describes a game defined class the editor can spawn, edit and save
+/// The struct is inserted as a component on spawned entities so game systems can query it directly
+pub trait GraniteClass:
+    Component + Reflect + GetTypeRegistration + Serialize + DeserializeOwned + Default + Clone
+{
+    /// Unique key stored in scene files
+    const CLASS_NAME: &'static str;
+    /// Full friendly name of this class
+    const TYPE_NAME: &'static str;
+    /// Short name, abv. of this class
+    const TYPE_ABV: &'static str;
+    const CATEGORY: ClassCategory;
+    /// Embedded PNG icon as (filename, bytes)
+    const ICON: Option<(&'static str, &'static [u8])> = None;
+    /// Icon loaded from the assets folder instead, wins over ICON
+    const ICON_PATH: Option<&'static str> = None;
+}
+
+/// Type erased functions and metadata for a registered GraniteClass
+#[derive(Clone)]
+pub struct CustomClassInfo {
+    pub class_name: &'static str,
+    pub type_name: &'static str,
+    pub type_abv: &'static str,
+    pub category: ClassCategory,
+    pub icon: Option<(&'static str, &'static [u8])>,
+    pub default_data: fn() -> String,
+    pub insert_component: fn(&mut EntityCommands, &str),
+    pub edit_via_ui: fn(&mut egui::Ui, &mut String, &TypeRegistry) -> bool,
+    /// Registry used to draw the reflected UI, includes the inspector impls for std/glam types
+    pub type_registry: Arc<TypeRegistry>,
+}
+
+lazy_static::lazy_static! {
+    static ref CUSTOM_CLASSES: RwLock<BTreeMap<&'static str, CustomClassInfo>> = RwLock::new(BTreeMap::new());
+}
+
+/// Register a #[derive(GraniteClass)] type so it can be spawned, edited and loaded from scenes
+pub fn register_granite_class<T: GraniteClass>(app: &mut App) {
+    let mut type_registry = TypeRegistry::default();
+    bevy_inspector_egui::inspector_egui_impls::register_std_impls(&mut type_registry);
+    bevy_inspector_egui::inspector_egui_impls::register_glam_impls(&mut type_registry);
+    type_registry.register::<T>();
+
+    let info = CustomClassInfo {
+        class_name: T::CLASS_NAME,
+        type_name: T::TYPE_NAME,
+        type_abv: T::TYPE_ABV,
+        category: T::CATEGORY,
+        icon: T::ICON,
+        default_data: default_data::<T>,
+        insert_component: insert_component::<T>,
+        edit_via_ui: edit_via_ui::<T>,
+        type_registry: Arc::new(type_registry),
+    };
+
+    CUSTOM_CLASSES.write().unwrap().insert(T::CLASS_NAME, info);
+    app.register_type::<T>();
+    let class = GraniteTypes::Custom(CustomClass::new(&T::default()));
+    if let Some(icon_path) = T::ICON_PATH {
+        register_class_icon(app, class.clone(), icon_path);
+    }
+    register_class(app, class);
+
+    log!(
+        LogType::Game,
+        LogLevel::Info,
+        LogCategory::System,
+        "Registered granite class: {}",
+        T::CLASS_NAME
+    );
+}
+
+pub fn get_custom_class_info(class_name: &str) -> Option<CustomClassInfo> {
+    CUSTOM_CLASSES.read().unwrap().get(class_name).cloned()
+}
+
+fn default_data<T: GraniteClass>() -> String {
+    ron::ser::to_string(&T::default()).unwrap_or_default()
+}
+
+fn insert_component<T: GraniteClass>(entity_commands: &mut EntityCommands, data: &str) {
+    let value = ron::de::from_str::<T>(data).unwrap_or_else(|e| {
+        log!(
+            LogType::Game,
+            LogLevel::Warning,
+            LogCategory::Entity,
+            "Failed to parse {} class data, using default: {}",
+            T::CLASS_NAME,
+            e
+        );
+        T::default()
+    });
+    entity_commands.insert(value);
+}
+
+fn edit_via_ui<T: GraniteClass>(
+    ui: &mut egui::Ui,
+    data: &mut String,
+    type_registry: &TypeRegistry,
+) -> bool {
+    let mut value = ron::de::from_str::<T>(data).unwrap_or_default();
+    if !bevy_inspector_egui::reflect_inspector::ui_for_value(&mut value, ui, type_registry) {
+        return false;
+    }
+
+    match ron::ser::to_string(&value) {
+        Ok(serialized) => {
+            *data = serialized;
+            true
+        }
+        Err(_) => false,
+    }
+}
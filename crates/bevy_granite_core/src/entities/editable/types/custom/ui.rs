@@ -0,0 +1,28 @@
+use super::CustomClass;
+use crate::GraniteType;
+use bevy_egui::egui;
+
+impl CustomClass {
+    /// Function to edit self's data via UI side panel
+    /// Fields are drawn through reflection of the registered struct
+    pub fn edit_via_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        // Small, Large, Normal
+        spacing: (f32, f32, f32),
+    ) -> bool {
+        let large_spacing = spacing.1;
+        ui.label(egui::RichText::new(self.type_name()).italics());
+        ui.add_space(large_spacing);
+
+        let Some(info) = self.info() else {
+            ui.label(format!(
+                "Class '{}' is not registered. Its data will be kept as is.",
+                self.class_name
+            ));
+            return false;
+        };
+
+        (info.edit_via_ui)(ui, &mut self.data, &info.type_registry)
+    }
+}
@@ -0,0 +1,54 @@
+use crate::entities::editable::RequestEntityUpdateFromClass;
+use crate::entities::editable::{CustomClass, UserUpdatedCustomClassEvent};
+use bevy::ecs::entity::Entity;
+use bevy::ecs::message::MessageReader;
+use bevy::ecs::system::Commands;
+use bevy_granite_logging::{log, LogCategory, LogLevel, LogType};
+
+impl CustomClass {
+    pub fn push_to_entity(
+        &self,
+        entity: Entity,
+        request_update: &mut RequestEntityUpdateFromClass,
+    ) {
+        log!(
+            LogType::Editor,
+            LogLevel::Info,
+            LogCategory::Entity,
+            "Requesting custom class entity update"
+        );
+
+        request_update.custom.write(UserUpdatedCustomClassEvent {
+            entity,
+            data: self.clone(),
+        });
+    }
+}
+
+/// Actually update the specific entity with the class data
+/// The registered struct component is replaced with the edited one
+pub fn update_custom_class_system(
+    mut reader: MessageReader<UserUpdatedCustomClassEvent>,
+    mut commands: Commands,
+) {
+    for UserUpdatedCustomClassEvent {
+        entity: requested_entity,
+        data: new,
+    } in reader.read()
+    {
+        log!(
+            LogType::Editor,
+            LogLevel::Info,
+            LogCategory::Entity,
+            "Heard custom class update event: {}",
+            requested_entity
+        );
+
+        let Some(info) = new.info() else {
+            continue;
+        };
+        if let Ok(mut entity_commands) = commands.get_entity(*requested_entity) {
+            (info.insert_component)(&mut entity_commands, &new.data);
+        }
+    }
+}
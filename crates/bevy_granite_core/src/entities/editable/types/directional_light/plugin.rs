@@ -1,6 +1,7 @@
 use crate::DirLight;
 
 use super::{update_directional_light_system, UserUpdatedDirectionalLightEvent};
+use crate::{register_class, GraniteTypes};
 use bevy::app::{App, Plugin, Update};
 
 pub struct DirLightPlugin;
@@ -19,5 +20,8 @@ impl Plugin for DirLightPlugin {
             // Schedule system
             //
             .add_systems(Update, update_directional_light_system);
+
+        // Class registry
+        register_class(app, GraniteTypes::DirLight(Default::default()));
     }
 }
@@ -49,10 +49,10 @@ impl DirLight {
                             egui::DragValue::new(&mut data.illuminance)
                                 .range(0.0..=200_000.0)
                                 .speed(2000.0)
-                                .suffix(" lm"),
+                                .suffix(" lx"),
                         )
                         .on_hover_text(
-                            "Intensity in lumens. Direct sunlight ~32,000. Office lighting ~400",
+                            "Illuminance in lux. Direct sunlight ~32,000. Office lighting ~400",
                         )
                         .changed();
                     ui.end_row();
@@ -1,4 +1,5 @@
 use super::{update_empty_system, Empty, UserUpdatedEmptyEvent};
+use crate::{register_class, GraniteTypes};
 use bevy::app::{App, Plugin, Update};
 
 pub struct EmptyPlugin;
@@ -17,5 +18,8 @@ impl Plugin for EmptyPlugin {
             // Schedule system
             //
             .add_systems(Update, update_empty_system);
+
+        // Class registry
+        register_class(app, GraniteTypes::Empty(Default::default()));
     }
 }
@@ -0,0 +1,208 @@
+use crate::{
+    entities::{
+        editable::{GraniteType, RequestEntityUpdateFromClass},
+        EntitySaveReadyData,
+    },
+    AvailableEditableMaterials, ClassCategory, PromptData,
+};
+use bevy::{
+    asset::{AssetServer, Assets},
+    ecs::{
+        entity::Entity,
+        message::Message,
+        system::{Commands, Res, ResMut},
+    },
+    math::Vec3,
+    mesh::Mesh,
+    pbr::StandardMaterial,
+    reflect::Reflect,
+    transform::components::{GlobalTransform, Transform},
+};
+use bevy_egui::egui;
+use serde::{Deserialize, Serialize};
+
+pub mod creation;
+pub mod plugin;
+pub mod ui;
+pub mod update_event;
+pub mod volumes;
+
+pub use plugin::*;
+pub use update_event::*;
+pub use volumes::*;
+
+/// Internal event thats called when user edits UI force volume variables
+#[derive(Message)]
+pub struct UserUpdatedForceVolumeEvent {
+    pub entity: Entity,
+    pub data: ForceVolume,
+}
+
+/// Shape of the force a volume applies
+#[derive(Serialize, Deserialize, Reflect, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ForceKind {
+    /// Pushes along the entity forward (-Z), i.e. wind
+    #[default]
+    Directional,
+    /// Pushes away from the center. Negative strength pulls in
+    Radial,
+    /// Swirls around the entity up axis. Negative strength turns the other way
+    Vortex,
+}
+
+impl ForceKind {
+    pub fn all() -> [ForceKind; 3] {
+        [ForceKind::Directional, ForceKind::Radial, ForceKind::Vortex]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ForceKind::Directional => "Directional",
+            ForceKind::Radial => "Radial",
+            ForceKind::Vortex => "Vortex",
+        }
+    }
+}
+
+/// How the strength fades from the center to the radius
+#[derive(Serialize, Deserialize, Reflect, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ForceFalloff {
+    /// Full strength up to the radius
+    #[default]
+    None,
+    Linear,
+    Quadratic,
+}
+
+impl ForceFalloff {
+    pub fn all() -> [ForceFalloff; 3] {
+        [
+            ForceFalloff::None,
+            ForceFalloff::Linear,
+            ForceFalloff::Quadratic,
+        ]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ForceFalloff::None => "None",
+            ForceFalloff::Linear => "Linear",
+            ForceFalloff::Quadratic => "Quadratic",
+        }
+    }
+
+    /// Strength multiplier at distance / radius
+    pub fn factor(&self, t: f32) -> f32 {
+        let remaining = (1.0 - t).clamp(0.0, 1.0);
+        match self {
+            ForceFalloff::None => 1.0,
+            ForceFalloff::Linear => remaining,
+            ForceFalloff::Quadratic => remaining * remaining,
+        }
+    }
+}
+
+/// Actual serialized class data thats stored inside IdentityData
+/// A spherical force field. Granite doesn't move anything with it,
+/// physics, movement or particle systems sample it through the ForceVolumes resource
+#[derive(Serialize, Deserialize, Reflect, Debug, Clone, PartialEq)]
+pub struct ForceVolume {
+    pub kind: ForceKind,
+    pub strength: f32,
+    /// World units, the entity scale doesn't change it
+    pub radius: f32,
+    pub falloff: ForceFalloff,
+}
+
+impl Default for ForceVolume {
+    fn default() -> Self {
+        Self {
+            kind: ForceKind::Directional,
+            strength: 5.0,
+            radius: 5.0,
+            falloff: ForceFalloff::Linear,
+        }
+    }
+}
+
+impl ForceVolume {
+    pub fn new(kind: ForceKind, strength: f32, radius: f32, falloff: ForceFalloff) -> Self {
+        Self {
+            kind,
+            strength,
+            radius,
+            falloff,
+        }
+    }
+
+    /// Force at a world position for a volume placed at transform. Zero outside the radius
+    pub fn force_at(&self, transform: &GlobalTransform, position: Vec3) -> Vec3 {
+        let offset = position - transform.translation();
+        let distance = offset.length();
+        if self.radius <= 0.0 || distance > self.radius {
+            return Vec3::ZERO;
+        }
+
+        let direction = match self.kind {
+            ForceKind::Directional => transform.forward().as_vec3(),
+            ForceKind::Radial => offset.normalize_or_zero(),
+            ForceKind::Vortex => transform.up().cross(offset).normalize_or_zero(),
+        };
+        direction * self.strength * self.falloff.factor(distance / self.radius)
+    }
+}
+
+impl GraniteType for ForceVolume {
+    fn type_name(&self) -> String {
+        "Force Volume".to_string()
+    }
+
+    fn type_abv(&self) -> String {
+        "Force".to_string()
+    }
+
+    fn category(&self) -> ClassCategory {
+        ClassCategory::Gameplay
+    }
+
+    fn get_embedded_icon_bytes(&self) -> Option<&'static [u8]> {
+        Some(include_bytes!("ForceVolume.png"))
+    }
+
+    fn get_icon_filename(&self) -> Option<&'static str> {
+        Some("ForceVolume.png")
+    }
+
+    fn spawn_from_new_identity(
+        &mut self,
+        commands: &mut Commands,
+        transform: Transform,
+        _standard_materials: ResMut<Assets<StandardMaterial>>,
+        _meshes: ResMut<Assets<Mesh>>,
+        _available_materials: ResMut<AvailableEditableMaterials>,
+        _asset_server: Res<AssetServer>,
+        _maybe_prompt_data: Option<PromptData>,
+    ) -> Entity {
+        ForceVolume::spawn_from_new_identity(self, commands, transform)
+    }
+
+    fn spawn_from_save_data(
+        &self,
+        save_data: &EntitySaveReadyData,
+        commands: &mut Commands,
+        _standard_materials: &mut ResMut<Assets<StandardMaterial>>,
+        _meshes: &mut ResMut<Assets<Mesh>>,
+        _available_materials: &mut ResMut<AvailableEditableMaterials>,
+        _asset_server: &Res<AssetServer>,
+    ) -> Entity {
+        ForceVolume::spawn_from_save_data(save_data, commands)
+    }
+
+    fn push_to_entity(&self, entity: Entity, request_update: &mut RequestEntityUpdateFromClass) {
+        self.push_to_entity(entity, request_update)
+    }
+
+    fn edit_via_ui(&mut self, ui: &mut egui::Ui, spacing: (f32, f32, f32)) -> bool {
+        self.edit_via_ui(ui, spacing)
+    }
+}
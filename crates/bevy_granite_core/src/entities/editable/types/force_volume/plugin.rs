@@ -0,0 +1,38 @@
+use super::{
+    update_force_volume_system, update_force_volumes_system, ForceVolume, ForceVolumes,
+    UserUpdatedForceVolumeEvent,
+};
+use crate::{register_class, GraniteTypes};
+use bevy::app::{App, Plugin, PostUpdate, Update};
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::transform::TransformSystems;
+
+pub struct ForceVolumePlugin;
+impl Plugin for ForceVolumePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            //
+            // Event
+            //
+            .add_message::<UserUpdatedForceVolumeEvent>()
+            //
+            // Register
+            //
+            .register_type::<ForceVolume>()
+            //
+            // Resources
+            //
+            .init_resource::<ForceVolumes>()
+            //
+            // Schedule system
+            //
+            .add_systems(Update, update_force_volume_system)
+            .add_systems(
+                PostUpdate,
+                update_force_volumes_system.after(TransformSystems::Propagate),
+            );
+
+        // Class registry
+        register_class(app, GraniteTypes::ForceVolume(Default::default()));
+    }
+}
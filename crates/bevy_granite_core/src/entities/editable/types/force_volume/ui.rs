@@ -0,0 +1,62 @@
+use super::{ForceFalloff, ForceKind, ForceVolume};
+use bevy_egui::egui;
+
+impl ForceVolume {
+    /// Function to edit self's data via UI side panel
+    pub fn edit_via_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        // Small, Large, Normal
+        spacing: (f32, f32, f32),
+    ) -> bool {
+        let large_spacing = spacing.1;
+        let mut changed = false;
+        egui::Grid::new("force_volume_grid")
+            .num_columns(2)
+            .spacing([large_spacing, large_spacing])
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Kind:");
+                egui::ComboBox::from_id_salt("force_volume_kind")
+                    .selected_text(self.kind.name())
+                    .show_ui(ui, |ui| {
+                        for kind in ForceKind::all() {
+                            changed |= ui
+                                .selectable_value(&mut self.kind, kind, kind.name())
+                                .changed();
+                        }
+                    });
+                ui.end_row();
+
+                ui.label("Strength:");
+                changed |= ui
+                    .add(egui::DragValue::new(&mut self.strength).speed(0.1))
+                    .changed();
+                ui.end_row();
+
+                ui.label("Radius:");
+                changed |= ui
+                    .add(
+                        egui::DragValue::new(&mut self.radius)
+                            .speed(0.1)
+                            .range(0.0..=f32::MAX),
+                    )
+                    .changed();
+                ui.end_row();
+
+                ui.label("Falloff:");
+                egui::ComboBox::from_id_salt("force_volume_falloff")
+                    .selected_text(self.falloff.name())
+                    .show_ui(ui, |ui| {
+                        for falloff in ForceFalloff::all() {
+                            changed |= ui
+                                .selectable_value(&mut self.falloff, falloff, falloff.name())
+                                .changed();
+                        }
+                    });
+                ui.end_row();
+            });
+        ui.add_space(large_spacing);
+        changed
+    }
+}
@@ -0,0 +1,46 @@
+use crate::entities::editable::{RequestEntityUpdateFromClass, UserUpdatedForceVolumeEvent};
+use crate::entities::ForceVolume;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::message::MessageReader;
+use bevy_granite_logging::{log, LogCategory, LogLevel, LogType};
+
+impl ForceVolume {
+    pub fn push_to_entity(
+        &self,
+        entity: Entity,
+        request_update: &mut RequestEntityUpdateFromClass,
+    ) {
+        log!(
+            LogType::Editor,
+            LogLevel::Info,
+            LogCategory::Entity,
+            "Requesting force volume entity update"
+        );
+
+        request_update
+            .force_volume
+            .write(UserUpdatedForceVolumeEvent {
+                entity,
+                data: self.clone(),
+            });
+    }
+}
+
+/// The settings are read from IdentityData, so ForceVolumes picks edits up on its own
+pub fn update_force_volume_system(mut reader: MessageReader<UserUpdatedForceVolumeEvent>) {
+    for UserUpdatedForceVolumeEvent {
+        entity: requested_entity,
+        data: new,
+    } in reader.read()
+    {
+        log!(
+            LogType::Editor,
+            LogLevel::Info,
+            LogCategory::Entity,
+            "Heard force volume update event: {} is now {} with strength {}",
+            requested_entity,
+            new.kind.name(),
+            new.strength
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use super::ForceVolume;
+use crate::{GraniteTypes, IdentityData};
+use bevy::{
+    ecs::{
+        entity::Entity,
+        resource::Resource,
+        system::{Query, ResMut},
+    },
+    math::Vec3,
+    transform::components::GlobalTransform,
+};
+
+/// Every ForceVolume entity in the world, refreshed each frame after transforms propagate
+/// Sample it from physics, movement or particle systems, i.e. `velocity += volumes.sample(position) * dt`
+#[derive(Resource, Default, Debug, Clone)]
+pub struct ForceVolumes {
+    volumes: Vec<(Entity, ForceVolume, GlobalTransform)>,
+}
+
+impl ForceVolumes {
+    /// Summed force of every volume at a world position
+    pub fn sample(&self, position: Vec3) -> Vec3 {
+        self.volumes
+            .iter()
+            .map(|(_, volume, transform)| volume.force_at(transform, position))
+            .sum()
+    }
+
+    /// Volumes whose radius contains the position, with the force each applies there
+    pub fn affecting(&self, position: Vec3) -> impl Iterator<Item = (Entity, Vec3)> + '_ {
+        self.volumes
+            .iter()
+            .filter(move |(_, volume, transform)| {
+                transform.translation().distance(position) <= volume.radius
+            })
+            .map(move |(entity, volume, transform)| (*entity, volume.force_at(transform, position)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &(Entity, ForceVolume, GlobalTransform)> {
+        self.volumes.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.volumes.is_empty()
+    }
+}
+
+pub fn update_force_volumes_system(
+    mut force_volumes: ResMut<ForceVolumes>,
+    query: Query<(Entity, &IdentityData, &GlobalTransform)>,
+) {
+    let mut volumes: Vec<(Entity, ForceVolume, GlobalTransform)> = query
+        .iter()
+        .filter_map(|(entity, identity, transform)| match &identity.class {
+            GraniteTypes::ForceVolume(volume) => Some((entity, volume.clone(), *transform)),
+            _ => None,
+        })
+        .collect();
+    // Query order isn't stable, keep volumes in a fixed order
+    volumes.sort_by_key(|(entity, ..)| *entity);
+
+    if force_volumes.volumes != volumes {
+        force_volumes.volumes = volumes;
+    }
+}
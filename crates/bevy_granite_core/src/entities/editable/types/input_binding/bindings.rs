@@ -0,0 +1,135 @@
+use super::{ActionBinding, BindingInput, InputBinding};
+use crate::{GraniteTypes, IdentityData};
+use bevy::{
+    ecs::{
+        resource::Resource,
+        system::{Query, Res, ResMut},
+    },
+    input::{gamepad::Gamepad, keyboard::KeyCode, ButtonInput},
+};
+use std::collections::{BTreeMap, HashSet};
+use uuid::Uuid;
+
+/// Action bindings from every InputBinding entity, and which actions are held this frame
+/// Refreshed in PreUpdate after Bevy reads input, i.e. `if bindings.just_pressed("jump") { ... }`
+/// A scene binding wins over a global one of the same action, and global ones over those bound from code
+#[derive(Resource, Default, Debug, Clone)]
+pub struct InputBindings {
+    /// Bound with `bind`, the fallback when no entity binds the action
+    code: BTreeMap<String, Vec<BindingInput>>,
+    /// Actions of global InputBinding entities by uuid, kept after the entity is despawned
+    global: BTreeMap<Uuid, Vec<ActionBinding>>,
+    /// Bindings in effect
+    actions: BTreeMap<String, Vec<BindingInput>>,
+    pressed: HashSet<String>,
+    just_pressed: HashSet<String>,
+    just_released: HashSet<String>,
+}
+
+impl InputBindings {
+    /// Default inputs for an action, used until a scene binds it
+    pub fn bind(&mut self, action: impl Into<String>, inputs: Vec<BindingInput>) {
+        self.code.insert(action.into(), inputs);
+    }
+
+    pub fn pressed(&self, action: &str) -> bool {
+        self.pressed.contains(action)
+    }
+
+    pub fn just_pressed(&self, action: &str) -> bool {
+        self.just_pressed.contains(action)
+    }
+
+    pub fn just_released(&self, action: &str) -> bool {
+        self.just_released.contains(action)
+    }
+
+    /// -1, 0 or 1 from two actions, i.e. `axis("move_left", "move_right")`
+    pub fn axis(&self, negative: &str, positive: &str) -> f32 {
+        self.pressed(positive) as i32 as f32 - self.pressed(negative) as i32 as f32
+    }
+
+    /// Inputs bound to an action
+    pub fn inputs(&self, action: &str) -> &[BindingInput] {
+        self.actions
+            .get(action)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn actions(&self) -> impl Iterator<Item = &str> {
+        self.actions.keys().map(String::as_str)
+    }
+
+    pub fn contains(&self, action: &str) -> bool {
+        self.actions.contains_key(action)
+    }
+}
+
+fn is_held(
+    input: &BindingInput,
+    keys: Option<&ButtonInput<KeyCode>>,
+    gamepads: &Query<&Gamepad>,
+) -> bool {
+    if let (Some(key), Some(keys)) = (input.to_key(), keys) {
+        return keys.pressed(key);
+    }
+    input
+        .to_gamepad_button()
+        .is_some_and(|button| gamepads.iter().any(|gamepad| gamepad.pressed(button)))
+}
+
+pub fn update_input_bindings_system(
+    mut bindings: ResMut<InputBindings>,
+    query: Query<&IdentityData>,
+    keys: Option<Res<ButtonInput<KeyCode>>>,
+    gamepads: Query<&Gamepad>,
+) {
+    let mut scene: BTreeMap<String, Vec<BindingInput>> = BTreeMap::new();
+    for identity in query.iter() {
+        let GraniteTypes::InputBinding(InputBinding { global, actions }) = &identity.class else {
+            continue;
+        };
+        if *global {
+            if bindings.global.get(&identity.uuid) != Some(actions) {
+                bindings.global.insert(identity.uuid, actions.clone());
+            }
+            continue;
+        }
+        bindings.global.remove(&identity.uuid);
+        for action in actions {
+            let inputs = scene.entry(action.action.clone()).or_default();
+            for input in action.inputs.iter() {
+                if !inputs.contains(input) {
+                    inputs.push(input.clone());
+                }
+            }
+        }
+    }
+
+    let mut actions = bindings.code.clone();
+    for action in bindings.global.values().flatten() {
+        actions.insert(action.action.clone(), action.inputs.clone());
+    }
+    actions.extend(scene);
+    if bindings.actions != actions {
+        bindings.actions = actions;
+    }
+
+    let keys = keys.as_deref();
+    let held: HashSet<String> = bindings
+        .actions
+        .iter()
+        .filter(|(_, inputs)| inputs.iter().any(|input| is_held(input, keys, &gamepads)))
+        .map(|(action, _)| action.clone())
+        .collect();
+    if held == bindings.pressed
+        && bindings.just_pressed.is_empty()
+        && bindings.just_released.is_empty()
+    {
+        return;
+    }
+    bindings.just_pressed = held.difference(&bindings.pressed).cloned().collect();
+    bindings.just_released = bindings.pressed.difference(&held).cloned().collect();
+    bindings.pressed = held;
+}
@@ -0,0 +1,315 @@
+use crate::{
+    entities::{
+        editable::{GraniteType, RequestEntityUpdateFromClass},
+        EntitySaveReadyData,
+    },
+    AvailableEditableMaterials, ClassCategory, PromptData,
+};
+use bevy::{
+    asset::{AssetServer, Assets},
+    ecs::{
+        entity::Entity,
+        message::Message,
+        system::{Commands, Res, ResMut},
+    },
+    input::{gamepad::GamepadButton, keyboard::KeyCode},
+    mesh::Mesh,
+    pbr::StandardMaterial,
+    reflect::Reflect,
+    transform::components::Transform,
+};
+use bevy_egui::egui;
+use serde::{Deserialize, Serialize};
+
+pub mod bindings;
+pub mod creation;
+pub mod plugin;
+pub mod ui;
+pub mod update_event;
+
+pub use bindings::*;
+pub use plugin::*;
+pub use update_event::*;
+
+/// Internal event thats called when user edits UI input binding variables
+#[derive(Message)]
+pub struct UserUpdatedInputBindingEvent {
+    pub entity: Entity,
+    pub data: InputBinding,
+}
+
+/// Keys a binding can use, by their KeyCode name
+pub const BINDABLE_KEYS: &[KeyCode] = &[
+    KeyCode::KeyA,
+    KeyCode::KeyB,
+    KeyCode::KeyC,
+    KeyCode::KeyD,
+    KeyCode::KeyE,
+    KeyCode::KeyF,
+    KeyCode::KeyG,
+    KeyCode::KeyH,
+    KeyCode::KeyI,
+    KeyCode::KeyJ,
+    KeyCode::KeyK,
+    KeyCode::KeyL,
+    KeyCode::KeyM,
+    KeyCode::KeyN,
+    KeyCode::KeyO,
+    KeyCode::KeyP,
+    KeyCode::KeyQ,
+    KeyCode::KeyR,
+    KeyCode::KeyS,
+    KeyCode::KeyT,
+    KeyCode::KeyU,
+    KeyCode::KeyV,
+    KeyCode::KeyW,
+    KeyCode::KeyX,
+    KeyCode::KeyY,
+    KeyCode::KeyZ,
+    KeyCode::Digit0,
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+    KeyCode::ArrowUp,
+    KeyCode::ArrowDown,
+    KeyCode::ArrowLeft,
+    KeyCode::ArrowRight,
+    KeyCode::Space,
+    KeyCode::Enter,
+    KeyCode::Escape,
+    KeyCode::Tab,
+    KeyCode::Backspace,
+    KeyCode::ShiftLeft,
+    KeyCode::ShiftRight,
+    KeyCode::ControlLeft,
+    KeyCode::ControlRight,
+    KeyCode::AltLeft,
+    KeyCode::AltRight,
+    KeyCode::CapsLock,
+    KeyCode::Backquote,
+    KeyCode::Minus,
+    KeyCode::Equal,
+    KeyCode::BracketLeft,
+    KeyCode::BracketRight,
+    KeyCode::Semicolon,
+    KeyCode::Quote,
+    KeyCode::Comma,
+    KeyCode::Period,
+    KeyCode::Slash,
+    KeyCode::F1,
+    KeyCode::F2,
+    KeyCode::F3,
+    KeyCode::F4,
+    KeyCode::F5,
+    KeyCode::F6,
+    KeyCode::F7,
+    KeyCode::F8,
+    KeyCode::F9,
+    KeyCode::F10,
+    KeyCode::F11,
+    KeyCode::F12,
+    KeyCode::Delete,
+    KeyCode::Insert,
+    KeyCode::Home,
+    KeyCode::End,
+    KeyCode::PageUp,
+    KeyCode::PageDown,
+    KeyCode::Numpad0,
+    KeyCode::Numpad1,
+    KeyCode::Numpad2,
+    KeyCode::Numpad3,
+    KeyCode::Numpad4,
+    KeyCode::Numpad5,
+    KeyCode::Numpad6,
+    KeyCode::Numpad7,
+    KeyCode::Numpad8,
+    KeyCode::Numpad9,
+    KeyCode::NumpadEnter,
+];
+
+/// Gamepad buttons a binding can use, by their GamepadButton name
+pub const BINDABLE_GAMEPAD_BUTTONS: &[GamepadButton] = &[
+    GamepadButton::South,
+    GamepadButton::East,
+    GamepadButton::North,
+    GamepadButton::West,
+    GamepadButton::C,
+    GamepadButton::Z,
+    GamepadButton::LeftTrigger,
+    GamepadButton::LeftTrigger2,
+    GamepadButton::RightTrigger,
+    GamepadButton::RightTrigger2,
+    GamepadButton::Select,
+    GamepadButton::Start,
+    GamepadButton::Mode,
+    GamepadButton::LeftThumb,
+    GamepadButton::RightThumb,
+    GamepadButton::DPadUp,
+    GamepadButton::DPadDown,
+    GamepadButton::DPadLeft,
+    GamepadButton::DPadRight,
+];
+
+/// One key or button an action listens to, stored by name so scene files stay readable
+#[derive(Serialize, Deserialize, Reflect, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BindingInput {
+    /// KeyCode name, i.e. "KeyW" or "Space"
+    Key(String),
+    /// GamepadButton name, i.e. "South" or "RightTrigger2"
+    Gamepad(String),
+}
+
+impl Default for BindingInput {
+    fn default() -> Self {
+        Self::Key(format!("{:?}", KeyCode::Space))
+    }
+}
+
+impl BindingInput {
+    pub fn key(key: KeyCode) -> Self {
+        Self::Key(format!("{:?}", key))
+    }
+
+    pub fn gamepad(button: GamepadButton) -> Self {
+        Self::Gamepad(format!("{:?}", button))
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            BindingInput::Key(name) | BindingInput::Gamepad(name) => name,
+        }
+    }
+
+    pub fn to_key(&self) -> Option<KeyCode> {
+        match self {
+            BindingInput::Key(name) => BINDABLE_KEYS
+                .iter()
+                .copied()
+                .find(|key| format!("{:?}", key) == *name),
+            BindingInput::Gamepad(_) => None,
+        }
+    }
+
+    pub fn to_gamepad_button(&self) -> Option<GamepadButton> {
+        match self {
+            BindingInput::Gamepad(name) => BINDABLE_GAMEPAD_BUTTONS
+                .iter()
+                .copied()
+                .find(|button| format!("{:?}", button) == *name),
+            BindingInput::Key(_) => None,
+        }
+    }
+
+    /// Whether the name matches a known key or button
+    pub fn is_valid(&self) -> bool {
+        self.to_key().is_some() || self.to_gamepad_button().is_some()
+    }
+}
+
+/// An action name and every input that triggers it
+#[derive(Serialize, Deserialize, Reflect, Debug, Clone, PartialEq, Eq, Default)]
+pub struct ActionBinding {
+    pub action: String,
+    pub inputs: Vec<BindingInput>,
+}
+
+impl ActionBinding {
+    pub fn new(action: impl Into<String>, inputs: Vec<BindingInput>) -> Self {
+        Self {
+            action: action.into(),
+            inputs,
+        }
+    }
+}
+
+/// Actual serialized class data thats stored inside IdentityData
+/// Action name to key and gamepad mappings, queried at runtime through the InputBindings resource
+/// Scene bindings apply while their entity exists. Global ones stay after their scene is despawned,
+/// so a bootstrap scene can set up controls for the whole game
+#[derive(Serialize, Deserialize, Reflect, Debug, Clone, PartialEq, Eq)]
+pub struct InputBinding {
+    pub global: bool,
+    pub actions: Vec<ActionBinding>,
+}
+
+impl Default for InputBinding {
+    fn default() -> Self {
+        Self {
+            global: false,
+            actions: vec![ActionBinding::new(
+                "jump",
+                vec![
+                    BindingInput::key(KeyCode::Space),
+                    BindingInput::gamepad(GamepadButton::South),
+                ],
+            )],
+        }
+    }
+}
+
+impl InputBinding {
+    pub fn new(global: bool, actions: Vec<ActionBinding>) -> Self {
+        Self { global, actions }
+    }
+}
+
+impl GraniteType for InputBinding {
+    fn type_name(&self) -> String {
+        "Input Binding".to_string()
+    }
+
+    fn type_abv(&self) -> String {
+        "Input".to_string()
+    }
+
+    fn category(&self) -> ClassCategory {
+        ClassCategory::Gameplay
+    }
+
+    fn get_embedded_icon_bytes(&self) -> Option<&'static [u8]> {
+        Some(include_bytes!("InputBinding.png"))
+    }
+
+    fn get_icon_filename(&self) -> Option<&'static str> {
+        Some("InputBinding.png")
+    }
+
+    fn spawn_from_new_identity(
+        &mut self,
+        commands: &mut Commands,
+        transform: Transform,
+        _standard_materials: ResMut<Assets<StandardMaterial>>,
+        _meshes: ResMut<Assets<Mesh>>,
+        _available_materials: ResMut<AvailableEditableMaterials>,
+        _asset_server: Res<AssetServer>,
+        _maybe_prompt_data: Option<PromptData>,
+    ) -> Entity {
+        InputBinding::spawn_from_new_identity(self, commands, transform)
+    }
+
+    fn spawn_from_save_data(
+        &self,
+        save_data: &EntitySaveReadyData,
+        commands: &mut Commands,
+        _standard_materials: &mut ResMut<Assets<StandardMaterial>>,
+        _meshes: &mut ResMut<Assets<Mesh>>,
+        _available_materials: &mut ResMut<AvailableEditableMaterials>,
+        _asset_server: &Res<AssetServer>,
+    ) -> Entity {
+        InputBinding::spawn_from_save_data(save_data, commands)
+    }
+
+    fn push_to_entity(&self, entity: Entity, request_update: &mut RequestEntityUpdateFromClass) {
+        self.push_to_entity(entity, request_update)
+    }
+
+    fn edit_via_ui(&mut self, ui: &mut egui::Ui, spacing: (f32, f32, f32)) -> bool {
+        self.edit_via_ui(ui, spacing)
+    }
+}
@@ -0,0 +1,37 @@
+use super::{
+    update_input_binding_system, update_input_bindings_system, ActionBinding, BindingInput,
+    InputBinding, InputBindings, UserUpdatedInputBindingEvent,
+};
+use crate::{register_class, GraniteTypes};
+use bevy::app::{App, Plugin, PreUpdate, Update};
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::input::InputSystems;
+
+pub struct InputBindingPlugin;
+impl Plugin for InputBindingPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            //
+            // Event
+            //
+            .add_message::<UserUpdatedInputBindingEvent>()
+            //
+            // Register
+            //
+            .register_type::<InputBinding>()
+            .register_type::<ActionBinding>()
+            .register_type::<BindingInput>()
+            //
+            // Resources
+            //
+            .init_resource::<InputBindings>()
+            //
+            // Schedule system
+            //
+            .add_systems(Update, update_input_binding_system)
+            .add_systems(PreUpdate, update_input_bindings_system.after(InputSystems));
+
+        // Class registry
+        register_class(app, GraniteTypes::InputBinding(Default::default()));
+    }
+}
@@ -0,0 +1,114 @@
+use super::{ActionBinding, BindingInput, InputBinding, BINDABLE_GAMEPAD_BUTTONS, BINDABLE_KEYS};
+use bevy::input::keyboard::KeyCode;
+use bevy_egui::egui;
+
+impl InputBinding {
+    /// Function to edit self's data via UI side panel
+    /// Inputs are picked from BINDABLE_KEYS and BINDABLE_GAMEPAD_BUTTONS, unknown names from hand edited files show in red
+    pub fn edit_via_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        // Small, Large, Normal
+        spacing: (f32, f32, f32),
+    ) -> bool {
+        let large_spacing = spacing.1;
+        let mut changed = false;
+        changed |= ui
+            .checkbox(&mut self.global, "Global")
+            .on_hover_text("Keep these bindings after this scene is despawned")
+            .changed();
+        ui.add_space(large_spacing);
+
+        ui.label(format!("Actions ({})", self.actions.len()));
+        let mut remove_action = None;
+        for (action_index, action) in self.actions.iter_mut().enumerate() {
+            ui.separator();
+            ui.horizontal(|ui| {
+                changed |= ui.text_edit_singleline(&mut action.action).changed();
+                if ui.small_button("Remove").clicked() {
+                    remove_action = Some(action_index);
+                }
+            });
+            changed |= edit_inputs(ui, action, action_index);
+        }
+        if let Some(index) = remove_action {
+            self.actions.remove(index);
+            changed = true;
+        }
+        ui.add_space(large_spacing);
+        if ui.button("Add action").clicked() {
+            let action = format!("action_{}", self.actions.len() + 1);
+            self.actions
+                .push(ActionBinding::new(action, vec![BindingInput::default()]));
+            changed = true;
+        }
+        ui.add_space(large_spacing);
+        changed
+    }
+}
+
+fn edit_inputs(ui: &mut egui::Ui, action: &mut ActionBinding, action_index: usize) -> bool {
+    let mut changed = false;
+    let mut remove_input = None;
+    for (input_index, input) in action.inputs.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            let is_key = matches!(input, BindingInput::Key(_));
+            egui::ComboBox::from_id_salt(("input_binding_kind", action_index, input_index))
+                .selected_text(if is_key { "Key" } else { "Gamepad" })
+                .width(80.)
+                .show_ui(ui, |ui| {
+                    if ui.selectable_label(is_key, "Key").clicked() && !is_key {
+                        *input = BindingInput::key(KeyCode::Space);
+                        changed = true;
+                    }
+                    if ui.selectable_label(!is_key, "Gamepad").clicked() && is_key {
+                        *input = BindingInput::gamepad(BINDABLE_GAMEPAD_BUTTONS[0]);
+                        changed = true;
+                    }
+                });
+
+            let names: Vec<String> = match input {
+                BindingInput::Key(_) => BINDABLE_KEYS
+                    .iter()
+                    .map(|key| format!("{:?}", key))
+                    .collect(),
+                BindingInput::Gamepad(_) => BINDABLE_GAMEPAD_BUTTONS
+                    .iter()
+                    .map(|button| format!("{:?}", button))
+                    .collect(),
+            };
+            let selected = if input.is_valid() {
+                egui::RichText::new(input.name())
+            } else {
+                egui::RichText::new(input.name()).color(egui::Color32::RED)
+            };
+            egui::ComboBox::from_id_salt(("input_binding_name", action_index, input_index))
+                .selected_text(selected)
+                .width(120.)
+                .show_ui(ui, |ui| {
+                    for name in names {
+                        if ui.selectable_label(input.name() == name, &name).clicked() {
+                            *input = match input {
+                                BindingInput::Key(_) => BindingInput::Key(name),
+                                BindingInput::Gamepad(_) => BindingInput::Gamepad(name),
+                            };
+                            changed = true;
+                        }
+                    }
+                });
+
+            if ui.small_button("Remove").clicked() {
+                remove_input = Some(input_index);
+            }
+        });
+    }
+    if let Some(index) = remove_input {
+        action.inputs.remove(index);
+        changed = true;
+    }
+    if ui.small_button("Add input").clicked() {
+        action.inputs.push(BindingInput::default());
+        changed = true;
+    }
+    changed
+}
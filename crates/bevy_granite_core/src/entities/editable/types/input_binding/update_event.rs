@@ -0,0 +1,45 @@
+use crate::entities::editable::{RequestEntityUpdateFromClass, UserUpdatedInputBindingEvent};
+use crate::entities::InputBinding;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::message::MessageReader;
+use bevy_granite_logging::{log, LogCategory, LogLevel, LogType};
+
+impl InputBinding {
+    pub fn push_to_entity(
+        &self,
+        entity: Entity,
+        request_update: &mut RequestEntityUpdateFromClass,
+    ) {
+        log!(
+            LogType::Editor,
+            LogLevel::Info,
+            LogCategory::Entity,
+            "Requesting input binding entity update"
+        );
+
+        request_update
+            .input_binding
+            .write(UserUpdatedInputBindingEvent {
+                entity,
+                data: self.clone(),
+            });
+    }
+}
+
+/// The actions are read from IdentityData, so InputBindings picks edits up on its own
+pub fn update_input_binding_system(mut reader: MessageReader<UserUpdatedInputBindingEvent>) {
+    for UserUpdatedInputBindingEvent {
+        entity: requested_entity,
+        data: new,
+    } in reader.read()
+    {
+        log!(
+            LogType::Editor,
+            LogLevel::Info,
+            LogCategory::Entity,
+            "Heard input binding update event: {} now binds {} actions",
+            requested_entity,
+            new.actions.len()
+        );
+    }
+}
@@ -0,0 +1,163 @@
+use crate::{
+    entities::{
+        editable::{GraniteType, RequestEntityUpdateFromClass},
+        EntitySaveReadyData,
+    },
+    AvailableEditableMaterials, ClassCategory, PromptData,
+};
+use bevy::{
+    asset::{AssetServer, Assets},
+    ecs::{
+        entity::Entity,
+        message::Message,
+        system::{Commands, Res, ResMut},
+    },
+    math::Vec3,
+    mesh::Mesh,
+    pbr::StandardMaterial,
+    reflect::Reflect,
+    transform::components::Transform,
+};
+use bevy_egui::egui;
+use serde::{Deserialize, Serialize};
+
+pub mod creation;
+pub mod plugin;
+pub mod probes;
+pub mod ui;
+pub mod update_event;
+
+pub use plugin::*;
+pub use probes::*;
+pub use update_event::*;
+
+/// Internal event thats called when user edits UI light probe variables
+#[derive(Message)]
+pub struct UserUpdatedLightProbeEvent {
+    pub entity: Entity,
+    pub data: LightProbeData,
+}
+
+/// What the probe lights its box with
+#[derive(Serialize, Deserialize, Reflect, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LightProbeKind {
+    /// Diffuse and specular cubemaps, for reflections from inside rooms
+    #[default]
+    Reflection,
+    /// Irradiance volume, a 3D texture of baked diffuse light
+    Irradiance,
+}
+
+impl LightProbeKind {
+    pub fn all() -> [LightProbeKind; 2] {
+        [LightProbeKind::Reflection, LightProbeKind::Irradiance]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            LightProbeKind::Reflection => "Reflection",
+            LightProbeKind::Irradiance => "Irradiance",
+        }
+    }
+}
+
+/// Actual serialized class data thats stored inside IdentityData
+/// A box overriding the environment lighting of meshes inside it, i.e. indoor reflections
+/// that shouldn't show the sky of the WorldEnvironment
+#[derive(Serialize, Deserialize, Reflect, Debug, Clone, PartialEq)]
+pub struct LightProbeData {
+    pub kind: LightProbeKind,
+    /// Cubemaps with six faces stacked vertically (+X, -X, +Y, -Y, +Z, -Z), both are needed for reflections
+    pub diffuse_map: String,
+    pub specular_map: String,
+    /// 3D texture for irradiance, i.e. a KTX2 file, the game needs Bevy's ktx2 feature to load it
+    pub voxels: String,
+    pub intensity: f32,
+    /// Half size of the box of influence, scaled by the entity scale
+    pub half_extents: Vec3,
+}
+
+impl Default for LightProbeData {
+    fn default() -> Self {
+        Self {
+            kind: LightProbeKind::Reflection,
+            diffuse_map: String::new(),
+            specular_map: String::new(),
+            voxels: String::new(),
+            intensity: 900.0,
+            half_extents: Vec3::splat(5.0),
+        }
+    }
+}
+
+impl LightProbeData {
+    /// Every texture its kind needs is set
+    pub fn has_maps(&self) -> bool {
+        match self.kind {
+            LightProbeKind::Reflection => {
+                !self.diffuse_map.is_empty() && !self.specular_map.is_empty()
+            }
+            LightProbeKind::Irradiance => !self.voxels.is_empty(),
+        }
+    }
+
+    /// Transform of the probe volume under the entity, Bevy light probes cover a unit cube
+    pub fn volume_transform(&self) -> Transform {
+        Transform::from_scale(self.half_extents * 2.0)
+    }
+}
+
+impl GraniteType for LightProbeData {
+    fn type_name(&self) -> String {
+        "Light Probe".to_string()
+    }
+
+    fn type_abv(&self) -> String {
+        "Probe".to_string()
+    }
+
+    fn category(&self) -> ClassCategory {
+        ClassCategory::Light
+    }
+
+    fn get_embedded_icon_bytes(&self) -> Option<&'static [u8]> {
+        Some(include_bytes!("LightProbe.png"))
+    }
+
+    fn get_icon_filename(&self) -> Option<&'static str> {
+        Some("LightProbe.png")
+    }
+
+    fn spawn_from_new_identity(
+        &mut self,
+        commands: &mut Commands,
+        transform: Transform,
+        _standard_materials: ResMut<Assets<StandardMaterial>>,
+        _meshes: ResMut<Assets<Mesh>>,
+        _available_materials: ResMut<AvailableEditableMaterials>,
+        _asset_server: Res<AssetServer>,
+        _maybe_prompt_data: Option<PromptData>,
+    ) -> Entity {
+        LightProbeData::spawn_from_new_identity(self, commands, transform)
+    }
+
+    fn spawn_from_save_data(
+        &self,
+        save_data: &EntitySaveReadyData,
+        commands: &mut Commands,
+        _standard_materials: &mut ResMut<Assets<StandardMaterial>>,
+        _meshes: &mut ResMut<Assets<Mesh>>,
+        _available_materials: &mut ResMut<AvailableEditableMaterials>,
+        _asset_server: &Res<AssetServer>,
+    ) -> Entity {
+        LightProbeData::spawn_from_save_data(save_data, commands)
+    }
+
+    fn push_to_entity(&self, entity: Entity, request_update: &mut RequestEntityUpdateFromClass) {
+        self.push_to_entity(entity, request_update)
+    }
+
+    fn edit_via_ui(&mut self, ui: &mut egui::Ui, spacing: (f32, f32, f32)) -> bool {
+        self.edit_via_ui(ui, spacing)
+    }
+}
@@ -0,0 +1,39 @@
+use super::{
+    apply_light_probe_maps_system, prepare_light_probe_cubemaps_system, sync_light_probes_system,
+    update_light_probe_system, LightProbeData, LightProbeKind, UserUpdatedLightProbeEvent,
+};
+use crate::{register_class, GraniteTypes};
+use bevy::app::{App, Plugin, Update};
+use bevy::ecs::schedule::IntoScheduleConfigs;
+
+pub struct LightProbePlugin;
+impl Plugin for LightProbePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            //
+            // Event
+            //
+            .add_message::<UserUpdatedLightProbeEvent>()
+            //
+            // Register
+            //
+            .register_type::<LightProbeData>()
+            .register_type::<LightProbeKind>()
+            //
+            // Schedule system
+            //
+            .add_systems(
+                Update,
+                (
+                    update_light_probe_system,
+                    sync_light_probes_system,
+                    prepare_light_probe_cubemaps_system,
+                    apply_light_probe_maps_system,
+                )
+                    .chain(),
+            );
+
+        // Class registry
+        register_class(app, GraniteTypes::LightProbe(Default::default()));
+    }
+}
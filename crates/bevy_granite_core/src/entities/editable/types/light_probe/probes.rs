@@ -0,0 +1,171 @@
+use super::{LightProbeData, LightProbeKind};
+use crate::{
+    entities::editable::types::world_environment::{cubemap_ready, prepare_cubemap},
+    EditorIgnore, GraniteTypes, IdentityData, TreeHiddenEntity,
+};
+use bevy::{
+    asset::{AssetEvent, AssetServer, Assets, Handle},
+    ecs::{
+        change_detection::DetectChanges,
+        component::Component,
+        entity::Entity,
+        hierarchy::Children,
+        message::MessageReader,
+        query::With,
+        system::{Commands, Query, Res, ResMut},
+        world::Ref,
+    },
+    image::Image,
+    light::{EnvironmentMapLight, IrradianceVolume, LightProbe},
+    prelude::Name,
+};
+
+// probes.rs
+// Light Probe entities get a child holding Bevy's LightProbe, scaled to the box of the class data
+// Its EnvironmentMapLight or IrradianceVolume is only added once the textures are ready to be used
+
+/// Probe volume spawned under a Light Probe entity. Never saved, it comes back from the class data on load
+#[derive(Component, Debug, Clone)]
+pub struct LightProbeVolume {
+    settings: LightProbeData,
+    diffuse: Option<Handle<Image>>,
+    specular: Option<Handle<Image>>,
+    voxels: Option<Handle<Image>>,
+    applied: bool,
+}
+
+impl LightProbeVolume {
+    pub fn settings(&self) -> &LightProbeData {
+        &self.settings
+    }
+
+    /// The textures are in and lighting the box
+    pub fn is_applied(&self) -> bool {
+        self.applied
+    }
+
+    fn cubemaps(&self) -> impl Iterator<Item = &Handle<Image>> {
+        [&self.diffuse, &self.specular].into_iter().flatten()
+    }
+}
+
+fn load_map(asset_server: &AssetServer, path: &str) -> Option<Handle<Image>> {
+    (!path.is_empty()).then(|| asset_server.load(path.to_string()))
+}
+
+/// Spawns the probe volume of new or edited Light Probe entities, replacing the previous one
+pub fn sync_light_probes_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    probes: Query<(Entity, Ref<IdentityData>, Option<&Children>)>,
+    volumes: Query<(), With<LightProbeVolume>>,
+) {
+    for (entity, identity, children) in probes.iter() {
+        if !identity.is_changed() {
+            continue;
+        }
+        let GraniteTypes::LightProbe(probe) = &identity.class else {
+            continue;
+        };
+        for child in children.into_iter().flatten() {
+            if volumes.contains(*child) {
+                commands.entity(*child).despawn();
+            }
+        }
+
+        let (diffuse, specular, voxels) = match probe.kind {
+            LightProbeKind::Reflection => (
+                load_map(&asset_server, &probe.diffuse_map),
+                load_map(&asset_server, &probe.specular_map),
+                None,
+            ),
+            LightProbeKind::Irradiance => (None, None, load_map(&asset_server, &probe.voxels)),
+        };
+        let volume = commands
+            .spawn((
+                LightProbe,
+                probe.volume_transform(),
+                LightProbeVolume {
+                    settings: probe.clone(),
+                    diffuse,
+                    specular,
+                    voxels,
+                    applied: false,
+                },
+                Name::new("Light Probe Volume"),
+                TreeHiddenEntity,
+                EditorIgnore::PICKING,
+            ))
+            .id();
+        commands.entity(entity).add_child(volume);
+    }
+}
+
+/// Images have no cubemap metadata, so reflection maps are turned into cubemaps once loaded
+pub fn prepare_light_probe_cubemaps_system(
+    mut events: MessageReader<AssetEvent<Image>>,
+    mut images: ResMut<Assets<Image>>,
+    volumes: Query<&LightProbeVolume>,
+) {
+    let loaded: Vec<_> = events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::LoadedWithDependencies { id } => Some(*id),
+            _ => None,
+        })
+        .filter(|id| {
+            volumes
+                .iter()
+                .any(|volume| volume.cubemaps().any(|handle| handle.id() == *id))
+        })
+        .collect();
+
+    for id in loaded {
+        if let Some(image) = images.get_mut(id) {
+            prepare_cubemap(image);
+        }
+    }
+}
+
+/// Adds the EnvironmentMapLight or IrradianceVolume to probe volumes whose textures are ready
+pub fn apply_light_probe_maps_system(
+    mut commands: Commands,
+    images: Res<Assets<Image>>,
+    mut volumes: Query<(Entity, &mut LightProbeVolume)>,
+) {
+    for (entity, mut volume) in volumes.iter_mut() {
+        if volume.applied {
+            continue;
+        }
+        let intensity = volume.settings.intensity;
+        match volume.settings.kind {
+            LightProbeKind::Reflection => {
+                let Some((diffuse_map, specular_map)) = cubemap_ready(&images, &volume.diffuse)
+                    .zip(cubemap_ready(&images, &volume.specular))
+                else {
+                    continue;
+                };
+                commands.entity(entity).insert(EnvironmentMapLight {
+                    diffuse_map,
+                    specular_map,
+                    intensity,
+                    ..Default::default()
+                });
+            }
+            LightProbeKind::Irradiance => {
+                let Some(voxels) = volume.voxels.clone() else {
+                    continue;
+                };
+                if images.get(&voxels).is_none() {
+                    continue;
+                }
+                commands.entity(entity).insert(IrradianceVolume {
+                    voxels,
+                    intensity,
+                    ..Default::default()
+                });
+            }
+        }
+        volume.applied = true;
+    }
+}
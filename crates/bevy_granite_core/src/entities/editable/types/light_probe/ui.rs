@@ -0,0 +1,99 @@
+use super::{LightProbeData, LightProbeKind};
+use crate::{
+    absolute_asset_to_rel, entities::editable::types::world_environment::ui::cubemap_path_edit,
+    shared::asset_file_browser,
+};
+use bevy_egui::egui;
+
+impl LightProbeData {
+    /// Function to edit self's data via UI side panel
+    pub fn edit_via_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        // Small, Large, Normal
+        spacing: (f32, f32, f32),
+    ) -> bool {
+        let large_spacing = spacing.1;
+        let mut changed = false;
+        egui::Grid::new("light_probe_grid")
+            .num_columns(2)
+            .spacing([large_spacing, large_spacing])
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Kind:");
+                egui::ComboBox::from_id_salt("light_probe_kind")
+                    .selected_text(self.kind.name())
+                    .show_ui(ui, |ui| {
+                        for kind in LightProbeKind::all() {
+                            changed |= ui
+                                .selectable_value(&mut self.kind, kind, kind.name())
+                                .changed();
+                        }
+                    });
+                ui.end_row();
+
+                match self.kind {
+                    LightProbeKind::Reflection => {
+                        ui.label("Diffuse:");
+                        changed |= cubemap_path_edit(ui, &mut self.diffuse_map);
+                        ui.end_row();
+
+                        ui.label("Specular:");
+                        changed |= cubemap_path_edit(ui, &mut self.specular_map);
+                    }
+                    LightProbeKind::Irradiance => {
+                        ui.label("Voxels:");
+                        changed |= voxels_path_edit(ui, &mut self.voxels);
+                    }
+                }
+                ui.end_row();
+
+                ui.label("Intensity:");
+                changed |= ui
+                    .add(
+                        egui::DragValue::new(&mut self.intensity)
+                            .speed(10.0)
+                            .range(0.0..=f32::MAX),
+                    )
+                    .changed();
+                ui.end_row();
+
+                ui.label("Half Extents:");
+                ui.horizontal(|ui| {
+                    for value in [
+                        &mut self.half_extents.x,
+                        &mut self.half_extents.y,
+                        &mut self.half_extents.z,
+                    ] {
+                        changed |= ui
+                            .add(egui::DragValue::new(value).speed(0.1).range(0.0..=f32::MAX))
+                            .changed();
+                    }
+                });
+                ui.end_row();
+            });
+        if !self.has_maps() {
+            ui.add_space(large_spacing);
+            ui.weak("Lights nothing until its textures are set.");
+        }
+        ui.add_space(large_spacing);
+        changed
+    }
+}
+
+fn voxels_path_edit(ui: &mut egui::Ui, path: &mut String) -> bool {
+    let mut changed = false;
+    ui.horizontal(|ui| {
+        changed |= ui
+            .text_edit_singleline(path)
+            .on_hover_text("Irradiance volume 3D texture, loading KTX2 needs Bevy's ktx2 feature")
+            .changed();
+        if ui.button("📁").clicked() {
+            if let Some(selected) = asset_file_browser("textures".to_string(), vec!["ktx2"]) {
+                *path = absolute_asset_to_rel(selected).to_string();
+                changed = true;
+            }
+        }
+    });
+    changed
+}
@@ -0,0 +1,45 @@
+use crate::entities::editable::{RequestEntityUpdateFromClass, UserUpdatedLightProbeEvent};
+use crate::entities::LightProbeData;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::message::MessageReader;
+use bevy_granite_logging::{log, LogCategory, LogLevel, LogType};
+
+impl LightProbeData {
+    pub fn push_to_entity(
+        &self,
+        entity: Entity,
+        request_update: &mut RequestEntityUpdateFromClass,
+    ) {
+        log!(
+            LogType::Editor,
+            LogLevel::Info,
+            LogCategory::Entity,
+            "Requesting light probe entity update"
+        );
+
+        request_update
+            .light_probe
+            .write(UserUpdatedLightProbeEvent {
+                entity,
+                data: self.clone(),
+            });
+    }
+}
+
+/// The settings are read from IdentityData, so the probe volume is respawned from it on its own
+pub fn update_light_probe_system(mut reader: MessageReader<UserUpdatedLightProbeEvent>) {
+    for UserUpdatedLightProbeEvent {
+        entity: requested_entity,
+        data: new,
+    } in reader.read()
+    {
+        log!(
+            LogType::Editor,
+            LogLevel::Info,
+            LogCategory::Entity,
+            "Heard light probe update event: {} is now a {} probe",
+            requested_entity,
+            new.kind.name()
+        );
+    }
+}
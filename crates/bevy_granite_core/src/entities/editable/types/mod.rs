@@ -1,23 +1,88 @@
+pub mod ambience_zone;
+pub mod anchor;
+pub mod audio_emitter;
 pub mod camera_3d;
+pub mod camera_rail;
+pub mod custom;
 pub mod directional_light;
 pub mod empty;
+pub mod force_volume;
+pub mod input_binding;
+pub mod light_probe;
 pub mod obj;
 pub mod point_light;
+pub mod rect_brush;
+pub mod spline_3d;
+pub mod spline_mesh;
 pub mod spot_light;
 pub mod unknown;
-pub mod rect_brush;
+pub mod vfx_spawner;
+pub mod waypoint;
+pub mod weather;
+pub mod world_environment;
 
 pub mod plugin;
 
 // Re-exports
 // Class Types
+pub use ambience_zone::{
+    AmbienceListener, AmbienceZone, AmbienceZoneEntered, AmbienceZoneExited, AmbienceZonePlugin,
+    AmbienceZoneShape, AmbienceZoneState, AmbienceZones, UserUpdatedAmbienceZoneEvent, ZoneReverb,
+};
+pub use anchor::{Anchor, AnchorPlugin, Anchors, UserUpdatedAnchorEvent};
+pub use audio_emitter::{
+    AudioEmitter3D, AudioEmitter3DPlugin, AudioEmitterPreview, AudioEmitterState, AudioEmitters,
+    AudioMonitor, AudioRolloff, UserUpdatedAudioEmitter3DEvent,
+};
 pub use camera_3d::{Camera3D, Camera3DPlugin, UserUpdatedCamera3DEvent, VolumetricFog};
+pub use camera_rail::{
+    CameraRail, CameraRailFinished, CameraRailPlayer, CameraRailPlugin, CameraRailPoint,
+    RailEasing, RailPath, UserUpdatedCameraRailEvent,
+};
+pub use custom::{
+    get_custom_class_info, register_granite_class, CustomClass, CustomClassInfo, CustomClassPlugin,
+    GraniteClass, UserUpdatedCustomClassEvent,
+};
 pub use directional_light::{DirLight, DirLightPlugin, UserUpdatedDirectionalLightEvent};
 pub use empty::{Empty, EmptyPlugin, UserUpdatedEmptyEvent};
-pub use obj::{OBJPlugin, UserUpdatedOBJEvent, OBJ};
+pub use force_volume::{
+    ForceFalloff, ForceKind, ForceVolume, ForceVolumePlugin, ForceVolumes,
+    UserUpdatedForceVolumeEvent,
+};
+pub use input_binding::{
+    ActionBinding, BindingInput, InputBinding, InputBindingPlugin, InputBindings,
+    UserUpdatedInputBindingEvent, BINDABLE_GAMEPAD_BUTTONS, BINDABLE_KEYS,
+};
+pub use light_probe::{
+    LightProbeData, LightProbeKind, LightProbePlugin, LightProbeVolume, UserUpdatedLightProbeEvent,
+};
+pub use obj::{mesh_to_obj, write_obj, OBJPlugin, UserUpdatedOBJEvent, OBJ};
 pub use point_light::{PointLightData, PointLightPlugin, UserUpdatedPointLightEvent};
+pub use rect_brush::{RectBrush, RectBrushPlugin, UserUpdatedRectBrushEvent};
+pub use spline_3d::{
+    Spline3D, Spline3DPlugin, SplineInterpolation, SplinePath, Splines, UserUpdatedSpline3DEvent,
+};
+pub use spline_mesh::{
+    BakedSplineMesh, SplineMesh, SplineMeshDirty, SplineMeshPlugin, SplinePoint, SplineProfile,
+    UserUpdatedSplineMeshEvent,
+};
 pub use spot_light::{SpotLightData, SpotLightPlugin, UserUpdatedSpotLightEvent};
 pub use unknown::Unknown;
-pub use rect_brush::{UserUpdatedRectBrushEvent, RectBrush, RectBrushPlugin};
+pub use vfx_spawner::{
+    registered_vfx_effects, vfx_effect_parameters, RunningVfx, UserUpdatedVfxSpawnerEvent,
+    VfxEffect, VfxEffectAppExt, VfxEffects, VfxSpawner, VfxSpawnerPlugin,
+};
+pub use waypoint::{
+    RequestConnectWaypointsEvent, UserUpdatedWaypointEvent, Waypoint, WaypointGraph, WaypointLink,
+    WaypointLinkMode, WaypointNetworks, WaypointNode, WaypointPlugin,
+};
+pub use weather::{
+    UserUpdatedWeatherEvent, Weather, WeatherController, WeatherFog, WeatherPlugin, WeatherPreset,
+};
+pub use world_environment::{
+    EnvironmentCamera, EnvironmentCameraFog, EnvironmentFog, EnvironmentFogMode,
+    UserUpdatedWorldEnvironmentEvent, WorldEnvironment, WorldEnvironmentPlugin,
+    WorldEnvironmentState,
+};
 
-pub use plugin::ClassTypePlugin;
\ No newline at end of file
+pub use plugin::ClassTypePlugin;
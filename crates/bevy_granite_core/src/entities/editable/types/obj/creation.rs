@@ -219,7 +219,9 @@ impl OBJ {
         ))
     }
 
-    fn extract_first_usemtl_name<P: AsRef<Path>>(obj_path: P) -> Result<String, std::io::Error> {
+    pub(crate) fn extract_first_usemtl_name<P: AsRef<Path>>(
+        obj_path: P,
+    ) -> Result<String, std::io::Error> {
         let file = File::open(obj_path)?;
         let reader = BufReader::new(file);
 
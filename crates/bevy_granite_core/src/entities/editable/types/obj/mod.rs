@@ -26,9 +26,11 @@ pub mod creation;
 pub mod plugin;
 pub mod ui;
 pub mod update_event;
+pub mod write;
 
 pub use plugin::*;
 pub use update_event::*;
+pub use write::{mesh_to_obj, write_obj};
 
 /// Internal event thats called when user edits UI OBJ variables
 #[derive(Message)]
@@ -1,5 +1,6 @@
 use super::{update_obj_system, UserUpdatedOBJEvent};
 use crate::OBJ;
+use crate::{register_class, GraniteTypes};
 use bevy::app::{App, Plugin, Update};
 
 pub struct OBJPlugin;
@@ -18,5 +19,8 @@ impl Plugin for OBJPlugin {
             // Schedule system
             //
             .add_systems(Update, update_obj_system);
+
+        // Class registry
+        register_class(app, GraniteTypes::OBJ(Default::default()));
     }
 }
@@ -0,0 +1,74 @@
+use bevy::mesh::{Indices, Mesh, PrimitiveTopology, VertexAttributeValues};
+use std::fmt::Write;
+
+// write.rs
+// Writes triangle meshes as .obj text that loads back through bevy_obj
+// bevy_obj flips V on load, so it is flipped here too
+
+/// OBJ text for a triangle list. Normals and UVs are per vertex, same length as positions
+pub fn write_obj(
+    name: &str,
+    material: Option<&str>,
+    positions: &[[f32; 3]],
+    normals: Option<&[[f32; 3]]>,
+    uvs: Option<&[[f32; 2]]>,
+    indices: &[u32],
+) -> String {
+    let normals = normals.filter(|normals| normals.len() == positions.len());
+    let uvs = uvs.filter(|uvs| uvs.len() == positions.len());
+
+    let mut obj = String::new();
+    let _ = writeln!(obj, "o {}", name);
+    if let Some(material) = material {
+        let _ = writeln!(obj, "usemtl {}", material);
+    }
+    for position in positions {
+        let _ = writeln!(obj, "v {} {} {}", position[0], position[1], position[2]);
+    }
+    for uv in uvs.unwrap_or_default() {
+        let _ = writeln!(obj, "vt {} {}", uv[0], 1.0 - uv[1]);
+    }
+    for normal in normals.unwrap_or_default() {
+        let _ = writeln!(obj, "vn {} {} {}", normal[0], normal[1], normal[2]);
+    }
+    for triangle in indices.chunks_exact(3) {
+        obj.push('f');
+        for index in triangle {
+            let index = index + 1;
+            let _ = match (uvs.is_some(), normals.is_some()) {
+                (true, true) => write!(obj, " {0}/{0}/{0}", index),
+                (true, false) => write!(obj, " {0}/{0}", index),
+                (false, true) => write!(obj, " {0}//{0}", index),
+                (false, false) => write!(obj, " {}", index),
+            };
+        }
+        obj.push('\n');
+    }
+    obj
+}
+
+/// OBJ text of a mesh in memory, None if it isn't a triangle list with positions
+pub fn mesh_to_obj(mesh: &Mesh, name: &str, material: Option<&str>) -> Option<String> {
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+        return None;
+    }
+    let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+    else {
+        return None;
+    };
+    let normals = match mesh.attribute(Mesh::ATTRIBUTE_NORMAL) {
+        Some(VertexAttributeValues::Float32x3(normals)) => Some(normals.as_slice()),
+        _ => None,
+    };
+    let uvs = match mesh.attribute(Mesh::ATTRIBUTE_UV_0) {
+        Some(VertexAttributeValues::Float32x2(uvs)) => Some(uvs.as_slice()),
+        _ => None,
+    };
+    let indices: Vec<u32> = match mesh.indices() {
+        Some(Indices::U16(indices)) => indices.iter().map(|index| *index as u32).collect(),
+        Some(Indices::U32(indices)) => indices.clone(),
+        None => (0..positions.len() as u32).collect(),
+    };
+    Some(write_obj(name, material, positions, normals, uvs, &indices))
+}
@@ -6,12 +6,27 @@ impl Plugin for ClassTypePlugin {
         app
             //
             // Class types
-            .add_plugins(Camera3DPlugin)
-            .add_plugins(DirLightPlugin)
+            // Order here is the order classes show up in within their spawn menu category
+            .add_plugins(OBJPlugin)
+            .add_plugins(EmptyPlugin)
             .add_plugins(PointLightPlugin)
             .add_plugins(SpotLightPlugin)
+            .add_plugins(DirLightPlugin)
+            .add_plugins(Camera3DPlugin)
+            .add_plugins(WeatherPlugin)
+            .add_plugins(WorldEnvironmentPlugin)
+            .add_plugins(LightProbePlugin)
+            .add_plugins(AnchorPlugin)
+            .add_plugins(WaypointPlugin)
+            .add_plugins(InputBindingPlugin)
+            .add_plugins(ForceVolumePlugin)
+            .add_plugins(AmbienceZonePlugin)
+            .add_plugins(AudioEmitter3DPlugin)
+            .add_plugins(VfxSpawnerPlugin)
+            .add_plugins(CameraRailPlugin)
             .add_plugins(RectBrushPlugin)
-            .add_plugins(EmptyPlugin)
-            .add_plugins(OBJPlugin);
+            .add_plugins(SplineMeshPlugin)
+            .add_plugins(Spline3DPlugin)
+            .add_plugins(CustomClassPlugin);
     }
 }
@@ -1,5 +1,6 @@
 use super::{update_point_light_system, UserUpdatedPointLightEvent};
 use crate::PointLightData;
+use crate::{register_class, GraniteTypes};
 use bevy::app::{App, Plugin, Update};
 
 pub struct PointLightPlugin;
@@ -18,5 +19,8 @@ impl Plugin for PointLightPlugin {
             // Schedule system
             //
             .add_systems(Update, update_point_light_system);
+
+        // Class registry
+        register_class(app, GraniteTypes::PointLightData(Default::default()));
     }
 }
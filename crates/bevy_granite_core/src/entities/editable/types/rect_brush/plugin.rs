@@ -1,6 +1,7 @@
 use crate::RectBrush;
 
 use super::{update_rectangle_brush_system, UserUpdatedRectBrushEvent};
+use crate::{register_class, GraniteTypes};
 use bevy::app::{App, Plugin, Update};
 
 pub struct RectBrushPlugin;
@@ -19,5 +20,8 @@ impl Plugin for RectBrushPlugin {
             // Schedule system
             //
             .add_systems(Update, update_rectangle_brush_system);
+
+        // Class registry
+        register_class(app, GraniteTypes::RectBrush(Default::default()));
     }
 }
@@ -0,0 +1,209 @@
+use super::spline_mesh::{sample_spline, SplinePoint};
+use crate::{
+    entities::{
+        editable::{GraniteType, RequestEntityUpdateFromClass},
+        EntitySaveReadyData,
+    },
+    AvailableEditableMaterials, ClassCategory, PromptData,
+};
+use bevy::{
+    asset::{AssetServer, Assets},
+    ecs::{
+        entity::Entity,
+        message::Message,
+        system::{Commands, Res, ResMut},
+    },
+    math::Vec3,
+    mesh::Mesh,
+    pbr::StandardMaterial,
+    reflect::Reflect,
+    transform::components::{GlobalTransform, Transform},
+};
+use bevy_egui::egui;
+use serde::{Deserialize, Serialize};
+
+pub mod creation;
+pub mod path;
+pub mod plugin;
+pub mod ui;
+pub mod update_event;
+
+pub use path::*;
+pub use plugin::*;
+pub use update_event::*;
+
+/// Internal event thats called when user edits UI spline variables
+#[derive(Message)]
+pub struct UserUpdatedSpline3DEvent {
+    pub entity: Entity,
+    pub data: Spline3D,
+}
+
+/// How a spline gets from one control point to the next
+#[derive(Serialize, Deserialize, Reflect, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SplineInterpolation {
+    /// Straight lines, i.e. for patrol paths between fixed spots
+    Linear,
+    /// Smooth curve through every point
+    #[default]
+    CatmullRom,
+}
+
+impl SplineInterpolation {
+    pub fn all() -> [SplineInterpolation; 2] {
+        [SplineInterpolation::Linear, SplineInterpolation::CatmullRom]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SplineInterpolation::Linear => "Linear",
+            SplineInterpolation::CatmullRom => "Catmull-Rom",
+        }
+    }
+}
+
+/// Actual serialized class data thats stored inside IdentityData
+/// An ordered path of control points for patrols, camera rails or road generation. Nothing is drawn in game,
+/// sample it with the Splines resource, i.e. `splines.by_name("patrol").map(|path| path.position_at(distance))`
+#[derive(Serialize, Deserialize, Reflect, Debug, Clone, PartialEq)]
+pub struct Spline3D {
+    /// Control points, relative to the entity
+    pub points: Vec<Vec3>,
+    pub interpolation: SplineInterpolation,
+    /// Connect the last point back to the first
+    pub closed: bool,
+    /// Samples between two control points of a curved spline
+    pub resolution: u32,
+}
+
+impl Default for Spline3D {
+    fn default() -> Self {
+        Self {
+            // Kept off the origin so no point sits under the transform gizmo
+            points: vec![
+                Vec3::new(-5.0, 0.0, 0.0),
+                Vec3::new(0.0, 0.0, -5.0),
+                Vec3::new(5.0, 0.0, 0.0),
+            ],
+            interpolation: SplineInterpolation::CatmullRom,
+            closed: false,
+            resolution: 16,
+        }
+    }
+}
+
+impl Spline3D {
+    /// Positions along the spline relative to the entity, from the first point to the last
+    /// Closed splines end back on the first point
+    pub fn sample_local(&self) -> Vec<Vec3> {
+        match self.interpolation {
+            SplineInterpolation::Linear => {
+                let mut positions = self.points.clone();
+                if self.closed && self.points.len() > 2 {
+                    positions.push(self.points[0]);
+                }
+                positions
+            }
+            SplineInterpolation::CatmullRom => {
+                let points: Vec<SplinePoint> = self
+                    .points
+                    .iter()
+                    .map(|position| SplinePoint::new(*position, 0.0))
+                    .collect();
+                sample_spline(
+                    &points,
+                    self.closed && self.points.len() > 2,
+                    self.resolution,
+                )
+                .into_iter()
+                .map(|sample| sample.position)
+                .collect()
+            }
+        }
+    }
+
+    /// The spline sampled in world space, to be walked by distance
+    pub fn path(&self, transform: &GlobalTransform) -> SplinePath {
+        let world = transform.affine();
+        SplinePath::new(
+            self.sample_local()
+                .into_iter()
+                .map(|position| world.transform_point3(position))
+                .collect(),
+        )
+    }
+
+    /// Add a point after `index`, halfway to the next point or continuing past the last one
+    /// Returns where the new point is
+    pub fn insert_after(&mut self, index: usize) -> usize {
+        let point = match (self.points.get(index), self.points.get(index + 1)) {
+            (Some(current), Some(next)) => current.lerp(*next, 0.5),
+            (Some(last), None) if self.closed && self.points.len() > 2 => {
+                last.lerp(self.points[0], 0.5)
+            }
+            (Some(last), None) => match index.checked_sub(1).map(|before| self.points[before]) {
+                Some(before) => *last + (*last - before),
+                None => *last + Vec3::NEG_Z * 5.0,
+            },
+            _ => Vec3::ZERO,
+        };
+        let index = (index + 1).min(self.points.len());
+        self.points.insert(index, point);
+        index
+    }
+}
+
+impl GraniteType for Spline3D {
+    fn type_name(&self) -> String {
+        "Spline 3D".to_string()
+    }
+
+    fn type_abv(&self) -> String {
+        "Path".to_string()
+    }
+
+    fn category(&self) -> ClassCategory {
+        ClassCategory::Gameplay
+    }
+
+    fn get_embedded_icon_bytes(&self) -> Option<&'static [u8]> {
+        Some(include_bytes!("Spline3D.png"))
+    }
+
+    fn get_icon_filename(&self) -> Option<&'static str> {
+        Some("Spline3D.png")
+    }
+
+    fn spawn_from_new_identity(
+        &mut self,
+        commands: &mut Commands,
+        transform: Transform,
+        _standard_materials: ResMut<Assets<StandardMaterial>>,
+        _meshes: ResMut<Assets<Mesh>>,
+        _available_materials: ResMut<AvailableEditableMaterials>,
+        _asset_server: Res<AssetServer>,
+        _maybe_prompt_data: Option<PromptData>,
+    ) -> Entity {
+        Spline3D::spawn_from_new_identity(self, commands, transform)
+    }
+
+    fn spawn_from_save_data(
+        &self,
+        save_data: &EntitySaveReadyData,
+        commands: &mut Commands,
+        _standard_materials: &mut ResMut<Assets<StandardMaterial>>,
+        _meshes: &mut ResMut<Assets<Mesh>>,
+        _available_materials: &mut ResMut<AvailableEditableMaterials>,
+        _asset_server: &Res<AssetServer>,
+    ) -> Entity {
+        Spline3D::spawn_from_save_data(save_data, commands)
+    }
+
+    fn push_to_entity(&self, entity: Entity, request_update: &mut RequestEntityUpdateFromClass) {
+        self.push_to_entity(entity, request_update)
+    }
+
+    fn edit_via_ui(&mut self, ui: &mut egui::Ui, spacing: (f32, f32, f32)) -> bool {
+        self.edit_via_ui(ui, spacing)
+    }
+}
@@ -0,0 +1,203 @@
+use crate::{GraniteTypes, IdentityData};
+use bevy::{
+    ecs::{
+        change_detection::DetectChanges,
+        entity::Entity,
+        resource::Resource,
+        system::{Query, ResMut},
+        world::Ref,
+    },
+    math::Vec3,
+    transform::components::{GlobalTransform, Transform},
+};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// A spline sampled in world space, walked by distance from its first point
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SplinePath {
+    positions: Vec<Vec3>,
+    /// Distance of each position from the first one
+    distances: Vec<f32>,
+}
+
+impl SplinePath {
+    pub fn new(positions: Vec<Vec3>) -> Self {
+        let mut distances = Vec::with_capacity(positions.len());
+        let mut distance = 0.0;
+        for (index, position) in positions.iter().enumerate() {
+            if index > 0 {
+                distance += positions[index - 1].distance(*position);
+            }
+            distances.push(distance);
+        }
+        Self {
+            positions,
+            distances,
+        }
+    }
+
+    pub fn length(&self) -> f32 {
+        self.distances.last().copied().unwrap_or(0.0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.positions.len() < 2
+    }
+
+    pub fn positions(&self) -> &[Vec3] {
+        &self.positions
+    }
+
+    /// Position at a distance along the path, clamped to its ends
+    pub fn position_at(&self, distance: f32) -> Vec3 {
+        match self.positions.as_slice() {
+            [] => Vec3::ZERO,
+            [only] => *only,
+            _ => {
+                let (index, blend) = self.segment_at(distance);
+                self.positions[index].lerp(self.positions[index + 1], blend)
+            }
+        }
+    }
+
+    /// Direction of travel at a distance along the path. Zero when the path has no length
+    pub fn tangent_at(&self, distance: f32) -> Vec3 {
+        if self.is_empty() {
+            return Vec3::ZERO;
+        }
+        let (index, _) = self.segment_at(distance);
+        (self.positions[index + 1] - self.positions[index]).normalize_or_zero()
+    }
+
+    /// Position at `progress` of the length, 0 is the first point and 1 the last
+    pub fn sample(&self, progress: f32) -> Vec3 {
+        self.position_at(progress * self.length())
+    }
+
+    /// Transform at a distance along the path, facing the direction of travel with Y up
+    pub fn transform_at(&self, distance: f32) -> Transform {
+        let position = self.position_at(distance);
+        let tangent = self.tangent_at(distance);
+        let transform = Transform::from_translation(position);
+        if tangent == Vec3::ZERO {
+            transform
+        } else {
+            transform.looking_to(tangent, Vec3::Y)
+        }
+    }
+
+    /// Distance along the path of its closest position to `point`, i.e. to pick up a patrol where the NPC stands
+    pub fn closest_distance(&self, point: Vec3) -> f32 {
+        let mut closest = (f32::MAX, 0.0);
+        for index in 0..self.positions.len().saturating_sub(1) {
+            let (a, b) = (self.positions[index], self.positions[index + 1]);
+            let segment = b - a;
+            let length_squared = segment.length_squared();
+            let blend = if length_squared > 0.0 {
+                ((point - a).dot(segment) / length_squared).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            let distance_squared = point.distance_squared(a + segment * blend);
+            if distance_squared < closest.0 {
+                let along = self.distances[index]
+                    + (self.distances[index + 1] - self.distances[index]) * blend;
+                closest = (distance_squared, along);
+            }
+        }
+        closest.1
+    }
+
+    /// Closest position on the path to `point`
+    pub fn closest_point(&self, point: Vec3) -> Vec3 {
+        self.position_at(self.closest_distance(point))
+    }
+
+    /// Segment a distance falls on and how far along it, 0 to 1. Needs at least two positions
+    fn segment_at(&self, distance: f32) -> (usize, f32) {
+        let distance = distance.clamp(0.0, self.length());
+        let index = self
+            .distances
+            .partition_point(|along| *along <= distance)
+            .clamp(1, self.positions.len() - 1)
+            - 1;
+        let span = self.distances[index + 1] - self.distances[index];
+        let blend = if span > 0.0 {
+            (distance - self.distances[index]) / span
+        } else {
+            0.0
+        };
+        (index, blend)
+    }
+}
+
+/// Every Spline3D in the world sampled in world space, refreshed after transforms propagate
+/// Names are not unique, `by_name` returns the first spline with that name
+#[derive(Resource, Default, Debug, Clone)]
+pub struct Splines {
+    paths: HashMap<Entity, SplinePath>,
+    by_name: BTreeMap<String, Vec<Entity>>,
+}
+
+impl Splines {
+    pub fn get(&self, entity: Entity) -> Option<&SplinePath> {
+        self.paths.get(&entity)
+    }
+
+    pub fn by_name(&self, name: &str) -> Option<&SplinePath> {
+        self.entity(name).and_then(|entity| self.get(entity))
+    }
+
+    /// First spline entity with this name
+    pub fn entity(&self, name: &str) -> Option<Entity> {
+        self.by_name
+            .get(name)
+            .and_then(|entities| entities.first())
+            .copied()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, &SplinePath)> {
+        self.paths.iter().map(|(entity, path)| (*entity, path))
+    }
+
+    pub fn len(&self) -> usize {
+        self.paths.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+}
+
+/// Samples splines again only when their class data or transform changed
+pub fn update_splines_system(
+    mut splines: ResMut<Splines>,
+    query: Query<(Entity, Ref<IdentityData>, Ref<GlobalTransform>)>,
+) {
+    let mut by_name: BTreeMap<String, Vec<Entity>> = BTreeMap::new();
+    let mut seen = HashSet::new();
+    for (entity, identity, transform) in query.iter() {
+        let GraniteTypes::Spline3D(spline) = &identity.class else {
+            continue;
+        };
+        seen.insert(entity);
+        by_name
+            .entry(identity.name.clone())
+            .or_default()
+            .push(entity);
+        if identity.is_changed() || transform.is_changed() || !splines.paths.contains_key(&entity) {
+            let path = spline.path(&transform);
+            splines.paths.insert(entity, path);
+        }
+    }
+    if splines.paths.len() != seen.len() {
+        splines.paths.retain(|entity, _| seen.contains(entity));
+    }
+    // Query order isn't stable, keep duplicates in a fixed order
+    for entities in by_name.values_mut() {
+        entities.sort();
+    }
+    if splines.by_name != by_name {
+        splines.by_name = by_name;
+    }
+}
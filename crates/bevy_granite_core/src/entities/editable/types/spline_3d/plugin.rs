@@ -0,0 +1,37 @@
+use super::{
+    update_spline_3d_system, update_splines_system, Spline3D, Splines, UserUpdatedSpline3DEvent,
+};
+use crate::{register_class, GraniteTypes};
+use bevy::app::{App, Plugin, PostUpdate, Update};
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::transform::TransformSystems;
+
+pub struct Spline3DPlugin;
+impl Plugin for Spline3DPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            //
+            // Event
+            //
+            .add_message::<UserUpdatedSpline3DEvent>()
+            //
+            // Register
+            //
+            .register_type::<Spline3D>()
+            //
+            // Resources
+            //
+            .init_resource::<Splines>()
+            //
+            // Schedule system
+            //
+            .add_systems(Update, update_spline_3d_system)
+            .add_systems(
+                PostUpdate,
+                update_splines_system.after(TransformSystems::Propagate),
+            );
+
+        // Class registry
+        register_class(app, GraniteTypes::Spline3D(Default::default()));
+    }
+}
@@ -0,0 +1,106 @@
+use super::{Spline3D, SplineInterpolation};
+use bevy::transform::components::GlobalTransform;
+use bevy_egui::egui;
+
+impl Spline3D {
+    /// Function to edit self's data via UI side panel
+    /// Points can also be dragged in the viewport while the spline is the active selection
+    pub fn edit_via_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        // Small, Large, Normal
+        spacing: (f32, f32, f32),
+    ) -> bool {
+        let large_spacing = spacing.1;
+        let mut changed = false;
+
+        ui.label(egui::RichText::new("Spline 3D").italics());
+        ui.add_space(large_spacing);
+
+        egui::Grid::new("spline_3d_grid")
+            .num_columns(2)
+            .spacing([large_spacing, large_spacing])
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Interpolation:");
+                egui::ComboBox::from_id_salt("spline_3d_interpolation")
+                    .selected_text(self.interpolation.name())
+                    .show_ui(ui, |ui| {
+                        for interpolation in SplineInterpolation::all() {
+                            changed |= ui
+                                .selectable_value(
+                                    &mut self.interpolation,
+                                    interpolation,
+                                    interpolation.name(),
+                                )
+                                .changed();
+                        }
+                    });
+                ui.end_row();
+
+                ui.label("Closed:");
+                changed |= ui.checkbox(&mut self.closed, "").changed();
+                ui.end_row();
+
+                ui.label("Resolution:");
+                changed |= ui
+                    .add_enabled(
+                        self.interpolation == SplineInterpolation::CatmullRom,
+                        egui::DragValue::new(&mut self.resolution).range(1..=64),
+                    )
+                    .on_hover_text("Samples between two control points")
+                    .changed();
+                ui.end_row();
+            });
+        ui.add_space(large_spacing);
+
+        ui.label(
+            egui::RichText::new(format!(
+                "Length {:.1}",
+                self.path(&GlobalTransform::IDENTITY).length()
+            ))
+            .weak(),
+        );
+        ui.add_space(large_spacing);
+
+        ui.label(format!("Points ({})", self.points.len()));
+        let mut remove = None;
+        let mut insert = None;
+        for (index, point) in self.points.iter_mut().enumerate() {
+            ui.push_id(index, |ui| {
+                ui.horizontal(|ui| {
+                    ui.monospace(format!("{:>2}", index));
+                    for axis in [&mut point.x, &mut point.y, &mut point.z] {
+                        changed |= ui
+                            .add(egui::DragValue::new(axis).speed(0.1).fixed_decimals(2))
+                            .changed();
+                    }
+                    if ui
+                        .small_button("+")
+                        .on_hover_text("Insert a point after this one")
+                        .clicked()
+                    {
+                        insert = Some(index);
+                    }
+                    if ui.small_button("Remove").clicked() {
+                        remove = Some(index);
+                    }
+                });
+            });
+        }
+        if let Some(index) = remove {
+            self.points.remove(index);
+            changed = true;
+        }
+        if let Some(index) = insert {
+            self.insert_after(index);
+            changed = true;
+        }
+        if ui.button("Add Point").clicked() {
+            self.insert_after(self.points.len().saturating_sub(1));
+            changed = true;
+        }
+        ui.add_space(large_spacing);
+        changed
+    }
+}
@@ -0,0 +1,43 @@
+use crate::entities::editable::{RequestEntityUpdateFromClass, UserUpdatedSpline3DEvent};
+use crate::entities::Spline3D;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::message::MessageReader;
+use bevy_granite_logging::{log, LogCategory, LogLevel, LogType};
+
+impl Spline3D {
+    pub fn push_to_entity(
+        &self,
+        entity: Entity,
+        request_update: &mut RequestEntityUpdateFromClass,
+    ) {
+        log!(
+            LogType::Editor,
+            LogLevel::Info,
+            LogCategory::Entity,
+            "Requesting spline entity update"
+        );
+
+        request_update.spline_3d.write(UserUpdatedSpline3DEvent {
+            entity,
+            data: self.clone(),
+        });
+    }
+}
+
+/// The Splines resource samples IdentityData again when it changes, so edits apply on their own
+pub fn update_spline_3d_system(mut reader: MessageReader<UserUpdatedSpline3DEvent>) {
+    for UserUpdatedSpline3DEvent {
+        entity: requested_entity,
+        data: new,
+    } in reader.read()
+    {
+        log!(
+            LogType::Editor,
+            LogLevel::Info,
+            LogCategory::Entity,
+            "Heard spline update event: {} now has {} points",
+            requested_entity,
+            new.points.len()
+        );
+    }
+}
@@ -0,0 +1,190 @@
+use super::{BakedSplineMesh, SplineMesh, SplineMeshDirty};
+use crate::{
+    entities::EntitySaveReadyData, rel_asset_to_absolute, AvailableEditableMaterials,
+    GraniteEditorSerdeEntity, GraniteType, GraniteTypes, HasRuntimeData, IdentityData,
+    NeedsTangents,
+};
+use bevy::{
+    asset::{AssetServer, Assets, Handle},
+    ecs::{
+        bundle::Bundle,
+        entity::Entity,
+        system::{Commands, Res, ResMut},
+    },
+    mesh::{Mesh, Mesh3d},
+    pbr::{MeshMaterial3d, StandardMaterial},
+    prelude::Name,
+    transform::components::Transform,
+};
+use std::path::Path;
+use uuid::Uuid;
+
+impl SplineMesh {
+    /// Extract needed info to spawn this entity via save data
+    pub fn spawn_from_save_data(
+        save_data: &EntitySaveReadyData,
+        commands: &mut Commands,
+        standard_materials: &mut ResMut<Assets<StandardMaterial>>,
+        available_materials: &mut ResMut<AvailableEditableMaterials>,
+        asset_server: &Res<AssetServer>,
+        meshes: &mut ResMut<Assets<Mesh>>,
+    ) -> Entity {
+        let identity = &save_data.identity;
+        let save_transform = &save_data.transform;
+
+        Self::spawn_from_identity(
+            commands,
+            identity,
+            save_transform.to_bevy(),
+            standard_materials,
+            available_materials,
+            asset_server,
+            meshes,
+        )
+    }
+
+    /// Take the name and class from identity to spawn
+    pub fn spawn_from_identity(
+        commands: &mut Commands,
+        identity: &IdentityData,
+        transform: Transform,
+        standard_materials: &mut ResMut<Assets<StandardMaterial>>,
+        available_materials: &mut ResMut<AvailableEditableMaterials>,
+        asset_server: &Res<AssetServer>,
+        meshes: &mut ResMut<Assets<Mesh>>,
+    ) -> Entity {
+        let mut class = Self::extract_class(identity);
+
+        // Load and configure the material
+        class.load_and_configure_material(available_materials, standard_materials, asset_server);
+        class.spawn(identity, commands, transform, asset_server, meshes)
+    }
+
+    /// Generally to be used from UI popups as it gives default name
+    pub fn spawn_from_new_identity(
+        &mut self,
+        commands: &mut Commands,
+        transform: Transform,
+        standard_materials: &mut ResMut<Assets<StandardMaterial>>,
+        available_materials: &mut ResMut<AvailableEditableMaterials>,
+        asset_server: &Res<AssetServer>,
+        meshes: &mut ResMut<Assets<Mesh>>,
+    ) -> Entity {
+        // A new entity can't use another one's bake
+        self.baked_mesh = None;
+        let identity = IdentityData {
+            name: self.type_name(),
+            uuid: Uuid::new_v4(),
+            class: GraniteTypes::SplineMesh(self.clone()),
+        };
+        self.load_and_configure_material(available_materials, standard_materials, asset_server);
+        self.spawn(&identity, commands, transform, asset_server, meshes)
+    }
+
+    /// Private core logic
+    fn spawn(
+        &mut self,
+        identity: &IdentityData,
+        commands: &mut Commands,
+        transform: Transform,
+        asset_server: &Res<AssetServer>,
+        meshes: &mut ResMut<Assets<Mesh>>,
+    ) -> Entity {
+        // Prefer the bake from the last save, generating needs the ground to be loaded when conforming
+        let baked = self
+            .baked_mesh
+            .as_ref()
+            .filter(|path| Path::new(rel_asset_to_absolute(path).as_ref()).exists())
+            .map(|path| asset_server.load::<Mesh>(path.clone()));
+        let is_baked = baked.is_some();
+        let mesh_handle = baked.unwrap_or_else(|| meshes.add(self.create_mesh()));
+
+        let mut entity = commands.spawn(Self::get_bundle(
+            self.clone(), // Material is fixed up by the callers
+            identity.clone(),
+            transform,
+            mesh_handle,
+        ));
+        if is_baked {
+            entity.insert(BakedSplineMesh);
+        } else if self.conform_to_ground {
+            entity.insert(SplineMeshDirty);
+        }
+        entity.id()
+    }
+
+    /// Build a bundle that is ready to spawn from a spline mesh
+    fn get_bundle(
+        spline_mesh: SplineMesh,
+        identity: IdentityData,
+        transform: Transform,
+        mesh_handle: Handle<Mesh>,
+    ) -> impl Bundle {
+        (
+            transform,
+            Mesh3d(mesh_handle),
+            MeshMaterial3d(
+                spline_mesh
+                    .material
+                    .current
+                    .handle
+                    .clone()
+                    .expect("Default material should always have a handle"),
+            ),
+            Name::new(identity.name.clone()),
+            HasRuntimeData,
+            GraniteEditorSerdeEntity,
+            NeedsTangents,
+            IdentityData {
+                name: identity.name.clone(),
+                uuid: identity.uuid,
+                class: GraniteTypes::SplineMesh(spline_mesh), // Use the updated spline mesh, not the old identity.class
+            },
+        )
+    }
+
+    fn extract_class(identity: &IdentityData) -> SplineMesh {
+        match &identity.class {
+            GraniteTypes::SplineMesh(spline_data) => spline_data.clone(),
+            _ => panic!("Expected SplineMesh class data, got different type from save data"),
+        }
+    }
+
+    /// Load and configure material with proper metadata
+    /// Uses a static friendly name and internal material as fallback as default spline meshes share the same material
+    fn load_and_configure_material(
+        &mut self,
+        available_materials: &mut ResMut<AvailableEditableMaterials>,
+        standard_materials: &mut ResMut<Assets<StandardMaterial>>,
+        asset_server: &Res<AssetServer>,
+    ) {
+        let fallback_path = Self::internal_material_path();
+
+        // Use saved material path if available, otherwise use fallback
+        let material_path = if !self.material.path.is_empty() {
+            self.material.path.clone()
+        } else {
+            fallback_path.clone()
+        };
+
+        // Set the path on the current material before loading
+        self.material.current.path = material_path.clone();
+
+        let _created_new = self.material.current.material_exists_and_load(
+            available_materials,
+            standard_materials,
+            asset_server,
+            &self.type_name(),
+            &material_path,
+        );
+
+        // Fix the material metadata after loading (since loaded materials have "None" path)
+        self.material.current.path = material_path;
+        self.material.current.friendly_name = self.type_name();
+        self.material.last = self.material.current.clone();
+
+        if self.material.path.is_empty() {
+            self.material.path = fallback_path;
+        }
+    }
+}
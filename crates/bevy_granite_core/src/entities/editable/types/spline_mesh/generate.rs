@@ -0,0 +1,294 @@
+use super::{SplineMesh, SplinePoint, SplineProfile};
+use bevy::{
+    asset::RenderAssetUsages,
+    math::{Affine3A, Vec2, Vec3, Vec3Swizzles},
+    mesh::{Indices, Mesh, PrimitiveTopology, VertexAttributeValues},
+};
+use bevy_granite_logging::{log, LogCategory, LogLevel, LogType};
+use std::{fmt::Write as _, fs, path::Path};
+
+/// Rays used to conform a spline start this far above each sample
+pub const GROUND_RAY_HEIGHT: f32 = 50.0;
+
+/// Point on the sampled spline, in the space of the control points
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SplineSample {
+    pub position: Vec3,
+    pub width: f32,
+}
+
+fn catmull_rom(p0: Vec3, p1: Vec3, p2: Vec3, p3: Vec3, t: f32) -> Vec3 {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    0.5 * ((2.0 * p1)
+        + (p2 - p0) * t
+        + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * t2
+        + (3.0 * p1 - p0 - 3.0 * p2 + p3) * t3)
+}
+
+/// Sample a Catmull-Rom spline through the control points. Widths are interpolated linearly
+/// Closed splines repeat the first sample at the end so the seam gets its own UVs
+pub fn sample_spline(points: &[SplinePoint], closed: bool, resolution: u32) -> Vec<SplineSample> {
+    let count = points.len();
+    if count < 2 {
+        return vec![];
+    }
+
+    let resolution = resolution.max(1);
+    let point = |index: isize| -> Vec3 {
+        if closed {
+            points[index.rem_euclid(count as isize) as usize].position
+        } else if index < 0 {
+            // Mirror the ends so the curve leaves straight
+            2.0 * points[0].position - points[1].position
+        } else if index as usize >= count {
+            2.0 * points[count - 1].position - points[count - 2].position
+        } else {
+            points[index as usize].position
+        }
+    };
+
+    let segments = if closed { count } else { count - 1 };
+    let mut samples = Vec::with_capacity(segments * resolution as usize + 1);
+    for segment in 0..segments {
+        let index = segment as isize;
+        let (p0, p1, p2, p3) = (
+            point(index - 1),
+            point(index),
+            point(index + 1),
+            point(index + 2),
+        );
+        let (width_from, width_to) = (points[segment].width, points[(segment + 1) % count].width);
+
+        for step in 0..resolution {
+            let t = step as f32 / resolution as f32;
+            samples.push(SplineSample {
+                position: catmull_rom(p0, p1, p2, p3, t),
+                width: width_from + (width_to - width_from) * t,
+            });
+        }
+    }
+
+    let last = if closed { points[0] } else { points[count - 1] };
+    samples.push(SplineSample {
+        position: last.position,
+        width: last.width,
+    });
+    samples
+}
+
+/// Move samples onto the ground. `ground_height` takes and returns world space, samples without ground stay put
+pub fn conform_samples(
+    samples: &mut [SplineSample],
+    to_world: &Affine3A,
+    offset: f32,
+    ground_height: impl Fn(Vec3) -> Option<f32>,
+) {
+    let to_local = to_world.inverse();
+    for sample in samples.iter_mut() {
+        let mut world = to_world.transform_point3(sample.position);
+        if let Some(height) = ground_height(world) {
+            world.y = height + offset;
+            sample.position = to_local.transform_point3(world);
+        }
+    }
+}
+
+/// Cross section of a profile as (across, up) offsets, left to right
+/// The last point equals the first for closed profiles, so the seam gets its own UVs
+pub fn profile_cross_section(profile: SplineProfile, width: f32) -> Vec<Vec2> {
+    let half = width * 0.5;
+    match profile {
+        SplineProfile::Road => {
+            let shoulder = width * 0.1;
+            vec![
+                Vec2::new(-half - shoulder, -shoulder),
+                Vec2::new(-half, 0.0),
+                Vec2::new(half, 0.0),
+                Vec2::new(half + shoulder, -shoulder),
+            ]
+        }
+        SplineProfile::River => vec![
+            Vec2::new(-half, 0.0),
+            Vec2::new(-half * 0.5, -width * 0.15),
+            Vec2::new(0.0, -width * 0.2),
+            Vec2::new(half * 0.5, -width * 0.15),
+            Vec2::new(half, 0.0),
+        ],
+        SplineProfile::Pipe => {
+            const SIDES: usize = 16;
+            // Starts at the top going right, so the faces point out. Sits on the spline
+            (0..=SIDES)
+                .map(|side| {
+                    let angle = std::f32::consts::FRAC_PI_2
+                        - std::f32::consts::TAU * side as f32 / SIDES as f32;
+                    Vec2::new(angle.cos() * half, angle.sin() * half + half)
+                })
+                .collect()
+        }
+    }
+}
+
+/// Extrude the profile along the samples. U goes across the profile, V along the spline scaled by uv_tiling
+pub fn build_spline_mesh(samples: &[SplineSample], profile: SplineProfile, uv_tiling: f32) -> Mesh {
+    let mut positions: Vec<[f32; 3]> = vec![];
+    let mut uvs: Vec<[f32; 2]> = vec![];
+    let mut indices: Vec<u32> = vec![];
+
+    let mut distance = 0.0;
+    let mut ring_size = 0;
+    for (index, sample) in samples.iter().enumerate() {
+        if index > 0 {
+            distance += sample.position.distance(samples[index - 1].position);
+        }
+        let previous = samples[index.saturating_sub(1)].position;
+        let next = samples[(index + 1).min(samples.len() - 1)].position;
+        let forward = (next - previous).normalize_or(Vec3::NEG_Z);
+        let right = forward.cross(Vec3::Y).normalize_or(Vec3::X);
+        let up = right.cross(forward).normalize_or(Vec3::Y);
+
+        let section = profile_cross_section(profile, sample.width);
+        ring_size = section.len();
+        for (column, offset) in section.iter().enumerate() {
+            let position = sample.position + right * offset.x + up * offset.y;
+            positions.push(position.to_array());
+            uvs.push([
+                column as f32 / (section.len() - 1) as f32,
+                distance * uv_tiling,
+            ]);
+        }
+    }
+
+    for ring in 1..samples.len() {
+        let (back, front) = ((ring - 1) * ring_size, ring * ring_size);
+        for column in 0..ring_size.saturating_sub(1) {
+            let (a, b) = ((back + column) as u32, (back + column + 1) as u32);
+            let (c, d) = ((front + column) as u32, (front + column + 1) as u32);
+            indices.extend_from_slice(&[a, b, c, b, d, c]);
+        }
+    }
+
+    let has_faces = !indices.is_empty();
+    let mut mesh = Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    );
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    mesh.insert_indices(Indices::U32(indices));
+    if has_faces {
+        mesh.compute_smooth_normals();
+        if let Err(e) = mesh.generate_tangents() {
+            log!(
+                LogType::Game,
+                LogLevel::Error,
+                LogCategory::Entity,
+                "Failed to generate spline mesh tangents: {:?}",
+                e
+            );
+        }
+    }
+    mesh
+}
+
+impl SplineMesh {
+    /// Generate the mesh from the control points, without conforming
+    pub fn create_mesh(&self) -> Mesh {
+        let samples = sample_spline(&self.points, self.closed, self.resolution);
+        build_spline_mesh(&samples, self.profile, self.uv_tiling)
+    }
+}
+
+/// Triangles of a scene mesh in world space, used to conform splines to it
+pub fn mesh_world_triangles(mesh: &Mesh, to_world: &Affine3A) -> Vec<[Vec3; 3]> {
+    let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+    else {
+        return vec![];
+    };
+    let positions: Vec<Vec3> = positions
+        .iter()
+        .map(|position| to_world.transform_point3(Vec3::from(*position)))
+        .collect();
+
+    let indices: Vec<usize> = match mesh.indices() {
+        Some(indices) => indices.iter().collect(),
+        None => (0..positions.len()).collect(),
+    };
+    indices
+        .chunks_exact(3)
+        .filter_map(|triangle| {
+            Some([
+                *positions.get(triangle[0])?,
+                *positions.get(triangle[1])?,
+                *positions.get(triangle[2])?,
+            ])
+        })
+        .collect()
+}
+
+/// Height of the highest triangle straight below `point`, within GROUND_RAY_HEIGHT above it
+pub fn ground_height(triangles: &[[Vec3; 3]], point: Vec3) -> Option<f32> {
+    let top = point.y + GROUND_RAY_HEIGHT;
+    let target = Vec2::new(point.x, point.z);
+    triangles
+        .iter()
+        .filter_map(|[a, b, c]| {
+            // Barycentric coordinates on the xz plane
+            let (a2, b2, c2) = (a.xz(), b.xz(), c.xz());
+            let denominator = (b2 - a2).perp_dot(c2 - a2);
+            if denominator.abs() < f32::EPSILON {
+                return None;
+            }
+            let v = (target - a2).perp_dot(c2 - a2) / denominator;
+            let w = (b2 - a2).perp_dot(target - a2) / denominator;
+            let u = 1.0 - v - w;
+            if u < 0.0 || v < 0.0 || w < 0.0 {
+                return None;
+            }
+            Some(a.y * u + b.y * v + c.y * w)
+        })
+        .filter(|height| *height <= top)
+        .reduce(f32::max)
+}
+
+/// Write a generated mesh as obj. V is flipped, obj textures start at the bottom
+pub fn write_mesh_obj(mesh: &Mesh, path: &Path) -> std::io::Result<()> {
+    let invalid =
+        |what: &str| std::io::Error::new(std::io::ErrorKind::InvalidData, what.to_string());
+    let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+    else {
+        return Err(invalid("mesh has no positions"));
+    };
+    let Some(indices) = mesh.indices() else {
+        return Err(invalid("mesh has no indices"));
+    };
+
+    let mut obj = String::from("# Baked by bevy_granite\n");
+    for [x, y, z] in positions.iter() {
+        let _ = writeln!(obj, "v {} {} {}", x, y, z);
+    }
+    if let Some(VertexAttributeValues::Float32x2(uvs)) = mesh.attribute(Mesh::ATTRIBUTE_UV_0) {
+        for [u, v] in uvs.iter() {
+            let _ = writeln!(obj, "vt {} {}", u, 1.0 - v);
+        }
+    }
+    if let Some(VertexAttributeValues::Float32x3(normals)) = mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
+    {
+        for [x, y, z] in normals.iter() {
+            let _ = writeln!(obj, "vn {} {} {}", x, y, z);
+        }
+    }
+    let indices: Vec<usize> = indices.iter().collect();
+    for triangle in indices.chunks_exact(3) {
+        // Obj indices start at 1
+        let (a, b, c) = (triangle[0] + 1, triangle[1] + 1, triangle[2] + 1);
+        let _ = writeln!(obj, "f {a}/{a}/{a} {b}/{b}/{b} {c}/{c}/{c}");
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, obj)
+}
@@ -0,0 +1,251 @@
+use crate::{
+    assets::EditableMaterial,
+    entities::{
+        editable::{
+            GraniteType, RequestEntityUpdateFromClass, RequiredMaterialData,
+            RequiredMaterialDataMut,
+        },
+        EntitySaveReadyData, PromptData,
+    },
+    AvailableEditableMaterials, ClassCategory, MaterialData,
+};
+use bevy::{
+    asset::{AssetServer, Assets},
+    ecs::{
+        component::Component,
+        entity::Entity,
+        message::Message,
+        system::{Commands, Res, ResMut},
+    },
+    math::Vec3,
+    mesh::Mesh,
+    pbr::StandardMaterial,
+    prelude::Reflect,
+    transform::components::Transform,
+};
+use bevy_egui::egui;
+use serde::{Deserialize, Serialize};
+
+pub mod creation;
+pub mod generate;
+pub mod plugin;
+pub mod ui;
+pub mod update_event;
+
+pub use generate::*;
+pub use plugin::*;
+pub use update_event::*;
+
+/// Internal event thats called when user edits UI spline mesh variables
+#[derive(Message)]
+pub struct UserUpdatedSplineMeshEvent {
+    pub entity: Entity,
+    pub data: SplineMesh,
+}
+
+/// Spline mesh needs to be generated again, i.e. its points changed or it has to conform to ground that wasn't loaded yet
+#[derive(Component, Default)]
+pub struct SplineMeshDirty;
+
+/// Spline mesh is showing its baked asset, it is only generated again once edited
+#[derive(Component, Default)]
+pub struct BakedSplineMesh;
+
+/// Control point of a spline mesh, relative to the entity
+#[derive(Serialize, Deserialize, Reflect, Debug, Clone, Copy, PartialEq)]
+pub struct SplinePoint {
+    pub position: Vec3,
+    /// Profile width at this point, interpolated to the next one
+    pub width: f32,
+}
+
+impl SplinePoint {
+    pub fn new(position: Vec3, width: f32) -> Self {
+        Self { position, width }
+    }
+}
+
+/// Cross section extruded along the spline
+#[derive(Serialize, Deserialize, Reflect, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SplineProfile {
+    /// Flat surface with sloped shoulders
+    #[default]
+    Road,
+    /// Shallow channel
+    River,
+    /// Closed tube, width is the diameter
+    Pipe,
+}
+
+impl SplineProfile {
+    pub fn all() -> [SplineProfile; 3] {
+        [
+            SplineProfile::Road,
+            SplineProfile::River,
+            SplineProfile::Pipe,
+        ]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SplineProfile::Road => "Road",
+            SplineProfile::River => "River",
+            SplineProfile::Pipe => "Pipe",
+        }
+    }
+}
+
+/// Actual serialized class data thats stored inside IdentityData
+/// A mesh extruded along a Catmull-Rom spline through its control points. Generated again when edited
+/// On save the generated mesh is baked to an obj under assets so games can load it without generating
+#[derive(Serialize, Deserialize, Reflect, Debug, Clone, PartialEq)]
+pub struct SplineMesh {
+    pub points: Vec<SplinePoint>,
+    pub profile: SplineProfile,
+    /// Connect the last point back to the first
+    pub closed: bool,
+    /// Samples between two control points
+    pub resolution: u32,
+    /// Texture repeats per unit of length along the spline
+    pub uv_tiling: f32,
+    /// Drop every sample onto the scene meshes below it
+    pub conform_to_ground: bool,
+    /// Height kept above the ground when conforming
+    pub ground_offset: f32,
+    /// Baked obj of the last save, relative to assets
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub baked_mesh: Option<String>,
+    pub material: MaterialData,
+}
+
+impl SplineMesh {
+    // Needed static access to the type name so we define them static-ly here this time
+    pub fn type_name_static() -> String {
+        "Spline Mesh".to_string()
+    }
+
+    pub fn type_abv_static() -> String {
+        "Spline".to_string()
+    }
+
+    pub fn internal_material_path() -> String {
+        "materials/internal/spline_mesh.mat".to_string()
+    }
+
+    /// Where the baked mesh of an entity is written on save, relative to assets
+    pub fn baked_mesh_path(uuid: &uuid::Uuid) -> String {
+        format!("meshes/baked/spline_{}.obj", uuid)
+    }
+}
+
+impl Default for SplineMesh {
+    fn default() -> Self {
+        let (path, name) = (Self::internal_material_path(), Self::type_name_static());
+
+        // Create a material with the internal defaults for spline meshes
+        let mut spline_material = EditableMaterial::get_new_unnamed_base_color();
+        spline_material.update_name(name.clone());
+        spline_material.update_path(path.clone());
+
+        Self {
+            points: vec![
+                SplinePoint::new(Vec3::new(0.0, 0.0, 0.0), 4.0),
+                SplinePoint::new(Vec3::new(0.0, 0.0, -10.0), 4.0),
+                SplinePoint::new(Vec3::new(5.0, 0.0, -20.0), 4.0),
+            ],
+            profile: SplineProfile::Road,
+            closed: false,
+            resolution: 8,
+            uv_tiling: 0.25,
+            conform_to_ground: false,
+            ground_offset: 0.05,
+            baked_mesh: None,
+            material: MaterialData {
+                path: path.clone(),
+                current: spline_material.clone(),
+                last: spline_material.clone(),
+            },
+        }
+    }
+}
+
+impl GraniteType for SplineMesh {
+    fn category(&self) -> ClassCategory {
+        ClassCategory::Mesh
+    }
+
+    fn type_name(&self) -> String {
+        SplineMesh::type_name_static()
+    }
+
+    fn type_abv(&self) -> String {
+        SplineMesh::type_abv_static()
+    }
+
+    fn get_embedded_icon_bytes(&self) -> Option<&'static [u8]> {
+        Some(include_bytes!("SplineMesh.png"))
+    }
+
+    fn get_icon_filename(&self) -> Option<&'static str> {
+        Some("SplineMesh.png")
+    }
+
+    fn spawn_from_new_identity(
+        &mut self,
+        commands: &mut Commands,
+        transform: Transform,
+        mut standard_materials: ResMut<Assets<StandardMaterial>>,
+        mut meshes: ResMut<Assets<Mesh>>,
+        mut available_materials: ResMut<AvailableEditableMaterials>,
+        asset_server: Res<AssetServer>,
+        _maybe_prompt_data: Option<PromptData>,
+    ) -> Entity {
+        self.spawn_from_new_identity(
+            commands,
+            transform,
+            &mut standard_materials,
+            &mut available_materials,
+            &asset_server,
+            &mut meshes,
+        )
+    }
+
+    fn spawn_from_save_data(
+        &self,
+        save_data: &EntitySaveReadyData,
+        commands: &mut Commands,
+        standard_materials: &mut ResMut<Assets<StandardMaterial>>,
+        meshes: &mut ResMut<Assets<Mesh>>,
+        available_materials: &mut ResMut<AvailableEditableMaterials>,
+        asset_server: &Res<AssetServer>,
+    ) -> Entity {
+        SplineMesh::spawn_from_save_data(
+            save_data,
+            commands,
+            standard_materials,
+            available_materials,
+            asset_server,
+            meshes,
+        )
+    }
+
+    fn push_to_entity(&self, entity: Entity, request_update: &mut RequestEntityUpdateFromClass) {
+        self.push_to_entity(entity, request_update)
+    }
+
+    fn edit_via_ui(&mut self, ui: &mut egui::Ui, spacing: (f32, f32, f32)) -> bool {
+        self.edit_via_ui(ui, spacing)
+    }
+
+    fn get_material_data(&self) -> Option<RequiredMaterialData<'_>> {
+        Some(self.material.as_ref())
+    }
+
+    fn get_mut_material_data(&mut self) -> Option<RequiredMaterialDataMut<'_>> {
+        Some(self.material.as_mut())
+    }
+
+    fn needs_unique_handle(&self) -> bool {
+        true
+    }
+}
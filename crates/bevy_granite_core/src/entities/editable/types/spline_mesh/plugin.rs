@@ -0,0 +1,47 @@
+use super::{
+    bake_spline_meshes_system, mark_changed_spline_meshes_system, regenerate_spline_meshes_system,
+    update_spline_mesh_system, SplineMesh, SplinePoint, SplineProfile, UserUpdatedSplineMeshEvent,
+};
+use crate::{register_class, world::save_request_system, GraniteTypes};
+use bevy::app::{App, Plugin, PostUpdate, Update};
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::transform::TransformSystems;
+
+pub struct SplineMeshPlugin;
+impl Plugin for SplineMeshPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            //
+            // Event
+            //
+            .add_message::<UserUpdatedSplineMeshEvent>()
+            //
+            // Register
+            //
+            .register_type::<SplineMesh>()
+            .register_type::<SplinePoint>()
+            .register_type::<SplineProfile>()
+            //
+            // Schedule system
+            //
+            .add_systems(
+                Update,
+                (
+                    update_spline_mesh_system,
+                    bake_spline_meshes_system.before(save_request_system),
+                ),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    mark_changed_spline_meshes_system,
+                    regenerate_spline_meshes_system,
+                )
+                    .chain()
+                    .after(TransformSystems::Propagate),
+            );
+
+        // Class registry
+        register_class(app, GraniteTypes::SplineMesh(Default::default()));
+    }
+}
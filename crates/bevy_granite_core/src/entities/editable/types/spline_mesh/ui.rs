@@ -0,0 +1,123 @@
+use super::{SplineMesh, SplinePoint, SplineProfile};
+use bevy::math::Vec3;
+use bevy_egui::egui;
+
+impl SplineMesh {
+    /// Function to edit self's data via UI side panel
+    /// We have a sister system that pushes changes to world entity - can be found inside 'update_event.rs'
+    /// When true, sends an update to propagate these vars to the world's entity
+    pub fn edit_via_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        // Small, Large, Normal
+        spacing: (f32, f32, f32),
+    ) -> bool {
+        let large_spacing = spacing.1;
+        let mut changed = false;
+
+        ui.label(egui::RichText::new("Spline Mesh Data").italics());
+        ui.add_space(large_spacing);
+
+        egui::Grid::new("spline_mesh_grid")
+            .num_columns(2)
+            .spacing([large_spacing, large_spacing])
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Profile:");
+                egui::ComboBox::from_id_salt("spline_mesh_profile")
+                    .selected_text(self.profile.name())
+                    .show_ui(ui, |ui| {
+                        for profile in SplineProfile::all() {
+                            changed |= ui
+                                .selectable_value(&mut self.profile, profile, profile.name())
+                                .changed();
+                        }
+                    });
+                ui.end_row();
+
+                ui.label("Closed:");
+                changed |= ui.checkbox(&mut self.closed, "").changed();
+                ui.end_row();
+
+                ui.label("Resolution:");
+                changed |= ui
+                    .add(egui::DragValue::new(&mut self.resolution).range(1..=64))
+                    .changed();
+                ui.end_row();
+
+                ui.label("UV Tiling:");
+                changed |= ui
+                    .add(
+                        egui::DragValue::new(&mut self.uv_tiling)
+                            .speed(0.01)
+                            .range(0.0..=f32::MAX),
+                    )
+                    .changed();
+                ui.end_row();
+
+                ui.label("Conform To Ground:");
+                changed |= ui.checkbox(&mut self.conform_to_ground, "").changed();
+                ui.end_row();
+
+                if self.conform_to_ground {
+                    ui.label("Ground Offset:");
+                    changed |= ui
+                        .add(egui::DragValue::new(&mut self.ground_offset).speed(0.01))
+                        .changed();
+                    ui.end_row();
+                }
+            });
+        ui.add_space(large_spacing);
+
+        ui.label(format!("Points ({})", self.points.len()));
+        let mut remove = None;
+        for (index, point) in self.points.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                ui.monospace(format!("{:>2}", index));
+                for axis in [
+                    &mut point.position.x,
+                    &mut point.position.y,
+                    &mut point.position.z,
+                ] {
+                    changed |= ui
+                        .add(egui::DragValue::new(axis).speed(0.1).fixed_decimals(2))
+                        .changed();
+                }
+                ui.label("W");
+                changed |= ui
+                    .add(
+                        egui::DragValue::new(&mut point.width)
+                            .speed(0.05)
+                            .range(0.0..=f32::MAX),
+                    )
+                    .changed();
+                if ui.small_button("Remove").clicked() {
+                    remove = Some(index);
+                }
+            });
+        }
+        if let Some(index) = remove {
+            self.points.remove(index);
+            changed = true;
+        }
+        if ui.button("Add Point").clicked() {
+            // Continue in the direction of the last segment
+            let point = match self.points.as_slice() {
+                [.., before, last] => SplinePoint::new(
+                    last.position + (last.position - before.position),
+                    last.width,
+                ),
+                [last] => SplinePoint::new(last.position + Vec3::NEG_Z * 10.0, last.width),
+                [] => SplinePoint::new(Vec3::ZERO, 4.0),
+            };
+            self.points.push(point);
+            changed = true;
+        }
+        ui.add_space(large_spacing);
+
+        if let Some(baked) = self.baked_mesh.as_ref() {
+            ui.label(egui::RichText::new(format!("Baked to {}", baked)).weak());
+        }
+        changed
+    }
+}
@@ -0,0 +1,263 @@
+use super::{
+    build_spline_mesh, conform_samples, ground_height, mesh_world_triangles, sample_spline,
+    write_mesh_obj, BakedSplineMesh, SplineMesh, SplineMeshDirty, UserUpdatedSplineMeshEvent,
+};
+use crate::{
+    absolute_asset_to_rel, entities::editable::RequestEntityUpdateFromClass,
+    events::RequestSaveEvent, rel_asset_to_absolute, world::SaveLock, EditorCapabilities,
+    GraniteTypes, IdentityData, SpawnSource,
+};
+use bevy::{
+    asset::{AssetServer, Assets},
+    camera::primitives::Aabb,
+    ecs::{
+        change_detection::{DetectChanges, DetectChangesMut},
+        entity::Entity,
+        message::MessageReader,
+        query::{Has, With},
+        system::{Commands, Query, Res, ResMut},
+        world::Ref,
+    },
+    math::{Vec2, Vec3, Vec3A, Vec3Swizzles},
+    mesh::{Mesh, Mesh3d},
+    transform::components::GlobalTransform,
+};
+use bevy_granite_logging::{log, LogCategory, LogLevel, LogType};
+use std::path::Path;
+
+type GroundQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Mesh3d,
+        &'static GlobalTransform,
+        &'static IdentityData,
+        Option<&'static Aabb>,
+    ),
+>;
+
+type SplineChangeQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        Ref<'static, IdentityData>,
+        Ref<'static, GlobalTransform>,
+        Has<BakedSplineMesh>,
+    ),
+>;
+
+impl SplineMesh {
+    pub fn push_to_entity(
+        &self,
+        entity: Entity,
+        request_update: &mut RequestEntityUpdateFromClass,
+    ) {
+        log!(
+            LogType::Editor,
+            LogLevel::Info,
+            LogCategory::Entity,
+            "Requesting spline mesh update"
+        );
+        request_update
+            .spline_mesh
+            .write(UserUpdatedSplineMeshEvent {
+                entity,
+                data: self.clone(),
+            });
+    }
+}
+
+/// The editor already wrote the class data into IdentityData, the mesh is generated again in PostUpdate
+pub fn update_spline_mesh_system(
+    mut commands: Commands,
+    mut reader: MessageReader<UserUpdatedSplineMeshEvent>,
+) {
+    for UserUpdatedSplineMeshEvent {
+        entity: requested_entity,
+        data: new,
+    } in reader.read()
+    {
+        log!(
+            LogType::Editor,
+            LogLevel::Info,
+            LogCategory::Entity,
+            "Heard spline mesh update event: {} with {} points",
+            requested_entity,
+            new.points.len()
+        );
+        if let Ok(mut entity) = commands.get_entity(*requested_entity) {
+            entity.remove::<BakedSplineMesh>().insert(SplineMeshDirty);
+        }
+    }
+}
+
+/// Spline meshes are generated again when their class data changes, or when they move while conforming to ground
+/// Spawning doesn't count, so a bake keeps showing until the first edit
+pub fn mark_changed_spline_meshes_system(mut commands: Commands, query: SplineChangeQuery) {
+    for (entity, identity, transform, baked) in query.iter() {
+        let GraniteTypes::SplineMesh(spline_mesh) = &identity.class else {
+            continue;
+        };
+        let edited = identity.is_changed() && !identity.is_added();
+        let moved =
+            transform.is_changed() && !transform.is_added() && spline_mesh.conform_to_ground;
+        if edited || moved {
+            let mut entity = commands.entity(entity);
+            entity.insert(SplineMeshDirty);
+            if baked {
+                entity.remove::<BakedSplineMesh>();
+            }
+        }
+    }
+}
+
+/// Generate dirty spline meshes. Conforming ones wait until the ground below them has loaded
+pub fn regenerate_spline_meshes_system(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    asset_server: Res<AssetServer>,
+    dirty: Query<(Entity, &IdentityData, &GlobalTransform), With<SplineMeshDirty>>,
+    ground: GroundQuery,
+) {
+    for (entity, identity, global_transform) in dirty.iter() {
+        let GraniteTypes::SplineMesh(spline_mesh) = &identity.class else {
+            commands.entity(entity).remove::<SplineMeshDirty>();
+            continue;
+        };
+
+        let mut samples = sample_spline(
+            &spline_mesh.points,
+            spline_mesh.closed,
+            spline_mesh.resolution,
+        );
+        if spline_mesh.conform_to_ground && !samples.is_empty() {
+            let to_world = global_transform.affine();
+            let max_width = samples
+                .iter()
+                .map(|sample| sample.width)
+                .fold(0.0, f32::max);
+            let (min, max) = samples.iter().fold(
+                (Vec2::splat(f32::MAX), Vec2::splat(f32::MIN)),
+                |(min, max), sample| {
+                    let world = to_world.transform_point3(sample.position).xz();
+                    (min.min(world), max.max(world))
+                },
+            );
+            let (min, max) = (min - Vec2::splat(max_width), max + Vec2::splat(max_width));
+
+            let mut waiting = false;
+            let mut triangles = vec![];
+            for (mesh_handle, ground_transform, ground_identity, aabb) in ground.iter() {
+                if matches!(ground_identity.class, GraniteTypes::SplineMesh(_)) {
+                    continue;
+                }
+                if let Some(aabb) = aabb {
+                    let (ground_min, ground_max) = world_xz_bounds(aabb, ground_transform);
+                    if ground_max.x < min.x
+                        || ground_min.x > max.x
+                        || ground_max.y < min.y
+                        || ground_min.y > max.y
+                    {
+                        continue;
+                    }
+                }
+                match meshes.get(&mesh_handle.0) {
+                    Some(mesh) => {
+                        triangles.extend(mesh_world_triangles(mesh, &ground_transform.affine()))
+                    }
+                    None => {
+                        waiting |= asset_server
+                            .get_load_state(&mesh_handle.0)
+                            .is_some_and(|state| state.is_loading())
+                    }
+                }
+            }
+            if waiting {
+                continue;
+            }
+
+            conform_samples(
+                &mut samples,
+                &to_world,
+                spline_mesh.ground_offset,
+                |point| ground_height(&triangles, point),
+            );
+        }
+
+        let mesh = build_spline_mesh(&samples, spline_mesh.profile, spline_mesh.uv_tiling);
+        commands
+            .entity(entity)
+            .insert(Mesh3d(meshes.add(mesh)))
+            .remove::<SplineMeshDirty>();
+    }
+}
+
+fn world_xz_bounds(aabb: &Aabb, transform: &GlobalTransform) -> (Vec2, Vec2) {
+    let (center, half) = (aabb.center, aabb.half_extents);
+    let corners = [-1.0, 1.0].into_iter().flat_map(|x| {
+        [-1.0, 1.0].into_iter().flat_map(move |y| {
+            [-1.0, 1.0]
+                .into_iter()
+                .map(move |z| center + half * Vec3A::new(x, y, z))
+        })
+    });
+    corners.fold(
+        (Vec2::splat(f32::MAX), Vec2::splat(f32::MIN)),
+        |(min, max), corner| {
+            let world = transform.transform_point(Vec3::from(corner)).xz();
+            (min.min(world), max.max(world))
+        },
+    )
+}
+
+/// Bake the generated meshes of a scene to obj right before it is saved, so the scene references the new bake
+/// Meshes still showing their bake are unchanged and skipped
+pub fn bake_spline_meshes_system(
+    mut reader: MessageReader<RequestSaveEvent>,
+    meshes: Res<Assets<Mesh>>,
+    save_lock: Res<SaveLock>,
+    capabilities: Res<EditorCapabilities>,
+    mut query: Query<(
+        &mut IdentityData,
+        &Mesh3d,
+        &SpawnSource,
+        Has<BakedSplineMesh>,
+    )>,
+) {
+    // Only the first request per frame is saved
+    let Some(RequestSaveEvent(path)) = reader.read().next() else {
+        return;
+    };
+    if save_lock.reason.is_some() || !capabilities.can_save(path) {
+        return;
+    }
+
+    let source = absolute_asset_to_rel(path.clone());
+    for (mut identity, mesh_handle, spawn_source, baked) in query.iter_mut() {
+        if spawn_source.str_ref() != source || baked {
+            continue;
+        }
+        let uuid = identity.uuid;
+        let GraniteTypes::SplineMesh(spline_mesh) = &mut identity.bypass_change_detection().class
+        else {
+            continue;
+        };
+        let Some(mesh) = meshes.get(&mesh_handle.0) else {
+            continue;
+        };
+
+        let baked_path = SplineMesh::baked_mesh_path(&uuid);
+        match write_mesh_obj(mesh, Path::new(rel_asset_to_absolute(&baked_path).as_ref())) {
+            Ok(()) => spline_mesh.baked_mesh = Some(baked_path),
+            Err(e) => log!(
+                LogType::Editor,
+                LogLevel::Error,
+                LogCategory::Asset,
+                "Failed to bake spline mesh '{}': {}",
+                baked_path,
+                e
+            ),
+        }
+    }
+}
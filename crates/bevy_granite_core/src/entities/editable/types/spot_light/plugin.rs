@@ -1,4 +1,5 @@
-use super::{update_spot_light_system, UserUpdatedSpotLightEvent, SpotLightData};
+use super::{update_spot_light_system, SpotLightData, UserUpdatedSpotLightEvent};
+use crate::{register_class, GraniteTypes};
 use bevy::app::{App, Plugin, Update};
 
 pub struct SpotLightPlugin;
@@ -17,5 +18,8 @@ impl Plugin for SpotLightPlugin {
             // Schedule system
             //
             .add_systems(Update, update_spot_light_system);
+
+        // Class registry
+        register_class(app, GraniteTypes::SpotLightData(Default::default()));
     }
 }
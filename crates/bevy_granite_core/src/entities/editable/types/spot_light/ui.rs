@@ -104,6 +104,31 @@ impl SpotLightData {
                     ui.label("Shadows Enabled:");
                     changed |= ui.checkbox(&mut data.shadows_enabled, "").changed();
                     ui.end_row();
+
+                    if data.shadows_enabled {
+                        ui.label("Shadow Depth Bias:");
+                        changed |= ui
+                            .add(
+                                egui::DragValue::new(&mut data.shadow_depth_bias)
+                                    .range(0.0..=1.0)
+                                    .speed(0.001),
+                            )
+                            .on_hover_text(
+                                "Raise to remove shadow acne, lower if shadows detach from objects",
+                            )
+                            .changed();
+                        ui.end_row();
+
+                        ui.label("Shadow Normal Bias:");
+                        changed |= ui
+                            .add(
+                                egui::DragValue::new(&mut data.shadow_normal_bias)
+                                    .range(0.0..=10.0)
+                                    .speed(0.01),
+                            )
+                            .changed();
+                        ui.end_row();
+                    }
                 });
         });
         changed
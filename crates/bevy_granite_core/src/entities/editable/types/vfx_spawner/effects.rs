@@ -0,0 +1,220 @@
+use super::VfxSpawner;
+use crate::{entities::PropertyValue, GraniteTypes, IdentityData};
+use bevy::{
+    ecs::query::QueryState,
+    prelude::{App, Entity, Resource, World},
+};
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, RwLock},
+};
+
+// effects.rs
+// Effects are registered by name from game code, each with a spawn hook building it on a VFX spawner entity
+// and an optional despawn hook tearing it down, so any particle crate can sit behind the class
+// A spawner's effect is rebuilt whenever its class data changes, and torn down when it is turned off or removed
+
+type VfxSpawnHook = Arc<dyn Fn(&mut World, Entity, &VfxSpawner) + Send + Sync>;
+type VfxDespawnHook = Arc<dyn Fn(&mut World, Entity) + Send + Sync>;
+
+/// An effect spawners can place, i.e.
+/// `VfxEffect::new("campfire", spawn_campfire).with_parameter("rate", 40.0)`
+#[derive(Clone)]
+pub struct VfxEffect {
+    pub name: String,
+    /// Parameters the effect reads, with their defaults. Offered in the editor when a spawner picks the effect
+    pub parameters: BTreeMap<String, PropertyValue>,
+    spawn: VfxSpawnHook,
+    despawn: Option<VfxDespawnHook>,
+}
+
+impl VfxEffect {
+    /// `spawn` builds the effect on the spawner entity, i.e. inserting a ParticleEffect or spawning children
+    pub fn new(
+        name: impl Into<String>,
+        spawn: impl Fn(&mut World, Entity, &VfxSpawner) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            parameters: BTreeMap::new(),
+            spawn: Arc::new(spawn),
+            despawn: None,
+        }
+    }
+
+    pub fn with_parameter(
+        mut self,
+        key: impl Into<String>,
+        value: impl Into<PropertyValue>,
+    ) -> Self {
+        self.parameters.insert(key.into(), value.into());
+        self
+    }
+
+    /// Tears the effect down before it is rebuilt or stopped. The entity may already be despawned
+    /// Without one, whatever spawn inserted stays until the spawn hook overwrites it
+    pub fn on_despawn(
+        mut self,
+        despawn: impl Fn(&mut World, Entity) + Send + Sync + 'static,
+    ) -> Self {
+        self.despawn = Some(Arc::new(despawn));
+        self
+    }
+}
+
+/// Registered effects by name. Register them with `add_vfx_effect` on App
+#[derive(Resource, Default, Clone)]
+pub struct VfxEffects {
+    effects: BTreeMap<String, VfxEffect>,
+}
+
+impl VfxEffects {
+    /// Replaces an effect registered under the same name
+    pub fn add(&mut self, effect: VfxEffect) {
+        VFX_EFFECT_PARAMETERS
+            .write()
+            .unwrap()
+            .insert(effect.name.clone(), effect.parameters.clone());
+        self.effects.insert(effect.name.clone(), effect);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&VfxEffect> {
+        self.effects.get(name)
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &String> {
+        self.effects.keys()
+    }
+
+    pub fn len(&self) -> usize {
+        self.effects.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.effects.is_empty()
+    }
+}
+
+lazy_static::lazy_static! {
+    // The class UI has no World, so names and defaults are kept here as well
+    static ref VFX_EFFECT_PARAMETERS: RwLock<BTreeMap<String, BTreeMap<String, PropertyValue>>> = RwLock::new(BTreeMap::new());
+}
+
+/// Every registered effect name, sorted
+pub fn registered_vfx_effects() -> Vec<String> {
+    VFX_EFFECT_PARAMETERS
+        .read()
+        .unwrap()
+        .keys()
+        .cloned()
+        .collect()
+}
+
+/// Parameters of a registered effect with their defaults
+pub fn vfx_effect_parameters(name: &str) -> Option<BTreeMap<String, PropertyValue>> {
+    VFX_EFFECT_PARAMETERS.read().unwrap().get(name).cloned()
+}
+
+/// i.e. `app.add_vfx_effect(VfxEffect::new("campfire", |world, entity, spawner| ..).with_parameter("rate", 40.0))`
+/// The effect is rebuilt when a spawner is edited and torn down when it is turned off or removed
+pub trait VfxEffectAppExt {
+    /// Let VFX spawners place `effect` by its name
+    fn add_vfx_effect(&mut self, effect: VfxEffect) -> &mut Self;
+}
+
+impl VfxEffectAppExt for App {
+    fn add_vfx_effect(&mut self, effect: VfxEffect) -> &mut Self {
+        self.init_resource::<VfxEffects>();
+        self.world_mut().resource_mut::<VfxEffects>().add(effect);
+        self
+    }
+}
+
+/// Spawners whose effect is built, with the class data it was built from
+#[derive(Resource, Default, Debug, Clone)]
+pub struct RunningVfx {
+    running: HashMap<Entity, VfxSpawner>,
+}
+
+impl RunningVfx {
+    pub fn get(&self, entity: Entity) -> Option<&VfxSpawner> {
+        self.running.get(&entity)
+    }
+
+    pub fn is_running(&self, entity: Entity) -> bool {
+        self.running.contains_key(&entity)
+    }
+
+    pub fn len(&self) -> usize {
+        self.running.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.running.is_empty()
+    }
+}
+
+/// Builds the effect of new or edited spawners, and tears it down for edited, inactive or removed ones
+pub fn run_vfx_effects_system(world: &mut World, query: &mut QueryState<(Entity, &IdentityData)>) {
+    let spawners: HashMap<Entity, VfxSpawner> = query
+        .iter(world)
+        .filter_map(|(entity, identity)| match &identity.class {
+            GraniteTypes::VfxSpawner(spawner) if spawner.active => Some((entity, spawner.clone())),
+            _ => None,
+        })
+        .collect();
+    let previous = std::mem::take(&mut world.resource_mut::<RunningVfx>().running);
+    if spawners.is_empty() && previous.is_empty() {
+        return;
+    }
+    let effects = world.resource::<VfxEffects>().clone();
+
+    let mut running = HashMap::new();
+    for (entity, built) in previous {
+        if spawners.get(&entity) == Some(&built) {
+            running.insert(entity, built);
+            continue;
+        }
+        if let Some(despawn) = effects
+            .get(&built.effect)
+            .and_then(|effect| effect.despawn.clone())
+        {
+            despawn(world, entity);
+        }
+    }
+
+    let mut started: Vec<(Entity, VfxSpawner)> = spawners
+        .into_iter()
+        .filter(|(entity, _)| !running.contains_key(entity))
+        .collect();
+    // HashMap order isn't stable, build effects in a fixed order
+    started.sort_by_key(|(entity, _)| *entity);
+    for (entity, spawner) in started {
+        // Tracked even when unknown, so the warning is logged once per edit and not every frame
+        running.insert(entity, spawner.clone());
+        let Some(effect) = effects.get(&spawner.effect) else {
+            if !spawner.effect.is_empty() {
+                log!(
+                    LogType::Game,
+                    LogLevel::Warning,
+                    LogCategory::Entity,
+                    "No vfx effect registered as '{}', spawner {} shows nothing",
+                    spawner.effect,
+                    entity
+                );
+            }
+            continue;
+        };
+        // An earlier hook may have despawned it
+        if world.get_entity(entity).is_err() {
+            continue;
+        }
+        (effect.spawn)(world, entity, &spawner);
+    }
+
+    world.resource_mut::<RunningVfx>().running = running;
+}
@@ -0,0 +1,152 @@
+use crate::{
+    entities::{
+        editable::{GraniteType, RequestEntityUpdateFromClass},
+        EntitySaveReadyData, PropertyValue,
+    },
+    AvailableEditableMaterials, ClassCategory, PromptData,
+};
+use bevy::{
+    asset::{AssetServer, Assets},
+    color::Color,
+    ecs::{
+        entity::Entity,
+        message::Message,
+        system::{Commands, Res, ResMut},
+    },
+    mesh::Mesh,
+    pbr::StandardMaterial,
+    reflect::Reflect,
+    transform::components::Transform,
+};
+use bevy_egui::egui;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+pub mod creation;
+pub mod effects;
+pub mod plugin;
+pub mod ui;
+pub mod update_event;
+
+pub use effects::*;
+pub use plugin::*;
+pub use update_event::*;
+
+/// Internal event thats called when user edits UI vfx spawner variables
+#[derive(Message)]
+pub struct UserUpdatedVfxSpawnerEvent {
+    pub entity: Entity,
+    pub data: VfxSpawner,
+}
+
+/// Actual serialized class data thats stored inside IdentityData
+/// Places an effect by name. Granite draws no particles itself,
+/// the effect registered under that name with `add_vfx_effect` builds it, i.e. with bevy_hanabi
+#[derive(Serialize, Deserialize, Reflect, Debug, Clone, PartialEq)]
+pub struct VfxSpawner {
+    /// Name the effect was registered under, i.e. "campfire"
+    pub effect: String,
+    /// Values handed to the effect, i.e. "rate": 40 or "tint": a color
+    pub parameters: BTreeMap<String, PropertyValue>,
+    /// Run the effect. Off keeps the spawner placed without it
+    pub active: bool,
+}
+
+impl Default for VfxSpawner {
+    fn default() -> Self {
+        Self {
+            effect: String::new(),
+            parameters: BTreeMap::new(),
+            active: true,
+        }
+    }
+}
+
+impl VfxSpawner {
+    pub fn new(effect: impl Into<String>) -> Self {
+        Self {
+            effect: effect.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with(mut self, key: impl Into<String>, value: impl Into<PropertyValue>) -> Self {
+        self.parameters.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn get(&self, key: &str) -> Option<&PropertyValue> {
+        self.parameters.get(key)
+    }
+
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.get(key).and_then(PropertyValue::as_str)
+    }
+
+    pub fn get_number(&self, key: &str) -> Option<f64> {
+        self.get(key).and_then(PropertyValue::as_number)
+    }
+
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        self.get(key).and_then(PropertyValue::as_bool)
+    }
+
+    pub fn get_color(&self, key: &str) -> Option<Color> {
+        self.get(key).and_then(PropertyValue::as_color)
+    }
+}
+
+impl GraniteType for VfxSpawner {
+    fn type_name(&self) -> String {
+        "VFX Spawner".to_string()
+    }
+
+    fn type_abv(&self) -> String {
+        "VFX".to_string()
+    }
+
+    fn category(&self) -> ClassCategory {
+        ClassCategory::Gameplay
+    }
+
+    fn get_embedded_icon_bytes(&self) -> Option<&'static [u8]> {
+        Some(include_bytes!("VfxSpawner.png"))
+    }
+
+    fn get_icon_filename(&self) -> Option<&'static str> {
+        Some("VfxSpawner.png")
+    }
+
+    fn spawn_from_new_identity(
+        &mut self,
+        commands: &mut Commands,
+        transform: Transform,
+        _standard_materials: ResMut<Assets<StandardMaterial>>,
+        _meshes: ResMut<Assets<Mesh>>,
+        _available_materials: ResMut<AvailableEditableMaterials>,
+        _asset_server: Res<AssetServer>,
+        _maybe_prompt_data: Option<PromptData>,
+    ) -> Entity {
+        VfxSpawner::spawn_from_new_identity(self, commands, transform)
+    }
+
+    fn spawn_from_save_data(
+        &self,
+        save_data: &EntitySaveReadyData,
+        commands: &mut Commands,
+        _standard_materials: &mut ResMut<Assets<StandardMaterial>>,
+        _meshes: &mut ResMut<Assets<Mesh>>,
+        _available_materials: &mut ResMut<AvailableEditableMaterials>,
+        _asset_server: &Res<AssetServer>,
+    ) -> Entity {
+        VfxSpawner::spawn_from_save_data(save_data, commands)
+    }
+
+    fn push_to_entity(&self, entity: Entity, request_update: &mut RequestEntityUpdateFromClass) {
+        self.push_to_entity(entity, request_update)
+    }
+
+    fn edit_via_ui(&mut self, ui: &mut egui::Ui, spacing: (f32, f32, f32)) -> bool {
+        self.edit_via_ui(ui, spacing)
+    }
+}
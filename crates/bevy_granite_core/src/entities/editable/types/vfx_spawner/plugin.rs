@@ -0,0 +1,34 @@
+use super::{
+    run_vfx_effects_system, update_vfx_spawner_system, RunningVfx, UserUpdatedVfxSpawnerEvent,
+    VfxEffects, VfxSpawner,
+};
+use crate::{register_class, GraniteTypes};
+use bevy::app::{App, Plugin, PostUpdate, Update};
+
+pub struct VfxSpawnerPlugin;
+impl Plugin for VfxSpawnerPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            //
+            // Event
+            //
+            .add_message::<UserUpdatedVfxSpawnerEvent>()
+            //
+            // Register
+            //
+            .register_type::<VfxSpawner>()
+            //
+            // Resources
+            //
+            .init_resource::<VfxEffects>()
+            .init_resource::<RunningVfx>()
+            //
+            // Schedule system
+            //
+            .add_systems(Update, update_vfx_spawner_system)
+            .add_systems(PostUpdate, run_vfx_effects_system);
+
+        // Class registry
+        register_class(app, GraniteTypes::VfxSpawner(Default::default()));
+    }
+}
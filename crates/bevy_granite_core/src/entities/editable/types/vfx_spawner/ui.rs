@@ -0,0 +1,130 @@
+use super::{registered_vfx_effects, vfx_effect_parameters, VfxSpawner};
+use crate::entities::PropertyValue;
+use bevy::color::Color;
+use bevy_egui::egui;
+
+impl VfxSpawner {
+    /// Function to edit self's data via UI side panel
+    pub fn edit_via_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        // Small, Large, Normal
+        spacing: (f32, f32, f32),
+    ) -> bool {
+        let large_spacing = spacing.1;
+        let mut changed = false;
+        let effects = registered_vfx_effects();
+        egui::Grid::new("vfx_spawner_grid")
+            .num_columns(2)
+            .spacing([large_spacing, large_spacing])
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Effect:");
+                ui.horizontal(|ui| {
+                    changed |= ui
+                        .text_edit_singleline(&mut self.effect)
+                        .on_hover_text("Name the effect was registered under with add_vfx_effect")
+                        .changed();
+                    egui::ComboBox::from_id_salt("vfx_spawner_effect")
+                        .selected_text("")
+                        .width(20.0)
+                        .show_ui(ui, |ui| {
+                            if effects.is_empty() {
+                                ui.weak("No effects registered");
+                            }
+                            for effect in effects.iter() {
+                                if ui
+                                    .selectable_label(self.effect == *effect, effect)
+                                    .clicked()
+                                    && self.effect != *effect
+                                {
+                                    self.effect = effect.clone();
+                                    self.add_missing_parameters();
+                                    changed = true;
+                                }
+                            }
+                        });
+                });
+                ui.end_row();
+
+                ui.label("Active:");
+                changed |= ui
+                    .checkbox(&mut self.active, "")
+                    .on_hover_text("Off keeps the spawner placed without running the effect")
+                    .changed();
+                ui.end_row();
+
+                let mut removed = None;
+                for (key, value) in self.parameters.iter_mut() {
+                    ui.label(format!("{}:", key));
+                    ui.horizontal(|ui| {
+                        changed |= edit_parameter(ui, value);
+                        if ui
+                            .small_button("❌")
+                            .on_hover_text("Remove parameter")
+                            .clicked()
+                        {
+                            removed = Some(key.clone());
+                        }
+                    });
+                    ui.end_row();
+                }
+                if let Some(key) = removed {
+                    self.parameters.remove(&key);
+                    changed = true;
+                }
+            });
+
+        ui.add_space(large_spacing);
+        let known = effects.contains(&self.effect);
+        if !self.effect.is_empty() && !known {
+            ui.colored_label(
+                egui::Color32::YELLOW,
+                "No effect registered under this name, nothing is shown",
+            );
+        }
+        if ui
+            .add_enabled(known, egui::Button::new("Add Effect Parameters"))
+            .on_hover_text(
+                "Adds the parameters the effect reads that are missing, with their defaults",
+            )
+            .clicked()
+        {
+            changed |= self.add_missing_parameters();
+        }
+        ui.add_space(large_spacing);
+        changed
+    }
+
+    /// Adds the registered effect's parameters this spawner lacks. Returns whether any were added
+    fn add_missing_parameters(&mut self) -> bool {
+        let Some(defaults) = vfx_effect_parameters(&self.effect) else {
+            return false;
+        };
+        let mut added = false;
+        for (key, value) in defaults {
+            if !self.parameters.contains_key(&key) {
+                self.parameters.insert(key, value);
+                added = true;
+            }
+        }
+        added
+    }
+}
+
+fn edit_parameter(ui: &mut egui::Ui, value: &mut PropertyValue) -> bool {
+    match value {
+        PropertyValue::String(text) => ui.text_edit_singleline(text).changed(),
+        PropertyValue::Number(number) => ui.add(egui::DragValue::new(number).speed(0.1)).changed(),
+        PropertyValue::Bool(flag) => ui.checkbox(flag, "").changed(),
+        PropertyValue::Color(color) => {
+            let srgba = color.to_srgba();
+            let mut rgba = [srgba.red, srgba.green, srgba.blue, srgba.alpha];
+            let edited = ui.color_edit_button_rgba_unmultiplied(&mut rgba).changed();
+            if edited {
+                *color = Color::srgba(rgba[0], rgba[1], rgba[2], rgba[3]);
+            }
+            edited
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use crate::entities::editable::{RequestEntityUpdateFromClass, UserUpdatedVfxSpawnerEvent};
+use crate::entities::VfxSpawner;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::message::MessageReader;
+use bevy_granite_logging::{log, LogCategory, LogLevel, LogType};
+
+impl VfxSpawner {
+    pub fn push_to_entity(
+        &self,
+        entity: Entity,
+        request_update: &mut RequestEntityUpdateFromClass,
+    ) {
+        log!(
+            LogType::Editor,
+            LogLevel::Info,
+            LogCategory::Entity,
+            "Requesting vfx spawner entity update"
+        );
+
+        request_update
+            .vfx_spawner
+            .write(UserUpdatedVfxSpawnerEvent {
+                entity,
+                data: self.clone(),
+            });
+    }
+}
+
+/// The settings are read from IdentityData, so the effect is rebuilt by run_vfx_effects_system on its own
+pub fn update_vfx_spawner_system(mut reader: MessageReader<UserUpdatedVfxSpawnerEvent>) {
+    for UserUpdatedVfxSpawnerEvent {
+        entity: requested_entity,
+        data: new,
+    } in reader.read()
+    {
+        log!(
+            LogType::Editor,
+            LogLevel::Info,
+            LogCategory::Entity,
+            "Heard vfx spawner update event: {} runs '{}'",
+            requested_entity,
+            new.effect
+        );
+    }
+}
@@ -0,0 +1,156 @@
+use crate::{
+    entities::{
+        editable::{GraniteType, RequestEntityUpdateFromClass},
+        EntitySaveReadyData,
+    },
+    AvailableEditableMaterials, ClassCategory, PromptData,
+};
+use bevy::{
+    asset::{AssetServer, Assets},
+    ecs::{
+        entity::Entity,
+        message::Message,
+        system::{Commands, Res, ResMut},
+    },
+    mesh::Mesh,
+    pbr::StandardMaterial,
+    reflect::Reflect,
+    transform::components::Transform,
+};
+use bevy_egui::egui;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub mod creation;
+pub mod network;
+pub mod plugin;
+pub mod ui;
+pub mod update_event;
+
+pub use network::*;
+pub use plugin::*;
+pub use update_event::*;
+
+/// Internal event thats called when user edits UI waypoint variable
+#[derive(Message)]
+pub struct UserUpdatedWaypointEvent {
+    pub entity: Entity,
+    pub data: Waypoint,
+}
+
+/// How RequestConnectWaypointsEvent links waypoints
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WaypointLinkMode {
+    Bidirectional,
+    OneWay,
+    Disconnect,
+}
+
+/// Link each waypoint to the next one in the list, i.e. the editor selection order
+#[derive(Message)]
+pub struct RequestConnectWaypointsEvent {
+    pub entities: Vec<Entity>,
+    pub mode: WaypointLinkMode,
+}
+
+/// Edge to another waypoint, by uuid so it survives save and load
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub struct WaypointLink {
+    pub target: Uuid,
+    /// Traversable both ways, otherwise only from this waypoint to target
+    pub bidirectional: bool,
+}
+
+/// Actual serialized class data thats stored inside IdentityData
+/// A node of a waypoint network, queried at runtime through WaypointNetworks
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Reflect)]
+pub struct Waypoint {
+    /// Waypoints only link within the same network, i.e. "guards"
+    pub network: String,
+    pub links: Vec<WaypointLink>,
+}
+
+impl Default for Waypoint {
+    fn default() -> Self {
+        Self {
+            network: "default".to_string(),
+            links: vec![],
+        }
+    }
+}
+
+impl Waypoint {
+    pub fn new(network: impl Into<String>) -> Self {
+        Self {
+            network: network.into(),
+            links: vec![],
+        }
+    }
+
+    pub fn link(&mut self, target: Uuid, bidirectional: bool) {
+        self.unlink(target);
+        self.links.push(WaypointLink {
+            target,
+            bidirectional,
+        });
+    }
+
+    pub fn unlink(&mut self, target: Uuid) {
+        self.links.retain(|link| link.target != target);
+    }
+}
+
+impl GraniteType for Waypoint {
+    fn type_name(&self) -> String {
+        "Waypoint".to_string()
+    }
+
+    fn type_abv(&self) -> String {
+        "Waypoint".to_string()
+    }
+
+    fn category(&self) -> ClassCategory {
+        ClassCategory::Gameplay
+    }
+
+    fn get_embedded_icon_bytes(&self) -> Option<&'static [u8]> {
+        Some(include_bytes!("Waypoint.png"))
+    }
+
+    fn get_icon_filename(&self) -> Option<&'static str> {
+        Some("Waypoint.png")
+    }
+
+    fn spawn_from_new_identity(
+        &mut self,
+        commands: &mut Commands,
+        transform: Transform,
+        _standard_materials: ResMut<Assets<StandardMaterial>>,
+        _meshes: ResMut<Assets<Mesh>>,
+        _available_materials: ResMut<AvailableEditableMaterials>,
+        _asset_server: Res<AssetServer>,
+        _maybe_prompt_data: Option<PromptData>,
+    ) -> Entity {
+        Waypoint::spawn_from_new_identity(self, commands, transform)
+    }
+
+    fn spawn_from_save_data(
+        &self,
+        save_data: &EntitySaveReadyData,
+        commands: &mut Commands,
+        _standard_materials: &mut ResMut<Assets<StandardMaterial>>,
+        _meshes: &mut ResMut<Assets<Mesh>>,
+        _available_materials: &mut ResMut<AvailableEditableMaterials>,
+        _asset_server: &Res<AssetServer>,
+    ) -> Entity {
+        Waypoint::spawn_from_save_data(save_data, commands)
+    }
+
+    fn push_to_entity(&self, entity: Entity, request_update: &mut RequestEntityUpdateFromClass) {
+        self.push_to_entity(entity, request_update)
+    }
+
+    fn edit_via_ui(&mut self, ui: &mut egui::Ui, spacing: (f32, f32, f32)) -> bool {
+        self.edit_via_ui(ui, spacing)
+    }
+}
@@ -0,0 +1,232 @@
+use crate::{GraniteTypes, IdentityData};
+use bevy::{
+    ecs::{
+        entity::Entity,
+        resource::Resource,
+        system::{Query, ResMut},
+    },
+    math::Vec3,
+    transform::components::GlobalTransform,
+};
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap},
+};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WaypointNode {
+    pub entity: Entity,
+    pub uuid: Uuid,
+    pub position: Vec3,
+}
+
+/// One named network. Edges are directed, a bidirectional link adds one each way
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WaypointGraph {
+    pub nodes: Vec<WaypointNode>,
+    /// Node index -> indices it can travel to
+    pub edges: Vec<Vec<usize>>,
+    index_by_entity: HashMap<Entity, usize>,
+}
+
+impl WaypointGraph {
+    pub fn node(&self, entity: Entity) -> Option<&WaypointNode> {
+        self.index_by_entity
+            .get(&entity)
+            .map(|index| &self.nodes[*index])
+    }
+
+    /// Waypoints reachable in one step from entity
+    pub fn neighbors(&self, entity: Entity) -> Vec<Entity> {
+        self.index_by_entity
+            .get(&entity)
+            .map(|index| {
+                self.edges[*index]
+                    .iter()
+                    .map(|next| self.nodes[*next].entity)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn nearest(&self, position: Vec3) -> Option<Entity> {
+        self.nodes
+            .iter()
+            .min_by(|a, b| {
+                a.position
+                    .distance_squared(position)
+                    .total_cmp(&b.position.distance_squared(position))
+            })
+            .map(|node| node.entity)
+    }
+
+    /// Shortest path by distance, both ends included. None when to can't be reached from from
+    pub fn path(&self, from: Entity, to: Entity) -> Option<Vec<Entity>> {
+        let start = *self.index_by_entity.get(&from)?;
+        let goal = *self.index_by_entity.get(&to)?;
+        let goal_position = self.nodes[goal].position;
+
+        let mut cost = vec![f32::INFINITY; self.nodes.len()];
+        let mut came_from: Vec<Option<usize>> = vec![None; self.nodes.len()];
+        let mut open = BinaryHeap::new();
+        cost[start] = 0.;
+        open.push(OpenNode {
+            index: start,
+            estimate: self.nodes[start].position.distance(goal_position),
+        });
+
+        // A*, distance to goal never overestimates so the first time goal is popped is the shortest path
+        while let Some(OpenNode { index, .. }) = open.pop() {
+            if index == goal {
+                let mut path = vec![self.nodes[goal].entity];
+                let mut current = goal;
+                while let Some(previous) = came_from[current] {
+                    path.push(self.nodes[previous].entity);
+                    current = previous;
+                }
+                path.reverse();
+                return Some(path);
+            }
+
+            for next in self.edges[index].iter().copied() {
+                let next_cost = cost[index]
+                    + self.nodes[index]
+                        .position
+                        .distance(self.nodes[next].position);
+                if next_cost < cost[next] {
+                    cost[next] = next_cost;
+                    came_from[next] = Some(index);
+                    open.push(OpenNode {
+                        index: next,
+                        estimate: next_cost + self.nodes[next].position.distance(goal_position),
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    /// Path between the waypoints nearest to two positions
+    pub fn path_between(&self, from: Vec3, to: Vec3) -> Option<Vec<Entity>> {
+        self.path(self.nearest(from)?, self.nearest(to)?)
+    }
+}
+
+struct OpenNode {
+    index: usize,
+    estimate: f32,
+}
+
+impl PartialEq for OpenNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.estimate == other.estimate
+    }
+}
+
+impl Eq for OpenNode {}
+
+impl Ord for OpenNode {
+    // Reversed, BinaryHeap pops the largest and we want the lowest estimate
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimate.total_cmp(&self.estimate)
+    }
+}
+
+impl PartialOrd for OpenNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Every waypoint network in the world by name, refreshed each frame after transforms propagate
+#[derive(Resource, Default, Debug, Clone)]
+pub struct WaypointNetworks {
+    networks: HashMap<String, WaypointGraph>,
+}
+
+impl WaypointNetworks {
+    pub fn get(&self, network: &str) -> Option<&WaypointGraph> {
+        self.networks.get(network)
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.networks.keys().map(String::as_str)
+    }
+
+    pub fn nearest(&self, network: &str, position: Vec3) -> Option<Entity> {
+        self.get(network)?.nearest(position)
+    }
+
+    pub fn path(&self, network: &str, from: Entity, to: Entity) -> Option<Vec<Entity>> {
+        self.get(network)?.path(from, to)
+    }
+
+    pub fn path_between(&self, network: &str, from: Vec3, to: Vec3) -> Option<Vec<Entity>> {
+        self.get(network)?.path_between(from, to)
+    }
+}
+
+pub fn update_waypoint_networks_system(
+    mut networks: ResMut<WaypointNetworks>,
+    query: Query<(Entity, &IdentityData, &GlobalTransform)>,
+) {
+    let mut waypoints: Vec<(Entity, &IdentityData, &GlobalTransform)> = query
+        .iter()
+        .filter(|(_, identity, _)| matches!(identity.class, GraniteTypes::Waypoint(_)))
+        .collect();
+    // Query order isn't stable, keep node order fixed so equal cost paths don't flicker
+    waypoints.sort_by_key(|(entity, _, _)| *entity);
+
+    let mut built: HashMap<String, WaypointGraph> = HashMap::new();
+    let mut index_by_uuid: HashMap<(String, Uuid), usize> = HashMap::new();
+    for (entity, identity, transform) in waypoints.iter() {
+        let GraniteTypes::Waypoint(waypoint) = &identity.class else {
+            continue;
+        };
+        let graph = built.entry(waypoint.network.clone()).or_default();
+        let index = graph.nodes.len();
+        graph.nodes.push(WaypointNode {
+            entity: *entity,
+            uuid: identity.uuid,
+            position: transform.translation(),
+        });
+        graph.edges.push(vec![]);
+        graph.index_by_entity.insert(*entity, index);
+        index_by_uuid.insert((waypoint.network.clone(), identity.uuid), index);
+    }
+
+    // Links to waypoints of another network or that aren't loaded are ignored
+    for (_, identity, _) in waypoints.iter() {
+        let GraniteTypes::Waypoint(waypoint) = &identity.class else {
+            continue;
+        };
+        let Some(from) = index_by_uuid
+            .get(&(waypoint.network.clone(), identity.uuid))
+            .copied()
+        else {
+            continue;
+        };
+        let Some(graph) = built.get_mut(&waypoint.network) else {
+            continue;
+        };
+        for link in waypoint.links.iter() {
+            let Some(to) = index_by_uuid
+                .get(&(waypoint.network.clone(), link.target))
+                .copied()
+            else {
+                continue;
+            };
+            if !graph.edges[from].contains(&to) {
+                graph.edges[from].push(to);
+            }
+            if link.bidirectional && !graph.edges[to].contains(&from) {
+                graph.edges[to].push(from);
+            }
+        }
+    }
+
+    if networks.networks != built {
+        networks.networks = built;
+    }
+}
@@ -0,0 +1,41 @@
+use super::{
+    connect_waypoints_system, update_waypoint_networks_system, update_waypoint_system,
+    RequestConnectWaypointsEvent, UserUpdatedWaypointEvent, Waypoint, WaypointLink,
+    WaypointNetworks,
+};
+use crate::{register_class, GraniteTypes};
+use bevy::app::{App, Plugin, PostUpdate, Update};
+use bevy::ecs::schedule::IntoScheduleConfigs;
+use bevy::transform::TransformSystems;
+
+pub struct WaypointPlugin;
+impl Plugin for WaypointPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            //
+            // Event
+            //
+            .add_message::<UserUpdatedWaypointEvent>()
+            .add_message::<RequestConnectWaypointsEvent>()
+            //
+            // Register
+            //
+            .register_type::<Waypoint>()
+            .register_type::<WaypointLink>()
+            //
+            // Resources
+            //
+            .init_resource::<WaypointNetworks>()
+            //
+            // Schedule system
+            //
+            .add_systems(Update, (update_waypoint_system, connect_waypoints_system))
+            .add_systems(
+                PostUpdate,
+                update_waypoint_networks_system.after(TransformSystems::Propagate),
+            );
+
+        // Class registry
+        register_class(app, GraniteTypes::Waypoint(Default::default()));
+    }
+}
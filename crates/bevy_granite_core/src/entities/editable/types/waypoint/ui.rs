@@ -0,0 +1,45 @@
+use super::Waypoint;
+use bevy_egui::egui;
+
+impl Waypoint {
+    /// Function to edit self's data via UI side panel
+    /// Links are made from the Entities tab, here they can only be flipped or removed
+    pub fn edit_via_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        // Small, Large, Normal
+        spacing: (f32, f32, f32),
+    ) -> bool {
+        let large_spacing = spacing.1;
+        let mut changed = false;
+        egui::Grid::new("waypoint_grid")
+            .num_columns(2)
+            .spacing([large_spacing, large_spacing])
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Network:");
+                changed |= ui.text_edit_singleline(&mut self.network).changed();
+                ui.end_row();
+            });
+        ui.add_space(large_spacing);
+
+        ui.label(format!("Links ({})", self.links.len()));
+        let mut remove = None;
+        for (index, link) in self.links.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                let target = link.target.to_string();
+                ui.monospace(&target[..8]);
+                changed |= ui.checkbox(&mut link.bidirectional, "Both ways").changed();
+                if ui.small_button("Remove").clicked() {
+                    remove = Some(index);
+                }
+            });
+        }
+        if let Some(index) = remove {
+            self.links.remove(index);
+            changed = true;
+        }
+        ui.add_space(large_spacing);
+        changed
+    }
+}
@@ -0,0 +1,102 @@
+use super::{RequestConnectWaypointsEvent, WaypointLinkMode};
+use crate::entities::editable::{RequestEntityUpdateFromClass, UserUpdatedWaypointEvent};
+use crate::entities::{GraniteTypes, IdentityData, Waypoint};
+use bevy::ecs::entity::Entity;
+use bevy::ecs::message::MessageReader;
+use bevy::ecs::system::Query;
+use bevy_granite_logging::{log, LogCategory, LogLevel, LogType};
+use uuid::Uuid;
+
+impl Waypoint {
+    pub fn push_to_entity(
+        &self,
+        entity: Entity,
+        request_update: &mut RequestEntityUpdateFromClass,
+    ) {
+        log!(
+            LogType::Editor,
+            LogLevel::Info,
+            LogCategory::Entity,
+            "Requesting waypoint entity update"
+        );
+
+        request_update.waypoint.write(UserUpdatedWaypointEvent {
+            entity,
+            data: self.clone(),
+        });
+    }
+}
+
+/// Links and network are read from IdentityData, so WaypointNetworks picks edits up on its own
+pub fn update_waypoint_system(mut reader: MessageReader<UserUpdatedWaypointEvent>) {
+    for UserUpdatedWaypointEvent {
+        entity: requested_entity,
+        data: new,
+    } in reader.read()
+    {
+        log!(
+            LogType::Editor,
+            LogLevel::Info,
+            LogCategory::Entity,
+            "Heard waypoint update event: {} in network '{}' with {} links",
+            requested_entity,
+            new.network,
+            new.links.len()
+        );
+    }
+}
+
+/// Link consecutive waypoints of the request. Existing links between a pair are replaced
+pub fn connect_waypoints_system(
+    mut reader: MessageReader<RequestConnectWaypointsEvent>,
+    mut query: Query<&mut IdentityData>,
+) {
+    for RequestConnectWaypointsEvent { entities, mode } in reader.read() {
+        let waypoints: Vec<(Entity, Uuid)> = entities
+            .iter()
+            .filter_map(|entity| {
+                query
+                    .get(*entity)
+                    .ok()
+                    .filter(|identity| matches!(identity.class, GraniteTypes::Waypoint(_)))
+                    .map(|identity| (*entity, identity.uuid))
+            })
+            .collect();
+        if waypoints.len() < 2 {
+            log!(
+                LogType::Editor,
+                LogLevel::Warning,
+                LogCategory::Entity,
+                "Select at least two waypoints to change their links"
+            );
+            continue;
+        }
+
+        for pair in waypoints.windows(2) {
+            let ((from, from_uuid), (to, to_uuid)) = (pair[0], pair[1]);
+            if let Ok(mut identity) = query.get_mut(to) {
+                if let GraniteTypes::Waypoint(waypoint) = &mut identity.class {
+                    waypoint.unlink(from_uuid);
+                }
+            }
+            if let Ok(mut identity) = query.get_mut(from) {
+                if let GraniteTypes::Waypoint(waypoint) = &mut identity.class {
+                    match mode {
+                        WaypointLinkMode::Bidirectional => waypoint.link(to_uuid, true),
+                        WaypointLinkMode::OneWay => waypoint.link(to_uuid, false),
+                        WaypointLinkMode::Disconnect => waypoint.unlink(to_uuid),
+                    }
+                }
+            }
+        }
+
+        log!(
+            LogType::Editor,
+            LogLevel::OK,
+            LogCategory::Entity,
+            "{:?} on {} waypoints",
+            mode,
+            waypoints.len()
+        );
+    }
+}
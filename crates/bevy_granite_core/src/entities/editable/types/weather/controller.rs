@@ -0,0 +1,226 @@
+use super::WeatherPreset;
+use crate::{GraniteTypes, IdentityData};
+use bevy::{
+    camera::{Camera, Camera3d, ClearColor, ClearColorConfig},
+    color::Color,
+    ecs::{
+        change_detection::DetectChanges,
+        component::Component,
+        entity::Entity,
+        query::With,
+        resource::Resource,
+        system::{Commands, Query, Res, ResMut},
+        world::Ref,
+    },
+    light::{DirectionalLight, GlobalAmbientLight},
+    pbr::{DistanceFog, FogFalloff},
+    time::Time,
+};
+
+/// Marks cameras whose DistanceFog comes from the weather, so it can be taken off again
+#[derive(Component)]
+pub struct WeatherFog;
+
+/// Runtime weather of the scene, following the first Weather entity in the world
+/// Game code switches weather with `transition_to`, the blended settings drive fog, sky, ambient and sun
+#[derive(Resource, Default, Debug, Clone)]
+pub struct WeatherController {
+    source: Option<Entity>,
+    presets: Vec<WeatherPreset>,
+    current: WeatherPreset,
+    from: WeatherPreset,
+    target: WeatherPreset,
+    elapsed: f32,
+    duration: f32,
+}
+
+impl WeatherController {
+    /// There is a Weather entity to follow
+    pub fn is_active(&self) -> bool {
+        self.source.is_some()
+    }
+
+    pub fn source(&self) -> Option<Entity> {
+        self.source
+    }
+
+    /// Blended settings of this frame
+    pub fn current(&self) -> &WeatherPreset {
+        &self.current
+    }
+
+    /// Preset being blended into, or the current one once done
+    pub fn target(&self) -> &WeatherPreset {
+        &self.target
+    }
+
+    pub fn presets(&self) -> &[WeatherPreset] {
+        &self.presets
+    }
+
+    pub fn is_transitioning(&self) -> bool {
+        self.elapsed < self.duration
+    }
+
+    /// 0 to 1, 1 when not transitioning
+    pub fn progress(&self) -> f32 {
+        if self.duration <= 0.0 {
+            1.0
+        } else {
+            (self.elapsed / self.duration).min(1.0)
+        }
+    }
+
+    /// Blend from the current settings into a preset of the scene. False if there is no preset with this name
+    pub fn transition_to(&mut self, name: &str, seconds: f32) -> bool {
+        let Some(preset) = self
+            .presets
+            .iter()
+            .find(|preset| preset.name == name)
+            .cloned()
+        else {
+            return false;
+        };
+        self.blend_into(preset, seconds);
+        true
+    }
+
+    /// Switch to a preset of the scene without blending
+    pub fn set(&mut self, name: &str) -> bool {
+        self.transition_to(name, 0.0)
+    }
+
+    fn blend_into(&mut self, preset: WeatherPreset, seconds: f32) {
+        self.from = self.current.clone();
+        self.target = preset;
+        self.elapsed = 0.0;
+        self.duration = seconds.max(0.0);
+        if self.duration == 0.0 {
+            self.current = self.target.clone();
+        }
+    }
+}
+
+/// Picks presets and the active one up from the Weather entity when it spawns or is edited
+pub fn sync_weather_system(
+    mut controller: ResMut<WeatherController>,
+    query: Query<(Entity, Ref<IdentityData>)>,
+) {
+    let weather = query.iter().find_map(|(entity, identity)| {
+        let changed = identity.is_changed();
+        match &identity.into_inner().class {
+            GraniteTypes::Weather(weather) => Some((entity, changed, weather)),
+            _ => None,
+        }
+    });
+    let Some((entity, changed, weather)) = weather else {
+        if controller.source.is_some() {
+            controller.source = None;
+        }
+        return;
+    };
+
+    let spawned = controller.source != Some(entity);
+    if !spawned && !changed {
+        return;
+    }
+    controller.source = Some(entity);
+    controller.presets = weather.presets.clone();
+
+    let Some(preset) = weather.preset(&weather.active).cloned() else {
+        return;
+    };
+    if spawned {
+        controller.blend_into(preset, 0.0);
+    } else if controller.target.name != preset.name {
+        controller.blend_into(preset, weather.transition_time);
+    } else if controller.target != preset {
+        // Editing the active preset previews it right away
+        controller.target = preset.clone();
+        controller.current = preset;
+        controller.elapsed = controller.duration;
+    }
+}
+
+pub fn blend_weather_system(time: Res<Time>, mut controller: ResMut<WeatherController>) {
+    if !controller.is_transitioning() {
+        return;
+    }
+    controller.elapsed += time.delta_secs();
+    let t = controller.progress();
+    controller.current = controller.from.lerp(&controller.target, t);
+}
+
+/// Drives fog, sky color, ambient light and directional lights from the blended weather
+/// Only cameras that clear the screen get fog, overlay cameras are left alone
+pub fn apply_weather_system(
+    mut commands: Commands,
+    controller: Res<WeatherController>,
+    mut ambient: ResMut<GlobalAmbientLight>,
+    mut clear_color: ResMut<ClearColor>,
+    cameras: Query<(Entity, &Camera), With<Camera3d>>,
+    fogged: Query<(), With<WeatherFog>>,
+    mut lights: Query<(Ref<IdentityData>, &mut DirectionalLight)>,
+) {
+    if !controller.is_active() {
+        // Only undo weather that was applied, so scenes without weather keep their own settings
+        if !controller.is_changed() || fogged.is_empty() {
+            return;
+        }
+        // Weather was removed, put the defaults back
+        for (entity, _) in cameras
+            .iter()
+            .filter(|(entity, _)| fogged.contains(*entity))
+        {
+            commands
+                .entity(entity)
+                .remove::<(DistanceFog, WeatherFog)>();
+        }
+        *ambient = GlobalAmbientLight::default();
+        *clear_color = ClearColor::default();
+        for (identity, mut light) in lights.iter_mut() {
+            if let GraniteTypes::DirLight(dir_light) = &identity.class {
+                light.illuminance = dir_light.illuminance;
+            }
+        }
+        return;
+    }
+
+    let weather = controller.current();
+    let fog = DistanceFog {
+        color: Color::srgb(
+            weather.fog_color.0,
+            weather.fog_color.1,
+            weather.fog_color.2,
+        ),
+        falloff: FogFalloff::Exponential {
+            density: weather.fog_density,
+        },
+        ..Default::default()
+    };
+    for (entity, camera) in cameras.iter() {
+        if matches!(camera.clear_color, ClearColorConfig::None) {
+            continue;
+        }
+        if controller.is_changed() || !fogged.contains(entity) {
+            commands.entity(entity).insert((fog.clone(), WeatherFog));
+        }
+    }
+
+    if controller.is_changed() {
+        ambient.brightness = weather.ambient_brightness;
+        clear_color.0 = Color::srgb(
+            weather.sky_color.0,
+            weather.sky_color.1,
+            weather.sky_color.2,
+        );
+    }
+    for (identity, mut light) in lights.iter_mut() {
+        if !controller.is_changed() && !identity.is_changed() {
+            continue;
+        }
+        if let GraniteTypes::DirLight(dir_light) = &identity.class {
+            light.illuminance = dir_light.illuminance * weather.sun_intensity;
+        }
+    }
+}
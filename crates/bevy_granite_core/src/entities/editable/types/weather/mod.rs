@@ -0,0 +1,214 @@
+use crate::{
+    entities::{
+        editable::{GraniteType, RequestEntityUpdateFromClass},
+        EntitySaveReadyData,
+    },
+    AvailableEditableMaterials, ClassCategory, PromptData,
+};
+use bevy::{
+    asset::{AssetServer, Assets},
+    ecs::{
+        entity::Entity,
+        message::Message,
+        system::{Commands, Res, ResMut},
+    },
+    mesh::Mesh,
+    pbr::StandardMaterial,
+    reflect::Reflect,
+    transform::components::Transform,
+};
+use bevy_egui::egui;
+use serde::{Deserialize, Serialize};
+
+pub mod controller;
+pub mod creation;
+pub mod plugin;
+pub mod ui;
+pub mod update_event;
+
+pub use controller::*;
+pub use plugin::*;
+pub use update_event::*;
+
+/// Internal event thats called when user edits UI weather variables
+#[derive(Message)]
+pub struct UserUpdatedWeatherEvent {
+    pub entity: Entity,
+    pub data: Weather,
+}
+
+/// Atmosphere, fog and light settings of one kind of weather
+#[derive(Serialize, Deserialize, Reflect, Debug, Clone, PartialEq)]
+pub struct WeatherPreset {
+    pub name: String,
+    /// Background of cameras that clear the screen
+    pub sky_color: (f32, f32, f32),
+    pub fog_color: (f32, f32, f32),
+    /// Exponential fog density, 0 is no fog
+    pub fog_density: f32,
+    pub ambient_brightness: f32,
+    /// Multiplies the illuminance of every directional light
+    pub sun_intensity: f32,
+    /// 0 to 1. Granite doesn't draw rain, particle or audio systems read it from WeatherController
+    pub rain_intensity: f32,
+}
+
+impl Default for WeatherPreset {
+    fn default() -> Self {
+        Self::clear()
+    }
+}
+
+impl WeatherPreset {
+    pub fn clear() -> Self {
+        Self {
+            name: "clear".to_string(),
+            sky_color: (0.45, 0.65, 0.95),
+            fog_color: (0.7, 0.8, 0.95),
+            fog_density: 0.0,
+            ambient_brightness: 80.0,
+            sun_intensity: 1.0,
+            rain_intensity: 0.0,
+        }
+    }
+
+    pub fn overcast() -> Self {
+        Self {
+            name: "overcast".to_string(),
+            sky_color: (0.6, 0.63, 0.67),
+            fog_color: (0.6, 0.63, 0.67),
+            fog_density: 0.005,
+            ambient_brightness: 120.0,
+            sun_intensity: 0.3,
+            rain_intensity: 0.0,
+        }
+    }
+
+    pub fn fog() -> Self {
+        Self {
+            name: "fog".to_string(),
+            sky_color: (0.7, 0.72, 0.75),
+            fog_color: (0.7, 0.72, 0.75),
+            fog_density: 0.05,
+            ambient_brightness: 100.0,
+            sun_intensity: 0.2,
+            rain_intensity: 0.0,
+        }
+    }
+
+    pub fn rain() -> Self {
+        Self {
+            name: "rain".to_string(),
+            sky_color: (0.4, 0.43, 0.47),
+            fog_color: (0.45, 0.48, 0.52),
+            fog_density: 0.015,
+            ambient_brightness: 60.0,
+            sun_intensity: 0.15,
+            rain_intensity: 1.0,
+        }
+    }
+
+    /// Blend towards other, t from 0 to 1. Takes the name of other
+    pub fn lerp(&self, other: &WeatherPreset, t: f32) -> WeatherPreset {
+        let t = t.clamp(0.0, 1.0);
+        let mix = |a: f32, b: f32| a + (b - a) * t;
+        let mix_color =
+            |a: (f32, f32, f32), b: (f32, f32, f32)| (mix(a.0, b.0), mix(a.1, b.1), mix(a.2, b.2));
+        WeatherPreset {
+            name: other.name.clone(),
+            sky_color: mix_color(self.sky_color, other.sky_color),
+            fog_color: mix_color(self.fog_color, other.fog_color),
+            fog_density: mix(self.fog_density, other.fog_density),
+            ambient_brightness: mix(self.ambient_brightness, other.ambient_brightness),
+            sun_intensity: mix(self.sun_intensity, other.sun_intensity),
+            rain_intensity: mix(self.rain_intensity, other.rain_intensity),
+        }
+    }
+}
+
+/// Actual serialized class data thats stored inside IdentityData
+/// Weather presets of the scene and the one that is active. One per scene, WeatherController follows the first it finds
+#[derive(Serialize, Deserialize, Reflect, Debug, Clone, PartialEq)]
+pub struct Weather {
+    pub presets: Vec<WeatherPreset>,
+    /// Name of the preset in use
+    pub active: String,
+    /// Seconds to blend into a newly activated preset
+    pub transition_time: f32,
+}
+
+impl Default for Weather {
+    fn default() -> Self {
+        Self {
+            presets: vec![
+                WeatherPreset::clear(),
+                WeatherPreset::overcast(),
+                WeatherPreset::fog(),
+                WeatherPreset::rain(),
+            ],
+            active: "clear".to_string(),
+            transition_time: 5.0,
+        }
+    }
+}
+
+impl Weather {
+    pub fn preset(&self, name: &str) -> Option<&WeatherPreset> {
+        self.presets.iter().find(|preset| preset.name == name)
+    }
+}
+
+impl GraniteType for Weather {
+    fn type_name(&self) -> String {
+        "Weather".to_string()
+    }
+
+    fn type_abv(&self) -> String {
+        "Weather".to_string()
+    }
+
+    fn category(&self) -> ClassCategory {
+        ClassCategory::Light
+    }
+
+    fn get_embedded_icon_bytes(&self) -> Option<&'static [u8]> {
+        Some(include_bytes!("Weather.png"))
+    }
+
+    fn get_icon_filename(&self) -> Option<&'static str> {
+        Some("Weather.png")
+    }
+
+    fn spawn_from_new_identity(
+        &mut self,
+        commands: &mut Commands,
+        transform: Transform,
+        _standard_materials: ResMut<Assets<StandardMaterial>>,
+        _meshes: ResMut<Assets<Mesh>>,
+        _available_materials: ResMut<AvailableEditableMaterials>,
+        _asset_server: Res<AssetServer>,
+        _maybe_prompt_data: Option<PromptData>,
+    ) -> Entity {
+        Weather::spawn_from_new_identity(self, commands, transform)
+    }
+
+    fn spawn_from_save_data(
+        &self,
+        save_data: &EntitySaveReadyData,
+        commands: &mut Commands,
+        _standard_materials: &mut ResMut<Assets<StandardMaterial>>,
+        _meshes: &mut ResMut<Assets<Mesh>>,
+        _available_materials: &mut ResMut<AvailableEditableMaterials>,
+        _asset_server: &Res<AssetServer>,
+    ) -> Entity {
+        Weather::spawn_from_save_data(save_data, commands)
+    }
+
+    fn push_to_entity(&self, entity: Entity, request_update: &mut RequestEntityUpdateFromClass) {
+        self.push_to_entity(entity, request_update)
+    }
+
+    fn edit_via_ui(&mut self, ui: &mut egui::Ui, spacing: (f32, f32, f32)) -> bool {
+        self.edit_via_ui(ui, spacing)
+    }
+}
@@ -0,0 +1,44 @@
+use super::{
+    apply_weather_system, blend_weather_system, sync_weather_system, update_weather_system,
+    UserUpdatedWeatherEvent, Weather, WeatherController, WeatherPreset,
+};
+use crate::{register_class, GraniteTypes};
+use bevy::app::{App, Plugin, PostUpdate, Update};
+use bevy::ecs::schedule::IntoScheduleConfigs;
+
+pub struct WeatherPlugin;
+impl Plugin for WeatherPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            //
+            // Event
+            //
+            .add_message::<UserUpdatedWeatherEvent>()
+            //
+            // Register
+            //
+            .register_type::<Weather>()
+            .register_type::<WeatherPreset>()
+            //
+            // Resources
+            //
+            .init_resource::<WeatherController>()
+            //
+            // Schedule system
+            //
+            .add_systems(
+                Update,
+                (
+                    update_weather_system,
+                    sync_weather_system,
+                    blend_weather_system,
+                )
+                    .chain(),
+            )
+            // After directional light edits, so the sun intensity stays on top of them
+            .add_systems(PostUpdate, apply_weather_system);
+
+        // Class registry
+        register_class(app, GraniteTypes::Weather(Default::default()));
+    }
+}
@@ -0,0 +1,154 @@
+use super::{Weather, WeatherPreset};
+use bevy_egui::egui;
+
+impl Weather {
+    /// Function to edit self's data via UI side panel
+    /// Picking another active preset blends the viewport into it, so presets can be previewed from here
+    pub fn edit_via_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        // Small, Large, Normal
+        spacing: (f32, f32, f32),
+    ) -> bool {
+        let large_spacing = spacing.1;
+        let mut changed = false;
+
+        ui.label(egui::RichText::new("Weather").italics());
+        ui.add_space(large_spacing);
+
+        egui::Grid::new("weather_grid")
+            .num_columns(2)
+            .spacing([large_spacing, large_spacing])
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Active:");
+                egui::ComboBox::from_id_salt("weather_active")
+                    .selected_text(self.active.clone())
+                    .show_ui(ui, |ui| {
+                        for preset in &self.presets {
+                            changed |= ui
+                                .selectable_value(
+                                    &mut self.active,
+                                    preset.name.clone(),
+                                    &preset.name,
+                                )
+                                .changed();
+                        }
+                    });
+                ui.end_row();
+
+                ui.label("Transition Time:");
+                changed |= ui
+                    .add(
+                        egui::DragValue::new(&mut self.transition_time)
+                            .speed(0.1)
+                            .range(0.0..=f32::MAX)
+                            .suffix(" s"),
+                    )
+                    .changed();
+                ui.end_row();
+            });
+        ui.add_space(large_spacing);
+
+        ui.label(format!("Presets ({})", self.presets.len()));
+        let mut remove = None;
+        for (index, preset) in self.presets.iter_mut().enumerate() {
+            ui.push_id(index, |ui| {
+                ui.collapsing(preset.name.clone(), |ui| {
+                    changed |= edit_preset(ui, preset, large_spacing);
+                    if ui.small_button("Remove").clicked() {
+                        remove = Some(index);
+                    }
+                });
+            });
+        }
+        if let Some(index) = remove {
+            self.presets.remove(index);
+            changed = true;
+        }
+        if ui.button("Add Preset").clicked() {
+            // Start from the active preset, it is usually the closest to the new one
+            let mut preset = self.preset(&self.active).cloned().unwrap_or_default();
+            preset.name = format!("preset_{}", self.presets.len());
+            self.presets.push(preset);
+            changed = true;
+        }
+        ui.add_space(large_spacing);
+        changed
+    }
+}
+
+fn edit_preset(ui: &mut egui::Ui, preset: &mut WeatherPreset, large_spacing: f32) -> bool {
+    let mut changed = false;
+    egui::Grid::new("weather_preset_grid")
+        .num_columns(2)
+        .spacing([large_spacing, large_spacing])
+        .striped(true)
+        .show(ui, |ui| {
+            ui.label("Name:");
+            changed |= ui.text_edit_singleline(&mut preset.name).changed();
+            ui.end_row();
+
+            ui.label("Sky Color:");
+            changed |= color_edit(ui, &mut preset.sky_color);
+            ui.end_row();
+
+            ui.label("Fog Color:");
+            changed |= color_edit(ui, &mut preset.fog_color);
+            ui.end_row();
+
+            ui.label("Fog Density:");
+            changed |= ui
+                .add(
+                    egui::DragValue::new(&mut preset.fog_density)
+                        .speed(0.001)
+                        .range(0.0..=1.0),
+                )
+                .changed();
+            ui.end_row();
+
+            ui.label("Ambient Brightness:");
+            changed |= ui
+                .add(
+                    egui::DragValue::new(&mut preset.ambient_brightness)
+                        .speed(1.0)
+                        .range(0.0..=f32::MAX),
+                )
+                .changed();
+            ui.end_row();
+
+            ui.label("Sun Intensity:");
+            changed |= ui
+                .add(
+                    egui::DragValue::new(&mut preset.sun_intensity)
+                        .speed(0.01)
+                        .range(0.0..=f32::MAX),
+                )
+                .changed();
+            ui.end_row();
+
+            ui.label("Rain Intensity:");
+            changed |= ui
+                .add(egui::Slider::new(&mut preset.rain_intensity, 0.0..=1.0))
+                .changed();
+            ui.end_row();
+        });
+    changed
+}
+
+fn color_edit(ui: &mut egui::Ui, color: &mut (f32, f32, f32)) -> bool {
+    let mut color_array = [
+        (color.0 * 255.0) as u8,
+        (color.1 * 255.0) as u8,
+        (color.2 * 255.0) as u8,
+    ];
+    if ui.color_edit_button_srgb(&mut color_array).changed() {
+        *color = (
+            color_array[0] as f32 / 255.0,
+            color_array[1] as f32 / 255.0,
+            color_array[2] as f32 / 255.0,
+        );
+        return true;
+    }
+    false
+}
@@ -0,0 +1,43 @@
+use crate::entities::editable::{RequestEntityUpdateFromClass, UserUpdatedWeatherEvent};
+use crate::entities::Weather;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::message::MessageReader;
+use bevy_granite_logging::{log, LogCategory, LogLevel, LogType};
+
+impl Weather {
+    pub fn push_to_entity(
+        &self,
+        entity: Entity,
+        request_update: &mut RequestEntityUpdateFromClass,
+    ) {
+        log!(
+            LogType::Editor,
+            LogLevel::Info,
+            LogCategory::Entity,
+            "Requesting weather entity update"
+        );
+
+        request_update.weather.write(UserUpdatedWeatherEvent {
+            entity,
+            data: self.clone(),
+        });
+    }
+}
+
+/// The presets are read from IdentityData, so WeatherController picks edits up on its own
+pub fn update_weather_system(mut reader: MessageReader<UserUpdatedWeatherEvent>) {
+    for UserUpdatedWeatherEvent {
+        entity: requested_entity,
+        data: new,
+    } in reader.read()
+    {
+        log!(
+            LogType::Editor,
+            LogLevel::Info,
+            LogCategory::Entity,
+            "Heard weather update event: {} is now '{}'",
+            requested_entity,
+            new.active
+        );
+    }
+}
@@ -0,0 +1,346 @@
+use super::{EnvironmentFogMode, WorldEnvironment};
+use crate::{GraniteTypes, IdentityData, QualityLevels, QualitySettings, WeatherController};
+use bevy::{
+    asset::{AssetEvent, AssetServer, Assets, Handle},
+    camera::{Camera, Camera3d, ClearColor, ClearColorConfig},
+    color::Color,
+    core_pipeline::Skybox,
+    ecs::{
+        change_detection::{DetectChanges, DetectChangesMut},
+        component::Component,
+        entity::Entity,
+        message::MessageReader,
+        query::{Has, With},
+        resource::Resource,
+        system::{Commands, Query, Res, ResMut, SystemParam},
+        world::Ref,
+    },
+    image::Image,
+    light::{
+        DirectionalLightShadowMap, EnvironmentMapLight, GlobalAmbientLight, PointLightShadowMap,
+    },
+    math::Quat,
+    pbr::{DistanceFog, FogFalloff},
+    render::render_resource::{TextureViewDescriptor, TextureViewDimension},
+};
+use bevy_granite_logging::{log, LogCategory, LogLevel, LogType};
+
+// apply.rs
+// Applies the WorldEnvironment of the scene to the global light resources and to every 3D camera
+// Weather, when the scene has one, keeps control of fog, ambient brightness and the clear color
+// Shadow map sizes follow QualitySettings on top of the authored ones
+
+/// Marks cameras whose skybox and environment map come from the WorldEnvironment
+#[derive(Component)]
+pub struct EnvironmentCamera;
+
+/// Marks cameras whose DistanceFog comes from the WorldEnvironment, so it can be taken off again
+#[derive(Component)]
+pub struct EnvironmentCameraFog;
+
+/// The WorldEnvironment entity being applied and the cubemaps it loaded
+#[derive(Resource, Default, Debug)]
+pub struct WorldEnvironmentState {
+    source: Option<Entity>,
+    settings: Option<WorldEnvironment>,
+    skybox: Option<Handle<Image>>,
+    diffuse: Option<Handle<Image>>,
+    specular: Option<Handle<Image>>,
+    /// Something was applied, so removing the entity has to put the defaults back
+    applied: bool,
+}
+
+impl WorldEnvironmentState {
+    pub fn source(&self) -> Option<Entity> {
+        self.source
+    }
+
+    pub fn settings(&self) -> Option<&WorldEnvironment> {
+        self.settings.as_ref()
+    }
+
+    fn handles(&self) -> impl Iterator<Item = &Handle<Image>> {
+        [&self.skybox, &self.diffuse, &self.specular]
+            .into_iter()
+            .flatten()
+    }
+}
+
+fn load_cubemap(asset_server: &AssetServer, path: &str) -> Option<Handle<Image>> {
+    (!path.is_empty()).then(|| asset_server.load(path.to_string()))
+}
+
+/// Follows the first WorldEnvironment entity in the world, loading its cubemaps when they change
+pub fn sync_world_environment_system(
+    mut state: ResMut<WorldEnvironmentState>,
+    asset_server: Res<AssetServer>,
+    query: Query<(Entity, Ref<IdentityData>)>,
+) {
+    let mut environments = query.iter().filter_map(|(entity, identity)| {
+        let changed = identity.is_changed();
+        match &identity.into_inner().class {
+            GraniteTypes::WorldEnvironment(environment) => Some((entity, changed, environment)),
+            _ => None,
+        }
+    });
+    let Some((entity, changed, environment)) = environments.next() else {
+        if state.source.is_some() {
+            state.source = None;
+            state.settings = None;
+            state.skybox = None;
+            state.diffuse = None;
+            state.specular = None;
+        }
+        return;
+    };
+
+    let spawned = state.source != Some(entity);
+    if !spawned && !changed {
+        return;
+    }
+    if spawned && environments.next().is_some() {
+        log!(
+            LogType::Game,
+            LogLevel::Warning,
+            LogCategory::Entity,
+            "More than one World Environment in the world, only {} is applied",
+            entity
+        );
+    }
+
+    let previous = state.settings.take().unwrap_or_default();
+    if spawned || previous.skybox != environment.skybox {
+        state.skybox = load_cubemap(&asset_server, &environment.skybox);
+    }
+    if spawned || previous.environment_diffuse != environment.environment_diffuse {
+        state.diffuse = load_cubemap(&asset_server, &environment.environment_diffuse);
+    }
+    if spawned || previous.environment_specular != environment.environment_specular {
+        state.specular = load_cubemap(&asset_server, &environment.environment_specular);
+    }
+    state.source = Some(entity);
+    state.settings = Some(environment.clone());
+}
+
+/// Images have no cubemap metadata, so six faces stacked vertically are turned into a cubemap once loaded
+pub fn prepare_environment_cubemaps_system(
+    mut state: ResMut<WorldEnvironmentState>,
+    mut events: MessageReader<AssetEvent<Image>>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    let loaded: Vec<_> = events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::LoadedWithDependencies { id } => Some(*id),
+            _ => None,
+        })
+        .filter(|id| state.handles().any(|handle| handle.id() == *id))
+        .collect();
+
+    for id in loaded {
+        let Some(image) = images.get_mut(id) else {
+            continue;
+        };
+        if prepare_cubemap(image) {
+            // Cameras only get the cubemaps once they are ready
+            state.set_changed();
+        }
+    }
+}
+
+/// Turns an image with six faces stacked vertically into a cubemap. False when it can't be one
+pub(crate) fn prepare_cubemap(image: &mut Image) -> bool {
+    if image.texture_descriptor.array_layer_count() == 1 {
+        if let Err(e) = image.reinterpret_stacked_2d_as_array(6) {
+            log!(
+                LogType::Game,
+                LogLevel::Error,
+                LogCategory::Asset,
+                "Environment cubemap needs six square faces stacked vertically: {:?}",
+                e
+            );
+            return false;
+        }
+    }
+    image.texture_view_descriptor = Some(TextureViewDescriptor {
+        dimension: Some(TextureViewDimension::Cube),
+        ..Default::default()
+    });
+    true
+}
+
+pub(crate) fn cubemap_ready(
+    images: &Assets<Image>,
+    handle: &Option<Handle<Image>>,
+) -> Option<Handle<Image>> {
+    let handle = handle.as_ref()?;
+    images
+        .get(handle)
+        .filter(|image| image.texture_descriptor.array_layer_count() == 6)
+        .map(|_| handle.clone())
+}
+
+type EnvironmentCameraQuery<'a> = (
+    Entity,
+    &'a Camera,
+    Has<EnvironmentCamera>,
+    Has<EnvironmentCameraFog>,
+);
+
+/// Pushes the environment into the light resources and cameras, and takes it off again when the entity is gone
+/// Only cameras that clear the screen are changed, overlay cameras are left alone
+pub fn apply_world_environment_system(
+    mut commands: Commands,
+    mut state: ResMut<WorldEnvironmentState>,
+    weather: Res<WeatherController>,
+    quality: Res<QualitySettings>,
+    images: Res<Assets<Image>>,
+    mut lights: EnvironmentLightResources,
+    cameras: Query<EnvironmentCameraQuery, With<Camera3d>>,
+) {
+    let update = state.is_changed() || weather.is_changed() || quality.is_changed();
+    let Some(environment) = state.settings.clone() else {
+        if !state.applied {
+            return;
+        }
+        state.applied = false;
+        for (entity, _, environment_camera, environment_fog) in cameras.iter() {
+            if environment_camera {
+                commands
+                    .entity(entity)
+                    .remove::<(Skybox, EnvironmentMapLight, EnvironmentCamera)>();
+            }
+            if environment_fog {
+                commands
+                    .entity(entity)
+                    .remove::<(DistanceFog, EnvironmentCameraFog)>();
+            }
+        }
+        lights.reset(!weather.is_active());
+        return;
+    };
+
+    if update {
+        lights.apply(&environment, &quality.levels(), !weather.is_active());
+        state.bypass_change_detection().applied = true;
+    }
+
+    let skybox = cubemap_ready(&images, &state.skybox);
+    let environment_map = cubemap_ready(&images, &state.diffuse)
+        .zip(cubemap_ready(&images, &state.specular))
+        .filter(|_| environment.has_environment_map());
+    let fog = (!weather.is_active())
+        .then(|| environment_fog(&environment))
+        .flatten();
+
+    for (entity, camera, environment_camera, environment_fog) in cameras.iter() {
+        if matches!(camera.clear_color, ClearColorConfig::None) {
+            continue;
+        }
+        if !update && environment_camera {
+            continue;
+        }
+        let mut camera = commands.entity(entity);
+        camera.insert(EnvironmentCamera);
+        match &skybox {
+            Some(image) => camera.insert(Skybox {
+                image: image.clone(),
+                brightness: environment.skybox_brightness,
+                rotation: Quat::IDENTITY,
+            }),
+            None => camera.remove::<Skybox>(),
+        };
+        match &environment_map {
+            Some((diffuse, specular)) => camera.insert(EnvironmentMapLight {
+                diffuse_map: diffuse.clone(),
+                specular_map: specular.clone(),
+                intensity: environment.environment_intensity,
+                ..Default::default()
+            }),
+            None => camera.remove::<EnvironmentMapLight>(),
+        };
+        match &fog {
+            Some(fog) => {
+                camera.insert((fog.clone(), EnvironmentCameraFog));
+            }
+            // Weather fog is Weather's to remove
+            None if environment_fog => {
+                camera.remove::<EnvironmentCameraFog>();
+                if !weather.is_active() {
+                    camera.remove::<DistanceFog>();
+                }
+            }
+            None => {}
+        }
+    }
+}
+
+fn environment_fog(environment: &WorldEnvironment) -> Option<DistanceFog> {
+    let fog = &environment.fog;
+    let falloff = match fog.mode {
+        EnvironmentFogMode::Off => return None,
+        EnvironmentFogMode::Linear => FogFalloff::Linear {
+            start: fog.start,
+            end: fog.end,
+        },
+        EnvironmentFogMode::Exponential => FogFalloff::Exponential {
+            density: fog.density,
+        },
+        EnvironmentFogMode::ExponentialSquared => FogFalloff::ExponentialSquared {
+            density: fog.density,
+        },
+    };
+    Some(DistanceFog {
+        color: Color::srgb(fog.color.0, fog.color.1, fog.color.2),
+        falloff,
+        ..Default::default()
+    })
+}
+
+/// Global light resources the environment drives
+#[derive(SystemParam)]
+pub struct EnvironmentLightResources<'w> {
+    ambient: ResMut<'w, GlobalAmbientLight>,
+    clear_color: ResMut<'w, ClearColor>,
+    directional_shadow_map: ResMut<'w, DirectionalLightShadowMap>,
+    point_shadow_map: ResMut<'w, PointLightShadowMap>,
+}
+
+impl EnvironmentLightResources<'_> {
+    /// Ambient brightness and clear color are skipped while weather drives them
+    fn apply(
+        &mut self,
+        environment: &WorldEnvironment,
+        quality: &QualityLevels,
+        brightness_and_sky: bool,
+    ) {
+        let (r, g, b) = environment.ambient_color;
+        self.ambient.color = Color::srgb(r, g, b);
+        if brightness_and_sky {
+            self.ambient.brightness = environment.ambient_brightness;
+            let (r, g, b) = environment.clear_color;
+            self.clear_color.0 = Color::srgb(r, g, b);
+        }
+        let directional = quality
+            .shadow_map_size(environment.directional_shadow_map_size)
+            .next_power_of_two();
+        if self.directional_shadow_map.size != directional {
+            self.directional_shadow_map.size = directional;
+        }
+        let point = quality.shadow_map_size(environment.point_shadow_map_size);
+        if self.point_shadow_map.size != point {
+            self.point_shadow_map.size = point;
+        }
+    }
+
+    fn reset(&mut self, brightness_and_sky: bool) {
+        if brightness_and_sky {
+            *self.ambient = GlobalAmbientLight::default();
+            *self.clear_color = ClearColor::default();
+        } else {
+            self.ambient.color = GlobalAmbientLight::default().color;
+        }
+        *self.directional_shadow_map = DirectionalLightShadowMap::default();
+        *self.point_shadow_map = PointLightShadowMap::default();
+    }
+}
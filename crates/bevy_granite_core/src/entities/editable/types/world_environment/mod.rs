@@ -0,0 +1,172 @@
+use crate::{
+    entities::{
+        editable::{GraniteType, RequestEntityUpdateFromClass},
+        EntitySaveReadyData,
+    },
+    AvailableEditableMaterials, ClassCategory, PromptData,
+};
+use bevy::{
+    asset::{AssetServer, Assets},
+    ecs::{
+        entity::Entity,
+        message::Message,
+        system::{Commands, Res, ResMut},
+    },
+    mesh::Mesh,
+    pbr::StandardMaterial,
+    reflect::Reflect,
+    transform::components::Transform,
+};
+use bevy_egui::egui;
+use serde::{Deserialize, Serialize};
+
+pub mod apply;
+pub mod creation;
+pub mod plugin;
+pub mod ui;
+pub mod update_event;
+
+pub use apply::*;
+pub use plugin::*;
+pub use update_event::*;
+
+/// Internal event thats called when user edits UI world environment variables
+#[derive(Message)]
+pub struct UserUpdatedWorldEnvironmentEvent {
+    pub entity: Entity,
+    pub data: WorldEnvironment,
+}
+
+#[derive(Serialize, Deserialize, Reflect, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EnvironmentFogMode {
+    #[default]
+    Off,
+    /// Fades in from start to end distance
+    Linear,
+    Exponential,
+    ExponentialSquared,
+}
+
+#[derive(Serialize, Deserialize, Reflect, Debug, Clone, PartialEq)]
+pub struct EnvironmentFog {
+    pub mode: EnvironmentFogMode,
+    pub color: (f32, f32, f32),
+    pub start: f32,
+    pub end: f32,
+    /// Used by the exponential modes
+    pub density: f32,
+}
+
+impl Default for EnvironmentFog {
+    fn default() -> Self {
+        Self {
+            mode: EnvironmentFogMode::Off,
+            color: (0.7, 0.8, 0.95),
+            start: 20.0,
+            end: 200.0,
+            density: 0.02,
+        }
+    }
+}
+
+/// Actual serialized class data thats stored inside IdentityData
+/// Global rendering settings of the scene. One per scene, the first one found is applied
+#[derive(Serialize, Deserialize, Reflect, Debug, Clone, PartialEq)]
+pub struct WorldEnvironment {
+    pub ambient_color: (f32, f32, f32),
+    pub ambient_brightness: f32,
+    /// Background of cameras that clear the screen, under the skybox if there is one
+    pub clear_color: (f32, f32, f32),
+    /// Cubemap with its six faces stacked vertically (+X, -X, +Y, -Y, +Z, -Z). Empty for none
+    pub skybox: String,
+    pub skybox_brightness: f32,
+    /// Diffuse and specular cubemaps lighting the scene, both are needed. Empty for none
+    pub environment_diffuse: String,
+    pub environment_specular: String,
+    pub environment_intensity: f32,
+    pub fog: EnvironmentFog,
+    /// Size of each directional light shadow cascade, a power of two
+    pub directional_shadow_map_size: usize,
+    /// Size of each face of the point light shadow cubemaps
+    pub point_shadow_map_size: usize,
+}
+
+impl Default for WorldEnvironment {
+    fn default() -> Self {
+        Self {
+            ambient_color: (1.0, 1.0, 1.0),
+            ambient_brightness: 80.0,
+            clear_color: (0.17, 0.17, 0.17),
+            skybox: String::new(),
+            skybox_brightness: 1000.0,
+            environment_diffuse: String::new(),
+            environment_specular: String::new(),
+            environment_intensity: 900.0,
+            fog: EnvironmentFog::default(),
+            directional_shadow_map_size: 2048,
+            point_shadow_map_size: 1024,
+        }
+    }
+}
+
+impl WorldEnvironment {
+    /// Both maps are set, a lone diffuse or specular map can't light anything
+    pub fn has_environment_map(&self) -> bool {
+        !self.environment_diffuse.is_empty() && !self.environment_specular.is_empty()
+    }
+}
+
+impl GraniteType for WorldEnvironment {
+    fn type_name(&self) -> String {
+        "World Environment".to_string()
+    }
+
+    fn type_abv(&self) -> String {
+        "Environment".to_string()
+    }
+
+    fn category(&self) -> ClassCategory {
+        ClassCategory::Light
+    }
+
+    fn get_embedded_icon_bytes(&self) -> Option<&'static [u8]> {
+        Some(include_bytes!("WorldEnvironment.png"))
+    }
+
+    fn get_icon_filename(&self) -> Option<&'static str> {
+        Some("WorldEnvironment.png")
+    }
+
+    fn spawn_from_new_identity(
+        &mut self,
+        commands: &mut Commands,
+        transform: Transform,
+        _standard_materials: ResMut<Assets<StandardMaterial>>,
+        _meshes: ResMut<Assets<Mesh>>,
+        _available_materials: ResMut<AvailableEditableMaterials>,
+        _asset_server: Res<AssetServer>,
+        _maybe_prompt_data: Option<PromptData>,
+    ) -> Entity {
+        WorldEnvironment::spawn_from_new_identity(self, commands, transform)
+    }
+
+    fn spawn_from_save_data(
+        &self,
+        save_data: &EntitySaveReadyData,
+        commands: &mut Commands,
+        _standard_materials: &mut ResMut<Assets<StandardMaterial>>,
+        _meshes: &mut ResMut<Assets<Mesh>>,
+        _available_materials: &mut ResMut<AvailableEditableMaterials>,
+        _asset_server: &Res<AssetServer>,
+    ) -> Entity {
+        WorldEnvironment::spawn_from_save_data(save_data, commands)
+    }
+
+    fn push_to_entity(&self, entity: Entity, request_update: &mut RequestEntityUpdateFromClass) {
+        self.push_to_entity(entity, request_update)
+    }
+
+    fn edit_via_ui(&mut self, ui: &mut egui::Ui, spacing: (f32, f32, f32)) -> bool {
+        self.edit_via_ui(ui, spacing)
+    }
+}
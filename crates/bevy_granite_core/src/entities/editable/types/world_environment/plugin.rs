@@ -0,0 +1,51 @@
+use super::{
+    apply_world_environment_system, prepare_environment_cubemaps_system,
+    sync_world_environment_system, update_world_environment_system, EnvironmentFog,
+    EnvironmentFogMode, UserUpdatedWorldEnvironmentEvent, WorldEnvironment, WorldEnvironmentState,
+};
+use crate::{
+    entities::editable::types::weather::apply_weather_system, register_class, GraniteTypes,
+};
+use bevy::app::{App, Plugin, PostUpdate, Update};
+use bevy::ecs::schedule::IntoScheduleConfigs;
+
+pub struct WorldEnvironmentPlugin;
+impl Plugin for WorldEnvironmentPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            //
+            // Event
+            //
+            .add_message::<UserUpdatedWorldEnvironmentEvent>()
+            //
+            // Register
+            //
+            .register_type::<WorldEnvironment>()
+            .register_type::<EnvironmentFog>()
+            .register_type::<EnvironmentFogMode>()
+            //
+            // Resources
+            //
+            .init_resource::<WorldEnvironmentState>()
+            //
+            // Schedule system
+            //
+            .add_systems(
+                Update,
+                (
+                    update_world_environment_system,
+                    sync_world_environment_system,
+                    prepare_environment_cubemaps_system,
+                )
+                    .chain(),
+            )
+            // After weather, which keeps fog, ambient brightness and sky color when the scene has one
+            .add_systems(
+                PostUpdate,
+                apply_world_environment_system.after(apply_weather_system),
+            );
+
+        // Class registry
+        register_class(app, GraniteTypes::WorldEnvironment(Default::default()));
+    }
+}
@@ -0,0 +1,236 @@
+use super::{EnvironmentFogMode, WorldEnvironment};
+use crate::{absolute_asset_to_rel, shared::asset_file_browser};
+use bevy_egui::egui;
+
+const SHADOW_MAP_SIZES: [usize; 5] = [512, 1024, 2048, 4096, 8192];
+
+impl WorldEnvironment {
+    /// Function to edit self's data via UI side panel
+    pub fn edit_via_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        // Small, Large, Normal
+        spacing: (f32, f32, f32),
+    ) -> bool {
+        let large_spacing = spacing.1;
+        let mut changed = false;
+
+        ui.label(egui::RichText::new("World Environment").italics());
+        ui.add_space(large_spacing);
+
+        egui::Grid::new("world_environment_grid")
+            .num_columns(2)
+            .spacing([large_spacing, large_spacing])
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Ambient Color:");
+                changed |= color_edit(ui, &mut self.ambient_color);
+                ui.end_row();
+
+                ui.label("Ambient Brightness:");
+                changed |= ui
+                    .add(
+                        egui::DragValue::new(&mut self.ambient_brightness)
+                            .speed(1.0)
+                            .range(0.0..=f32::MAX),
+                    )
+                    .changed();
+                ui.end_row();
+
+                ui.label("Clear Color:");
+                changed |= color_edit(ui, &mut self.clear_color);
+                ui.end_row();
+            });
+        ui.add_space(large_spacing);
+
+        ui.collapsing("Skybox", |ui| {
+            egui::Grid::new("world_environment_skybox_grid")
+                .num_columns(2)
+                .spacing([large_spacing, large_spacing])
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label("Cubemap:");
+                    changed |= cubemap_path_edit(ui, &mut self.skybox);
+                    ui.end_row();
+
+                    ui.label("Brightness:");
+                    changed |= ui
+                        .add(
+                            egui::DragValue::new(&mut self.skybox_brightness)
+                                .speed(10.0)
+                                .range(0.0..=f32::MAX),
+                        )
+                        .changed();
+                    ui.end_row();
+                });
+        });
+
+        ui.collapsing("Environment Map", |ui| {
+            egui::Grid::new("world_environment_map_grid")
+                .num_columns(2)
+                .spacing([large_spacing, large_spacing])
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label("Diffuse:");
+                    changed |= cubemap_path_edit(ui, &mut self.environment_diffuse);
+                    ui.end_row();
+
+                    ui.label("Specular:");
+                    changed |= cubemap_path_edit(ui, &mut self.environment_specular);
+                    ui.end_row();
+
+                    ui.label("Intensity:");
+                    changed |= ui
+                        .add(
+                            egui::DragValue::new(&mut self.environment_intensity)
+                                .speed(10.0)
+                                .range(0.0..=f32::MAX),
+                        )
+                        .changed();
+                    ui.end_row();
+                });
+        });
+
+        ui.collapsing("Fog", |ui| {
+            let fog = &mut self.fog;
+            egui::Grid::new("world_environment_fog_grid")
+                .num_columns(2)
+                .spacing([large_spacing, large_spacing])
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label("Mode:");
+                    egui::ComboBox::from_id_salt("world_environment_fog_mode")
+                        .selected_text(format!("{:?}", fog.mode))
+                        .show_ui(ui, |ui| {
+                            for mode in [
+                                EnvironmentFogMode::Off,
+                                EnvironmentFogMode::Linear,
+                                EnvironmentFogMode::Exponential,
+                                EnvironmentFogMode::ExponentialSquared,
+                            ] {
+                                changed |= ui
+                                    .selectable_value(&mut fog.mode, mode, format!("{:?}", mode))
+                                    .changed();
+                            }
+                        });
+                    ui.end_row();
+
+                    if fog.mode == EnvironmentFogMode::Off {
+                        return;
+                    }
+
+                    ui.label("Color:");
+                    changed |= color_edit(ui, &mut fog.color);
+                    ui.end_row();
+
+                    if fog.mode == EnvironmentFogMode::Linear {
+                        ui.label("Start:");
+                        changed |= ui
+                            .add(
+                                egui::DragValue::new(&mut fog.start)
+                                    .speed(0.5)
+                                    .range(0.0..=fog.end),
+                            )
+                            .changed();
+                        ui.end_row();
+
+                        ui.label("End:");
+                        changed |= ui
+                            .add(
+                                egui::DragValue::new(&mut fog.end)
+                                    .speed(0.5)
+                                    .range(fog.start..=f32::MAX),
+                            )
+                            .changed();
+                        ui.end_row();
+                    } else {
+                        ui.label("Density:");
+                        changed |= ui
+                            .add(
+                                egui::DragValue::new(&mut fog.density)
+                                    .speed(0.001)
+                                    .range(0.0..=1.0),
+                            )
+                            .changed();
+                        ui.end_row();
+                    }
+                });
+        });
+
+        ui.collapsing("Shadows", |ui| {
+            egui::Grid::new("world_environment_shadow_grid")
+                .num_columns(2)
+                .spacing([large_spacing, large_spacing])
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label("Directional Map Size:");
+                    changed |= shadow_map_size_edit(
+                        ui,
+                        "world_environment_directional_shadow",
+                        &mut self.directional_shadow_map_size,
+                    );
+                    ui.end_row();
+
+                    ui.label("Point Map Size:");
+                    changed |= shadow_map_size_edit(
+                        ui,
+                        "world_environment_point_shadow",
+                        &mut self.point_shadow_map_size,
+                    );
+                    ui.end_row();
+                });
+        });
+        ui.add_space(large_spacing);
+        changed
+    }
+}
+
+pub(crate) fn cubemap_path_edit(ui: &mut egui::Ui, path: &mut String) -> bool {
+    let mut changed = false;
+    ui.horizontal(|ui| {
+        changed |= ui
+            .text_edit_singleline(path)
+            .on_hover_text("Six square faces stacked vertically: +X, -X, +Y, -Y, +Z, -Z")
+            .changed();
+        if ui.button("📁").clicked() {
+            if let Some(selected) =
+                asset_file_browser("textures".to_string(), vec!["png", "jpg", "jpeg", "tga"])
+            {
+                *path = absolute_asset_to_rel(selected).to_string();
+                changed = true;
+            }
+        }
+    });
+    changed
+}
+
+fn shadow_map_size_edit(ui: &mut egui::Ui, id: &str, size: &mut usize) -> bool {
+    let mut changed = false;
+    egui::ComboBox::from_id_salt(id)
+        .selected_text(size.to_string())
+        .show_ui(ui, |ui| {
+            for option in SHADOW_MAP_SIZES {
+                changed |= ui
+                    .selectable_value(size, option, option.to_string())
+                    .changed();
+            }
+        });
+    changed
+}
+
+fn color_edit(ui: &mut egui::Ui, color: &mut (f32, f32, f32)) -> bool {
+    let mut color_array = [
+        (color.0 * 255.0) as u8,
+        (color.1 * 255.0) as u8,
+        (color.2 * 255.0) as u8,
+    ];
+    if ui.color_edit_button_srgb(&mut color_array).changed() {
+        *color = (
+            color_array[0] as f32 / 255.0,
+            color_array[1] as f32 / 255.0,
+            color_array[2] as f32 / 255.0,
+        );
+        return true;
+    }
+    false
+}
@@ -0,0 +1,46 @@
+use crate::entities::editable::{RequestEntityUpdateFromClass, UserUpdatedWorldEnvironmentEvent};
+use crate::entities::WorldEnvironment;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::message::MessageReader;
+use bevy_granite_logging::{log, LogCategory, LogLevel, LogType};
+
+impl WorldEnvironment {
+    pub fn push_to_entity(
+        &self,
+        entity: Entity,
+        request_update: &mut RequestEntityUpdateFromClass,
+    ) {
+        log!(
+            LogType::Editor,
+            LogLevel::Info,
+            LogCategory::Entity,
+            "Requesting world environment entity update"
+        );
+
+        request_update
+            .world_environment
+            .write(UserUpdatedWorldEnvironmentEvent {
+                entity,
+                data: self.clone(),
+            });
+    }
+}
+
+/// The settings are read from IdentityData, so the environment systems pick edits up on their own
+pub fn update_world_environment_system(
+    mut reader: MessageReader<UserUpdatedWorldEnvironmentEvent>,
+) {
+    for UserUpdatedWorldEnvironmentEvent {
+        entity: requested_entity,
+        ..
+    } in reader.read()
+    {
+        log!(
+            LogType::Editor,
+            LogLevel::Info,
+            LogCategory::Entity,
+            "Heard world environment update event: {}",
+            requested_entity
+        );
+    }
+}
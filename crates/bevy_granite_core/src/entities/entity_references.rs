@@ -0,0 +1,306 @@
+use super::IdentityData;
+use bevy::{
+    prelude::{AppTypeRegistry, Entity, EntityRef, ReflectComponent, World},
+    reflect::{DynamicEnum, DynamicVariant, PartialReflect, ReflectMut, ReflectRef},
+};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+// entity_references.rs
+// Entities point at each other by uuid so the links survive save and load: joints, waypoint links,
+// and any component of a game with a Uuid field. Nothing notices when the entity pointed at is deleted,
+// so deletes look for them first through reflection, and clear or reassign them before despawning
+
+/// One component of an entity that holds the uuid of another entity
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntityReference {
+    /// Entity holding the reference
+    pub entity: Entity,
+    /// Short type path of the component holding it
+    pub component: String,
+    /// Full type path of the component, to find it again
+    pub type_path: String,
+    /// Uuid referenced
+    pub target: Uuid,
+}
+
+/// References to any of `targets` held by entities outside of them
+/// Every reflected component is searched, including the class data in IdentityData
+pub fn find_entity_references(world: &mut World, targets: &[Uuid]) -> Vec<EntityReference> {
+    let targets: HashSet<Uuid> = targets.iter().copied().collect();
+    if targets.is_empty() {
+        return vec![];
+    }
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = type_registry.read();
+
+    let mut references = vec![];
+    let mut query = world.query::<EntityRef>();
+    for entity_ref in query.iter(world) {
+        // The targets themselves go away, and an entity's own uuid is not a reference
+        if entity_ref
+            .get::<IdentityData>()
+            .is_some_and(|identity| targets.contains(&identity.uuid))
+        {
+            continue;
+        }
+        for component_id in entity_ref.archetype().components() {
+            let Some(registration) = world
+                .components()
+                .get_info(*component_id)
+                .and_then(|info| info.type_id())
+                .and_then(|type_id| registry.get(type_id))
+            else {
+                continue;
+            };
+            let Some(value) = registration
+                .data::<ReflectComponent>()
+                .and_then(|reflect| reflect.reflect(entity_ref))
+            else {
+                continue;
+            };
+            let mut found = vec![];
+            let skip_own = registration.type_id() == std::any::TypeId::of::<IdentityData>();
+            collect_uuids(value.as_partial_reflect(), &targets, skip_own, &mut found);
+            for target in found {
+                references.push(EntityReference {
+                    entity: entity_ref.id(),
+                    component: registration
+                        .type_info()
+                        .type_path_table()
+                        .short_path()
+                        .to_string(),
+                    type_path: registration.type_info().type_path().to_string(),
+                    target,
+                });
+            }
+        }
+    }
+    references
+}
+
+/// Points the given references at `replacement`, or clears them when it is None
+/// Cleared optional fields become None and list entries are removed. A component whose plain uuid field
+/// can't be cleared is removed whole, i.e. a joint to a deleted body. Returns how many components changed
+pub fn retarget_entity_references(
+    world: &mut World,
+    references: &[EntityReference],
+    replacement: Option<Uuid>,
+) -> usize {
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = type_registry.read();
+
+    let mut by_component: Vec<(Entity, &str, HashSet<Uuid>)> = vec![];
+    for reference in references {
+        match by_component.iter_mut().find(|(entity, type_path, _)| {
+            *entity == reference.entity && *type_path == reference.type_path
+        }) {
+            Some((_, _, targets)) => {
+                targets.insert(reference.target);
+            }
+            None => by_component.push((
+                reference.entity,
+                reference.type_path.as_str(),
+                HashSet::from([reference.target]),
+            )),
+        }
+    }
+
+    let mut changed = 0;
+    for (entity, type_path, targets) in by_component {
+        let Some(registration) = registry.get_with_type_path(type_path) else {
+            continue;
+        };
+        let Some(reflect_component) = registration.data::<ReflectComponent>() else {
+            continue;
+        };
+        let is_identity = registration.type_id() == std::any::TypeId::of::<IdentityData>();
+        let Ok(mut entity_mut) = world.get_entity_mut(entity) else {
+            continue;
+        };
+        let Some(mut value) = reflect_component.reflect_mut(&mut entity_mut) else {
+            continue;
+        };
+        let unclearable = replace_uuids(
+            value.as_partial_reflect_mut(),
+            &targets,
+            replacement,
+            is_identity,
+        );
+        if unclearable {
+            if is_identity {
+                // The class data stays, the reference becomes nil
+                replace_uuids(
+                    value.as_partial_reflect_mut(),
+                    &targets,
+                    Some(Uuid::nil()),
+                    true,
+                );
+            } else {
+                reflect_component.remove(&mut entity_mut);
+            }
+        }
+        changed += 1;
+    }
+    changed
+}
+
+fn collect_uuids(
+    value: &dyn PartialReflect,
+    targets: &HashSet<Uuid>,
+    skip_own: bool,
+    found: &mut Vec<Uuid>,
+) {
+    if let Some(uuid) = value.try_downcast_ref::<Uuid>() {
+        if targets.contains(uuid) {
+            found.push(*uuid);
+        }
+        return;
+    }
+    match value.reflect_ref() {
+        ReflectRef::Struct(value) => {
+            for index in 0..value.field_len() {
+                if skip_own && value.name_at(index) == Some("uuid") {
+                    continue;
+                }
+                if let Some(field) = value.field_at(index) {
+                    collect_uuids(field, targets, false, found);
+                }
+            }
+        }
+        ReflectRef::TupleStruct(value) => {
+            for field in value.iter_fields() {
+                collect_uuids(field, targets, false, found);
+            }
+        }
+        ReflectRef::Tuple(value) => {
+            for field in value.iter_fields() {
+                collect_uuids(field, targets, false, found);
+            }
+        }
+        ReflectRef::List(value) => {
+            for item in value.iter() {
+                collect_uuids(item, targets, false, found);
+            }
+        }
+        ReflectRef::Array(value) => {
+            for item in value.iter() {
+                collect_uuids(item, targets, false, found);
+            }
+        }
+        ReflectRef::Map(value) => {
+            for (_, item) in value.iter() {
+                collect_uuids(item, targets, false, found);
+            }
+        }
+        ReflectRef::Enum(value) => {
+            for field in value.iter_fields() {
+                collect_uuids(field.value(), targets, false, found);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Returns true when the value holds a target in a place that can't be cleared, for the caller to drop it
+fn replace_uuids(
+    value: &mut dyn PartialReflect,
+    targets: &HashSet<Uuid>,
+    replacement: Option<Uuid>,
+    skip_own: bool,
+) -> bool {
+    if let Some(uuid) = value.try_downcast_mut::<Uuid>() {
+        if !targets.contains(uuid) {
+            return false;
+        }
+        return match replacement {
+            Some(replacement) => {
+                *uuid = replacement;
+                false
+            }
+            None => true,
+        };
+    }
+    match value.reflect_mut() {
+        ReflectMut::Struct(value) => {
+            let mut unclearable = false;
+            for index in 0..value.field_len() {
+                if skip_own && value.name_at(index) == Some("uuid") {
+                    continue;
+                }
+                if let Some(field) = value.field_at_mut(index) {
+                    unclearable |= replace_uuids(field, targets, replacement, false);
+                }
+            }
+            unclearable
+        }
+        ReflectMut::TupleStruct(value) => {
+            let mut unclearable = false;
+            for index in 0..value.field_len() {
+                if let Some(field) = value.field_mut(index) {
+                    unclearable |= replace_uuids(field, targets, replacement, false);
+                }
+            }
+            unclearable
+        }
+        ReflectMut::Tuple(value) => {
+            let mut unclearable = false;
+            for index in 0..value.field_len() {
+                if let Some(field) = value.field_mut(index) {
+                    unclearable |= replace_uuids(field, targets, replacement, false);
+                }
+            }
+            unclearable
+        }
+        ReflectMut::List(value) => {
+            for index in (0..value.len()).rev() {
+                let drop = value
+                    .get_mut(index)
+                    .is_some_and(|item| replace_uuids(item, targets, replacement, false));
+                if drop {
+                    value.remove(index);
+                }
+            }
+            false
+        }
+        ReflectMut::Array(value) => {
+            let mut unclearable = false;
+            for index in 0..value.len() {
+                if let Some(item) = value.get_mut(index) {
+                    unclearable |= replace_uuids(item, targets, replacement, false);
+                }
+            }
+            unclearable
+        }
+        ReflectMut::Map(value) => {
+            let keys: Vec<Box<dyn PartialReflect>> =
+                value.iter().map(|(key, _)| key.to_dynamic()).collect();
+            for key in keys {
+                let drop = value
+                    .get_mut(key.as_ref())
+                    .is_some_and(|item| replace_uuids(item, targets, replacement, false));
+                if drop {
+                    value.remove(key.as_ref());
+                }
+            }
+            false
+        }
+        ReflectMut::Enum(value) => {
+            let mut unclearable = false;
+            for index in 0..value.field_len() {
+                if let Some(field) = value.field_at_mut(index) {
+                    unclearable |= replace_uuids(field, targets, replacement, false);
+                }
+            }
+            let is_option = value
+                .get_represented_type_info()
+                .is_some_and(|info| info.type_path().starts_with("core::option::Option"));
+            if unclearable && is_option {
+                value.apply(&DynamicEnum::new("None", DynamicVariant::Unit));
+                return false;
+            }
+            unclearable
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,250 @@
+use super::{EditorIgnore, TreeHiddenEntity};
+use bevy::{
+    asset::{AssetServer, Assets},
+    camera::{visibility::VisibilityRange, Camera, Camera3d},
+    ecs::{
+        component::Component,
+        entity::Entity,
+        hierarchy::{ChildOf, Children},
+        query::{Added, Changed, With, Without},
+        system::{Commands, Query, Res, ResMut},
+    },
+    math::{primitives::Rectangle, Affine2, Vec2},
+    mesh::{Mesh, Mesh3d},
+    pbr::{MeshMaterial3d, StandardMaterial},
+    prelude::{
+        AlphaMode, GlobalTransform, Name, ReflectComponent, ReflectDefault, ReflectDeserialize,
+        ReflectFromReflect, ReflectSerialize, RemovedComponents, Transform, Vec3,
+    },
+    reflect::Reflect,
+};
+use serde::{Deserialize, Serialize};
+
+// imposters.rs
+// Distant props swap to a camera facing billboard of themselves, picked from an atlas baked in the editor
+// Bevy's VisibilityRange does the swap per camera, these systems only keep the billboard turned and on the right view
+
+/// Billboard stand in for a prop beyond a distance. The atlas holds `views` renders around the prop
+/// side by side, the first one seen from the prop's back (+Z) and going counterclockwise seen from above
+#[derive(Reflect, Serialize, Deserialize, Debug, Clone, Component, PartialEq)]
+#[reflect(Component, Serialize, Deserialize, Default, FromReflect)]
+pub struct Imposter {
+    /// Atlas image relative to assets
+    pub atlas: String,
+    pub views: u32,
+    /// Camera distance beyond which the billboard is drawn instead of the prop
+    pub distance: f32,
+    /// World units over which prop and billboard crossfade, past the distance
+    pub fade: f32,
+    /// Billboard width and height before the prop's scale
+    pub size: Vec2,
+    /// Billboard center relative to the prop, in its local space
+    pub center: Vec3,
+}
+
+impl Default for Imposter {
+    fn default() -> Self {
+        Self {
+            atlas: String::new(),
+            views: 8,
+            distance: 60.0,
+            fade: 5.0,
+            size: Vec2::ONE,
+            center: Vec3::ZERO,
+        }
+    }
+}
+
+impl Imposter {
+    /// Where the editor bakes the atlas of an entity, relative to assets
+    pub fn atlas_path(uuid: &uuid::Uuid) -> String {
+        format!("textures/imposters/imposter_{}.png", uuid)
+    }
+
+    /// Atlas view facing a camera at a local space direction from the prop
+    pub fn view_for(&self, local_direction: Vec3) -> u32 {
+        let views = self.views.max(1);
+        let angle = local_direction
+            .x
+            .atan2(local_direction.z)
+            .rem_euclid(std::f32::consts::TAU);
+        let step = std::f32::consts::TAU / views as f32;
+        (angle / step).round() as u32 % views
+    }
+
+    /// Direction from the prop to the camera that rendered a view, in the prop's local space
+    pub fn view_direction(&self, view: u32) -> Vec3 {
+        let angle = view as f32 * std::f32::consts::TAU / self.views.max(1) as f32;
+        Vec3::new(angle.sin(), 0.0, angle.cos())
+    }
+
+    fn prop_range(&self) -> VisibilityRange {
+        VisibilityRange {
+            start_margin: 0.0..0.0,
+            end_margin: self.distance..self.distance + self.fade,
+            use_aabb: false,
+        }
+    }
+
+    fn billboard_range(&self) -> VisibilityRange {
+        VisibilityRange {
+            start_margin: self.distance..self.distance + self.fade,
+            end_margin: f32::MAX..f32::MAX,
+            use_aabb: false,
+        }
+    }
+}
+
+/// Billboard spawned under a prop with an Imposter. Never saved, it comes back from the component on load
+#[derive(Component, Debug, Clone, Copy)]
+pub struct ImposterBillboard {
+    pub view: u32,
+}
+
+/// Spawn the billboard of new or edited imposters and put the prop's meshes on the near range
+/// Meshes that show up later under a prop, i.e. from a glTF that finished loading, get the range too
+#[allow(clippy::too_many_arguments)]
+pub fn sync_imposter_billboards_system(
+    mut commands: Commands,
+    imposters: Query<(Entity, &Imposter, Option<&Children>), Changed<Imposter>>,
+    all_imposters: Query<&Imposter>,
+    added_meshes: Query<Entity, (Added<Mesh3d>, Without<ImposterBillboard>)>,
+    parents: Query<&ChildOf>,
+    children_query: Query<&Children>,
+    meshes_query: Query<(), (With<Mesh3d>, Without<ImposterBillboard>)>,
+    billboards: Query<(), With<ImposterBillboard>>,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    for mesh_entity in added_meshes.iter() {
+        let imposter = std::iter::once(mesh_entity)
+            .chain(parents.iter_ancestors(mesh_entity))
+            .find_map(|entity| all_imposters.get(entity).ok());
+        if let Some(imposter) = imposter {
+            commands.entity(mesh_entity).insert(imposter.prop_range());
+        }
+    }
+
+    for (entity, imposter, children) in imposters.iter() {
+        for child in children.into_iter().flatten() {
+            if billboards.contains(*child) {
+                commands.entity(*child).despawn();
+            }
+        }
+
+        let range = imposter.prop_range();
+        for mesh_entity in std::iter::once(entity).chain(children_query.iter_descendants(entity)) {
+            if meshes_query.contains(mesh_entity) {
+                commands.entity(mesh_entity).insert(range.clone());
+            }
+        }
+
+        if imposter.atlas.is_empty() {
+            continue;
+        }
+        let material = materials.add(StandardMaterial {
+            base_color_texture: Some(asset_server.load(imposter.atlas.clone())),
+            alpha_mode: AlphaMode::Mask(0.5),
+            unlit: true,
+            double_sided: true,
+            cull_mode: None,
+            uv_transform: view_uv_transform(0, imposter.views),
+            ..Default::default()
+        });
+        let billboard = commands
+            .spawn((
+                Mesh3d(meshes.add(Rectangle::from_size(imposter.size))),
+                MeshMaterial3d(material),
+                Transform::from_translation(imposter.center),
+                imposter.billboard_range(),
+                ImposterBillboard { view: 0 },
+                Name::new("Imposter Billboard"),
+                TreeHiddenEntity,
+                EditorIgnore::PICKING,
+            ))
+            .id();
+        commands.entity(entity).add_child(billboard);
+    }
+}
+
+/// Props that lose their Imposter go back to drawing at every distance
+pub fn remove_imposter_billboards_system(
+    mut commands: Commands,
+    mut removed: RemovedComponents<Imposter>,
+    children_query: Query<&Children>,
+    billboards: Query<(), With<ImposterBillboard>>,
+    ranged: Query<(), (With<VisibilityRange>, Without<ImposterBillboard>)>,
+) {
+    for entity in removed.read() {
+        for descendant in std::iter::once(entity).chain(children_query.iter_descendants(entity)) {
+            if billboards.contains(descendant) {
+                commands.entity(descendant).try_despawn();
+            } else if ranged.contains(descendant) {
+                commands.entity(descendant).try_remove::<VisibilityRange>();
+            }
+        }
+    }
+}
+
+/// Turn billboards around the world up axis toward the camera and show the atlas view closest to it
+/// Follows the active 3D camera with the highest order
+pub fn face_imposter_billboards_system(
+    cameras: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    props: Query<(&Imposter, &GlobalTransform)>,
+    mut billboards: Query<
+        (
+            &mut ImposterBillboard,
+            &mut Transform,
+            &ChildOf,
+            &MeshMaterial3d<StandardMaterial>,
+        ),
+        Without<Imposter>,
+    >,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let Some(camera_position) = cameras
+        .iter()
+        .filter(|(camera, _)| camera.is_active)
+        .max_by_key(|(camera, _)| camera.order)
+        .map(|(_, transform)| transform.translation())
+    else {
+        return;
+    };
+
+    for (mut billboard, mut transform, child_of, material) in billboards.iter_mut() {
+        let Ok((imposter, prop_transform)) = props.get(child_of.parent()) else {
+            continue;
+        };
+        let center = prop_transform.transform_point(imposter.center);
+        let mut to_camera = camera_position - center;
+        to_camera.y = 0.0;
+        if to_camera.length_squared() <= f32::EPSILON {
+            continue;
+        }
+
+        let (scale, rotation, _) = prop_transform.to_scale_rotation_translation();
+        let view = imposter.view_for(rotation.inverse() * to_camera);
+        if view != billboard.view {
+            billboard.view = view;
+            if let Some(material) = materials.get_mut(&material.0) {
+                material.uv_transform = view_uv_transform(view, imposter.views);
+            }
+        }
+
+        // The quad faces +Z, undo the prop's rotation and scale so it stands upright facing the camera
+        let facing = Transform::from_translation(center)
+            .looking_to(-to_camera, Vec3::Y)
+            .with_scale(scale);
+        *transform = GlobalTransform::from(facing).reparented_to(prop_transform);
+    }
+}
+
+fn view_uv_transform(view: u32, views: u32) -> Affine2 {
+    let width = 1.0 / views.max(1) as f32;
+    Affine2::from_scale_angle_translation(
+        Vec2::new(width, 1.0),
+        0.0,
+        Vec2::new(view as f32 * width, 0.0),
+    )
+}
@@ -0,0 +1,205 @@
+use super::IdentityData;
+use bevy::{
+    ecs::{
+        change_detection::DetectChanges,
+        component::Component,
+        entity::Entity,
+        message::{Message, MessageReader},
+        system::{Commands, Query},
+        world::Ref,
+    },
+    prelude::{
+        EntityEvent, GlobalTransform, ReflectComponent, ReflectDefault, ReflectDeserialize,
+        ReflectFromReflect, ReflectSerialize, Vec2, Vec3,
+    },
+    reflect::Reflect,
+};
+use bevy_granite_logging::{log, LogCategory, LogLevel, LogType};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+#[derive(Reflect, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[reflect(Serialize, Deserialize, Default)]
+pub enum JointKind {
+    /// Both bodies move as one
+    #[default]
+    Fixed,
+    /// Rotates around the axis
+    Hinge,
+    /// Moves along the axis
+    Slider,
+    /// Rotates freely around the anchor
+    Ball,
+}
+
+impl JointKind {
+    pub fn all() -> [JointKind; 4] {
+        [
+            JointKind::Fixed,
+            JointKind::Hinge,
+            JointKind::Slider,
+            JointKind::Ball,
+        ]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            JointKind::Fixed => "Fixed",
+            JointKind::Hinge => "Hinge",
+            JointKind::Slider => "Slider",
+            JointKind::Ball => "Ball",
+        }
+    }
+
+    pub fn uses_axis(&self) -> bool {
+        matches!(self, JointKind::Hinge | JointKind::Slider)
+    }
+}
+
+/// Serialized physics joint between this entity and another scene entity
+/// Granite doesn't simulate it, the physics integration of the app spawns the engine joint on GraniteJointReady
+#[derive(Reflect, Serialize, Deserialize, Debug, Clone, Component, PartialEq)]
+#[reflect(Component, Serialize, Deserialize, Default, FromReflect)]
+pub struct GraniteJoint {
+    pub kind: JointKind,
+    /// Uuid of the other body, so the joint survives save and load
+    pub connected: Uuid,
+    /// Joint origin relative to this entity
+    pub anchor: Vec3,
+    /// Joint origin relative to the connected entity
+    pub connected_anchor: Vec3,
+    /// Hinge or slider axis relative to this entity
+    pub axis: Vec3,
+    /// Min and max angle in radians for hinges, distance for sliders. Unused by fixed and ball joints
+    pub limits: Option<Vec2>,
+}
+
+impl Default for GraniteJoint {
+    fn default() -> Self {
+        Self {
+            kind: JointKind::Fixed,
+            connected: Uuid::nil(),
+            anchor: Vec3::ZERO,
+            connected_anchor: Vec3::ZERO,
+            axis: Vec3::X,
+            limits: None,
+        }
+    }
+}
+
+/// Runtime entities of a GraniteJoint, once the connected uuid is found
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JointBodies {
+    pub body: Entity,
+    pub connected: Entity,
+}
+
+/// Triggered when a joint's bodies are both spawned, and again whenever the joint is edited
+/// Spawn or replace the physics engine joint from here, i.e. `app.add_observer(|ready: On<GraniteJointReady>, ..| ..)`
+#[derive(EntityEvent, Debug, Clone)]
+pub struct GraniteJointReady {
+    pub entity: Entity,
+    pub connected: Entity,
+    pub joint: GraniteJoint,
+}
+
+/// Joint each entity to the one before it in the list, i.e. the editor selection order
+/// Anchors start halfway between the two bodies
+#[derive(Message)]
+pub struct RequestCreateJointEvent {
+    pub entities: Vec<Entity>,
+    pub kind: JointKind,
+}
+
+pub fn create_joint_system(
+    mut commands: Commands,
+    mut reader: MessageReader<RequestCreateJointEvent>,
+    query: Query<(&IdentityData, &GlobalTransform)>,
+) {
+    for RequestCreateJointEvent { entities, kind } in reader.read() {
+        let bodies: Vec<(Entity, Uuid, &GlobalTransform)> = entities
+            .iter()
+            .filter_map(|entity| {
+                query
+                    .get(*entity)
+                    .ok()
+                    .map(|(identity, transform)| (*entity, identity.uuid, transform))
+            })
+            .collect();
+        if bodies.len() < 2 {
+            log!(
+                LogType::Editor,
+                LogLevel::Warning,
+                LogCategory::Entity,
+                "Select at least two entities to joint them"
+            );
+            continue;
+        }
+
+        for pair in bodies.windows(2) {
+            let ((_, connected_uuid, connected_transform), (entity, _, transform)) =
+                (pair[0], pair[1]);
+            let middle = (transform.translation() + connected_transform.translation()) * 0.5;
+            commands.entity(entity).insert(GraniteJoint {
+                kind: *kind,
+                connected: connected_uuid,
+                anchor: transform.affine().inverse().transform_point3(middle),
+                connected_anchor: connected_transform
+                    .affine()
+                    .inverse()
+                    .transform_point3(middle),
+                ..Default::default()
+            });
+        }
+
+        log!(
+            LogType::Editor,
+            LogLevel::OK,
+            LogCategory::Entity,
+            "Created {} {} joints",
+            bodies.len() - 1,
+            kind.name()
+        );
+    }
+}
+
+/// Finds the connected body of new or edited joints and triggers GraniteJointReady
+/// Joints whose connected body isn't spawned yet are retried every frame
+pub fn resolve_joints_system(
+    mut commands: Commands,
+    joints: Query<(Entity, Ref<GraniteJoint>, Option<&JointBodies>)>,
+    identities: Query<(Entity, &IdentityData)>,
+) {
+    let pending: Vec<(Entity, &GraniteJoint)> = joints
+        .iter()
+        .filter(|(_, joint, bodies)| bodies.is_none() || joint.is_changed())
+        .map(|(entity, joint, _)| (entity, joint.into_inner()))
+        .collect();
+    if pending.is_empty() {
+        return;
+    }
+
+    let by_uuid: HashMap<Uuid, Entity> = identities
+        .iter()
+        .map(|(entity, identity)| (identity.uuid, entity))
+        .collect();
+    for (entity, joint) in pending {
+        let Some(connected) = by_uuid.get(&joint.connected).copied() else {
+            continue;
+        };
+        if connected == entity {
+            continue;
+        }
+
+        commands.entity(entity).insert(JointBodies {
+            body: entity,
+            connected,
+        });
+        commands.trigger(GraniteJointReady {
+            entity,
+            connected,
+            joint: joint.clone(),
+        });
+    }
+}
@@ -0,0 +1,135 @@
+use bevy::{
+    ecs::{component::Component, entity::Entity},
+    prelude::{
+        GlobalTransform, Quat, ReflectComponent, ReflectDefault, ReflectDeserialize,
+        ReflectFromReflect, ReflectSerialize, Vec3,
+    },
+    reflect::Reflect,
+    transform::components::Transform,
+};
+use serde::{Deserialize, Serialize};
+
+/// Named attachment point of a modular kit piece, relative to the entity
+/// Sockets face their local forward (-Z), two sockets snap together facing each other
+#[derive(Reflect, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[reflect(Serialize, Deserialize, Default, FromReflect)]
+pub struct KitSocket {
+    pub name: String,
+    /// Only sockets of the same kind connect, i.e. "wall" or "door". Empty connects to anything
+    pub kind: String,
+    pub position: Vec3,
+    pub rotation: Quat,
+}
+
+impl Default for KitSocket {
+    fn default() -> Self {
+        Self {
+            name: "socket".to_string(),
+            kind: String::new(),
+            position: Vec3::ZERO,
+            rotation: Quat::IDENTITY,
+        }
+    }
+}
+
+impl KitSocket {
+    pub fn new(
+        name: impl Into<String>,
+        kind: impl Into<String>,
+        position: Vec3,
+        rotation: Quat,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            kind: kind.into(),
+            position,
+            rotation,
+        }
+    }
+
+    pub fn accepts(&self, other: &KitSocket) -> bool {
+        self.kind.is_empty() || other.kind.is_empty() || self.kind == other.kind
+    }
+
+    pub fn local_transform(&self) -> Transform {
+        Transform::from_translation(self.position).with_rotation(self.rotation)
+    }
+}
+
+/// Sockets of a modular kit piece. While moving a piece in the editor with socket snapping on,
+/// it aligns to the closest compatible socket of another piece
+#[derive(Reflect, Serialize, Deserialize, Debug, Clone, Component, Default, PartialEq)]
+#[reflect(Component, Serialize, Deserialize, Default, FromReflect)]
+pub struct KitSockets {
+    pub sockets: Vec<KitSocket>,
+}
+
+impl KitSockets {
+    pub fn with(mut self, socket: KitSocket) -> Self {
+        self.sockets.push(socket);
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&KitSocket> {
+        self.sockets.iter().find(|socket| socket.name == name)
+    }
+}
+
+/// Where a piece goes to connect one of its sockets to a socket of another piece
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KitSnap {
+    pub target: Entity,
+    /// Index into the target's sockets
+    pub target_socket: usize,
+    /// Index into the piece's sockets
+    pub socket: usize,
+    /// World transform of the piece once connected
+    pub transform: Transform,
+    /// How far the sockets were apart before snapping
+    pub distance: f32,
+}
+
+/// Closest compatible socket pair within max_distance. `piece` is the world transform of the moving piece
+pub fn find_kit_snap<'a>(
+    piece: &Transform,
+    sockets: &KitSockets,
+    candidates: impl IntoIterator<Item = (Entity, &'a GlobalTransform, &'a KitSockets)>,
+    max_distance: f32,
+) -> Option<KitSnap> {
+    let mut best: Option<KitSnap> = None;
+    for (target, target_transform, target_sockets) in candidates {
+        let target_transform = target_transform.compute_transform();
+        for (target_index, target_socket) in target_sockets.sockets.iter().enumerate() {
+            let target_world = target_transform.mul_transform(target_socket.local_transform());
+            for (index, socket) in sockets.sockets.iter().enumerate() {
+                if !socket.accepts(target_socket) {
+                    continue;
+                }
+                let distance = piece
+                    .transform_point(socket.position)
+                    .distance(target_world.translation);
+                if distance > max_distance || best.is_some_and(|best| best.distance <= distance) {
+                    continue;
+                }
+
+                // Turn the piece socket around so both face each other
+                let facing = target_world.rotation * Quat::from_rotation_y(std::f32::consts::PI);
+                let rotation = (facing * socket.rotation.inverse()).normalize();
+                let translation =
+                    target_world.translation - rotation * (socket.position * piece.scale);
+                best = Some(KitSnap {
+                    target,
+                    target_socket: target_index,
+                    socket: index,
+                    transform: Transform {
+                        translation,
+                        rotation,
+                        scale: piece.scale,
+                    },
+                    distance,
+                });
+            }
+        }
+    }
+    best
+}
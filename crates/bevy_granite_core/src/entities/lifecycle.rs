@@ -1,26 +1,93 @@
-use super::{IdentityData, SpawnSource};
-use crate::events::{RequestDespawnBySource, RequestDespawnSerializableEntities};
+use super::{GraniteType, IdentityData, SpawnSource};
+use crate::{
+    events::{RequestDespawnBySource, RequestDespawnSerializableEntities},
+    world::PrefabMember,
+};
 use bevy_granite_logging::{
     config::{LogCategory, LogLevel, LogType},
     log,
 };
 
-use bevy::prelude::{Commands, Entity, MessageReader, Query, With};
+use bevy::prelude::{
+    ChildOf, Commands, Component, Entity, GlobalTransform, Has, MessageReader, Query, Reflect,
+    ReflectComponent, ReflectDefault,
+};
+use std::collections::HashSet;
+
+/// Entities with this survive despawn requests and world reloads, i.e. the player or game systems
+/// Only a DespawnFilter with include_persistent removes them
+#[derive(Reflect, Component, Debug, Clone, Copy, Default, PartialEq)]
+#[reflect(Component, Default)]
+pub struct PersistAcrossLoads;
+
+/// Narrows what a despawn request removes
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DespawnFilter {
+    /// Also despawn entities marked PersistAcrossLoads
+    pub include_persistent: bool,
+    /// Only despawn these classes, by type name or abv. i.e. "Point Light". Empty despawns every class
+    pub only_classes: Vec<String>,
+}
+
+impl DespawnFilter {
+    pub fn only_classes(classes: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            only_classes: classes.into_iter().map(Into::into).collect(),
+            ..Default::default()
+        }
+    }
+
+    pub fn including_persistent(mut self) -> Self {
+        self.include_persistent = true;
+        self
+    }
+
+    /// True when every entity of a source goes, not only some classes
+    pub fn is_complete(&self) -> bool {
+        self.only_classes.is_empty()
+    }
+
+    pub fn matches(&self, identity: &IdentityData, persistent: bool) -> bool {
+        if persistent && !self.include_persistent {
+            return false;
+        }
+        self.only_classes.is_empty()
+            || self.only_classes.iter().any(|class| {
+                *class == identity.class.type_name() || *class == identity.class.type_abv()
+            })
+    }
+}
+
+/// Serializable entities a despawn request can remove
+pub type DespawnCandidates<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static IdentityData,
+        Option<&'static SpawnSource>,
+        Has<PersistAcrossLoads>,
+        Option<&'static ChildOf>,
+        Option<&'static GlobalTransform>,
+        Has<PrefabMember>,
+    ),
+>;
 
 /// If entity has IdentityData, it is despawned
 pub fn despawn_entities_system(
     mut despawn_watcher: MessageReader<RequestDespawnSerializableEntities>,
     mut commands: Commands,
-    serializable_query: Query<Entity, With<IdentityData>>,
+    candidates: DespawnCandidates,
 ) {
-    for RequestDespawnSerializableEntities in despawn_watcher.read() {
-        despawn_recursive_serializable_entities(&mut commands, &serializable_query);
+    for RequestDespawnSerializableEntities(filter) in despawn_watcher.read() {
+        let despawned_count = despawn_filtered(&mut commands, &candidates, None, filter);
 
         log!(
             LogType::Editor,
             LogLevel::OK,
             LogCategory::System,
-            "Despawned serializable entities"
+            "Despawned {} serializable entities",
+            despawned_count
         );
     }
 }
@@ -29,17 +96,10 @@ pub fn despawn_entities_system(
 pub fn despawn_entities_by_source_system(
     mut despawn_watcher: MessageReader<RequestDespawnBySource>,
     mut commands: Commands,
-    serializable_query: Query<(Entity, &SpawnSource), With<IdentityData>>,
+    candidates: DespawnCandidates,
 ) {
-    for RequestDespawnBySource(source) in despawn_watcher.read() {
-        let mut despawned_count = 0;
-
-        for (entity, entity_source) in serializable_query.iter() {
-            if entity_source.0 == *source {
-                commands.entity(entity).try_despawn();
-                despawned_count += 1;
-            }
-        }
+    for RequestDespawnBySource(source, filter) in despawn_watcher.read() {
+        let despawned_count = despawn_filtered(&mut commands, &candidates, Some(source), filter);
 
         log!(
             LogType::Editor,
@@ -52,12 +112,48 @@ pub fn despawn_entities_by_source_system(
     }
 }
 
+/// Despawn every serializable entity the filter allows, optionally only from one source
+/// Kept entities whose parent goes are detached first, in place, so the recursive despawn doesn't take them along
+/// Prefab members always go with their instance
+pub fn despawn_filtered(
+    commands: &mut Commands,
+    candidates: &DespawnCandidates,
+    source: Option<&str>,
+    filter: &DespawnFilter,
+) -> usize {
+    let despawning: HashSet<Entity> = candidates
+        .iter()
+        .filter(|(_, identity, spawn_source, persistent, ..)| {
+            source.is_none_or(|source| spawn_source.is_some_and(|s| s.str_ref() == source))
+                && filter.matches(identity, *persistent)
+        })
+        .map(|(entity, ..)| entity)
+        .collect();
+
+    for (entity, _, _, _, child_of, global_transform, prefab_member) in candidates.iter() {
+        let Some(parent) = child_of.map(|child_of| child_of.parent()) else {
+            continue;
+        };
+        if prefab_member || despawning.contains(&entity) || !despawning.contains(&parent) {
+            continue;
+        }
+        let mut entity_commands = commands.entity(entity);
+        entity_commands.remove::<ChildOf>();
+        if let Some(global_transform) = global_transform {
+            entity_commands.insert(global_transform.compute_transform());
+        }
+    }
+
+    for entity in despawning.iter() {
+        commands.entity(*entity).try_despawn();
+    }
+    despawning.len()
+}
+
 // Despawn recursive
 pub fn despawn_recursive_serializable_entities(
     commands: &mut Commands,
-    serializable_query: &Query<Entity, With<IdentityData>>,
+    candidates: &DespawnCandidates,
 ) {
-    for entity in serializable_query.iter() {
-        commands.entity(entity).try_despawn();
-    }
+    despawn_filtered(commands, candidates, None, &DespawnFilter::default());
 }
@@ -13,12 +13,23 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 pub mod component_editor;
+pub mod conditions;
+pub mod custom_properties;
 pub mod deserialize;
 pub mod editable;
+pub mod entity_references;
 pub mod generate_tangents;
+pub mod imposters;
+pub mod joints;
+pub mod kit_sockets;
 pub mod lifecycle;
 pub mod plugin;
+pub mod quality;
+pub mod randomization;
+pub mod schemas;
 pub mod serialize;
+pub mod spawn_hooks;
+pub mod visible;
 pub use editable::*;
 
 /// Main camera
@@ -174,17 +185,57 @@ impl Default for PromptData {
 pub use component_editor::{
     is_bridge_component_check, BridgeTag, ComponentEditor, ExposedToEditor, ReflectedComponent,
 };
-pub use deserialize::{deserialize_entities, GraniteEditorSerdeEntity};
+pub use conditions::{SceneTarget, SpawnCondition, SpawnConditions};
+pub use custom_properties::{CustomProperties, PropertyValue};
+pub use deserialize::{
+    deserialize_entities, order_parents_first, GraniteEditorSerdeEntity, SceneOrder,
+};
+pub(crate) use deserialize::{spawn_scene_entities, SceneSpawnTarget};
 pub use editable::{
-    Camera3D, DirLight, Empty, GraniteTypes, PointLightData, RectBrush, VolumetricFog, OBJ,
+    ActionBinding, Anchor, Anchors, BindingInput, Camera3D, DirLight, Empty, ForceFalloff,
+    ForceKind, ForceVolume, ForceVolumes, GraniteTypes, InputBinding, InputBindings,
+    LightProbeData, LightProbeKind, LightProbeVolume, PointLightData, RectBrush,
+    RequestConnectWaypointsEvent, SplineMesh, SplinePoint, SplineProfile, VolumetricFog, Waypoint,
+    WaypointGraph, WaypointLink, WaypointLinkMode, WaypointNetworks, Weather, WeatherController,
+    WeatherPreset, OBJ,
 };
+pub use entity_references::{find_entity_references, retarget_entity_references, EntityReference};
 pub use generate_tangents::{generate_tangents_system, NeedsTangents};
+pub use imposters::{
+    face_imposter_billboards_system, remove_imposter_billboards_system,
+    sync_imposter_billboards_system, Imposter, ImposterBillboard,
+};
+pub use joints::{
+    create_joint_system, resolve_joints_system, GraniteJoint, GraniteJointReady, JointBodies,
+    JointKind, RequestCreateJointEvent,
+};
+pub use kit_sockets::{find_kit_snap, KitSnap, KitSocket, KitSockets};
 pub use lifecycle::{
-    despawn_entities_by_source_system, despawn_entities_system,
-    despawn_recursive_serializable_entities,
+    despawn_entities_by_source_system, despawn_entities_system, despawn_filtered,
+    despawn_recursive_serializable_entities, DespawnCandidates, DespawnFilter, PersistAcrossLoads,
 };
 pub use plugin::EntityPlugin;
-pub use serialize::{serialize_entities, EntitySaveReadyData, SceneData, SceneMetadata};
+pub use quality::{
+    apply_quality_rules_system, QualityLevels, QualityPreset, QualityRuleAppExt, QualityRules,
+    QualitySettings,
+};
+pub use randomization::{RandomizationRoll, SpawnRandomization};
+pub use schemas::{
+    gather_component_schemas, reload_component_schemas_system, ComponentField, ComponentRebind,
+    ComponentSchema, ComponentSchemaChange, ComponentSchemaReport, ComponentSchemas,
+    SchemaChangeKind,
+};
+pub use serialize::{
+    read_scene_entities, read_scene_metadata, round_quat, round_vec3, scene_to_string,
+    serialize_entities, write_scene_file, EntitySaveReadyData, SceneData, SceneMetadata,
+};
+pub use spawn_hooks::{
+    run_granite_spawn_hooks, GraniteSpawnHookAppExt, GraniteSpawnHooks, GraniteSpawned,
+};
+pub use visible::{
+    update_visible_serializable_entities_system, SerializableVisibilityChanged,
+    VisibleSerializableEntities,
+};
 
 // Im adding this so you cant select the editor camera
 // and to stop a crash because you can select a gizmo that then despawns its self
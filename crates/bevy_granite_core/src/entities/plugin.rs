@@ -1,9 +1,21 @@
 use super::{
-    despawn_entities_system, despawn_entities_by_source_system, generate_tangents_system, BridgeTag, ComponentEditor, HasRuntimeData,
-    IdentityData, InternalNote, MainCamera, SpawnSource, UICamera
+    apply_quality_rules_system, create_joint_system, despawn_entities_by_source_system,
+    despawn_entities_system, face_imposter_billboards_system, generate_tangents_system,
+    reload_component_schemas_system, remove_imposter_billboards_system, resolve_joints_system,
+    sync_imposter_billboards_system, update_visible_serializable_entities_system, BridgeTag,
+    ComponentEditor, ComponentSchemaReport, ComponentSchemas, CustomProperties, GraniteJoint,
+    GraniteSpawnHooks, HasRuntimeData, IdentityData, Imposter, InternalNote, JointKind, KitSocket,
+    KitSockets, MainCamera, PersistAcrossLoads, PropertyValue, QualityLevels, QualityPreset,
+    QualityRules, QualitySettings, RequestCreateJointEvent, SceneOrder, SceneTarget,
+    SerializableVisibilityChanged, SpawnCondition, SpawnConditions, SpawnRandomization,
+    SpawnSource, UICamera, VisibleSerializableEntities,
 };
 use crate::entities::{editable::ClassTypePlugin, PromptImportSettings};
-use bevy::app::{App, Plugin, Update};
+use bevy::app::{App, Plugin, PostUpdate, Update};
+use bevy::camera::visibility::VisibilitySystems;
+use bevy::ecs::schedule::{common_conditions::resource_exists, IntoScheduleConfigs};
+use bevy::transform::TransformSystems;
+use bevy_inspector_egui::inspector_egui_impls::InspectorEguiImpl;
 
 pub struct EntityPlugin;
 impl Plugin for EntityPlugin {
@@ -23,16 +35,73 @@ impl Plugin for EntityPlugin {
             .register_type_data::<MainCamera, BridgeTag>()
             .register_type::<InternalNote>()
             .register_type_data::<InternalNote, BridgeTag>()
+            .register_type::<KitSocket>()
+            .register_type::<KitSockets>()
+            .register_type_data::<KitSockets, BridgeTag>()
+            .register_type::<Imposter>()
+            .register_type_data::<Imposter, BridgeTag>()
+            .register_type::<JointKind>()
+            .register_type::<GraniteJoint>()
+            .register_type_data::<GraniteJoint, BridgeTag>()
+            .register_type::<PropertyValue>()
+            .register_type::<CustomProperties>()
+            .register_type_data::<CustomProperties, BridgeTag>()
+            .register_type_data::<CustomProperties, InspectorEguiImpl>()
+            .register_type::<SpawnCondition>()
+            .register_type::<SpawnConditions>()
+            .register_type_data::<SpawnConditions, BridgeTag>()
+            .register_type::<SpawnRandomization>()
+            .register_type_data::<SpawnRandomization, BridgeTag>()
+            //
+            // Events
+            //
+            .add_message::<RequestCreateJointEvent>()
+            .add_message::<SerializableVisibilityChanged>()
             .register_type::<IdentityData>()
             .register_type::<HasRuntimeData>()
+            .register_type::<PersistAcrossLoads>()
+            .register_type::<SceneOrder>()
+            .register_type::<QualitySettings>()
+            .register_type::<QualityPreset>()
+            .register_type::<QualityLevels>()
             //
             // Resources
             //
             .insert_resource(ComponentEditor::default())
             .insert_resource(PromptImportSettings::default())
+            .init_resource::<GraniteSpawnHooks>()
+            .init_resource::<QualitySettings>()
+            .init_resource::<SceneTarget>()
+            .init_resource::<QualityRules>()
+            .init_resource::<ComponentSchemas>()
+            .init_resource::<ComponentSchemaReport>()
             //
             // Schedule system
             //
-            .add_systems(Update, (despawn_entities_system, despawn_entities_by_source_system, generate_tangents_system));
+            .add_systems(
+                Update,
+                (
+                    despawn_entities_system,
+                    despawn_entities_by_source_system,
+                    generate_tangents_system,
+                    create_joint_system,
+                    resolve_joints_system,
+                    sync_imposter_billboards_system,
+                    remove_imposter_billboards_system,
+                ),
+            )
+            .add_systems(Update, reload_component_schemas_system)
+            .add_systems(
+                PostUpdate,
+                // Opt-in, only runs once VisibleSerializableEntities is inserted
+                update_visible_serializable_entities_system
+                    .after(VisibilitySystems::CheckVisibility)
+                    .run_if(resource_exists::<VisibleSerializableEntities>),
+            )
+            .add_systems(PostUpdate, apply_quality_rules_system)
+            .add_systems(
+                PostUpdate,
+                face_imposter_billboards_system.before(TransformSystems::Propagate),
+            );
     }
 }
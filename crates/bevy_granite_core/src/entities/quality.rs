@@ -0,0 +1,185 @@
+use super::{GraniteType, IdentityData};
+use bevy::{
+    ecs::{
+        change_detection::DetectChanges,
+        query::{Changed, QueryState, With},
+    },
+    prelude::{App, Entity, Reflect, Resource, World},
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+// quality.rs
+// Runtime quality presets. Authored class data is never touched, rules scale the Bevy components built from it
+// so one scene adapts to weak hardware and still saves with its authored values
+// Rules are per class and run when an entity's class data changes or the preset does
+
+/// Multipliers applied on top of authored values, 1.0 keeps them as authored
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct QualityLevels {
+    /// Shadow map sizes, rounded to a power of two for directional lights
+    pub shadow_map: f32,
+    /// Volumetric fog ray march steps
+    pub fog_steps: f32,
+    /// Atmosphere lookup texture sizes
+    pub atmosphere_lut: f32,
+    /// Bloom intensity, 0 turns bloom off
+    pub bloom: f32,
+}
+
+impl Default for QualityLevels {
+    fn default() -> Self {
+        Self {
+            shadow_map: 1.0,
+            fog_steps: 1.0,
+            atmosphere_lut: 1.0,
+            bloom: 1.0,
+        }
+    }
+}
+
+impl QualityLevels {
+    /// Scales a count or size, never below 1
+    pub fn scale(value: u32, scale: f32) -> u32 {
+        ((value as f32 * scale.max(0.0)).round() as u32).max(1)
+    }
+
+    pub fn shadow_map_size(&self, size: usize) -> usize {
+        Self::scale(size as u32, self.shadow_map) as usize
+    }
+}
+
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum QualityPreset {
+    Low,
+    Medium,
+    /// Authored values as they are
+    #[default]
+    High,
+    Custom(QualityLevels),
+}
+
+impl QualityPreset {
+    pub fn levels(&self) -> QualityLevels {
+        match self {
+            Self::Low => QualityLevels {
+                shadow_map: 0.25,
+                fog_steps: 0.25,
+                atmosphere_lut: 0.5,
+                bloom: 0.0,
+            },
+            Self::Medium => QualityLevels {
+                shadow_map: 0.5,
+                fog_steps: 0.5,
+                atmosphere_lut: 0.75,
+                bloom: 1.0,
+            },
+            Self::High => QualityLevels::default(),
+            Self::Custom(levels) => *levels,
+        }
+    }
+}
+
+/// Quality the scene is shown at. Change it at any time, i.e. from a game's settings menu
+#[derive(Resource, Reflect, Debug, Clone, Copy, PartialEq, Default)]
+pub struct QualitySettings {
+    pub preset: QualityPreset,
+}
+
+impl QualitySettings {
+    pub fn levels(&self) -> QualityLevels {
+        self.preset.levels()
+    }
+}
+
+type QualityRule = Arc<dyn Fn(&mut World, Entity, &QualityLevels) + Send + Sync>;
+
+/// Per class rules scaling an entity's components by the current QualityLevels
+/// Register them with `add_quality_rule` on App. Rules rebuild from the class data, so running one twice is harmless
+#[derive(Resource, Default, Clone)]
+pub struct QualityRules {
+    rules: Vec<(String, QualityRule)>,
+}
+
+impl QualityRules {
+    /// Run for entities of a class, by type name or abv. i.e. "Camera 3D"
+    pub fn add(
+        &mut self,
+        class: impl Into<String>,
+        rule: impl Fn(&mut World, Entity, &QualityLevels) + Send + Sync + 'static,
+    ) {
+        self.rules.push((class.into(), Arc::new(rule)));
+    }
+
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+}
+
+/// Scales a class's authored values to the quality preset without touching its saved data
+pub trait QualityRuleAppExt {
+    /// Call `rule` for every entity of a class when it spawns, is edited, or the quality changes
+    fn add_quality_rule(
+        &mut self,
+        class: impl Into<String>,
+        rule: impl Fn(&mut World, Entity, &QualityLevels) + Send + Sync + 'static,
+    ) -> &mut Self;
+}
+
+impl QualityRuleAppExt for App {
+    fn add_quality_rule(
+        &mut self,
+        class: impl Into<String>,
+        rule: impl Fn(&mut World, Entity, &QualityLevels) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.init_resource::<QualityRules>();
+        self.world_mut()
+            .resource_mut::<QualityRules>()
+            .add(class, rule);
+        self
+    }
+}
+
+/// Runs the matching rules on entities whose class data changed, or on all of them when the quality did
+pub fn apply_quality_rules_system(
+    world: &mut World,
+    changed: &mut QueryState<Entity, Changed<IdentityData>>,
+    all: &mut QueryState<Entity, With<IdentityData>>,
+) {
+    let Some(rules) = world.get_resource::<QualityRules>() else {
+        return;
+    };
+    if rules.is_empty() {
+        return;
+    }
+    let rules = rules.rules.clone();
+    let quality = world.resource_ref::<QualitySettings>();
+    let (quality_changed, levels) = (quality.is_changed(), quality.levels());
+
+    let entities: Vec<Entity> = if quality_changed {
+        all.iter(world).collect()
+    } else {
+        changed.iter(world).collect()
+    };
+
+    for entity in entities {
+        let Some(identity) = world.get::<IdentityData>(entity) else {
+            continue;
+        };
+        let (name, abv) = (identity.class.type_name(), identity.class.type_abv());
+        for (class, rule) in rules.iter() {
+            if *class != name && *class != abv {
+                continue;
+            }
+            // An earlier rule may have despawned it
+            if world.get_entity(entity).is_err() {
+                break;
+            }
+            rule(world, entity, &levels);
+        }
+    }
+}
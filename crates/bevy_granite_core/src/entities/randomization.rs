@@ -0,0 +1,182 @@
+use super::EntitySaveReadyData;
+use bevy::{
+    ecs::component::Component,
+    math::Quat,
+    prelude::{
+        ReflectComponent, ReflectDefault, ReflectDeserialize, ReflectFromReflect, ReflectSerialize,
+    },
+    reflect::Reflect,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use uuid::Uuid;
+
+// randomization.rs
+// Lightweight procedural variation for hand authored levels. SpawnRandomization is saved with the entity
+// and rolled once when its scene loads, next to SpawnConditions: whether the entity spawns, which one of its
+// children is kept and a rotation in steps around Y. Rolls come from the SceneTarget seed and the entity uuid,
+// so a seed always gives the same level. The editor loads everything, its preview shows the rolls without applying them
+
+/// Randomized spawning of an entity and its children
+#[derive(Component, Reflect, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[reflect(Component, Serialize, Deserialize, Default, FromReflect)]
+pub struct SpawnRandomization {
+    /// Chance to spawn from 0 to 1, the children go with the entity
+    pub chance: f32,
+    /// Keep only one of the direct children, i.e. variants of a prop
+    pub pick_one_child: bool,
+    /// Weight of each child by name when picking one, children not listed weigh 1
+    pub child_weights: BTreeMap<String, f32>,
+    /// Rotation around Y in random steps of this many degrees, i.e. 90. 0 for none
+    pub rotation_step: f32,
+}
+
+impl Default for SpawnRandomization {
+    fn default() -> Self {
+        Self {
+            chance: 1.0,
+            pick_one_child: false,
+            child_weights: BTreeMap::new(),
+            rotation_step: 0.0,
+        }
+    }
+}
+
+/// What a SpawnRandomization rolled for one entity
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RandomizationRoll {
+    pub spawn: bool,
+    /// Rotation to apply before the entity's own
+    pub rotation: Quat,
+    /// The child kept when picking one, none otherwise or without children
+    pub kept_child: Option<Uuid>,
+}
+
+impl SpawnRandomization {
+    /// Roll for an entity, with its direct children as (uuid, name) in file order
+    pub fn roll(&self, seed: u64, uuid: Uuid, children: &[(Uuid, &str)]) -> RandomizationRoll {
+        let spawn = self.chance >= 1.0 || random(seed, uuid, 0) < self.chance;
+
+        let rotation = if self.rotation_step > 0.0 {
+            let steps = (360.0 / self.rotation_step).floor().max(1.0);
+            let step = (random(seed, uuid, 1) * steps).floor();
+            Quat::from_rotation_y((step * self.rotation_step).to_radians())
+        } else {
+            Quat::IDENTITY
+        };
+
+        let kept_child = if self.pick_one_child {
+            let weight = |name: &str| {
+                self.child_weights
+                    .get(name)
+                    .copied()
+                    .unwrap_or(1.0)
+                    .max(0.0)
+            };
+            let total: f32 = children.iter().map(|(_, name)| weight(name)).sum();
+            let mut left = random(seed, uuid, 2) * total;
+            children
+                .iter()
+                .find(|(_, name)| {
+                    left -= weight(name);
+                    left < 0.0
+                })
+                .or(children.last())
+                .map(|(child, _)| *child)
+        } else {
+            None
+        };
+
+        RandomizationRoll {
+            spawn,
+            rotation,
+            kept_child,
+        }
+    }
+}
+
+/// Same number from 0 to 1 for the same seed, entity and roll
+fn random(seed: u64, uuid: Uuid, roll: u64) -> f32 {
+    let (high, low) = uuid.as_u64_pair();
+    // splitmix64
+    let mut x = seed ^ high.rotate_left(17) ^ low ^ roll.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    x = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^= x >> 31;
+    (x >> 40) as f32 / (1u64 << 24) as f32
+}
+
+/// Rolls the SpawnRandomization of scene entities, rotating the ones that spawn
+/// Entities that didn't spawn and children that weren't picked are marked in `keep` and `skipped`
+pub(crate) fn randomize_entities(
+    seed: u64,
+    entities: &mut [EntitySaveReadyData],
+    keep: &mut [bool],
+    skipped: &mut HashSet<Uuid>,
+) {
+    let mut children: HashMap<Uuid, Vec<(Uuid, String)>> = HashMap::new();
+    for entity in entities.iter() {
+        if let Some(parent) = entity.parent {
+            children
+                .entry(parent)
+                .or_default()
+                .push((entity.identity.uuid, entity.identity.name.clone()));
+        }
+    }
+
+    let mut dropped: HashSet<Uuid> = HashSet::new();
+    for (index, entity) in entities.iter_mut().enumerate() {
+        if !keep[index] {
+            continue;
+        }
+        let Some(randomization) = read_randomization(entity) else {
+            continue;
+        };
+        let uuid = entity.identity.uuid;
+        let entity_children: Vec<(Uuid, &str)> = children
+            .get(&uuid)
+            .map(|children| {
+                children
+                    .iter()
+                    .map(|(child, name)| (*child, name.as_str()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let roll = randomization.roll(seed, uuid, &entity_children);
+        if !roll.spawn {
+            dropped.insert(uuid);
+            continue;
+        }
+        entity.transform.rotation = roll.rotation * entity.transform.rotation;
+        if let Some(kept) = roll.kept_child {
+            dropped.extend(
+                entity_children
+                    .iter()
+                    .map(|(child, _)| *child)
+                    .filter(|child| *child != kept),
+            );
+        }
+    }
+
+    for (index, entity) in entities.iter().enumerate() {
+        if dropped.contains(&entity.identity.uuid) {
+            keep[index] = false;
+        }
+    }
+    skipped.extend(dropped);
+}
+
+/// SpawnRandomization of saved entity data. Saved as `{"type::path":(..)}`, see ComponentEditor
+fn read_randomization(entity: &EntitySaveReadyData) -> Option<SpawnRandomization> {
+    let serialized = entity
+        .components
+        .as_ref()?
+        .iter()
+        .find(|(type_path, _)| type_path.ends_with("::SpawnRandomization"))
+        .map(|(_, serialized)| serialized)?;
+    ron::from_str::<HashMap<String, SpawnRandomization>>(serialized)
+        .ok()?
+        .into_values()
+        .next()
+}
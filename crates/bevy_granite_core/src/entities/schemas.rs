@@ -0,0 +1,406 @@
+use super::is_bridge_component_check;
+use crate::{events::RequestReloadComponentSchemasEvent, setup::RegisteredTypeNames};
+use bevy::{
+    ecs::system::SystemState,
+    platform::collections::HashMap,
+    prelude::{
+        AppTypeRegistry, Entity, EntityRef, MessageReader, ReflectComponent, ReflectDefault, Res,
+        ResMut, Resource, World,
+    },
+    reflect::{PartialReflect, ReflectMut, ReflectRef, TypeInfo, TypeRegistration, TypeRegistry},
+};
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+use std::any::TypeId;
+
+// schemas.rs
+// Keeps the field layout of every bridge component so a dynamic-library hot reload can be picked up without restarting
+// After a reload the new build registers its types again under new TypeIds. Entities still hold the old ones,
+// which are rebuilt as the new type field by field. Fields that no longer fit are reported, not guessed
+
+/// Name and type of one field. Tuple struct fields are named by index
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComponentField {
+    pub name: String,
+    pub type_path: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComponentSchema {
+    pub type_id: TypeId,
+    pub fields: Vec<ComponentField>,
+}
+
+impl ComponentSchema {
+    pub fn from_registration(registration: &TypeRegistration) -> Self {
+        let fields = match registration.type_info() {
+            TypeInfo::Struct(info) => info
+                .iter()
+                .map(|field| ComponentField {
+                    name: field.name().to_string(),
+                    type_path: field.type_path().to_string(),
+                })
+                .collect(),
+            TypeInfo::TupleStruct(info) => info
+                .iter()
+                .map(|field| ComponentField {
+                    name: field.index().to_string(),
+                    type_path: field.type_path().to_string(),
+                })
+                .collect(),
+            // Enums and opaque types are rebound whole
+            _ => Vec::new(),
+        };
+        Self {
+            type_id: registration.type_id(),
+            fields,
+        }
+    }
+
+    fn field(&self, name: &str) -> Option<&ComponentField> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+}
+
+/// Bridge component schemas by type path, as of startup or the last reload
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct ComponentSchemas {
+    pub by_type: HashMap<String, ComponentSchema>,
+}
+
+impl ComponentSchemas {
+    pub fn gather(registry: &TypeRegistry) -> Self {
+        Self {
+            by_type: registry
+                .iter()
+                .filter(|registration| {
+                    registration.data::<ReflectComponent>().is_some()
+                        && is_bridge_component_check(registration)
+                })
+                .map(|registration| {
+                    (
+                        registration.type_info().type_path().to_string(),
+                        ComponentSchema::from_registration(registration),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// What changed from `self` to `new`, sorted by type path. Types whose fields are the same are left out
+    pub fn diff(&self, new: &ComponentSchemas) -> Vec<ComponentSchemaChange> {
+        let mut changes: Vec<ComponentSchemaChange> = new
+            .by_type
+            .iter()
+            .filter_map(|(type_path, schema)| {
+                let Some(old) = self.by_type.get(type_path) else {
+                    return Some(ComponentSchemaChange {
+                        type_path: type_path.clone(),
+                        kind: SchemaChangeKind::Added,
+                        ..Default::default()
+                    });
+                };
+                let mut change = ComponentSchemaChange {
+                    type_path: type_path.clone(),
+                    kind: SchemaChangeKind::Changed,
+                    ..Default::default()
+                };
+                for field in schema.fields.iter() {
+                    match old.field(&field.name) {
+                        None => change.added_fields.push(field.name.clone()),
+                        Some(old_field) if old_field.type_path != field.type_path => {
+                            change.retyped_fields.push(field.name.clone())
+                        }
+                        Some(_) => {}
+                    }
+                }
+                change.removed_fields = old
+                    .fields
+                    .iter()
+                    .filter(|field| schema.field(&field.name).is_none())
+                    .map(|field| field.name.clone())
+                    .collect();
+                let reordered = old.fields != schema.fields;
+                (reordered || !change.is_compatible()).then_some(change)
+            })
+            .collect();
+        changes.extend(
+            self.by_type
+                .keys()
+                .filter(|type_path| !new.by_type.contains_key(*type_path))
+                .map(|type_path| ComponentSchemaChange {
+                    type_path: type_path.clone(),
+                    kind: SchemaChangeKind::Removed,
+                    ..Default::default()
+                }),
+        );
+        changes.sort_by(|a, b| a.type_path.cmp(&b.type_path));
+        changes
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SchemaChangeKind {
+    Added,
+    Removed,
+    #[default]
+    Changed,
+}
+
+/// How one bridge component's fields differ after a reload
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ComponentSchemaChange {
+    pub type_path: String,
+    pub kind: SchemaChangeKind,
+    pub added_fields: Vec<String>,
+    pub removed_fields: Vec<String>,
+    /// Fields kept by name whose type changed
+    pub retyped_fields: Vec<String>,
+}
+
+impl ComponentSchemaChange {
+    /// Existing data still fits, new fields just take their default
+    pub fn is_compatible(&self) -> bool {
+        self.removed_fields.is_empty() && self.retyped_fields.is_empty()
+    }
+}
+
+/// One component rebuilt as its reloaded type
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComponentRebind {
+    pub entity: Entity,
+    pub type_path: String,
+    /// Fields whose old value was dropped because the field is gone, its type changed or the value didn't apply
+    pub incompatible: Vec<String>,
+    /// Fields that took their default because the old type didn't have them
+    pub defaulted: Vec<String>,
+    /// The new type has no Default and the old value couldn't be converted, so the old component was left on the entity
+    pub failed: bool,
+}
+
+/// Result of the last component schema reload
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct ComponentSchemaReport {
+    pub changes: Vec<ComponentSchemaChange>,
+    pub rebinds: Vec<ComponentRebind>,
+    pub reloaded: bool,
+}
+
+impl ComponentSchemaReport {
+    /// Rebinds that lost data or failed
+    pub fn flagged(&self) -> impl Iterator<Item = &ComponentRebind> {
+        self.rebinds
+            .iter()
+            .filter(|rebind| rebind.failed || !rebind.incompatible.is_empty())
+    }
+}
+
+pub fn gather_component_schemas(
+    type_registry: Res<AppTypeRegistry>,
+    mut schemas: ResMut<ComponentSchemas>,
+) {
+    *schemas = ComponentSchemas::gather(&type_registry.read());
+}
+
+/// Picks up component types registered again by a hot reload, then rebinds the entities still holding the old types
+pub fn reload_component_schemas_system(
+    world: &mut World,
+    state: &mut SystemState<MessageReader<RequestReloadComponentSchemasEvent>>,
+) {
+    if state.get_mut(world).read().count() == 0 {
+        return;
+    }
+
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = type_registry.read();
+    let schemas = ComponentSchemas::gather(&registry);
+    let changes = world.resource::<ComponentSchemas>().diff(&schemas);
+
+    let mut stale: Vec<(Entity, TypeId, TypeId)> = Vec::new();
+    let mut query = world.query::<EntityRef>();
+    for entity_ref in query.iter(world) {
+        for component_id in entity_ref.archetype().components() {
+            let Some(type_id) = world
+                .components()
+                .get_info(*component_id)
+                .and_then(|info| info.type_id())
+            else {
+                continue;
+            };
+            let Some(registration) = registry.get(type_id) else {
+                continue;
+            };
+            if !is_bridge_component_check(registration) {
+                continue;
+            }
+            if let Some(schema) = schemas.by_type.get(registration.type_info().type_path()) {
+                if schema.type_id != type_id {
+                    stale.push((entity_ref.id(), type_id, schema.type_id));
+                }
+            }
+        }
+    }
+
+    let rebinds: Vec<ComponentRebind> = stale
+        .into_iter()
+        .filter_map(|(entity, old_type, new_type)| {
+            rebind_component(world, &registry, entity, old_type, new_type)
+        })
+        .collect();
+
+    for change in changes.iter().filter(|change| !change.is_compatible()) {
+        log!(
+            LogType::Game,
+            LogLevel::Warning,
+            LogCategory::System,
+            "Component {} changed incompatibly. Removed: {:?}, retyped: {:?}",
+            change.type_path,
+            change.removed_fields,
+            change.retyped_fields
+        );
+    }
+    for rebind in rebinds.iter() {
+        if rebind.failed {
+            log!(
+                LogType::Game,
+                LogLevel::Error,
+                LogCategory::Entity,
+                "Could not rebind {} on {:?}, it has no Default and the old value doesn't convert",
+                rebind.type_path,
+                rebind.entity
+            );
+        } else if !rebind.incompatible.is_empty() {
+            log!(
+                LogType::Game,
+                LogLevel::Warning,
+                LogCategory::Entity,
+                "Rebound {} on {:?}, dropped the values of {:?}",
+                rebind.type_path,
+                rebind.entity,
+                rebind.incompatible
+            );
+        }
+    }
+    log!(
+        LogType::Game,
+        LogLevel::OK,
+        LogCategory::System,
+        "Reloaded component schemas: {} changed, {} components rebound",
+        changes.len(),
+        rebinds.len()
+    );
+
+    let names = crate::setup::registered_type_names(&registry);
+    drop(registry);
+    world.resource_mut::<RegisteredTypeNames>().names = names;
+    *world.resource_mut::<ComponentSchemas>() = schemas;
+    *world.resource_mut::<ComponentSchemaReport>() = ComponentSchemaReport {
+        changes,
+        rebinds,
+        reloaded: true,
+    };
+}
+
+/// Replace the entity's old-type component with the new type, carrying over each field that still fits
+fn rebind_component(
+    world: &mut World,
+    registry: &TypeRegistry,
+    entity: Entity,
+    old_type: TypeId,
+    new_type: TypeId,
+) -> Option<ComponentRebind> {
+    let old_registration = registry.get(old_type)?;
+    let new_registration = registry.get(new_type)?;
+    let old_component = old_registration.data::<ReflectComponent>()?;
+    let new_component = new_registration.data::<ReflectComponent>()?;
+    let old_value = old_component
+        .reflect(world.entity(entity))?
+        .reflect_clone()
+        .ok()?;
+
+    let mut rebind = ComponentRebind {
+        entity,
+        type_path: new_registration.type_info().type_path().to_string(),
+        incompatible: Vec::new(),
+        defaulted: Vec::new(),
+        failed: false,
+    };
+
+    let Some(mut new_value) = new_registration
+        .data::<ReflectDefault>()
+        .map(|default| default.default())
+    else {
+        // Without a Default only a value that still converts as a whole can be carried over
+        let converted = new_registration
+            .data::<bevy::reflect::ReflectFromReflect>()
+            .and_then(|from_reflect| from_reflect.from_reflect(old_value.as_partial_reflect()));
+        match converted {
+            Some(value) => {
+                let mut entity_mut = world.entity_mut(entity);
+                old_component.remove(&mut entity_mut);
+                new_component.insert(&mut entity_mut, value.as_partial_reflect(), registry);
+            }
+            None => rebind.failed = true,
+        }
+        return Some(rebind);
+    };
+
+    match (old_value.reflect_ref(), new_value.reflect_mut()) {
+        (ReflectRef::Struct(old), ReflectMut::Struct(new)) => {
+            for index in 0..new.field_len() {
+                let Some(name) = new.name_at(index).map(str::to_string) else {
+                    continue;
+                };
+                let Some(field) = new.field_at_mut(index) else {
+                    continue;
+                };
+                match old.field(&name) {
+                    Some(old_field) => {
+                        if !carry_field(old_field, field) {
+                            rebind.incompatible.push(name);
+                        }
+                    }
+                    None => rebind.defaulted.push(name),
+                }
+            }
+            for index in 0..old.field_len() {
+                if let Some(name) = old.name_at(index) {
+                    if new.field(name).is_none() {
+                        rebind.incompatible.push(name.to_string());
+                    }
+                }
+            }
+        }
+        (ReflectRef::TupleStruct(old), ReflectMut::TupleStruct(new)) => {
+            for index in 0..new.field_len().max(old.field_len()) {
+                match (old.field(index), new.field_mut(index)) {
+                    (Some(old_field), Some(field)) => {
+                        if !carry_field(old_field, field) {
+                            rebind.incompatible.push(index.to_string());
+                        }
+                    }
+                    (None, Some(_)) => rebind.defaulted.push(index.to_string()),
+                    (Some(_), None) => rebind.incompatible.push(index.to_string()),
+                    (None, None) => {}
+                }
+            }
+        }
+        (_, _) => {
+            if new_value.try_apply(old_value.as_partial_reflect()).is_err() {
+                rebind.incompatible.push(rebind.type_path.clone());
+            }
+        }
+    }
+
+    let mut entity_mut = world.entity_mut(entity);
+    old_component.remove(&mut entity_mut);
+    new_component.insert(&mut entity_mut, new_value.as_partial_reflect(), registry);
+    Some(rebind)
+}
+
+/// Apply the old field value onto the new one if it is still the same type. Returns false if it was dropped
+fn carry_field(old: &dyn PartialReflect, new: &mut dyn PartialReflect) -> bool {
+    old.reflect_type_path() == new.reflect_type_path() && new.try_apply(old).is_ok()
+}
@@ -1,5 +1,10 @@
-use super::{IdentityData, TransformData, SaveSettings};
-use crate::{ shared::version::Version, world::WorldState};
+use super::{IdentityData, SaveSettings, TransformData};
+use crate::{
+    shared::{
+        read_binary_scene, read_scene_file, version::Version, write_scene_data, SceneCompression,
+    },
+    world::{region::merge_unloaded_entities, SceneRegionIndex, WorldState},
+};
 use bevy::prelude::{Quat, Vec3};
 use bevy_granite_logging::{
     config::{LogCategory, LogLevel, LogType},
@@ -8,18 +13,19 @@ use bevy_granite_logging::{
 
 use ron::ser::{to_string_pretty, PrettyConfig};
 use serde::{Deserialize, Serialize};
-use std::{
-    collections::HashMap,
-    fs::{self, File},
-    io::{Write, Read},
-    path::Path,
-};
+use std::{collections::HashMap, path::Path};
 use uuid::Uuid;
 
-#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SceneMetadata {
     pub format_version: Version,
     pub entity_count: usize,
+    /// Companion scenes loaded along with this one in batch loads, i.e. "scenes/shared_props.scene"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub requires: Vec<String>,
+    /// Entity positions by grid cell, written for big scenes so regions load without parsing the rest
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region_index: Option<SceneRegionIndex>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -28,7 +34,7 @@ pub struct SceneData {
     pub entities: Vec<EntitySaveReadyData>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct EntitySaveReadyData {
     pub identity: IdentityData,
     pub transform: TransformData,
@@ -41,21 +47,27 @@ pub struct EntitySaveReadyData {
 }
 
 // want set order or something? only actually save to disk if things changed. Same with editor toml
-pub fn serialize_entities(world_state: WorldState, path: Option<String>) {
+pub fn serialize_entities(
+    world_state: WorldState,
+    path: Option<String>,
+    compression: SceneCompression,
+) {
     let entities_data = world_state.entity_data;
     let runtime_data_provider = world_state.component_data.unwrap_or_default();
+    let unchanged = world_state.unchanged;
+    let mut reused = 0;
 
     // Read original file data for PreserveDiskFull entities
     let original_entities = if let Some(ref path_str) = path {
-        read_existing_file_data(path_str)
+        read_scene_entities(path_str)
     } else {
         Vec::new()
     };
-    
+
     // Create map of UUID -> original data for quick lookup
     let original_by_uuid: HashMap<Uuid, EntitySaveReadyData> = original_entities
-        .into_iter()
-        .map(|entity| (entity.identity.uuid, entity))
+        .iter()
+        .map(|entity| (entity.identity.uuid, entity.clone()))
         .collect();
 
     // Map entity indices to their actual UUIDs from IdentityData
@@ -71,7 +83,13 @@ pub fn serialize_entities(world_state: WorldState, path: Option<String>) {
             .iter()
             .map(|(entity, identity, transform, parent, save_as)| {
                 let parent_uuid = parent.and_then(|p| entity_uuid_map.get(&p.index()).copied());
-                
+
+                // Unchanged since the file was read or written, its record is kept as is
+                if let Some(record) = unchanged.get(entity) {
+                    reused += 1;
+                    return record.clone();
+                }
+
                 match save_as {
                     SaveSettings::Runtime => {
                         // Use current world state
@@ -153,18 +171,34 @@ pub fn serialize_entities(world_state: WorldState, path: Option<String>) {
         None => Vec::new(),
     };
 
-    let pretty_config = PrettyConfig::new()
-        .depth_limit(15)
-        .separate_tuple_members(false)
-        .enumerate_arrays(false)
-        .compact_arrays(true)
-        .indentor("\t".to_string());
+    // Scenes loaded by region only hold part of their file, entities never loaded are kept as they are on disk
+    let entities_to_serialize = match &world_state.partial {
+        Some(loaded) => merge_unloaded_entities(entities_to_serialize, &original_entities, loaded),
+        None => entities_to_serialize,
+    };
+
+    if reused > 0 {
+        log!(
+            LogType::Game,
+            LogLevel::Info,
+            LogCategory::System,
+            "Kept {} of {} entity records unchanged from disk",
+            reused,
+            entities_to_serialize.len()
+        );
+    }
+
+    let pretty_config = scene_pretty_config();
 
     if let Some(path) = path {
         // Create metadata with version from TOML file
         let metadata = SceneMetadata {
             format_version: Version::CURRENT_VERSION,
             entity_count: entities_to_serialize.len(),
+            requires: read_scene_metadata(&path)
+                .map(|metadata| metadata.requires)
+                .unwrap_or_default(),
+            region_index: SceneRegionIndex::for_scene(&entities_to_serialize),
         };
 
         // Wrap entities with metadata
@@ -173,24 +207,8 @@ pub fn serialize_entities(world_state: WorldState, path: Option<String>) {
             entities: entities_to_serialize,
         };
 
-        let serialized_data = to_string_pretty(&scene_data, pretty_config).unwrap();
-
-        // TODO:
-        // Compress the data (Encrypt?)
-        // (and uncompressor)
-        let mut file = {
-            // Create parent directories first
-            if let Some(parent) = Path::new(&path).parent() {
-                fs::create_dir_all(parent).unwrap_or_else(|e| {
-                    panic!("Failed to create directories for path{}: {e}", path)
-                });
-            }
-
-            File::create(&path).unwrap_or_else(|e| panic!("Failed to create file{}: {e}", path))
-        };
-
-        file.write_all(serialized_data.as_bytes())
-            .expect("Failed to write to file");
+        write_scene_data(&path, &scene_data, pretty_config, compression)
+            .unwrap_or_else(|e| panic!("Failed to write file {}: {e}", path));
 
         log!(
             LogType::Game,
@@ -208,34 +226,88 @@ pub fn serialize_entities(world_state: WorldState, path: Option<String>) {
     }
 }
 
+fn scene_pretty_config() -> PrettyConfig {
+    PrettyConfig::new()
+        .depth_limit(15)
+        .separate_tuple_members(false)
+        .enumerate_arrays(false)
+        .compact_arrays(true)
+        .indentor("\t".to_string())
+}
+
+/// Write already prepared entities to a scene file, with metadata, in the same format the save system uses
+/// Used for scenes that don't come from the world, like project templates
+/// Compressed only when named .scene.gz, .scene.zst or .scene.bin
+pub fn write_scene_file(path: &str, entities: Vec<EntitySaveReadyData>) -> std::io::Result<()> {
+    let requires = read_scene_metadata(path)
+        .map(|metadata| metadata.requires)
+        .unwrap_or_default();
+    let compression = SceneCompression::from_extension(path).unwrap_or_default();
+    write_scene_data(
+        path,
+        &scene_data(entities, requires),
+        scene_pretty_config(),
+        compression,
+    )
+}
+
+fn scene_data(entities: Vec<EntitySaveReadyData>, requires: Vec<String>) -> SceneData {
+    SceneData {
+        metadata: SceneMetadata {
+            format_version: Version::CURRENT_VERSION,
+            entity_count: entities.len(),
+            requires,
+            region_index: SceneRegionIndex::for_scene(&entities),
+        },
+        entities,
+    }
+}
+
+/// Scene file contents for prepared entities, without touching disk
+pub fn scene_to_string(
+    entities: Vec<EntitySaveReadyData>,
+    requires: Vec<String>,
+) -> Result<String, ron::Error> {
+    to_string_pretty(&scene_data(entities, requires), scene_pretty_config())
+}
+
+/// Metadata of an existing scene file, None for missing files and scenes from before metadata
+pub fn read_scene_metadata(path: &str) -> Option<SceneMetadata> {
+    if let Some(scene) = read_binary_scene(path).ok()? {
+        return Some(scene.metadata);
+    }
+    let contents = read_scene_file(path).ok()?;
+    ron::de::from_str::<SceneData>(&contents)
+        .ok()
+        .map(|scene| scene.metadata)
+}
+
 fn round3(f: f32) -> f32 {
     (f * 1000.0).round() / 1000.0
 }
 
-fn round_vec3(v: Vec3) -> Vec3 {
+pub fn round_vec3(v: Vec3) -> Vec3 {
     Vec3::new(round3(v.x), round3(v.y), round3(v.z))
 }
 
-fn round_quat(q: Quat) -> Quat {
+pub fn round_quat(q: Quat) -> Quat {
     Quat::from_xyzw(round3(q.x), round3(q.y), round3(q.z), round3(q.w))
 }
 
-/// Read existing file data to get original entity data for PreserveDiskFull entities
-fn read_existing_file_data(path: &str) -> Vec<EntitySaveReadyData> {
+/// Read existing file data to get original entity data for PreserveDiskFull entities and save game deltas
+pub fn read_scene_entities(path: &str) -> Vec<EntitySaveReadyData> {
     let file_path = Path::new(path);
     if !file_path.exists() {
         return Vec::new();
     }
 
-    let mut file = match std::fs::File::open(file_path) {
-        Ok(file) => file,
-        Err(_) => return Vec::new(),
-    };
+    if let Ok(Some(scene)) = read_binary_scene(path) {
+        return scene.entities;
+    }
 
-    let mut file_contents = String::new();
-    if file.read_to_string(&mut file_contents).is_err() {
+    let Ok(file_contents) = read_scene_file(path) else {
         return Vec::new();
-    }
+    };
 
     if file_contents.trim().is_empty() {
         return Vec::new();
@@ -0,0 +1,144 @@
+use super::{GraniteType, IdentityData, SpawnSource};
+use bevy::{
+    ecs::world::EntityRef,
+    prelude::{App, Component, Entity, EntityEvent, Resource, World},
+};
+use std::sync::Arc;
+
+/// Triggered on each scene entity once it is fully constructed: spawned, parented and with its components loaded
+/// Observe it for scene driven setup, i.e. `app.add_observer(|spawned: On<GraniteSpawned>| ..)`
+#[derive(EntityEvent, Debug, Clone)]
+pub struct GraniteSpawned {
+    pub entity: Entity,
+    /// Scene the entity was loaded from, relative to assets
+    pub source: String,
+}
+
+type SpawnHookMatcher = Arc<dyn Fn(&EntityRef) -> bool + Send + Sync>;
+type SpawnHookCallback = Arc<dyn Fn(&mut World, Entity) + Send + Sync>;
+
+#[derive(Clone)]
+struct SpawnHook {
+    matches: SpawnHookMatcher,
+    callback: SpawnHookCallback,
+}
+
+/// Callbacks run on scene entities after they are fully constructed, keyed by component or class
+/// Register them with `on_granite_spawn` and `on_granite_class_spawn` on App
+#[derive(Resource, Default, Clone)]
+pub struct GraniteSpawnHooks {
+    hooks: Vec<SpawnHook>,
+}
+
+impl GraniteSpawnHooks {
+    /// Run for loaded entities that have component C, i.e. a #[derive(GraniteClass)] struct or Camera3d
+    pub fn add_for_component<C: Component>(
+        &mut self,
+        hook: impl Fn(&mut World, Entity) + Send + Sync + 'static,
+    ) {
+        self.hooks.push(SpawnHook {
+            matches: Arc::new(|entity: &EntityRef| entity.contains::<C>()),
+            callback: Arc::new(hook),
+        });
+    }
+
+    /// Run for loaded entities of a class, by type name or abv. i.e. "Camera 3D"
+    pub fn add_for_class(
+        &mut self,
+        class: impl Into<String>,
+        hook: impl Fn(&mut World, Entity) + Send + Sync + 'static,
+    ) {
+        let class = class.into();
+        self.hooks.push(SpawnHook {
+            matches: Arc::new(move |entity: &EntityRef| {
+                entity.get::<IdentityData>().is_some_and(|identity| {
+                    identity.class.type_name() == class || identity.class.type_abv() == class
+                })
+            }),
+            callback: Arc::new(hook),
+        });
+    }
+
+    pub fn len(&self) -> usize {
+        self.hooks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hooks.is_empty()
+    }
+}
+
+/// Per type setup of loaded scene entities instead of querying for newly added components
+/// i.e. `app.on_granite_spawn::<Camera3d>(|world, entity| ..)` or `app.on_granite_class_spawn("Point Light", ..)`
+pub trait GraniteSpawnHookAppExt {
+    /// Call `hook` for every entity with component C that a scene load spawns
+    fn on_granite_spawn<C: Component>(
+        &mut self,
+        hook: impl Fn(&mut World, Entity) + Send + Sync + 'static,
+    ) -> &mut Self;
+
+    /// Call `hook` for every entity of a class that a scene load spawns, by type name or abv
+    fn on_granite_class_spawn(
+        &mut self,
+        class: impl Into<String>,
+        hook: impl Fn(&mut World, Entity) + Send + Sync + 'static,
+    ) -> &mut Self;
+}
+
+impl GraniteSpawnHookAppExt for App {
+    fn on_granite_spawn<C: Component>(
+        &mut self,
+        hook: impl Fn(&mut World, Entity) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.init_resource::<GraniteSpawnHooks>();
+        self.world_mut()
+            .resource_mut::<GraniteSpawnHooks>()
+            .add_for_component::<C>(hook);
+        self
+    }
+
+    fn on_granite_class_spawn(
+        &mut self,
+        class: impl Into<String>,
+        hook: impl Fn(&mut World, Entity) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.init_resource::<GraniteSpawnHooks>();
+        self.world_mut()
+            .resource_mut::<GraniteSpawnHooks>()
+            .add_for_class(class, hook);
+        self
+    }
+}
+
+/// Trigger GraniteSpawned and run the matching hooks, in the order given
+/// Called by scene loads once every entity of the scene has its components
+pub fn run_granite_spawn_hooks(world: &mut World, entities: &[Entity]) {
+    let hooks = world
+        .get_resource::<GraniteSpawnHooks>()
+        .map(|hooks| hooks.hooks.clone())
+        .unwrap_or_default();
+
+    for entity in entities.iter().copied() {
+        let Ok(entity_ref) = world.get_entity(entity) else {
+            continue;
+        };
+        let source = entity_ref
+            .get::<SpawnSource>()
+            .map(|source| source.str_ref().to_string())
+            .unwrap_or_default();
+        let matching: Vec<SpawnHookCallback> = hooks
+            .iter()
+            .filter(|hook| (hook.matches)(&entity_ref))
+            .map(|hook| hook.callback.clone())
+            .collect();
+
+        world.trigger(GraniteSpawned { entity, source });
+        for callback in matching {
+            // An earlier hook may have despawned it
+            if world.get_entity(entity).is_err() {
+                break;
+            }
+            callback(world, entity);
+        }
+    }
+}
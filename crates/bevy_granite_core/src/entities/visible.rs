@@ -0,0 +1,107 @@
+use super::IdentityData;
+use bevy::{
+    camera::{visibility::VisibleEntities, Camera},
+    ecs::{
+        entity::Entity,
+        hierarchy::ChildOf,
+        message::{Message, MessageWriter},
+        query::With,
+        resource::Resource,
+        system::{Query, ResMut},
+    },
+    platform::collections::{HashMap, HashSet},
+};
+
+/// Serializable entities each active camera rendered this frame, after frustum and visibility culling
+/// Meshes of a glTF or other child hierarchy count for the serializable entity above them
+/// Opt-in, tracking starts once it is inserted, i.e. app.init_resource::<VisibleSerializableEntities>()
+/// CPU-based: reads Bevy's per camera VisibleEntities every frame and walks up to the saved entity,
+/// so the cost grows with visible meshes. GPU occlusion culling isn't taken into account
+#[derive(Resource, Default, Debug, Clone)]
+pub struct VisibleSerializableEntities {
+    by_camera: HashMap<Entity, HashSet<Entity>>,
+    visible: HashSet<Entity>,
+}
+
+impl VisibleSerializableEntities {
+    /// Visible to at least one active camera
+    pub fn is_visible(&self, entity: Entity) -> bool {
+        self.visible.contains(&entity)
+    }
+
+    pub fn is_visible_to(&self, camera: Entity, entity: Entity) -> bool {
+        self.by_camera
+            .get(&camera)
+            .is_some_and(|visible| visible.contains(&entity))
+    }
+
+    /// Every serializable entity the camera sees. Empty for inactive or unknown cameras
+    pub fn visible_to(&self, camera: Entity) -> impl Iterator<Item = Entity> + '_ {
+        self.by_camera
+            .get(&camera)
+            .into_iter()
+            .flat_map(|visible| visible.iter().copied())
+    }
+
+    /// Every serializable entity visible to any active camera
+    pub fn iter(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.visible.iter().copied()
+    }
+
+    pub fn cameras(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.by_camera.keys().copied()
+    }
+}
+
+/// Sent when a serializable entity comes into view of any camera, or leaves the view of all of them
+#[derive(Message, Debug, Clone, Copy)]
+pub struct SerializableVisibilityChanged {
+    pub entity: Entity,
+    pub visible: bool,
+}
+
+/// Only runs while VisibleSerializableEntities exists
+pub fn update_visible_serializable_entities_system(
+    mut visible_entities: ResMut<VisibleSerializableEntities>,
+    mut writer: MessageWriter<SerializableVisibilityChanged>,
+    cameras: Query<(Entity, &Camera, &VisibleEntities)>,
+    serializable: Query<(), With<IdentityData>>,
+    parents: Query<&ChildOf>,
+) {
+    let mut by_camera: HashMap<Entity, HashSet<Entity>> = HashMap::default();
+    for (camera_entity, camera, camera_visible) in cameras.iter() {
+        if !camera.is_active {
+            continue;
+        }
+        let mut seen = HashSet::default();
+        for entity in camera_visible.entities.values().flatten() {
+            // Walk up to the entity that is saved, a mesh can sit deep inside an imported hierarchy
+            let owner = std::iter::once(*entity)
+                .chain(parents.iter_ancestors(*entity))
+                .find(|candidate| serializable.contains(*candidate));
+            if let Some(owner) = owner {
+                seen.insert(owner);
+            }
+        }
+        by_camera.insert(camera_entity, seen);
+    }
+    let visible: HashSet<Entity> = by_camera.values().flatten().copied().collect();
+
+    for entity in visible.difference(&visible_entities.visible) {
+        writer.write(SerializableVisibilityChanged {
+            entity: *entity,
+            visible: true,
+        });
+    }
+    for entity in visible_entities.visible.difference(&visible) {
+        writer.write(SerializableVisibilityChanged {
+            entity: *entity,
+            visible: false,
+        });
+    }
+
+    if visible_entities.by_camera != by_camera {
+        visible_entities.by_camera = by_camera;
+        visible_entities.visible = visible;
+    }
+}
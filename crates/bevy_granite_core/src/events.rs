@@ -1,5 +1,16 @@
-use crate::entities::SaveSettings;
-use bevy::{ecs::message::Message, prelude::Event, transform::components::Transform};
+use crate::assets::materials::EditableMaterialChange;
+use crate::entities::{DespawnFilter, SaveSettings};
+use crate::world::{
+    CsvColumns, CsvImportSummary, GltfExportSummary, GltfImportSummary, SandboxReport,
+    SceneDependencyGraph, SceneSandbox, SchemaExportSummary,
+};
+use bevy::{
+    ecs::{entity::Entity, message::Message},
+    math::bounding::Aabb3d,
+    prelude::Event,
+    transform::components::Transform,
+};
+use uuid::Uuid;
 
 #[derive(Message)]
 pub struct RuntimeDataReadyEvent(pub String);
@@ -10,12 +21,17 @@ pub struct CollectRuntimeDataEvent(pub String);
 #[derive(Message)]
 pub struct WorldLoadSuccessEvent(pub String);
 
+/// Every scene a batch loaded, companion scenes included
 #[derive(Message)]
 pub struct WorldLoadBatchSuccessEvent(pub Vec<String>);
 
 #[derive(Message)]
 pub struct WorldSaveSuccessEvent(pub String);
 
+/// A loaded scene file was changed on disk by something else, relative to assets. See SceneFileWatcher
+#[derive(Message)]
+pub struct SceneFileChangedEvent(pub String);
+
 // User callable events begin with "Request"
 
 #[derive(Message)]
@@ -29,11 +45,238 @@ pub struct RequestReloadEvent(pub String);
 pub struct RequestLoadEvent(pub String, pub SaveSettings, pub Option<Transform>);
 
 /// Request the loading of multiple serialized save data files. Each tuple contains (path, save_settings, transform_override)
+/// Companion scenes listed in a scene's metadata `requires` are loaded once along with it, and despawned with the last scene needing them
 #[derive(Message)]
 pub struct RequestLoadBatchEvent(pub Vec<(String, SaveSettings, Option<Transform>)>);
 
+/// Load a scene from an untrusted source, i.e. a mod. Only whitelisted components and asset folders are used
+/// A SandboxedLoadReportEvent is sent with everything that was stripped or rejected
+#[derive(Message)]
+pub struct RequestSandboxedLoadEvent {
+    pub path: String,
+    pub save_settings: SaveSettings,
+    pub transform: Option<Transform>,
+    pub sandbox: SceneSandbox,
+}
+
+#[derive(Message)]
+pub struct SandboxedLoadReportEvent(pub SandboxReport);
+
+/// Load only the entities of a scene inside a region, given in scene space before the transform is applied
+/// Send more regions of the same scene to stream it in, entities already loaded are skipped
+#[derive(Message)]
+pub struct RequestRegionLoadEvent {
+    pub path: String,
+    pub save_settings: SaveSettings,
+    pub transform: Option<Transform>,
+    pub region: Aabb3d,
+}
+
+/// Which entities a RequestReassignSpawnSource moves
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReassignEntities {
+    /// These entities and all their children
+    Entities(Vec<Entity>),
+    /// Everything spawned from a scene source
+    Source(String),
+}
+
+/// Move entities to another scene file, so content can be reorganized between files
+/// Both files are marked dirty in DirtyScenes, and saved right away when `save` is set
+#[derive(Message)]
+pub struct RequestReassignSpawnSource {
+    pub entities: ReassignEntities,
+    pub target: String,
+    pub save: bool,
+}
+
+/// Despawn every serializable entity the filter allows. PersistAcrossLoads entities are kept by default
+#[derive(Message, Default)]
+pub struct RequestDespawnSerializableEntities(pub DespawnFilter);
+
+/// Despawn the entities loaded from a source that the filter allows
+#[derive(Message)]
+pub struct RequestDespawnBySource(pub String, pub DespawnFilter);
+
+impl RequestDespawnBySource {
+    /// Despawn the whole source, except PersistAcrossLoads entities
+    pub fn new(source: impl Into<String>) -> Self {
+        Self(source.into(), DespawnFilter::default())
+    }
+}
+
+/// Save what changed at runtime in a loaded scene, compared to its file, into a save game file
+#[derive(Message)]
+pub struct RequestSaveGameEvent {
+    pub scene: String,
+    /// Where to write the save game, i.e. "saves/slot_1.save"
+    pub path: String,
+}
+
+#[derive(Message)]
+pub struct SaveGameSuccessEvent(pub String);
+
+/// Reload the base scene of a save game file and re-apply its deltas
+#[derive(Message)]
+pub struct RequestLoadSaveGameEvent(pub String);
+
+/// Sent with the scene source once a save game is applied
 #[derive(Message)]
-pub struct RequestDespawnSerializableEntities;
+pub struct SaveGameLoadedEvent(pub String);
 
+/// Request a dependency report for a scene. Optionally takes a folder to package the scene and its dependencies into
 #[derive(Message)]
-pub struct RequestDespawnBySource(pub String);
+pub struct RequestSceneDependencyReportEvent(pub String, pub Option<String>);
+
+#[derive(Message)]
+pub struct SceneDependencyReportEvent(pub SceneDependencyGraph);
+
+/// Export serializable entities to a .gltf or .glb file. Source limits the export to one scene, None exports every loaded scene
+#[derive(Message, Debug, Clone)]
+pub struct RequestExportGltf {
+    pub path: String,
+    pub source: Option<String>,
+}
+
+/// Sent with the absolute path of a written glTF export
+#[derive(Message, Debug, Clone)]
+pub struct GltfExportedEvent {
+    pub path: String,
+    pub summary: GltfExportSummary,
+}
+
+/// Write a JSON schema of every registered class and editor visible component: fields, types, defaults and attributes
+#[derive(Message, Debug, Clone)]
+pub struct RequestExportSchema {
+    pub path: String,
+}
+
+/// Sent with the absolute path of a written schema and how many classes and components it describes
+#[derive(Message, Debug, Clone)]
+pub struct SchemaExportedEvent {
+    pub path: String,
+    pub summary: SchemaExportSummary,
+}
+
+/// Write entities to a CSV file, one row each, keyed by their uuid
+#[derive(Message, Debug, Clone)]
+pub struct RequestExportEntityCsv {
+    pub path: String,
+    pub entities: Vec<Entity>,
+    pub columns: CsvColumns,
+}
+
+/// Sent with the path of a written CSV and how many entity rows it holds
+#[derive(Message, Debug, Clone)]
+pub struct EntityCsvExportedEvent {
+    pub path: String,
+    pub rows: usize,
+}
+
+/// Apply an edited CSV back onto the entities whose uuid matches its id column
+#[derive(Message, Debug, Clone)]
+pub struct RequestImportEntityCsv {
+    pub path: String,
+}
+
+#[derive(Message, Debug, Clone)]
+pub struct EntityCsvImportedEvent {
+    pub path: String,
+    pub summary: CsvImportSummary,
+}
+
+/// Recalculate the normals and tangents of an entity's mesh, writing .obj meshes back to disk
+#[derive(Message, Debug, Clone)]
+pub struct RequestRecalculateMeshNormals(pub Entity);
+
+/// Import a .gltf or .glb as editable entities of the source scene. Its meshes, materials and textures are written to the assets folder
+#[derive(Message, Debug, Clone)]
+pub struct RequestImportGltf {
+    pub path: String,
+    pub source: String,
+}
+
+/// Sent with the absolute path of an imported glTF and the uuid of the entity its nodes were spawned under
+#[derive(Message, Debug, Clone)]
+pub struct GltfImportedEvent {
+    pub path: String,
+    pub source: String,
+    pub root: Uuid,
+    pub summary: GltfImportSummary,
+}
+
+/// Sent when a material is added to, removed from or modified in AvailableEditableMaterials
+#[derive(Message, Debug, Clone)]
+pub struct EditableMaterialChangedEvent {
+    pub path: String,
+    pub change: EditableMaterialChange,
+}
+
+/// Look for assets that are alive but unused. The result lands in the AssetLeakReport resource
+#[derive(Message)]
+pub struct RequestAssetLeakScanEvent;
+
+/// Run every registered SceneValidator on the loaded scenes. The result lands in the SceneIssues resource
+#[derive(Message)]
+pub struct RequestValidateScenesEvent;
+
+/// Free the assets a leak scan reports
+#[derive(Message)]
+pub struct RequestPurgeUnusedAssetsEvent;
+
+/// Pick up component types registered again after a dynamic-library hot reload and rebind the entities using them
+/// The result lands in the ComponentSchemaReport resource
+#[derive(Message)]
+pub struct RequestReloadComponentSchemasEvent;
+
+/// Move or rename a file or folder inside the assets folder, rewriting every scene and material that references it
+#[derive(Message)]
+pub struct RequestAssetMoveEvent(pub String, pub String);
+
+/// Save an entity and its children as a prefab file, relative to assets, and replace them with an instance of it
+#[derive(Message, Clone)]
+pub struct RequestCreatePrefabEvent {
+    pub entity: Entity,
+    pub path: String,
+}
+
+/// Drop the overrides of a prefab instance root and respawn it as the prefab is
+#[derive(Message)]
+pub struct RequestRevertPrefabEvent(pub Entity);
+
+/// Swap every loaded entity using the material at `from` to the one at `to`, both relative to assets
+#[derive(Message, Clone)]
+pub struct RequestReplaceMaterialEvent {
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Message)]
+pub struct MaterialReplacedEvent {
+    pub from: String,
+    pub to: String,
+    /// Entities that now use `to`
+    pub entities: Vec<Entity>,
+}
+
+#[derive(Message)]
+pub struct AssetMovedEvent {
+    pub from: String,
+    pub to: String,
+    /// Scene and material files that had their references rewritten
+    pub rewritten: Vec<String>,
+}
+
+/// Move every editable descendant of each root directly under it, keeping where they are in the world.
+/// Empty groups left in between are despawned
+#[derive(Message, Clone)]
+pub struct RequestFlattenHierarchyEvent(pub Vec<Entity>);
+
+/// Bake the scale and/or rotation of entities into their mesh and editable children, then reset them on the entity.
+/// .obj meshes are written to a new file next to the original
+#[derive(Message, Clone)]
+pub struct RequestApplyTransformEvent {
+    pub entities: Vec<Entity>,
+    pub scale: bool,
+    pub rotation: bool,
+}
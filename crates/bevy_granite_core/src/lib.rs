@@ -6,6 +6,7 @@ use bevy::{
 use bevy_egui::{EguiGlobalSettings, EguiPlugin};
 use bevy_granite_logging::setup_logging;
 use bevy_obj::ObjPlugin;
+use entities::gather_component_schemas;
 use setup::{gather_registered_types, setup_component_editor};
 
 // Sub modules
@@ -14,6 +15,8 @@ pub mod entities;
 pub mod events;
 pub mod setup;
 pub mod shared;
+#[cfg(feature = "testing")]
+pub mod testing;
 pub mod world;
 
 // Internal plugins from modules
@@ -24,10 +27,15 @@ use crate::world::WorldPlugin;
 
 // Re-exports
 pub use assets::{
-    get_material_from_path, load_texture_with_repeat, material_from_path_into_scene,
-    materials_from_folder_into_scene, AvailableEditableMaterials, EditableMaterial,
-    EditableMaterialError, EditableMaterialField, MaterialData, NewEditableMaterial,
-    RequiredMaterialData, RequiredMaterialDataMut, StandardMaterialDef,
+    find_asset_references, get_material_from_path, load_texture_with_repeat,
+    material_from_path_into_scene, materials_from_folder_into_scene, move_asset_with_references,
+    AssetLeakReport, AvailableEditableMaterials, CustomMaterialDef, CustomMaterialState,
+    CustomMaterials, CustomShaderMaterial, CustomUniform, CustomUniformValue, DetailSettings,
+    EditableMaterial, EditableMaterialChange, EditableMaterialError, EditableMaterialField,
+    ExtendedMaterialState, ExtensionSettings, GraniteExtension, GraniteMaterial, GraniteMaterials,
+    LeakedAsset, MaterialData, MaterialOverride, MaterialOverrideState, MaterialWind, MeshStats,
+    NewEditableMaterial, RequiredMaterialData, RequiredMaterialDataMut, StandardMaterialDef,
+    TriplanarSettings, WindMask, WindSettings,
 };
 pub use bevy_granite_macros::register_editor_components;
 
@@ -41,26 +49,84 @@ pub trait UICallableEventProvider {
 }
 
 pub use entities::{
-    BridgeTag, Camera3D, ClassCategory, ComponentEditor, DirLight, EditorIgnore,
-    GraniteEditorSerdeEntity, GraniteType, GraniteTypes, HasRuntimeData, IdentityData, MainCamera,
-    MaterialNameSource, NeedsTangents, PointLightData, PromptData, PromptImportSettings, RectBrush,
-    ReflectedComponent, SaveSettings, SpawnSource, TransformData, TreeHiddenEntity, UICamera,
-    VolumetricFog, OBJ,
+    find_entity_references, find_kit_snap, get_custom_class_info, read_scene_entities,
+    register_class, register_class_category_order, register_class_icon, register_granite_class,
+    registered_vfx_effects, retarget_entity_references, scene_to_string, vfx_effect_parameters,
+    write_scene_file, ActionBinding, AmbienceListener, AmbienceZone, AmbienceZoneEntered,
+    AmbienceZoneExited, AmbienceZoneShape, AmbienceZones, Anchor, Anchors, AudioEmitter3D,
+    AudioEmitterPreview, AudioEmitterState, AudioEmitters, AudioMonitor, AudioRolloff,
+    BindingInput, BridgeTag, Camera3D, CameraRail, CameraRailFinished, CameraRailPlayer,
+    CameraRailPoint, ClassCategory, ComponentEditor, ComponentField, ComponentRebind,
+    ComponentSchema, ComponentSchemaChange, ComponentSchemaReport, ComponentSchemas, CustomClass,
+    CustomClassInfo, CustomProperties, DespawnFilter, DirLight, EditorIgnore, EntityReference,
+    EntitySaveReadyData, EnvironmentFog, EnvironmentFogMode, ForceFalloff, ForceKind, ForceVolume,
+    ForceVolumes, GraniteClass, GraniteClassRegistry, GraniteEditorSerdeEntity, GraniteJoint,
+    GraniteJointReady, GraniteSpawnHookAppExt, GraniteSpawnHooks, GraniteSpawned, GraniteType,
+    GraniteTypes, HasRuntimeData, IdentityData, Imposter, ImposterBillboard, InputBinding,
+    InputBindings, JointBodies, JointKind, KitSnap, KitSocket, KitSockets, LightProbeData,
+    LightProbeKind, LightProbeVolume, MainCamera, MaterialNameSource, NeedsTangents,
+    PersistAcrossLoads, PointLightData, PromptData, PromptImportSettings, PropertyValue,
+    QualityLevels, QualityPreset, QualityRuleAppExt, QualityRules, QualitySettings, RailEasing,
+    RailPath, RandomizationRoll, RectBrush, ReflectedComponent, RequestConnectWaypointsEvent,
+    RequestCreateJointEvent, RunningVfx, SaveSettings, SceneOrder, SceneTarget, SchemaChangeKind,
+    SerializableVisibilityChanged, SpawnCondition, SpawnConditions, SpawnRandomization,
+    SpawnSource, Spline3D, SplineInterpolation, SplineMesh, SplinePath, SplinePoint, SplineProfile,
+    Splines, TransformData, TreeHiddenEntity, UICamera, VfxEffect, VfxEffectAppExt, VfxEffects,
+    VfxSpawner, VisibleSerializableEntities, VolumetricFog, Waypoint, WaypointLink,
+    WaypointLinkMode, WaypointNetworks, Weather, WeatherController, WeatherPreset,
+    WorldEnvironment, WorldEnvironmentState, ZoneReverb, OBJ,
 };
 pub use events::{
-    CollectRuntimeDataEvent, RequestDespawnBySource, RequestDespawnSerializableEntities,
-    RequestLoadEvent, RequestLoadBatchEvent, RequestReloadEvent, RequestSaveEvent, RuntimeDataReadyEvent,
-    WorldLoadSuccessEvent, WorldLoadBatchSuccessEvent, WorldSaveSuccessEvent,
+    AssetMovedEvent, CollectRuntimeDataEvent, EditableMaterialChangedEvent, EntityCsvExportedEvent,
+    EntityCsvImportedEvent, GltfExportedEvent, GltfImportedEvent, MaterialReplacedEvent,
+    ReassignEntities, RequestApplyTransformEvent, RequestAssetLeakScanEvent, RequestAssetMoveEvent,
+    RequestCreatePrefabEvent, RequestDespawnBySource, RequestDespawnSerializableEntities,
+    RequestExportEntityCsv, RequestExportGltf, RequestExportSchema, RequestFlattenHierarchyEvent,
+    RequestImportEntityCsv, RequestImportGltf, RequestLoadBatchEvent, RequestLoadEvent,
+    RequestLoadSaveGameEvent, RequestPurgeUnusedAssetsEvent, RequestReassignSpawnSource,
+    RequestRecalculateMeshNormals, RequestRegionLoadEvent, RequestReloadComponentSchemasEvent,
+    RequestReloadEvent, RequestReplaceMaterialEvent, RequestRevertPrefabEvent,
+    RequestSandboxedLoadEvent, RequestSaveEvent, RequestSaveGameEvent,
+    RequestSceneDependencyReportEvent, RequestValidateScenesEvent, RuntimeDataReadyEvent,
+    SandboxedLoadReportEvent, SaveGameLoadedEvent, SaveGameSuccessEvent,
+    SceneDependencyReportEvent, SceneFileChangedEvent, SchemaExportedEvent,
+    WorldLoadBatchSuccessEvent, WorldLoadSuccessEvent, WorldSaveSuccessEvent,
 };
 pub use setup::RegisteredTypeNames;
 pub use shared::{
-    absolute_asset_to_rel, is_scene_version_compatible, mouse_to_world_delta,
-    rel_asset_to_absolute, CursorWindowPos, IconEntity, IconProxy, IconType, InputTypes, UserInput,
+    absolute_asset_to_rel, is_scene_file, is_scene_version_compatible, mouse_to_world_delta,
+    read_binary_scene, read_scene_file, read_scene_file_compressed, rel_asset_to_absolute,
+    strip_scene_extension, write_scene_contents, write_scene_data, CursorWindowPos,
+    EditorCapabilities, GraniteJobs, IconEntity, IconProxy, IconType, InputTypes, JobContext,
+    JobFinishedEvent, JobId, JobInfo, JobResult, JobResultCache, JobStatus, SceneCompression,
+    SceneCompressionSettings, UserInput, SCENE_FILE_EXTENSIONS,
 };
+#[cfg(feature = "testing")]
+pub use testing::{HeadlessPlugins, TestHarness};
+pub use world::{
+    apply_issue_fix, apply_transform, diff_scene_entities, flatten_hierarchy, is_prefab_file,
+    prefab_member_uuid, run_scene_validators, save_project_resources, CsvColumns, CsvImportSummary,
+    DirtyScenes, EntityDelta, EntityProfile, GltfDocument, GltfExportSummary, GltfImportSummary,
+    GraniteSnapshot, IncrementalSave, IssueFix, IssueSeverity, PartialScenes, PrefabInstance,
+    PrefabLibrary, PrefabMember, PrefabOverride, ProfileTotals, ProjectResources, SandboxReport,
+    SandboxViolation, SaveGameData, SaveLock, SaveMode, SceneCompanions, SceneDependency,
+    SceneDependencyGraph, SceneDependencyKind, SceneEntityChange, SceneEntityDiff,
+    SceneFileNotifier, SceneFileWatcher, SceneIssue, SceneIssues, SceneProfile, SceneProfileKind,
+    SceneProfileReport, SceneRegionIndex, SceneSandbox, SceneValidation, SceneValidationPlugin,
+    SceneValidator, SceneValidatorAppExt, SceneValidators, SnapshotEntity, StartupWorlds,
+    DEFAULT_SCENE_WATCH_DEBOUNCE, PREFAB_FILE_EXTENSION, PROJECT_RESOURCES_PATH, SCENE_PROFILE_TOP,
+    STARTUP_WORLD_ARG, STARTUP_WORLD_ENV,
+};
+#[cfg(feature = "stress")]
+pub use world::{SoakTest, SoakTestPlugin};
+#[cfg(feature = "visual")]
+pub use world::{VisualComparison, VisualOutcome, VisualRegression, VisualRegressionPlugin};
 
 // Bevy Granite Core plugin
 pub struct BevyGraniteCore {
     pub logging: bool,
+    /// Named worlds to pick the startup world from
+    pub startup_worlds: StartupWorlds,
 }
 impl Plugin for BevyGraniteCore {
     fn build(&self, app: &mut App) {
@@ -86,24 +152,61 @@ impl Plugin for BevyGraniteCore {
             //
             .add_message::<RequestLoadEvent>()
             .add_message::<RequestLoadBatchEvent>()
+            .add_message::<RequestSandboxedLoadEvent>()
+            .add_message::<SandboxedLoadReportEvent>()
+            .add_message::<RequestRegionLoadEvent>()
             .add_message::<WorldLoadSuccessEvent>()
             .add_message::<WorldLoadBatchSuccessEvent>()
             .add_message::<RequestDespawnSerializableEntities>()
             .add_message::<RequestDespawnBySource>()
             .add_message::<WorldSaveSuccessEvent>()
+            .add_message::<SceneFileChangedEvent>()
             .add_message::<RequestSaveEvent>()
             .add_message::<CollectRuntimeDataEvent>()
             .add_message::<RuntimeDataReadyEvent>()
             .add_message::<RequestReloadEvent>()
+            .add_message::<RequestSceneDependencyReportEvent>()
+            .add_message::<SceneDependencyReportEvent>()
+            .add_message::<RequestExportGltf>()
+            .add_message::<GltfExportedEvent>()
+            .add_message::<RequestExportSchema>()
+            .add_message::<SchemaExportedEvent>()
+            .add_message::<RequestImportGltf>()
+            .add_message::<GltfImportedEvent>()
+            .add_message::<RequestExportEntityCsv>()
+            .add_message::<EntityCsvExportedEvent>()
+            .add_message::<RequestImportEntityCsv>()
+            .add_message::<EntityCsvImportedEvent>()
+            .add_message::<RequestRecalculateMeshNormals>()
+            .add_message::<RequestFlattenHierarchyEvent>()
+            .add_message::<RequestApplyTransformEvent>()
+            .add_message::<RequestAssetMoveEvent>()
+            .add_message::<AssetMovedEvent>()
+            .add_message::<RequestCreatePrefabEvent>()
+            .add_message::<RequestRevertPrefabEvent>()
+            .add_message::<EditableMaterialChangedEvent>()
+            .add_message::<RequestAssetLeakScanEvent>()
+            .add_message::<RequestPurgeUnusedAssetsEvent>()
+            .add_message::<RequestValidateScenesEvent>()
+            .add_message::<RequestReplaceMaterialEvent>()
+            .add_message::<MaterialReplacedEvent>()
+            .add_message::<RequestReloadComponentSchemasEvent>()
+            .add_message::<RequestReassignSpawnSource>()
+            .add_message::<RequestSaveGameEvent>()
+            .add_message::<SaveGameSuccessEvent>()
+            .add_message::<RequestLoadSaveGameEvent>()
+            .add_message::<SaveGameLoadedEvent>()
             //
             // Resources
             //
             .insert_resource(RegisteredTypeNames::default())
+            .insert_resource(self.startup_worlds.clone())
             //
             // Schedule systems
             //
             .add_systems(PreStartup, setup_logging.run_if(move || logging_enabled))
             .add_systems(PreStartup, gather_registered_types.after(setup_logging))
+            .add_systems(PreStartup, gather_component_schemas.after(setup_logging))
             .add_systems(PostStartup, setup_component_editor);
     }
 }
@@ -23,10 +23,7 @@ pub fn gather_registered_types(
         LogCategory::System,
         "Gathering serializable component names"
     );
-    registered_names.names = get_bridge_reflect_component_names(&type_registry.read());
-    registered_names
-        .names
-        .append(&mut get_bevy_reflect_component_names(&type_registry.read()));
+    registered_names.names = registered_type_names(&type_registry.read());
     log!(
         LogType::Game,
         LogLevel::Info,
@@ -36,6 +33,13 @@ pub fn gather_registered_types(
     );
 }
 
+/// Bridge components followed by the exposed bevy ones
+pub(crate) fn registered_type_names(type_registry: &TypeRegistry) -> Vec<Cow<'static, str>> {
+    let mut names = get_bridge_reflect_component_names(type_registry);
+    names.append(&mut get_bevy_reflect_component_names(type_registry));
+    names
+}
+
 fn get_bridge_reflect_component_names(type_registry: &TypeRegistry) -> Vec<Cow<'static, str>> {
     type_registry
         .iter()
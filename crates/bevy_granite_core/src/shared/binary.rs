@@ -0,0 +1,904 @@
+use serde::{
+    de::{
+        self,
+        value::{BorrowedStrDeserializer, UnitDeserializer},
+        DeserializeSeed, EnumAccess, IgnoredAny, MapAccess, SeqAccess, VariantAccess, Visitor,
+    },
+    ser::{self, Serialize},
+    Deserialize,
+};
+use std::fmt;
+
+// binary.rs
+// Compact self-describing serde format for binary scenes, see compression.rs
+// Every value starts with a tag byte. Structs keep their field names, so skipped, defaulted and
+// renamed fields behave as they do in RON. Numbers and lengths are little endian
+
+const NONE: u8 = 0;
+const SOME: u8 = 1;
+const UNIT: u8 = 2;
+const FALSE: u8 = 3;
+const TRUE: u8 = 4;
+const INT: u8 = 5;
+const UINT: u8 = 6;
+const F32: u8 = 7;
+const F64: u8 = 8;
+const CHAR: u8 = 9;
+const STR: u8 = 10;
+const BYTES: u8 = 11;
+const SEQ: u8 = 12;
+const MAP: u8 = 13;
+const UNIT_VARIANT: u8 = 14;
+const VARIANT: u8 = 15;
+/// Deepest nesting read back, so corrupt data can't recurse until the stack overflows
+const MAX_DEPTH: u32 = 128;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BinaryError(String);
+
+impl fmt::Display for BinaryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for BinaryError {}
+
+impl ser::Error for BinaryError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+impl de::Error for BinaryError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+type Result<T> = std::result::Result<T, BinaryError>;
+
+pub fn to_bytes<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>> {
+    let mut serializer = BinarySerializer { output: Vec::new() };
+    value.serialize(&mut serializer)?;
+    Ok(serializer.output)
+}
+
+pub fn from_bytes<'de, T: Deserialize<'de>>(bytes: &'de [u8]) -> Result<T> {
+    let mut deserializer = BinaryDeserializer {
+        input: bytes,
+        depth: 0,
+    };
+    let value = T::deserialize(&mut deserializer)?;
+    if !deserializer.input.is_empty() {
+        return Err(BinaryError(format!(
+            "{} trailing bytes",
+            deserializer.input.len()
+        )));
+    }
+    Ok(value)
+}
+
+struct BinarySerializer {
+    output: Vec<u8>,
+}
+
+impl BinarySerializer {
+    fn len(&mut self, len: usize) -> Result<()> {
+        let len = u32::try_from(len).map_err(|_| BinaryError("length over u32".to_string()))?;
+        self.output.extend(len.to_le_bytes());
+        Ok(())
+    }
+
+    fn str(&mut self, value: &str) -> Result<()> {
+        self.len(value.len())?;
+        self.output.extend(value.as_bytes());
+        Ok(())
+    }
+
+    /// Sequences and maps get their length patched in once they end, serde doesn't always know it up front
+    fn open(&mut self, tag: u8) -> Compound<'_> {
+        self.output.push(tag);
+        let at = self.output.len();
+        self.output.extend([0; 4]);
+        Compound {
+            serializer: self,
+            at,
+            count: 0,
+        }
+    }
+
+    fn variant(&mut self, variant: &str) -> Result<()> {
+        self.output.push(VARIANT);
+        self.str(variant)
+    }
+}
+
+struct Compound<'a> {
+    serializer: &'a mut BinarySerializer,
+    at: usize,
+    count: u32,
+}
+
+impl Compound<'_> {
+    fn element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.count += 1;
+        value.serialize(&mut *self.serializer)
+    }
+
+    fn field<T: Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<()> {
+        self.serializer.output.push(STR);
+        self.serializer.str(key)?;
+        self.element(value)
+    }
+
+    fn close(self) -> Result<()> {
+        self.serializer.output[self.at..self.at + 4].copy_from_slice(&self.count.to_le_bytes());
+        Ok(())
+    }
+}
+
+impl<'a> ser::Serializer for &'a mut BinarySerializer {
+    type Ok = ();
+    type Error = BinaryError;
+    type SerializeSeq = Compound<'a>;
+    type SerializeTuple = Compound<'a>;
+    type SerializeTupleStruct = Compound<'a>;
+    type SerializeTupleVariant = Compound<'a>;
+    type SerializeMap = Compound<'a>;
+    type SerializeStruct = Compound<'a>;
+    type SerializeStructVariant = Compound<'a>;
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    fn serialize_bool(self, v: bool) -> Result<()> {
+        self.output.push(if v { TRUE } else { FALSE });
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<()> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<()> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<()> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<()> {
+        self.output.push(INT);
+        self.output.extend(v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<()> {
+        self.serialize_u64(v as u64)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<()> {
+        self.serialize_u64(v as u64)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<()> {
+        self.serialize_u64(v as u64)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<()> {
+        self.output.push(UINT);
+        self.output.extend(v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<()> {
+        self.output.push(F32);
+        self.output.extend(v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<()> {
+        self.output.push(F64);
+        self.output.extend(v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_char(self, v: char) -> Result<()> {
+        self.output.push(CHAR);
+        self.output.extend((v as u32).to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<()> {
+        self.output.push(STR);
+        self.str(v)
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<()> {
+        self.output.push(BYTES);
+        self.len(v.len())?;
+        self.output.extend(v);
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<()> {
+        self.output.push(NONE);
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<()> {
+        self.output.push(SOME);
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<()> {
+        self.output.push(UNIT);
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<()> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<()> {
+        self.output.push(UNIT_VARIANT);
+        self.str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        self.variant(variant)?;
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Compound<'a>> {
+        Ok(self.open(SEQ))
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Compound<'a>> {
+        Ok(self.open(SEQ))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Compound<'a>> {
+        Ok(self.open(SEQ))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a>> {
+        self.variant(variant)?;
+        Ok(self.open(SEQ))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Compound<'a>> {
+        Ok(self.open(MAP))
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Compound<'a>> {
+        Ok(self.open(MAP))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a>> {
+        self.variant(variant)?;
+        Ok(self.open(MAP))
+    }
+}
+
+impl ser::SerializeSeq for Compound<'_> {
+    type Ok = ();
+    type Error = BinaryError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<()> {
+        self.close()
+    }
+}
+
+impl ser::SerializeTuple for Compound<'_> {
+    type Ok = ();
+    type Error = BinaryError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<()> {
+        self.close()
+    }
+}
+
+impl ser::SerializeTupleStruct for Compound<'_> {
+    type Ok = ();
+    type Error = BinaryError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<()> {
+        self.close()
+    }
+}
+
+impl ser::SerializeTupleVariant for Compound<'_> {
+    type Ok = ();
+    type Error = BinaryError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<()> {
+        self.close()
+    }
+}
+
+impl ser::SerializeMap for Compound<'_> {
+    type Ok = ();
+    type Error = BinaryError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        key.serialize(&mut *self.serializer)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<()> {
+        self.close()
+    }
+}
+
+impl ser::SerializeStruct for Compound<'_> {
+    type Ok = ();
+    type Error = BinaryError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<()> {
+        self.close()
+    }
+}
+
+impl ser::SerializeStructVariant for Compound<'_> {
+    type Ok = ();
+    type Error = BinaryError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<()> {
+        self.close()
+    }
+}
+
+struct BinaryDeserializer<'de> {
+    input: &'de [u8],
+    depth: u32,
+}
+
+impl<'de> BinaryDeserializer<'de> {
+    fn take(&mut self, len: usize) -> Result<&'de [u8]> {
+        if self.input.len() < len {
+            return Err(BinaryError("unexpected end of data".to_string()));
+        }
+        let (taken, rest) = self.input.split_at(len);
+        self.input = rest;
+        Ok(taken)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    fn peek(&self) -> Result<u8> {
+        self.input
+            .first()
+            .copied()
+            .ok_or_else(|| BinaryError("unexpected end of data".to_string()))
+    }
+
+    fn len(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    fn str(&mut self) -> Result<&'de str> {
+        let len = self.len()? as usize;
+        std::str::from_utf8(self.take(len)?).map_err(|e| BinaryError(e.to_string()))
+    }
+
+    fn nested<T>(&mut self, read: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        if self.depth >= MAX_DEPTH {
+            return Err(BinaryError("nested too deep".to_string()));
+        }
+        self.depth += 1;
+        let result = read(self);
+        self.depth -= 1;
+        result
+    }
+}
+
+impl<'de> de::Deserializer<'de> for &mut BinaryDeserializer<'de> {
+    type Error = BinaryError;
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.take(1)?[0] {
+            NONE => visitor.visit_none(),
+            SOME => self.nested(|de| visitor.visit_some(de)),
+            UNIT => visitor.visit_unit(),
+            FALSE => visitor.visit_bool(false),
+            TRUE => visitor.visit_bool(true),
+            INT => visitor.visit_i64(i64::from_le_bytes(self.array()?)),
+            UINT => visitor.visit_u64(u64::from_le_bytes(self.array()?)),
+            F32 => visitor.visit_f32(f32::from_le_bytes(self.array()?)),
+            F64 => visitor.visit_f64(f64::from_le_bytes(self.array()?)),
+            CHAR => {
+                let code = u32::from_le_bytes(self.array()?);
+                let char = char::from_u32(code)
+                    .ok_or_else(|| BinaryError(format!("invalid char {}", code)))?;
+                visitor.visit_char(char)
+            }
+            STR => visitor.visit_borrowed_str(self.str()?),
+            BYTES => {
+                let len = self.len()? as usize;
+                visitor.visit_borrowed_bytes(self.take(len)?)
+            }
+            SEQ => {
+                let remaining = self.len()?;
+                self.nested(|de| {
+                    visitor.visit_seq(Counted {
+                        deserializer: de,
+                        remaining,
+                    })
+                })
+            }
+            MAP => {
+                let remaining = self.len()?;
+                self.nested(|de| {
+                    visitor.visit_map(Counted {
+                        deserializer: de,
+                        remaining,
+                    })
+                })
+            }
+            tag @ (UNIT_VARIANT | VARIANT) => self.nested(|de| {
+                visitor.visit_enum(Variant {
+                    deserializer: de,
+                    unit: tag == UNIT_VARIANT,
+                })
+            }),
+            tag => Err(BinaryError(format!("unknown tag {}", tag))),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.peek()? {
+            NONE => {
+                self.take(1)?;
+                visitor.visit_none()
+            }
+            SOME => {
+                self.take(1)?;
+                self.nested(|de| visitor.visit_some(de))
+            }
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        unit unit_struct seq tuple tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+struct Counted<'a, 'de> {
+    deserializer: &'a mut BinaryDeserializer<'de>,
+    remaining: u32,
+}
+
+impl<'de> SeqAccess<'de> for Counted<'_, 'de> {
+    type Error = BinaryError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        seed.deserialize(&mut *self.deserializer).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining as usize)
+    }
+}
+
+impl<'de> MapAccess<'de> for Counted<'_, 'de> {
+    type Error = BinaryError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        seed.deserialize(&mut *self.deserializer).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        seed.deserialize(&mut *self.deserializer)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining as usize)
+    }
+}
+
+struct Variant<'a, 'de> {
+    deserializer: &'a mut BinaryDeserializer<'de>,
+    /// Unit variants have nothing after their name
+    unit: bool,
+}
+
+impl<'de> EnumAccess<'de> for Variant<'_, 'de> {
+    type Error = BinaryError;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self)> {
+        let name = self.deserializer.str()?;
+        let value = seed.deserialize(BorrowedStrDeserializer::<BinaryError>::new(name))?;
+        Ok((value, self))
+    }
+}
+
+impl<'de> VariantAccess<'de> for Variant<'_, 'de> {
+    type Error = BinaryError;
+
+    fn unit_variant(self) -> Result<()> {
+        if !self.unit {
+            IgnoredAny::deserialize(&mut *self.deserializer)?;
+        }
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value> {
+        if self.unit {
+            return seed.deserialize(UnitDeserializer::<BinaryError>::new());
+        }
+        seed.deserialize(&mut *self.deserializer)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value> {
+        de::Deserializer::deserialize_any(&mut *self.deserializer, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        de::Deserializer::deserialize_any(&mut *self.deserializer, visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        entities::{
+            EntitySaveReadyData, GraniteTypes, IdentityData, PropertyValue, SceneData,
+            SceneMetadata, TransformData, OBJ,
+        },
+        shared::version::Version,
+        world::{SceneRegionEntry, SceneRegionIndex},
+    };
+    use bevy::prelude::{Color, IVec3, Quat, Vec3};
+    use serde::{Deserialize, Serialize};
+    use std::collections::{BTreeMap, HashMap};
+    use uuid::Uuid;
+
+    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+    struct Unit;
+
+    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+    struct Newtype(u16);
+
+    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+    enum Kind {
+        Unit,
+        Newtype(String),
+        Tuple(i8, f32),
+        Struct { value: Option<u64>, flag: bool },
+    }
+
+    /// One field per kind of value serde can hand the format
+    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+    struct EveryKind {
+        bool: bool,
+        signed: i64,
+        small: i8,
+        unsigned: u64,
+        float: f32,
+        double: f64,
+        char: char,
+        string: String,
+        bytes: Vec<u8>,
+        unit: (),
+        unit_struct: Unit,
+        newtype: Newtype,
+        tuple: (u8, String, Vec3),
+        nested: Option<Option<u32>>,
+        nested_none: Option<Option<u32>>,
+        kinds: Vec<Kind>,
+        map: HashMap<String, Vec<Kind>>,
+        ordered: BTreeMap<u32, Option<PropertyValue>>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        skipped: Option<String>,
+        uuid: Uuid,
+    }
+
+    fn every_kind() -> EveryKind {
+        EveryKind {
+            bool: true,
+            signed: i64::MIN,
+            small: -3,
+            unsigned: u64::MAX,
+            float: 0.25,
+            double: -1.5e300,
+            char: 'ß',
+            string: "dungeon \"entrance\" ✓".to_string(),
+            bytes: vec![0, 1, 255],
+            unit: (),
+            unit_struct: Unit,
+            newtype: Newtype(7),
+            tuple: (9, String::new(), Vec3::new(1.0, -2.0, 3.5)),
+            nested: Some(None),
+            nested_none: None,
+            kinds: vec![
+                Kind::Unit,
+                Kind::Newtype("torch".to_string()),
+                Kind::Tuple(-1, 2.0),
+                Kind::Struct {
+                    value: Some(42),
+                    flag: false,
+                },
+                Kind::Struct {
+                    value: None,
+                    flag: true,
+                },
+            ],
+            map: HashMap::from([
+                ("empty".to_string(), vec![]),
+                ("units".to_string(), vec![Kind::Unit, Kind::Unit]),
+            ]),
+            ordered: BTreeMap::from([
+                (0, Some(PropertyValue::String("name".to_string()))),
+                (1, Some(PropertyValue::Number(12.5))),
+                (2, Some(PropertyValue::Bool(true))),
+                (
+                    3,
+                    Some(PropertyValue::Color(Color::srgba(1.0, 0.5, 0.0, 0.75))),
+                ),
+                (4, None),
+            ]),
+            skipped: None,
+            uuid: Uuid::from_u128(0x1234_5678_9abc_def0),
+        }
+    }
+
+    fn entity(id: u128, class: GraniteTypes, parent: Option<u128>) -> EntitySaveReadyData {
+        EntitySaveReadyData {
+            identity: IdentityData {
+                uuid: Uuid::from_u128(id),
+                name: format!("Entity {}", id),
+                class,
+            },
+            transform: TransformData {
+                position: Vec3::new(id as f32, 0.5, -2.0),
+                rotation: Quat::from_rotation_y(0.3),
+                scale: Vec3::ONE,
+            },
+            parent: parent.map(Uuid::from_u128),
+            components: None,
+        }
+    }
+
+    fn scene() -> SceneData {
+        // Every class, so each variant and its nested options go through the format
+        let classes = [
+            GraniteTypes::OBJ(OBJ {
+                mesh_path: "models/crate.obj".into(),
+                ..Default::default()
+            }),
+            GraniteTypes::Camera3D(Default::default()),
+            GraniteTypes::Weather(Default::default()),
+            GraniteTypes::Empty(Default::default()),
+            GraniteTypes::PointLightData(Default::default()),
+            GraniteTypes::SpotLightData(Default::default()),
+            GraniteTypes::DirLight(Default::default()),
+            GraniteTypes::WorldEnvironment(Default::default()),
+            GraniteTypes::LightProbe(Default::default()),
+            GraniteTypes::Anchor(Default::default()),
+            GraniteTypes::Waypoint(Default::default()),
+            GraniteTypes::InputBinding(Default::default()),
+            GraniteTypes::ForceVolume(Default::default()),
+            GraniteTypes::AmbienceZone(Default::default()),
+            GraniteTypes::AudioEmitter3D(Default::default()),
+            GraniteTypes::VfxSpawner(Default::default()),
+            GraniteTypes::CameraRail(Default::default()),
+            GraniteTypes::RectBrush(Default::default()),
+            GraniteTypes::SplineMesh(Default::default()),
+            GraniteTypes::Spline3D(Default::default()),
+            GraniteTypes::Custom(Default::default()),
+            GraniteTypes::Unknown(Default::default()),
+        ];
+        let mut entities: Vec<EntitySaveReadyData> = classes
+            .into_iter()
+            .enumerate()
+            .map(|(index, class)| {
+                let parent = (index > 0).then_some(1);
+                entity(index as u128 + 1, class, parent)
+            })
+            .collect();
+        // Components are saved as RON text, one per registered type
+        entities[1].components = Some(HashMap::from([
+            (
+                "game::Health".to_string(),
+                "(current: 80.0, max: Some(100.0))".to_string(),
+            ),
+            (
+                "bevy_granite_core::entities::custom_properties::CustomProperties".to_string(),
+                ron::to_string(&every_kind().ordered).unwrap(),
+            ),
+            ("game::Marker".to_string(), "()".to_string()),
+        ]));
+        entities[2].components = Some(HashMap::new());
+
+        SceneData {
+            metadata: SceneMetadata {
+                format_version: Version::CURRENT_VERSION,
+                entity_count: entities.len(),
+                requires: vec!["scenes/shared_props.scene".to_string()],
+                region_index: Some(SceneRegionIndex {
+                    cell_size: 32.0,
+                    cells: vec![(
+                        IVec3::new(-1, 0, 2),
+                        vec![
+                            SceneRegionEntry {
+                                index: 0,
+                                position: Vec3::ZERO,
+                                parent: None,
+                            },
+                            SceneRegionEntry {
+                                index: 1,
+                                position: Vec3::X,
+                                parent: Some(0),
+                            },
+                        ],
+                    )],
+                }),
+            },
+            entities,
+        }
+    }
+
+    #[test]
+    fn every_kind_round_trips() {
+        let value = every_kind();
+        let bytes = to_bytes(&value).unwrap();
+        assert_eq!(from_bytes::<EveryKind>(&bytes).unwrap(), value);
+    }
+
+    #[test]
+    fn scene_round_trips() {
+        let scene = scene();
+        let bytes = to_bytes(&scene).unwrap();
+        let read: SceneData = from_bytes(&bytes).unwrap();
+        assert_eq!(read.entities, scene.entities);
+        assert_eq!(
+            format!("{:?}", read.metadata),
+            format!("{:?}", scene.metadata)
+        );
+    }
+
+    #[test]
+    fn truncated_input_is_an_error() {
+        let bytes = to_bytes(&scene()).unwrap();
+        for len in 0..bytes.len() {
+            assert!(
+                from_bytes::<SceneData>(&bytes[..len]).is_err(),
+                "{} of {} bytes read as a scene",
+                len,
+                bytes.len()
+            );
+        }
+    }
+
+    #[test]
+    fn corrupt_input_does_not_panic() {
+        let bytes = to_bytes(&scene()).unwrap();
+        // A flipped byte may still decode into another valid scene, it just can't panic
+        for index in 0..bytes.len() {
+            let mut corrupt = bytes.clone();
+            corrupt[index] ^= 0xFF;
+            let _ = from_bytes::<SceneData>(&corrupt);
+        }
+
+        let mut unknown_tag = bytes.clone();
+        unknown_tag[0] = 0xFF;
+        assert!(from_bytes::<SceneData>(&unknown_tag).is_err());
+
+        let mut trailing = bytes.clone();
+        trailing.push(NONE);
+        assert!(from_bytes::<SceneData>(&trailing).is_err());
+
+        let mut huge_length = bytes;
+        huge_length[1..5].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(from_bytes::<SceneData>(&huge_length).is_err());
+    }
+
+    #[test]
+    fn deep_nesting_is_an_error() {
+        let bytes = vec![SOME; 100_000];
+        assert!(from_bytes::<IgnoredAny>(&bytes).is_err());
+        assert!(from_bytes::<Option<u32>>(&bytes).is_err());
+    }
+}
@@ -0,0 +1,75 @@
+use bevy::ecs::resource::Resource;
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+
+/// What the editor is allowed to do. Everything is allowed by default
+/// Games embedding the editor for modding/UGC can expose a limited safe subset, i.e.
+/// app.insert_resource(EditorCapabilities { allow_file_ops: false, ..EditorCapabilities::restricted() })
+/// Checked by the request handlers listed on each field, custom systems writing files or entities need their own check
+#[derive(Resource, Clone, Debug, PartialEq)]
+pub struct EditorCapabilities {
+    /// Writing scene files, autosaves, trash files and the project resources file
+    pub allow_save: bool,
+    /// Despawning entities from the editor, deleting material files, emptying the trash and purging unused assets
+    pub allow_delete: bool,
+    /// Edits from the entity panel: transform, class data, materials and registered components
+    /// Also reparenting, flattening, duplicating and undo/redo
+    pub allow_component_edit: bool,
+    /// Moving assets, packaging scene dependencies and writing cubemap and imposter captures
+    pub allow_file_ops: bool,
+}
+
+impl Default for EditorCapabilities {
+    fn default() -> Self {
+        Self {
+            allow_save: true,
+            allow_delete: true,
+            allow_component_edit: true,
+            allow_file_ops: true,
+        }
+    }
+}
+
+impl EditorCapabilities {
+    /// Nothing destructive allowed, view and select only
+    pub fn restricted() -> Self {
+        Self {
+            allow_save: false,
+            allow_delete: false,
+            allow_component_edit: false,
+            allow_file_ops: false,
+        }
+    }
+
+    pub fn can_save(&self, target: &str) -> bool {
+        Self::check(self.allow_save, "save", target)
+    }
+
+    pub fn can_delete(&self, target: &str) -> bool {
+        Self::check(self.allow_delete, "delete", target)
+    }
+
+    pub fn can_edit_components(&self, target: &str) -> bool {
+        Self::check(self.allow_component_edit, "edit", target)
+    }
+
+    pub fn can_use_files(&self, target: &str) -> bool {
+        Self::check(self.allow_file_ops, "modify files for", target)
+    }
+
+    fn check(allowed: bool, action: &str, target: &str) -> bool {
+        if !allowed {
+            log!(
+                LogType::Editor,
+                LogLevel::Warning,
+                LogCategory::System,
+                "Refused to {} '{}': not allowed by editor capabilities",
+                action,
+                target
+            );
+        }
+        allowed
+    }
+}
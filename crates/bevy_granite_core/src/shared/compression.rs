@@ -0,0 +1,239 @@
+use super::{absolute_asset_to_rel, binary};
+use crate::entities::SceneData;
+use bevy::{platform::collections::HashMap, prelude::Resource, reflect::Reflect};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use ruzstd::{
+    decoding::StreamingDecoder,
+    encoding::{compress_to_vec, CompressionLevel},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    io::{self, Read, Write},
+    path::Path,
+};
+
+// compression.rs
+// Optional gzip or zstd compression of scene files. Plain RON stays the default, it diffs and merges in version control
+// Binary skips RON entirely for large scenes: SceneData in the format of binary.rs, then zstd
+// Loading doesn't depend on the name: compressed scenes are recognized by their header bytes
+// Saving picks the compression from the extension (.scene.gz, .scene.zst), then the per scene setting, then the default
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+const BINARY_MAGIC: [u8; 4] = *b"GSB1";
+
+/// Extensions for file dialog filters, compressed scenes end in .scene.gz, .scene.zst or .scene.bin
+/// Prefabs are scenes too, opening one edits it
+pub const SCENE_FILE_EXTENSIONS: [&str; 5] = ["scene", "gz", "zst", "bin", "prefab"];
+
+/// How a scene file is written. None keeps plain RON so scenes diff in version control
+/// Loading detects the compression by the file header, whatever the file is named
+#[derive(Reflect, Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum SceneCompression {
+    #[default]
+    None,
+    Gzip,
+    Zstd,
+    /// Not text at all, parses and writes much faster than RON but doesn't diff
+    Binary,
+}
+
+impl SceneCompression {
+    pub const ALL: [Self; 4] = [Self::None, Self::Gzip, Self::Zstd, Self::Binary];
+
+    /// Compression forced by the file name, None for plain .scene paths
+    pub fn from_extension(path: &str) -> Option<Self> {
+        let path = path.to_lowercase();
+        if path.ends_with(".gz") {
+            Some(Self::Gzip)
+        } else if path.ends_with(".zst") {
+            Some(Self::Zstd)
+        } else if path.ends_with(".bin") {
+            Some(Self::Binary)
+        } else {
+            None
+        }
+    }
+
+    /// Compression of existing file contents, from their header bytes
+    pub fn detect(bytes: &[u8]) -> Self {
+        if bytes.starts_with(&BINARY_MAGIC) {
+            Self::Binary
+        } else if bytes.starts_with(&GZIP_MAGIC) {
+            Self::Gzip
+        } else if bytes.starts_with(&ZSTD_MAGIC) {
+            Self::Zstd
+        } else {
+            Self::None
+        }
+    }
+
+    pub fn compress(self, contents: &str) -> io::Result<Vec<u8>> {
+        match self {
+            Self::None => Ok(contents.as_bytes().to_vec()),
+            Self::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(contents.as_bytes())?;
+                encoder.finish()
+            }
+            Self::Zstd => Ok(compress_to_vec(
+                contents.as_bytes(),
+                CompressionLevel::Fastest,
+            )),
+            Self::Binary => {
+                let scene = ron::de::from_str::<SceneData>(contents)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                encode_binary_scene(&scene)
+            }
+        }
+    }
+
+    /// Binary scenes come back as RON text, for callers that only handle text. Loading reads them with read_binary_scene
+    pub fn decompress(self, bytes: &[u8]) -> io::Result<Vec<u8>> {
+        let mut decompressed = Vec::new();
+        match self {
+            Self::None => decompressed.extend_from_slice(bytes),
+            Self::Binary => {
+                let scene = decode_binary_scene(bytes)?;
+                let contents = ron::ser::to_string_pretty(&scene, Default::default())
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                decompressed.extend_from_slice(contents.as_bytes());
+            }
+            Self::Gzip => {
+                GzDecoder::new(bytes).read_to_end(&mut decompressed)?;
+            }
+            Self::Zstd => decompressed = zstd_decompress(bytes)?,
+        }
+        Ok(decompressed)
+    }
+}
+
+fn zstd_decompress(bytes: &[u8]) -> io::Result<Vec<u8>> {
+    let mut decompressed = Vec::new();
+    let mut source = bytes;
+    StreamingDecoder::new(&mut source)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?
+        .read_to_end(&mut decompressed)?;
+    Ok(decompressed)
+}
+
+/// Binary scene file contents: header, then the zstd compressed encoding
+pub fn encode_binary_scene(scene: &SceneData) -> io::Result<Vec<u8>> {
+    let encoded =
+        binary::to_bytes(scene).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    let mut bytes = BINARY_MAGIC.to_vec();
+    bytes.extend(compress_to_vec(
+        encoded.as_slice(),
+        CompressionLevel::Fastest,
+    ));
+    Ok(bytes)
+}
+
+pub fn decode_binary_scene(bytes: &[u8]) -> io::Result<SceneData> {
+    let Some(compressed) = bytes.strip_prefix(&BINARY_MAGIC) else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "not a binary scene",
+        ));
+    };
+    binary::from_bytes(&zstd_decompress(compressed)?)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// How scenes are compressed when saved. Files named .scene.gz, .scene.zst or .scene.bin always use their own compression
+#[derive(Resource, Debug, Clone, Default)]
+pub struct SceneCompressionSettings {
+    pub default: SceneCompression,
+    /// Per scene overrides, keyed by path relative to assets
+    pub scenes: HashMap<String, SceneCompression>,
+}
+
+impl SceneCompressionSettings {
+    pub fn for_scene(&self, path: &str) -> SceneCompression {
+        SceneCompression::from_extension(path)
+            .or_else(|| {
+                self.scenes
+                    .get(absolute_asset_to_rel(path.to_string()).as_ref())
+                    .copied()
+            })
+            .unwrap_or(self.default)
+    }
+
+    pub fn set(&mut self, scene: &str, compression: SceneCompression) {
+        self.scenes.insert(
+            absolute_asset_to_rel(scene.to_string()).to_string(),
+            compression,
+        );
+    }
+}
+
+/// Scene files, compressed or not, and prefabs
+pub fn is_scene_file(path: &str) -> bool {
+    let path = path.to_lowercase();
+    [".scene", ".scene.gz", ".scene.zst", ".scene.bin", ".prefab"]
+        .iter()
+        .any(|extension| path.ends_with(extension))
+}
+
+/// Path without its .scene, .scene.gz, .scene.zst, .scene.bin or .prefab extension
+pub fn strip_scene_extension(path: &str) -> &str {
+    [".scene.gz", ".scene.zst", ".scene.bin", ".scene", ".prefab"]
+        .iter()
+        .find_map(|extension| path.strip_suffix(extension))
+        .unwrap_or(path)
+}
+
+/// Scene file contents, decompressed when the header says so
+pub fn read_scene_file(path: &str) -> io::Result<String> {
+    read_scene_file_compressed(path).map(|(contents, _)| contents)
+}
+
+/// Scene file contents and the compression they were stored with
+pub fn read_scene_file_compressed(path: &str) -> io::Result<(String, SceneCompression)> {
+    let bytes = fs::read(path)?;
+    let compression = SceneCompression::detect(&bytes);
+    let contents = String::from_utf8(compression.decompress(&bytes)?)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    Ok((contents, compression))
+}
+
+/// Scene data of a binary scene file, without going through RON. None when the file isn't binary
+pub fn read_binary_scene(path: &str) -> io::Result<Option<SceneData>> {
+    let mut header = [0; 4];
+    let mut file = fs::File::open(path)?;
+    if file.read_exact(&mut header).is_err() || header != BINARY_MAGIC {
+        return Ok(None);
+    }
+    decode_binary_scene(&fs::read(path)?).map(Some)
+}
+
+/// Write scene data with the given compression, binary scenes skip RON
+pub fn write_scene_data(
+    path: &str,
+    scene: &SceneData,
+    pretty: ron::ser::PrettyConfig,
+    compression: SceneCompression,
+) -> io::Result<()> {
+    if compression != SceneCompression::Binary {
+        let contents = ron::ser::to_string_pretty(scene, pretty)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        return write_scene_contents(path, &contents, compression);
+    }
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, encode_binary_scene(scene)?)
+}
+
+/// Write scene contents with the given compression, creating parent folders
+pub fn write_scene_contents(
+    path: &str,
+    contents: &str,
+    compression: SceneCompression,
+) -> io::Result<()> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, compression.compress(contents)?)
+}
@@ -0,0 +1,450 @@
+use bevy::{
+    asset::io::file::FileAssetReader,
+    ecs::{
+        message::{Message, MessageWriter},
+        resource::Resource,
+        system::ResMut,
+    },
+    platform::collections::HashMap,
+    tasks::{futures::check_ready, AsyncComputeTaskPool, Task},
+};
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+use std::{
+    collections::VecDeque,
+    fs,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, AtomicU32, Ordering},
+        Arc, Mutex,
+    },
+};
+
+// jobs.rs
+// Background jobs shared by every subsystem that needs heavy work off the main thread,
+// i.e. thumbnails, bakes and texture compression. Jobs run on the async compute pool,
+// report progress, can be cancelled, and can keep their result on disk under a cache key
+
+/// Folder under the project root where cached job results are written
+const JOB_CACHE_DIR: &str = ".granite/job_cache";
+/// Finished jobs kept around for the status bar
+const FINISHED_HISTORY: usize = 16;
+
+pub type JobId = u64;
+pub type JobResult = Result<Arc<Vec<u8>>, String>;
+type JobWork = Box<dyn FnOnce(&JobContext) -> Result<Vec<u8>, String> + Send + Sync + 'static>;
+
+/// Handed to a running job to report progress and check for cancellation
+/// Long jobs should check `is_cancelled` between steps and return early when it is set
+#[derive(Clone, Default)]
+pub struct JobContext {
+    progress: Arc<AtomicU32>,
+    cancelled: Arc<AtomicBool>,
+    message: Arc<Mutex<String>>,
+}
+
+impl JobContext {
+    /// 0 to 1
+    pub fn set_progress(&self, progress: f32) {
+        self.progress
+            .store(progress.clamp(0.0, 1.0).to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn progress(&self) -> f32 {
+        f32::from_bits(self.progress.load(Ordering::Relaxed))
+    }
+
+    /// Short note of what the job is doing, shown next to its progress
+    pub fn set_message(&self, message: impl Into<String>) {
+        if let Ok(mut current) = self.message.lock() {
+            *current = message.into();
+        }
+    }
+
+    pub fn message(&self) -> String {
+        self.message
+            .lock()
+            .map(|message| message.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Done,
+    /// Result came from the cache, the work never ran
+    Cached,
+    Failed(String),
+    Cancelled,
+}
+
+impl JobStatus {
+    pub fn is_finished(&self) -> bool {
+        !matches!(self, JobStatus::Queued | JobStatus::Running)
+    }
+}
+
+/// What the status bar shows for a job
+#[derive(Debug, Clone)]
+pub struct JobInfo {
+    pub id: JobId,
+    /// Subsystem the job belongs to, i.e. "thumbnail" or "cubemap"
+    pub kind: String,
+    pub label: String,
+    pub progress: f32,
+    pub message: String,
+    pub status: JobStatus,
+}
+
+/// Sent once per job when it is done, failed or cancelled
+#[derive(Message, Debug, Clone)]
+pub struct JobFinishedEvent {
+    pub id: JobId,
+    pub kind: String,
+    pub cache_key: Option<String>,
+    /// Bytes the job returned, cancelled jobs report an error
+    pub result: JobResult,
+}
+
+struct QueuedJob {
+    info: JobInfo,
+    cache_key: Option<String>,
+    context: JobContext,
+    work: JobWork,
+}
+
+struct RunningJob {
+    info: JobInfo,
+    cache_key: Option<String>,
+    context: JobContext,
+    task: Task<Result<Vec<u8>, String>>,
+}
+
+/// Queue of background jobs. Subsystems `spawn` work and read `JobFinishedEvent` for the result
+#[derive(Resource)]
+pub struct GraniteJobs {
+    /// Jobs running at the same time, the rest wait in the queue
+    pub max_concurrent: usize,
+    next_id: JobId,
+    queued: VecDeque<QueuedJob>,
+    running: Vec<RunningJob>,
+    finished: VecDeque<JobInfo>,
+    cache: JobResultCache,
+    /// Jobs that finished without running, reported on the next update
+    completed: Vec<JobFinishedEvent>,
+}
+
+impl Default for GraniteJobs {
+    fn default() -> Self {
+        Self {
+            max_concurrent: 2,
+            next_id: 0,
+            queued: VecDeque::new(),
+            running: Vec::new(),
+            finished: VecDeque::new(),
+            cache: JobResultCache::default(),
+            completed: Vec::new(),
+        }
+    }
+}
+
+impl GraniteJobs {
+    /// Queue work to run in the background
+    pub fn spawn(
+        &mut self,
+        kind: impl Into<String>,
+        label: impl Into<String>,
+        work: impl FnOnce(&JobContext) -> Result<Vec<u8>, String> + Send + Sync + 'static,
+    ) -> JobId {
+        self.queue(kind.into(), label.into(), None, Box::new(work))
+    }
+
+    /// Like `spawn`, but a result already cached under the key is reused instead of running the work
+    /// The key should change whenever the inputs do, i.e. include a source path and its modified time
+    pub fn spawn_cached(
+        &mut self,
+        kind: impl Into<String>,
+        label: impl Into<String>,
+        cache_key: impl Into<String>,
+        work: impl FnOnce(&JobContext) -> Result<Vec<u8>, String> + Send + Sync + 'static,
+    ) -> JobId {
+        let cache_key = cache_key.into();
+        let (kind, label) = (kind.into(), label.into());
+        let Some(bytes) = self.cache.get(&cache_key) else {
+            return self.queue(kind, label, Some(cache_key), Box::new(work));
+        };
+
+        let id = self.next_id();
+        self.completed.push(JobFinishedEvent {
+            id,
+            kind: kind.clone(),
+            cache_key: Some(cache_key),
+            result: Ok(bytes),
+        });
+        self.push_finished(JobInfo {
+            id,
+            kind,
+            label,
+            progress: 1.0,
+            message: String::new(),
+            status: JobStatus::Cached,
+        });
+        id
+    }
+
+    /// Queued jobs are dropped right away, running ones stop at their next cancellation check
+    pub fn cancel(&mut self, id: JobId) {
+        if let Some(job) = self.running.iter().find(|job| job.info.id == id) {
+            job.context.cancelled.store(true, Ordering::Relaxed);
+            return;
+        }
+        if let Some(index) = self.queued.iter().position(|job| job.info.id == id) {
+            if let Some(job) = self.queued.remove(index) {
+                self.cancelled(job.info, job.cache_key);
+            }
+        }
+    }
+
+    /// Cancel every job of a kind, i.e. when the subsystem that started them goes away
+    pub fn cancel_kind(&mut self, kind: &str) {
+        let ids: Vec<JobId> = self
+            .active()
+            .filter(|job| job.kind == kind)
+            .map(|job| job.id)
+            .collect();
+        for id in ids {
+            self.cancel(id);
+        }
+    }
+
+    /// Running then queued jobs, progress as of the last update
+    pub fn active(&self) -> impl Iterator<Item = &JobInfo> + '_ {
+        self.running
+            .iter()
+            .map(|job| &job.info)
+            .chain(self.queued.iter().map(|job| &job.info))
+    }
+
+    /// Most recently finished jobs, newest first
+    pub fn finished(&self) -> impl Iterator<Item = &JobInfo> + '_ {
+        self.finished.iter()
+    }
+
+    pub fn clear_finished(&mut self) {
+        self.finished.clear();
+    }
+
+    pub fn is_busy(&self) -> bool {
+        !self.running.is_empty() || !self.queued.is_empty()
+    }
+
+    pub fn cache(&self) -> &JobResultCache {
+        &self.cache
+    }
+
+    pub fn cache_mut(&mut self) -> &mut JobResultCache {
+        &mut self.cache
+    }
+
+    fn next_id(&mut self) -> JobId {
+        self.next_id += 1;
+        self.next_id
+    }
+
+    fn queue(
+        &mut self,
+        kind: String,
+        label: String,
+        cache_key: Option<String>,
+        work: JobWork,
+    ) -> JobId {
+        let id = self.next_id();
+        self.queued.push_back(QueuedJob {
+            info: JobInfo {
+                id,
+                kind,
+                label,
+                progress: 0.0,
+                message: String::new(),
+                status: JobStatus::Queued,
+            },
+            cache_key,
+            context: JobContext::default(),
+            work,
+        });
+        id
+    }
+
+    fn cancelled(&mut self, mut info: JobInfo, cache_key: Option<String>) {
+        info.status = JobStatus::Cancelled;
+        self.completed.push(JobFinishedEvent {
+            id: info.id,
+            kind: info.kind.clone(),
+            cache_key,
+            result: Err("Cancelled".to_string()),
+        });
+        self.push_finished(info);
+    }
+
+    fn push_finished(&mut self, info: JobInfo) {
+        self.finished.push_front(info);
+        self.finished.truncate(FINISHED_HISTORY);
+    }
+}
+
+/// Job results kept in memory and written to disk, so they survive restarts
+/// Files live in `.granite/job_cache` under the project root, named after their key
+#[derive(Default)]
+pub struct JobResultCache {
+    loaded: HashMap<String, Arc<Vec<u8>>>,
+}
+
+impl JobResultCache {
+    pub fn get(&mut self, key: &str) -> Option<Arc<Vec<u8>>> {
+        if let Some(bytes) = self.loaded.get(key) {
+            return Some(bytes.clone());
+        }
+        let bytes = Arc::new(fs::read(Self::path_for(key)).ok()?);
+        self.loaded.insert(key.to_string(), bytes.clone());
+        Some(bytes)
+    }
+
+    pub fn insert(&mut self, key: &str, bytes: Arc<Vec<u8>>) {
+        let path = Self::path_for(key);
+        let written = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(&path, bytes.as_slice()));
+        if let Err(e) = written {
+            log!(
+                LogType::Game,
+                LogLevel::Warning,
+                LogCategory::System,
+                "Failed to write job cache '{}': {}",
+                path.display(),
+                e
+            );
+        }
+        self.loaded.insert(key.to_string(), bytes);
+    }
+
+    pub fn remove(&mut self, key: &str) {
+        self.loaded.remove(key);
+        let _ = fs::remove_file(Self::path_for(key));
+    }
+
+    /// Drop every cached result, in memory and on disk
+    pub fn clear(&mut self) {
+        self.loaded.clear();
+        let _ = fs::remove_dir_all(FileAssetReader::get_base_path().join(JOB_CACHE_DIR));
+    }
+
+    fn path_for(key: &str) -> PathBuf {
+        let file_name: String = key
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '.' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        FileAssetReader::get_base_path()
+            .join(JOB_CACHE_DIR)
+            .join(format!("{}.bin", file_name))
+    }
+}
+
+/// Starts queued jobs on the async compute pool, polls running ones and reports the finished
+pub fn update_jobs_system(
+    mut jobs: ResMut<GraniteJobs>,
+    mut writer: MessageWriter<JobFinishedEvent>,
+) {
+    let jobs = &mut *jobs;
+
+    while jobs.running.len() < jobs.max_concurrent.max(1) {
+        let Some(QueuedJob {
+            mut info,
+            cache_key,
+            context,
+            work,
+        }) = jobs.queued.pop_front()
+        else {
+            break;
+        };
+        info.status = JobStatus::Running;
+        let task_context = context.clone();
+        let task = AsyncComputeTaskPool::get().spawn(async move { work(&task_context) });
+        jobs.running.push(RunningJob {
+            info,
+            cache_key,
+            context,
+            task,
+        });
+    }
+
+    let mut index = 0;
+    while index < jobs.running.len() {
+        let job = &mut jobs.running[index];
+        job.info.progress = job.context.progress();
+        job.info.message = job.context.message();
+        let Some(result) = check_ready(&mut job.task) else {
+            index += 1;
+            continue;
+        };
+
+        let RunningJob {
+            mut info,
+            cache_key,
+            context,
+            ..
+        } = jobs.running.swap_remove(index);
+        let result = if context.is_cancelled() {
+            Err("Cancelled".to_string())
+        } else {
+            result.map(Arc::new)
+        };
+        info.status = match &result {
+            _ if context.is_cancelled() => JobStatus::Cancelled,
+            Ok(_) => JobStatus::Done,
+            Err(e) => JobStatus::Failed(e.clone()),
+        };
+        if info.status == JobStatus::Done {
+            info.progress = 1.0;
+        }
+        if let (Some(key), Ok(bytes)) = (&cache_key, &result) {
+            jobs.cache.insert(key, bytes.clone());
+        }
+        if let JobStatus::Failed(e) = &info.status {
+            log!(
+                LogType::Game,
+                LogLevel::Error,
+                LogCategory::System,
+                "Job '{}' failed: {}",
+                info.label,
+                e
+            );
+        }
+
+        jobs.completed.push(JobFinishedEvent {
+            id: info.id,
+            kind: info.kind.clone(),
+            cache_key,
+            result,
+        });
+        jobs.push_finished(info);
+    }
+
+    for finished in jobs.completed.drain(..) {
+        writer.write(finished);
+    }
+}
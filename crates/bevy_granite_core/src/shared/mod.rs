@@ -1,13 +1,27 @@
+pub mod binary;
+pub mod capabilities;
+pub mod compression;
 pub mod file;
 pub mod file_browser;
 pub mod icon;
+pub mod jobs;
 pub mod plugin;
 pub mod user_input;
 pub mod version;
 
+pub use capabilities::EditorCapabilities;
+pub use compression::{
+    is_scene_file, read_binary_scene, read_scene_file, read_scene_file_compressed,
+    strip_scene_extension, write_scene_contents, write_scene_data, SceneCompression,
+    SceneCompressionSettings, SCENE_FILE_EXTENSIONS,
+};
 pub use file::*;
 pub use file_browser::{asset_file_browser, asset_file_browser_multiple};
 pub use icon::{IconEntity, IconProxy, IconType};
+pub use jobs::{
+    update_jobs_system, GraniteJobs, JobContext, JobFinishedEvent, JobId, JobInfo, JobResult,
+    JobResultCache, JobStatus,
+};
 pub use plugin::SharedPlugin;
 pub use user_input::{
     capture_input_events, mouse_to_world_delta, update_mouse_pos, CursorWindowPos, InputTypes,
@@ -1,4 +1,7 @@
-use super::{capture_input_events, update_mouse_pos, CursorWindowPos, UserInput};
+use super::{
+    capture_input_events, update_jobs_system, update_mouse_pos, CursorWindowPos,
+    EditorCapabilities, GraniteJobs, JobFinishedEvent, SceneCompressionSettings, UserInput,
+};
 use bevy::app::{Plugin, PreUpdate};
 use bevy::prelude::{App, Update};
 
@@ -11,10 +14,17 @@ impl Plugin for SharedPlugin {
             //
             .insert_resource(UserInput::default())
             .insert_resource(CursorWindowPos::default())
+            .init_resource::<EditorCapabilities>()
+            .init_resource::<GraniteJobs>()
+            .init_resource::<SceneCompressionSettings>()
+            //
+            // Messages
+            //
+            .add_message::<JobFinishedEvent>()
             //
             // Schedule systems
             //
             .add_systems(PreUpdate, capture_input_events)
-            .add_systems(Update, update_mouse_pos);
+            .add_systems(Update, (update_mouse_pos, update_jobs_system));
     }
 }
@@ -39,8 +39,10 @@ pub struct UserInput {
     pub key_r: UserButtonState,
     pub key_f: UserButtonState,
     pub key_p: UserButtonState,
+    pub key_x: UserButtonState,
     pub key_delete: UserButtonState,
     pub key_space: UserButtonState,
+    pub key_tab: UserButtonState,
 }
 
 #[derive(PartialEq, Default, Clone, Copy, Debug)]
@@ -159,7 +161,9 @@ pub fn capture_input_events(
     let mut key_q = user_input.key_q;
     let mut key_z = user_input.key_z;
     let mut key_f = user_input.key_f;
+    let mut key_x = user_input.key_x;
     let mut key_space = user_input.key_space;
+    let mut key_tab = user_input.key_tab;
 
     // Update state
     mouse_left.update_mouse(&mouse_input, MouseButton::Left, &mut user_input);
@@ -187,7 +191,9 @@ pub fn capture_input_events(
     key_f3.update_key(&keyboard_input, KeyCode::F3, &mut user_input);
     key_q.update_key(&keyboard_input, KeyCode::KeyQ, &mut user_input);
     key_f.update_key(&keyboard_input, KeyCode::KeyF, &mut user_input);
+    key_x.update_key(&keyboard_input, KeyCode::KeyX, &mut user_input);
     key_space.update_key(&keyboard_input, KeyCode::Space, &mut user_input);
+    key_tab.update_key(&keyboard_input, KeyCode::Tab, &mut user_input);
 
     // Write the updated states back
     user_input.key_delete = key_delete;
@@ -212,7 +218,9 @@ pub fn capture_input_events(
     user_input.key_d = key_d;
     user_input.key_q = key_q;
     user_input.key_z = key_z;
+    user_input.key_x = key_x;
     user_input.key_space = key_space;
+    user_input.key_tab = key_tab;
     user_input.alt_left = alt_left;
 
     if let Ok(ctx) = contexts.ctx_mut() {
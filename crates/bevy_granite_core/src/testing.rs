@@ -0,0 +1,387 @@
+use crate::{
+    absolute_asset_to_rel, entities::SaveSettings, read_scene_file, world::SnapshotEntity,
+    BevyGraniteCore, GraniteSnapshot, GraniteType, IdentityData, RequestDespawnBySource,
+    RequestLoadEvent, RequestSaveEvent, SpawnSource, WorldLoadSuccessEvent, WorldSaveSuccessEvent,
+};
+use bevy::{
+    app::{App, PluginGroup, PluginGroupBuilder, Plugins, PluginsState, TaskPoolPlugin},
+    asset::AssetPlugin,
+    camera::CameraPlugin,
+    core_pipeline::CorePipelinePlugin,
+    diagnostic::{DiagnosticsPlugin, FrameCountPlugin},
+    ecs::{
+        component::Component,
+        entity::Entity,
+        message::{Message, Messages},
+        world::World,
+    },
+    image::ImagePlugin,
+    input::InputPlugin,
+    light::LightPlugin,
+    mesh::MeshPlugin,
+    pbr::PbrPlugin,
+    post_process::PostProcessPlugin,
+    prelude::{default, Transform},
+    render::{
+        settings::{RenderCreation, WgpuSettings},
+        RenderPlugin,
+    },
+    time::TimePlugin,
+    transform::TransformPlugin,
+    window::{ExitCondition, WindowPlugin},
+};
+use std::{collections::HashMap, fs, path::PathBuf};
+use uuid::Uuid;
+
+// testing.rs
+// Test utilities for games writing integration tests against their Granite scenes, behind the `testing` feature
+// TestHarness is a headless App with Granite core that needs no window or GPU, driven frame by frame from the test
+// Scenes load from asset paths or straight from a string, and every helper panics with a readable message on failure
+
+/// Frames a load or save may take before the harness gives up
+const STEP_TIMEOUT_FRAMES: u32 = 600;
+/// Saved transforms are rounded to 3 decimals
+const TRANSFORM_TOLERANCE: f32 = 0.002;
+
+/// The DefaultPlugins Granite core needs, without winit or a window, and with no GPU backend
+/// Listed by hand so games that enable `bevy_winit` can still run the harness off the main thread
+pub struct HeadlessPlugins;
+
+impl PluginGroup for HeadlessPlugins {
+    fn build(self) -> PluginGroupBuilder {
+        PluginGroupBuilder::start::<Self>()
+            .add(TaskPoolPlugin::default())
+            .add(FrameCountPlugin)
+            .add(TimePlugin)
+            .add(TransformPlugin)
+            .add(DiagnosticsPlugin)
+            .add(InputPlugin)
+            .add(WindowPlugin {
+                primary_window: None,
+                exit_condition: ExitCondition::DontExit,
+                ..default()
+            })
+            .add(AssetPlugin::default())
+            .add(RenderPlugin {
+                render_creation: RenderCreation::Automatic(WgpuSettings {
+                    backends: None,
+                    ..default()
+                }),
+                ..default()
+            })
+            .add(ImagePlugin::default())
+            .add(MeshPlugin)
+            .add(CameraPlugin)
+            .add(LightPlugin)
+            .add(CorePipelinePlugin)
+            .add(PostProcessPlugin)
+            .add(PbrPlugin::default())
+    }
+}
+
+/// Headless App with Granite core for integration tests, no window or GPU needed
+/// Loads scenes by path or from a string, saves them, and asserts entities, components and round trips
+/// Game plugins go in with `with_plugins`
+pub struct TestHarness {
+    pub app: App,
+    ready: bool,
+    /// Scenes written by `load_scene_str`, deleted when the harness drops
+    temp_scenes: Vec<PathBuf>,
+}
+
+impl Default for TestHarness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TestHarness {
+    /// Headless App with HeadlessPlugins and Granite core. Meshes and materials still load as assets
+    /// Winit is left out, tests run off the main thread and often without a display
+    pub fn new() -> Self {
+        let mut app = App::new();
+        app.add_plugins(HeadlessPlugins)
+            .add_plugins(BevyGraniteCore {
+                logging: false,
+                startup_worlds: default(),
+            });
+        Self {
+            app,
+            ready: false,
+            temp_scenes: Vec::new(),
+        }
+    }
+
+    /// Add the game's own plugins, before the first update
+    pub fn with_plugins<M>(mut self, plugins: impl Plugins<M>) -> Self {
+        self.app.add_plugins(plugins);
+        self
+    }
+
+    pub fn world(&self) -> &World {
+        self.app.world()
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        self.app.world_mut()
+    }
+
+    /// Run one frame. The first call finishes plugin setup and runs Startup
+    pub fn update(&mut self) {
+        if !self.ready {
+            while self.app.plugins_state() == PluginsState::Adding {
+                bevy::tasks::tick_global_task_pools_on_main_thread();
+            }
+            self.app.finish();
+            self.app.cleanup();
+            self.ready = true;
+        }
+        self.app.update();
+    }
+
+    pub fn update_frames(&mut self, frames: u32) {
+        for _ in 0..frames {
+            self.update();
+        }
+    }
+
+    /// Load a scene by asset path and wait until its entities are spawned. Returns the scene's SpawnSource
+    pub fn load_scene(&mut self, path: &str) -> String {
+        let source = absolute_asset_to_rel(path.to_string()).to_string();
+        let found = self.send_and_wait(
+            RequestLoadEvent(path.to_string(), SaveSettings::Runtime, None),
+            |WorldLoadSuccessEvent(loaded)| absolute_asset_to_rel(loaded.clone()) == source,
+        );
+        if !found {
+            panic!(
+                "Scene '{}' did not load within {} frames",
+                path, STEP_TIMEOUT_FRAMES
+            );
+        }
+        // Components are inserted by commands queued during the load
+        self.update();
+        source
+    }
+
+    /// Load a scene from its file contents, through a temporary scene file. Returns the scene's SpawnSource
+    pub fn load_scene_str(&mut self, scene: &str) -> String {
+        let folder = std::env::temp_dir().join("granite_tests");
+        let path = folder.join(format!("{}.scene", Uuid::new_v4()));
+        if let Err(e) = fs::create_dir_all(&folder).and_then(|_| fs::write(&path, scene)) {
+            panic!(
+                "Failed to write temporary scene '{}': {}",
+                path.display(),
+                e
+            );
+        }
+        let path = path.canonicalize().unwrap_or(path);
+        self.temp_scenes.push(path.clone());
+        self.load_scene(&path.display().to_string())
+    }
+
+    /// Save every entity of a source and return the written file contents, decompressed
+    pub fn save_scene(&mut self, source: &str) -> String {
+        let mut written = None;
+        let found = self.send_and_wait(
+            RequestSaveEvent(source.to_string()),
+            |WorldSaveSuccessEvent(saved)| {
+                let matches = absolute_asset_to_rel(saved.clone()) == source;
+                if matches {
+                    written = Some(saved.clone());
+                }
+                matches
+            },
+        );
+        let (true, Some(path)) = (found, written) else {
+            panic!(
+                "Scene '{}' did not save within {} frames",
+                source, STEP_TIMEOUT_FRAMES
+            );
+        };
+        read_scene_file(&path)
+            .unwrap_or_else(|e| panic!("Failed to read saved scene '{}': {}", path, e))
+    }
+
+    /// Despawn every entity of a source
+    pub fn despawn_scene(&mut self, source: &str) {
+        self.world_mut()
+            .write_message(RequestDespawnBySource::new(source.to_string()));
+        self.update_frames(2);
+    }
+
+    pub fn entities_from(&mut self, source: &str) -> Vec<Entity> {
+        let mut query = self.world_mut().query::<(Entity, &SpawnSource)>();
+        query
+            .iter(self.app.world())
+            .filter(|(_, spawn_source)| spawn_source.str_ref() == source)
+            .map(|(entity, _)| entity)
+            .collect()
+    }
+
+    /// First entity with this name, from any scene
+    pub fn find_entity(&mut self, name: &str) -> Option<Entity> {
+        let mut query = self.world_mut().query::<(Entity, &IdentityData)>();
+        query
+            .iter(self.app.world())
+            .find(|(_, identity)| identity.name == name)
+            .map(|(entity, _)| entity)
+    }
+
+    pub fn assert_entity(&mut self, name: &str) -> Entity {
+        self.find_entity(name)
+            .unwrap_or_else(|| panic!("No entity named '{}'", name))
+    }
+
+    pub fn assert_no_entity(&mut self, name: &str) {
+        if let Some(entity) = self.find_entity(name) {
+            panic!("Entity named '{}' exists as {}", name, entity);
+        }
+    }
+
+    /// Entity with this name that has component C
+    pub fn assert_component<C: Component>(&mut self, name: &str) -> Entity {
+        let entity = self.assert_entity(name);
+        if !self.world().entity(entity).contains::<C>() {
+            panic!(
+                "Entity '{}' has no {} component",
+                name,
+                std::any::type_name::<C>()
+            );
+        }
+        entity
+    }
+
+    pub fn assert_entity_count(&mut self, source: &str, expected: usize) {
+        let count = self.entities_from(source).len();
+        if count != expected {
+            panic!(
+                "Scene '{}' has {} entities, expected {}",
+                source, count, expected
+            );
+        }
+    }
+
+    /// Save a source, despawn it and load it back. Returns every difference between before and after
+    pub fn round_trip(&mut self, source: &str) -> Vec<String> {
+        let before = self.capture_source(source);
+        self.save_scene(source);
+        self.despawn_scene(source);
+        self.load_scene(source);
+        let after = self.capture_source(source);
+        compare_entities(&before, &after)
+    }
+
+    pub fn assert_round_trip(&mut self, source: &str) {
+        let differences = self.round_trip(source);
+        if !differences.is_empty() {
+            panic!(
+                "Scene '{}' changed in a save and reload:\n{}",
+                source,
+                differences.join("\n")
+            );
+        }
+    }
+
+    fn capture_source(&mut self, source: &str) -> HashMap<Uuid, SnapshotEntity> {
+        GraniteSnapshot::capture_quiet(self.world_mut())
+            .entities
+            .into_iter()
+            .filter(|entity| {
+                entity
+                    .source
+                    .as_ref()
+                    .is_some_and(|spawn_source| spawn_source.str_ref() == source)
+            })
+            .map(|entity| (entity.data.identity.uuid, entity))
+            .collect()
+    }
+
+    /// Send a request and run frames until a message matching `done` shows up
+    fn send_and_wait<R: Message, D: Message>(
+        &mut self,
+        request: R,
+        mut done: impl FnMut(&D) -> bool,
+    ) -> bool {
+        if !self.ready {
+            self.update();
+        }
+        let mut cursor = self.world().resource::<Messages<D>>().get_cursor_current();
+        self.world_mut().write_message(request);
+        for _ in 0..STEP_TIMEOUT_FRAMES {
+            self.update();
+            let messages = self.world().resource::<Messages<D>>();
+            if cursor.read(messages).any(&mut done) {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+impl Drop for TestHarness {
+    fn drop(&mut self) {
+        for path in self.temp_scenes.drain(..) {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+fn compare_entities(
+    before: &HashMap<Uuid, SnapshotEntity>,
+    after: &HashMap<Uuid, SnapshotEntity>,
+) -> Vec<String> {
+    let mut differences = Vec::new();
+    for (uuid, old) in before.iter() {
+        let name = &old.data.identity.name;
+        let Some(new) = after.get(uuid) else {
+            differences.push(format!("'{}' ({}) is missing", name, uuid));
+            continue;
+        };
+        if new.data.identity.name != *name {
+            differences.push(format!(
+                "'{}' was renamed to '{}'",
+                name, new.data.identity.name
+            ));
+        }
+        let (old_class, new_class) = (
+            old.data.identity.class.type_name(),
+            new.data.identity.class.type_name(),
+        );
+        if old_class != new_class {
+            differences.push(format!(
+                "'{}' changed class from {} to {}",
+                name, old_class, new_class
+            ));
+        }
+        if new.data.parent != old.data.parent {
+            differences.push(format!("'{}' changed parent", name));
+        }
+        if !transforms_match(&old.data.transform.to_bevy(), &new.data.transform.to_bevy()) {
+            differences.push(format!(
+                "'{}' moved from {:?} to {:?}",
+                name, old.data.transform, new.data.transform
+            ));
+        }
+        if new.data.components != old.data.components {
+            differences.push(format!("'{}' changed components", name));
+        }
+    }
+    for (uuid, new) in after.iter() {
+        if !before.contains_key(uuid) {
+            differences.push(format!(
+                "'{}' ({}) was not in the scene before",
+                new.data.identity.name, uuid
+            ));
+        }
+    }
+    differences
+}
+
+/// Rounded quaternions aren't normalized, so compare components, allowing q and -q
+fn transforms_match(a: &Transform, b: &Transform) -> bool {
+    a.translation
+        .abs_diff_eq(b.translation, TRANSFORM_TOLERANCE)
+        && a.scale.abs_diff_eq(b.scale, TRANSFORM_TOLERANCE)
+        && (a.rotation.abs_diff_eq(b.rotation, TRANSFORM_TOLERANCE)
+            || a.rotation.abs_diff_eq(-b.rotation, TRANSFORM_TOLERANCE))
+}
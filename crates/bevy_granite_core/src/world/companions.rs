@@ -0,0 +1,200 @@
+use crate::{
+    absolute_asset_to_rel,
+    entities::{read_scene_metadata, IdentityData, SaveSettings, SpawnSource},
+    events::{RequestDespawnBySource, RequestDespawnSerializableEntities},
+    rel_asset_to_absolute,
+};
+use bevy::{
+    ecs::message::{MessageCursor, Messages},
+    prelude::*,
+};
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+use std::collections::{HashMap, HashSet};
+
+/// Companion scenes loaded for batch loads and the scenes that still need them
+/// A companion is despawned once the last scene requiring it is despawned
+#[derive(Resource, Default, Debug, Clone)]
+pub struct SceneCompanions {
+    /// Companion source -> sources that required it
+    dependents: HashMap<String, HashSet<String>>,
+}
+
+impl SceneCompanions {
+    /// Scenes currently keeping a companion loaded
+    pub fn dependents(&self, companion: &str) -> Vec<String> {
+        self.dependents
+            .get(companion)
+            .map(|dependents| dependents.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn is_companion(&self, source: &str) -> bool {
+        self.dependents.contains_key(source)
+    }
+
+    fn add(&mut self, companion: &str, dependent: &str) {
+        self.dependents
+            .entry(companion.to_string())
+            .or_default()
+            .insert(dependent.to_string());
+    }
+
+    /// Drop a despawned scene from every companion. Returns companions nothing needs anymore
+    fn release(&mut self, dependent: &str) -> Vec<String> {
+        self.dependents.remove(dependent);
+        let mut unused = vec![];
+        self.dependents.retain(|companion, dependents| {
+            dependents.remove(dependent);
+            if dependents.is_empty() {
+                unused.push(companion.clone());
+                false
+            } else {
+                true
+            }
+        });
+        unused
+    }
+}
+
+/// Companion scenes a scene declares in its metadata, relative to assets
+pub fn scene_requires(source: &str) -> Vec<String> {
+    read_scene_metadata(&rel_asset_to_absolute(source))
+        .map(|metadata| {
+            metadata
+                .requires
+                .into_iter()
+                .map(|path| absolute_asset_to_rel(path).to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Expand a batch with the companions its scenes require, companions first so they spawn before their dependents
+/// Companions already loaded or already in the batch are not loaded again
+pub fn resolve_batch_companions(
+    worlds: &[(String, SaveSettings, Option<Transform>)],
+    loaded_sources: &HashSet<String>,
+    companions: &mut SceneCompanions,
+) -> Vec<(String, SaveSettings, Option<Transform>)> {
+    let requested: Vec<(String, SaveSettings, Option<Transform>)> = worlds
+        .iter()
+        .map(|(path, save_settings, transform)| {
+            (
+                absolute_asset_to_rel(path.to_string()).to_string(),
+                save_settings.clone(),
+                *transform,
+            )
+        })
+        .collect();
+
+    let mut queued: HashSet<String> = requested.iter().map(|(path, _, _)| path.clone()).collect();
+    let mut resolved = vec![];
+    let mut visiting = HashSet::new();
+    for (path, save_settings, _) in requested.iter() {
+        collect_companions(
+            path,
+            save_settings,
+            loaded_sources,
+            companions,
+            &mut queued,
+            &mut visiting,
+            &mut resolved,
+        );
+    }
+
+    resolved.extend(requested);
+    resolved
+}
+
+fn collect_companions(
+    source: &str,
+    save_settings: &SaveSettings,
+    loaded_sources: &HashSet<String>,
+    companions: &mut SceneCompanions,
+    queued: &mut HashSet<String>,
+    visiting: &mut HashSet<String>,
+    resolved: &mut Vec<(String, SaveSettings, Option<Transform>)>,
+) {
+    // Guard against scenes that require each other
+    if !visiting.insert(source.to_string()) {
+        return;
+    }
+
+    for companion in scene_requires(source) {
+        if companion == source {
+            continue;
+        }
+        companions.add(&companion, source);
+
+        if loaded_sources.contains(&companion) || !queued.insert(companion.clone()) {
+            continue;
+        }
+
+        // Companions of companions load before it
+        collect_companions(
+            &companion,
+            save_settings,
+            loaded_sources,
+            companions,
+            queued,
+            visiting,
+            resolved,
+        );
+        log!(
+            LogType::Game,
+            LogLevel::Info,
+            LogCategory::System,
+            "Loading companion scene '{}' required by '{}'",
+            companion,
+            source
+        );
+        resolved.push((companion, save_settings.clone(), None));
+    }
+}
+
+/// Despawns companion scenes once the last scene requiring them is despawned
+/// The companion despawn requests are read next frame, so nested companions are released too
+pub fn release_scene_companions_system(
+    mut cursor: Local<MessageCursor<RequestDespawnBySource>>,
+    mut despawn_requests: ResMut<Messages<RequestDespawnBySource>>,
+    mut despawn_all_reader: MessageReader<RequestDespawnSerializableEntities>,
+    mut companions: ResMut<SceneCompanions>,
+) {
+    if despawn_all_reader
+        .read()
+        .any(|RequestDespawnSerializableEntities(filter)| filter.is_complete())
+    {
+        *companions = SceneCompanions::default();
+    }
+
+    // Despawning only some classes keeps the scene loaded
+    let despawned: Vec<String> = cursor
+        .read(&despawn_requests)
+        .filter(|RequestDespawnBySource(_, filter)| filter.is_complete())
+        .map(|RequestDespawnBySource(source, _)| source.clone())
+        .collect();
+
+    for source in despawned {
+        for companion in companions.release(&source) {
+            log!(
+                LogType::Game,
+                LogLevel::Info,
+                LogCategory::System,
+                "Despawning companion scene '{}', no loaded scene requires it anymore",
+                companion
+            );
+            despawn_requests.write(RequestDespawnBySource::new(companion));
+        }
+    }
+}
+
+/// Sources that currently have spawned entities
+pub fn loaded_sources(source_query: &Query<&SpawnSource, With<IdentityData>>) -> HashSet<String> {
+    source_query
+        .iter()
+        .map(|source| source.str_ref().to_string())
+        .collect()
+}
@@ -0,0 +1,721 @@
+use super::DirtyScenes;
+use crate::{
+    entities::{CustomProperties, IdentityData, PropertyValue, SpawnSource},
+    events::{
+        EntityCsvExportedEvent, EntityCsvImportedEvent, RequestExportEntityCsv,
+        RequestImportEntityCsv,
+    },
+    shared::EditorCapabilities,
+};
+use bevy::{
+    color::{Color, Srgba},
+    ecs::{
+        entity::Entity,
+        message::MessageReader,
+        reflect::{AppTypeRegistry, ReflectComponent},
+        system::SystemState,
+        world::World,
+    },
+    math::{EulerRot, Quat, Vec3},
+    prelude::{ChildOf, GlobalTransform, Name, Transform},
+    reflect::{
+        DynamicEnum, DynamicVariant, GetPath, PartialReflect, ReflectRef, TypeRegistry, VariantType,
+    },
+};
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+use std::collections::{BTreeSet, HashMap};
+use uuid::Uuid;
+
+// csv_edit.rs
+// Bulk editing of entity data in a spreadsheet: selected entities go out as CSV rows keyed by their uuid,
+// and the edited file is read back onto the entities with the same uuids
+// Only cells that differ from the entity's current value are applied, empty cells are left alone
+
+pub const CSV_ID_COLUMN: &str = "id";
+pub const CSV_NAME_COLUMN: &str = "name";
+/// Prefix of CustomProperties columns, i.e. "property:health"
+pub const CSV_PROPERTY_PREFIX: &str = "property:";
+const TRANSFORM_COLUMNS: [&str; 9] = [
+    "position.x",
+    "position.y",
+    "position.z",
+    "rotation.x",
+    "rotation.y",
+    "rotation.z",
+    "scale.x",
+    "scale.y",
+    "scale.z",
+];
+/// Nested structs deeper than this are not flattened into columns
+const MAX_FIELD_DEPTH: usize = 4;
+
+/// Which data an export writes as columns
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvColumns {
+    pub name: bool,
+    /// World position, rotation as XYZ euler degrees, and scale
+    pub transform: bool,
+    /// One column per CustomProperties key found on the entities
+    pub properties: bool,
+    /// Type path of a component whose number, bool, text and unit enum fields become columns
+    pub component: Option<String>,
+}
+
+impl Default for CsvColumns {
+    fn default() -> Self {
+        Self {
+            name: true,
+            transform: true,
+            properties: true,
+            component: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CsvImportSummary {
+    pub rows: usize,
+    /// Entities with at least one cell applied
+    pub updated: usize,
+    /// Ids in the file that match no entity
+    pub unmatched: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+// ---------------------------------------------------------------------------------------------
+// CSV text
+
+fn escape_cell(cell: &str) -> String {
+    if cell.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+pub fn write_csv(rows: &[Vec<String>]) -> String {
+    let mut csv = String::new();
+    for row in rows {
+        let cells: Vec<String> = row.iter().map(|cell| escape_cell(cell)).collect();
+        csv.push_str(&cells.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Rows of a CSV file, quoted cells may hold commas, quotes and line breaks
+pub fn parse_csv(text: &str) -> Result<Vec<Vec<String>>, String> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut cell = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if quoted {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    cell.push('"');
+                }
+                '"' => quoted = false,
+                _ => cell.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' if cell.is_empty() => quoted = true,
+            ',' => row.push(std::mem::take(&mut cell)),
+            '\r' => {}
+            '\n' => {
+                row.push(std::mem::take(&mut cell));
+                rows.push(std::mem::take(&mut row));
+            }
+            _ => cell.push(c),
+        }
+    }
+    if quoted {
+        return Err("unterminated quoted cell".to_string());
+    }
+    if !cell.is_empty() || !row.is_empty() {
+        row.push(cell);
+        rows.push(row);
+    }
+    rows.retain(|row| row.iter().any(|cell| !cell.trim().is_empty()));
+    Ok(rows)
+}
+
+// ---------------------------------------------------------------------------------------------
+// Cells
+
+fn format_rounded(value: f32) -> String {
+    let rounded = (value * 10000.0).round() / 10000.0;
+    // No "-0" in the sheet
+    format!("{}", rounded + 0.0)
+}
+
+fn transform_cells(global: &GlobalTransform) -> Vec<String> {
+    let (scale, rotation, translation) = global.to_scale_rotation_translation();
+    let (x, y, z) = rotation.to_euler(EulerRot::XYZ);
+    [
+        translation.x,
+        translation.y,
+        translation.z,
+        x.to_degrees(),
+        y.to_degrees(),
+        z.to_degrees(),
+        scale.x,
+        scale.y,
+        scale.z,
+    ]
+    .into_iter()
+    .map(format_rounded)
+    .collect()
+}
+
+fn format_property(value: &PropertyValue) -> String {
+    match value {
+        PropertyValue::String(text) => text.clone(),
+        PropertyValue::Number(number) => number.to_string(),
+        PropertyValue::Bool(flag) => flag.to_string(),
+        PropertyValue::Color(color) => color.to_srgba().to_hex(),
+    }
+}
+
+/// Cell text as the kind of an existing value, or a guess for new keys
+fn parse_property(cell: &str, existing: Option<&PropertyValue>) -> Result<PropertyValue, String> {
+    let cell = cell.trim();
+    let parse_number = || {
+        cell.parse::<f64>()
+            .map(PropertyValue::Number)
+            .map_err(|_| format!("'{}' is not a number", cell))
+    };
+    let parse_bool = || match cell.to_lowercase().as_str() {
+        "true" | "1" | "yes" => Ok(PropertyValue::Bool(true)),
+        "false" | "0" | "no" => Ok(PropertyValue::Bool(false)),
+        _ => Err(format!("'{}' is not true or false", cell)),
+    };
+    let parse_color = || {
+        Srgba::hex(cell)
+            .map(|color| PropertyValue::Color(Color::Srgba(color)))
+            .map_err(|_| format!("'{}' is not a hex color", cell))
+    };
+    match existing {
+        Some(PropertyValue::String(_)) => Ok(PropertyValue::String(cell.to_string())),
+        Some(PropertyValue::Number(_)) => parse_number(),
+        Some(PropertyValue::Bool(_)) => parse_bool(),
+        Some(PropertyValue::Color(_)) => parse_color(),
+        None => Ok(parse_number()
+            .or_else(|_| {
+                if cell.eq_ignore_ascii_case("true") || cell.eq_ignore_ascii_case("false") {
+                    parse_bool()
+                } else {
+                    Err(String::new())
+                }
+            })
+            .or_else(|_| {
+                if cell.starts_with('#') {
+                    parse_color()
+                } else {
+                    Err(String::new())
+                }
+            })
+            .unwrap_or_else(|_| PropertyValue::String(cell.to_string()))),
+    }
+}
+
+/// Text of a leaf field, None for fields that don't fit in a cell
+fn format_leaf(value: &dyn PartialReflect) -> Option<String> {
+    macro_rules! leaf {
+        ($($ty:ty),*) => {
+            $(if let Some(value) = value.try_downcast_ref::<$ty>() {
+                return Some(value.to_string());
+            })*
+        };
+    }
+    leaf!(f32, f64, i8, i16, i32, i64, u8, u16, u32, u64, usize, isize, bool, String);
+    None
+}
+
+/// Sets a leaf field from cell text, parsed as the field's own type
+fn apply_leaf(field: &mut dyn PartialReflect, cell: &str) -> Result<(), String> {
+    let cell = cell.trim();
+    macro_rules! leaf {
+        ($($ty:ty),*) => {
+            $(if let Some(value) = field.try_downcast_mut::<$ty>() {
+                *value = cell
+                    .parse::<$ty>()
+                    .map_err(|_| format!("'{}' is not a {}", cell, stringify!($ty)))?;
+                return Ok(());
+            })*
+        };
+    }
+    leaf!(f32, f64, i8, i16, i32, i64, u8, u16, u32, u64, usize, isize, bool);
+    if let Some(value) = field.try_downcast_mut::<String>() {
+        *value = cell.to_string();
+        return Ok(());
+    }
+    if let ReflectRef::Enum(_) = field.reflect_ref() {
+        return field
+            .try_apply(&DynamicEnum::new(cell, DynamicVariant::Unit))
+            .map_err(|_| format!("'{}' is not a variant without fields", cell));
+    }
+    Err("field can't be set from text".to_string())
+}
+
+/// Flattens a component into (path, text) pairs, paths use bevy_reflect's syntax, i.e. "stats.0.speed"
+fn flatten_fields(
+    value: &dyn PartialReflect,
+    path: String,
+    depth: usize,
+    out: &mut Vec<(String, String)>,
+) {
+    if let Some(text) = format_leaf(value) {
+        if !path.is_empty() {
+            out.push((path, text));
+        }
+        return;
+    }
+    if depth >= MAX_FIELD_DEPTH {
+        return;
+    }
+    let join = |name: &str| {
+        if path.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}.{}", path, name)
+        }
+    };
+    match value.reflect_ref() {
+        ReflectRef::Struct(fields) => {
+            for index in 0..fields.field_len() {
+                if let (Some(name), Some(field)) = (fields.name_at(index), fields.field_at(index)) {
+                    flatten_fields(field, join(name), depth + 1, out);
+                }
+            }
+        }
+        ReflectRef::TupleStruct(fields) => {
+            for index in 0..fields.field_len() {
+                if let Some(field) = fields.field(index) {
+                    flatten_fields(field, join(&index.to_string()), depth + 1, out);
+                }
+            }
+        }
+        ReflectRef::Tuple(fields) => {
+            for index in 0..fields.field_len() {
+                if let Some(field) = fields.field(index) {
+                    flatten_fields(field, join(&index.to_string()), depth + 1, out);
+                }
+            }
+        }
+        ReflectRef::Enum(variant)
+            if variant.variant_type() == VariantType::Unit && !path.is_empty() =>
+        {
+            out.push((path, variant.variant_name().to_string()));
+        }
+        _ => {}
+    }
+}
+
+/// Short name of a component type path, used as its column prefix
+fn component_label(type_path: &str) -> &str {
+    type_path.rsplit("::").next().unwrap_or(type_path)
+}
+
+fn component_fields(
+    world: &World,
+    registry: &TypeRegistry,
+    entity: Entity,
+    type_path: &str,
+) -> Option<Vec<(String, String)>> {
+    let registration = registry.get_with_type_path(type_path)?;
+    let reflect_component = registration.data::<ReflectComponent>()?;
+    let value = reflect_component.reflect(world.entity(entity))?;
+    let mut fields = Vec::new();
+    flatten_fields(value.as_partial_reflect(), String::new(), 0, &mut fields);
+    Some(fields)
+}
+
+/// Current cells of an entity for the given header, empty where it has no such data
+fn entity_cells(
+    world: &World,
+    registry: &TypeRegistry,
+    entity: Entity,
+    header: &[String],
+    component: Option<&str>,
+) -> HashMap<String, String> {
+    let mut cells = HashMap::new();
+    let Some(identity) = world.get::<IdentityData>(entity) else {
+        return cells;
+    };
+    cells.insert(CSV_ID_COLUMN.to_string(), identity.uuid.to_string());
+    cells.insert(CSV_NAME_COLUMN.to_string(), identity.name.clone());
+    if let Some(global) = world.get::<GlobalTransform>(entity) {
+        for (column, cell) in TRANSFORM_COLUMNS.iter().zip(transform_cells(global)) {
+            cells.insert(column.to_string(), cell);
+        }
+    }
+    if let Some(properties) = world.get::<CustomProperties>(entity) {
+        for (key, value) in properties.iter() {
+            cells.insert(
+                format!("{}{}", CSV_PROPERTY_PREFIX, key),
+                format_property(value),
+            );
+        }
+    }
+    if let Some(component) = component {
+        let label = component_label(component);
+        for (path, text) in component_fields(world, registry, entity, component).unwrap_or_default()
+        {
+            cells.insert(format!("{}.{}", label, path), text);
+        }
+    }
+    cells.retain(|column, _| header.contains(column));
+    cells
+}
+
+// ---------------------------------------------------------------------------------------------
+// Export
+
+/// Header and rows for entities, columns come from what the entities have
+pub fn entities_to_csv(
+    world: &World,
+    entities: &[Entity],
+    columns: &CsvColumns,
+) -> Vec<Vec<String>> {
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let entities: Vec<Entity> = entities
+        .iter()
+        .copied()
+        .filter(|entity| world.get::<IdentityData>(*entity).is_some())
+        .collect();
+
+    let mut header = vec![CSV_ID_COLUMN.to_string()];
+    if columns.name {
+        header.push(CSV_NAME_COLUMN.to_string());
+    }
+    if columns.transform {
+        header.extend(TRANSFORM_COLUMNS.iter().map(|column| column.to_string()));
+    }
+    if columns.properties {
+        let keys: BTreeSet<&String> = entities
+            .iter()
+            .filter_map(|entity| world.get::<CustomProperties>(*entity))
+            .flat_map(|properties| properties.iter().map(|(key, _)| key))
+            .collect();
+        header.extend(
+            keys.into_iter()
+                .map(|key| format!("{}{}", CSV_PROPERTY_PREFIX, key)),
+        );
+    }
+    if let Some(component) = &columns.component {
+        let label = component_label(component);
+        let mut paths: Vec<String> = Vec::new();
+        for entity in entities.iter() {
+            for (path, _) in
+                component_fields(world, &registry, *entity, component).unwrap_or_default()
+            {
+                if !paths.contains(&path) {
+                    paths.push(path);
+                }
+            }
+        }
+        header.extend(paths.into_iter().map(|path| format!("{}.{}", label, path)));
+    }
+
+    let mut rows = vec![header.clone()];
+    for entity in entities {
+        let cells = entity_cells(
+            world,
+            &registry,
+            entity,
+            &header,
+            columns.component.as_deref(),
+        );
+        rows.push(
+            header
+                .iter()
+                .map(|column| cells.get(column).cloned().unwrap_or_default())
+                .collect(),
+        );
+    }
+    rows
+}
+
+/// Writes the requested entities to a CSV file
+pub fn export_entity_csv_system(
+    world: &mut World,
+    state: &mut SystemState<MessageReader<RequestExportEntityCsv>>,
+) {
+    let requests: Vec<RequestExportEntityCsv> = state.get_mut(world).read().cloned().collect();
+    for RequestExportEntityCsv {
+        path,
+        entities,
+        columns,
+    } in requests
+    {
+        if !world.resource::<EditorCapabilities>().can_use_files(&path) {
+            continue;
+        }
+        let rows = entities_to_csv(world, &entities, &columns);
+        let count = rows.len() - 1;
+        if let Err(e) = std::fs::write(&path, write_csv(&rows)) {
+            log!(
+                LogType::Game,
+                LogLevel::Error,
+                LogCategory::System,
+                "Failed to write CSV '{}': {}",
+                path,
+                e
+            );
+            continue;
+        }
+        log!(
+            LogType::Game,
+            LogLevel::OK,
+            LogCategory::System,
+            "Exported {} entities to '{}'",
+            count,
+            path
+        );
+        world.write_message(EntityCsvExportedEvent { path, rows: count });
+    }
+}
+
+// ---------------------------------------------------------------------------------------------
+// Import
+
+/// Applies the changed cells of one row, returns whether anything changed
+fn apply_row(
+    world: &mut World,
+    registry: &TypeRegistry,
+    entity: Entity,
+    header: &[String],
+    row: &[String],
+    errors: &mut Vec<String>,
+) -> bool {
+    let component = header.iter().find_map(|column| {
+        let (label, _) = column.split_once('.')?;
+        if label == "position" || label == "rotation" || label == "scale" {
+            return None;
+        }
+        // The entity's own component with that short name
+        registry
+            .iter()
+            .filter(|registration| registration.data::<ReflectComponent>().is_some())
+            .map(|registration| registration.type_info().type_path())
+            .find(|type_path| {
+                component_label(type_path) == label
+                    && component_fields(world, registry, entity, type_path).is_some()
+            })
+            .map(|type_path| type_path.to_string())
+    });
+    let current = entity_cells(world, registry, entity, header, component.as_deref());
+    let name = world
+        .get::<IdentityData>(entity)
+        .map(|identity| identity.name.clone())
+        .unwrap_or_default();
+
+    let mut changed = false;
+    let mut transform = [None; 9];
+    for (column, cell) in header.iter().zip(row.iter()) {
+        if cell.trim().is_empty() || column == CSV_ID_COLUMN {
+            continue;
+        }
+        if current.get(column).is_some_and(|current| current == cell) {
+            continue;
+        }
+
+        if column == CSV_NAME_COLUMN {
+            if let Some(mut identity) = world.get_mut::<IdentityData>(entity) {
+                identity.name = cell.clone();
+            }
+            world.entity_mut(entity).insert(Name::new(cell.clone()));
+            changed = true;
+        } else if let Some(index) = TRANSFORM_COLUMNS.iter().position(|c| c == column) {
+            match cell.trim().parse::<f32>() {
+                Ok(value) => transform[index] = Some(value),
+                Err(_) => errors.push(format!("{}: {} '{}' is not a number", name, column, cell)),
+            }
+        } else if let Some(key) = column.strip_prefix(CSV_PROPERTY_PREFIX) {
+            let existing = world
+                .get::<CustomProperties>(entity)
+                .and_then(|properties| properties.get(key).cloned());
+            match parse_property(cell, existing.as_ref()) {
+                Ok(value) => {
+                    let mut entity_mut = world.entity_mut(entity);
+                    match entity_mut.get_mut::<CustomProperties>() {
+                        Some(mut properties) => properties.set(key, value),
+                        None => {
+                            entity_mut.insert(CustomProperties::default().with(key, value));
+                        }
+                    }
+                    changed = true;
+                }
+                Err(e) => errors.push(format!("{}: {}: {}", name, column, e)),
+            }
+        } else if let Some(component) = &component {
+            let Some((_, path)) = column.split_once('.') else {
+                continue;
+            };
+            let Some(reflect_component) = registry
+                .get_with_type_path(component)
+                .and_then(|registration| registration.data::<ReflectComponent>())
+            else {
+                continue;
+            };
+            let Some(mut value) = reflect_component.reflect_mut(world.entity_mut(entity)) else {
+                continue;
+            };
+            let result = value
+                .reflect_path_mut(path)
+                .map_err(|e| e.to_string())
+                .and_then(|field| apply_leaf(field, cell));
+            match result {
+                Ok(()) => changed = true,
+                Err(e) => errors.push(format!("{}: {}: {}", name, column, e)),
+            }
+        }
+    }
+
+    if transform.iter().any(Option::is_some) {
+        if let Some(global) = world.get::<GlobalTransform>(entity).copied() {
+            let (scale, rotation, translation) = global.to_scale_rotation_translation();
+            let (rx, ry, rz) = rotation.to_euler(EulerRot::XYZ);
+            let value = |index: usize, current: f32| transform[index].unwrap_or(current);
+            let new_global = GlobalTransform::from(Transform {
+                translation: Vec3::new(
+                    value(0, translation.x),
+                    value(1, translation.y),
+                    value(2, translation.z),
+                ),
+                rotation: Quat::from_euler(
+                    EulerRot::XYZ,
+                    transform[3].map_or(rx, f32::to_radians),
+                    transform[4].map_or(ry, f32::to_radians),
+                    transform[5].map_or(rz, f32::to_radians),
+                ),
+                scale: Vec3::new(value(6, scale.x), value(7, scale.y), value(8, scale.z)),
+            });
+            // The sheet holds world values, the entity keeps its local transform
+            let parent = world
+                .get::<ChildOf>(entity)
+                .and_then(|child_of| world.get::<GlobalTransform>(child_of.parent()).copied());
+            let local = match parent {
+                Some(parent) => new_global.reparented_to(&parent),
+                None => new_global.compute_transform(),
+            };
+            world.entity_mut(entity).insert((local, new_global));
+            changed = true;
+        }
+    }
+    changed
+}
+
+/// Reads an edited CSV back onto the entities whose uuid matches the id column
+pub fn import_entity_csv(world: &mut World, text: &str) -> Result<CsvImportSummary, String> {
+    let rows = parse_csv(text)?;
+    let Some((header, rows)) = rows.split_first() else {
+        return Err("the file is empty".to_string());
+    };
+    let Some(id_column) = header.iter().position(|column| column == CSV_ID_COLUMN) else {
+        return Err(format!("no '{}' column", CSV_ID_COLUMN));
+    };
+
+    let mut by_uuid = HashMap::new();
+    let mut query = world.query::<(Entity, &IdentityData)>();
+    for (entity, identity) in query.iter(world) {
+        by_uuid.insert(identity.uuid, entity);
+    }
+
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let mut summary = CsvImportSummary {
+        rows: rows.len(),
+        ..Default::default()
+    };
+    for row in rows {
+        let id = row.get(id_column).map(|id| id.trim()).unwrap_or_default();
+        let Some(entity) = Uuid::parse_str(id)
+            .ok()
+            .and_then(|uuid| by_uuid.get(&uuid).copied())
+        else {
+            summary.unmatched.push(id.to_string());
+            continue;
+        };
+        let source = world
+            .get::<SpawnSource>(entity)
+            .map(|source| source.str_ref().to_string());
+        if let Some(source) = &source {
+            if !world
+                .resource::<EditorCapabilities>()
+                .can_edit_components(source)
+            {
+                summary
+                    .errors
+                    .push(format!("{}: scene '{}' is read only", id, source));
+                continue;
+            }
+        }
+        if apply_row(world, &registry, entity, header, row, &mut summary.errors) {
+            summary.updated += 1;
+            if let Some(source) = source {
+                world.resource_mut::<DirtyScenes>().mark(source);
+            }
+        }
+    }
+    Ok(summary)
+}
+
+pub fn import_entity_csv_system(
+    world: &mut World,
+    state: &mut SystemState<MessageReader<RequestImportEntityCsv>>,
+) {
+    let requests: Vec<RequestImportEntityCsv> = state.get_mut(world).read().cloned().collect();
+    for RequestImportEntityCsv { path } in requests {
+        if !world.resource::<EditorCapabilities>().can_use_files(&path) {
+            continue;
+        }
+        let result = std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| import_entity_csv(world, &text));
+        let summary = match result {
+            Ok(summary) => summary,
+            Err(e) => {
+                log!(
+                    LogType::Game,
+                    LogLevel::Error,
+                    LogCategory::System,
+                    "Failed to import CSV '{}': {}",
+                    path,
+                    e
+                );
+                continue;
+            }
+        };
+        for error in summary.errors.iter() {
+            log!(
+                LogType::Game,
+                LogLevel::Warning,
+                LogCategory::System,
+                "CSV import: {}",
+                error
+            );
+        }
+        log!(
+            LogType::Game,
+            LogLevel::OK,
+            LogCategory::System,
+            "Imported '{}': {} of {} rows changed an entity, {} ids not found",
+            path,
+            summary.updated,
+            summary.rows,
+            summary.unmatched.len()
+        );
+        world.write_message(EntityCsvImportedEvent { path, summary });
+    }
+}
@@ -0,0 +1,332 @@
+use crate::{
+    entities::{EntitySaveReadyData, SceneData},
+    events::{RequestSceneDependencyReportEvent, SceneDependencyReportEvent},
+    shared::{is_scene_file, read_scene_file, rel_asset_to_absolute, EditorCapabilities},
+};
+use bevy::prelude::{MessageReader, MessageWriter, Res};
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fs,
+    path::Path,
+};
+
+/// What kind of asset a scene dependency is. Decided by file extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum SceneDependencyKind {
+    Scene,
+    Mesh,
+    Material,
+    Texture,
+    Audio,
+}
+
+impl SceneDependencyKind {
+    pub fn from_path(path: &str) -> Option<Self> {
+        if is_scene_file(path) {
+            return Some(Self::Scene);
+        }
+        let extension = Path::new(path)
+            .extension()?
+            .to_string_lossy()
+            .to_lowercase();
+
+        match extension.as_str() {
+            "obj" | "gltf" | "glb" => Some(Self::Mesh),
+            "mat" => Some(Self::Material),
+            "png" | "jpg" | "jpeg" | "ktx2" | "dds" | "tga" | "bmp" | "hdr" | "exr" => {
+                Some(Self::Texture)
+            }
+            "ogg" | "wav" | "mp3" | "flac" => Some(Self::Audio),
+            _ => None,
+        }
+    }
+
+    pub fn get_friendly_name(&self) -> &'static str {
+        match self {
+            Self::Scene => "Scene",
+            Self::Mesh => "Mesh",
+            Self::Material => "Material",
+            Self::Texture => "Texture",
+            Self::Audio => "Audio",
+        }
+    }
+}
+
+/// A single file a scene needs. Path is relative to the assets folder
+#[derive(Debug, Clone)]
+pub struct SceneDependency {
+    pub path: String,
+    pub kind: SceneDependencyKind,
+    pub exists: bool,
+    /// Files that reference this dependency
+    pub required_by: Vec<String>,
+}
+
+/// Every file reachable from a root scene. Sub-scenes and materials are followed recursively
+#[derive(Debug, Clone, Default)]
+pub struct SceneDependencyGraph {
+    pub root: String,
+    pub nodes: BTreeMap<String, SceneDependency>,
+}
+
+impl SceneDependencyGraph {
+    /// Walk the scene on disk and everything it references
+    pub fn build(root: &str) -> Self {
+        let root = normalize_rel_path(root);
+        let mut graph = Self {
+            root: root.clone(),
+            nodes: BTreeMap::new(),
+        };
+
+        let mut queue: VecDeque<(String, Option<String>)> = VecDeque::new();
+        queue.push_back((root, None));
+
+        while let Some((path, parent)) = queue.pop_front() {
+            if let Some(existing) = graph.nodes.get_mut(&path) {
+                if let Some(parent) = parent {
+                    if !existing.required_by.contains(&parent) {
+                        existing.required_by.push(parent);
+                    }
+                }
+                continue;
+            }
+
+            let Some(kind) = SceneDependencyKind::from_path(&path) else {
+                continue;
+            };
+            let abs_path = rel_asset_to_absolute(&path);
+            let exists = Path::new(abs_path.as_ref()).is_file();
+
+            if exists {
+                let children = match kind {
+                    SceneDependencyKind::Scene => scene_references(abs_path.as_ref()),
+                    SceneDependencyKind::Material => fs::read_to_string(abs_path.as_ref())
+                        .map(|contents| asset_references(&contents))
+                        .unwrap_or_default(),
+                    _ => vec![],
+                };
+
+                for child in children {
+                    queue.push_back((child, Some(path.clone())));
+                }
+            }
+
+            graph.nodes.insert(
+                path.clone(),
+                SceneDependency {
+                    path,
+                    kind,
+                    exists,
+                    required_by: parent.into_iter().collect(),
+                },
+            );
+        }
+
+        graph
+    }
+
+    pub fn missing(&self) -> impl Iterator<Item = &SceneDependency> {
+        self.nodes.values().filter(|dependency| !dependency.exists)
+    }
+
+    pub fn of_kind(&self, kind: SceneDependencyKind) -> impl Iterator<Item = &SceneDependency> {
+        self.nodes
+            .values()
+            .filter(move |dependency| dependency.kind == kind)
+    }
+
+    /// Human readable listing of every file, grouped by kind, with missing files flagged
+    pub fn report(&self) -> String {
+        let mut report = format!(
+            "Dependency report for '{}'\n{} files, {} missing\n",
+            self.root,
+            self.nodes.len(),
+            self.missing().count()
+        );
+
+        for kind in [
+            SceneDependencyKind::Scene,
+            SceneDependencyKind::Mesh,
+            SceneDependencyKind::Material,
+            SceneDependencyKind::Texture,
+            SceneDependencyKind::Audio,
+        ] {
+            let mut dependencies = self.of_kind(kind).peekable();
+            if dependencies.peek().is_none() {
+                continue;
+            }
+
+            report.push_str(&format!("\n[{}]\n", kind.get_friendly_name()));
+            for dependency in dependencies {
+                let status = if dependency.exists { "   " } else { "!! " };
+                report.push_str(&format!("{}{}", status, dependency.path));
+                if !dependency.required_by.is_empty() {
+                    report.push_str(&format!("  <- {}", dependency.required_by.join(", ")));
+                }
+                report.push('\n');
+            }
+        }
+
+        report
+    }
+
+    /// Copy every existing dependency into dist_dir, keeping the assets folder layout
+    /// A dependencies.txt report is written alongside. Returns the number of copied files
+    pub fn package(&self, dist_dir: &Path) -> std::io::Result<usize> {
+        let mut copied = 0;
+
+        for dependency in self.nodes.values().filter(|dependency| dependency.exists) {
+            let target = dist_dir.join(&dependency.path);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::copy(rel_asset_to_absolute(&dependency.path).as_ref(), &target)?;
+            copied += 1;
+        }
+
+        fs::create_dir_all(dist_dir)?;
+        fs::write(dist_dir.join("dependencies.txt"), self.report())?;
+
+        Ok(copied)
+    }
+}
+
+/// Builds a dependency report for a scene, and optionally packages it into a folder
+pub fn scene_dependency_report_system(
+    mut request_reader: MessageReader<RequestSceneDependencyReportEvent>,
+    mut report_writer: MessageWriter<SceneDependencyReportEvent>,
+    capabilities: Res<EditorCapabilities>,
+) {
+    for RequestSceneDependencyReportEvent(path, package_dir) in request_reader.read() {
+        let graph = SceneDependencyGraph::build(path);
+
+        for line in graph.report().lines() {
+            log!(
+                LogType::Game,
+                LogLevel::Info,
+                LogCategory::Asset,
+                "{}",
+                line
+            );
+        }
+
+        for dependency in graph.missing() {
+            log!(
+                LogType::Game,
+                LogLevel::Warning,
+                LogCategory::Asset,
+                "Missing dependency '{}' required by: {}",
+                dependency.path,
+                dependency.required_by.join(", ")
+            );
+        }
+
+        if let Some(package_dir) = package_dir
+            .as_ref()
+            .filter(|dir| capabilities.can_use_files(dir))
+        {
+            match graph.package(Path::new(package_dir)) {
+                Ok(copied) => log!(
+                    LogType::Game,
+                    LogLevel::OK,
+                    LogCategory::Asset,
+                    "Packaged {} files for '{}' into: {}",
+                    copied,
+                    graph.root,
+                    package_dir
+                ),
+                Err(e) => log!(
+                    LogType::Game,
+                    LogLevel::Error,
+                    LogCategory::Asset,
+                    "Failed to package '{}' into {}: {}",
+                    graph.root,
+                    package_dir,
+                    e
+                ),
+            }
+        }
+
+        report_writer.write(SceneDependencyReportEvent(graph));
+    }
+}
+
+pub(crate) fn normalize_rel_path(path: &str) -> String {
+    path.replace('\\', "/").trim_start_matches("./").to_string()
+}
+
+/// All asset paths referenced by the entities of a scene file
+/// Class data and serialized components are both scanned
+fn scene_references(abs_path: &str) -> Vec<String> {
+    let Ok(contents) = read_scene_file(abs_path) else {
+        return vec![];
+    };
+
+    let entities: Vec<EntitySaveReadyData> =
+        if let Ok(scene_data) = ron::de::from_str::<SceneData>(&contents) {
+            scene_data.entities
+        } else if let Ok(entities) = ron::de::from_str::<Vec<EntitySaveReadyData>>(&contents) {
+            entities
+        } else {
+            log!(
+                LogType::Game,
+                LogLevel::Warning,
+                LogCategory::Asset,
+                "Could not parse scene for dependencies: {}",
+                abs_path
+            );
+            return vec![];
+        };
+
+    let mut references = vec![];
+    for entity in entities {
+        if let Ok(class) = ron::ser::to_string(&entity.identity.class) {
+            references.extend(asset_references(&class));
+        }
+        for value in entity.components.unwrap_or_default().values() {
+            references.extend(asset_references(value));
+        }
+    }
+
+    references
+}
+
+/// Every string literal in RON text that looks like an asset path
+pub(crate) fn asset_references(ron_text: &str) -> Vec<String> {
+    string_literals(ron_text)
+        .into_iter()
+        .map(|literal| normalize_rel_path(&literal))
+        .filter(|literal| SceneDependencyKind::from_path(literal).is_some())
+        .collect()
+}
+
+fn string_literals(text: &str) -> Vec<String> {
+    let mut literals = vec![];
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '"' {
+            continue;
+        }
+
+        let mut literal = String::new();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => {
+                    if let Some(escaped) = chars.next() {
+                        literal.push(escaped);
+                    }
+                }
+                '"' => break,
+                _ => literal.push(c),
+            }
+        }
+        literals.push(literal);
+    }
+
+    literals
+}
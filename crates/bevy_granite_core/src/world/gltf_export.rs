@@ -0,0 +1,747 @@
+use crate::{
+    entities::{IdentityData, SpawnSource},
+    events::{GltfExportedEvent, RequestExportGltf},
+    shared::{rel_asset_to_absolute, EditorCapabilities},
+    AvailableEditableMaterials,
+};
+use bevy::{
+    asset::{AssetId, Assets, Handle},
+    ecs::system::SystemParam,
+    mesh::{Indices, Mesh, Mesh3d, PrimitiveTopology, VertexAttributeValues},
+    pbr::{MeshMaterial3d, StandardMaterial},
+    prelude::{
+        AlphaMode, ChildOf, Entity, GlobalTransform, Image, MessageReader, MessageWriter, Query,
+        Res, Transform,
+    },
+};
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+// gltf_export.rs
+// Writes serializable entities to a glTF 2.0 file, so levels built in Granite open in DCC tools and other engines
+// Every serializable entity becomes a node with its name, transform and place in the hierarchy
+// Meshes (triangle lists: positions, normals, UVs, indices) and StandardMaterial data go along, textures are referenced by path
+// .glb packs everything into one file, .gltf writes the mesh data to a .bin next to it
+
+const GLB_MAGIC: u32 = 0x4654_6C67;
+const GLB_CHUNK_JSON: u32 = 0x4E4F_534A;
+const GLB_CHUNK_BIN: u32 = 0x004E_4942;
+
+const COMPONENT_U16: u32 = 5123;
+const COMPONENT_U32: u32 = 5125;
+const COMPONENT_F32: u32 = 5126;
+
+const TARGET_ARRAY_BUFFER: u32 = 34962;
+const TARGET_ELEMENT_ARRAY_BUFFER: u32 = 34963;
+
+/// What an export wrote
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GltfExportSummary {
+    pub nodes: usize,
+    pub meshes: usize,
+    pub materials: usize,
+    pub textures: usize,
+    /// Meshes that aren't triangle lists with positions, or are no longer in memory
+    pub skipped_meshes: usize,
+}
+
+type GltfExportEntity = (
+    Entity,
+    &'static IdentityData,
+    Option<&'static SpawnSource>,
+    &'static Transform,
+    &'static GlobalTransform,
+    Option<&'static ChildOf>,
+    Option<&'static Mesh3d>,
+    Option<&'static MeshMaterial3d<StandardMaterial>>,
+);
+
+#[derive(SystemParam)]
+pub struct GltfExportSources<'w, 's> {
+    entities: Query<'w, 's, GltfExportEntity>,
+    meshes: Res<'w, Assets<Mesh>>,
+    materials: Res<'w, Assets<StandardMaterial>>,
+    available_materials: Res<'w, AvailableEditableMaterials>,
+}
+
+impl GltfExportSources<'_, '_> {
+    /// Serializable entities of a source, or of every source. Children without a source (prefab members) follow their parent
+    fn collect(&self, source: Option<&str>) -> Vec<Entity> {
+        let mut included: HashMap<Entity, bool> = HashMap::new();
+        let mut entities: Vec<(Entity, &IdentityData)> = Vec::new();
+        for (entity, identity, ..) in self.entities.iter() {
+            if self.is_included(entity, source, &mut included) {
+                entities.push((entity, identity));
+            }
+        }
+        entities.sort_by_key(|(_, identity)| identity.uuid);
+        entities.into_iter().map(|(entity, _)| entity).collect()
+    }
+
+    fn is_included(
+        &self,
+        entity: Entity,
+        source: Option<&str>,
+        included: &mut HashMap<Entity, bool>,
+    ) -> bool {
+        if let Some(known) = included.get(&entity) {
+            return *known;
+        }
+        let result = match self.entities.get(entity) {
+            Err(_) => false,
+            Ok((_, _, spawn_source, _, _, child_of, ..)) => match (source, spawn_source) {
+                (None, _) => true,
+                (Some(source), Some(spawn_source)) => spawn_source.str_ref() == source,
+                (Some(_), None) => child_of
+                    .is_some_and(|child_of| self.is_included(child_of.parent(), source, included)),
+            },
+        };
+        included.insert(entity, result);
+        result
+    }
+
+    /// Build the glTF document of the given entities, to be written to `path`
+    pub fn build(&self, entities: &[Entity], path: &Path) -> GltfDocument {
+        let material_names: HashMap<AssetId<StandardMaterial>, String> = self
+            .available_materials
+            .materials()
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|material| {
+                let handle = material.handle.as_ref()?;
+                Some((handle.id(), material.friendly_name.clone()))
+            })
+            .collect();
+        let image_paths: HashMap<AssetId<Image>, &String> = self
+            .available_materials
+            .image_paths
+            .iter()
+            .map(|(handle, path)| (handle.id(), path))
+            .collect();
+
+        // GLB buffers live in the file itself
+        let buffer_uri = (!is_glb(path)).then(|| {
+            path.with_extension("bin")
+                .file_name()
+                .map(|name| name.to_string_lossy().replace(' ', "%20"))
+                .unwrap_or_default()
+        });
+        let mut builder = GltfBuilder {
+            meshes: &self.meshes,
+            materials: &self.materials,
+            material_names,
+            image_paths,
+            output_dir: path.parent().map(Path::to_path_buf).unwrap_or_default(),
+            buffer_uri,
+            bin: Vec::new(),
+            buffer_views: Vec::new(),
+            accessors: Vec::new(),
+            mesh_json: Vec::new(),
+            material_json: Vec::new(),
+            image_json: Vec::new(),
+            texture_json: Vec::new(),
+            extensions_used: Vec::new(),
+            mesh_index: HashMap::new(),
+            material_index: HashMap::new(),
+            texture_index: HashMap::new(),
+            summary: GltfExportSummary::default(),
+        };
+
+        let node_index: HashMap<Entity, usize> = entities
+            .iter()
+            .enumerate()
+            .map(|(index, entity)| (*entity, index))
+            .collect();
+        let mut children: Vec<Vec<usize>> = vec![Vec::new(); entities.len()];
+        let mut roots = Vec::new();
+        let mut nodes = Vec::with_capacity(entities.len());
+
+        for (index, entity) in entities.iter().enumerate() {
+            let Ok((_, identity, _, transform, global_transform, child_of, mesh, material)) =
+                self.entities.get(*entity)
+            else {
+                continue;
+            };
+
+            // Nodes whose parent isn't exported keep their place in the world
+            let parent = child_of.and_then(|child_of| node_index.get(&child_of.parent()));
+            let transform = match parent {
+                Some(parent) => {
+                    children[*parent].push(index);
+                    *transform
+                }
+                None => {
+                    roots.push(index);
+                    global_transform.compute_transform()
+                }
+            };
+
+            let mesh = mesh.and_then(|mesh| {
+                let exported = builder.mesh(&mesh.0, material.map(|material| &material.0));
+                if exported.is_none() {
+                    builder.summary.skipped_meshes += 1;
+                }
+                exported
+            });
+            nodes.push((identity.name.clone(), transform, mesh));
+        }
+
+        builder.summary.nodes = nodes.len();
+        let nodes = nodes
+            .into_iter()
+            .zip(children)
+            .map(|((name, transform, mesh), children)| {
+                node_json(&name, &transform, mesh, &children)
+            })
+            .collect();
+        builder.finish(nodes, roots)
+    }
+}
+
+/// A built glTF file: the JSON and the binary buffer it points into
+#[derive(Debug, Clone, Default)]
+pub struct GltfDocument {
+    pub json: String,
+    pub bin: Vec<u8>,
+    pub summary: GltfExportSummary,
+}
+
+impl GltfDocument {
+    /// Write as .glb, or as .gltf with a .bin next to it, picked by the path's extension
+    pub fn write(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        if is_glb(path) {
+            return fs::write(path, self.to_glb()).map_err(|e| e.to_string());
+        }
+        if !self.bin.is_empty() {
+            fs::write(path.with_extension("bin"), &self.bin).map_err(|e| e.to_string())?;
+        }
+        fs::write(path, &self.json).map_err(|e| e.to_string())
+    }
+
+    pub fn to_glb(&self) -> Vec<u8> {
+        let mut json = self.json.clone().into_bytes();
+        json.resize(json.len().next_multiple_of(4), b' ');
+        let mut bin = self.bin.clone();
+        bin.resize(bin.len().next_multiple_of(4), 0);
+
+        let mut length = 12 + 8 + json.len();
+        if !bin.is_empty() {
+            length += 8 + bin.len();
+        }
+        let mut glb = Vec::with_capacity(length);
+        glb.extend_from_slice(&GLB_MAGIC.to_le_bytes());
+        glb.extend_from_slice(&2u32.to_le_bytes());
+        glb.extend_from_slice(&(length as u32).to_le_bytes());
+        glb.extend_from_slice(&(json.len() as u32).to_le_bytes());
+        glb.extend_from_slice(&GLB_CHUNK_JSON.to_le_bytes());
+        glb.extend_from_slice(&json);
+        if !bin.is_empty() {
+            glb.extend_from_slice(&(bin.len() as u32).to_le_bytes());
+            glb.extend_from_slice(&GLB_CHUNK_BIN.to_le_bytes());
+            glb.extend_from_slice(&bin);
+        }
+        glb
+    }
+}
+
+struct GltfBuilder<'a> {
+    meshes: &'a Assets<Mesh>,
+    materials: &'a Assets<StandardMaterial>,
+    material_names: HashMap<AssetId<StandardMaterial>, String>,
+    image_paths: HashMap<AssetId<Image>, &'a String>,
+    output_dir: PathBuf,
+    buffer_uri: Option<String>,
+    bin: Vec<u8>,
+    buffer_views: Vec<String>,
+    accessors: Vec<String>,
+    mesh_json: Vec<String>,
+    material_json: Vec<String>,
+    image_json: Vec<String>,
+    texture_json: Vec<String>,
+    extensions_used: Vec<&'static str>,
+    mesh_index: HashMap<(AssetId<Mesh>, Option<AssetId<StandardMaterial>>), usize>,
+    material_index: HashMap<AssetId<StandardMaterial>, usize>,
+    texture_index: HashMap<String, usize>,
+    summary: GltfExportSummary,
+}
+
+impl GltfBuilder<'_> {
+    fn push_view(&mut self, bytes: &[u8], target: u32) -> usize {
+        let offset = self.bin.len();
+        self.bin.extend_from_slice(bytes);
+        self.bin.resize(self.bin.len().next_multiple_of(4), 0);
+        self.buffer_views.push(format!(
+            "{{\"buffer\":0,\"byteOffset\":{},\"byteLength\":{},\"target\":{}}}",
+            offset,
+            bytes.len(),
+            target
+        ));
+        self.buffer_views.len() - 1
+    }
+
+    fn push_accessor(
+        &mut self,
+        bytes: &[u8],
+        target: u32,
+        component_type: u32,
+        count: usize,
+        kind: &str,
+        bounds: Option<([f32; 3], [f32; 3])>,
+    ) -> usize {
+        let view = self.push_view(bytes, target);
+        let bounds = bounds
+            .map(|(min, max)| format!(",\"min\":{},\"max\":{}", floats(&min), floats(&max)))
+            .unwrap_or_default();
+        self.accessors.push(format!(
+            "{{\"bufferView\":{},\"componentType\":{},\"count\":{},\"type\":\"{}\"{}}}",
+            view, component_type, count, kind, bounds
+        ));
+        self.accessors.len() - 1
+    }
+
+    /// Meshes are shared between nodes that use the same mesh and material
+    fn mesh(
+        &mut self,
+        mesh: &Handle<Mesh>,
+        material: Option<&Handle<StandardMaterial>>,
+    ) -> Option<usize> {
+        let key = (mesh.id(), material.map(|material| material.id()));
+        if let Some(index) = self.mesh_index.get(&key) {
+            return Some(*index);
+        }
+
+        let meshes = self.meshes;
+        let mesh_asset = meshes.get(mesh)?;
+        if mesh_asset.primitive_topology() != PrimitiveTopology::TriangleList {
+            return None;
+        }
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh_asset.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            return None;
+        };
+
+        let mut min = [f32::MAX; 3];
+        let mut max = [f32::MIN; 3];
+        for position in positions {
+            for axis in 0..3 {
+                min[axis] = min[axis].min(position[axis]);
+                max[axis] = max[axis].max(position[axis]);
+            }
+        }
+        let bounds = (!positions.is_empty()).then_some((min, max));
+        let mut attributes = vec![format!(
+            "\"POSITION\":{}",
+            self.push_accessor(
+                &f32_bytes(positions.iter().flatten()),
+                TARGET_ARRAY_BUFFER,
+                COMPONENT_F32,
+                positions.len(),
+                "VEC3",
+                bounds,
+            )
+        )];
+
+        if let Some(VertexAttributeValues::Float32x3(normals)) =
+            mesh_asset.attribute(Mesh::ATTRIBUTE_NORMAL)
+        {
+            let accessor = self.push_accessor(
+                &f32_bytes(normals.iter().flatten()),
+                TARGET_ARRAY_BUFFER,
+                COMPONENT_F32,
+                normals.len(),
+                "VEC3",
+                None,
+            );
+            attributes.push(format!("\"NORMAL\":{}", accessor));
+        }
+        if let Some(VertexAttributeValues::Float32x2(uvs)) =
+            mesh_asset.attribute(Mesh::ATTRIBUTE_UV_0)
+        {
+            let accessor = self.push_accessor(
+                &f32_bytes(uvs.iter().flatten()),
+                TARGET_ARRAY_BUFFER,
+                COMPONENT_F32,
+                uvs.len(),
+                "VEC2",
+                None,
+            );
+            attributes.push(format!("\"TEXCOORD_0\":{}", accessor));
+        }
+
+        let mut primitive = format!("\"attributes\":{{{}}},\"mode\":4", attributes.join(","));
+        let indices = match mesh_asset.indices() {
+            Some(Indices::U16(indices)) => Some((
+                indices
+                    .iter()
+                    .flat_map(|index| index.to_le_bytes())
+                    .collect::<Vec<u8>>(),
+                COMPONENT_U16,
+                indices.len(),
+            )),
+            Some(Indices::U32(indices)) => Some((
+                indices
+                    .iter()
+                    .flat_map(|index| index.to_le_bytes())
+                    .collect(),
+                COMPONENT_U32,
+                indices.len(),
+            )),
+            None => None,
+        };
+        if let Some((bytes, component_type, count)) = indices {
+            let accessor = self.push_accessor(
+                &bytes,
+                TARGET_ELEMENT_ARRAY_BUFFER,
+                component_type,
+                count,
+                "SCALAR",
+                None,
+            );
+            primitive.push_str(&format!(",\"indices\":{}", accessor));
+        }
+        if let Some(material) = material.and_then(|material| self.material(material)) {
+            primitive.push_str(&format!(",\"material\":{}", material));
+        }
+
+        self.mesh_json
+            .push(format!("{{\"primitives\":[{{{}}}]}}", primitive));
+        let index = self.mesh_json.len() - 1;
+        self.mesh_index.insert(key, index);
+        Some(index)
+    }
+
+    fn material(&mut self, handle: &Handle<StandardMaterial>) -> Option<usize> {
+        if let Some(index) = self.material_index.get(&handle.id()) {
+            return Some(*index);
+        }
+        let materials = self.materials;
+        let material = materials.get(handle)?;
+
+        let base_color = material.base_color.to_linear();
+        let mut pbr = vec![
+            format!(
+                "\"baseColorFactor\":{}",
+                floats(&[
+                    base_color.red,
+                    base_color.green,
+                    base_color.blue,
+                    base_color.alpha
+                ])
+            ),
+            format!("\"metallicFactor\":{}", float(material.metallic)),
+            format!(
+                "\"roughnessFactor\":{}",
+                float(material.perceptual_roughness)
+            ),
+        ];
+        if let Some(texture) = self.texture(material.base_color_texture.as_ref()) {
+            pbr.push(format!("\"baseColorTexture\":{{\"index\":{}}}", texture));
+        }
+        if let Some(texture) = self.texture(material.metallic_roughness_texture.as_ref()) {
+            pbr.push(format!(
+                "\"metallicRoughnessTexture\":{{\"index\":{}}}",
+                texture
+            ));
+        }
+
+        let name = self
+            .material_names
+            .get(&handle.id())
+            .cloned()
+            .unwrap_or_else(|| format!("material_{}", self.material_json.len()));
+        let mut fields = vec![
+            format!("\"name\":{}", string(&name)),
+            format!("\"pbrMetallicRoughness\":{{{}}}", pbr.join(",")),
+        ];
+        if let Some(texture) = self.texture(material.normal_map_texture.as_ref()) {
+            fields.push(format!("\"normalTexture\":{{\"index\":{}}}", texture));
+        }
+        if let Some(texture) = self.texture(material.occlusion_texture.as_ref()) {
+            fields.push(format!("\"occlusionTexture\":{{\"index\":{}}}", texture));
+        }
+        if let Some(texture) = self.texture(material.emissive_texture.as_ref()) {
+            fields.push(format!("\"emissiveTexture\":{{\"index\":{}}}", texture));
+        }
+
+        // glTF emissive factors stop at 1, brighter emissive goes through the strength extension
+        let emissive = [
+            material.emissive.red,
+            material.emissive.green,
+            material.emissive.blue,
+        ];
+        let strength = emissive.iter().copied().fold(0.0, f32::max);
+        let mut extensions = Vec::new();
+        if strength > 0.0 {
+            let scale = strength.max(1.0);
+            fields.push(format!(
+                "\"emissiveFactor\":{}",
+                floats(&emissive.map(|channel| channel / scale))
+            ));
+            if scale > 1.0 {
+                self.use_extension("KHR_materials_emissive_strength");
+                extensions.push(format!(
+                    "\"KHR_materials_emissive_strength\":{{\"emissiveStrength\":{}}}",
+                    float(scale)
+                ));
+            }
+        }
+        if material.unlit {
+            self.use_extension("KHR_materials_unlit");
+            extensions.push("\"KHR_materials_unlit\":{}".to_string());
+        }
+        if !extensions.is_empty() {
+            fields.push(format!("\"extensions\":{{{}}}", extensions.join(",")));
+        }
+
+        match material.alpha_mode {
+            AlphaMode::Opaque => {}
+            AlphaMode::Mask(cutoff) => {
+                fields.push("\"alphaMode\":\"MASK\"".to_string());
+                fields.push(format!("\"alphaCutoff\":{}", float(cutoff)));
+            }
+            _ => fields.push("\"alphaMode\":\"BLEND\"".to_string()),
+        }
+        if material.double_sided {
+            fields.push("\"doubleSided\":true".to_string());
+        }
+
+        self.material_json.push(format!("{{{}}}", fields.join(",")));
+        let index = self.material_json.len() - 1;
+        self.material_index.insert(handle.id(), index);
+        Some(index)
+    }
+
+    /// Textures are referenced by path, relative to the exported file. Textures Granite didn't load from a file are left out
+    fn texture(&mut self, handle: Option<&Handle<Image>>) -> Option<usize> {
+        let path = *self.image_paths.get(&handle?.id())?;
+        if let Some(index) = self.texture_index.get(path) {
+            return Some(*index);
+        }
+        let absolute = PathBuf::from(rel_asset_to_absolute(path).as_ref());
+        let uri = relative_path(&self.output_dir, &absolute)
+            .to_string_lossy()
+            .replace('\\', "/")
+            .replace(' ', "%20");
+        self.image_json
+            .push(format!("{{\"uri\":{}}}", string(&uri)));
+        self.texture_json
+            .push(format!("{{\"source\":{}}}", self.image_json.len() - 1));
+        let index = self.texture_json.len() - 1;
+        self.texture_index.insert(path.clone(), index);
+        Some(index)
+    }
+
+    fn use_extension(&mut self, extension: &'static str) {
+        if !self.extensions_used.contains(&extension) {
+            self.extensions_used.push(extension);
+        }
+    }
+
+    fn finish(mut self, nodes: Vec<String>, roots: Vec<usize>) -> GltfDocument {
+        self.summary.meshes = self.mesh_json.len();
+        self.summary.materials = self.material_json.len();
+        self.summary.textures = self.texture_json.len();
+
+        let mut sections = vec![
+            "\"asset\":{\"version\":\"2.0\",\"generator\":\"bevy_granite\"}".to_string(),
+            "\"scene\":0".to_string(),
+            format!("\"scenes\":[{{\"nodes\":[{}]}}]", indices(&roots)),
+            format!("\"nodes\":[{}]", nodes.join(",")),
+        ];
+        let arrays = [
+            ("meshes", &self.mesh_json),
+            ("materials", &self.material_json),
+            ("images", &self.image_json),
+            ("textures", &self.texture_json),
+            ("accessors", &self.accessors),
+            ("bufferViews", &self.buffer_views),
+        ];
+        for (key, values) in arrays {
+            if !values.is_empty() {
+                sections.push(format!("\"{}\":[{}]", key, values.join(",")));
+            }
+        }
+        if !self.bin.is_empty() {
+            let uri = self
+                .buffer_uri
+                .as_ref()
+                .map(|uri| format!(",\"uri\":{}", string(uri)))
+                .unwrap_or_default();
+            sections.push(format!(
+                "\"buffers\":[{{\"byteLength\":{}{}}}]",
+                self.bin.len(),
+                uri
+            ));
+        }
+        if !self.extensions_used.is_empty() {
+            let used: Vec<String> = self.extensions_used.iter().map(|e| string(e)).collect();
+            sections.push(format!("\"extensionsUsed\":[{}]", used.join(",")));
+        }
+
+        GltfDocument {
+            json: format!("{{{}}}", sections.join(",")),
+            bin: self.bin,
+            summary: self.summary,
+        }
+    }
+}
+
+fn node_json(name: &str, transform: &Transform, mesh: Option<usize>, children: &[usize]) -> String {
+    let rotation = transform.rotation.normalize();
+    let mut fields = vec![
+        format!("\"name\":{}", string(name)),
+        format!(
+            "\"translation\":{}",
+            floats(&transform.translation.to_array())
+        ),
+        format!("\"rotation\":{}", floats(&rotation.to_array())),
+        format!("\"scale\":{}", floats(&transform.scale.to_array())),
+    ];
+    if let Some(mesh) = mesh {
+        fields.push(format!("\"mesh\":{}", mesh));
+    }
+    if !children.is_empty() {
+        fields.push(format!("\"children\":[{}]", indices(children)));
+    }
+    format!("{{{}}}", fields.join(","))
+}
+
+fn is_glb(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("glb"))
+}
+
+fn f32_bytes<'a>(values: impl Iterator<Item = &'a f32>) -> Vec<u8> {
+    values.flat_map(|value| value.to_le_bytes()).collect()
+}
+
+/// JSON has no NaN or infinity
+fn float(value: f32) -> String {
+    if value.is_finite() {
+        value.to_string()
+    } else {
+        "0".to_string()
+    }
+}
+
+fn floats(values: &[f32]) -> String {
+    let values: Vec<String> = values.iter().map(|value| float(*value)).collect();
+    format!("[{}]", values.join(","))
+}
+
+fn indices(values: &[usize]) -> String {
+    let values: Vec<String> = values.iter().map(usize::to_string).collect();
+    values.join(",")
+}
+
+fn string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for character in value.chars() {
+        match character {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+/// Path of `to` as seen from the `from` folder
+fn relative_path(from: &Path, to: &Path) -> PathBuf {
+    let from = from.canonicalize().unwrap_or_else(|_| from.to_path_buf());
+    let to = to.canonicalize().unwrap_or_else(|_| to.to_path_buf());
+    let from: Vec<Component> = from.components().collect();
+    let to_components: Vec<Component> = to.components().collect();
+    let shared = from
+        .iter()
+        .zip(&to_components)
+        .take_while(|(a, b)| a == b)
+        .count();
+    // Different drives, nothing to be relative to
+    if shared == 0 {
+        return to;
+    }
+    let mut relative = PathBuf::new();
+    for _ in shared..from.len() {
+        relative.push("..");
+    }
+    for component in &to_components[shared..] {
+        relative.push(component);
+    }
+    relative
+}
+
+/// Exports serializable entities to glTF when requested
+pub fn export_gltf_system(
+    mut requests: MessageReader<RequestExportGltf>,
+    mut exported_writer: MessageWriter<GltfExportedEvent>,
+    capabilities: Res<EditorCapabilities>,
+    sources: GltfExportSources,
+) {
+    for request in requests.read() {
+        if !capabilities.can_use_files(&request.path) {
+            continue;
+        }
+        let path = PathBuf::from(rel_asset_to_absolute(&request.path).as_ref());
+        let entities = sources.collect(request.source.as_deref());
+        let document = sources.build(&entities, &path);
+
+        match document.write(&path) {
+            Ok(()) => {
+                let summary = &document.summary;
+                log!(
+                    LogType::Game,
+                    LogLevel::OK,
+                    LogCategory::Asset,
+                    "Exported {} nodes, {} meshes, {} materials and {} textures to: {}",
+                    summary.nodes,
+                    summary.meshes,
+                    summary.materials,
+                    summary.textures,
+                    path.display()
+                );
+                if summary.skipped_meshes > 0 {
+                    log!(
+                        LogType::Game,
+                        LogLevel::Warning,
+                        LogCategory::Asset,
+                        "Skipped {} meshes that aren't triangle lists or aren't in memory",
+                        summary.skipped_meshes
+                    );
+                }
+                exported_writer.write(GltfExportedEvent {
+                    path: path.to_string_lossy().replace('\\', "/"),
+                    summary: document.summary,
+                });
+            }
+            Err(e) => log!(
+                LogType::Game,
+                LogLevel::Error,
+                LogCategory::Asset,
+                "Failed to export glTF to '{}': {}",
+                path.display(),
+                e
+            ),
+        }
+    }
+}
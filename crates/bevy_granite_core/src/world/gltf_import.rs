@@ -0,0 +1,1190 @@
+use crate::{
+    assets::{AvailableEditableMaterials, MaterialData, StandardMaterialDef},
+    entities::{
+        spawn_scene_entities, write_obj, Empty, EntitySaveReadyData, GraniteTypes, IdentityData,
+        PromptImportSettings, SaveSettings, SceneSpawnTarget, TransformData, OBJ,
+    },
+    events::{GltfImportedEvent, RequestImportGltf},
+    shared::{absolute_asset_to_rel, rel_asset_to_absolute, EditorCapabilities},
+};
+use bevy::{
+    ecs::system::SystemParam,
+    math::{Mat4, Quat, Vec3},
+    pbr::StandardMaterial,
+    prelude::{AssetServer, Assets, Commands, Mesh, MessageReader, MessageWriter, Res, ResMut},
+};
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use uuid::Uuid;
+
+// gltf_import.rs
+// Turns a .gltf/.glb into regular editable Granite entities, the counterpart of gltf_export.rs
+// Every triangle primitive is written to an .obj under models/imported/<file>/, every material to a .mat under
+// materials/imported/<file>/ and embedded textures to textures/imported/<file>/. The files are then owned by the project
+// Nodes spawn as Empty (or OBJ when they hold a single primitive) under one root named after the file, keeping names
+// and hierarchy, so the node tree mirrors the glTF scene. Skins, animations and morph targets are not imported
+
+const GLB_MAGIC: u32 = 0x4654_6C67;
+const GLB_CHUNK_JSON: u32 = 0x4E4F_534A;
+const GLB_CHUNK_BIN: u32 = 0x004E_4942;
+
+const COMPONENT_I8: u64 = 5120;
+const COMPONENT_U8: u64 = 5121;
+const COMPONENT_I16: u64 = 5122;
+const COMPONENT_U16: u64 = 5123;
+const COMPONENT_U32: u64 = 5125;
+const COMPONENT_F32: u64 = 5126;
+
+const MODE_TRIANGLES: u64 = 4;
+
+const DEFAULT_MATERIAL: &str = "materials/internal/default.mat";
+
+/// What an import wrote and spawned
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GltfImportSummary {
+    pub nodes: usize,
+    pub meshes: usize,
+    pub materials: usize,
+    pub textures: usize,
+    /// Primitives that aren't triangle lists with positions
+    pub skipped_primitives: usize,
+}
+
+// ---------------------------------------------------------------------------------------------------
+// JSON
+
+/// Just enough JSON to read a glTF document
+#[derive(Debug, Clone, PartialEq)]
+enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    fn parse(text: &str) -> Result<Json, String> {
+        let mut parser = JsonParser {
+            bytes: text.as_bytes(),
+            pos: 0,
+        };
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        if parser.pos != parser.bytes.len() {
+            return Err(format!("unexpected data at byte {}", parser.pos));
+        }
+        Ok(value)
+    }
+
+    fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(fields) => fields
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// Element of an array field, i.e. `json.at("nodes", 3)`
+    fn at(&self, key: &str, index: usize) -> Option<&Json> {
+        self.get(key)?.items().get(index)
+    }
+
+    fn items(&self) -> &[Json] {
+        match self {
+            Json::Array(items) => items,
+            _ => &[],
+        }
+    }
+
+    fn as_f64(&self) -> Option<f64> {
+        match self {
+            Json::Number(number) => Some(*number),
+            _ => None,
+        }
+    }
+
+    fn as_usize(&self) -> Option<usize> {
+        self.as_f64()
+            .filter(|number| *number >= 0.0)
+            .map(|number| number as usize)
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    fn as_bool(&self) -> Option<bool> {
+        match self {
+            Json::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    fn usize(&self, key: &str) -> Option<usize> {
+        self.get(key)?.as_usize()
+    }
+
+    fn str(&self, key: &str) -> Option<&str> {
+        self.get(key)?.as_str()
+    }
+
+    fn floats(&self, key: &str) -> Option<Vec<f32>> {
+        let items = self.get(key)?.items();
+        items
+            .iter()
+            .map(|item| item.as_f64().map(|number| number as f32))
+            .collect()
+    }
+}
+
+struct JsonParser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl JsonParser<'_> {
+    fn skip_whitespace(&mut self) {
+        while self
+            .bytes
+            .get(self.pos)
+            .is_some_and(|byte| byte.is_ascii_whitespace())
+        {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_whitespace();
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), String> {
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(format!("expected '{}' at byte {}", byte as char, self.pos))
+        }
+    }
+
+    fn literal(&mut self, literal: &str, value: Json) -> Result<Json, String> {
+        if self.bytes[self.pos..].starts_with(literal.as_bytes()) {
+            self.pos += literal.len();
+            Ok(value)
+        } else {
+            Err(format!("invalid literal at byte {}", self.pos))
+        }
+    }
+
+    fn value(&mut self) -> Result<Json, String> {
+        match self.peek() {
+            Some(b'{') => self.object(),
+            Some(b'[') => self.array(),
+            Some(b'"') => self.string().map(Json::String),
+            Some(b't') => self.literal("true", Json::Bool(true)),
+            Some(b'f') => self.literal("false", Json::Bool(false)),
+            Some(b'n') => self.literal("null", Json::Null),
+            Some(_) => self.number(),
+            None => Err("unexpected end of data".to_string()),
+        }
+    }
+
+    fn object(&mut self) -> Result<Json, String> {
+        self.expect(b'{')?;
+        let mut fields = Vec::new();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(Json::Object(fields));
+        }
+        loop {
+            if self.peek() != Some(b'"') {
+                return Err(format!("expected a key at byte {}", self.pos));
+            }
+            let key = self.string()?;
+            self.expect(b':')?;
+            fields.push((key, self.value()?));
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(Json::Object(fields));
+                }
+                _ => return Err(format!("expected ',' or '}}' at byte {}", self.pos)),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Json, String> {
+        self.expect(b'[')?;
+        let mut items = Vec::new();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(Json::Array(items));
+        }
+        loop {
+            items.push(self.value()?);
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(Json::Array(items));
+                }
+                _ => return Err(format!("expected ',' or ']' at byte {}", self.pos)),
+            }
+        }
+    }
+
+    fn hex4(&mut self) -> Result<u32, String> {
+        let digits = self
+            .bytes
+            .get(self.pos..self.pos + 4)
+            .and_then(|digits| std::str::from_utf8(digits).ok())
+            .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+            .ok_or_else(|| format!("invalid unicode escape at byte {}", self.pos))?;
+        self.pos += 4;
+        Ok(digits)
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.expect(b'"')?;
+        let mut bytes = Vec::new();
+        loop {
+            let Some(&byte) = self.bytes.get(self.pos) else {
+                return Err("unterminated string".to_string());
+            };
+            self.pos += 1;
+            match byte {
+                b'"' => break,
+                b'\\' => {
+                    let Some(&escape) = self.bytes.get(self.pos) else {
+                        return Err("unterminated string".to_string());
+                    };
+                    self.pos += 1;
+                    let unescaped = match escape {
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'u' => {
+                            let mut code = self.hex4()?;
+                            // Surrogate pair
+                            if (0xD800..0xDC00).contains(&code)
+                                && self.bytes[self.pos..].starts_with(b"\\u")
+                            {
+                                self.pos += 2;
+                                let low = self.hex4()?;
+                                code = 0x10000
+                                    + ((code - 0xD800) << 10)
+                                    + (low.wrapping_sub(0xDC00) & 0x3FF);
+                            }
+                            char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER)
+                        }
+                        _ => return Err(format!("invalid escape at byte {}", self.pos)),
+                    };
+                    let mut buffer = [0; 4];
+                    bytes.extend_from_slice(unescaped.encode_utf8(&mut buffer).as_bytes());
+                }
+                _ => bytes.push(byte),
+            }
+        }
+        String::from_utf8(bytes).map_err(|e| e.to_string())
+    }
+
+    fn number(&mut self) -> Result<Json, String> {
+        let start = self.pos;
+        while self
+            .bytes
+            .get(self.pos)
+            .is_some_and(|byte| matches!(byte, b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9'))
+        {
+            self.pos += 1;
+        }
+        std::str::from_utf8(&self.bytes[start..self.pos])
+            .ok()
+            .and_then(|number| number.parse::<f64>().ok())
+            .map(Json::Number)
+            .ok_or_else(|| format!("invalid value at byte {}", start))
+    }
+}
+
+/// Decodes standard base64, as used by data: URIs
+fn decode_base64(text: &str) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::with_capacity(text.len() * 3 / 4);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for byte in text.bytes() {
+        let value = match byte {
+            b'A'..=b'Z' => byte - b'A',
+            b'a'..=b'z' => byte - b'a' + 26,
+            b'0'..=b'9' => byte - b'0' + 52,
+            b'+' | b'-' => 62,
+            b'/' | b'_' => 63,
+            b'=' | b'\r' | b'\n' | b' ' => continue,
+            _ => return Err(format!("invalid base64 character '{}'", byte as char)),
+        };
+        buffer = (buffer << 6) | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+    Ok(bytes)
+}
+
+/// Decodes %XX escapes in relative glTF URIs
+fn decode_uri(uri: &str) -> String {
+    let bytes = uri.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let escaped = (bytes[index] == b'%')
+            .then(|| bytes.get(index + 1..index + 3))
+            .flatten()
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            None => {
+                decoded.push(bytes[index]);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Keeps a name usable as a file name
+fn file_safe_name(name: &str) -> String {
+    let safe: String = name
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if safe.is_empty() {
+        "unnamed".to_string()
+    } else {
+        safe
+    }
+}
+
+fn unique_name(used: &mut HashSet<String>, base: String) -> String {
+    let mut name = base.clone();
+    let mut suffix = 1;
+    while !used.insert(name.to_lowercase()) {
+        name = format!("{}_{}", base, suffix);
+        suffix += 1;
+    }
+    name
+}
+
+// ---------------------------------------------------------------------------------------------------
+// Reading
+
+/// A parsed glTF document with its buffers loaded
+struct GltfSource {
+    json: Json,
+    buffers: Vec<Vec<u8>>,
+    dir: PathBuf,
+}
+
+impl GltfSource {
+    fn read(path: &Path) -> Result<Self, String> {
+        let data = fs::read(path).map_err(|e| e.to_string())?;
+        let dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+
+        let (json, glb_bin) = if data.len() >= 12 && read_u32(&data, 0) == Some(GLB_MAGIC) {
+            let (json, bin) = Self::read_glb(&data)?;
+            (json, bin)
+        } else {
+            let text = std::str::from_utf8(&data).map_err(|e| e.to_string())?;
+            (Json::parse(text)?, None)
+        };
+
+        let mut glb_bin = glb_bin;
+        let mut buffers = Vec::new();
+        for (index, buffer) in json
+            .get("buffers")
+            .map(Json::items)
+            .unwrap_or(&[])
+            .iter()
+            .enumerate()
+        {
+            let bytes = match buffer.str("uri") {
+                Some(uri) => read_uri(&dir, uri)?,
+                None => glb_bin.take().ok_or_else(|| {
+                    format!(
+                        "buffer {} has no uri and there is no GLB binary chunk",
+                        index
+                    )
+                })?,
+            };
+            buffers.push(bytes);
+        }
+
+        Ok(Self { json, buffers, dir })
+    }
+
+    fn read_glb(data: &[u8]) -> Result<(Json, Option<Vec<u8>>), String> {
+        let mut offset = 12;
+        let mut json = None;
+        let mut bin = None;
+        while let (Some(length), Some(kind)) = (read_u32(data, offset), read_u32(data, offset + 4))
+        {
+            let start = offset + 8;
+            let end = start + length as usize;
+            let chunk = data
+                .get(start..end)
+                .ok_or_else(|| "GLB chunk runs past the end of the file".to_string())?;
+            match kind {
+                GLB_CHUNK_JSON => {
+                    let text = std::str::from_utf8(chunk).map_err(|e| e.to_string())?;
+                    json = Some(Json::parse(text)?);
+                }
+                GLB_CHUNK_BIN if bin.is_none() => bin = Some(chunk.to_vec()),
+                _ => {}
+            }
+            offset = end;
+        }
+        let json = json.ok_or_else(|| "GLB has no JSON chunk".to_string())?;
+        Ok((json, bin))
+    }
+
+    fn buffer_view(&self, index: usize) -> Option<(&[u8], Option<usize>)> {
+        let view = self.json.at("bufferViews", index)?;
+        let buffer = self.buffers.get(view.usize("buffer")?)?;
+        let start = view.usize("byteOffset").unwrap_or(0);
+        let bytes = buffer.get(start..start + view.usize("byteLength")?)?;
+        Some((bytes, view.usize("byteStride")))
+    }
+
+    /// Accessor values as floats, `components` per element. Normalized integers are mapped to 0..1 or -1..1
+    fn read_floats(&self, index: usize) -> Option<(Vec<f32>, usize)> {
+        let accessor = self.json.at("accessors", index)?;
+        let normalized = accessor
+            .get("normalized")
+            .and_then(Json::as_bool)
+            .unwrap_or(false);
+        let component_type = accessor.get("componentType")?.as_f64()? as u64;
+        let (values, components) = self.read_accessor(accessor, component_type)?;
+        let floats = values
+            .into_iter()
+            .map(|value| match (component_type, normalized) {
+                (COMPONENT_U8, true) => value as f32 / 255.0,
+                (COMPONENT_U16, true) => value as f32 / 65535.0,
+                (COMPONENT_I8, true) => (value as f32 / 127.0).max(-1.0),
+                (COMPONENT_I16, true) => (value as f32 / 32767.0).max(-1.0),
+                _ => value as f32,
+            })
+            .collect();
+        Some((floats, components))
+    }
+
+    fn read_indices(&self, index: usize) -> Option<Vec<u32>> {
+        let accessor = self.json.at("accessors", index)?;
+        let component_type = accessor.get("componentType")?.as_f64()? as u64;
+        if !matches!(component_type, COMPONENT_U8 | COMPONENT_U16 | COMPONENT_U32) {
+            return None;
+        }
+        let (values, _) = self.read_accessor(accessor, component_type)?;
+        Some(values.into_iter().map(|value| value as u32).collect())
+    }
+
+    fn read_accessor(&self, accessor: &Json, component_type: u64) -> Option<(Vec<f64>, usize)> {
+        let count = accessor.usize("count")?;
+        let components = match accessor.str("type")? {
+            "SCALAR" => 1,
+            "VEC2" => 2,
+            "VEC3" => 3,
+            "VEC4" => 4,
+            "MAT2" => 4,
+            "MAT3" => 9,
+            "MAT4" => 16,
+            _ => return None,
+        };
+        let component_size = match component_type {
+            COMPONENT_I8 | COMPONENT_U8 => 1,
+            COMPONENT_I16 | COMPONENT_U16 => 2,
+            COMPONENT_U32 | COMPONENT_F32 => 4,
+            _ => return None,
+        };
+
+        // No buffer view means all zeros
+        let Some(view_index) = accessor.usize("bufferView") else {
+            return Some((vec![0.0; count * components], components));
+        };
+        let (bytes, stride) = self.buffer_view(view_index)?;
+        let stride = stride.unwrap_or(component_size * components);
+        let offset = accessor.usize("byteOffset").unwrap_or(0);
+
+        let mut values = Vec::with_capacity(count * components);
+        for element in 0..count {
+            for component in 0..components {
+                let at = offset + element * stride + component * component_size;
+                let raw = bytes.get(at..at + component_size)?;
+                values.push(match component_type {
+                    COMPONENT_I8 => raw[0] as i8 as f64,
+                    COMPONENT_U8 => raw[0] as f64,
+                    COMPONENT_I16 => i16::from_le_bytes([raw[0], raw[1]]) as f64,
+                    COMPONENT_U16 => u16::from_le_bytes([raw[0], raw[1]]) as f64,
+                    COMPONENT_U32 => u32::from_le_bytes([raw[0], raw[1], raw[2], raw[3]]) as f64,
+                    _ => f32::from_le_bytes([raw[0], raw[1], raw[2], raw[3]]) as f64,
+                });
+            }
+        }
+        Some((values, components))
+    }
+
+    /// Bytes of an image and the extension to save it with
+    fn image_bytes(&self, image: &Json) -> Result<(Vec<u8>, &'static str), String> {
+        let extension = match image.str("mimeType") {
+            Some("image/jpeg") => "jpg",
+            Some("image/ktx2") => "ktx2",
+            _ => "png",
+        };
+        if let Some(uri) = image.str("uri") {
+            let extension = match uri.strip_prefix("data:") {
+                Some(data) if data.starts_with("image/jpeg") => "jpg",
+                Some(_) => extension,
+                None => match Path::new(uri).extension().and_then(|ext| ext.to_str()) {
+                    Some("jpg") | Some("jpeg") => "jpg",
+                    Some("ktx2") => "ktx2",
+                    _ => extension,
+                },
+            };
+            return Ok((read_uri(&self.dir, uri)?, extension));
+        }
+        let view = image
+            .usize("bufferView")
+            .ok_or_else(|| "image has neither uri nor bufferView".to_string())?;
+        let (bytes, _) = self
+            .buffer_view(view)
+            .ok_or_else(|| format!("image buffer view {} is out of range", view))?;
+        Ok((bytes.to_vec(), extension))
+    }
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset + 4)?;
+    Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+/// Contents of a buffer or image uri, either embedded base64 or a file next to the glTF
+fn read_uri(dir: &Path, uri: &str) -> Result<Vec<u8>, String> {
+    if let Some(data) = uri.strip_prefix("data:") {
+        let (_, encoded) = data
+            .split_once(";base64,")
+            .ok_or_else(|| "only base64 data uris are supported".to_string())?;
+        return decode_base64(encoded);
+    }
+    let path = dir.join(decode_uri(uri));
+    fs::read(&path).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+// ---------------------------------------------------------------------------------------------------
+// Converting
+
+/// An .obj written for one primitive, with the .mat it uses
+struct ImportedPrimitive {
+    mesh_path: String,
+    material_path: String,
+}
+
+/// Converts a glTF document into asset files and scene entities
+struct GltfImporter<'a> {
+    source: &'a GltfSource,
+    stem: String,
+    create_materials: bool,
+    used_names: HashSet<String>,
+    meshes: HashMap<usize, Vec<ImportedPrimitive>>,
+    materials: HashMap<usize, String>,
+    images: HashMap<usize, Option<String>>,
+    entities: Vec<EntitySaveReadyData>,
+    summary: GltfImportSummary,
+}
+
+impl<'a> GltfImporter<'a> {
+    fn new(source: &'a GltfSource, stem: String, create_materials: bool) -> Self {
+        Self {
+            source,
+            stem,
+            create_materials,
+            used_names: HashSet::new(),
+            meshes: HashMap::new(),
+            materials: HashMap::new(),
+            images: HashMap::new(),
+            entities: Vec::new(),
+            summary: GltfImportSummary::default(),
+        }
+    }
+
+    /// Asset relative path of a new file in the import folder of `kind`, i.e. models/imported/<file>/<name>.obj
+    fn asset_path(&mut self, kind: &str, name: &str, extension: &str) -> String {
+        let name = unique_name(
+            &mut self.used_names,
+            format!("{}/{}", kind, file_safe_name(name)),
+        );
+        let name = name.split_once('/').map_or(name.as_str(), |(_, name)| name);
+        format!("{}/imported/{}/{}.{}", kind, self.stem, name, extension)
+    }
+
+    fn write_asset(path: &str, contents: &[u8]) -> Result<(), String> {
+        let abs = PathBuf::from(rel_asset_to_absolute(path).as_ref());
+        if let Some(parent) = abs.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        fs::write(&abs, contents).map_err(|e| format!("{}: {}", abs.display(), e))
+    }
+
+    fn import(&mut self) -> Result<(), String> {
+        let json = &self.source.json;
+        let root_nodes: Vec<usize> = match json
+            .usize("scene")
+            .and_then(|scene| json.at("scenes", scene))
+            .or_else(|| json.at("scenes", 0))
+        {
+            Some(scene) => scene
+                .get("nodes")
+                .map(Json::items)
+                .unwrap_or(&[])
+                .iter()
+                .filter_map(Json::as_usize)
+                .collect(),
+            // No scenes, every node that isn't a child is a root
+            None => {
+                let children: HashSet<usize> = json
+                    .get("nodes")
+                    .map(Json::items)
+                    .unwrap_or(&[])
+                    .iter()
+                    .flat_map(|node| node.get("children").map(Json::items).unwrap_or(&[]))
+                    .filter_map(Json::as_usize)
+                    .collect();
+                (0..json.get("nodes").map_or(0, |nodes| nodes.items().len()))
+                    .filter(|node| !children.contains(node))
+                    .collect()
+            }
+        };
+
+        let root = Uuid::new_v4();
+        self.entities.push(EntitySaveReadyData {
+            identity: IdentityData {
+                uuid: root,
+                name: self.stem.clone(),
+                class: GraniteTypes::Empty(Empty::default()),
+            },
+            transform: TransformData {
+                position: Vec3::ZERO,
+                rotation: Quat::IDENTITY,
+                scale: Vec3::ONE,
+            },
+            parent: None,
+            components: None,
+        });
+
+        let mut visited = HashSet::new();
+        for node in root_nodes {
+            self.import_node(node, root, &mut visited)?;
+        }
+        Ok(())
+    }
+
+    fn import_node(
+        &mut self,
+        index: usize,
+        parent: Uuid,
+        visited: &mut HashSet<usize>,
+    ) -> Result<(), String> {
+        // Nodes may only have one parent, a cycle would recurse forever
+        if !visited.insert(index) {
+            return Ok(());
+        }
+        let Some(node) = self.source.json.at("nodes", index) else {
+            return Err(format!("node {} is out of range", index));
+        };
+
+        let (scale, rotation, position) = match node.floats("matrix") {
+            Some(matrix) if matrix.len() == 16 => {
+                let mut columns = [0.0; 16];
+                columns.copy_from_slice(&matrix);
+                Mat4::from_cols_array(&columns).to_scale_rotation_translation()
+            }
+            _ => (
+                node.floats("scale")
+                    .filter(|scale| scale.len() == 3)
+                    .map_or(Vec3::ONE, |scale| Vec3::from_slice(&scale)),
+                node.floats("rotation")
+                    .filter(|rotation| rotation.len() == 4)
+                    .map_or(Quat::IDENTITY, |rotation| {
+                        Quat::from_slice(&rotation).normalize()
+                    }),
+                node.floats("translation")
+                    .filter(|translation| translation.len() == 3)
+                    .map_or(Vec3::ZERO, |translation| Vec3::from_slice(&translation)),
+            ),
+        };
+
+        let mesh = node.usize("mesh");
+        let name = node
+            .str("name")
+            .or_else(|| mesh.and_then(|mesh| self.source.json.at("meshes", mesh)?.str("name")))
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("Node {}", index));
+
+        if let Some(mesh) = mesh {
+            self.import_mesh(mesh)?;
+        }
+        let primitives = mesh
+            .and_then(|mesh| self.meshes.get(&mesh))
+            .map_or(0, Vec::len);
+
+        let uuid = Uuid::new_v4();
+        let class = if primitives == 1 {
+            self.obj_class(mesh.unwrap_or_default(), 0)
+        } else {
+            GraniteTypes::Empty(Empty::default())
+        };
+        self.entities.push(EntitySaveReadyData {
+            identity: IdentityData {
+                uuid,
+                name: name.clone(),
+                class,
+            },
+            transform: TransformData {
+                position,
+                rotation,
+                scale,
+            },
+            parent: Some(parent),
+            components: None,
+        });
+        self.summary.nodes += 1;
+
+        // Several primitives, each gets its own child
+        if primitives > 1 {
+            for primitive in 0..primitives {
+                let class = self.obj_class(mesh.unwrap_or_default(), primitive);
+                self.entities.push(EntitySaveReadyData {
+                    identity: IdentityData {
+                        uuid: Uuid::new_v4(),
+                        name: format!("{} {}", name, primitive),
+                        class,
+                    },
+                    transform: TransformData {
+                        position: Vec3::ZERO,
+                        rotation: Quat::IDENTITY,
+                        scale: Vec3::ONE,
+                    },
+                    parent: Some(uuid),
+                    components: None,
+                });
+            }
+        }
+
+        let children: Vec<usize> = node
+            .get("children")
+            .map(Json::items)
+            .unwrap_or(&[])
+            .iter()
+            .filter_map(Json::as_usize)
+            .collect();
+        for child in children {
+            self.import_node(child, uuid, visited)?;
+        }
+        Ok(())
+    }
+
+    fn obj_class(&self, mesh: usize, primitive: usize) -> GraniteTypes {
+        let imported = &self.meshes[&mesh][primitive];
+        GraniteTypes::OBJ(OBJ {
+            mesh_path: imported.mesh_path.clone().into(),
+            material: MaterialData::new(imported.material_path.clone()),
+            reload_requested: false,
+        })
+    }
+
+    /// Writes an .obj for every triangle primitive of a mesh, once per mesh
+    fn import_mesh(&mut self, index: usize) -> Result<(), String> {
+        if self.meshes.contains_key(&index) {
+            return Ok(());
+        }
+        let Some(mesh) = self.source.json.at("meshes", index) else {
+            return Err(format!("mesh {} is out of range", index));
+        };
+        let mesh_name = mesh
+            .str("name")
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("mesh_{}", index));
+        let primitives = mesh.get("primitives").map(Json::items).unwrap_or(&[]);
+        let single = primitives.len() == 1;
+
+        let mut imported = Vec::new();
+        for (primitive_index, primitive) in primitives.iter().enumerate() {
+            let name = if single {
+                mesh_name.clone()
+            } else {
+                format!("{}_{}", mesh_name, primitive_index)
+            };
+            let Some(contents) = self.primitive_obj(primitive, &name) else {
+                self.summary.skipped_primitives += 1;
+                continue;
+            };
+            let mesh_path = self.asset_path("models", &name, "obj");
+            Self::write_asset(&mesh_path, contents.as_bytes())?;
+            self.summary.meshes += 1;
+
+            let material_path = match primitive.usize("material") {
+                Some(material) if self.create_materials => self.import_material(material)?,
+                _ => DEFAULT_MATERIAL.to_string(),
+            };
+            imported.push(ImportedPrimitive {
+                mesh_path,
+                material_path,
+            });
+        }
+        self.meshes.insert(index, imported);
+        Ok(())
+    }
+
+    /// OBJ text of a primitive, None when it has no triangles to write
+    fn primitive_obj(&self, primitive: &Json, name: &str) -> Option<String> {
+        let mode = primitive.usize("mode").unwrap_or(MODE_TRIANGLES as usize) as u64;
+        if mode != MODE_TRIANGLES {
+            return None;
+        }
+        let attributes = primitive.get("attributes")?;
+        let (positions, position_components) =
+            self.source.read_floats(attributes.usize("POSITION")?)?;
+        if position_components != 3 {
+            return None;
+        }
+        let vertex_count = positions.len() / 3;
+        let normals = attributes
+            .usize("NORMAL")
+            .and_then(|accessor| self.source.read_floats(accessor))
+            .filter(|(normals, components)| *components == 3 && normals.len() == positions.len())
+            .map(|(normals, _)| normals);
+        let uvs = attributes
+            .usize("TEXCOORD_0")
+            .and_then(|accessor| self.source.read_floats(accessor))
+            .filter(|(uvs, components)| *components == 2 && uvs.len() == vertex_count * 2)
+            .map(|(uvs, _)| uvs);
+        let indices = match primitive.usize("indices") {
+            Some(accessor) => self.source.read_indices(accessor)?,
+            None => (0..vertex_count as u32).collect(),
+        };
+        if indices.len() < 3 || indices.iter().any(|index| *index as usize >= vertex_count) {
+            return None;
+        }
+
+        let material_name = primitive
+            .usize("material")
+            .and_then(|material| self.source.json.at("materials", material)?.str("name"))
+            .unwrap_or("default");
+
+        let vec3s = |values: &[f32]| -> Vec<[f32; 3]> {
+            values
+                .chunks_exact(3)
+                .map(|value| [value[0], value[1], value[2]])
+                .collect()
+        };
+        let positions = vec3s(&positions);
+        let normals = normals.map(|normals| vec3s(&normals));
+        let uvs: Option<Vec<[f32; 2]>> =
+            uvs.map(|uvs| uvs.chunks_exact(2).map(|uv| [uv[0], uv[1]]).collect());
+        Some(write_obj(
+            name,
+            Some(&file_safe_name(material_name)),
+            &positions,
+            normals.as_deref(),
+            uvs.as_deref(),
+            &indices,
+        ))
+    }
+
+    /// Writes a .mat for a glTF material, once per material
+    fn import_material(&mut self, index: usize) -> Result<String, String> {
+        if let Some(path) = self.materials.get(&index) {
+            return Ok(path.clone());
+        }
+        let Some(material) = self.source.json.at("materials", index) else {
+            return Err(format!("material {} is out of range", index));
+        };
+        let name = material
+            .str("name")
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("material_{}", index));
+
+        // glTF factors are linear, .mat colors are sRGB
+        let srgb = |linear: f32| {
+            let linear = linear.clamp(0.0, 1.0);
+            if linear <= 0.003_130_8 {
+                linear * 12.92
+            } else {
+                1.055 * linear.powf(1.0 / 2.4) - 0.055
+            }
+        };
+
+        let mut def = StandardMaterialDef {
+            friendly_name: name.clone(),
+            ..Default::default()
+        };
+        let pbr = material.get("pbrMetallicRoughness");
+        if let Some(color) = pbr
+            .and_then(|pbr| pbr.floats("baseColorFactor"))
+            .filter(|color| color.len() == 4)
+        {
+            def.base_color = Some((srgb(color[0]), srgb(color[1]), srgb(color[2]), color[3]));
+        }
+        def.metalness = Some(
+            pbr.and_then(|pbr| pbr.get("metallicFactor")?.as_f64())
+                .unwrap_or(1.0) as f32,
+        );
+        def.roughness = Some(
+            pbr.and_then(|pbr| pbr.get("roughnessFactor")?.as_f64())
+                .unwrap_or(1.0) as f32,
+        );
+        if let Some(emissive) = material
+            .floats("emissiveFactor")
+            .filter(|emissive| emissive.len() == 3 && emissive.iter().any(|channel| *channel > 0.0))
+        {
+            def.emissive = Some((srgb(emissive[0]), srgb(emissive[1]), srgb(emissive[2])));
+        }
+        def.alpha_mode = match material.str("alphaMode") {
+            Some("BLEND") => Some("Blend".to_string()),
+            Some("MASK") => Some("Mask".to_string()),
+            _ => None,
+        };
+        if material
+            .get("doubleSided")
+            .and_then(Json::as_bool)
+            .unwrap_or(false)
+        {
+            def.double_sided = Some(true);
+            def.cull_mode = Some("None".to_string());
+        }
+        if material
+            .get("extensions")
+            .and_then(|extensions| extensions.get("KHR_materials_unlit"))
+            .is_some()
+        {
+            def.unlit = Some(true);
+        }
+
+        let texture =
+            |importer: &mut Self, info: Option<&Json>| -> Result<Option<String>, String> {
+                match info.and_then(|info| info.usize("index")) {
+                    Some(texture) => importer.import_texture(texture),
+                    None => Ok(None),
+                }
+            };
+        def.base_color_texture = texture(self, pbr.and_then(|pbr| pbr.get("baseColorTexture")))?;
+        def.metallic_roughness_texture = texture(
+            self,
+            pbr.and_then(|pbr| pbr.get("metallicRoughnessTexture")),
+        )?;
+        def.normal_map_texture = texture(self, material.get("normalTexture"))?;
+        def.occlusion_map = texture(self, material.get("occlusionTexture"))?;
+        def.emissive_texture = texture(self, material.get("emissiveTexture"))?;
+
+        let path = self.asset_path("materials", &name, "mat");
+        let ron = ron::ser::to_string_pretty(&def, ron::ser::PrettyConfig::default())
+            .map_err(|e| e.to_string())?;
+        Self::write_asset(&path, ron.as_bytes())?;
+        self.summary.materials += 1;
+        self.materials.insert(index, path.clone());
+        Ok(path)
+    }
+
+    /// Asset path of a texture's image. Images already in the assets folder are used in place, others are copied in
+    fn import_texture(&mut self, index: usize) -> Result<Option<String>, String> {
+        let Some(image) = self
+            .source
+            .json
+            .at("textures", index)
+            .and_then(|texture| texture.usize("source"))
+        else {
+            return Ok(None);
+        };
+        if let Some(path) = self.images.get(&image) {
+            return Ok(path.clone());
+        }
+        let Some(image_json) = self.source.json.at("images", image) else {
+            return Err(format!("image {} is out of range", image));
+        };
+
+        if let Some(uri) = image_json
+            .str("uri")
+            .filter(|uri| !uri.starts_with("data:"))
+        {
+            let abs = self.source.dir.join(decode_uri(uri));
+            let rel = absolute_asset_to_rel(abs.to_string_lossy().into_owned());
+            if !Path::new(rel.as_ref()).is_absolute() {
+                let path = Some(rel.into_owned());
+                self.images.insert(image, path.clone());
+                return Ok(path);
+            }
+        }
+
+        let name = image_json
+            .str("name")
+            .map(str::to_string)
+            .or_else(|| {
+                image_json
+                    .str("uri")
+                    .filter(|uri| !uri.starts_with("data:"))
+                    .and_then(|uri| Path::new(uri).file_stem()?.to_str().map(str::to_string))
+            })
+            .unwrap_or_else(|| format!("image_{}", image));
+        let path = match self.source.image_bytes(image_json) {
+            Ok((bytes, extension)) => {
+                let path = self.asset_path("textures", &name, extension);
+                Self::write_asset(&path, &bytes)?;
+                self.summary.textures += 1;
+                Some(path)
+            }
+            Err(e) => {
+                log!(
+                    LogType::Game,
+                    LogLevel::Warning,
+                    LogCategory::Asset,
+                    "Skipped glTF image '{}': {}",
+                    name,
+                    e
+                );
+                None
+            }
+        };
+        self.images.insert(image, path.clone());
+        Ok(path)
+    }
+}
+
+/// Reads a glTF file and writes its meshes, materials and textures into the assets folder
+/// Returns the entities to spawn, parents first, under a root named after the file
+pub fn import_gltf(
+    path: &Path,
+    create_materials: bool,
+) -> Result<(Vec<EntitySaveReadyData>, GltfImportSummary), String> {
+    let source = GltfSource::read(path)?;
+    let stem = file_safe_name(
+        path.file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or("gltf"),
+    );
+    let mut importer = GltfImporter::new(&source, stem, create_materials);
+    importer.import()?;
+    Ok((importer.entities, importer.summary))
+}
+
+#[derive(SystemParam)]
+pub struct GltfImportSpawner<'w, 's> {
+    commands: Commands<'w, 's>,
+    asset_server: Res<'w, AssetServer>,
+    materials: ResMut<'w, Assets<StandardMaterial>>,
+    meshes: ResMut<'w, Assets<Mesh>>,
+    available_materials: ResMut<'w, AvailableEditableMaterials>,
+}
+
+pub fn import_gltf_system(
+    mut requests: MessageReader<RequestImportGltf>,
+    mut imported_writer: MessageWriter<GltfImportedEvent>,
+    capabilities: Res<EditorCapabilities>,
+    import_settings: Res<PromptImportSettings>,
+    mut spawner: GltfImportSpawner,
+) {
+    for request in requests.read() {
+        if !capabilities.can_use_files(&request.path) {
+            continue;
+        }
+        let path = PathBuf::from(rel_asset_to_absolute(&request.path).as_ref());
+        let (entities, summary) = match import_gltf(&path, import_settings.create_mat_on_import) {
+            Ok(imported) => imported,
+            Err(e) => {
+                log!(
+                    LogType::Game,
+                    LogLevel::Error,
+                    LogCategory::Asset,
+                    "Failed to import glTF '{}': {}",
+                    path.display(),
+                    e
+                );
+                continue;
+            }
+        };
+
+        spawn_scene_entities(
+            &spawner.asset_server,
+            &mut spawner.commands,
+            &mut spawner.materials,
+            &mut spawner.available_materials,
+            &mut spawner.meshes,
+            &entities,
+            &SceneSpawnTarget {
+                abs_path: rel_asset_to_absolute(&request.source),
+                save_settings: SaveSettings::Runtime,
+                transform_override: None,
+                file_indices: None,
+                loaded: HashMap::new(),
+                prefab_sources: None,
+                new_to_scene: true,
+                file_time: Duration::ZERO,
+            },
+        );
+
+        log!(
+            LogType::Game,
+            LogLevel::OK,
+            LogCategory::Asset,
+            "Imported {} nodes, {} meshes, {} materials and {} textures from: {}",
+            summary.nodes,
+            summary.meshes,
+            summary.materials,
+            summary.textures,
+            path.display()
+        );
+        if summary.skipped_primitives > 0 {
+            log!(
+                LogType::Game,
+                LogLevel::Warning,
+                LogCategory::Asset,
+                "Skipped {} primitives that aren't triangle lists with positions",
+                summary.skipped_primitives
+            );
+        }
+        imported_writer.write(GltfImportedEvent {
+            path: path.to_string_lossy().replace('\\', "/"),
+            source: request.source.clone(),
+            root: entities[0].identity.uuid,
+            summary,
+        });
+    }
+}
@@ -0,0 +1,139 @@
+use crate::{
+    absolute_asset_to_rel,
+    entities::{ComponentEditor, IdentityData, SpawnSource},
+    events::WorldLoadSuccessEvent,
+};
+use bevy::{
+    ecs::{change_detection::Tick, system::SystemChangeTick},
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    any::TypeId,
+    collections::{HashMap, HashSet},
+};
+
+// incremental.rs
+// Incremental scene saves. Entities untouched since they were loaded or last saved keep their record from disk
+// as it is, and only edited ones are serialized again. Edits are found from the change ticks of what a record holds:
+// identity, transform, parent, spawn source and the saved components. Nothing has to remember to mark them
+
+/// How RequestSaveEvent writes a scene
+#[derive(Reflect, Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SaveMode {
+    /// Every entity is serialized again
+    #[default]
+    Full,
+    /// Only edited entities are serialized again, the rest is copied from the file
+    Incremental,
+}
+
+/// Save mode and per entity dirty tracking
+/// i.e. `app.insert_resource(IncrementalSave::new(SaveMode::Incremental))`
+#[derive(Resource, Default, Debug, Clone)]
+pub struct IncrementalSave {
+    pub mode: SaveMode,
+    /// Change tick at which each entity last matched its record on disk
+    synced: HashMap<Entity, Tick>,
+    /// Dirty whatever their change ticks say
+    marked: HashSet<Entity>,
+}
+
+impl IncrementalSave {
+    pub fn new(mode: SaveMode) -> Self {
+        Self {
+            mode,
+            ..Default::default()
+        }
+    }
+
+    pub fn is_incremental(&self) -> bool {
+        self.mode == SaveMode::Incremental
+    }
+
+    /// Serialize an entity on the next save, for edits change ticks don't show
+    pub fn mark(&mut self, entity: Entity) {
+        self.marked.insert(entity);
+    }
+
+    /// The entity matches its record on disk as of `tick`
+    pub fn sync(&mut self, entity: Entity, tick: Tick) {
+        self.synced.insert(entity, tick);
+        self.marked.remove(&entity);
+    }
+
+    pub fn forget(&mut self, entity: Entity) {
+        self.synced.remove(&entity);
+        self.marked.remove(&entity);
+    }
+
+    /// Whether an entity may differ from its record on disk. Entities never loaded or saved always do
+    pub fn is_dirty(&self, world: &World, entity: Entity) -> bool {
+        if self.marked.contains(&entity) {
+            return true;
+        }
+        let (Some(since), Ok(entity_ref)) = (self.synced.get(&entity), world.get_entity(entity))
+        else {
+            return true;
+        };
+        let this_run = world.read_change_tick();
+        let component_editor = world.resource::<ComponentEditor>();
+        let registry = component_editor.type_registry.read();
+        let record_types = [
+            TypeId::of::<IdentityData>(),
+            TypeId::of::<Transform>(),
+            TypeId::of::<ChildOf>(),
+            TypeId::of::<SpawnSource>(),
+        ];
+
+        entity_ref
+            .archetype()
+            .components()
+            .iter()
+            .any(|component_id| {
+                let Some(type_id) = world
+                    .components()
+                    .get_info(*component_id)
+                    .and_then(|info| info.type_id())
+                else {
+                    return false;
+                };
+                let saved = record_types.contains(&type_id)
+                    || registry.get(type_id).is_some_and(|registration| {
+                        !component_editor.should_skip_component(registration)
+                    });
+                saved
+                    && entity_ref
+                        .get_change_ticks_by_id(*component_id)
+                        .is_some_and(|ticks| ticks.is_changed(*since, this_run))
+            })
+    }
+}
+
+/// Entities of a scene that just loaded match the file, unless they were already tracked
+/// Runs in PostUpdate, so the load's commands have been applied
+pub fn sync_loaded_entities_system(
+    mut load_reader: MessageReader<WorldLoadSuccessEvent>,
+    mut removed: RemovedComponents<IdentityData>,
+    mut incremental: ResMut<IncrementalSave>,
+    ticks: SystemChangeTick,
+    query: Query<(Entity, &SpawnSource), With<IdentityData>>,
+) {
+    for entity in removed.read() {
+        incremental.forget(entity);
+    }
+
+    let loaded: HashSet<String> = load_reader
+        .read()
+        .map(|WorldLoadSuccessEvent(path)| absolute_asset_to_rel(path.clone()).to_string())
+        .collect();
+    if loaded.is_empty() {
+        return;
+    }
+
+    for (entity, source) in query.iter() {
+        if loaded.contains(source.str_ref()) && !incremental.synced.contains_key(&entity) {
+            incremental.sync(entity, ticks.this_run());
+        }
+    }
+}
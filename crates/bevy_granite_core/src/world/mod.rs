@@ -1,12 +1,93 @@
+pub mod companions;
+pub mod csv_edit;
+pub mod dependencies;
+pub mod gltf_export;
+pub mod gltf_import;
+pub mod incremental;
 pub mod open;
 pub mod plugin;
+pub mod prefab;
+pub mod profiling;
+pub mod project_resources;
+pub mod reassign;
+pub mod region;
 pub mod reload;
+pub mod sandbox;
 pub mod save;
+pub mod save_game;
+pub mod schema_export;
+pub mod snapshot;
+#[cfg(feature = "stress")]
+pub mod soak;
+pub mod startup;
+pub mod transform_baking;
+pub mod validation;
+#[cfg(feature = "visual")]
+pub mod visual_regression;
+pub mod watch;
 
-pub use open::{open_world_reader, open_world_batch_reader};
+pub use companions::{
+    release_scene_companions_system, resolve_batch_companions, scene_requires, SceneCompanions,
+};
+pub use csv_edit::{
+    entities_to_csv, export_entity_csv_system, import_entity_csv, import_entity_csv_system,
+    parse_csv, write_csv, CsvColumns, CsvImportSummary,
+};
+pub use dependencies::{
+    scene_dependency_report_system, SceneDependency, SceneDependencyGraph, SceneDependencyKind,
+};
+pub use gltf_export::{export_gltf_system, GltfDocument, GltfExportSources, GltfExportSummary};
+pub use gltf_import::{import_gltf, import_gltf_system, GltfImportSpawner, GltfImportSummary};
+pub use incremental::{sync_loaded_entities_system, IncrementalSave, SaveMode};
+pub use open::{open_world_batch_reader, open_world_reader};
+pub use plugin::WorldPlugin;
+pub use prefab::{
+    create_prefab_system, is_prefab_file, prefab_member_uuid, refresh_prefab_overrides,
+    spawn_prefab_instances_system, update_prefab_instances_system, PrefabInstance, PrefabLibrary,
+    PrefabMember, PrefabOverride, PrefabSpawned, PREFAB_FILE_EXTENSION,
+};
+pub use profiling::{
+    EntityProfile, ProfileTotals, SceneProfile, SceneProfileKind, SceneProfileReport,
+    SCENE_PROFILE_TOP,
+};
+pub use project_resources::{
+    load_project_resources_system, save_project_resources, ProjectResources, PROJECT_RESOURCES_PATH,
+};
+pub use reassign::{clear_dirty_scenes_system, reassign_spawn_source_system, DirtyScenes};
+pub use region::{
+    read_scene_region, region_world_reader, PartialScenes, SceneRegionEntry, SceneRegionIndex,
+    REGION_INDEX_MIN_ENTITIES,
+};
 pub use reload::reload_world_system;
+pub use sandbox::{sandboxed_world_reader, SandboxReport, SandboxViolation, SceneSandbox};
 pub use save::{
-    collect_components_system, save_data_ready_system, save_request_system, SaveWorldRequestData,
-    WorldState,
+    collect_components_system, save_data_ready_system, save_request_system, SaveEntityData,
+    SaveLock, SaveRequestGuards, SaveWorldRequestData, WorldState,
+};
+pub use save_game::{
+    apply_save_game_system, build_save_game, load_save_game_request_system, read_save_game,
+    save_game_request_system, write_save_game, EntityDelta, PendingSaveGames, RuntimeEntityState,
+    SaveGameData, SaveGameMetadata,
+};
+pub use schema_export::{build_schema_json, export_schema_system, SchemaExportSummary};
+pub use snapshot::{GraniteSnapshot, SnapshotEntity};
+#[cfg(feature = "stress")]
+pub use soak::{soak_test_system, SoakTest, SoakTestPlugin};
+pub use startup::{load_startup_world_system, StartupWorlds, STARTUP_WORLD_ARG, STARTUP_WORLD_ENV};
+pub use transform_baking::{
+    apply_transform, apply_transform_system, flatten_hierarchy, flatten_hierarchy_system,
+};
+pub use validation::{
+    apply_issue_fix, run_scene_validators, scene_validation_system, validate_scenes_request_system,
+    IssueFix, IssueSeverity, SceneIssue, SceneIssues, SceneValidation, SceneValidationPlugin,
+    SceneValidator, SceneValidatorAppExt, SceneValidators,
+};
+#[cfg(feature = "visual")]
+pub use visual_regression::{
+    visual_regression_system, VisualComparison, VisualOutcome, VisualRegression,
+    VisualRegressionPlugin, VISUAL_REGRESSION_ANGLES,
+};
+pub use watch::{
+    diff_scene_entities, track_scene_files_system, watch_scene_files_system, SceneEntityChange,
+    SceneEntityDiff, SceneFileNotifier, SceneFileWatcher, DEFAULT_SCENE_WATCH_DEBOUNCE,
 };
-pub use plugin::WorldPlugin;
\ No newline at end of file
@@ -1,6 +1,12 @@
-use crate::events::{RequestLoadEvent, RequestLoadBatchEvent, WorldLoadSuccessEvent, WorldLoadBatchSuccessEvent};
-use crate::{absolute_asset_to_rel};
-use crate::{assets::AvailableEditableMaterials, entities::deserialize_entities};
+use super::companions::{loaded_sources, resolve_batch_companions, SceneCompanions};
+use crate::absolute_asset_to_rel;
+use crate::events::{
+    RequestLoadBatchEvent, RequestLoadEvent, WorldLoadBatchSuccessEvent, WorldLoadSuccessEvent,
+};
+use crate::{
+    assets::AvailableEditableMaterials,
+    entities::{deserialize_entities, IdentityData, SceneTarget, SpawnSource},
+};
 use bevy::prelude::*;
 use bevy_granite_logging::{
     config::{LogCategory, LogLevel, LogType},
@@ -8,12 +14,14 @@ use bevy_granite_logging::{
 };
 
 /// Watches for RequestLoadEvent and then deserializes the world from its path
+#[allow(clippy::too_many_arguments)]
 pub fn open_world_reader(
     asset_server: Res<AssetServer>,
     mut commands: Commands,
     mut materials: ResMut<Assets<StandardMaterial>>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut available_materials: ResMut<AvailableEditableMaterials>,
+    scene_target: Res<SceneTarget>,
     mut world_open_reader: MessageReader<RequestLoadEvent>,
     mut world_load_success_writer: MessageWriter<WorldLoadSuccessEvent>,
 ) {
@@ -30,6 +38,8 @@ pub fn open_world_reader(
             rel.clone(),
             save_settings.clone(),
             *translation,
+            None,
+            &scene_target,
         );
 
         log!(
@@ -45,20 +55,31 @@ pub fn open_world_reader(
 }
 
 /// Watches for RequestLoadBatchEvent and then deserializes all worlds from their paths
+/// Companion scenes required in scene metadata are loaded first, once, and included in the success event
+#[allow(clippy::too_many_arguments)]
 pub fn open_world_batch_reader(
     asset_server: Res<AssetServer>,
     mut commands: Commands,
     mut materials: ResMut<Assets<StandardMaterial>>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut available_materials: ResMut<AvailableEditableMaterials>,
-    mut world_batch_reader: MessageReader<RequestLoadBatchEvent>,
-    mut world_load_batch_success_writer: MessageWriter<WorldLoadBatchSuccessEvent>,
+    scene_target: Res<SceneTarget>,
+    mut batch_events: (
+        MessageReader<RequestLoadBatchEvent>,
+        MessageWriter<WorldLoadBatchSuccessEvent>,
+    ),
+    mut companions: (
+        ResMut<SceneCompanions>,
+        Query<&SpawnSource, With<IdentityData>>,
+    ),
 ) {
+    let (world_batch_reader, world_load_batch_success_writer) = &mut batch_events;
     if let Some(RequestLoadBatchEvent(worlds)) = world_batch_reader.read().next() {
+        let loaded_sources = loaded_sources(&companions.1);
+        let worlds = resolve_batch_companions(worlds, &loaded_sources, &mut companions.0);
         let mut loaded_paths = Vec::new();
 
-        for (path, save_settings, translation) in worlds.iter() {
-            let rel = absolute_asset_to_rel(path.to_string()).to_string();
+        for (rel, save_settings, translation) in worlds.into_iter() {
             deserialize_entities(
                 &asset_server,
                 &mut commands,
@@ -66,8 +87,10 @@ pub fn open_world_batch_reader(
                 &mut available_materials,
                 &mut meshes,
                 rel.clone(),
-                save_settings.clone(),
-                *translation,
+                save_settings,
+                translation,
+                None,
+                &scene_target,
             );
 
             log!(
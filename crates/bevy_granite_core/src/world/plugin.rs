@@ -1,8 +1,22 @@
-use super::{open_world_reader, open_world_batch_reader, SaveWorldRequestData,
-    collect_components_system, reload_world_system, save_request_system, save_data_ready_system,
+use super::{
+    apply_save_game_system, apply_transform_system, clear_dirty_scenes_system,
+    collect_components_system, create_prefab_system, export_entity_csv_system, export_gltf_system,
+    export_schema_system, flatten_hierarchy_system, import_entity_csv_system, import_gltf_system,
+    load_project_resources_system, load_save_game_request_system, load_startup_world_system,
+    open_world_batch_reader, open_world_reader, reassign_spawn_source_system, region_world_reader,
+    release_scene_companions_system, reload_world_system, sandboxed_world_reader,
+    save_data_ready_system, save_game_request_system, save_request_system,
+    scene_dependency_report_system, spawn_prefab_instances_system, sync_loaded_entities_system,
+    track_scene_files_system, update_prefab_instances_system, validate_scenes_request_system,
+    watch_scene_files_system, DirtyScenes, IncrementalSave, PartialScenes, PendingSaveGames,
+    PrefabInstance, PrefabLibrary, PrefabMember, PrefabOverride, ProjectResources, SaveLock,
+    SaveMode, SaveWorldRequestData, SceneCompanions, SceneFileNotifier, SceneFileWatcher,
+    SceneIssues, SceneProfileReport, SceneValidators, StartupWorlds,
 };
+use crate::entities::BridgeTag;
 use bevy::{
-    app::{App, Plugin, Update},
+    app::{App, Plugin, PostUpdate, PreStartup, Startup, Update},
+    ecs::schedule::IntoScheduleConfigs,
 };
 
 pub struct WorldPlugin;
@@ -13,10 +27,59 @@ impl Plugin for WorldPlugin {
             // Resources
             //
             .init_resource::<SaveWorldRequestData>()
+            .init_resource::<SaveLock>()
+            .init_resource::<StartupWorlds>()
+            .init_resource::<SceneCompanions>()
+            .init_resource::<DirtyScenes>()
+            .init_resource::<PendingSaveGames>()
+            .init_resource::<PartialScenes>()
+            .init_resource::<PrefabLibrary>()
+            .init_resource::<ProjectResources>()
+            .init_resource::<IncrementalSave>()
+            .init_resource::<SceneFileWatcher>()
+            .init_resource::<SceneFileNotifier>()
+            .init_resource::<SceneProfileReport>()
+            .init_resource::<SceneValidators>()
+            .init_resource::<SceneIssues>()
+            //
+            // Register Types
+            //
+            .register_type::<SaveMode>()
+            .register_type::<PrefabOverride>()
+            .register_type::<PrefabInstance>()
+            .register_type_data::<PrefabInstance, BridgeTag>()
+            .register_type::<PrefabMember>()
+            .register_type_data::<PrefabMember, BridgeTag>()
             //
             // Schedule system
             //
-            .add_systems(Update, (open_world_reader, open_world_batch_reader))
+            .add_systems(PreStartup, load_project_resources_system)
+            .add_systems(Startup, load_startup_world_system)
+            .add_systems(
+                Update,
+                (
+                    open_world_reader,
+                    open_world_batch_reader,
+                    sandboxed_world_reader,
+                    region_world_reader,
+                ),
+            )
+            .add_systems(Update, release_scene_companions_system)
+            .add_systems(
+                Update,
+                (
+                    reassign_spawn_source_system.before(save_request_system),
+                    clear_dirty_scenes_system,
+                ),
+            )
+            .add_systems(
+                Update,
+                (
+                    save_game_request_system,
+                    load_save_game_request_system,
+                    apply_save_game_system.after(open_world_reader),
+                ),
+            )
             .add_systems(
                 Update,
                 (
@@ -24,7 +87,30 @@ impl Plugin for WorldPlugin {
                     reload_world_system,
                     save_request_system,
                     save_data_ready_system,
+                    scene_dependency_report_system,
+                    export_gltf_system,
+                    import_gltf_system,
                 ),
+            )
+            .add_systems(Update, (export_entity_csv_system, import_entity_csv_system))
+            .add_systems(Update, export_schema_system)
+            .add_systems(Update, (flatten_hierarchy_system, apply_transform_system))
+            .add_systems(Update, validate_scenes_request_system)
+            .add_systems(
+                Update,
+                (track_scene_files_system, watch_scene_files_system)
+                    .chain()
+                    .after(save_data_ready_system),
+            )
+            .add_systems(PostUpdate, sync_loaded_entities_system)
+            .add_systems(
+                Update,
+                (
+                    create_prefab_system,
+                    update_prefab_instances_system.after(save_data_ready_system),
+                    spawn_prefab_instances_system,
+                )
+                    .chain(),
             );
     }
 }
@@ -0,0 +1,609 @@
+use super::{DirtyScenes, GraniteSnapshot};
+use crate::{
+    assets::AvailableEditableMaterials,
+    entities::{
+        read_scene_entities, round_quat, round_vec3, spawn_scene_entities, write_scene_file,
+        ComponentEditor, Empty, EntitySaveReadyData, GraniteTypes, IdentityData, SaveSettings,
+        SceneSpawnTarget, SceneTarget, SpawnSource, TransformData,
+    },
+    events::{RequestCreatePrefabEvent, RequestRevertPrefabEvent},
+    shared::{absolute_asset_to_rel, rel_asset_to_absolute, EditorCapabilities},
+    GraniteType, WorldSaveSuccessEvent,
+};
+use bevy::{
+    ecs::system::{SystemParam, SystemState},
+    prelude::*,
+    reflect::TypePath,
+};
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    time::{Duration, SystemTime},
+};
+use uuid::Uuid;
+
+// prefab.rs
+// A prefab is a group of entities saved once to a .prefab file (same format as a scene) and instanced in scenes
+// Scenes only store the instance root, an entity with PrefabInstance. Its members are spawned from the prefab file
+// as children of the root, with PrefabMember instead of a SpawnSource, so scene saves skip them
+// Per instance edits are stored on the root as overrides, captured when its scene saves and applied on spawn
+
+pub const PREFAB_FILE_EXTENSION: &str = "prefab";
+
+/// Root of a prefab instance. Members of the prefab spawn as its children
+#[derive(Reflect, Serialize, Deserialize, Debug, Clone, Component, Default, PartialEq)]
+#[reflect(Component, Serialize, Deserialize, Default, FromReflect)]
+pub struct PrefabInstance {
+    /// Prefab file relative to assets, i.e. "prefabs/crate.prefab"
+    pub prefab: String,
+    /// How members differ from the prefab file, in file order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub overrides: Vec<PrefabOverride>,
+    /// Prefab entities deleted from this instance. Their children go with them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub removed: Vec<Uuid>,
+}
+
+impl PrefabInstance {
+    pub fn new(prefab: impl Into<String>) -> Self {
+        Self {
+            prefab: prefab.into(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Changes to one entity of the prefab, for one instance
+#[derive(Reflect, Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[reflect(Serialize, Deserialize, Default, FromReflect)]
+pub struct PrefabOverride {
+    /// Uuid of the entity in the prefab file
+    pub target: Uuid,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identity: Option<IdentityData>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transform: Option<TransformData>,
+    /// Serialized components that differ or were added, by type path
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub components: Vec<(String, String)>,
+}
+
+impl PrefabOverride {
+    fn is_empty(&self) -> bool {
+        self.identity.is_none() && self.transform.is_none() && self.components.is_empty()
+    }
+}
+
+/// Entity spawned from a prefab file as part of an instance
+#[derive(Reflect, Serialize, Deserialize, Debug, Clone, Copy, Component, Default, PartialEq)]
+#[reflect(Component, Serialize, Deserialize, Default, FromReflect)]
+pub struct PrefabMember {
+    /// Uuid of the entity in the prefab file
+    pub source: Uuid,
+}
+
+/// Prefab path the instance's members were spawned from. Runtime only
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct PrefabSpawned(pub String);
+
+/// Uuid of a member, derived from the instance root and the prefab entity so it is stable across loads
+pub fn prefab_member_uuid(instance: Uuid, source: Uuid) -> Uuid {
+    Uuid::from_u128(instance.as_u128().rotate_left(64) ^ source.as_u128())
+}
+
+pub fn is_prefab_file(path: &str) -> bool {
+    path.to_lowercase()
+        .ends_with(&format!(".{}", PREFAB_FILE_EXTENSION))
+}
+
+struct PrefabFile {
+    modified: Option<SystemTime>,
+    entities: Vec<EntitySaveReadyData>,
+}
+
+/// Prefab files instances were spawned from, by relative path
+#[derive(Resource, Default)]
+pub struct PrefabLibrary {
+    files: HashMap<String, PrefabFile>,
+}
+
+impl PrefabLibrary {
+    /// Prefab entities, read again when the file changed on disk
+    pub fn load(&mut self, path: &str) -> &[EntitySaveReadyData] {
+        let abs = rel_asset_to_absolute(path).to_string();
+        let modified = std::fs::metadata(&abs)
+            .and_then(|metadata| metadata.modified())
+            .ok();
+        let stale = self
+            .files
+            .get(path)
+            .is_none_or(|file| file.modified != modified);
+        if stale {
+            self.files.insert(
+                path.to_string(),
+                PrefabFile {
+                    modified,
+                    entities: read_scene_entities(&abs),
+                },
+            );
+        }
+        &self.files[path].entities
+    }
+
+    /// Prefab entities as they were when instances last spawned from them
+    pub fn spawned_from(&mut self, path: &str) -> &[EntitySaveReadyData] {
+        if !self.files.contains_key(path) {
+            return self.load(path);
+        }
+        &self.files[path].entities
+    }
+}
+
+#[derive(SystemParam)]
+pub struct PrefabSpawner<'w, 's> {
+    commands: Commands<'w, 's>,
+    asset_server: Res<'w, AssetServer>,
+    materials: ResMut<'w, Assets<StandardMaterial>>,
+    meshes: ResMut<'w, Assets<Mesh>>,
+    available_materials: ResMut<'w, AvailableEditableMaterials>,
+    library: ResMut<'w, PrefabLibrary>,
+    scene_target: Res<'w, SceneTarget>,
+}
+
+impl PrefabSpawner<'_, '_> {
+    /// Spawn the members of an instance under its root, with the instance's overrides applied
+    fn spawn(&mut self, root: Entity, root_uuid: Uuid, instance: &PrefabInstance) {
+        let source = self.library.load(&instance.prefab);
+        if source.is_empty() {
+            log!(
+                LogType::Game,
+                LogLevel::Warning,
+                LogCategory::Entity,
+                "Prefab '{}' has no entities or could not be read",
+                instance.prefab
+            );
+            return;
+        }
+
+        // Removed entities take their children with them
+        let mut removed: HashSet<Uuid> = instance.removed.iter().copied().collect();
+        for (_, entity) in crate::entities::order_parents_first(source) {
+            if entity
+                .parent
+                .is_some_and(|parent| removed.contains(&parent))
+            {
+                removed.insert(entity.identity.uuid);
+            }
+        }
+        let overrides: HashMap<Uuid, &PrefabOverride> = instance
+            .overrides
+            .iter()
+            .map(|prefab_override| (prefab_override.target, prefab_override))
+            .collect();
+
+        let mut sources = HashMap::new();
+        let members: Vec<EntitySaveReadyData> = source
+            .iter()
+            .filter(|entity| !removed.contains(&entity.identity.uuid))
+            .map(|entity| {
+                let mut member = entity.clone();
+                if let Some(prefab_override) = overrides.get(&entity.identity.uuid) {
+                    if let Some(identity) = &prefab_override.identity {
+                        member.identity = identity.clone();
+                    }
+                    if let Some(transform) = &prefab_override.transform {
+                        member.transform = transform.clone();
+                    }
+                    if !prefab_override.components.is_empty() {
+                        member
+                            .components
+                            .get_or_insert_with(HashMap::new)
+                            .extend(prefab_override.components.iter().cloned());
+                    }
+                }
+                member.identity.uuid = prefab_member_uuid(root_uuid, entity.identity.uuid);
+                member.parent = Some(
+                    entity
+                        .parent
+                        .map_or(root_uuid, |parent| prefab_member_uuid(root_uuid, parent)),
+                );
+                sources.insert(member.identity.uuid, entity.identity.uuid);
+                member
+            })
+            .collect();
+        let members = self.scene_target.apply(members);
+
+        spawn_scene_entities(
+            &self.asset_server,
+            &mut self.commands,
+            &mut self.materials,
+            &mut self.available_materials,
+            &mut self.meshes,
+            &members,
+            &SceneSpawnTarget {
+                abs_path: rel_asset_to_absolute(&instance.prefab),
+                save_settings: SaveSettings::Runtime,
+                transform_override: None,
+                file_indices: None,
+                loaded: HashMap::from([(root_uuid, root)]),
+                prefab_sources: Some(sources),
+                new_to_scene: false,
+                file_time: Duration::ZERO,
+            },
+        );
+        self.commands
+            .entity(root)
+            .insert(PrefabSpawned(instance.prefab.clone()));
+    }
+}
+
+type InstanceQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static IdentityData,
+        &'static PrefabInstance,
+        Option<&'static PrefabSpawned>,
+        Option<&'static Children>,
+    ),
+    Changed<PrefabInstance>,
+>;
+
+/// Spawns the members of new instances, and respawns them when the instance points at another prefab
+pub fn spawn_prefab_instances_system(
+    mut spawner: PrefabSpawner,
+    instances: InstanceQuery,
+    members: Query<(), With<PrefabMember>>,
+) {
+    for (root, identity, instance, spawned, children) in instances.iter() {
+        if spawned.is_some_and(|spawned| spawned.0 == instance.prefab) {
+            continue;
+        }
+        let existing: Vec<Entity> = children
+            .map(|children| {
+                children
+                    .iter()
+                    .filter(|child| members.contains(*child))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        // Members restored along with the root, i.e. by undo, are kept
+        if spawned.is_none() && !existing.is_empty() {
+            spawner
+                .commands
+                .entity(root)
+                .insert(PrefabSpawned(instance.prefab.clone()));
+            continue;
+        }
+        for member in existing {
+            spawner.commands.entity(member).despawn();
+        }
+        spawner.spawn(root, identity.uuid, instance);
+    }
+}
+
+/// Members of an instance. Members of nested instances belong to their own root and are not included
+fn instance_members(world: &World, root: Entity) -> Vec<Entity> {
+    let mut members = vec![];
+    let mut stack: Vec<Entity> = world
+        .get::<Children>(root)
+        .map(|children| children.iter().collect())
+        .unwrap_or_default();
+    while let Some(entity) = stack.pop() {
+        if world.get::<PrefabMember>(entity).is_none() {
+            continue;
+        }
+        members.push(entity);
+        if world.get::<PrefabInstance>(entity).is_some() {
+            continue;
+        }
+        if let Some(children) = world.get::<Children>(entity) {
+            stack.extend(children.iter());
+        }
+    }
+    members
+}
+
+/// Store how the members of each instance root differ from their prefab, so saving the root's scene keeps
+/// per instance edits. Nested instances are refreshed first. Entities that are not instance roots are skipped
+pub fn refresh_prefab_overrides(world: &mut World, entities: &[Entity]) {
+    for &root in entities {
+        refresh_instance_overrides(world, root);
+    }
+}
+
+fn refresh_instance_overrides(world: &mut World, root: Entity) {
+    let Some(instance) = world.get::<PrefabInstance>(root).cloned() else {
+        return;
+    };
+    let members = instance_members(world, root);
+    for &member in members.iter() {
+        refresh_instance_overrides(world, member);
+    }
+
+    let source: Vec<EntitySaveReadyData> = world
+        .resource_mut::<PrefabLibrary>()
+        .spawned_from(&instance.prefab)
+        .to_vec();
+    let source_by_uuid: HashMap<Uuid, &EntitySaveReadyData> = source
+        .iter()
+        .map(|entity| (entity.identity.uuid, entity))
+        .collect();
+    let member_type = PrefabMember::type_path();
+
+    let mut found: HashMap<Uuid, PrefabOverride> = HashMap::new();
+    for member in members {
+        let (Some(prefab_member), Some(identity)) = (
+            world.get::<PrefabMember>(member).copied(),
+            world.get::<IdentityData>(member),
+        ) else {
+            continue;
+        };
+        let Some(original) = source_by_uuid.get(&prefab_member.source) else {
+            continue;
+        };
+
+        let mut identity = identity.clone();
+        identity.uuid = original.identity.uuid;
+        let transform = world.get::<Transform>(member).copied().unwrap_or_default();
+        let transform = TransformData {
+            position: round_vec3(transform.translation),
+            rotation: round_quat(transform.rotation),
+            scale: round_vec3(transform.scale),
+        };
+        let components = world
+            .get_resource::<ComponentEditor>()
+            .map(|editor| editor.serialize_entity_components(world, member))
+            .unwrap_or_default();
+        let original_components = original.components.clone().unwrap_or_default();
+        let mut changed_components: Vec<(String, String)> = components
+            .into_iter()
+            .filter(|(type_name, data)| {
+                type_name != member_type && original_components.get(type_name) != Some(data)
+            })
+            .collect();
+        changed_components.sort();
+
+        // Class data is compared serialized, runtime only fields don't count as edits
+        let identity_changed =
+            ron::to_string(&identity).ok() != ron::to_string(&original.identity).ok();
+        found.insert(
+            original.identity.uuid,
+            PrefabOverride {
+                target: original.identity.uuid,
+                identity: identity_changed.then_some(identity),
+                transform: (transform != original.transform).then_some(transform),
+                components: changed_components,
+            },
+        );
+    }
+
+    let mut refreshed = PrefabInstance::new(instance.prefab.clone());
+    for original in source.iter() {
+        match found.remove(&original.identity.uuid) {
+            Some(prefab_override) if !prefab_override.is_empty() => {
+                refreshed.overrides.push(prefab_override)
+            }
+            Some(_) => {}
+            None => refreshed.removed.push(original.identity.uuid),
+        }
+    }
+
+    if refreshed != instance {
+        if let Some(mut instance) = world.get_mut::<PrefabInstance>(root) {
+            *instance = refreshed;
+        }
+    }
+}
+
+/// Despawn the members of an instance so they spawn again from the prefab file
+fn respawn_instance(world: &mut World, root: Entity) {
+    for member in instance_members(world, root) {
+        if let Ok(member) = world.get_entity_mut(member) {
+            member.despawn();
+        }
+    }
+    let mut root = world.entity_mut(root);
+    root.remove::<PrefabSpawned>();
+    if let Some(mut instance) = root.get_mut::<PrefabInstance>() {
+        instance.set_changed();
+    }
+}
+
+type PrefabUpdateMessages<'w, 's> = (
+    MessageReader<'w, 's, WorldSaveSuccessEvent>,
+    MessageReader<'w, 's, RequestRevertPrefabEvent>,
+    Query<'w, 's, (Entity, &'static PrefabInstance)>,
+);
+
+/// After a prefab file is saved, every instance of it respawns with its overrides on top of the new contents
+/// Reverting drops an instance's overrides and respawns it as the prefab is
+pub fn update_prefab_instances_system(
+    world: &mut World,
+    state: &mut SystemState<PrefabUpdateMessages>,
+) {
+    let (mut saved_reader, mut revert_reader, instances) = state.get_mut(world);
+    let saved: HashSet<String> = saved_reader
+        .read()
+        .map(|WorldSaveSuccessEvent(path)| absolute_asset_to_rel(path.clone()).to_string())
+        .filter(|path| is_prefab_file(path))
+        .collect();
+    let reverted: Vec<Entity> = revert_reader
+        .read()
+        .map(|RequestRevertPrefabEvent(entity)| *entity)
+        .collect();
+    let outdated: Vec<Entity> = instances
+        .iter()
+        .filter(|(_, instance)| saved.contains(&instance.prefab))
+        .map(|(entity, _)| entity)
+        .collect();
+
+    // Edits made since the scene was last saved are kept, compared against what the members spawned from
+    refresh_prefab_overrides(world, &outdated);
+    for path in saved.iter() {
+        world.resource_mut::<PrefabLibrary>().load(path);
+    }
+    for root in outdated.iter() {
+        respawn_instance(world, *root);
+    }
+    if !outdated.is_empty() {
+        log!(
+            LogType::Game,
+            LogLevel::OK,
+            LogCategory::Entity,
+            "Updated {} prefab instances",
+            outdated.len()
+        );
+    }
+
+    for root in reverted {
+        {
+            let Some(mut instance) = world.get_mut::<PrefabInstance>(root) else {
+                continue;
+            };
+            instance.overrides.clear();
+            instance.removed.clear();
+        }
+        respawn_instance(world, root);
+    }
+}
+
+type CreatePrefabParams<'w, 's> = (
+    MessageReader<'w, 's, RequestCreatePrefabEvent>,
+    Commands<'w, 's>,
+    Res<'w, AssetServer>,
+    ResMut<'w, Assets<StandardMaterial>>,
+    ResMut<'w, Assets<Mesh>>,
+    ResMut<'w, AvailableEditableMaterials>,
+);
+
+/// Saves an entity and its children to a prefab file, and puts an instance of it in their place
+pub fn create_prefab_system(world: &mut World, state: &mut SystemState<CreatePrefabParams>) {
+    let requests: Vec<RequestCreatePrefabEvent> = state.get_mut(world).0.read().cloned().collect();
+
+    for RequestCreatePrefabEvent { entity, path } in requests {
+        let path = absolute_asset_to_rel(path).to_string();
+        if !world.resource::<EditorCapabilities>().can_save(&path) {
+            continue;
+        }
+        let (Some(identity), Some(source)) = (
+            world.get::<IdentityData>(entity).cloned(),
+            world.get::<SpawnSource>(entity).cloned(),
+        ) else {
+            log!(
+                LogType::Game,
+                LogLevel::Warning,
+                LogCategory::Entity,
+                "Only scene entities can become prefabs"
+            );
+            continue;
+        };
+
+        // Members of instances inside the selection are not saved, their roots are
+        let mut subtree = vec![entity];
+        let mut index = 0;
+        while index < subtree.len() {
+            if let Some(children) = world.get::<Children>(subtree[index]) {
+                subtree.extend(children.iter().filter(|child| {
+                    world.get::<IdentityData>(*child).is_some()
+                        && world.get::<PrefabMember>(*child).is_none()
+                }));
+            }
+            index += 1;
+        }
+
+        let entities: Vec<EntitySaveReadyData> = GraniteSnapshot::capture_entities(world, &subtree)
+            .into_iter()
+            .map(|captured| {
+                let mut data = captured.data;
+                if data.identity.uuid == identity.uuid {
+                    data.parent = None;
+                    data.transform = TransformData {
+                        position: Vec3::ZERO,
+                        rotation: Quat::IDENTITY,
+                        scale: Vec3::ONE,
+                    };
+                } else {
+                    data.transform = TransformData {
+                        position: round_vec3(data.transform.position),
+                        rotation: round_quat(data.transform.rotation),
+                        scale: round_vec3(data.transform.scale),
+                    };
+                }
+                data
+            })
+            .collect();
+
+        let abs = rel_asset_to_absolute(&path).to_string();
+        if let Some(parent) = std::path::Path::new(&abs).parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        if let Err(e) = write_scene_file(&abs, entities) {
+            log!(
+                LogType::Game,
+                LogLevel::Error,
+                LogCategory::System,
+                "Failed to write prefab '{}': {}",
+                path,
+                e
+            );
+            continue;
+        }
+
+        // The instance root takes the place of the original entity
+        let transform = world.get::<Transform>(entity).copied().unwrap_or_default();
+        let parent = world
+            .get::<ChildOf>(entity)
+            .map(|child_of| child_of.parent());
+        let root_data = EntitySaveReadyData {
+            identity: IdentityData {
+                uuid: Uuid::new_v4(),
+                name: identity.name.clone(),
+                class: GraniteTypes::Empty(Empty::default()),
+            },
+            transform: TransformData {
+                position: transform.translation,
+                rotation: transform.rotation,
+                scale: transform.scale,
+            },
+            parent: None,
+            components: None,
+        };
+        {
+            let (_, mut commands, asset_server, mut materials, mut meshes, mut available) =
+                state.get_mut(world);
+            let root = root_data.identity.class.spawn_from_save_data(
+                &root_data,
+                &mut commands,
+                &mut materials,
+                &mut meshes,
+                &mut available,
+                &asset_server,
+            );
+            commands
+                .entity(root)
+                .insert((source.clone(), PrefabInstance::new(path.clone())));
+            if let Some(parent) = parent {
+                commands.entity(parent).add_child(root);
+            }
+            commands.entity(entity).despawn();
+        }
+        state.apply(world);
+        world.resource_mut::<DirtyScenes>().mark(source.str_ref());
+
+        log!(
+            LogType::Game,
+            LogLevel::OK,
+            LogCategory::Entity,
+            "Created prefab '{}' from '{}'",
+            path,
+            identity.name
+        );
+    }
+}
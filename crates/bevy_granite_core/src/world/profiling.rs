@@ -0,0 +1,208 @@
+use bevy::prelude::Resource;
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+use std::{collections::HashMap, time::Duration};
+use uuid::Uuid;
+
+// profiling.rs
+// Times scene loads and saves per entity, per class and per component, with the size of their saved data
+// So the one component that makes a scene take seconds to open or save can be found
+
+/// Entities and components listed as top offenders in reports and logs
+pub const SCENE_PROFILE_TOP: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SceneProfileKind {
+    Load,
+    Save,
+}
+
+/// Time and saved data of one entity
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntityProfile {
+    pub uuid: Uuid,
+    pub name: String,
+    pub class: String,
+    /// Spawning or collecting the entity, plus loading or serializing its components
+    pub time: Duration,
+    /// Bytes of serialized component data
+    pub bytes: usize,
+}
+
+/// Totals of every entity of a class, or every instance of a component type
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ProfileTotals {
+    pub name: String,
+    pub count: usize,
+    pub time: Duration,
+    pub bytes: usize,
+}
+
+/// Timings of one scene load or save
+#[derive(Debug, Clone, PartialEq)]
+pub struct SceneProfile {
+    pub kind: SceneProfileKind,
+    /// Scene path relative to assets
+    pub scene: String,
+    /// From the load or save request being handled to the last entity done
+    pub total: Duration,
+    /// Reading and parsing the file on load, formatting and writing it on save
+    pub file: Duration,
+    pub entities: Vec<EntityProfile>,
+    /// By component type path
+    pub components: HashMap<String, ProfileTotals>,
+}
+
+impl SceneProfile {
+    pub fn new(kind: SceneProfileKind, scene: impl Into<String>) -> Self {
+        Self {
+            kind,
+            scene: scene.into(),
+            total: Duration::ZERO,
+            file: Duration::ZERO,
+            entities: vec![],
+            components: HashMap::new(),
+        }
+    }
+
+    /// Adds time and bytes to the entity, listing it first time
+    pub fn record_entity(
+        &mut self,
+        uuid: Uuid,
+        name: &str,
+        class: &str,
+        time: Duration,
+        bytes: usize,
+    ) {
+        match self.entities.iter_mut().find(|entity| entity.uuid == uuid) {
+            Some(entity) => {
+                entity.time += time;
+                entity.bytes += bytes;
+            }
+            None => self.entities.push(EntityProfile {
+                uuid,
+                name: name.to_string(),
+                class: class.to_string(),
+                time,
+                bytes,
+            }),
+        }
+    }
+
+    /// Adds one instance of a component to its type's totals, and its time to the entity
+    pub fn record_component(&mut self, uuid: Uuid, type_path: &str, time: Duration, bytes: usize) {
+        let totals = self
+            .components
+            .entry(type_path.to_string())
+            .or_insert_with(|| ProfileTotals {
+                name: type_path.to_string(),
+                ..Default::default()
+            });
+        totals.count += 1;
+        totals.time += time;
+        totals.bytes += bytes;
+        if let Some(entity) = self.entities.iter_mut().find(|entity| entity.uuid == uuid) {
+            entity.time += time;
+        }
+    }
+
+    /// Time spent on entities and their components, the rest of total is file and bookkeeping
+    pub fn entity_time(&self) -> Duration {
+        self.entities.iter().map(|entity| entity.time).sum()
+    }
+
+    pub fn bytes(&self) -> usize {
+        self.entities.iter().map(|entity| entity.bytes).sum()
+    }
+
+    /// Slowest entities first
+    pub fn top_entities(&self, count: usize) -> Vec<&EntityProfile> {
+        let mut entities: Vec<&EntityProfile> = self.entities.iter().collect();
+        entities.sort_by(|a, b| b.time.cmp(&a.time).then(b.bytes.cmp(&a.bytes)));
+        entities.truncate(count);
+        entities
+    }
+
+    /// Totals by class, slowest first
+    pub fn classes(&self) -> Vec<ProfileTotals> {
+        let mut classes: HashMap<&str, ProfileTotals> = HashMap::new();
+        for entity in self.entities.iter() {
+            let totals = classes
+                .entry(entity.class.as_str())
+                .or_insert_with(|| ProfileTotals {
+                    name: entity.class.clone(),
+                    ..Default::default()
+                });
+            totals.count += 1;
+            totals.time += entity.time;
+            totals.bytes += entity.bytes;
+        }
+        let mut classes: Vec<ProfileTotals> = classes.into_values().collect();
+        classes.sort_by(|a, b| b.time.cmp(&a.time).then(b.bytes.cmp(&a.bytes)));
+        classes
+    }
+
+    /// Component types, slowest first
+    pub fn top_components(&self, count: usize) -> Vec<&ProfileTotals> {
+        let mut components: Vec<&ProfileTotals> = self.components.values().collect();
+        components.sort_by(|a, b| b.time.cmp(&a.time).then(b.bytes.cmp(&a.bytes)));
+        components.truncate(count);
+        components
+    }
+
+    /// One line summary with the worst entity and component, for the log
+    pub fn summary(&self) -> String {
+        let verb = match self.kind {
+            SceneProfileKind::Load => "Loaded",
+            SceneProfileKind::Save => "Saved",
+        };
+        let mut summary = format!(
+            "{} '{}' in {:.1?} ({} entities, {} bytes, file {:.1?})",
+            verb,
+            self.scene,
+            self.total,
+            self.entities.len(),
+            self.bytes(),
+            self.file
+        );
+        if let Some(entity) = self.top_entities(1).first() {
+            summary.push_str(&format!(
+                ", slowest entity '{}' {:.1?}",
+                entity.name, entity.time
+            ));
+        }
+        if let Some(component) = self.top_components(1).first() {
+            summary.push_str(&format!(
+                ", slowest component {} {:.1?}",
+                component.name, component.time
+            ));
+        }
+        summary
+    }
+}
+
+/// Profiles of the last scene load and save
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct SceneProfileReport {
+    pub load: Option<SceneProfile>,
+    pub save: Option<SceneProfile>,
+}
+
+impl SceneProfileReport {
+    /// Keeps a finished profile as the last of its kind and logs its summary
+    pub fn finish(&mut self, profile: SceneProfile) {
+        log!(
+            LogType::Game,
+            LogLevel::Info,
+            LogCategory::System,
+            "{}",
+            profile.summary()
+        );
+        match profile.kind {
+            SceneProfileKind::Load => self.load = Some(profile),
+            SceneProfileKind::Save => self.save = Some(profile),
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use crate::{rel_asset_to_absolute, EditorCapabilities};
+use bevy::{
+    prelude::*,
+    reflect::{
+        serde::{TypedReflectDeserializer, TypedReflectSerializer},
+        PartialReflect, TypeRegistry,
+    },
+};
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+use serde::de::{DeserializeSeed, IgnoredAny, MapAccess, Visitor};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt, fs, io,
+    path::Path,
+};
+
+// project_resources.rs
+// Resources saved into a project config file, i.e. game settings tuned in the editor's Resources tab
+// The file is a RON map of type path to value. It is read on PreStartup, so saved values replace the ones set in code
+// Resources need Reflect with #[reflect(Resource)] to be listed, opaque fields also need #[reflect(Serialize, Deserialize)]
+
+pub const PROJECT_RESOURCES_PATH: &str = "config/resources.ron";
+
+/// Which resources the project config file holds, and where it is relative to assets
+#[derive(Resource, Clone, Debug, PartialEq)]
+pub struct ProjectResources {
+    pub path: String,
+    /// Type paths of the saved resources
+    pub saved: BTreeSet<String>,
+}
+
+impl Default for ProjectResources {
+    fn default() -> Self {
+        Self {
+            path: PROJECT_RESOURCES_PATH.to_string(),
+            saved: BTreeSet::new(),
+        }
+    }
+}
+
+impl ProjectResources {
+    pub fn is_saved(&self, type_path: &str) -> bool {
+        self.saved.contains(type_path)
+    }
+
+    pub fn set_saved(&mut self, type_path: &str, saved: bool) {
+        if saved {
+            self.saved.insert(type_path.to_string());
+        } else {
+            self.saved.remove(type_path);
+        }
+    }
+}
+
+/// Reads values by type path, types missing from the registry are skipped
+struct SavedResources<'a>(&'a TypeRegistry);
+
+impl<'de> DeserializeSeed<'de> for SavedResources<'_> {
+    type Value = Vec<(String, Box<dyn PartialReflect>)>;
+
+    fn deserialize<D: serde::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for SavedResources<'_> {
+    type Value = Vec<(String, Box<dyn PartialReflect>)>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map of resource type paths to values")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut values = Vec::new();
+        while let Some(type_path) = map.next_key::<String>()? {
+            match self.0.get_with_type_path(&type_path) {
+                Some(registration) => {
+                    let value =
+                        map.next_value_seed(TypedReflectDeserializer::new(registration, self.0))?;
+                    values.push((type_path, value));
+                }
+                None => {
+                    map.next_value::<IgnoredAny>()?;
+                    log!(
+                        LogType::Game,
+                        LogLevel::Warning,
+                        LogCategory::System,
+                        "Project resource '{}' isn't registered, skipped",
+                        type_path
+                    );
+                }
+            }
+        }
+        Ok(values)
+    }
+}
+
+/// Inserts or updates the resources saved in the project config file
+pub fn load_project_resources_system(world: &mut World) {
+    let path = world.resource::<ProjectResources>().path.clone();
+    let abs_path = rel_asset_to_absolute(&path);
+    let Ok(contents) = fs::read_to_string(abs_path.as_ref()) else {
+        return;
+    };
+
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let values = ron::Deserializer::from_str(&contents)
+        .map_err(|e| e.to_string())
+        .and_then(|mut deserializer| {
+            SavedResources(&registry)
+                .deserialize(&mut deserializer)
+                .map_err(|e| e.to_string())
+        });
+    let values = match values {
+        Ok(values) => values,
+        Err(e) => {
+            log!(
+                LogType::Game,
+                LogLevel::Error,
+                LogCategory::System,
+                "Failed to read project resources {}: {}",
+                path,
+                e
+            );
+            return;
+        }
+    };
+
+    for (type_path, value) in values {
+        let Some(reflect_resource) = registry
+            .get_with_type_path(&type_path)
+            .and_then(|registration| registration.data::<ReflectResource>())
+        else {
+            continue;
+        };
+        reflect_resource.apply_or_insert(world, value.as_ref(), &registry);
+        world
+            .resource_mut::<ProjectResources>()
+            .saved
+            .insert(type_path);
+    }
+}
+
+/// Writes the saved resources as they are in the world, returning how many were written
+pub fn save_project_resources(world: &World) -> io::Result<usize> {
+    let project = world.resource::<ProjectResources>();
+    if !world
+        .resource::<EditorCapabilities>()
+        .can_save(&project.path)
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "not allowed by editor capabilities",
+        ));
+    }
+    let registry = world.resource::<AppTypeRegistry>().read();
+
+    let values: Vec<(&str, &dyn PartialReflect)> = project
+        .saved
+        .iter()
+        .filter_map(|type_path| {
+            let reflect_resource = registry
+                .get_with_type_path(type_path)?
+                .data::<ReflectResource>()?;
+            let value = reflect_resource.reflect(world).ok()?;
+            Some((type_path.as_str(), value.as_partial_reflect()))
+        })
+        .collect();
+
+    let map: BTreeMap<&str, TypedReflectSerializer> = values
+        .iter()
+        .map(|(type_path, value)| (*type_path, TypedReflectSerializer::new(*value, &registry)))
+        .collect();
+    let contents = ron::ser::to_string_pretty(
+        &map,
+        ron::ser::PrettyConfig::new().indentor("\t".to_string()),
+    )
+    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    let abs_path = rel_asset_to_absolute(&project.path);
+    if let Some(parent) = Path::new(abs_path.as_ref()).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(abs_path.as_ref(), contents)?;
+    Ok(values.len())
+}
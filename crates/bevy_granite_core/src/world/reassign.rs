@@ -0,0 +1,198 @@
+use crate::{
+    absolute_asset_to_rel,
+    entities::{read_scene_metadata, IdentityData, SpawnSource},
+    events::{
+        ReassignEntities, RequestDespawnBySource, RequestDespawnSerializableEntities,
+        RequestReassignSpawnSource, RequestSaveEvent,
+    },
+    rel_asset_to_absolute, EditorCapabilities, WorldSaveSuccessEvent,
+};
+use bevy::prelude::*;
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+use std::collections::{BTreeSet, HashSet};
+
+/// Scene sources with changes that are not on disk yet
+#[derive(Resource, Default, Debug, Clone)]
+pub struct DirtyScenes {
+    sources: HashSet<String>,
+}
+
+impl DirtyScenes {
+    pub fn mark(&mut self, source: impl Into<String>) {
+        self.sources.insert(source.into());
+    }
+
+    pub fn is_dirty(&self, source: &str) -> bool {
+        self.sources.contains(source)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &String> {
+        self.sources.iter()
+    }
+
+    pub fn clear(&mut self, source: &str) {
+        self.sources.remove(source);
+    }
+}
+
+/// Moves entities to another scene file. Children move with their parents
+/// Every source that lost or gained entities is marked dirty, and saved together when requested
+pub fn reassign_spawn_source_system(
+    mut commands: Commands,
+    mut reassign_reader: MessageReader<RequestReassignSpawnSource>,
+    mut save_writer: MessageWriter<RequestSaveEvent>,
+    mut dirty_scenes: ResMut<DirtyScenes>,
+    capabilities: Res<EditorCapabilities>,
+    source_query: Query<(Entity, &SpawnSource, Option<&ChildOf>), With<IdentityData>>,
+    children_query: Query<&Children>,
+) {
+    for RequestReassignSpawnSource {
+        entities,
+        target,
+        save,
+    } in reassign_reader.read()
+    {
+        let target = absolute_asset_to_rel(target.to_string()).to_string();
+        if !capabilities.can_edit_components(&target) {
+            continue;
+        }
+
+        let mut to_move: Vec<Entity> = match entities {
+            ReassignEntities::Entities(roots) => roots
+                .iter()
+                .flat_map(|root| {
+                    std::iter::once(*root).chain(children_query.iter_descendants(*root))
+                })
+                .collect(),
+            ReassignEntities::Source(from) => {
+                let from = absolute_asset_to_rel(from.to_string());
+                source_query
+                    .iter()
+                    .filter(|(_, source, _)| source.str_ref() == from)
+                    .map(|(entity, _, _)| entity)
+                    .collect()
+            }
+        };
+        let mut seen = HashSet::new();
+        to_move.retain(|entity| seen.insert(*entity));
+        to_move.retain(|entity| {
+            source_query
+                .get(*entity)
+                .is_ok_and(|(_, source, _)| source.str_ref() != target)
+        });
+
+        if to_move.is_empty() {
+            log!(
+                LogType::Editor,
+                LogLevel::Warning,
+                LogCategory::Entity,
+                "Nothing to move to '{}'",
+                target
+            );
+            continue;
+        }
+
+        // Keep the settings the target scene was loaded with, if any of it is loaded
+        let target_settings = source_query
+            .iter()
+            .find(|(_, source, _)| source.str_ref() == target)
+            .map(|(_, source, _)| source.save_settings_ref().clone());
+
+        // Saving a scene that isn't loaded would replace its entities with only the moved ones
+        let unloaded_entities = read_scene_metadata(&rel_asset_to_absolute(&target))
+            .map(|metadata| metadata.entity_count)
+            .unwrap_or_default();
+        if target_settings.is_none() && unloaded_entities > 0 {
+            log!(
+                LogType::Editor,
+                LogLevel::Error,
+                LogCategory::Entity,
+                "Refused to move entities to '{}': it has {} entities on disk but is not loaded. Load it first",
+                target,
+                unloaded_entities
+            );
+            continue;
+        }
+
+        let moving: HashSet<Entity> = to_move.iter().copied().collect();
+        let mut affected: BTreeSet<String> = BTreeSet::new();
+        for entity in to_move.iter() {
+            let Ok((_, source, child_of)) = source_query.get(*entity) else {
+                continue;
+            };
+            affected.insert(source.str_ref().to_string());
+
+            if let Some(parent) = child_of.map(|child_of| child_of.parent()) {
+                let parent_source = source_query
+                    .get(parent)
+                    .ok()
+                    .filter(|_| !moving.contains(&parent))
+                    .map(|(_, source, _)| source.str_ref().to_string());
+                if let Some(parent_source) = parent_source.filter(|source| *source != target) {
+                    log!(
+                        LogType::Editor,
+                        LogLevel::Warning,
+                        LogCategory::Entity,
+                        "{:?} keeps its parent from '{}', the link only resolves while both scenes are loaded",
+                        entity,
+                        parent_source
+                    );
+                }
+            }
+
+            let settings = target_settings
+                .clone()
+                .unwrap_or_else(|| source.save_settings_ref().clone());
+            commands
+                .entity(*entity)
+                .insert(SpawnSource::new(target.clone(), settings));
+        }
+        affected.insert(target.clone());
+
+        log!(
+            LogType::Editor,
+            LogLevel::OK,
+            LogCategory::Entity,
+            "Moved {} entities to '{}' (affected scenes: {:?})",
+            to_move.len(),
+            target,
+            affected
+        );
+
+        for source in affected {
+            if *save {
+                save_writer.write(RequestSaveEvent(source.clone()));
+            }
+            dirty_scenes.mark(source);
+        }
+    }
+}
+
+/// Scenes stop being dirty once saved or despawned
+pub fn clear_dirty_scenes_system(
+    mut dirty_scenes: ResMut<DirtyScenes>,
+    mut save_reader: MessageReader<WorldSaveSuccessEvent>,
+    mut despawn_readers: (
+        MessageReader<RequestDespawnBySource>,
+        MessageReader<RequestDespawnSerializableEntities>,
+    ),
+) {
+    for WorldSaveSuccessEvent(path) in save_reader.read() {
+        dirty_scenes.clear(&absolute_asset_to_rel(path.to_string()));
+    }
+    for RequestDespawnBySource(source, filter) in despawn_readers.0.read() {
+        if filter.is_complete() {
+            dirty_scenes.clear(source);
+        }
+    }
+    if despawn_readers
+        .1
+        .read()
+        .any(|RequestDespawnSerializableEntities(filter)| filter.is_complete())
+    {
+        *dirty_scenes = DirtyScenes::default();
+    }
+}
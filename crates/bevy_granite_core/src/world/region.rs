@@ -0,0 +1,456 @@
+use crate::{
+    absolute_asset_to_rel,
+    entities::{
+        order_parents_first, round_vec3, spawn_scene_entities, EntitySaveReadyData, IdentityData,
+        SceneData, SceneMetadata, SceneSpawnTarget, SceneTarget, SpawnSource,
+    },
+    events::{
+        RequestDespawnBySource, RequestDespawnSerializableEntities, RequestLoadEvent,
+        RequestRegionLoadEvent, WorldLoadSuccessEvent,
+    },
+    materials_from_folder_into_scene, read_binary_scene, read_scene_file, rel_asset_to_absolute,
+    AvailableEditableMaterials,
+};
+use bevy::{ecs::system::SystemParam, math::bounding::Aabb3d, prelude::*};
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+use serde::{
+    de::{DeserializeSeed, IgnoredAny, MapAccess, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    time::Duration,
+};
+use uuid::Uuid;
+
+// region.rs
+// Loading only the entities of a scene inside a bounding box, so one huge world file can be streamed in piece by piece
+// Big scenes keep a region index in their metadata: entity positions bucketed into grid cells. The entities to load
+// are picked from the header and only those are parsed, everything else in the file is skipped over
+// Scenes without an index still load by region, they are parsed whole and filtered
+
+/// Scenes with fewer entities don't get a region index, keeping small scenes short and easy to diff
+pub const REGION_INDEX_MIN_ENTITIES: usize = 1000;
+const REGION_CELL_SIZE: f32 = 32.0;
+
+/// Where an entity of the scene sits, by its index in the file
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SceneRegionEntry {
+    pub index: u32,
+    /// Scene space position, parents applied
+    pub position: Vec3,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SceneRegionIndex {
+    pub cell_size: f32,
+    pub cells: Vec<(IVec3, Vec<SceneRegionEntry>)>,
+}
+
+impl SceneRegionIndex {
+    pub fn build(entities: &[EntitySaveReadyData]) -> Self {
+        let index_by_uuid: HashMap<Uuid, usize> = entities
+            .iter()
+            .enumerate()
+            .map(|(index, entity)| (entity.identity.uuid, index))
+            .collect();
+
+        // Parents come first, so their scene space transform is known by the time a child needs it
+        let mut world = vec![Transform::IDENTITY; entities.len()];
+        let mut parents = vec![None; entities.len()];
+        for (index, entity) in order_parents_first(entities) {
+            let parent = entity
+                .parent
+                .and_then(|parent| index_by_uuid.get(&parent).copied());
+            let local = entity.transform.to_bevy();
+            world[index] = match parent {
+                Some(parent) => world[parent].mul_transform(local),
+                None => local,
+            };
+            parents[index] = parent;
+        }
+
+        let mut cells: HashMap<IVec3, Vec<SceneRegionEntry>> = HashMap::new();
+        for (index, transform) in world.iter().enumerate() {
+            let position = round_vec3(transform.translation);
+            cells
+                .entry(cell_of(position, REGION_CELL_SIZE))
+                .or_default()
+                .push(SceneRegionEntry {
+                    index: index as u32,
+                    position,
+                    parent: parents[index].map(|parent| parent as u32),
+                });
+        }
+        let mut cells: Vec<(IVec3, Vec<SceneRegionEntry>)> = cells.into_iter().collect();
+        cells.sort_by_key(|(cell, _)| (cell.x, cell.y, cell.z));
+
+        Self {
+            cell_size: REGION_CELL_SIZE,
+            cells,
+        }
+    }
+
+    /// Index worth writing into the scene metadata, None for small scenes
+    pub fn for_scene(entities: &[EntitySaveReadyData]) -> Option<Self> {
+        (entities.len() >= REGION_INDEX_MIN_ENTITIES).then(|| Self::build(entities))
+    }
+
+    /// File indices of the entities inside the region, plus their parents so child transforms stay right
+    pub fn query(&self, region: &Aabb3d) -> Vec<usize> {
+        let (min, max) = (Vec3::from(region.min), Vec3::from(region.max));
+        let (min_cell, max_cell) = (cell_of(min, self.cell_size), cell_of(max, self.cell_size));
+        let mut selected: HashSet<u32> = self
+            .cells
+            .iter()
+            .filter(|(cell, _)| cell.cmpge(min_cell).all() && cell.cmple(max_cell).all())
+            .flat_map(|(_, entries)| entries.iter())
+            .filter(|entry| entry.position.cmpge(min).all() && entry.position.cmple(max).all())
+            .map(|entry| entry.index)
+            .collect();
+
+        let parents: HashMap<u32, u32> = self
+            .cells
+            .iter()
+            .flat_map(|(_, entries)| entries.iter())
+            .filter_map(|entry| entry.parent.map(|parent| (entry.index, parent)))
+            .collect();
+        for index in selected.clone() {
+            let mut current = parents.get(&index);
+            while let Some(&parent) = current {
+                if !selected.insert(parent) {
+                    break;
+                }
+                current = parents.get(&parent);
+            }
+        }
+
+        let mut indices: Vec<usize> = selected.into_iter().map(|index| index as usize).collect();
+        indices.sort();
+        indices
+    }
+}
+
+fn cell_of(position: Vec3, cell_size: f32) -> IVec3 {
+    (position / cell_size).floor().as_ivec3()
+}
+
+/// Scenes loaded by region, with the entities read from their file so far
+/// Saving one of these keeps the entities that were never loaded as they are on disk
+#[derive(Resource, Default, Debug)]
+pub struct PartialScenes {
+    scenes: HashMap<String, HashSet<Uuid>>,
+}
+
+impl PartialScenes {
+    pub fn is_partial(&self, source: &str) -> bool {
+        self.scenes.contains_key(source)
+    }
+
+    /// Entities of the scene read from its file, by uuid
+    pub fn loaded(&self, source: &str) -> Option<&HashSet<Uuid>> {
+        self.scenes.get(source)
+    }
+
+    pub fn clear(&mut self, source: &str) {
+        self.scenes.remove(source);
+    }
+}
+
+/// Scene entities to save, with the entities a region load never read put back in file order
+/// Loaded entities missing from `saved` were deleted and stay deleted
+pub(crate) fn merge_unloaded_entities(
+    saved: Vec<EntitySaveReadyData>,
+    original: &[EntitySaveReadyData],
+    loaded: &HashSet<Uuid>,
+) -> Vec<EntitySaveReadyData> {
+    let mut saved_by_uuid: HashMap<Uuid, EntitySaveReadyData> = HashMap::new();
+    let mut saved_order: Vec<Uuid> = Vec::with_capacity(saved.len());
+    for entity in saved {
+        saved_order.push(entity.identity.uuid);
+        saved_by_uuid.insert(entity.identity.uuid, entity);
+    }
+
+    let mut merged = Vec::with_capacity(original.len().max(saved_order.len()));
+    for entity in original.iter() {
+        let uuid = entity.identity.uuid;
+        if let Some(saved) = saved_by_uuid.remove(&uuid) {
+            merged.push(saved);
+        } else if !loaded.contains(&uuid) {
+            merged.push(entity.clone());
+        }
+    }
+    // Entities new to the scene go last
+    merged.extend(
+        saved_order
+            .into_iter()
+            .filter_map(|uuid| saved_by_uuid.remove(&uuid)),
+    );
+    merged
+}
+
+/// Just the metadata, the entities are an unknown field and skipped without being built
+#[derive(Deserialize)]
+struct SceneHeader {
+    metadata: SceneMetadata,
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum SceneField {
+    Entities,
+    #[serde(other)]
+    Other,
+}
+
+/// Deserializes a scene keeping only the entities at the wanted file indices
+struct SelectedEntities<'a>(&'a HashSet<usize>);
+
+impl<'de> DeserializeSeed<'de> for SelectedEntities<'_> {
+    type Value = Vec<(usize, EntitySaveReadyData)>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_struct("SceneData", &["metadata", "entities"], self)
+    }
+}
+
+impl<'de> Visitor<'de> for SelectedEntities<'_> {
+    type Value = Vec<(usize, EntitySaveReadyData)>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a scene with metadata and entities")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut entities = Vec::new();
+        while let Some(field) = map.next_key::<SceneField>()? {
+            match field {
+                SceneField::Entities => {
+                    entities = map.next_value_seed(SelectedEntityList(self.0))?;
+                }
+                SceneField::Other => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        Ok(entities)
+    }
+}
+
+struct SelectedEntityList<'a>(&'a HashSet<usize>);
+
+impl<'de> DeserializeSeed<'de> for SelectedEntityList<'_> {
+    type Value = Vec<(usize, EntitySaveReadyData)>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for SelectedEntityList<'_> {
+    type Value = Vec<(usize, EntitySaveReadyData)>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a list of entities")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut entities = Vec::with_capacity(self.0.len());
+        let mut index = 0;
+        loop {
+            if self.0.contains(&index) {
+                match seq.next_element::<EntitySaveReadyData>()? {
+                    Some(entity) => entities.push((index, entity)),
+                    None => break,
+                }
+            } else if seq.next_element::<IgnoredAny>()?.is_none() {
+                break;
+            }
+            index += 1;
+        }
+        Ok(entities)
+    }
+}
+
+/// The scene's entities inside the region, with their file indices
+pub fn read_scene_region(
+    abs_path: &str,
+    region: &Aabb3d,
+) -> Result<Vec<(usize, EntitySaveReadyData)>, String> {
+    // Binary scenes decode whole, that's still far quicker than picking entities out of RON
+    if let Some(scene) = read_binary_scene(abs_path).map_err(|e| e.to_string())? {
+        let index = scene
+            .metadata
+            .region_index
+            .unwrap_or_else(|| SceneRegionIndex::build(&scene.entities));
+        let wanted: HashSet<usize> = index.query(region).into_iter().collect();
+        return Ok(scene
+            .entities
+            .into_iter()
+            .enumerate()
+            .filter(|(index, _)| wanted.contains(index))
+            .collect());
+    }
+
+    let contents = read_scene_file(abs_path).map_err(|e| e.to_string())?;
+
+    if let Some(index) = ron::de::from_str::<SceneHeader>(&contents)
+        .ok()
+        .and_then(|header| header.metadata.region_index)
+    {
+        let wanted: HashSet<usize> = index.query(region).into_iter().collect();
+        let mut deserializer =
+            ron::de::Deserializer::from_str(&contents).map_err(|e| e.to_string())?;
+        return SelectedEntities(&wanted)
+            .deserialize(&mut deserializer)
+            .map_err(|e| e.to_string());
+    }
+
+    // No index, parse everything and filter
+    let entities = match ron::de::from_str::<SceneData>(&contents) {
+        Ok(scene) => scene.entities,
+        Err(_) => {
+            ron::de::from_str::<Vec<EntitySaveReadyData>>(&contents).map_err(|e| e.to_string())?
+        }
+    };
+    let wanted: HashSet<usize> = SceneRegionIndex::build(&entities)
+        .query(region)
+        .into_iter()
+        .collect();
+    Ok(entities
+        .into_iter()
+        .enumerate()
+        .filter(|(index, _)| wanted.contains(index))
+        .collect())
+}
+
+/// Region loads plus the requests that make a scene whole again or unload it
+#[derive(SystemParam)]
+pub struct RegionLoadReaders<'w, 's> {
+    region: MessageReader<'w, 's, RequestRegionLoadEvent>,
+    load: MessageReader<'w, 's, RequestLoadEvent>,
+    despawn_source: MessageReader<'w, 's, RequestDespawnBySource>,
+    despawn_all: MessageReader<'w, 's, RequestDespawnSerializableEntities>,
+}
+
+type RegionLoadState<'w, 's> = (
+    ResMut<'w, PartialScenes>,
+    Query<'w, 's, (Entity, &'static IdentityData, &'static SpawnSource)>,
+    MessageWriter<'w, WorldLoadSuccessEvent>,
+    Res<'w, SceneTarget>,
+);
+
+/// Watches for RequestRegionLoadEvent and spawns the entities of the region not loaded yet
+pub fn region_world_reader(
+    asset_server: Res<AssetServer>,
+    mut commands: Commands,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut available_materials: ResMut<AvailableEditableMaterials>,
+    mut readers: RegionLoadReaders,
+    state: RegionLoadState,
+) {
+    let (mut partial, loaded_query, mut world_load_success_writer, scene_target) = state;
+
+    // A full load or an unload ends the partial state of a scene
+    for RequestLoadEvent(path, ..) in readers.load.read() {
+        partial.clear(&absolute_asset_to_rel(path.to_string()));
+    }
+    for RequestDespawnBySource(path, _) in readers.despawn_source.read() {
+        partial.clear(&absolute_asset_to_rel(path.to_string()));
+    }
+    if readers.despawn_all.read().count() > 0 {
+        partial.scenes.clear();
+    }
+
+    for RequestRegionLoadEvent {
+        path,
+        save_settings,
+        transform,
+        region,
+    } in readers.region.read()
+    {
+        let rel = absolute_asset_to_rel(path.to_string()).to_string();
+        let abs_path = rel_asset_to_absolute(&rel);
+        let selected = match read_scene_region(abs_path.as_ref(), region) {
+            Ok(selected) => selected,
+            Err(e) => {
+                log!(
+                    LogType::Game,
+                    LogLevel::Error,
+                    LogCategory::System,
+                    "Failed to load region of '{}': {}",
+                    rel,
+                    e
+                );
+                continue;
+            }
+        };
+
+        // Entities already in the world from an earlier region stay, new children attach to them
+        let loaded: HashMap<Uuid, Entity> = loaded_query
+            .iter()
+            .filter(|(_, _, source)| source.str_ref() == rel)
+            .map(|(entity, identity, _)| (identity.uuid, entity))
+            .collect();
+        // Skipped entities are never read, so saving the partial scene keeps them from the file
+        let (indices, mut selected): (Vec<usize>, Vec<EntitySaveReadyData>) =
+            selected.into_iter().unzip();
+        let keep = scene_target.filter(&mut selected);
+        let (file_indices, entities): (Vec<usize>, Vec<EntitySaveReadyData>) = indices
+            .into_iter()
+            .zip(selected)
+            .zip(keep)
+            .filter_map(|(selected, keep)| keep.then_some(selected))
+            .filter(|(_, entity)| !loaded.contains_key(&entity.identity.uuid))
+            .unzip();
+
+        let read = partial.scenes.entry(rel.clone()).or_default();
+        read.extend(loaded.keys().copied());
+        read.extend(entities.iter().map(|entity| entity.identity.uuid));
+
+        materials_from_folder_into_scene(
+            "materials",
+            &mut materials,
+            &mut available_materials,
+            &asset_server,
+        );
+        let count = entities.len();
+        spawn_scene_entities(
+            &asset_server,
+            &mut commands,
+            &mut materials,
+            &mut available_materials,
+            &mut meshes,
+            &entities,
+            &SceneSpawnTarget {
+                abs_path: abs_path.clone(),
+                save_settings: save_settings.clone(),
+                transform_override: *transform,
+                file_indices: Some(file_indices),
+                loaded,
+                prefab_sources: None,
+                new_to_scene: false,
+                file_time: Duration::ZERO,
+            },
+        );
+
+        log!(
+            LogType::Game,
+            LogLevel::OK,
+            LogCategory::System,
+            "Loaded {} entities from region {:?}..{:?} of {:?}",
+            count,
+            Vec3::from(region.min),
+            Vec3::from(region.max),
+            &rel
+        );
+        world_load_success_writer.write(WorldLoadSuccessEvent(rel));
+    }
+}
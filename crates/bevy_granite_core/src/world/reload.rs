@@ -1,15 +1,15 @@
 use crate::{
     absolute_asset_to_rel,
-    entities::{despawn_recursive_serializable_entities, IdentityData, SaveSettings},
+    entities::{despawn_recursive_serializable_entities, DespawnCandidates, SaveSettings},
     events::{RequestLoadEvent, RequestReloadEvent},
 };
-use bevy::prelude::{Commands, Entity, MessageReader, MessageWriter, Query, With};
+use bevy::prelude::{Commands, MessageReader, MessageWriter};
 
-/// Despawns all entities then loads the world
+/// Despawns all entities, except PersistAcrossLoads ones, then loads the world
 pub fn reload_world_system(
     mut relead_watcher: MessageReader<RequestReloadEvent>,
     mut commands: Commands,
-    serializable_query: Query<Entity, With<IdentityData>>,
+    serializable_query: DespawnCandidates,
     mut load_world_writter: MessageWriter<RequestLoadEvent>,
 ) {
     for RequestReloadEvent(path) in relead_watcher.read() {
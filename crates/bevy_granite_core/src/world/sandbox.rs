@@ -0,0 +1,238 @@
+use super::dependencies::{asset_references, normalize_rel_path};
+use crate::{
+    absolute_asset_to_rel,
+    entities::{deserialize_entities, EntitySaveReadyData, GraniteType, SceneTarget},
+    events::{RequestSandboxedLoadEvent, SandboxedLoadReportEvent, WorldLoadSuccessEvent},
+    AvailableEditableMaterials,
+};
+use bevy::prelude::*;
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+use std::{collections::HashSet, fmt};
+
+/// Rules for loading scenes from untrusted sources, i.e. mods or user generated content
+/// Anything not whitelisted is stripped and listed in the SandboxReport
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SceneSandbox {
+    /// Component type paths entities may carry, i.e. "my_game::Health". A bare type name also matches
+    pub allowed_components: Vec<String>,
+    /// Folders relative to assets that the scene and every referenced file must be in, i.e. "mods/castle"
+    pub allowed_asset_dirs: Vec<String>,
+    /// Refuse the whole scene on any violation, instead of loading what is allowed
+    pub reject_on_violation: bool,
+}
+
+/// Something a sandboxed scene tried to use that it is not allowed to
+#[derive(Debug, Clone, PartialEq)]
+pub enum SandboxViolation {
+    /// The scene file itself is outside the allowed folders
+    Scene(String),
+    /// A component was stripped from an entity
+    Component { entity: String, component: String },
+    /// An entity referenced a file outside the allowed folders and was not spawned
+    Asset { entity: String, path: String },
+}
+
+impl fmt::Display for SandboxViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Scene(path) => write!(f, "Scene '{}' is outside the allowed folders", path),
+            Self::Component { entity, component } => {
+                write!(f, "Stripped component '{}' from '{}'", component, entity)
+            }
+            Self::Asset { entity, path } => {
+                write!(f, "Skipped '{}', it references '{}'", entity, path)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SandboxReport {
+    pub path: String,
+    pub violations: Vec<SandboxViolation>,
+    /// Entities that passed the sandbox and were spawned
+    pub loaded_entities: usize,
+    /// True when nothing was loaded because of reject_on_violation or the scene path
+    pub rejected: bool,
+}
+
+impl SandboxReport {
+    pub fn is_clean(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+impl SceneSandbox {
+    pub fn is_component_allowed(&self, type_path: &str) -> bool {
+        let short_name = type_path.rsplit("::").next().unwrap_or(type_path);
+        self.allowed_components
+            .iter()
+            .any(|allowed| allowed == type_path || allowed == short_name)
+    }
+
+    /// Relative paths inside an allowed folder. Absolute paths and ".." never pass
+    pub fn is_path_allowed(&self, path: &str) -> bool {
+        let path = normalize_rel_path(path);
+        if path.starts_with('/') || path.contains(':') || path.split('/').any(|part| part == "..") {
+            return false;
+        }
+
+        self.allowed_asset_dirs.iter().any(|dir| {
+            let dir = normalize_rel_path(dir);
+            let dir = dir.trim_end_matches('/');
+            path.strip_prefix(dir)
+                .is_some_and(|rest| rest.starts_with('/'))
+        })
+    }
+
+    /// Strip what is not allowed from the scene entities
+    pub fn filter(
+        &self,
+        path: &str,
+        entities: Vec<EntitySaveReadyData>,
+    ) -> (Vec<EntitySaveReadyData>, SandboxReport) {
+        let mut report = SandboxReport {
+            path: path.to_string(),
+            ..Default::default()
+        };
+
+        if !self.is_path_allowed(path) {
+            report
+                .violations
+                .push(SandboxViolation::Scene(path.to_string()));
+            report.rejected = true;
+            return (vec![], report);
+        }
+
+        let mut allowed_entities = vec![];
+        for mut entity in entities {
+            let entity_name = format!(
+                "{} ({})",
+                entity.identity.name,
+                entity.identity.class.type_abv()
+            );
+
+            let mut references = ron::ser::to_string(&entity.identity.class)
+                .map(|class| asset_references(&class))
+                .unwrap_or_default();
+
+            if let Some(components) = entity.components.as_mut() {
+                let stripped: Vec<String> = components
+                    .keys()
+                    .filter(|component| !self.is_component_allowed(component))
+                    .cloned()
+                    .collect();
+                for component in stripped {
+                    components.remove(&component);
+                    report.violations.push(SandboxViolation::Component {
+                        entity: entity_name.clone(),
+                        component,
+                    });
+                }
+                for value in components.values() {
+                    references.extend(asset_references(value));
+                }
+            }
+
+            let outside: HashSet<String> = references
+                .into_iter()
+                .filter(|reference| !self.is_path_allowed(reference))
+                .collect();
+            if outside.is_empty() {
+                allowed_entities.push(entity);
+            } else {
+                for path in outside {
+                    report.violations.push(SandboxViolation::Asset {
+                        entity: entity_name.clone(),
+                        path,
+                    });
+                }
+            }
+        }
+
+        if self.reject_on_violation && !report.is_clean() {
+            report.rejected = true;
+            return (vec![], report);
+        }
+
+        report.loaded_entities = allowed_entities.len();
+        (allowed_entities, report)
+    }
+}
+
+/// Watches for RequestSandboxedLoadEvent, loads what the sandbox allows and reports the rest
+#[allow(clippy::too_many_arguments)]
+pub fn sandboxed_world_reader(
+    asset_server: Res<AssetServer>,
+    mut commands: Commands,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut available_materials: ResMut<AvailableEditableMaterials>,
+    scene_target: Res<SceneTarget>,
+    mut load_reader: MessageReader<RequestSandboxedLoadEvent>,
+    mut writers: (
+        MessageWriter<SandboxedLoadReportEvent>,
+        MessageWriter<WorldLoadSuccessEvent>,
+    ),
+) {
+    let (report_writer, world_load_success_writer) = &mut writers;
+    if let Some(RequestSandboxedLoadEvent {
+        path,
+        save_settings,
+        transform,
+        sandbox,
+    }) = load_reader.read().next()
+    {
+        let rel = absolute_asset_to_rel(path.to_string()).to_string();
+        let report = deserialize_entities(
+            &asset_server,
+            &mut commands,
+            &mut materials,
+            &mut available_materials,
+            &mut meshes,
+            rel.clone(),
+            save_settings.clone(),
+            *transform,
+            Some(sandbox),
+            &scene_target,
+        )
+        .unwrap_or_default();
+
+        for violation in report.violations.iter() {
+            log!(
+                LogType::Game,
+                LogLevel::Warning,
+                LogCategory::System,
+                "Sandbox: {}",
+                violation
+            );
+        }
+
+        if report.rejected {
+            log!(
+                LogType::Game,
+                LogLevel::Error,
+                LogCategory::System,
+                "Sandbox rejected scene '{}' ({} violations)",
+                rel,
+                report.violations.len()
+            );
+        } else {
+            log!(
+                LogType::Game,
+                LogLevel::OK,
+                LogCategory::System,
+                "Loaded sandboxed world: {:?} ({} entities, {} violations)",
+                &rel,
+                report.loaded_entities,
+                report.violations.len()
+            );
+            world_load_success_writer.write(WorldLoadSuccessEvent(rel));
+        }
+
+        report_writer.write(SandboxedLoadReportEvent(report));
+    }
+}
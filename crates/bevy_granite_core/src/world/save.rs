@@ -1,13 +1,22 @@
 use crate::{
-    entities::{serialize_entities, ComponentEditor, HasRuntimeData, IdentityData, SpawnSource},
+    entities::{
+        read_scene_entities, serialize_entities, ComponentEditor, EntitySaveReadyData,
+        HasRuntimeData, IdentityData, SceneOrder, SpawnSource,
+    },
     events::{CollectRuntimeDataEvent, RequestSaveEvent, RuntimeDataReadyEvent},
-    shared::absolute_asset_to_rel,
-    WorldSaveSuccessEvent,
+    shared::{absolute_asset_to_rel, EditorCapabilities, SceneCompressionSettings},
+    world::{
+        refresh_prefab_overrides, IncrementalSave, PartialScenes, SceneFileWatcher, SceneProfile,
+        SceneProfileKind, SceneProfileReport,
+    },
+    GraniteType, WorldSaveSuccessEvent,
 };
 use bevy::{
     asset::io::file::FileAssetReader,
-    ecs::entity::Entity,
-    prelude::{ChildOf, Commands, MessageReader, MessageWriter, Query, ResMut, Resource, World},
+    ecs::{change_detection::Tick, entity::Entity, system::SystemParam},
+    prelude::{
+        ChildOf, Commands, MessageReader, MessageWriter, Query, Res, ResMut, Resource, World,
+    },
     transform::components::Transform,
 };
 use bevy_granite_logging::{
@@ -15,12 +24,17 @@ use bevy_granite_logging::{
     log,
 };
 use std::path::PathBuf;
-use std::{borrow::Cow, collections::HashMap};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    time::Instant,
+};
+use uuid::Uuid;
 
 #[derive(Default, Debug, Clone)]
 pub struct WorldState {
     // Can easily be queried for, so we can immediately get this data
-    pub entity_data: Option<Vec<(Entity, IdentityData, Transform, Option<Entity>, crate::entities::SaveSettings)>>, // Added parent entity, UUID, and SaveSettings
+    pub entity_data: Option<Vec<SaveEntityData>>, // Added parent entity, UUID, and SaveSettings
 
     // More difficult to get, so we do no have this off rip
     // We need to use World and the type registry to build and send event back saying its ready
@@ -28,6 +42,25 @@ pub struct WorldState {
 
     // Inside world runner, when gathered this flag gets set
     pub components_ready: bool,
+
+    /// Entities read from file, when the scene was only loaded by region. The rest of the file is kept on save
+    pub partial: Option<HashSet<Uuid>>,
+
+    /// Incremental saves: records on disk of entities not edited since the file was read or written, written back as is
+    pub unchanged: HashMap<Entity, EntitySaveReadyData>,
+
+    /// When components were collected, entities saved match their records as of then
+    pub collected_tick: Option<Tick>,
+
+    /// Timings of component collection, finished once the file is written
+    pub profile: Option<SceneProfile>,
+}
+
+/// While a reason is set, scene saves and asset moves are refused
+/// Used by the editor for its read-only mode
+#[derive(Resource, Default, Clone)]
+pub struct SaveLock {
+    pub reason: Option<String>,
 }
 
 #[derive(Resource, Default)]
@@ -35,6 +68,66 @@ pub struct SaveWorldRequestData {
     pub pending_saves: HashMap<Cow<'static, str>, (PathBuf, WorldState)>, // source -> (path, world_state)
 }
 
+/// Sort entities back into their file order, entities without a SceneOrder (new to the scene) go last
+pub(crate) fn sort_by_scene_order<T>(
+    items: &mut [T],
+    key: impl Fn(&T) -> (Entity, Option<SceneOrder>),
+) {
+    items.sort_by_key(|item| {
+        let (entity, order) = key(item);
+        (order.is_none(), order, entity)
+    });
+}
+
+/// Entity data gathered for a save, i.e. identity, transform, parent and save settings
+pub type SaveEntityData = (
+    Entity,
+    IdentityData,
+    Transform,
+    Option<Entity>,
+    crate::entities::SaveSettings,
+);
+
+/// Everything a save request is checked against before entities are gathered
+#[derive(SystemParam)]
+pub struct SaveRequestGuards<'w> {
+    save_lock: Res<'w, SaveLock>,
+    watcher: Res<'w, SceneFileWatcher>,
+    capabilities: Res<'w, EditorCapabilities>,
+    partial: Res<'w, PartialScenes>,
+}
+
+impl SaveRequestGuards<'_> {
+    /// Logs and returns false if the file at path may not be saved right now
+    fn allows(&self, path: &str) -> bool {
+        if let Some(reason) = &self.save_lock.reason {
+            log!(
+                LogType::Editor,
+                LogLevel::Warning,
+                LogCategory::System,
+                "Refused to save '{}': {}",
+                path,
+                reason
+            );
+            return false;
+        }
+        if !self.capabilities.can_save(path) {
+            return false;
+        }
+        if self.watcher.is_changed(path) {
+            log!(
+                LogType::Editor,
+                LogLevel::Warning,
+                LogCategory::System,
+                "Refused to save '{}': the file changed on disk, reload it or keep the loaded scene first",
+                path
+            );
+            return false;
+        }
+        true
+    }
+}
+
 /// Part 1.
 /// We gather all entities that are serializeable with
 /// IdentityData and Transform
@@ -45,16 +138,22 @@ pub fn save_request_system(
     mut save_request: ResMut<SaveWorldRequestData>,
     mut event_writer: MessageWriter<CollectRuntimeDataEvent>,
     mut event_reader: MessageReader<RequestSaveEvent>,
+    guards: SaveRequestGuards,
     query: Query<(
         Entity,
         &IdentityData,
         Option<&Transform>,
         Option<&ChildOf>,
         &SpawnSource,
+        Option<&SceneOrder>,
     )>,
 ) {
     // Process only one save request per frame to avoid conflicts
     if let Some(RequestSaveEvent(path)) = event_reader.read().next() {
+        if !guards.allows(path) {
+            return;
+        }
+
         let spawn_source = absolute_asset_to_rel(path.clone());
 
         log!(
@@ -71,10 +170,17 @@ pub fn save_request_system(
         // Part 1.
         // Gather all entities that are serializeable and contain IdentityData and Transform
         // Filter by SpawnSource to only include entities from the target source
-        let entities_data: Vec<(Entity, IdentityData, Transform, Option<Entity>, crate::entities::SaveSettings)> = query
+        // Keep file order so saves don't reshuffle the scene, entities new to it go last
+        let mut ordered: Vec<_> = query
             .iter()
-            .filter(|(_, _, _, _, source)| source.str_ref() == spawn_source)
-            .map(|(entity, obj, transform, relation, source)| {
+            .filter(|(_, _, _, _, source, _)| source.str_ref() == spawn_source)
+            .collect();
+        sort_by_scene_order(&mut ordered, |(entity, _, _, _, _, order)| {
+            (*entity, order.copied())
+        });
+        let entities_data: Vec<SaveEntityData> = ordered
+            .into_iter()
+            .map(|(entity, obj, transform, relation, source, _)| {
                 (
                     entity,
                     obj.clone(),
@@ -113,6 +219,10 @@ pub fn save_request_system(
             entity_data: Some(entities_data),
             component_data: None,
             components_ready: false,
+            partial: guards.partial.loaded(&spawn_source).cloned(),
+            unchanged: HashMap::new(),
+            collected_tick: None,
+            profile: None,
         };
 
         save_request
@@ -167,12 +277,46 @@ pub fn collect_components_system(
 
         // Need access to world to get components
         commands.queue(move |world: &mut World| {
+            let started = Instant::now();
+            // Instance roots save how their prefab members were edited
+            refresh_prefab_overrides(world, &entities);
+
+            let unchanged = unchanged_records(world, &spawn_source_clone);
             let component_editor = world.resource::<ComponentEditor>();
             let mut collected_data = HashMap::new();
+            let mut profile = SceneProfile::new(
+                SceneProfileKind::Save,
+                absolute_asset_to_rel(spawn_source_clone.to_string()).to_string(),
+            );
 
             for entity in entities {
-                let serialized_components =
-                    component_editor.serialize_entity_components(world, entity);
+                if unchanged.contains_key(&entity) {
+                    continue;
+                }
+                let entity_started = Instant::now();
+                let (serialized_components, timings) =
+                    component_editor.serialize_entity_components_timed(world, entity);
+                if let Some(identity) = world.get::<IdentityData>(entity) {
+                    let bytes = serialized_components
+                        .iter()
+                        .map(|(type_path, data)| type_path.len() + data.len())
+                        .sum();
+                    let component_time = timings.iter().map(|(_, time)| *time).sum();
+                    profile.record_entity(
+                        identity.uuid,
+                        &identity.name,
+                        &identity.class.type_name(),
+                        entity_started.elapsed().saturating_sub(component_time),
+                        bytes,
+                    );
+                    for (type_path, time) in timings {
+                        let bytes = serialized_components
+                            .get(&type_path)
+                            .map(|data| type_path.len() + data.len())
+                            .unwrap_or_default();
+                        profile.record_component(identity.uuid, &type_path, time, bytes);
+                    }
+                }
 
                 if !serialized_components.is_empty() {
                     collected_data.insert(entity, serialized_components);
@@ -187,9 +331,14 @@ pub fn collect_components_system(
                 collected_data
             );
 
+            let collected_tick = world.change_tick();
+            profile.total = started.elapsed();
             if let Some(mut data) = world.get_resource_mut::<SaveWorldRequestData>() {
                 if let Some((_, world_state)) = data.pending_saves.get_mut(&spawn_source_clone) {
                     world_state.component_data = Some(collected_data);
+                    world_state.unchanged = unchanged;
+                    world_state.collected_tick = Some(collected_tick);
+                    world_state.profile = Some(profile);
                     world_state.components_ready = true;
 
                     log!(
@@ -207,11 +356,71 @@ pub fn collect_components_system(
     }
 }
 
+/// Incremental saves: the disk records of entities that weren't edited and still have the same components and parent
+/// Their components are not serialized again
+fn unchanged_records(world: &mut World, source: &str) -> HashMap<Entity, EntitySaveReadyData> {
+    if !world.resource::<IncrementalSave>().is_incremental() {
+        return HashMap::new();
+    }
+    let Some(path) = world
+        .resource::<SaveWorldRequestData>()
+        .pending_saves
+        .get(source)
+        .map(|(path, _)| path.display().to_string())
+    else {
+        return HashMap::new();
+    };
+    let mut originals: HashMap<Uuid, EntitySaveReadyData> = read_scene_entities(&path)
+        .into_iter()
+        .map(|record| (record.identity.uuid, record))
+        .collect();
+
+    let entities: Vec<(Entity, Uuid, Option<Entity>)> = world
+        .query::<(Entity, &IdentityData, &SpawnSource, Option<&ChildOf>)>()
+        .iter(world)
+        .filter(|(_, _, spawn_source, _)| spawn_source.str_ref() == source)
+        .map(|(entity, identity, _, parent)| (entity, identity.uuid, parent.map(|p| p.parent())))
+        .collect();
+
+    let incremental = world.resource::<IncrementalSave>();
+    let component_editor = world.resource::<ComponentEditor>();
+    let mut unchanged = HashMap::new();
+    for (entity, uuid, parent) in entities {
+        if incremental.is_dirty(world, entity) {
+            continue;
+        }
+        let Some(original) = originals.remove(&uuid) else {
+            continue;
+        };
+        let parent_uuid = parent
+            .and_then(|parent| world.get::<IdentityData>(parent))
+            .map(|identity| identity.uuid);
+        let components = component_editor.saved_component_names(world, entity);
+        let original_components: HashSet<&str> = original
+            .components
+            .iter()
+            .flatten()
+            .map(|(type_path, _)| type_path.as_str())
+            .collect();
+        let same_components = components.len() == original_components.len()
+            && components
+                .iter()
+                .all(|type_path| original_components.contains(type_path.as_str()));
+        if original.parent == parent_uuid && same_components {
+            unchanged.insert(entity, original);
+        }
+    }
+    unchanged
+}
+
 /// Component data is ready, we can save the world
 pub fn save_data_ready_system(
     mut event_reader: MessageReader<RuntimeDataReadyEvent>,
     mut save_request_data: ResMut<SaveWorldRequestData>,
     mut saved_event_writer: MessageWriter<WorldSaveSuccessEvent>,
+    mut incremental: ResMut<IncrementalSave>,
+    compression: Res<SceneCompressionSettings>,
+    mut profile_report: ResMut<SceneProfileReport>,
 ) {
     for RuntimeDataReadyEvent(source) in event_reader.read() {
         log!(
@@ -223,7 +432,7 @@ pub fn save_data_ready_system(
         );
         let source: &str = source.as_ref();
 
-        if let Some((path, world_state)) = save_request_data.pending_saves.remove(source) {
+        if let Some((path, mut world_state)) = save_request_data.pending_saves.remove(source) {
             if !world_state.components_ready {
                 log!(
                     LogType::Game,
@@ -242,7 +451,30 @@ pub fn save_data_ready_system(
                 "Components gathered and ready to save for source '{}'",
                 source
             );
-            serialize_entities(world_state, Some(path.display().to_string()));
+            let saved: Vec<Entity> = world_state
+                .entity_data
+                .iter()
+                .flatten()
+                .map(|(entity, ..)| *entity)
+                .collect();
+            let collected_tick = world_state.collected_tick;
+            let profile = world_state.profile.take();
+            let write_started = Instant::now();
+            serialize_entities(
+                world_state,
+                Some(path.display().to_string()),
+                compression.for_scene(source),
+            );
+            if let Some(mut profile) = profile {
+                profile.file = write_started.elapsed();
+                profile.total += profile.file;
+                profile_report.finish(profile);
+            }
+            if let Some(tick) = collected_tick {
+                for entity in saved {
+                    incremental.sync(entity, tick);
+                }
+            }
             log!(
                 LogType::Game,
                 LogLevel::OK,
@@ -262,3 +494,67 @@ pub fn save_data_ready_system(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{order_parents_first, TransformData};
+
+    fn entity(id: u128, parent: Option<u128>) -> EntitySaveReadyData {
+        EntitySaveReadyData {
+            identity: IdentityData {
+                uuid: Uuid::from_u128(id),
+                ..Default::default()
+            },
+            transform: TransformData::default(),
+            parent: parent.map(Uuid::from_u128),
+            components: None,
+        }
+    }
+
+    #[test]
+    fn save_order_follows_scene_order_with_new_entities_last() {
+        let mut world = World::new();
+        let new_entity = world.spawn_empty().id();
+        let second = world.spawn_empty().id();
+        let first = world.spawn_empty().id();
+        let mut items = vec![
+            (new_entity, None),
+            (second, Some(SceneOrder(1))),
+            (first, Some(SceneOrder(0))),
+        ];
+        sort_by_scene_order(&mut items, |(entity, order)| (*entity, *order));
+        let entities: Vec<Entity> = items.into_iter().map(|(entity, _)| entity).collect();
+        assert_eq!(entities, vec![first, second, new_entity]);
+    }
+
+    #[test]
+    fn load_then_save_keeps_file_order() {
+        // Children listed before their parents are spawned after them on load
+        let file = [
+            entity(3, Some(2)),
+            entity(1, None),
+            entity(2, Some(1)),
+            entity(4, None),
+        ];
+        let mut world = World::new();
+        let mut spawned: Vec<(Entity, Option<SceneOrder>, u128)> = order_parents_first(&file)
+            .into_iter()
+            .map(|(file_index, save_data)| {
+                (
+                    world.spawn_empty().id(),
+                    Some(SceneOrder(file_index)),
+                    save_data.identity.uuid.as_u128(),
+                )
+            })
+            .collect();
+        sort_by_scene_order(&mut spawned, |(entity, order, _)| (*entity, *order));
+
+        let saved: Vec<u128> = spawned.into_iter().map(|(_, _, id)| id).collect();
+        let expected: Vec<u128> = file
+            .iter()
+            .map(|save_data| save_data.identity.uuid.as_u128())
+            .collect();
+        assert_eq!(saved, expected);
+    }
+}
@@ -0,0 +1,346 @@
+use crate::{
+    absolute_asset_to_rel,
+    entities::{
+        despawn_filtered, read_scene_entities, round_quat, round_vec3, ComponentEditor,
+        DespawnCandidates, DespawnFilter, EntitySaveReadyData, HasRuntimeData, IdentityData,
+        SaveSettings, SpawnSource, TransformData,
+    },
+    events::{
+        RequestLoadEvent, RequestLoadSaveGameEvent, RequestSaveGameEvent, SaveGameLoadedEvent,
+        SaveGameSuccessEvent, WorldLoadSuccessEvent,
+    },
+    rel_asset_to_absolute,
+    shared::version::Version,
+};
+use bevy::prelude::*;
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::Path,
+};
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SaveGameMetadata {
+    pub format_version: Version,
+    /// Authored scene the deltas apply to, relative to assets
+    pub scene: String,
+}
+
+/// What changed on one authored entity since the scene was loaded
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct EntityDelta {
+    pub uuid: Uuid,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transform: Option<TransformData>,
+
+    /// Components added or with new values. type path -> serialized component
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub components: HashMap<String, String>,
+
+    /// Components the authored entity had and the runtime one doesn't
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub removed_components: Vec<String>,
+}
+
+impl EntityDelta {
+    pub fn is_empty(&self) -> bool {
+        self.transform.is_none() && self.components.is_empty() && self.removed_components.is_empty()
+    }
+}
+
+/// Runtime changes to an authored scene. Only what differs from the scene file is stored
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SaveGameData {
+    pub metadata: SaveGameMetadata,
+    #[serde(default)]
+    pub changed: Vec<EntityDelta>,
+    /// Authored entities that no longer exist
+    #[serde(default)]
+    pub destroyed: Vec<Uuid>,
+}
+
+impl SaveGameData {
+    pub fn new(scene: impl Into<String>) -> Self {
+        Self {
+            metadata: SaveGameMetadata {
+                format_version: Version::CURRENT_VERSION,
+                scene: scene.into(),
+            },
+            changed: vec![],
+            destroyed: vec![],
+        }
+    }
+}
+
+/// Save games waiting for their base scene to finish loading. scene source -> deltas
+#[derive(Resource, Default)]
+pub struct PendingSaveGames {
+    pending: HashMap<String, SaveGameData>,
+}
+
+/// Runtime state of one entity, compared against the authored scene
+pub struct RuntimeEntityState {
+    pub uuid: Uuid,
+    pub transform: Transform,
+    pub components: HashMap<String, String>,
+}
+
+/// Compare runtime entities against the authored ones from the same scene
+/// Entities that are not in the authored scene are ignored, they were spawned at runtime
+pub fn build_save_game(
+    scene: &str,
+    authored: &[EntitySaveReadyData],
+    runtime: &[RuntimeEntityState],
+) -> SaveGameData {
+    let runtime_by_uuid: HashMap<Uuid, &RuntimeEntityState> =
+        runtime.iter().map(|state| (state.uuid, state)).collect();
+    let mut save_game = SaveGameData::new(scene);
+
+    for authored_entity in authored {
+        let uuid = authored_entity.identity.uuid;
+        let Some(state) = runtime_by_uuid.get(&uuid) else {
+            save_game.destroyed.push(uuid);
+            continue;
+        };
+
+        let transform = TransformData {
+            position: round_vec3(state.transform.translation),
+            rotation: round_quat(state.transform.rotation),
+            scale: round_vec3(state.transform.scale),
+        };
+        let authored_components = authored_entity.components.clone().unwrap_or_default();
+
+        let delta = EntityDelta {
+            uuid,
+            transform: (transform != authored_entity.transform).then_some(transform),
+            components: state
+                .components
+                .iter()
+                .filter(|(name, value)| authored_components.get(*name) != Some(*value))
+                .map(|(name, value)| (name.clone(), value.clone()))
+                .collect(),
+            removed_components: authored_components
+                .keys()
+                .filter(|name| !state.components.contains_key(*name))
+                .cloned()
+                .collect(),
+        };
+        if !delta.is_empty() {
+            save_game.changed.push(delta);
+        }
+    }
+
+    save_game
+}
+
+pub fn write_save_game(path: &str, save_game: &SaveGameData) -> std::io::Result<()> {
+    let serialized = ron::to_string(save_game)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serialized)
+}
+
+pub fn read_save_game(path: &str) -> Result<SaveGameData, String> {
+    let contents = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    ron::de::from_str::<SaveGameData>(&contents).map_err(|e| e.to_string())
+}
+
+/// Diffs a loaded scene against its file on disk and writes the deltas to the save file
+pub fn save_game_request_system(
+    mut commands: Commands,
+    mut save_game_reader: MessageReader<RequestSaveGameEvent>,
+    query: Query<(
+        Entity,
+        &IdentityData,
+        &Transform,
+        &SpawnSource,
+        Has<HasRuntimeData>,
+    )>,
+) {
+    for RequestSaveGameEvent { scene, path } in save_game_reader.read() {
+        let scene = absolute_asset_to_rel(scene.to_string()).to_string();
+        let entities: Vec<(Entity, Uuid, Transform, bool)> = query
+            .iter()
+            .filter(|(_, _, _, source, _)| source.str_ref() == scene)
+            .map(|(entity, identity, transform, _, has_runtime_data)| {
+                (entity, identity.uuid, *transform, has_runtime_data)
+            })
+            .collect();
+        let path = path.clone();
+
+        // Components need the world, same as regular saves
+        commands.queue(move |world: &mut World| {
+            let runtime: Vec<RuntimeEntityState> = {
+                let component_editor = world.resource::<ComponentEditor>();
+                entities
+                    .into_iter()
+                    .map(
+                        |(entity, uuid, transform, has_runtime_data)| RuntimeEntityState {
+                            uuid,
+                            transform,
+                            components: if has_runtime_data {
+                                component_editor.serialize_entity_components(world, entity)
+                            } else {
+                                HashMap::new()
+                            },
+                        },
+                    )
+                    .collect()
+            };
+
+            let authored = read_scene_entities(&rel_asset_to_absolute(&scene));
+            let save_game = build_save_game(&scene, &authored, &runtime);
+            if let Err(e) = write_save_game(&path, &save_game) {
+                log!(
+                    LogType::Game,
+                    LogLevel::Error,
+                    LogCategory::System,
+                    "Failed to write save game '{}': {}",
+                    path,
+                    e
+                );
+                return;
+            }
+
+            log!(
+                LogType::Game,
+                LogLevel::OK,
+                LogCategory::System,
+                "Saved game '{}' for scene '{}': {} changed, {} destroyed",
+                path,
+                scene,
+                save_game.changed.len(),
+                save_game.destroyed.len()
+            );
+            world.write_message(SaveGameSuccessEvent(path));
+        });
+    }
+}
+
+/// Reads a save file, then reloads its base scene. Deltas are applied once the scene is loaded
+pub fn load_save_game_request_system(
+    mut commands: Commands,
+    mut load_save_game_reader: MessageReader<RequestLoadSaveGameEvent>,
+    mut load_writer: MessageWriter<RequestLoadEvent>,
+    mut pending: ResMut<PendingSaveGames>,
+    candidates: DespawnCandidates,
+) {
+    for RequestLoadSaveGameEvent(path) in load_save_game_reader.read() {
+        let save_game = match read_save_game(path) {
+            Ok(save_game) => save_game,
+            Err(e) => {
+                log!(
+                    LogType::Game,
+                    LogLevel::Error,
+                    LogCategory::System,
+                    "Failed to read save game '{}': {}",
+                    path,
+                    e
+                );
+                continue;
+            }
+        };
+
+        // Start from the authored scene, the deltas are relative to it
+        let scene = save_game.metadata.scene.clone();
+        despawn_filtered(
+            &mut commands,
+            &candidates,
+            Some(&scene),
+            &DespawnFilter::default(),
+        );
+        load_writer.write(RequestLoadEvent(scene.clone(), SaveSettings::Runtime, None));
+        pending.pending.insert(scene, save_game);
+    }
+}
+
+/// Applies pending save game deltas once their base scene has spawned
+/// Runs after the scene loaders so the spawned entities and their components exist
+pub fn apply_save_game_system(
+    mut commands: Commands,
+    mut load_success_reader: MessageReader<WorldLoadSuccessEvent>,
+    mut pending: ResMut<PendingSaveGames>,
+    query: Query<(Entity, &IdentityData, &SpawnSource)>,
+) {
+    for WorldLoadSuccessEvent(scene) in load_success_reader.read() {
+        let Some(save_game) = pending.pending.remove(scene) else {
+            continue;
+        };
+
+        let by_uuid: HashMap<Uuid, Entity> = query
+            .iter()
+            .filter(|(_, _, source)| source.str_ref() == scene)
+            .map(|(entity, identity, _)| (identity.uuid, entity))
+            .collect();
+
+        let destroyed: HashSet<Uuid> = save_game.destroyed.iter().copied().collect();
+        for uuid in destroyed.iter() {
+            if let Some(entity) = by_uuid.get(uuid) {
+                commands.entity(*entity).try_despawn();
+            }
+        }
+
+        let mut applied = vec![];
+        for delta in save_game.changed.into_iter() {
+            let Some(entity) = by_uuid.get(&delta.uuid).copied() else {
+                log!(
+                    LogType::Game,
+                    LogLevel::Warning,
+                    LogCategory::Entity,
+                    "Save game entity {} is no longer in scene '{}'",
+                    delta.uuid,
+                    scene
+                );
+                continue;
+            };
+            if let Some(transform) = delta.transform.as_ref() {
+                commands.entity(entity).insert(transform.to_bevy());
+            }
+            if !delta.components.is_empty() || !delta.removed_components.is_empty() {
+                applied.push((entity, delta.components, delta.removed_components));
+            }
+        }
+
+        let scene = scene.clone();
+        commands.queue(move |world: &mut World| {
+            let type_registry = world.resource::<AppTypeRegistry>().clone();
+            // Remove the resource to avoid borrowing errors
+            let Some(component_editor) = world.remove_resource::<ComponentEditor>() else {
+                return;
+            };
+            for (entity, components, removed_components) in applied {
+                if world.get_entity(entity).is_err() {
+                    continue;
+                }
+                for component in removed_components.iter() {
+                    component_editor.remove_component_by_name(world, entity, component);
+                }
+                component_editor.load_components_from_scene_data(
+                    world,
+                    entity,
+                    components,
+                    type_registry.clone(),
+                );
+            }
+            world.insert_resource(component_editor);
+
+            log!(
+                LogType::Game,
+                LogLevel::OK,
+                LogCategory::System,
+                "Applied save game to scene '{}'",
+                scene
+            );
+            world.write_message(SaveGameLoadedEvent(scene));
+        });
+    }
+}
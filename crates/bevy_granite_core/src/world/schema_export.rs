@@ -0,0 +1,392 @@
+use crate::{
+    entities::{GraniteClassRegistry, GraniteType, GraniteTypes},
+    events::{RequestExportSchema, SchemaExportedEvent},
+    setup::registered_type_names,
+    shared::{rel_asset_to_absolute, version::Version, EditorCapabilities},
+};
+use bevy::{
+    prelude::{
+        AppTypeRegistry, MessageReader, MessageWriter, ReflectDefault, ReflectDeserialize,
+        ReflectSerialize, Res,
+    },
+    reflect::{
+        attributes::CustomAttributes, PartialReflect, ReflectRef, TypeInfo, TypeRegistration,
+        TypeRegistry, VariantType,
+    },
+};
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+use std::borrow::Cow;
+
+// schema_export.rs
+// Writes a JSON description of what scene files can hold: every registered class and editor visible component
+// with its fields, types, defaults and reflect attributes, for validators, doc generators and web scene viewers
+// Values are written the way they appear in scene files, so a default here reads like the RON a scene would contain
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SchemaExportSummary {
+    pub classes: usize,
+    pub components: usize,
+}
+
+/// The schema document and what went in it
+pub fn build_schema_json(
+    registry: &TypeRegistry,
+    classes: &GraniteClassRegistry,
+) -> (String, SchemaExportSummary) {
+    let class_json: Vec<String> = classes
+        .iter()
+        .map(|class| class_json(class, registry))
+        .collect();
+    let component_json: Vec<String> = registered_type_names(registry)
+        .iter()
+        .filter_map(|type_path| registry.get_with_type_path(type_path))
+        .map(component_json)
+        .collect();
+
+    let json = format!(
+        "{{\n\"format\":\"bevy_granite\",\n\"format_version\":{},\n\"classes\":[\n{}\n],\n\"components\":[\n{}\n]\n}}\n",
+        string(&Version::CURRENT_VERSION.to_string()),
+        class_json.join(",\n"),
+        component_json.join(",\n")
+    );
+    let summary = SchemaExportSummary {
+        classes: class_json.len(),
+        components: component_json.len(),
+    };
+    (json, summary)
+}
+
+fn class_json(class: &GraniteTypes, registry: &TypeRegistry) -> String {
+    let mut fields = vec![
+        format!("\"name\":{}", string(&class.type_name())),
+        format!("\"abbreviation\":{}", string(&class.type_abv())),
+        format!(
+            "\"category\":{}",
+            string(&format!("{:?}", class.category()))
+        ),
+    ];
+
+    match class {
+        GraniteTypes::Custom(custom) => {
+            // Game classes are stored by class name with their struct as RON, see CustomClass
+            fields.push(format!("\"key\":{}", string(&custom.class_name)));
+            match registry.get_with_short_type_path(&custom.class_name) {
+                Some(registration) => {
+                    fields.push(format!(
+                        "\"type_path\":{}",
+                        string(registration.type_info().type_path())
+                    ));
+                    fields.push(type_info_json(registration.type_info()));
+                }
+                None => fields.push("\"fields\":[]".to_string()),
+            }
+            let default = custom
+                .info()
+                .map(|info| (info.default_data)())
+                .unwrap_or_else(|| custom.data.clone());
+            fields.push(format!("\"default\":{}", string(&default)));
+        }
+        _ => {
+            let ReflectRef::Enum(variant) = class.reflect_ref() else {
+                return format!("{{{}}}", fields.join(","));
+            };
+            fields.push(format!("\"key\":{}", string(variant.variant_name())));
+            if let Some(data) = variant.field_at(0) {
+                if let Some(info) = data.get_represented_type_info() {
+                    fields.push(format!("\"type_path\":{}", string(info.type_path())));
+                    fields.push(type_info_json(info));
+                }
+                fields.push(format!("\"default\":{}", value_json(data)));
+            }
+        }
+    }
+    format!("{{{}}}", fields.join(","))
+}
+
+fn component_json(registration: &TypeRegistration) -> String {
+    let type_path = registration.type_info().type_path();
+    let short_name = registration.type_info().type_path_table().short_path();
+    let mut fields = vec![
+        format!("\"type_path\":{}", string(type_path)),
+        format!("\"name\":{}", string(short_name)),
+        type_info_json(registration.type_info()),
+    ];
+
+    let type_data: Vec<String> = [
+        ("Default", registration.data::<ReflectDefault>().is_some()),
+        (
+            "Serialize",
+            registration.data::<ReflectSerialize>().is_some(),
+        ),
+        (
+            "Deserialize",
+            registration.data::<ReflectDeserialize>().is_some(),
+        ),
+    ]
+    .into_iter()
+    .filter(|(_, registered)| *registered)
+    .map(|(name, _)| string(name))
+    .collect();
+    fields.push(format!("\"type_data\":[{}]", type_data.join(",")));
+
+    let default = registration
+        .data::<ReflectDefault>()
+        .map(|default| value_json(default.default().as_partial_reflect()))
+        .unwrap_or_else(|| "null".to_string());
+    fields.push(format!("\"default\":{}", default));
+
+    format!("{{{}}}", fields.join(","))
+}
+
+/// "kind" plus the fields or variants of a type, and its custom reflect attributes
+fn type_info_json(info: &TypeInfo) -> String {
+    match info {
+        TypeInfo::Struct(info) => {
+            let fields: Vec<String> = info
+                .iter()
+                .map(|field| field_json(field.name(), field.type_path(), field.custom_attributes()))
+                .collect();
+            format!(
+                "\"kind\":\"struct\",\"fields\":[{}],\"attributes\":{}",
+                fields.join(","),
+                attributes_json(info.custom_attributes())
+            )
+        }
+        TypeInfo::TupleStruct(info) => {
+            let fields: Vec<String> = info
+                .iter()
+                .map(|field| {
+                    field_json(
+                        &field.index().to_string(),
+                        field.type_path(),
+                        field.custom_attributes(),
+                    )
+                })
+                .collect();
+            format!(
+                "\"kind\":\"tuple_struct\",\"fields\":[{}],\"attributes\":{}",
+                fields.join(","),
+                attributes_json(info.custom_attributes())
+            )
+        }
+        TypeInfo::Enum(info) => {
+            let variants: Vec<String> = info
+                .variant_names()
+                .iter()
+                .map(|name| string(name))
+                .collect();
+            format!(
+                "\"kind\":\"enum\",\"variants\":[{}],\"attributes\":{}",
+                variants.join(","),
+                attributes_json(info.custom_attributes())
+            )
+        }
+        TypeInfo::Tuple(_) => "\"kind\":\"tuple\"".to_string(),
+        TypeInfo::List(_) | TypeInfo::Array(_) | TypeInfo::Set(_) => {
+            "\"kind\":\"list\"".to_string()
+        }
+        TypeInfo::Map(_) => "\"kind\":\"map\"".to_string(),
+        TypeInfo::Opaque(_) => "\"kind\":\"value\"".to_string(),
+    }
+}
+
+fn field_json(name: &str, type_path: &str, attributes: &CustomAttributes) -> String {
+    format!(
+        "{{\"name\":{},\"type\":{},\"attributes\":{}}}",
+        string(name),
+        string(type_path),
+        attributes_json(attributes)
+    )
+}
+
+/// Custom reflect attributes as an object of type path to value
+fn attributes_json(attributes: &CustomAttributes) -> String {
+    let entries: Vec<String> = attributes
+        .iter()
+        .map(|(_, attribute)| {
+            format!(
+                "{}:{}",
+                string(attribute.reflect_type_path()),
+                value_json(attribute.as_partial_reflect())
+            )
+        })
+        .collect();
+    format!("{{{}}}", entries.join(","))
+}
+
+/// A reflected value as JSON. Structs become objects, sequences arrays, unit variants strings
+/// and other variants an object keyed by the variant name
+fn value_json(value: &dyn PartialReflect) -> String {
+    match value.reflect_ref() {
+        ReflectRef::Struct(value) => {
+            let fields: Vec<String> = (0..value.field_len())
+                .filter_map(|index| {
+                    Some(format!(
+                        "{}:{}",
+                        string(value.name_at(index)?),
+                        value_json(value.field_at(index)?)
+                    ))
+                })
+                .collect();
+            format!("{{{}}}", fields.join(","))
+        }
+        ReflectRef::TupleStruct(value) => {
+            let fields: Vec<String> = value.iter_fields().map(value_json).collect();
+            format!("[{}]", fields.join(","))
+        }
+        ReflectRef::Tuple(value) => {
+            let fields: Vec<String> = value.iter_fields().map(value_json).collect();
+            format!("[{}]", fields.join(","))
+        }
+        ReflectRef::List(value) => {
+            let items: Vec<String> = value.iter().map(value_json).collect();
+            format!("[{}]", items.join(","))
+        }
+        ReflectRef::Array(value) => {
+            let items: Vec<String> = value.iter().map(value_json).collect();
+            format!("[{}]", items.join(","))
+        }
+        ReflectRef::Set(value) => {
+            let items: Vec<String> = value.iter().map(value_json).collect();
+            format!("[{}]", items.join(","))
+        }
+        ReflectRef::Map(value) => {
+            let entries: Vec<String> = value
+                .iter()
+                .map(|(key, value)| {
+                    let key = match key.try_downcast_ref::<String>() {
+                        Some(key) => key.clone(),
+                        None => value_json(key).trim_matches('"').to_string(),
+                    };
+                    format!("{}:{}", string(&key), value_json(value))
+                })
+                .collect();
+            format!("{{{}}}", entries.join(","))
+        }
+        ReflectRef::Enum(value) => {
+            let name = value.variant_name();
+            if value.field_len() == 0 {
+                return string(name);
+            }
+            let fields: Vec<String> = value
+                .iter_fields()
+                .map(|field| match field.name() {
+                    Some(field_name) => {
+                        format!("{}:{}", string(field_name), value_json(field.value()))
+                    }
+                    None => value_json(field.value()),
+                })
+                .collect();
+            let body = match value.variant_type() {
+                VariantType::Struct => format!("{{{}}}", fields.join(",")),
+                _ if fields.len() == 1 => fields[0].clone(),
+                _ => format!("[{}]", fields.join(",")),
+            };
+            format!("{{{}:{}}}", string(name), body)
+        }
+        ReflectRef::Opaque(value) => opaque_json(value),
+    }
+}
+
+fn opaque_json(value: &dyn PartialReflect) -> String {
+    macro_rules! number {
+        ($($ty:ty),*) => {
+            $(if let Some(number) = value.try_downcast_ref::<$ty>() {
+                return number.to_string();
+            })*
+        };
+    }
+    number!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+    if let Some(number) = value.try_downcast_ref::<f32>() {
+        return float_json(*number as f64);
+    }
+    if let Some(number) = value.try_downcast_ref::<f64>() {
+        return float_json(*number);
+    }
+    if let Some(flag) = value.try_downcast_ref::<bool>() {
+        return flag.to_string();
+    }
+    if let Some(text) = value.try_downcast_ref::<String>() {
+        return string(text);
+    }
+    if let Some(text) = value.try_downcast_ref::<Cow<'static, str>>() {
+        return string(text);
+    }
+    if let Some(text) = value.try_downcast_ref::<&'static str>() {
+        return string(text);
+    }
+    // Uuids, paths, handles and the like, as they print
+    string(format!("{:?}", value).trim_matches('"'))
+}
+
+/// JSON has no NaN or infinity
+fn float_json(number: f64) -> String {
+    if number.is_finite() {
+        format!("{:?}", number)
+    } else {
+        "null".to_string()
+    }
+}
+
+fn string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for character in value.chars() {
+        match character {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+/// Writes the schema of every registered class and component to the requested JSON file
+pub fn export_schema_system(
+    mut requests: MessageReader<RequestExportSchema>,
+    mut exported_writer: MessageWriter<SchemaExportedEvent>,
+    capabilities: Res<EditorCapabilities>,
+    type_registry: Res<AppTypeRegistry>,
+    classes: Res<GraniteClassRegistry>,
+) {
+    for request in requests.read() {
+        if !capabilities.can_use_files(&request.path) {
+            continue;
+        }
+        let path = rel_asset_to_absolute(&request.path).to_string();
+        let (json, summary) = build_schema_json(&type_registry.read(), &classes);
+        match std::fs::write(&path, json) {
+            Ok(()) => {
+                log!(
+                    LogType::Game,
+                    LogLevel::OK,
+                    LogCategory::System,
+                    "Exported the schema of {} classes and {} components to: {}",
+                    summary.classes,
+                    summary.components,
+                    path
+                );
+                exported_writer.write(SchemaExportedEvent {
+                    path: path.replace('\\', "/"),
+                    summary,
+                });
+            }
+            Err(e) => log!(
+                LogType::Game,
+                LogLevel::Error,
+                LogCategory::System,
+                "Failed to export schema to '{}': {}",
+                path,
+                e
+            ),
+        }
+    }
+}
@@ -0,0 +1,352 @@
+use crate::{
+    assets::AvailableEditableMaterials,
+    entities::{
+        despawn_filtered, ComponentEditor, DespawnCandidates, DespawnFilter, EntitySaveReadyData,
+        HasRuntimeData, IdentityData, PersistAcrossLoads, SpawnSource, TransformData,
+    },
+    GraniteType,
+};
+use bevy::{ecs::system::SystemState, prelude::*};
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// One serializable entity as it was when the snapshot was captured
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnapshotEntity {
+    pub data: EntitySaveReadyData,
+    pub source: Option<SpawnSource>,
+    pub persistent: bool,
+}
+
+/// In memory copy of every serializable entity, for checkpoints and rollback. Nothing is written to disk
+/// Applying despawns the current serializable entities and respawns the captured ones, so Entity ids change
+#[derive(Debug, Clone, Default)]
+pub struct GraniteSnapshot {
+    pub entities: Vec<SnapshotEntity>,
+}
+
+type SnapshotQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static IdentityData,
+        Option<&'static Transform>,
+        Option<&'static ChildOf>,
+        Option<&'static SpawnSource>,
+        Has<PersistAcrossLoads>,
+        Has<HasRuntimeData>,
+    ),
+>;
+
+type ApplyParams<'w, 's> = (
+    Commands<'w, 's>,
+    Res<'w, AssetServer>,
+    ResMut<'w, Assets<StandardMaterial>>,
+    ResMut<'w, Assets<Mesh>>,
+    ResMut<'w, AvailableEditableMaterials>,
+    DespawnCandidates<'w, 's>,
+);
+
+impl GraniteSnapshot {
+    /// Capture every entity with IdentityData, its transform, parent, source and components
+    pub fn capture(world: &mut World) -> Self {
+        let snapshot = Self::capture_quiet(world);
+        log!(
+            LogType::Game,
+            LogLevel::Info,
+            LogCategory::System,
+            "Captured snapshot of {} entities",
+            snapshot.len()
+        );
+        snapshot
+    }
+
+    /// Same as `capture` without the log line, for captures taken every few seconds
+    pub fn capture_quiet(world: &mut World) -> Self {
+        Self {
+            entities: capture_filtered(world, None),
+        }
+    }
+
+    /// Capture only some entities, i.e. the ones an edit touched. Entities without IdentityData are skipped
+    pub fn capture_entities(world: &mut World, entities: &[Entity]) -> Vec<SnapshotEntity> {
+        capture_filtered(world, Some(entities))
+    }
+
+    /// Roll the world back to this snapshot. PersistAcrossLoads entities are restored as well
+    pub fn apply(&self, world: &mut World) {
+        let mut state: SystemState<ApplyParams> = SystemState::new(world);
+        let mut uuid_to_entity: HashMap<Uuid, Entity> = HashMap::new();
+        {
+            let (
+                mut commands,
+                asset_server,
+                mut materials,
+                mut meshes,
+                mut available_materials,
+                candidates,
+            ) = state.get_mut(world);
+
+            despawn_filtered(
+                &mut commands,
+                &candidates,
+                None,
+                &DespawnFilter::default().including_persistent(),
+            );
+
+            for snapshot_entity in self.entities.iter() {
+                let entity = snapshot_entity.data.identity.class.spawn_from_save_data(
+                    &snapshot_entity.data,
+                    &mut commands,
+                    &mut materials,
+                    &mut meshes,
+                    &mut available_materials,
+                    &asset_server,
+                );
+                if let Some(source) = snapshot_entity.source.as_ref() {
+                    commands.entity(entity).insert(source.clone());
+                }
+                if snapshot_entity.persistent {
+                    commands.entity(entity).insert(PersistAcrossLoads);
+                }
+                uuid_to_entity.insert(snapshot_entity.data.identity.uuid, entity);
+            }
+
+            for snapshot_entity in self.entities.iter() {
+                let Some(parent) = snapshot_entity
+                    .data
+                    .parent
+                    .and_then(|parent| uuid_to_entity.get(&parent))
+                else {
+                    continue;
+                };
+                if let Some(child) = uuid_to_entity.get(&snapshot_entity.data.identity.uuid) {
+                    commands.entity(*parent).add_child(*child);
+                }
+            }
+        }
+        state.apply(world);
+
+        // Components go in once the entities exist
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        if let Some(component_editor) = world.remove_resource::<ComponentEditor>() {
+            for snapshot_entity in self.entities.iter() {
+                let (Some(components), Some(entity)) = (
+                    snapshot_entity.data.components.as_ref(),
+                    uuid_to_entity.get(&snapshot_entity.data.identity.uuid),
+                ) else {
+                    continue;
+                };
+                component_editor.load_components_from_scene_data(
+                    world,
+                    *entity,
+                    components.clone(),
+                    type_registry.clone(),
+                );
+            }
+            world.insert_resource(component_editor);
+        }
+
+        log!(
+            LogType::Game,
+            LogLevel::OK,
+            LogCategory::System,
+            "Restored snapshot of {} entities",
+            self.entities.len()
+        );
+    }
+
+    /// Put entities back into the given states by uuid, leaving the rest of the world alone
+    /// None despawns the entity. Entities whose class, identity or components differ are respawned, so their Entity changes
+    /// Returns the entity of every restored uuid
+    pub fn restore_entities(
+        world: &mut World,
+        states: &[(Uuid, Option<SnapshotEntity>)],
+    ) -> HashMap<Uuid, Entity> {
+        let mut lookup: SystemState<Query<(Entity, &IdentityData)>> = SystemState::new(world);
+        let mut uuid_to_entity: HashMap<Uuid, Entity> = lookup
+            .get(world)
+            .iter()
+            .map(|(entity, identity)| (identity.uuid, entity))
+            .collect();
+        let restored: HashSet<Uuid> = states.iter().map(|(uuid, _)| *uuid).collect();
+        let existing: Vec<Entity> = states
+            .iter()
+            .filter_map(|(uuid, _)| uuid_to_entity.get(uuid).copied())
+            .collect();
+        let current: HashMap<Uuid, SnapshotEntity> = Self::capture_entities(world, &existing)
+            .into_iter()
+            .map(|entity| (entity.data.identity.uuid, entity))
+            .collect();
+
+        // Children outside the restored set must survive their parent being respawned
+        let mut detached: Vec<(Entity, Uuid)> = Vec::new();
+        let mut respawned: Vec<&SnapshotEntity> = Vec::new();
+        let mut state: SystemState<ApplyParams> = SystemState::new(world);
+        {
+            let (mut commands, asset_server, mut materials, mut meshes, mut available_materials, _) =
+                state.get_mut(world);
+
+            for (uuid, target) in states.iter() {
+                let entity = uuid_to_entity.get(uuid).copied();
+                if let (Some(entity), Some(target), Some(current)) =
+                    (entity, target, current.get(uuid))
+                {
+                    if current.data.identity == target.data.identity
+                        && current.data.components == target.data.components
+                        && current.source == target.source
+                    {
+                        commands
+                            .entity(entity)
+                            .insert(target.data.transform.to_bevy());
+                        continue;
+                    }
+                }
+
+                if let Some(entity) = entity {
+                    for (child, parent) in current.values().filter_map(|child| {
+                        let parent = child.data.parent?;
+                        (parent == *uuid && !restored.contains(&child.data.identity.uuid))
+                            .then_some((child.data.identity.uuid, parent))
+                    }) {
+                        if let Some(child_entity) = uuid_to_entity.get(&child) {
+                            commands.entity(*child_entity).remove::<ChildOf>();
+                            detached.push((*child_entity, parent));
+                        }
+                    }
+                    commands.entity(entity).try_despawn();
+                    uuid_to_entity.remove(uuid);
+                }
+
+                let Some(target) = target else {
+                    continue;
+                };
+                let new_entity = target.data.identity.class.spawn_from_save_data(
+                    &target.data,
+                    &mut commands,
+                    &mut materials,
+                    &mut meshes,
+                    &mut available_materials,
+                    &asset_server,
+                );
+                if let Some(source) = target.source.as_ref() {
+                    commands.entity(new_entity).insert(source.clone());
+                }
+                if target.persistent {
+                    commands.entity(new_entity).insert(PersistAcrossLoads);
+                }
+                uuid_to_entity.insert(*uuid, new_entity);
+                respawned.push(target);
+            }
+
+            for (uuid, target) in states.iter() {
+                let (Some(target), Some(entity)) = (target, uuid_to_entity.get(uuid)) else {
+                    continue;
+                };
+                match target
+                    .data
+                    .parent
+                    .and_then(|parent| uuid_to_entity.get(&parent))
+                {
+                    Some(parent) => {
+                        commands.entity(*parent).add_child(*entity);
+                    }
+                    None => {
+                        commands.entity(*entity).remove::<ChildOf>();
+                    }
+                }
+            }
+            for (child, parent) in detached {
+                if let Some(parent) = uuid_to_entity.get(&parent) {
+                    commands.entity(*parent).add_child(child);
+                }
+            }
+        }
+        state.apply(world);
+
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        if let Some(component_editor) = world.remove_resource::<ComponentEditor>() {
+            for target in respawned {
+                let (Some(components), Some(entity)) = (
+                    target.data.components.as_ref(),
+                    uuid_to_entity.get(&target.data.identity.uuid),
+                ) else {
+                    continue;
+                };
+                component_editor.load_components_from_scene_data(
+                    world,
+                    *entity,
+                    components.clone(),
+                    type_registry.clone(),
+                );
+            }
+            world.insert_resource(component_editor);
+        }
+
+        states
+            .iter()
+            .filter_map(|(uuid, _)| uuid_to_entity.get(uuid).map(|entity| (*uuid, *entity)))
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+}
+
+/// Every serializable entity, or only the listed ones
+fn capture_filtered(world: &mut World, only: Option<&[Entity]>) -> Vec<SnapshotEntity> {
+    let mut state: SystemState<SnapshotQuery> = SystemState::new(world);
+    let query = state.get(world);
+    let uuids: HashMap<Entity, Uuid> = query
+        .iter()
+        .map(|(entity, identity, ..)| (entity, identity.uuid))
+        .collect();
+
+    let component_editor = world.get_resource::<ComponentEditor>();
+    let snapshot_entity =
+        |(entity, identity, transform, child_of, source, persistent, has_runtime_data): (
+            Entity,
+            &IdentityData,
+            Option<&Transform>,
+            Option<&ChildOf>,
+            Option<&SpawnSource>,
+            bool,
+            bool,
+        )| {
+            let transform = transform.cloned().unwrap_or_default();
+            let components = component_editor
+                .filter(|_| has_runtime_data)
+                .map(|editor| editor.serialize_entity_components(world, entity))
+                .filter(|components| !components.is_empty());
+
+            SnapshotEntity {
+                data: EntitySaveReadyData {
+                    identity: identity.clone(),
+                    transform: TransformData {
+                        position: transform.translation,
+                        rotation: transform.rotation,
+                        scale: transform.scale,
+                    },
+                    parent: child_of.and_then(|child_of| uuids.get(&child_of.parent()).copied()),
+                    components,
+                },
+                source: source.cloned(),
+                persistent,
+            }
+        };
+
+    match only {
+        Some(entities) => query.iter_many(entities).map(snapshot_entity).collect(),
+        None => query.iter().map(snapshot_entity).collect(),
+    }
+}
@@ -0,0 +1,509 @@
+use crate::{
+    absolute_asset_to_rel,
+    entities::{round_quat, round_vec3, IdentityData, SaveSettings, SpawnSource},
+    rel_asset_to_absolute, RequestDespawnBySource, RequestLoadEvent, RequestSaveEvent,
+    WorldLoadSuccessEvent, WorldSaveSuccessEvent,
+};
+use bevy::{
+    app::{App, AppExit, Plugin, Update},
+    asset::Assets,
+    ecs::{
+        entity::Entity,
+        hierarchy::{ChildOf, Children},
+        message::{MessageReader, MessageWriter},
+        resource::Resource,
+        system::{Commands, Query, Res, ResMut},
+    },
+    math::{Quat, Vec3},
+    mesh::Mesh,
+    pbr::StandardMaterial,
+    transform::components::Transform,
+};
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+use std::{collections::HashMap, fs};
+use uuid::Uuid;
+
+// soak.rs
+// Soak test mode, behind the `stress` feature. Loops load, randomized edits, save, despawn and reload on a copy
+// of a scene, and checks every round trip: the reloaded entities must match what was saved, saving again without
+// edits must write the same bytes, and entity and asset counts must not grow between iterations
+// Edits are what game code can do to a loaded scene: move, rotate, scale, rename and despawn leaf entities
+
+/// Frames a step may wait for its load or save before the iteration counts as failed
+const STEP_TIMEOUT_FRAMES: u32 = 600;
+/// Saved transforms are rounded to 3 decimals
+const TRANSFORM_TOLERANCE: f32 = 0.002;
+
+/// Headless soak test of a scene, i.e.
+/// `cargo run --example soak_test --no-default-features --features stress -- scenes/dungeon.scene 50`
+pub struct SoakTestPlugin {
+    /// Scene to test, relative to assets. It is copied first, the original is never written
+    pub scene: String,
+    pub iterations: u32,
+    pub edits_per_iteration: u32,
+    /// Same seed, same edits, so failures can be replayed
+    pub seed: u64,
+    /// Send AppExit when done, with an error code if anything failed
+    pub exit_when_done: bool,
+}
+
+impl Default for SoakTestPlugin {
+    fn default() -> Self {
+        Self {
+            scene: String::new(),
+            iterations: 100,
+            edits_per_iteration: 8,
+            seed: 0x5eed,
+            exit_when_done: true,
+        }
+    }
+}
+
+impl Plugin for SoakTestPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            //
+            // Resources
+            //
+            .insert_resource(SoakTest::new(self))
+            //
+            // Schedule systems
+            //
+            .add_systems(Update, soak_test_system);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SoakStep {
+    Start,
+    Loading,
+    Edit,
+    SaveEdited,
+    Saving,
+    Despawn,
+    Reload,
+    Reloading,
+    Verify,
+    Resaving,
+    Done,
+}
+
+/// Entity state that has to survive a save and reload
+#[derive(Debug, Clone, PartialEq)]
+struct SavedEntity {
+    name: String,
+    transform: Transform,
+    parent: Option<Uuid>,
+}
+
+/// Running soak test and its results so far
+#[derive(Resource)]
+pub struct SoakTest {
+    source: String,
+    work: String,
+    iterations: u32,
+    edits_per_iteration: u32,
+    exit_when_done: bool,
+    rng: u64,
+    step: SoakStep,
+    /// Frames spent in the current step
+    frames: u32,
+    iteration: u32,
+    expected: HashMap<Uuid, SavedEntity>,
+    saved_bytes: Vec<u8>,
+    /// Entity, mesh and material counts after the first despawn
+    baseline: Option<(usize, usize, usize)>,
+    failures: Vec<String>,
+}
+
+impl SoakTest {
+    fn new(plugin: &SoakTestPlugin) -> Self {
+        let source = absolute_asset_to_rel(plugin.scene.clone()).to_string();
+        let work = match source.strip_suffix(".scene") {
+            Some(stem) => format!("{}.soak.scene", stem),
+            None => format!("{}.soak", source),
+        };
+        Self {
+            source,
+            work,
+            iterations: plugin.iterations,
+            edits_per_iteration: plugin.edits_per_iteration,
+            exit_when_done: plugin.exit_when_done,
+            rng: plugin.seed.max(1),
+            step: SoakStep::Start,
+            frames: 0,
+            iteration: 0,
+            expected: HashMap::new(),
+            saved_bytes: Vec::new(),
+            baseline: None,
+            failures: Vec::new(),
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.step == SoakStep::Done
+    }
+
+    /// Completed iterations
+    pub fn iteration(&self) -> u32 {
+        self.iteration
+    }
+
+    pub fn failures(&self) -> &[String] {
+        &self.failures
+    }
+
+    /// xorshift64, enough for picking edits and reproducible from the seed
+    fn next(&mut self) -> u64 {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        self.rng
+    }
+
+    /// -1 to 1
+    fn next_signed(&mut self) -> f32 {
+        (self.next() % 20_001) as f32 / 10_000.0 - 1.0
+    }
+
+    fn go(&mut self, step: SoakStep) {
+        self.step = step;
+        self.frames = 0;
+    }
+
+    fn fail(&mut self, reason: String) {
+        log!(
+            LogType::Game,
+            LogLevel::Error,
+            LogCategory::System,
+            "Soak test iteration {}: {}",
+            self.iteration,
+            reason
+        );
+        self.failures
+            .push(format!("iteration {}: {}", self.iteration, reason));
+    }
+}
+
+type SoakEntityQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static mut IdentityData,
+        &'static mut Transform,
+        &'static SpawnSource,
+        Option<&'static ChildOf>,
+        Option<&'static Children>,
+    ),
+>;
+
+type SoakReaders<'w, 's> = (
+    MessageReader<'w, 's, WorldLoadSuccessEvent>,
+    MessageReader<'w, 's, WorldSaveSuccessEvent>,
+);
+
+type SoakWriters<'w> = (
+    MessageWriter<'w, RequestLoadEvent>,
+    MessageWriter<'w, RequestSaveEvent>,
+    MessageWriter<'w, RequestDespawnBySource>,
+    MessageWriter<'w, AppExit>,
+);
+
+type SoakCounts<'w, 's> = (
+    Query<'w, 's, Entity>,
+    Res<'w, Assets<Mesh>>,
+    Res<'w, Assets<StandardMaterial>>,
+);
+
+pub fn soak_test_system(
+    mut soak: ResMut<SoakTest>,
+    mut readers: SoakReaders,
+    mut writers: SoakWriters,
+    mut entities: SoakEntityQuery,
+    mut commands: Commands,
+    counts: SoakCounts,
+) {
+    let loaded = readers
+        .0
+        .read()
+        .any(|WorldLoadSuccessEvent(path)| absolute_asset_to_rel(path.clone()) == soak.work);
+    let saved = readers
+        .1
+        .read()
+        .any(|WorldSaveSuccessEvent(path)| absolute_asset_to_rel(path.clone()) == soak.work);
+
+    soak.frames += 1;
+    let waiting = matches!(
+        soak.step,
+        SoakStep::Loading | SoakStep::Saving | SoakStep::Reloading | SoakStep::Resaving
+    );
+    if waiting && soak.frames > STEP_TIMEOUT_FRAMES {
+        let reason = format!("timed out in {:?}", soak.step);
+        soak.fail(reason);
+        soak.go(SoakStep::Done);
+        finish(&mut soak, &mut writers.3);
+        return;
+    }
+
+    match soak.step {
+        SoakStep::Start => {
+            let copied = fs::copy(
+                rel_asset_to_absolute(&soak.source).to_string(),
+                rel_asset_to_absolute(&soak.work).to_string(),
+            );
+            if let Err(e) = copied {
+                let reason = format!("could not copy '{}': {}", soak.source, e);
+                soak.fail(reason);
+                soak.go(SoakStep::Done);
+                finish(&mut soak, &mut writers.3);
+                return;
+            }
+            log!(
+                LogType::Game,
+                LogLevel::Info,
+                LogCategory::System,
+                "Soak test on '{}': {} iterations of {} edits",
+                soak.source,
+                soak.iterations,
+                soak.edits_per_iteration
+            );
+            writers.0.write(RequestLoadEvent(
+                soak.work.clone(),
+                SaveSettings::Runtime,
+                None,
+            ));
+            soak.go(SoakStep::Loading);
+        }
+        SoakStep::Loading if loaded => soak.go(SoakStep::Edit),
+        // Loaded entities are spawned through commands, give them a frame
+        SoakStep::Edit if soak.frames > 1 => {
+            apply_random_edits(&mut soak, &mut entities, &mut commands);
+            soak.go(SoakStep::SaveEdited);
+        }
+        SoakStep::SaveEdited if soak.frames > 1 => {
+            soak.expected = saved_state(&soak.work, &entities);
+            writers.1.write(RequestSaveEvent(soak.work.clone()));
+            soak.go(SoakStep::Saving);
+        }
+        SoakStep::Saving if saved => {
+            soak.saved_bytes =
+                fs::read(rel_asset_to_absolute(&soak.work).to_string()).unwrap_or_default();
+            writers
+                .2
+                .write(RequestDespawnBySource::new(soak.work.clone()));
+            soak.go(SoakStep::Despawn);
+        }
+        SoakStep::Despawn if soak.frames > 2 => {
+            let current = (counts.0.iter().count(), counts.1.len(), counts.2.len());
+            match soak.baseline {
+                None => soak.baseline = Some(current),
+                Some(baseline)
+                    if current.0 > baseline.0
+                        || current.1 > baseline.1
+                        || current.2 > baseline.2 =>
+                {
+                    let reason = format!(
+                        "leak after despawn, entities/meshes/materials {:?} grew from {:?}",
+                        current, baseline
+                    );
+                    soak.fail(reason);
+                }
+                Some(_) => {}
+            }
+            soak.go(SoakStep::Reload);
+        }
+        SoakStep::Reload => {
+            writers.0.write(RequestLoadEvent(
+                soak.work.clone(),
+                SaveSettings::Runtime,
+                None,
+            ));
+            soak.go(SoakStep::Reloading);
+        }
+        SoakStep::Reloading if loaded => soak.go(SoakStep::Verify),
+        SoakStep::Verify if soak.frames > 1 => {
+            let reloaded = saved_state(&soak.work, &entities);
+            for reason in compare_states(&soak.expected, &reloaded) {
+                soak.fail(reason);
+            }
+            writers.1.write(RequestSaveEvent(soak.work.clone()));
+            soak.go(SoakStep::Resaving);
+        }
+        SoakStep::Resaving if saved => {
+            let bytes = fs::read(rel_asset_to_absolute(&soak.work).to_string()).unwrap_or_default();
+            if bytes != soak.saved_bytes {
+                soak.fail("saving the reloaded scene wrote different bytes".to_string());
+            }
+            soak.iteration += 1;
+            if soak.iteration >= soak.iterations {
+                soak.go(SoakStep::Done);
+                finish(&mut soak, &mut writers.3);
+            } else {
+                soak.go(SoakStep::Edit);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn apply_random_edits(
+    soak: &mut SoakTest,
+    entities: &mut SoakEntityQuery,
+    commands: &mut Commands,
+) {
+    let mut candidates: Vec<Entity> = entities
+        .iter()
+        .filter(|(_, _, _, source, ..)| source.str_ref() == soak.work)
+        .map(|(entity, ..)| entity)
+        .collect();
+    candidates.sort();
+    let total = candidates.len();
+
+    for _ in 0..soak.edits_per_iteration {
+        if candidates.is_empty() {
+            return;
+        }
+        let index = (soak.next() % candidates.len() as u64) as usize;
+        let Ok((entity, mut identity, mut transform, _, _, children)) =
+            entities.get_mut(candidates[index])
+        else {
+            continue;
+        };
+        match soak.next() % 5 {
+            0 => {
+                transform.translation +=
+                    Vec3::new(soak.next_signed(), soak.next_signed(), soak.next_signed())
+            }
+            1 => transform.rotate(Quat::from_rotation_y(
+                soak.next_signed() * std::f32::consts::PI,
+            )),
+            2 => transform.scale = Vec3::splat(1.0 + soak.next_signed() * 0.5),
+            3 => {
+                let suffix = soak.next() % 1000;
+                identity.name = format!("soak_{}_{}", soak.iteration, suffix);
+            }
+            // Keep at least half of the scene, and only despawn leaves so no children are orphaned
+            _ if children.is_none() && candidates.len() * 2 > total => {
+                commands.entity(entity).despawn();
+                candidates.swap_remove(index);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn saved_state(work: &str, entities: &SoakEntityQuery) -> HashMap<Uuid, SavedEntity> {
+    let uuids: HashMap<Entity, Uuid> = entities
+        .iter()
+        .map(|(entity, identity, ..)| (entity, identity.uuid))
+        .collect();
+    entities
+        .iter()
+        .filter(|(_, _, _, source, ..)| source.str_ref() == work)
+        .map(|(_, identity, transform, _, child_of, _)| {
+            (
+                identity.uuid,
+                SavedEntity {
+                    name: identity.name.clone(),
+                    transform: Transform {
+                        translation: round_vec3(transform.translation),
+                        rotation: round_quat(transform.rotation),
+                        scale: round_vec3(transform.scale),
+                    },
+                    parent: child_of.and_then(|child_of| uuids.get(&child_of.parent()).copied()),
+                },
+            )
+        })
+        .collect()
+}
+
+fn compare_states(
+    expected: &HashMap<Uuid, SavedEntity>,
+    reloaded: &HashMap<Uuid, SavedEntity>,
+) -> Vec<String> {
+    let mut problems = Vec::new();
+    if expected.len() != reloaded.len() {
+        problems.push(format!(
+            "saved {} entities but reloaded {}",
+            expected.len(),
+            reloaded.len()
+        ));
+    }
+    for (uuid, saved) in expected.iter() {
+        let Some(loaded) = reloaded.get(uuid) else {
+            problems.push(format!(
+                "entity '{}' ({}) missing after reload",
+                saved.name, uuid
+            ));
+            continue;
+        };
+        if loaded.name != saved.name {
+            problems.push(format!(
+                "entity {} renamed '{}' -> '{}'",
+                uuid, saved.name, loaded.name
+            ));
+        }
+        if loaded.parent != saved.parent {
+            problems.push(format!("entity '{}' lost its parent", saved.name));
+        }
+        let moved = !loaded
+            .transform
+            .translation
+            .abs_diff_eq(saved.transform.translation, TRANSFORM_TOLERANCE)
+            || !loaded
+                .transform
+                .scale
+                .abs_diff_eq(saved.transform.scale, TRANSFORM_TOLERANCE)
+            // Rounded rotations aren't normalized, compare components. q and -q are the same rotation
+            || !(loaded
+                .transform
+                .rotation
+                .abs_diff_eq(saved.transform.rotation, TRANSFORM_TOLERANCE)
+                || loaded
+                    .transform
+                    .rotation
+                    .abs_diff_eq(-saved.transform.rotation, TRANSFORM_TOLERANCE));
+        if moved {
+            problems.push(format!(
+                "entity '{}' transform {:?} reloaded as {:?}",
+                saved.name, saved.transform, loaded.transform
+            ));
+        }
+    }
+    problems
+}
+
+fn finish(soak: &mut SoakTest, exit: &mut MessageWriter<AppExit>) {
+    if soak.failures.is_empty() {
+        log!(
+            LogType::Game,
+            LogLevel::OK,
+            LogCategory::System,
+            "Soak test passed: {} iterations on '{}'",
+            soak.iteration,
+            soak.source
+        );
+        let _ = fs::remove_file(rel_asset_to_absolute(&soak.work).to_string());
+    } else {
+        log!(
+            LogType::Game,
+            LogLevel::Error,
+            LogCategory::System,
+            "Soak test failed {} checks in {} iterations, work scene kept at '{}'",
+            soak.failures.len(),
+            soak.iteration,
+            soak.work
+        );
+    }
+    if soak.exit_when_done {
+        exit.write(if soak.failures.is_empty() {
+            AppExit::Success
+        } else {
+            AppExit::error()
+        });
+    }
+}
@@ -0,0 +1,162 @@
+use crate::{entities::SaveSettings, events::RequestLoadEvent};
+use bevy::prelude::*;
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+
+/// Env var selecting the startup world by name, i.e. GRANITE_WORLD=benchmark
+pub const STARTUP_WORLD_ENV: &str = "GRANITE_WORLD";
+/// Launch arg selecting the startup world by name, i.e. --granite-world=demo or --granite-world demo
+pub const STARTUP_WORLD_ARG: &str = "--granite-world";
+
+/// Named worlds the app can start in, i.e. dev, demo and benchmark
+/// The launch arg wins over the env var, which wins over the active world set in code
+/// Can be changed at runtime, the editor's default world follows the active one
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
+pub struct StartupWorlds {
+    /// (name, scene path relative to assets) in the order they were added
+    pub worlds: Vec<(String, String)>,
+    /// Name of the selected world. The first world is used when None
+    pub active: Option<String>,
+    /// Send a RequestLoadEvent for the active world on Startup
+    pub load_on_startup: bool,
+}
+
+impl StartupWorlds {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_world(mut self, name: impl Into<String>, path: impl Into<String>) -> Self {
+        self.add(name, path);
+        self
+    }
+
+    pub fn with_active(mut self, name: impl Into<String>) -> Self {
+        self.active = Some(name.into());
+        self
+    }
+
+    pub fn with_load_on_startup(mut self, load: bool) -> Self {
+        self.load_on_startup = load;
+        self
+    }
+
+    /// Add a world, or change the path of an existing one
+    pub fn add(&mut self, name: impl Into<String>, path: impl Into<String>) {
+        let (name, path) = (name.into(), path.into());
+        match self
+            .worlds
+            .iter_mut()
+            .find(|(existing, _)| *existing == name)
+        {
+            Some(world) => world.1 = path,
+            None => self.worlds.push((name, path)),
+        }
+    }
+
+    pub fn path(&self, name: &str) -> Option<&str> {
+        self.worlds
+            .iter()
+            .find(|(existing, _)| existing == name)
+            .map(|(_, path)| path.as_str())
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.worlds.iter().map(|(name, _)| name.as_str())
+    }
+
+    pub fn active_name(&self) -> Option<&str> {
+        match &self.active {
+            Some(name) if self.path(name).is_some() => Some(name.as_str()),
+            _ => self.worlds.first().map(|(name, _)| name.as_str()),
+        }
+    }
+
+    pub fn active_path(&self) -> Option<&str> {
+        self.active_name().and_then(|name| self.path(name))
+    }
+
+    /// Make a world active by name. Unknown names are ignored
+    pub fn select(&mut self, name: &str) -> bool {
+        if self.path(name).is_none() {
+            log!(
+                LogType::Game,
+                LogLevel::Warning,
+                LogCategory::System,
+                "Unknown startup world '{}', available: {:?}",
+                name,
+                self.names().collect::<Vec<_>>()
+            );
+            return false;
+        }
+        self.active = Some(name.to_string());
+        true
+    }
+
+    /// Select the world named by the launch arg or env var, if any
+    pub fn apply_launch_selection(&mut self) {
+        let Some((name, origin)) = launch_selection() else {
+            return;
+        };
+        if self.select(&name) {
+            log!(
+                LogType::Game,
+                LogLevel::Info,
+                LogCategory::System,
+                "Startup world '{}' selected via {}",
+                name,
+                origin
+            );
+        }
+    }
+}
+
+fn launch_selection() -> Option<(String, &'static str)> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if let Some(name) = arg.strip_prefix(STARTUP_WORLD_ARG) {
+            match name.strip_prefix('=') {
+                Some(name) => return Some((name.to_string(), STARTUP_WORLD_ARG)),
+                None if name.is_empty() => {
+                    return args.next().map(|name| (name, STARTUP_WORLD_ARG));
+                }
+                None => {}
+            }
+        }
+    }
+
+    std::env::var(STARTUP_WORLD_ENV)
+        .ok()
+        .filter(|name| !name.is_empty())
+        .map(|name| (name, STARTUP_WORLD_ENV))
+}
+
+/// Loads the active startup world when load_on_startup is set
+pub fn load_startup_world_system(
+    startup_worlds: Res<StartupWorlds>,
+    mut load_writer: MessageWriter<RequestLoadEvent>,
+) {
+    if !startup_worlds.load_on_startup {
+        return;
+    }
+    let (Some(name), Some(path)) = (startup_worlds.active_name(), startup_worlds.active_path())
+    else {
+        return;
+    };
+
+    log!(
+        LogType::Game,
+        LogLevel::Info,
+        LogCategory::System,
+        "Loading startup world '{}': {}",
+        name,
+        path
+    );
+    load_writer.write(RequestLoadEvent(
+        path.to_string(),
+        SaveSettings::Runtime,
+        None,
+    ));
+}
@@ -0,0 +1,304 @@
+use super::DirtyScenes;
+use crate::{
+    entities::{
+        mesh_to_obj, GraniteTypes, IdentityData, SpawnSource, UserUpdatedRectBrushEvent, OBJ,
+    },
+    events::{RequestApplyTransformEvent, RequestFlattenHierarchyEvent},
+    shared::{rel_asset_to_absolute, EditorCapabilities},
+};
+use bevy::{
+    asset::Assets,
+    ecs::{entity::Entity, message::MessageReader, system::SystemState, world::World},
+    math::{Affine3A, Mat4, Quat, Vec3},
+    mesh::{Mesh, Mesh3d},
+    prelude::{ChildOf, Children, Transform},
+};
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+use std::{fs, path::Path};
+
+// transform_baking.rs
+// Cleanup of imported content. Flattening moves every editable descendant straight under the root,
+// with the transforms of the groups in between baked in, then despawns those groups
+// Applying scale or rotation bakes it into the mesh and the editable children, so the entity's own transform is reset
+
+fn editable_children(world: &World, entity: Entity) -> Vec<Entity> {
+    world
+        .get::<Children>(entity)
+        .map(|children| {
+            children
+                .iter()
+                .copied()
+                .filter(|child| world.get::<IdentityData>(*child).is_some())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn mark_dirty(world: &mut World, entity: Entity) {
+    if let Some(source) = world
+        .get::<SpawnSource>(entity)
+        .map(|source| source.str_ref().to_string())
+    {
+        world.resource_mut::<DirtyScenes>().mark(source);
+    }
+}
+
+fn can_edit(world: &World, entity: Entity) -> bool {
+    world.get::<SpawnSource>(entity).is_none_or(|source| {
+        world
+            .resource::<EditorCapabilities>()
+            .can_edit_components(source.str_ref())
+    })
+}
+
+/// Moves every editable descendant of `root` directly under it, keeping their place in the world
+/// Empties that grouped other entities are despawned, Empties without children are kept as markers
+/// Returns how many entities were moved
+pub fn flatten_hierarchy(world: &mut World, root: Entity) -> usize {
+    // Transform of each descendant relative to the root, parents first
+    let mut descendants: Vec<(Entity, Affine3A)> = vec![];
+    let mut stack: Vec<(Entity, Affine3A)> = vec![(root, Affine3A::IDENTITY)];
+    while let Some((parent, parent_affine)) = stack.pop() {
+        for child in editable_children(world, parent) {
+            let local = world
+                .get::<Transform>(child)
+                .map_or(Affine3A::IDENTITY, Transform::compute_affine);
+            let affine = parent_affine * local;
+            descendants.push((child, affine));
+            stack.push((child, affine));
+        }
+    }
+
+    let mut moved = 0;
+    let mut groups = vec![];
+    for (entity, affine) in descendants.iter() {
+        let is_group = matches!(
+            world
+                .get::<IdentityData>(*entity)
+                .map(|identity| &identity.class),
+            Some(GraniteTypes::Empty(_))
+        ) && !editable_children(world, *entity).is_empty();
+        if is_group {
+            groups.push(*entity);
+            continue;
+        }
+        if world.get::<ChildOf>(*entity).map(ChildOf::parent) == Some(root) {
+            continue;
+        }
+        world
+            .entity_mut(*entity)
+            .insert((ChildOf(root), Transform::from_matrix(Mat4::from(*affine))));
+        moved += 1;
+    }
+    for group in groups {
+        mark_dirty(world, group);
+        world.despawn(group);
+    }
+    if moved > 0 {
+        mark_dirty(world, root);
+    }
+    moved
+}
+
+/// Path next to an .obj for its baked copy, not used by any file yet
+fn baked_mesh_path(mesh_path: &str) -> String {
+    let path = Path::new(mesh_path);
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let base = stem.split("_baked").next().unwrap_or_default().to_string();
+    let parent = path.parent().unwrap_or(Path::new(""));
+    (1..)
+        .map(|n| {
+            let name = if n == 1 {
+                format!("{}_baked.obj", base)
+            } else {
+                format!("{}_baked_{}.obj", base, n)
+            };
+            parent.join(name).to_string_lossy().replace('\\', "/")
+        })
+        .find(|candidate| !Path::new(rel_asset_to_absolute(candidate).as_ref()).exists())
+        .unwrap_or_default()
+}
+
+/// Writes the mesh of an OBJ entity with `bake` applied to a new .obj, and points the entity at it
+fn bake_obj_mesh(
+    world: &mut World,
+    entity: Entity,
+    identity: &mut IdentityData,
+    bake: Transform,
+) -> Result<(), String> {
+    let GraniteTypes::OBJ(obj) = &mut identity.class else {
+        return Ok(());
+    };
+    if obj.mesh_path.is_empty() {
+        return Err("it has no mesh file".to_string());
+    }
+    if !world
+        .resource::<EditorCapabilities>()
+        .can_save(&obj.mesh_path)
+    {
+        return Err(format!("'{}' can't be saved", obj.mesh_path));
+    }
+    let mesh = world
+        .get::<Mesh3d>(entity)
+        .and_then(|mesh| world.resource::<Assets<Mesh>>().get(&mesh.0))
+        .ok_or("its mesh isn't loaded yet")?
+        .clone()
+        .transformed_by(bake);
+
+    let material =
+        OBJ::extract_first_usemtl_name(rel_asset_to_absolute(&obj.mesh_path).as_ref()).ok();
+    let contents = mesh_to_obj(&mesh, &identity.name, material.as_deref())
+        .ok_or("only triangle meshes can be written")?;
+    let baked_path = baked_mesh_path(&obj.mesh_path);
+    fs::write(rel_asset_to_absolute(&baked_path).as_ref(), contents)
+        .map_err(|e| format!("failed to write '{}': {}", baked_path, e))?;
+
+    let handle = world.resource_mut::<Assets<Mesh>>().add(mesh);
+    world.entity_mut(entity).insert(Mesh3d(handle));
+    obj.mesh_path = baked_path.into();
+    Ok(())
+}
+
+/// Bakes the scale and/or rotation of `entity` into its mesh and editable children, and resets them on the entity
+/// Works on .obj meshes, Rectangle Brushes (scale only, they stay axis aligned) and Empties
+pub fn apply_transform(
+    world: &mut World,
+    entity: Entity,
+    scale: bool,
+    rotation: bool,
+) -> Result<(), String> {
+    let (Some(transform), Some(mut identity)) = (
+        world.get::<Transform>(entity).copied(),
+        world.get::<IdentityData>(entity).cloned(),
+    ) else {
+        return Err("it isn't an editable entity".to_string());
+    };
+    let bake = Transform {
+        translation: Vec3::ZERO,
+        rotation: if rotation {
+            transform.rotation
+        } else {
+            Quat::IDENTITY
+        },
+        scale: if scale { transform.scale } else { Vec3::ONE },
+    };
+    if bake == Transform::IDENTITY {
+        return Ok(());
+    }
+    // Rotating under a non-uniform scale would shear, which a Transform can't hold
+    if rotation && !scale && transform.rotation != Quat::IDENTITY {
+        let uniform = transform.scale.abs();
+        if uniform.max_element() - uniform.min_element() > f32::EPSILON {
+            return Err("its scale isn't uniform, apply rotation and scale together".to_string());
+        }
+    }
+
+    match identity.class {
+        GraniteTypes::Empty(_) => {}
+        GraniteTypes::OBJ(_) => bake_obj_mesh(world, entity, &mut identity, bake)?,
+        GraniteTypes::RectBrush(ref mut brush) => {
+            if bake.rotation != Quat::IDENTITY {
+                return Err(
+                    "Rectangle Brushes stay axis aligned, only scale can be applied".to_string(),
+                );
+            }
+            brush.size *= bake.scale.abs();
+            let data = brush.clone();
+            world.write_message(UserUpdatedRectBrushEvent { entity, data });
+        }
+        _ => {
+            return Err("only .obj meshes, Rectangle Brushes and Empties can be baked".to_string())
+        }
+    }
+
+    for child in editable_children(world, entity) {
+        if let Some(mut child_transform) = world.get_mut::<Transform>(child) {
+            *child_transform = bake.mul_transform(*child_transform);
+        }
+    }
+    let mut entity_mut = world.entity_mut(entity);
+    entity_mut.insert(identity);
+    if let Some(mut entity_transform) = entity_mut.get_mut::<Transform>() {
+        if rotation {
+            entity_transform.rotation = Quat::IDENTITY;
+        }
+        if scale {
+            entity_transform.scale = Vec3::ONE;
+        }
+    }
+    mark_dirty(world, entity);
+    Ok(())
+}
+
+pub fn flatten_hierarchy_system(
+    world: &mut World,
+    state: &mut SystemState<MessageReader<RequestFlattenHierarchyEvent>>,
+) {
+    let requests: Vec<RequestFlattenHierarchyEvent> =
+        state.get_mut(world).read().cloned().collect();
+    for RequestFlattenHierarchyEvent(roots) in requests {
+        for root in roots {
+            if world.get_entity(root).is_err() || !can_edit(world, root) {
+                continue;
+            }
+            // Grouping Empties are despawned along the way
+            if !world
+                .resource::<EditorCapabilities>()
+                .can_delete(&root.to_string())
+            {
+                continue;
+            }
+            let moved = flatten_hierarchy(world, root);
+            log!(
+                LogType::Editor,
+                LogLevel::OK,
+                LogCategory::Entity,
+                "Flattened hierarchy of {}: {} entities moved",
+                root,
+                moved
+            );
+        }
+    }
+}
+
+pub fn apply_transform_system(
+    world: &mut World,
+    state: &mut SystemState<MessageReader<RequestApplyTransformEvent>>,
+) {
+    let requests: Vec<RequestApplyTransformEvent> = state.get_mut(world).read().cloned().collect();
+    for RequestApplyTransformEvent {
+        entities,
+        scale,
+        rotation,
+    } in requests
+    {
+        for entity in entities {
+            if world.get_entity(entity).is_err() || !can_edit(world, entity) {
+                continue;
+            }
+            match apply_transform(world, entity, scale, rotation) {
+                Ok(()) => log!(
+                    LogType::Editor,
+                    LogLevel::OK,
+                    LogCategory::Entity,
+                    "Applied transform of {}",
+                    entity
+                ),
+                Err(e) => log!(
+                    LogType::Editor,
+                    LogLevel::Warning,
+                    LogCategory::Entity,
+                    "Could not apply transform of {}: {}",
+                    entity,
+                    e
+                ),
+            }
+        }
+    }
+}
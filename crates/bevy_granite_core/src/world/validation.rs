@@ -0,0 +1,472 @@
+use crate::{
+    absolute_asset_to_rel,
+    entities::{SaveSettings, SpawnSource},
+    RequestLoadEvent, RequestValidateScenesEvent, WorldLoadSuccessEvent,
+};
+use bevy::{
+    app::{App, AppExit, Plugin, Update},
+    ecs::{
+        entity::Entity,
+        message::{MessageReader, MessageWriter},
+        resource::Resource,
+        system::{Commands, ResMut},
+    },
+    prelude::World,
+};
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+use std::{fmt, sync::Arc};
+
+// validation.rs
+// Project specific checks on loaded scenes, i.e. "every EnemySpawner must reference a valid patrol graph"
+// Game crates implement SceneValidator and register it with `add_scene_validator`. Validators run on request,
+// from the editor's Issues tab or the headless SceneValidationPlugin, and may offer a quick fix for what they find
+
+/// Frames a scene may take to load before validation gives up on it
+const LOAD_TIMEOUT_FRAMES: u32 = 600;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum IssueSeverity {
+    Info,
+    Warning,
+    /// Fails the headless validate command
+    Error,
+}
+
+impl IssueSeverity {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Info => "Info",
+            Self::Warning => "Warning",
+            Self::Error => "Error",
+        }
+    }
+
+    pub fn log_level(&self) -> LogLevel {
+        match self {
+            Self::Info => LogLevel::Info,
+            Self::Warning => LogLevel::Warning,
+            Self::Error => LogLevel::Error,
+        }
+    }
+}
+
+/// Quick fix offered with an issue, run on the world when picked
+#[derive(Clone)]
+pub struct IssueFix {
+    pub label: String,
+    apply: Arc<dyn Fn(&mut World) + Send + Sync>,
+}
+
+impl IssueFix {
+    pub fn new(
+        label: impl Into<String>,
+        apply: impl Fn(&mut World) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            label: label.into(),
+            apply: Arc::new(apply),
+        }
+    }
+
+    pub fn apply(&self, world: &mut World) {
+        (self.apply)(world);
+    }
+}
+
+impl fmt::Debug for IssueFix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IssueFix")
+            .field("label", &self.label)
+            .finish()
+    }
+}
+
+impl PartialEq for IssueFix {
+    fn eq(&self, other: &Self) -> bool {
+        self.label == other.label
+    }
+}
+
+/// One problem found by a validator
+#[derive(Debug, Clone, PartialEq)]
+pub struct SceneIssue {
+    /// Name of the validator that found it, filled in when validators run
+    pub validator: String,
+    pub severity: IssueSeverity,
+    pub message: String,
+    pub entity: Option<Entity>,
+    /// Scene path relative to assets, taken from the entity's SpawnSource when not set
+    pub scene: Option<String>,
+    pub fix: Option<IssueFix>,
+}
+
+impl SceneIssue {
+    pub fn new(severity: IssueSeverity, message: impl Into<String>) -> Self {
+        Self {
+            validator: String::new(),
+            severity,
+            message: message.into(),
+            entity: None,
+            scene: None,
+            fix: None,
+        }
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::new(IssueSeverity::Error, message)
+    }
+
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self::new(IssueSeverity::Warning, message)
+    }
+
+    pub fn info(message: impl Into<String>) -> Self {
+        Self::new(IssueSeverity::Info, message)
+    }
+
+    /// The entity the issue is about, selected when the issue is clicked in the editor
+    pub fn on(mut self, entity: Entity) -> Self {
+        self.entity = Some(entity);
+        self
+    }
+
+    pub fn in_scene(mut self, scene: impl Into<String>) -> Self {
+        self.scene = Some(scene.into());
+        self
+    }
+
+    pub fn with_fix(
+        mut self,
+        label: impl Into<String>,
+        apply: impl Fn(&mut World) + Send + Sync + 'static,
+    ) -> Self {
+        self.fix = Some(IssueFix::new(label, apply));
+        self
+    }
+}
+
+/// A project rule checked against every loaded scene
+/// Validators should only read the world, changes belong in the fixes they return
+pub trait SceneValidator: Send + Sync + 'static {
+    /// Shown next to each issue, i.e. "Enemy patrols"
+    fn name(&self) -> &str;
+
+    fn validate(&self, world: &mut World) -> Vec<SceneIssue>;
+}
+
+/// Validators registered with `add_scene_validator`, run in order
+#[derive(Resource, Default, Clone)]
+pub struct SceneValidators {
+    validators: Vec<Arc<dyn SceneValidator>>,
+}
+
+impl SceneValidators {
+    pub fn add(&mut self, validator: impl SceneValidator) {
+        self.validators.push(Arc::new(validator));
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.validators
+            .iter()
+            .map(|validator| validator.name().to_string())
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.validators.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.validators.is_empty()
+    }
+}
+
+pub trait SceneValidatorAppExt {
+    /// Run `validator` whenever loaded scenes are validated
+    fn add_scene_validator(&mut self, validator: impl SceneValidator) -> &mut Self;
+}
+
+impl SceneValidatorAppExt for App {
+    fn add_scene_validator(&mut self, validator: impl SceneValidator) -> &mut Self {
+        self.init_resource::<SceneValidators>();
+        self.world_mut()
+            .resource_mut::<SceneValidators>()
+            .add(validator);
+        self
+    }
+}
+
+/// Issues of the last validation, worst first
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct SceneIssues {
+    pub issues: Vec<SceneIssue>,
+    /// Validations run so far, 0 until the first
+    pub runs: u32,
+}
+
+impl SceneIssues {
+    pub fn count(&self, severity: IssueSeverity) -> usize {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == severity)
+            .count()
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.count(IssueSeverity::Error) > 0
+    }
+}
+
+/// Runs every registered validator on the loaded scenes and keeps the result in SceneIssues
+pub fn run_scene_validators(world: &mut World) -> Vec<SceneIssue> {
+    let validators = world
+        .get_resource::<SceneValidators>()
+        .map(|validators| validators.validators.clone())
+        .unwrap_or_default();
+
+    let mut issues = vec![];
+    for validator in validators.iter() {
+        for mut issue in validator.validate(world) {
+            issue.validator = validator.name().to_string();
+            if issue.scene.is_none() {
+                issue.scene = issue
+                    .entity
+                    .and_then(|entity| world.get::<SpawnSource>(entity))
+                    .map(|source| source.str_ref().to_string());
+            }
+            issues.push(issue);
+        }
+    }
+    issues.sort_by(|a, b| {
+        b.severity
+            .cmp(&a.severity)
+            .then_with(|| a.scene.cmp(&b.scene))
+            .then_with(|| a.validator.cmp(&b.validator))
+    });
+
+    let mut scene_issues = world.get_resource_or_init::<SceneIssues>();
+    scene_issues.issues = issues.clone();
+    scene_issues.runs += 1;
+    issues
+}
+
+/// Applies the quick fix of an issue from the last validation, then validates again
+pub fn apply_issue_fix(world: &mut World, index: usize) {
+    let fix = world
+        .get_resource::<SceneIssues>()
+        .and_then(|issues| issues.issues.get(index))
+        .and_then(|issue| issue.fix.clone());
+    let Some(fix) = fix else {
+        return;
+    };
+    log!(
+        LogType::Game,
+        LogLevel::Info,
+        LogCategory::System,
+        "Applying fix '{}'",
+        fix.label
+    );
+    fix.apply(world);
+    run_scene_validators(world);
+}
+
+pub fn validate_scenes_request_system(
+    mut requests: MessageReader<RequestValidateScenesEvent>,
+    mut commands: Commands,
+) {
+    if requests.read().count() == 0 {
+        return;
+    }
+    commands.queue(|world: &mut World| {
+        let issues = run_scene_validators(world);
+        log!(
+            LogType::Game,
+            LogLevel::Info,
+            LogCategory::System,
+            "Validated loaded scenes: {} issues",
+            issues.len()
+        );
+    });
+}
+
+/// Headless validate command: loads scenes, runs every registered validator on them, logs the issues
+/// and exits with an error code if any of them is an error
+pub struct SceneValidationPlugin {
+    /// Scenes to validate, relative to assets
+    pub scenes: Vec<String>,
+    /// Send AppExit when done
+    pub exit_when_done: bool,
+}
+
+impl Default for SceneValidationPlugin {
+    fn default() -> Self {
+        Self {
+            scenes: vec![],
+            exit_when_done: true,
+        }
+    }
+}
+
+impl Plugin for SceneValidationPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            //
+            // Resources
+            //
+            .insert_resource(SceneValidation::new(self))
+            //
+            // Schedule systems
+            //
+            .add_systems(Update, scene_validation_system);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ValidationStep {
+    Start,
+    Loading,
+    Validate,
+    Done,
+}
+
+/// Running headless validation
+#[derive(Resource)]
+pub struct SceneValidation {
+    scenes: Vec<String>,
+    exit_when_done: bool,
+    step: ValidationStep,
+    /// Frames spent in the current step
+    frames: u32,
+    pending: Vec<String>,
+}
+
+impl SceneValidation {
+    fn new(plugin: &SceneValidationPlugin) -> Self {
+        Self {
+            scenes: plugin
+                .scenes
+                .iter()
+                .map(|scene| absolute_asset_to_rel(scene.clone()).to_string())
+                .collect(),
+            exit_when_done: plugin.exit_when_done,
+            step: ValidationStep::Start,
+            frames: 0,
+            pending: vec![],
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.step == ValidationStep::Done
+    }
+
+    fn go(&mut self, step: ValidationStep) {
+        self.step = step;
+        self.frames = 0;
+    }
+}
+
+pub fn scene_validation_system(
+    mut validation: ResMut<SceneValidation>,
+    mut loaded: MessageReader<WorldLoadSuccessEvent>,
+    mut load: MessageWriter<RequestLoadEvent>,
+    mut commands: Commands,
+) {
+    for WorldLoadSuccessEvent(path) in loaded.read() {
+        let path = absolute_asset_to_rel(path.clone()).to_string();
+        validation.pending.retain(|scene| *scene != path);
+    }
+    validation.frames += 1;
+
+    match validation.step {
+        ValidationStep::Start => {
+            log!(
+                LogType::Game,
+                LogLevel::Info,
+                LogCategory::System,
+                "Validating {} scenes",
+                validation.scenes.len()
+            );
+            for scene in validation.scenes.iter() {
+                load.write(RequestLoadEvent(scene.clone(), SaveSettings::Runtime, None));
+            }
+            validation.pending = validation.scenes.clone();
+            validation.go(ValidationStep::Loading);
+        }
+        ValidationStep::Loading if validation.pending.is_empty() => {
+            validation.go(ValidationStep::Validate)
+        }
+        ValidationStep::Loading if validation.frames > LOAD_TIMEOUT_FRAMES => {
+            for scene in validation.pending.iter() {
+                log!(
+                    LogType::Game,
+                    LogLevel::Error,
+                    LogCategory::System,
+                    "Validation timed out loading '{}'",
+                    scene
+                );
+            }
+            validation.go(ValidationStep::Done);
+            if validation.exit_when_done {
+                commands.write_message(AppExit::error());
+            }
+        }
+        // Loaded entities are spawned through commands, give them a frame
+        ValidationStep::Validate if validation.frames > 1 => {
+            let exit_when_done = validation.exit_when_done;
+            validation.go(ValidationStep::Done);
+            commands.queue(move |world: &mut World| {
+                let issues = run_scene_validators(world);
+                log_issues(&issues);
+                if exit_when_done {
+                    let failed = issues
+                        .iter()
+                        .any(|issue| issue.severity == IssueSeverity::Error);
+                    world.write_message(if failed {
+                        AppExit::error()
+                    } else {
+                        AppExit::Success
+                    });
+                }
+            });
+        }
+        _ => {}
+    }
+}
+
+fn log_issues(issues: &[SceneIssue]) {
+    for issue in issues.iter() {
+        log!(
+            LogType::Game,
+            issue.severity.log_level(),
+            LogCategory::System,
+            "[{}] {}: {}{}",
+            issue.validator,
+            issue.scene.as_deref().unwrap_or("-"),
+            issue.message,
+            issue
+                .fix
+                .as_ref()
+                .map(|fix| format!(" (fix: {})", fix.label))
+                .unwrap_or_default()
+        );
+    }
+    let errors = issues
+        .iter()
+        .filter(|issue| issue.severity == IssueSeverity::Error)
+        .count();
+    log!(
+        LogType::Game,
+        if errors == 0 {
+            LogLevel::OK
+        } else {
+            LogLevel::Error
+        },
+        LogCategory::System,
+        "Validation done: {} issues, {} errors",
+        issues.len(),
+        errors
+    );
+}
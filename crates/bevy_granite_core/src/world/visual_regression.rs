@@ -0,0 +1,614 @@
+use crate::{
+    absolute_asset_to_rel, entities::SaveSettings, RequestLoadEvent, WorldLoadSuccessEvent,
+};
+use bevy::{
+    app::{App, AppExit, Plugin, Update},
+    asset::{Assets, Handle, RenderAssetUsages},
+    camera::{primitives::Aabb, Camera, Camera3d, PerspectiveProjection, Projection, RenderTarget},
+    ecs::{
+        entity::Entity,
+        message::{MessageReader, MessageWriter},
+        observer::On,
+        resource::Resource,
+        system::{Commands, Query, Res, ResMut},
+    },
+    image::{CompressedImageFormats, Image, ImageSampler, ImageType},
+    math::{EulerRot, Quat, Vec3},
+    mesh::{Mesh, Mesh3d},
+    pbr::{MeshMaterial3d, StandardMaterial},
+    prelude::{GlobalTransform, Name, Time, Virtual},
+    render::{
+        render_resource::TextureFormat,
+        view::screenshot::{Screenshot, ScreenshotCaptured},
+    },
+    transform::components::Transform,
+};
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+use image::{Rgba, RgbaImage};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+// visual_regression.rs
+// Visual regression mode, behind the `visual` feature. Loads a scene, renders it from fixed angles around its bounds
+// into PNGs and compares them with stored baselines, so content or engine upgrades that change the look are caught
+// Virtual time is paused once the scene loads, so animated materials render the same every run
+// A render fails when more than `threshold` of its pixels differ by more than PIXEL_TOLERANCE in any channel.
+// Failed renders keep the new image and a diff image (differing pixels red over a dimmed baseline) in the output folder
+
+/// Frames to wait for meshes and textures before rendering anyway
+const ASSET_TIMEOUT_FRAMES: u32 = 600;
+/// Frames after the scene loaded before anything is checked, so spawned entities get bounds
+const SETTLE_FRAMES: u32 = 10;
+/// Frames the cameras render before the images are read back, so shadows and exposure settle
+const CAPTURE_DELAY_FRAMES: u32 = 5;
+/// Channel difference, out of 255, still counted as the same pixel. Absorbs dithering and driver rounding
+const PIXEL_TOLERANCE: u8 = 8;
+const FOV: f32 = std::f32::consts::FRAC_PI_4;
+
+/// Name, yaw and pitch (degrees) of each render, looking at the center of the scene bounds
+pub const VISUAL_REGRESSION_ANGLES: [(&str, f32, f32); 6] = [
+    ("front", 0.0, -15.0),
+    ("right", 90.0, -15.0),
+    ("back", 180.0, -15.0),
+    ("left", 270.0, -15.0),
+    ("top", 0.0, -89.0),
+    ("iso", 45.0, -35.0),
+];
+
+/// Renders a scene from VISUAL_REGRESSION_ANGLES and compares each render with its baseline PNG, i.e.
+/// `cargo run --example visual_regression --no-default-features --features visual -- scenes/dungeon.scene tests/visual`
+/// Add `--update` to accept the new renders. Needs a GPU but no window
+pub struct VisualRegressionPlugin {
+    /// Scene to render, relative to assets
+    pub scene: String,
+    /// Folder holding the baseline PNGs, i.e. tests/visual/dungeon_front.png
+    pub baseline_dir: PathBuf,
+    /// Folder the report, and the renders and diffs of failed angles, are written to
+    pub output_dir: PathBuf,
+    /// Fraction of pixels allowed to differ, 0.001 is 0.1%
+    pub threshold: f32,
+    pub width: u32,
+    pub height: u32,
+    /// Overwrite the baselines with this run's renders instead of comparing
+    pub update_baselines: bool,
+    /// Send AppExit when done, with an error code if any angle failed
+    pub exit_when_done: bool,
+}
+
+impl Default for VisualRegressionPlugin {
+    fn default() -> Self {
+        Self {
+            scene: String::new(),
+            baseline_dir: PathBuf::from("tests/visual"),
+            output_dir: PathBuf::from("target/visual"),
+            threshold: 0.001,
+            width: 640,
+            height: 360,
+            update_baselines: false,
+            exit_when_done: true,
+        }
+    }
+}
+
+impl Plugin for VisualRegressionPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            //
+            // Resources
+            //
+            .insert_resource(VisualRegression::new(self))
+            //
+            // Schedule systems
+            //
+            .add_systems(Update, visual_regression_system);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum VisualStep {
+    Start,
+    Loading,
+    Settling,
+    Rendering,
+    Capturing,
+    Done,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VisualOutcome {
+    Passed,
+    Failed,
+    /// No baseline yet, this run's render was stored as one
+    NewBaseline,
+    /// Written over the baseline, see `update_baselines`
+    Updated,
+}
+
+/// Comparison of one angle against its baseline
+#[derive(Debug, Clone)]
+pub struct VisualComparison {
+    pub angle: String,
+    pub outcome: VisualOutcome,
+    /// Fraction of differing pixels, 0 when nothing was compared
+    pub difference: f32,
+    pub note: Option<String>,
+}
+
+type CapturedImages = Arc<Mutex<Vec<Option<Image>>>>;
+
+/// Running visual regression test and its results
+#[derive(Resource)]
+pub struct VisualRegression {
+    scene: String,
+    baseline_dir: PathBuf,
+    output_dir: PathBuf,
+    threshold: f32,
+    width: u32,
+    height: u32,
+    update_baselines: bool,
+    exit_when_done: bool,
+    step: VisualStep,
+    /// Frames spent in the current step
+    frames: u32,
+    cameras: Vec<Entity>,
+    targets: Vec<Handle<Image>>,
+    captured: CapturedImages,
+    results: Vec<VisualComparison>,
+}
+
+impl VisualRegression {
+    fn new(plugin: &VisualRegressionPlugin) -> Self {
+        Self {
+            scene: absolute_asset_to_rel(plugin.scene.clone()).to_string(),
+            baseline_dir: plugin.baseline_dir.clone(),
+            output_dir: plugin.output_dir.clone(),
+            threshold: plugin.threshold,
+            width: plugin.width.max(1),
+            height: plugin.height.max(1),
+            update_baselines: plugin.update_baselines,
+            exit_when_done: plugin.exit_when_done,
+            step: VisualStep::Start,
+            frames: 0,
+            cameras: Vec::new(),
+            targets: Vec::new(),
+            captured: Arc::new(Mutex::new(Vec::new())),
+            results: Vec::new(),
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.step == VisualStep::Done
+    }
+
+    pub fn results(&self) -> &[VisualComparison] {
+        &self.results
+    }
+
+    pub fn passed(&self) -> bool {
+        self.is_done()
+            && !self.results.is_empty()
+            && self
+                .results
+                .iter()
+                .all(|result| result.outcome != VisualOutcome::Failed)
+    }
+
+    fn go(&mut self, step: VisualStep) {
+        self.step = step;
+        self.frames = 0;
+    }
+
+    /// Scene file name without extensions, prefix of every image of this scene
+    fn stem(&self) -> String {
+        let file = self.scene.rsplit('/').next().unwrap_or(&self.scene);
+        file.split('.').next().unwrap_or(file).to_string()
+    }
+
+    fn image_name(&self, angle: &str) -> String {
+        format!("{}_{}.png", self.stem(), angle)
+    }
+}
+
+type VisualMeshQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Mesh3d,
+        Option<&'static MeshMaterial3d<StandardMaterial>>,
+    ),
+>;
+
+type VisualAssets<'w> = (
+    Res<'w, Assets<Mesh>>,
+    Res<'w, Assets<StandardMaterial>>,
+    ResMut<'w, Assets<Image>>,
+);
+
+pub fn visual_regression_system(
+    mut visual: ResMut<VisualRegression>,
+    mut loaded_reader: MessageReader<WorldLoadSuccessEvent>,
+    mut writers: (MessageWriter<RequestLoadEvent>, MessageWriter<AppExit>),
+    mut commands: Commands,
+    mut time: ResMut<Time<Virtual>>,
+    meshes: VisualMeshQuery,
+    bounds: Query<(&GlobalTransform, &Aabb)>,
+    mut assets: VisualAssets,
+) {
+    let loaded = loaded_reader
+        .read()
+        .any(|WorldLoadSuccessEvent(path)| absolute_asset_to_rel(path.clone()) == visual.scene);
+    visual.frames += 1;
+
+    match visual.step {
+        VisualStep::Start => {
+            log!(
+                LogType::Game,
+                LogLevel::Info,
+                LogCategory::System,
+                "Visual regression on '{}': {} angles at {}x{}",
+                visual.scene,
+                VISUAL_REGRESSION_ANGLES.len(),
+                visual.width,
+                visual.height
+            );
+            writers.0.write(RequestLoadEvent(
+                visual.scene.clone(),
+                SaveSettings::Runtime,
+                None,
+            ));
+            visual.go(VisualStep::Loading);
+        }
+        VisualStep::Loading if loaded => {
+            time.pause();
+            visual.go(VisualStep::Settling);
+        }
+        VisualStep::Loading if visual.frames > ASSET_TIMEOUT_FRAMES => {
+            log!(
+                LogType::Game,
+                LogLevel::Error,
+                LogCategory::System,
+                "Visual regression timed out loading '{}'",
+                visual.scene
+            );
+            finish(&mut visual, &mut writers.1);
+        }
+        VisualStep::Settling if visual.frames > SETTLE_FRAMES => {
+            let ready = scene_assets_ready(&meshes, &assets);
+            if !ready && visual.frames <= ASSET_TIMEOUT_FRAMES {
+                return;
+            }
+            if !ready {
+                log!(
+                    LogType::Game,
+                    LogLevel::Warning,
+                    LogCategory::System,
+                    "Visual regression: some meshes or textures of '{}' never loaded, rendering anyway",
+                    visual.scene
+                );
+            }
+            spawn_cameras(&mut visual, &mut commands, &bounds, &mut assets.2);
+            visual.go(VisualStep::Rendering);
+        }
+        VisualStep::Rendering if visual.frames >= CAPTURE_DELAY_FRAMES => {
+            for (index, target) in visual.targets.iter().enumerate() {
+                let captured = visual.captured.clone();
+                commands.spawn(Screenshot::image(target.clone())).observe(
+                    move |event: On<ScreenshotCaptured>| {
+                        if let Ok(mut captured) = captured.lock() {
+                            captured[index] = Some(event.image.clone());
+                        }
+                    },
+                );
+            }
+            visual.go(VisualStep::Capturing);
+        }
+        VisualStep::Capturing => {
+            let images = {
+                let Ok(captured) = visual.captured.lock() else {
+                    return;
+                };
+                if captured.iter().any(Option::is_none) {
+                    return;
+                }
+                captured.iter().flatten().cloned().collect::<Vec<_>>()
+            };
+            for camera in visual.cameras.drain(..) {
+                commands.entity(camera).despawn();
+            }
+            compare_renders(&mut visual, images);
+            write_report(&visual);
+            finish(&mut visual, &mut writers.1);
+        }
+        _ => {}
+    }
+}
+
+/// Meshes exist and every texture their materials use is loaded
+fn scene_assets_ready(meshes: &VisualMeshQuery, assets: &VisualAssets) -> bool {
+    meshes.iter().all(|(mesh, material)| {
+        if !assets.0.contains(&mesh.0) {
+            return false;
+        }
+        let Some(material) = material.and_then(|material| assets.1.get(&material.0)) else {
+            return material.is_none();
+        };
+        [
+            &material.base_color_texture,
+            &material.normal_map_texture,
+            &material.metallic_roughness_texture,
+            &material.emissive_texture,
+            &material.occlusion_texture,
+        ]
+        .into_iter()
+        .flatten()
+        .all(|texture| assets.2.contains(texture))
+    })
+}
+
+/// One camera per angle, framing the bounds of everything with a mesh
+fn spawn_cameras(
+    visual: &mut VisualRegression,
+    commands: &mut Commands,
+    bounds: &Query<(&GlobalTransform, &Aabb)>,
+    images: &mut Assets<Image>,
+) {
+    let mut min = Vec3::splat(f32::MAX);
+    let mut max = Vec3::splat(f32::MIN);
+    for (transform, aabb) in bounds.iter() {
+        let center = Vec3::from(aabb.center);
+        let extents = Vec3::from(aabb.half_extents);
+        for corner in [-1.0, 1.0]
+            .into_iter()
+            .flat_map(|x| [-1.0, 1.0].into_iter().map(move |y| (x, y)))
+            .flat_map(|(x, y)| [-1.0, 1.0].into_iter().map(move |z| Vec3::new(x, y, z)))
+        {
+            let point = transform.transform_point(center + extents * corner);
+            min = min.min(point);
+            max = max.max(point);
+        }
+    }
+    let (center, radius) = if min.x <= max.x {
+        ((min + max) * 0.5, ((max - min).length() * 0.5).max(0.5))
+    } else {
+        (Vec3::ZERO, 5.0)
+    };
+    // Far enough for the bounding sphere to fit the narrower field of view
+    let aspect = visual.width as f32 / visual.height as f32;
+    let half_fov = (FOV * 0.5).min(((FOV * 0.5).tan() * aspect).atan());
+    let distance = radius / half_fov.sin() * 1.1;
+
+    let mut cameras = Vec::with_capacity(VISUAL_REGRESSION_ANGLES.len());
+    let mut targets = Vec::with_capacity(VISUAL_REGRESSION_ANGLES.len());
+    for (name, yaw, pitch) in VISUAL_REGRESSION_ANGLES {
+        let direction =
+            Quat::from_euler(EulerRot::YXZ, yaw.to_radians(), pitch.to_radians(), 0.0) * Vec3::Z;
+        let target = images.add(Image::new_target_texture(
+            visual.width,
+            visual.height,
+            TextureFormat::Rgba8UnormSrgb,
+            None,
+        ));
+        let camera = commands
+            .spawn((
+                Transform::from_translation(center + direction * distance)
+                    .looking_at(center, Vec3::Y),
+                Camera3d::default(),
+                Camera {
+                    order: -1,
+                    ..Default::default()
+                },
+                RenderTarget::Image(target.clone().into()),
+                Projection::Perspective(PerspectiveProjection {
+                    fov: FOV,
+                    aspect_ratio: aspect,
+                    near: 0.1,
+                    far: distance + radius * 2.0,
+                    ..Default::default()
+                }),
+                Name::new(format!("Visual Regression Camera ({})", name)),
+            ))
+            .id();
+        cameras.push(camera);
+        targets.push(target);
+    }
+    visual.captured = Arc::new(Mutex::new(vec![None; targets.len()]));
+    visual.cameras = cameras;
+    visual.targets = targets;
+}
+
+fn compare_renders(visual: &mut VisualRegression, images: Vec<Image>) {
+    let _ = fs::create_dir_all(&visual.output_dir);
+    let _ = fs::create_dir_all(&visual.baseline_dir);
+
+    for ((angle, ..), image) in VISUAL_REGRESSION_ANGLES.iter().zip(images) {
+        let name = visual.image_name(angle);
+        let baseline_path = visual.baseline_dir.join(&name);
+        let result = match image.try_into_dynamic() {
+            Ok(dynamic) => compare_render(
+                visual,
+                &dynamic.to_rgba8(),
+                &baseline_path,
+                &visual.output_dir.join(&name),
+            ),
+            Err(e) => Err(e.to_string()),
+        };
+        let comparison = match result {
+            Ok((outcome, difference)) => VisualComparison {
+                angle: angle.to_string(),
+                outcome,
+                difference,
+                note: None,
+            },
+            Err(e) => VisualComparison {
+                angle: angle.to_string(),
+                outcome: VisualOutcome::Failed,
+                difference: 0.0,
+                note: Some(e),
+            },
+        };
+        let level = match comparison.outcome {
+            VisualOutcome::Failed => LogLevel::Error,
+            _ => LogLevel::Info,
+        };
+        log!(
+            LogType::Game,
+            level,
+            LogCategory::System,
+            "Visual regression '{}' {}: {:?}, {:.3}% differs {}",
+            visual.scene,
+            angle,
+            comparison.outcome,
+            comparison.difference * 100.0,
+            comparison.note.clone().unwrap_or_default()
+        );
+        visual.results.push(comparison);
+    }
+}
+
+fn compare_render(
+    visual: &VisualRegression,
+    render: &RgbaImage,
+    baseline_path: &Path,
+    output_path: &Path,
+) -> Result<(VisualOutcome, f32), String> {
+    if visual.update_baselines || !baseline_path.exists() {
+        render.save(baseline_path).map_err(|e| e.to_string())?;
+        return Ok(if visual.update_baselines {
+            (VisualOutcome::Updated, 0.0)
+        } else {
+            (VisualOutcome::NewBaseline, 0.0)
+        });
+    }
+
+    let baseline = read_png(baseline_path)?;
+    if baseline.dimensions() != render.dimensions() {
+        render.save(output_path).map_err(|e| e.to_string())?;
+        return Err(format!(
+            "baseline is {:?}, render is {:?}",
+            baseline.dimensions(),
+            render.dimensions()
+        ));
+    }
+
+    let mut diff = RgbaImage::new(render.width(), render.height());
+    let mut differing = 0usize;
+    for ((rendered, expected), out) in render
+        .pixels()
+        .zip(baseline.pixels())
+        .zip(diff.pixels_mut())
+    {
+        let differs = rendered
+            .0
+            .iter()
+            .zip(expected.0.iter())
+            .any(|(a, b)| a.abs_diff(*b) > PIXEL_TOLERANCE);
+        *out = if differs {
+            differing += 1;
+            Rgba([255, 0, 0, 255])
+        } else {
+            let [r, g, b, _] = expected.0;
+            let gray = ((r as u32 + g as u32 + b as u32) / 9) as u8;
+            Rgba([gray, gray, gray, 255])
+        };
+    }
+
+    let difference = differing as f32 / (render.width() * render.height()).max(1) as f32;
+    if difference <= visual.threshold {
+        return Ok((VisualOutcome::Passed, difference));
+    }
+    render.save(output_path).map_err(|e| e.to_string())?;
+    diff.save(output_path.with_extension("diff.png"))
+        .map_err(|e| e.to_string())?;
+    Ok((VisualOutcome::Failed, difference))
+}
+
+fn read_png(path: &Path) -> Result<RgbaImage, String> {
+    let bytes = fs::read(path).map_err(|e| format!("could not read baseline: {}", e))?;
+    Image::from_buffer(
+        &bytes,
+        ImageType::Extension("png"),
+        CompressedImageFormats::NONE,
+        true,
+        ImageSampler::Default,
+        RenderAssetUsages::default(),
+    )
+    .map_err(|e| e.to_string())?
+    .try_into_dynamic()
+    .map(|dynamic| dynamic.to_rgba8())
+    .map_err(|e| e.to_string())
+}
+
+/// Plain text report, one line per angle, next to the failed renders
+fn write_report(visual: &VisualRegression) {
+    let mut report = format!(
+        "Visual regression report for {}\nthreshold {:.3}%, pixel tolerance {}\n\n",
+        visual.scene,
+        visual.threshold * 100.0,
+        PIXEL_TOLERANCE
+    );
+    for result in visual.results.iter() {
+        report.push_str(&format!(
+            "{:<8} {:<12} {:>8.3}%  {}\n",
+            result.angle,
+            format!("{:?}", result.outcome),
+            result.difference * 100.0,
+            result.note.clone().unwrap_or_default()
+        ));
+    }
+    let path = visual
+        .output_dir
+        .join(format!("{}_report.txt", visual.stem()));
+    if let Err(e) = fs::write(&path, report) {
+        log!(
+            LogType::Game,
+            LogLevel::Error,
+            LogCategory::System,
+            "Failed to write visual regression report '{}': {}",
+            path.display(),
+            e
+        );
+    }
+}
+
+fn finish(visual: &mut VisualRegression, exit: &mut MessageWriter<AppExit>) {
+    visual.go(VisualStep::Done);
+    let failed = visual
+        .results
+        .iter()
+        .filter(|result| result.outcome == VisualOutcome::Failed)
+        .count();
+    if visual.passed() {
+        log!(
+            LogType::Game,
+            LogLevel::OK,
+            LogCategory::System,
+            "Visual regression passed: {} angles of '{}'",
+            visual.results.len(),
+            visual.scene
+        );
+    } else {
+        log!(
+            LogType::Game,
+            LogLevel::Error,
+            LogCategory::System,
+            "Visual regression failed {} of {} angles of '{}', see '{}'",
+            failed,
+            VISUAL_REGRESSION_ANGLES.len(),
+            visual.scene,
+            visual.output_dir.display()
+        );
+    }
+    if visual.exit_when_done {
+        exit.write(if visual.passed() {
+            AppExit::Success
+        } else {
+            AppExit::error()
+        });
+    }
+}
@@ -0,0 +1,399 @@
+use crate::{
+    absolute_asset_to_rel,
+    entities::EntitySaveReadyData,
+    events::{
+        SceneFileChangedEvent, WorldLoadBatchSuccessEvent, WorldLoadSuccessEvent,
+        WorldSaveSuccessEvent,
+    },
+    rel_asset_to_absolute,
+};
+use bevy::prelude::*;
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+use notify_debouncer_full::{
+    new_debouncer,
+    notify::{RecommendedWatcher, RecursiveMode},
+    DebounceEventResult, Debouncer, RecommendedCache,
+};
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    fs,
+    hash::{DefaultHasher, Hash, Hasher},
+    path::{Path, PathBuf},
+    sync::{
+        mpsc::{channel, Receiver},
+        Mutex,
+    },
+    time::Duration,
+};
+use uuid::Uuid;
+
+// watch.rs
+// Notices loaded scene files changed on disk by another process, i.e. a text editor or a git checkout
+// A debounced notify watcher on the folders of loaded scenes feeds a channel that is drained every frame.
+// Each file's contents are hashed when it is loaded or saved, so Granite's own saves and touches that change nothing are not changes
+// A changed scene is refused by RequestSaveEvent until the change is acknowledged or the scene reloaded,
+// so edits on disk are never silently overwritten
+
+/// Seconds filesystem events are held and merged before a change is reported, editors often write a file in several steps
+pub const DEFAULT_SCENE_WATCH_DEBOUNCE: f32 = 0.25;
+
+/// Hash of a scene file's contents, None for missing files
+fn content_hash(scene: &str) -> Option<u64> {
+    let contents = fs::read(rel_asset_to_absolute(scene).as_ref()).ok()?;
+    let mut hasher = DefaultHasher::new();
+    contents.hash(&mut hasher);
+    Some(hasher.finish())
+}
+
+/// Folder a scene file is in, the watcher follows folders so files replaced by a rename are still seen
+fn scene_folder(scene: &str) -> Option<PathBuf> {
+    Path::new(rel_asset_to_absolute(scene).as_ref())
+        .parent()
+        .map(Path::to_path_buf)
+}
+
+/// Loaded scene files and whether they changed on disk since
+/// Off by default, the editor turns it on. i.e. `app.insert_resource(SceneFileWatcher::enabled())`
+#[derive(Resource, Debug, Clone)]
+pub struct SceneFileWatcher {
+    pub enabled: bool,
+    /// Seconds filesystem events are merged for before they count, read when the watcher starts
+    pub debounce: f32,
+    /// Content hashes of watched scenes by relative path, none when the file was missing
+    files: HashMap<String, Option<u64>>,
+    /// Scenes changed on disk, waiting to be reloaded or acknowledged
+    changed: BTreeSet<String>,
+}
+
+impl Default for SceneFileWatcher {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            debounce: DEFAULT_SCENE_WATCH_DEBOUNCE,
+            files: HashMap::new(),
+            changed: BTreeSet::new(),
+        }
+    }
+}
+
+impl SceneFileWatcher {
+    pub fn enabled() -> Self {
+        Self {
+            enabled: true,
+            ..Default::default()
+        }
+    }
+
+    /// Start watching a scene as it is on disk now. Clears a pending change
+    pub fn watch(&mut self, scene: &str) {
+        let scene = absolute_asset_to_rel(scene.to_string()).to_string();
+        self.changed.remove(&scene);
+        self.files.insert(scene.clone(), content_hash(&scene));
+    }
+
+    pub fn unwatch(&mut self, scene: &str) {
+        let scene = absolute_asset_to_rel(scene.to_string()).to_string();
+        self.changed.remove(&scene);
+        self.files.remove(&scene);
+    }
+
+    /// Keep the loaded scene over the change on disk, the next save overwrites the file
+    pub fn acknowledge(&mut self, scene: &str) {
+        self.watch(scene);
+    }
+
+    pub fn is_watched(&self, scene: &str) -> bool {
+        self.files
+            .contains_key(absolute_asset_to_rel(scene.to_string()).as_ref())
+    }
+
+    pub fn is_changed(&self, scene: &str) -> bool {
+        self.changed
+            .contains(absolute_asset_to_rel(scene.to_string()).as_ref())
+    }
+
+    /// Scenes changed on disk, relative to assets
+    pub fn changed(&self) -> impl Iterator<Item = &str> {
+        self.changed.iter().map(String::as_str)
+    }
+
+    /// Folders holding watched scenes
+    fn folders(&self) -> HashSet<PathBuf> {
+        self.files
+            .keys()
+            .filter_map(|scene| scene_folder(scene))
+            .collect()
+    }
+
+    /// A watched scene whose contents no longer match, not marked yet
+    fn check(&self, scene: &str) -> bool {
+        self.files
+            .get(scene)
+            .is_some_and(|hash| !self.changed.contains(scene) && content_hash(scene) != *hash)
+    }
+}
+
+/// The notify side of the SceneFileWatcher, started once the watcher is enabled and stopped when it is turned off
+#[derive(Resource, Default)]
+pub struct SceneFileNotifier {
+    backend: Option<NotifyBackend>,
+    /// Set when the watcher couldn't start, so it isn't retried every frame
+    failed: bool,
+}
+
+struct NotifyBackend {
+    debouncer: Mutex<Debouncer<RecommendedWatcher, RecommendedCache>>,
+    events: Mutex<Receiver<DebounceEventResult>>,
+    folders: HashSet<PathBuf>,
+}
+
+impl SceneFileNotifier {
+    pub fn is_running(&self) -> bool {
+        self.backend.is_some()
+    }
+
+    fn start(&mut self, debounce: f32) {
+        let (sender, events) = channel();
+        match new_debouncer(Duration::from_secs_f32(debounce.max(0.0)), None, sender) {
+            Ok(debouncer) => {
+                self.backend = Some(NotifyBackend {
+                    debouncer: Mutex::new(debouncer),
+                    events: Mutex::new(events),
+                    folders: HashSet::new(),
+                });
+            }
+            Err(e) => {
+                self.failed = true;
+                log!(
+                    LogType::Game,
+                    LogLevel::Error,
+                    LogCategory::System,
+                    "Failed to start the scene file watcher: {}",
+                    e
+                );
+            }
+        }
+    }
+
+    fn stop(&mut self) {
+        self.backend = None;
+        self.failed = false;
+    }
+
+    /// Watch exactly these folders
+    fn sync_folders(&mut self, folders: HashSet<PathBuf>) {
+        let Some(backend) = self.backend.as_mut() else {
+            return;
+        };
+        if backend.folders == folders {
+            return;
+        }
+        let Ok(mut debouncer) = backend.debouncer.lock() else {
+            return;
+        };
+        for folder in backend.folders.difference(&folders) {
+            let _ = debouncer.unwatch(folder);
+        }
+        for folder in folders.difference(&backend.folders) {
+            if let Err(e) = debouncer.watch(folder, RecursiveMode::NonRecursive) {
+                log!(
+                    LogType::Game,
+                    LogLevel::Warning,
+                    LogCategory::System,
+                    "Can't watch '{}' for scene changes: {}",
+                    folder.display(),
+                    e
+                );
+            }
+        }
+        drop(debouncer);
+        backend.folders = folders;
+    }
+
+    /// Files touched since the last call
+    fn drain(&self) -> HashSet<PathBuf> {
+        let mut paths = HashSet::new();
+        let Some(events) = self
+            .backend
+            .as_ref()
+            .and_then(|backend| backend.events.lock().ok())
+        else {
+            return paths;
+        };
+        for result in events.try_iter() {
+            match result {
+                Ok(batch) => paths.extend(batch.into_iter().flat_map(|event| event.event.paths)),
+                Err(errors) => {
+                    for e in errors {
+                        log!(
+                            LogType::Game,
+                            LogLevel::Warning,
+                            LogCategory::System,
+                            "Scene file watcher error: {}",
+                            e
+                        );
+                    }
+                }
+            }
+        }
+        paths
+    }
+}
+
+/// Watches scenes as they are loaded and saved, saves write the file themselves so they are never a change
+pub fn track_scene_files_system(
+    mut watcher: ResMut<SceneFileWatcher>,
+    mut load_reader: MessageReader<WorldLoadSuccessEvent>,
+    mut batch_reader: MessageReader<WorldLoadBatchSuccessEvent>,
+    mut save_reader: MessageReader<WorldSaveSuccessEvent>,
+) {
+    if !watcher.enabled {
+        load_reader.clear();
+        batch_reader.clear();
+        save_reader.clear();
+        return;
+    }
+    for WorldLoadSuccessEvent(path) in load_reader.read() {
+        watcher.watch(path);
+    }
+    for WorldLoadBatchSuccessEvent(paths) in batch_reader.read() {
+        for path in paths {
+            watcher.watch(path);
+        }
+    }
+    for WorldSaveSuccessEvent(path) in save_reader.read() {
+        watcher.watch(path);
+    }
+}
+
+/// Keeps the notify watcher on the folders of watched scenes and drains its events,
+/// sending a SceneFileChangedEvent once per change
+pub fn watch_scene_files_system(
+    mut watcher: ResMut<SceneFileWatcher>,
+    mut notifier: ResMut<SceneFileNotifier>,
+    mut changed_writer: MessageWriter<SceneFileChangedEvent>,
+) {
+    if !watcher.enabled {
+        if notifier.is_running() || notifier.failed {
+            notifier.stop();
+        }
+        return;
+    }
+    if !notifier.is_running() && !notifier.failed {
+        notifier.start(watcher.debounce);
+    }
+    notifier.sync_folders(watcher.folders());
+
+    let touched: BTreeSet<String> = notifier
+        .drain()
+        .into_iter()
+        .map(|path| absolute_asset_to_rel(path.display().to_string()).to_string())
+        .collect();
+    for scene in touched {
+        if !watcher.check(&scene) {
+            continue;
+        }
+        log!(
+            LogType::Game,
+            LogLevel::Warning,
+            LogCategory::System,
+            "'{}' changed on disk",
+            scene
+        );
+        watcher.changed.insert(scene.clone());
+        changed_writer.write(SceneFileChangedEvent(scene));
+    }
+}
+
+/// How an entity differs between a scene file and the loaded scene
+#[derive(Debug, Clone, PartialEq)]
+pub enum SceneEntityChange {
+    /// Only in the file, i.e. added by the other process
+    OnDisk,
+    /// Only in the loaded scene
+    Loaded,
+    /// In both, with the parts that differ: name, class, transform, parent or components
+    Changed(Vec<&'static str>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SceneEntityDiff {
+    pub uuid: Uuid,
+    pub name: String,
+    pub change: SceneEntityChange,
+}
+
+/// Saved components, none and empty are the same
+fn components(entity: &EntitySaveReadyData) -> Option<&HashMap<String, String>> {
+    entity
+        .components
+        .as_ref()
+        .filter(|components| !components.is_empty())
+}
+
+/// Entities that differ between the file and the loaded scene, by uuid in file order
+/// Transforms within a thousandth are equal, saves round them
+pub fn diff_scene_entities(
+    disk: &[EntitySaveReadyData],
+    loaded: &[EntitySaveReadyData],
+) -> Vec<SceneEntityDiff> {
+    let loaded_by_uuid: HashMap<Uuid, &EntitySaveReadyData> = loaded
+        .iter()
+        .map(|entity| (entity.identity.uuid, entity))
+        .collect();
+    let disk_uuids: BTreeSet<Uuid> = disk.iter().map(|entity| entity.identity.uuid).collect();
+    let mut diffs = Vec::new();
+
+    for entity in disk {
+        let Some(other) = loaded_by_uuid.get(&entity.identity.uuid) else {
+            diffs.push(SceneEntityDiff {
+                uuid: entity.identity.uuid,
+                name: entity.identity.name.clone(),
+                change: SceneEntityChange::OnDisk,
+            });
+            continue;
+        };
+        let mut parts = Vec::new();
+        if entity.identity.name != other.identity.name {
+            parts.push("name");
+        }
+        if entity.identity.class != other.identity.class {
+            parts.push("class");
+        }
+        let (a, b) = (&entity.transform, &other.transform);
+        if !a.position.abs_diff_eq(b.position, 1e-3)
+            || !a.rotation.abs_diff_eq(b.rotation, 1e-3)
+            || !a.scale.abs_diff_eq(b.scale, 1e-3)
+        {
+            parts.push("transform");
+        }
+        if entity.parent != other.parent {
+            parts.push("parent");
+        }
+        if components(entity) != components(other) {
+            parts.push("components");
+        }
+        if !parts.is_empty() {
+            diffs.push(SceneEntityDiff {
+                uuid: entity.identity.uuid,
+                name: entity.identity.name.clone(),
+                change: SceneEntityChange::Changed(parts),
+            });
+        }
+    }
+
+    diffs.extend(
+        loaded
+            .iter()
+            .filter(|entity| !disk_uuids.contains(&entity.identity.uuid))
+            .map(|entity| SceneEntityDiff {
+                uuid: entity.identity.uuid,
+                name: entity.identity.name.clone(),
+                change: SceneEntityChange::Loaded,
+            }),
+    );
+    diffs
+}
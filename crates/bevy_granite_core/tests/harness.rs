@@ -0,0 +1,85 @@
+use bevy::ecs::hierarchy::ChildOf;
+use bevy_granite_core::{entities::SceneData, IdentityData, TestHarness};
+
+// harness.rs
+// Loads a small scene through the headless TestHarness, checks what spawned, then saves it and compares the file
+
+const SCENE: &str = r#"(
+    metadata: (
+        format_version: "0.1.4",
+        entity_count: 3,
+    ),
+    entities: [(
+        identity: (
+            uuid: "00000000-0000-0000-0000-000000000003",
+            name: "Lamp",
+            class: Empty(()),
+        ),
+        transform: (
+            position: (0.0, 2.0, 0.0),
+            rotation: (0.0, 0.0, 0.0, 1.0),
+            scale: (1.0, 1.0, 1.0),
+        ),
+        parent: Some("00000000-0000-0000-0000-000000000002"),
+    ), (
+        identity: (
+            uuid: "00000000-0000-0000-0000-000000000001",
+            name: "Room",
+            class: Empty(()),
+        ),
+        transform: (
+            position: (1.0, 0.0, -3.0),
+            rotation: (0.0, 0.0, 0.0, 1.0),
+            scale: (1.0, 1.0, 1.0),
+        ),
+    ), (
+        identity: (
+            uuid: "00000000-0000-0000-0000-000000000002",
+            name: "Table",
+            class: Empty(()),
+        ),
+        transform: (
+            position: (0.5, 0.0, 0.5),
+            rotation: (0.0, 0.0, 0.0, 1.0),
+            scale: (2.0, 1.0, 2.0),
+        ),
+        parent: Some("00000000-0000-0000-0000-000000000001"),
+    )],
+)"#;
+
+fn parse(scene: &str) -> SceneData {
+    ron::de::from_str(scene).unwrap_or_else(|e| panic!("Scene doesn't parse: {}", e))
+}
+
+#[test]
+fn load_step_and_save_scene() {
+    let mut harness = TestHarness::new();
+    let source = harness.load_scene_str(SCENE);
+    harness.update_frames(2);
+
+    harness.assert_entity_count(&source, 3);
+    let room = harness.assert_component::<IdentityData>("Room");
+    let table = harness.assert_entity("Table");
+    let lamp = harness.assert_entity("Lamp");
+    let parent_of = |harness: &TestHarness, entity| {
+        harness
+            .world()
+            .get::<ChildOf>(entity)
+            .map(|child_of| child_of.parent())
+    };
+    assert_eq!(parent_of(&harness, room), None);
+    assert_eq!(parent_of(&harness, table), Some(room));
+    assert_eq!(parent_of(&harness, lamp), Some(table));
+
+    let saved = parse(&harness.save_scene(&source));
+    let original = parse(SCENE);
+    assert_eq!(saved.entities.len(), original.entities.len());
+    // Saves keep file order even though the lamp spawned after its parents
+    for (saved, original) in saved.entities.iter().zip(original.entities.iter()) {
+        assert_eq!(saved.identity, original.identity);
+        assert_eq!(saved.parent, original.parent);
+        assert_eq!(saved.transform, original.transform);
+    }
+
+    harness.assert_round_trip(&source);
+}
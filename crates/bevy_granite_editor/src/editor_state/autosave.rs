@@ -0,0 +1,226 @@
+use crate::{
+    editor_state::{EditorMutationEvent, EditorState, ProjectLock},
+    interface::{popups::PopupType, PopupMenuRequestedEvent},
+};
+use bevy::{
+    math::Vec2,
+    prelude::{MessageReader, MessageWriter, Query, Res, ResMut, Resource, World},
+    time::{Real, Time},
+};
+use bevy_granite_core::{
+    absolute_asset_to_rel, read_scene_file, read_scene_file_compressed, rel_asset_to_absolute,
+    scene_to_string, write_scene_contents, EditorCapabilities, GraniteSnapshot, SceneCompression,
+    SpawnSource, WorldLoadSuccessEvent, WorldSaveSuccessEvent,
+};
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs, io,
+    time::SystemTime,
+};
+
+// autosave.rs
+// Timed autosave of edited scenes to .scene.autosave siblings, and recovery from them after a crash
+// Only scenes edited since their last save or autosave are written. Saving a scene removes its autosave,
+// so an autosave newer than its scene when the scene loads means the edits never made it to disk
+
+pub const AUTOSAVE_EXTENSION: &str = "autosave";
+/// Autosave interval when nothing is configured
+pub const DEFAULT_AUTOSAVE_MINUTES: f32 = 2.0;
+
+/// i.e. scenes/level_1.scene becomes <assets>/scenes/level_1.scene.autosave
+pub fn autosave_path_for_scene(scene: &str) -> String {
+    format!("{}.{}", rel_asset_to_absolute(scene), AUTOSAVE_EXTENSION)
+}
+
+/// An autosave newer than its scene, waiting for the user to recover or discard it
+#[derive(Debug, Clone, PartialEq)]
+pub struct AutosaveRecovery {
+    /// Relative to assets
+    pub scene: String,
+    pub modified: SystemTime,
+}
+
+/// Edited scenes are written to a .scene.autosave next to them on the `Autosave Interval` of the editor settings, 0 turns it off
+/// `recoveries` lists autosaves found newer than their scene on load, the editor offers to recover or discard them
+#[derive(Resource, Default, Debug, Clone)]
+pub struct Autosave {
+    /// Scenes edited since their last save or autosave, relative to assets
+    dirty: HashSet<String>,
+    /// Entities were removed, their scene is gone with them so every loaded scene is written
+    despawned: bool,
+    since_autosave: f32,
+    pub recoveries: Vec<AutosaveRecovery>,
+}
+
+/// Writes the autosave of a scene over the scene itself, keeping the scene's compression, then removes the autosave
+pub fn recover_autosave(scene: &str) -> io::Result<()> {
+    let autosave_path = autosave_path_for_scene(scene);
+    let scene_path = rel_asset_to_absolute(scene).to_string();
+    let contents = read_scene_file(&autosave_path)?;
+    let compression = read_scene_file_compressed(&scene_path)
+        .map(|(_, compression)| compression)
+        .ok()
+        .or_else(|| SceneCompression::from_extension(&scene_path))
+        .unwrap_or_default();
+    write_scene_contents(&scene_path, &contents, compression)?;
+    fs::remove_file(autosave_path)
+}
+
+pub fn discard_autosave(scene: &str) -> io::Result<()> {
+    fs::remove_file(autosave_path_for_scene(scene))
+}
+
+fn modified(path: &str) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// Notes edited scenes from EditorMutationEvent, and drops the autosave of scenes that were saved
+pub fn track_autosave_system(
+    mut autosave: ResMut<Autosave>,
+    mut mutation_reader: MessageReader<EditorMutationEvent>,
+    mut save_reader: MessageReader<WorldSaveSuccessEvent>,
+    sources: Query<&SpawnSource>,
+    time: Res<Time<Real>>,
+) {
+    for event in mutation_reader.read() {
+        if matches!(event, EditorMutationEvent::Despawned { .. }) {
+            autosave.despawned = true;
+        } else if let Ok(source) = sources.get(event.entity()) {
+            autosave.dirty.insert(source.str_ref().to_string());
+        }
+    }
+
+    for WorldSaveSuccessEvent(path) in save_reader.read() {
+        let scene = absolute_asset_to_rel(path.to_string()).to_string();
+        autosave.dirty.remove(&scene);
+        let autosave_path = autosave_path_for_scene(&scene);
+        if fs::exists(&autosave_path).unwrap_or(false) {
+            let _ = fs::remove_file(autosave_path);
+        }
+    }
+
+    autosave.since_autosave += time.delta_secs();
+}
+
+/// Writes an autosave for every edited scene once the interval from the editor settings has passed
+/// An interval of zero turns autosave off. Skipped in read-only mode, the other editor owns the project,
+/// and when EditorCapabilities doesn't allow saving
+pub fn write_autosaves_system(world: &mut World) {
+    let editor_state = world.resource::<EditorState>();
+    let interval = editor_state.config.autosave_minutes * 60.0;
+    let loaded = editor_state.loaded_sources.clone();
+    let read_only = world.resource::<ProjectLock>().read_only;
+    let autosave = world.resource::<Autosave>();
+    if interval <= 0.0 || read_only || autosave.since_autosave < interval {
+        return;
+    }
+
+    let allowed = world.resource::<EditorCapabilities>().can_save("autosave");
+    let mut autosave = world.resource_mut::<Autosave>();
+    autosave.since_autosave = 0.0;
+    if !allowed {
+        return;
+    }
+    let mut scenes = std::mem::take(&mut autosave.dirty);
+    if std::mem::take(&mut autosave.despawned) {
+        scenes.extend(loaded);
+    }
+    if scenes.is_empty() {
+        return;
+    }
+
+    let snapshot = GraniteSnapshot::capture_quiet(world);
+    let mut by_source: BTreeMap<String, Vec<_>> = scenes
+        .into_iter()
+        .map(|scene| (scene, Vec::new()))
+        .collect();
+    for entity in snapshot.entities {
+        let Some(source) = entity.source.as_ref() else {
+            continue;
+        };
+        if let Some(entities) = by_source.get_mut(source.str_ref()) {
+            entities.push(entity.data);
+        }
+    }
+
+    for (scene, entities) in by_source {
+        let result = scene_to_string(entities, Vec::new())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            .and_then(|contents| {
+                write_scene_contents(
+                    &autosave_path_for_scene(&scene),
+                    &contents,
+                    SceneCompression::None,
+                )
+            });
+        match result {
+            Ok(()) => log!(
+                LogType::Editor,
+                LogLevel::Info,
+                LogCategory::System,
+                "Autosaved '{}'",
+                scene
+            ),
+            Err(e) => log!(
+                LogType::Editor,
+                LogLevel::Error,
+                LogCategory::System,
+                "Failed to autosave '{}': {}",
+                scene,
+                e
+            ),
+        }
+    }
+}
+
+/// Offers recovery when a scene loads with an autosave newer than itself, i.e. on startup after a crash
+pub fn check_autosave_recovery_system(
+    mut autosave: ResMut<Autosave>,
+    mut load_reader: MessageReader<WorldLoadSuccessEvent>,
+    mut popup_writer: MessageWriter<PopupMenuRequestedEvent>,
+) {
+    let mut found = false;
+    for WorldLoadSuccessEvent(path) in load_reader.read() {
+        let scene = absolute_asset_to_rel(path.to_string()).to_string();
+        if autosave
+            .recoveries
+            .iter()
+            .any(|recovery| recovery.scene == scene)
+        {
+            continue;
+        }
+        let Some(autosave_modified) = modified(&autosave_path_for_scene(&scene)) else {
+            continue;
+        };
+        let scene_modified = modified(&rel_asset_to_absolute(&scene));
+        if scene_modified.is_some_and(|scene_modified| scene_modified >= autosave_modified) {
+            continue;
+        }
+
+        log!(
+            LogType::Editor,
+            LogLevel::Warning,
+            LogCategory::System,
+            "'{}' has an autosave newer than the saved file",
+            scene
+        );
+        autosave.recoveries.push(AutosaveRecovery {
+            scene,
+            modified: autosave_modified,
+        });
+        found = true;
+    }
+
+    if found {
+        popup_writer.write(PopupMenuRequestedEvent {
+            popup: PopupType::AutosaveRecovery,
+            mouse_pos: Vec2::ZERO,
+        });
+    }
+}
@@ -0,0 +1,72 @@
+use crate::interface::events::{
+    PopupMenuRequestedEvent, RequestBakeImposters, RequestCameraEntityFrame, RequestCubemapCapture,
+    RequestNewParent, RequestRemoveParents, RequestSceneThumbnail, UserRequestGraniteTypeViaPopup,
+    UserUpdatedComponentsEvent, UserUpdatedIdentityEvent, UserUpdatedTransformEvent,
+};
+use bevy::{ecs::system::SystemParam, prelude::MessageReader};
+use bevy_granite_core::{
+    absolute_asset_to_rel, RequestDespawnSerializableEntities, RequestLoadEvent,
+    RequestReloadEvent, RequestSaveEvent,
+};
+
+/// An editor command seen this frame, with the scene it targets for saves and loads
+#[derive(Debug, Clone, PartialEq)]
+pub struct EditorCommand {
+    pub name: &'static str,
+    /// Scene path relative to assets
+    pub scene: Option<String>,
+}
+
+/// Editor commands read alongside their real handlers, for telemetry and crash reports
+/// Every system using this gets its own readers, so they never steal messages from each other
+#[derive(SystemParam)]
+pub struct EditorCommandReaders<'w, 's> {
+    save: MessageReader<'w, 's, RequestSaveEvent>,
+    load: MessageReader<'w, 's, RequestLoadEvent>,
+    reload: MessageReader<'w, 's, RequestReloadEvent>,
+    spawn: MessageReader<'w, 's, UserRequestGraniteTypeViaPopup>,
+    transform: MessageReader<'w, 's, UserUpdatedTransformEvent>,
+    identity: MessageReader<'w, 's, UserUpdatedIdentityEvent>,
+    components: MessageReader<'w, 's, UserUpdatedComponentsEvent>,
+    parent: MessageReader<'w, 's, RequestNewParent>,
+    remove_parent: MessageReader<'w, 's, RequestRemoveParents>,
+    despawn_all: MessageReader<'w, 's, RequestDespawnSerializableEntities>,
+    popup: MessageReader<'w, 's, PopupMenuRequestedEvent>,
+    frame: MessageReader<'w, 's, RequestCameraEntityFrame>,
+    thumbnail: MessageReader<'w, 's, RequestSceneThumbnail>,
+    cubemap: MessageReader<'w, 's, RequestCubemapCapture>,
+    imposters: MessageReader<'w, 's, RequestBakeImposters>,
+}
+
+impl EditorCommandReaders<'_, '_> {
+    /// Commands sent since the last call
+    pub fn read(&mut self) -> Vec<EditorCommand> {
+        let scene = |name, path: &String| EditorCommand {
+            name,
+            scene: Some(absolute_asset_to_rel(path.clone()).to_string()),
+        };
+        let mut commands: Vec<EditorCommand> = Vec::new();
+        commands.extend(self.save.read().map(|event| scene("save", &event.0)));
+        commands.extend(self.load.read().map(|event| scene("load", &event.0)));
+        commands.extend(self.reload.read().map(|event| scene("reload", &event.0)));
+
+        let counts = [
+            ("spawn_class", self.spawn.read().count()),
+            ("edit_transform", self.transform.read().count()),
+            ("edit_identity", self.identity.read().count()),
+            ("edit_components", self.components.read().count()),
+            ("set_parent", self.parent.read().count()),
+            ("remove_parent", self.remove_parent.read().count()),
+            ("despawn_all", self.despawn_all.read().count()),
+            ("open_popup", self.popup.read().count()),
+            ("frame_selection", self.frame.read().count()),
+            ("scene_thumbnail", self.thumbnail.read().count()),
+            ("cubemap_capture", self.cubemap.read().count()),
+            ("bake_imposters", self.imposters.read().count()),
+        ];
+        for (name, count) in counts {
+            commands.extend((0..count).map(|_| EditorCommand { name, scene: None }));
+        }
+        commands
+    }
+}
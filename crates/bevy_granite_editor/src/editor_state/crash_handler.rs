@@ -0,0 +1,194 @@
+use crate::editor_state::{EditorCommand, EditorCommandReaders};
+use bevy::{
+    asset::io::file::FileAssetReader,
+    ecs::{
+        lifecycle::RemovedComponents,
+        query::{Changed, Or},
+        world::World,
+    },
+    prelude::{Query, Res, ResMut, Resource, Transform, With},
+    time::{Real, Time},
+};
+use bevy_granite_core::{scene_to_string, GraniteSnapshot, IdentityData};
+use bevy_granite_logging::LOG_BUFFER;
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fs,
+    panic::PanicHookInfo,
+    path::PathBuf,
+    sync::{LazyLock, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+// crash_handler.rs
+// Panic hook that writes a crash report before the editor goes down
+// The hook can't reach the World, so the scene snapshot and recent commands are kept in a static, refreshed while editing
+// A report is a folder under crash_reports/ with panic.txt, commands.txt, log.txt and one .scene per loaded scene
+
+pub const CRASH_REPORT_DIR: &str = "crash_reports";
+const RECENT_COMMANDS: usize = 50;
+const LOG_TAIL: usize = 200;
+/// Seconds between scene snapshots while the scene keeps changing
+const SNAPSHOT_INTERVAL: f32 = 5.0;
+
+type ChangedSerializableFilter = (
+    With<IdentityData>,
+    Or<(Changed<IdentityData>, Changed<Transform>)>,
+);
+
+#[derive(Default)]
+struct CrashContext {
+    /// Scene source and its serialized entities, as of the last snapshot
+    scenes: Vec<(String, String)>,
+    commands: VecDeque<String>,
+}
+
+static CRASH_CONTEXT: LazyLock<Mutex<CrashContext>> =
+    LazyLock::new(|| Mutex::new(CrashContext::default()));
+
+/// Writes crash_reports/crash_<timestamp>/ in the project root when the editor panics
+#[derive(Resource)]
+pub struct CrashHandler {
+    /// Keep serialized scenes ready for a crash report. Costs a snapshot every few seconds while editing
+    pub snapshot_scenes: bool,
+    dirty: bool,
+    since_snapshot: f32,
+}
+
+impl Default for CrashHandler {
+    fn default() -> Self {
+        Self {
+            snapshot_scenes: true,
+            dirty: true,
+            since_snapshot: SNAPSHOT_INTERVAL,
+        }
+    }
+}
+
+/// Chain a crash report writer in front of the current panic hook
+pub fn install_crash_handler_system() {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        write_crash_report(info);
+        previous(info);
+    }));
+}
+
+/// Remembers the last commands and notes when serializable entities change
+pub fn track_crash_context_system(
+    mut handler: ResMut<CrashHandler>,
+    mut readers: EditorCommandReaders,
+    changed: Query<(), ChangedSerializableFilter>,
+    mut removed: RemovedComponents<IdentityData>,
+    time: Res<Time<Real>>,
+) {
+    let commands = readers.read();
+    if !commands.is_empty() {
+        if let Ok(mut context) = CRASH_CONTEXT.lock() {
+            let elapsed = time.elapsed_secs();
+            for EditorCommand { name, scene } in commands {
+                context.commands.push_back(match scene {
+                    Some(scene) => format!("[{:.1}s] {} {}", elapsed, name, scene),
+                    None => format!("[{:.1}s] {}", elapsed, name),
+                });
+            }
+            let overflow = context.commands.len().saturating_sub(RECENT_COMMANDS);
+            context.commands.drain(..overflow);
+        }
+    }
+
+    let changed = !changed.is_empty() || removed.read().count() > 0;
+    if changed && !handler.dirty {
+        handler.dirty = true;
+    }
+    handler.since_snapshot += time.delta_secs();
+}
+
+/// Serializes the loaded scenes for the crash report, at most every few seconds and only after changes
+pub fn snapshot_crash_context_system(world: &mut World) {
+    let Some(handler) = world.get_resource::<CrashHandler>() else {
+        return;
+    };
+    if !handler.snapshot_scenes || !handler.dirty || handler.since_snapshot < SNAPSHOT_INTERVAL {
+        return;
+    }
+    if let Some(mut handler) = world.get_resource_mut::<CrashHandler>() {
+        handler.dirty = false;
+        handler.since_snapshot = 0.0;
+    }
+
+    let snapshot = GraniteSnapshot::capture_quiet(world);
+    let mut by_source: BTreeMap<String, Vec<_>> = BTreeMap::new();
+    for entity in snapshot.entities {
+        let source = entity
+            .source
+            .as_ref()
+            .map(|source| source.str_ref().to_string())
+            .unwrap_or_else(|| "unsaved".to_string());
+        by_source.entry(source).or_default().push(entity.data);
+    }
+    let scenes: Vec<(String, String)> = by_source
+        .into_iter()
+        .filter_map(|(source, entities)| {
+            scene_to_string(entities, Vec::new())
+                .ok()
+                .map(|scene| (source, scene))
+        })
+        .collect();
+
+    if let Ok(mut context) = CRASH_CONTEXT.lock() {
+        context.scenes = scenes;
+    }
+}
+
+/// Runs inside the panic hook. Only try_lock, a panic while holding a lock must not deadlock here
+fn write_crash_report(info: &PanicHookInfo) {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+    let folder = FileAssetReader::get_base_path()
+        .join(CRASH_REPORT_DIR)
+        .join(format!("crash_{}", timestamp));
+    if fs::create_dir_all(&folder).is_err() {
+        return;
+    }
+
+    let backtrace = std::backtrace::Backtrace::force_capture();
+    let _ = fs::write(
+        folder.join("panic.txt"),
+        format!("{}\n\nBacktrace:\n{}", info, backtrace),
+    );
+
+    if let Ok(context) = CRASH_CONTEXT.try_lock() {
+        let commands: Vec<&str> = context.commands.iter().map(String::as_str).collect();
+        let _ = fs::write(folder.join("commands.txt"), commands.join("\n"));
+        for (source, scene) in context.scenes.iter() {
+            let _ = fs::write(folder.join(scene_file_name(source)), scene);
+        }
+    }
+
+    if let Ok(buffer) = LOG_BUFFER.try_lock() {
+        let tail: Vec<String> = buffer
+            .iter()
+            .skip(buffer.len().saturating_sub(LOG_TAIL))
+            .map(|entry| {
+                format!(
+                    "{} [{:?}] [{:?}] {}",
+                    entry.timestamp, entry.level, entry.category, entry.message
+                )
+            })
+            .collect();
+        let _ = fs::write(folder.join("log.txt"), tail.join("\n"));
+    }
+
+    eprintln!("Crash report written to {}", folder.display());
+}
+
+/// scenes/level_1.scene becomes scenes_level_1.scene, so every scene sits flat in the report folder
+fn scene_file_name(source: &str) -> PathBuf {
+    let name = source
+        .trim_end_matches(".scene")
+        .replace(['/', '\\', ':'], "_");
+    PathBuf::from(format!("{}.scene", name))
+}
@@ -0,0 +1,241 @@
+use crate::{
+    interface::events::RequestCubemapCapture,
+    viewport::{camera::LAYER_SCENE, ViewportCameraState},
+};
+use bevy::{
+    asset::{Assets, Handle},
+    camera::{
+        visibility::RenderLayers, Camera, Camera3d, PerspectiveProjection, Projection, RenderTarget,
+    },
+    core_pipeline::tonemapping::Tonemapping,
+    ecs::{observer::On, resource::Resource},
+    image::Image,
+    prelude::{
+        Commands, Entity, GlobalTransform, MessageReader, Name, Query, Res, ResMut, Transform,
+        Vec3, With,
+    },
+    render::{
+        render_resource::TextureFormat,
+        view::screenshot::{Screenshot, ScreenshotCaptured},
+    },
+};
+use bevy_granite_core::{
+    EditorCapabilities, EditorIgnore, GraniteJobs, JobContext, TreeHiddenEntity,
+};
+use bevy_granite_gizmos::ActiveSelection;
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+// cubemap_capture.rs
+// Renders the scene around a point into the six faces of a cubemap, for skyboxes and reflection probes
+// Saved as a vertical strip PNG Bevy loads as a cubemap, plus one PNG per face, i.e. sky.png, sky_px.png ... sky_nz.png
+
+const CUBEMAP_FACE_SIZE: u32 = 512;
+/// Frames the capture cameras render before reading back, so shadows and late assets settle
+const CAPTURE_DELAY_FRAMES: u32 = 3;
+
+/// Face suffix, world look direction and up of each face, in the layer order of a Bevy cubemap
+/// Bevy samples cubemaps with z flipped, so the +Z face looks down world -Z
+const CUBEMAP_FACES: [(&str, Vec3, Vec3); 6] = [
+    ("px", Vec3::X, Vec3::Y),
+    ("nx", Vec3::NEG_X, Vec3::Y),
+    ("py", Vec3::Y, Vec3::Z),
+    ("ny", Vec3::NEG_Y, Vec3::NEG_Z),
+    ("pz", Vec3::NEG_Z, Vec3::Y),
+    ("nz", Vec3::Z, Vec3::Y),
+];
+
+type CapturedFaces = Arc<Mutex<[Option<Image>; 6]>>;
+
+struct PendingCubemap {
+    path: PathBuf,
+    cameras: Vec<Entity>,
+    images: Vec<Handle<Image>>,
+    frames: u32,
+    faces: CapturedFaces,
+}
+
+#[derive(Resource, Default)]
+pub struct PendingCubemapCaptures(Vec<PendingCubemap>);
+
+/// Spawn six capture cameras at the active selection, or at the viewport camera when nothing is selected
+pub fn start_cubemap_capture_system(
+    mut commands: Commands,
+    mut reader: MessageReader<RequestCubemapCapture>,
+    mut images: ResMut<Assets<Image>>,
+    mut pending: ResMut<PendingCubemapCaptures>,
+    active_selection: Query<&GlobalTransform, With<ActiveSelection>>,
+    viewport_camera_state: Res<ViewportCameraState>,
+    capabilities: Res<EditorCapabilities>,
+    transforms: Query<&GlobalTransform>,
+) {
+    for RequestCubemapCapture(path) in reader.read() {
+        // Writes new image files into the assets
+        if !capabilities.can_use_files(path) {
+            continue;
+        }
+        let position = active_selection
+            .single()
+            .ok()
+            .or_else(|| {
+                viewport_camera_state
+                    .active_camera()
+                    .and_then(|camera| transforms.get(camera).ok())
+            })
+            .map(|transform| transform.translation())
+            .unwrap_or(Vec3::ZERO);
+
+        let mut cameras = Vec::with_capacity(CUBEMAP_FACES.len());
+        let mut handles = Vec::with_capacity(CUBEMAP_FACES.len());
+        for (suffix, direction, up) in CUBEMAP_FACES {
+            let handle = images.add(Image::new_target_texture(
+                CUBEMAP_FACE_SIZE,
+                CUBEMAP_FACE_SIZE,
+                TextureFormat::Rgba8UnormSrgb,
+                None,
+            ));
+            let camera = commands
+                .spawn((
+                    Transform::from_translation(position).looking_to(direction, up),
+                    Camera3d::default(),
+                    Camera {
+                        order: -1,
+                        ..Default::default()
+                    },
+                    RenderTarget::Image(handle.clone().into()),
+                    Projection::Perspective(PerspectiveProjection {
+                        fov: std::f32::consts::FRAC_PI_2,
+                        aspect_ratio: 1.0,
+                        ..Default::default()
+                    }),
+                    Tonemapping::None,
+                    RenderLayers::layer(LAYER_SCENE),
+                    Name::new(format!("Cubemap Capture Camera ({})", suffix)),
+                    TreeHiddenEntity,
+                    EditorIgnore::PICKING,
+                ))
+                .id();
+            cameras.push(camera);
+            handles.push(handle);
+        }
+
+        log!(
+            LogType::Editor,
+            LogLevel::Info,
+            LogCategory::System,
+            "Capturing cubemap at {:?} to '{}'",
+            position,
+            path
+        );
+        pending.0.push(PendingCubemap {
+            path: PathBuf::from(path),
+            cameras,
+            images: handles,
+            frames: 0,
+            faces: Arc::new(Mutex::new(Default::default())),
+        });
+    }
+}
+
+/// Read the faces back once the cameras had a few frames, then queue the file writes and clean the cameras up
+pub fn finish_cubemap_capture_system(
+    mut commands: Commands,
+    mut pending: ResMut<PendingCubemapCaptures>,
+    mut jobs: ResMut<GraniteJobs>,
+) {
+    pending.0.retain_mut(|capture| {
+        capture.frames += 1;
+        if capture.frames == CAPTURE_DELAY_FRAMES {
+            for (index, handle) in capture.images.iter().enumerate() {
+                let faces = capture.faces.clone();
+                commands.spawn(Screenshot::image(handle.clone())).observe(
+                    move |captured: On<ScreenshotCaptured>| {
+                        if let Ok(mut faces) = faces.lock() {
+                            faces[index] = Some(captured.image.clone());
+                        }
+                    },
+                );
+            }
+            return true;
+        }
+
+        let Ok(faces) = capture.faces.lock() else {
+            return true;
+        };
+        if faces.iter().any(Option::is_none) {
+            return true;
+        }
+
+        let faces = faces.clone();
+        let path = capture.path.clone();
+        jobs.spawn(
+            "cubemap",
+            format!("Cubemap {}", path.display()),
+            move |context| {
+                save_cubemap(&faces, &path, context);
+                Ok(Vec::new())
+            },
+        );
+        for camera in capture.cameras.iter() {
+            commands.entity(*camera).despawn();
+        }
+        false
+    });
+}
+
+fn save_cubemap(faces: &[Option<Image>; 6], path: &Path, context: &JobContext) {
+    let result = faces
+        .iter()
+        .flatten()
+        .map(|face| {
+            if context.is_cancelled() {
+                return Err("Cancelled".to_string());
+            }
+            face.clone()
+                .try_into_dynamic()
+                .map(|dynamic| dynamic.to_rgba8())
+                .map_err(|e| e.to_string())
+        })
+        .collect::<Result<Vec<_>, String>>()
+        .and_then(|faces| {
+            let stem = path.with_extension("");
+            let mut strip = image::RgbaImage::new(CUBEMAP_FACE_SIZE, CUBEMAP_FACE_SIZE * 6);
+            for ((suffix, ..), (index, face)) in CUBEMAP_FACES.iter().zip(faces.iter().enumerate())
+            {
+                image::imageops::replace(
+                    &mut strip,
+                    face,
+                    0,
+                    (CUBEMAP_FACE_SIZE as usize * index) as i64,
+                );
+                face.save(format!("{}_{}.png", stem.display(), suffix))
+                    .map_err(|e| e.to_string())?;
+                context.set_progress((index + 1) as f32 / 7.0);
+            }
+            strip.save(path).map_err(|e| e.to_string())
+        });
+
+    match result {
+        Ok(()) => log!(
+            LogType::Editor,
+            LogLevel::OK,
+            LogCategory::System,
+            "Saved cubemap: {}",
+            path.display()
+        ),
+        Err(e) => log!(
+            LogType::Editor,
+            LogLevel::Error,
+            LogCategory::System,
+            "Failed to save cubemap '{}': {}",
+            path.display(),
+            e
+        ),
+    }
+}
@@ -3,7 +3,7 @@ use crate::{
     editor_state::EditorState,
     interface::{BottomDockState, EditorSettingsTabData, SideDockState, SideTab},
 };
-use bevy::time::Time;
+use bevy::time::{Real, Time};
 use bevy::window::WindowClosing;
 use bevy::{
     asset::io::file::FileAssetReader,
@@ -63,7 +63,7 @@ pub fn save_dock_on_window_close_system(
 
 /// System that automatically saves dock layout every minute
 pub fn auto_save_dock_layout_system(
-    time: Res<Time>,
+    time: Res<Time<Real>>,
     editor_state: Res<EditorState>,
     side_dock_res: Res<SideDockState>,
     bottom_dock_res: Res<BottomDockState>,
@@ -5,20 +5,24 @@ use crate::{
 
 use crate::utils::{load_from_toml_file, save_to_toml_file};
 use bevy::ecs::message::MessageReader;
-use bevy::{asset::io::file::FileAssetReader, prelude::ResMut};
+use bevy::{
+    asset::io::file::FileAssetReader,
+    prelude::{DetectChanges, Res, ResMut},
+};
 use bevy_granite_core::{
     absolute_asset_to_rel,
     events::{
         RequestDespawnBySource, RequestDespawnSerializableEntities, WorldLoadSuccessEvent,
         WorldSaveSuccessEvent,
     },
+    StartupWorlds,
 };
 use bevy_granite_logging::{
     config::{LogCategory, LogLevel, LogType},
     log,
 };
 
-use crate::interface::RequestEditorToggle;
+use crate::interface::{RequestEditorOverlayToggle, RequestEditorToggle};
 use bevy::prelude::Commands;
 use bevy_granite_gizmos::{selection::events::EntityEvents, GizmoVisibilityState};
 
@@ -26,6 +30,21 @@ use bevy_granite_gizmos::{selection::events::EntityEvents, GizmoVisibilityState}
 // This has functions related to saving the editor settings
 // Currently the settings data is coming directly from the right Tab settings.
 
+/// Keep the default world pointed at the active startup world when it is switched at runtime
+pub fn sync_startup_world_system(
+    startup_worlds: Res<StartupWorlds>,
+    mut editor_state: ResMut<EditorState>,
+) {
+    if !startup_worlds.is_changed() {
+        return;
+    }
+    if let Some(path) = startup_worlds.active_path() {
+        if editor_state.default_world != path {
+            editor_state.default_world = path.to_string();
+        }
+    }
+}
+
 pub fn update_active_world_system(
     mut open_success_reader: MessageReader<WorldLoadSuccessEvent>,
     mut world_save_success_reader: MessageReader<WorldSaveSuccessEvent>,
@@ -34,7 +53,11 @@ pub fn update_active_world_system(
     mut set_active_world_reader: MessageReader<SetActiveWorld>,
     mut editor_state: ResMut<EditorState>,
 ) {
-    for RequestDespawnSerializableEntities in entities_despawned_reader.read() {
+    for RequestDespawnSerializableEntities(filter) in entities_despawned_reader.read() {
+        // Filtered despawns leave the sources loaded
+        if !filter.is_complete() {
+            continue;
+        }
         editor_state.current_file = None;
         editor_state.loaded_sources.clear();
         log!(
@@ -45,7 +68,10 @@ pub fn update_active_world_system(
         );
     }
 
-    for RequestDespawnBySource(source) in entities_despawned_by_source_reader.read() {
+    for RequestDespawnBySource(source, filter) in entities_despawned_by_source_reader.read() {
+        if !filter.is_complete() {
+            continue;
+        }
         editor_state.loaded_sources.remove(source);
 
         // If the current file was despawned, clear it
@@ -106,6 +132,7 @@ pub fn update_active_world_system(
 
 pub fn update_editor_vis_system(
     mut toggle_reader: MessageReader<RequestEditorToggle>,
+    mut overlay_reader: MessageReader<RequestEditorOverlayToggle>,
     mut editor_state: ResMut<EditorState>,
     mut gizmo_state: ResMut<GizmoVisibilityState>,
     mut commands: Commands,
@@ -117,6 +144,24 @@ pub fn update_editor_vis_system(
         editor_state.active = !editor_state.active;
         gizmo_state.active = editor_state.active;
     }
+
+    for RequestEditorOverlayToggle in overlay_reader.read() {
+        if editor_state.active {
+            editor_state.overlay = !editor_state.overlay;
+        } else {
+            editor_state.active = true;
+            editor_state.overlay = true;
+            gizmo_state.active = true;
+        }
+
+        log!(
+            LogType::Editor,
+            LogLevel::Info,
+            LogCategory::UI,
+            "Editor overlay: {}",
+            editor_state.overlay
+        );
+    }
 }
 
 // FIX: Probably need to decouple this from the UI tab. Sep res for editor data and keep them synced.
@@ -0,0 +1,63 @@
+use crate::interface::{popups::PopupType, PopupMenuRequestedEvent};
+use bevy::{
+    math::Vec2,
+    prelude::{MessageReader, MessageWriter, Resource, World},
+};
+use bevy_granite_core::{
+    diff_scene_entities, read_scene_entities, rel_asset_to_absolute, GraniteSnapshot,
+    SceneEntityDiff, SceneFileChangedEvent,
+};
+use std::collections::BTreeMap;
+
+// external_changes.rs
+// Prompt for scenes changed on disk while loaded, i.e. by a git checkout. The core SceneFileWatcher finds them
+// and refuses to save over them, here the user picks between reloading, keeping the loaded scene or a diff first
+
+/// Diffs of changed scenes against the loaded ones, by relative path
+#[derive(Resource, Default, Debug, Clone)]
+pub struct ExternalSceneChanges {
+    /// Scenes to diff at the end of the frame, a diff needs the whole world
+    pub diff_requests: Vec<String>,
+    pub diffs: BTreeMap<String, Vec<SceneEntityDiff>>,
+}
+
+/// Opens the prompt when a loaded scene changes on disk
+pub fn check_external_scene_changes_system(
+    mut changed_reader: MessageReader<SceneFileChangedEvent>,
+    mut popup_writer: MessageWriter<PopupMenuRequestedEvent>,
+) {
+    if changed_reader.read().count() > 0 {
+        popup_writer.write(PopupMenuRequestedEvent {
+            popup: PopupType::SceneFileChanged,
+            mouse_pos: Vec2::ZERO,
+        });
+    }
+}
+
+/// Compares the requested scene files against their loaded entities
+pub fn build_scene_diffs_system(world: &mut World) {
+    let requests = std::mem::take(&mut world.resource_mut::<ExternalSceneChanges>().diff_requests);
+    if requests.is_empty() {
+        return;
+    }
+
+    let snapshot = GraniteSnapshot::capture_quiet(world);
+    let mut changes = world.resource_mut::<ExternalSceneChanges>();
+    for scene in requests {
+        let disk = read_scene_entities(&rel_asset_to_absolute(&scene));
+        let loaded: Vec<_> = snapshot
+            .entities
+            .iter()
+            .filter(|entity| {
+                entity
+                    .source
+                    .as_ref()
+                    .is_some_and(|source| source.str_ref() == scene)
+            })
+            .map(|entity| entity.data.clone())
+            .collect();
+        changes
+            .diffs
+            .insert(scene, diff_scene_entities(&disk, &loaded));
+    }
+}
@@ -0,0 +1,444 @@
+use crate::{
+    editor_state::EditorState,
+    interface::events::{RequestRedo, RequestUndo, UserUpdatedComponentsEvent},
+};
+use bevy::{
+    ecs::{
+        lifecycle::RemovedComponents,
+        query::{Changed, Or},
+        system::{SystemParam, SystemState},
+        world::World,
+    },
+    input::{mouse::MouseButton, ButtonInput},
+    prelude::{ChildOf, Entity, MessageReader, Mut, Query, Resource, Transform, With},
+    time::{Real, Time},
+};
+use bevy_granite_core::{
+    EditorCapabilities, GraniteSnapshot, IdentityData, RequestDespawnBySource,
+    RequestDespawnSerializableEntities, RequestLoadEvent, RequestReloadEvent, SnapshotEntity,
+    WorldLoadSuccessEvent,
+};
+use bevy_granite_gizmos::{selection::events::EntityEvents, Selected};
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+use std::collections::{HashMap, VecDeque};
+use uuid::Uuid;
+
+// history.rs
+// Undo and redo for editor operations
+// Edits aren't recorded by each tool: the last known state of every serializable entity is kept by uuid,
+// and whatever changed between two quiet moments (a gizmo drag, a field edit, a spawn, a delete) becomes one undo step
+// Loads, despawns of whole scenes and the undo itself only refresh the known states, they are never recorded
+
+/// Most steps kept before the oldest are dropped
+const HISTORY_LIMIT: usize = 100;
+/// Frames after a load or an undo where changes are taken as the new state instead of recorded
+pub(crate) const QUIET_FRAMES: u32 = 3;
+/// Seconds a step may keep collecting changes while nothing is held, so constant changes still end up as steps
+const MAX_STEP_AGE: f32 = 0.5;
+
+/// Something the editor can take back and do again
+pub trait UndoableCommand: Send + Sync + 'static {
+    fn label(&self) -> &str;
+    fn undo(&self, world: &mut World);
+    fn redo(&self, world: &mut World);
+}
+
+/// Entity states by uuid before and after an edit. None is an entity that didn't exist
+pub struct EntityStateCommand {
+    pub label: String,
+    pub before: Vec<(Uuid, Option<SnapshotEntity>)>,
+    pub after: Vec<(Uuid, Option<SnapshotEntity>)>,
+}
+
+impl UndoableCommand for EntityStateCommand {
+    fn label(&self) -> &str {
+        &self.label
+    }
+
+    fn undo(&self, world: &mut World) {
+        restore_keeping_selection(world, &self.before);
+    }
+
+    fn redo(&self, world: &mut World) {
+        restore_keeping_selection(world, &self.after);
+    }
+}
+
+type StateList = [(Uuid, Option<SnapshotEntity>)];
+
+/// Restored entities may be respawned, select the new ones if the old ones were selected
+fn restore_keeping_selection(world: &mut World, states: &StateList) {
+    let mut selected: SystemState<Query<&IdentityData, With<Selected>>> = SystemState::new(world);
+    let was_selected: Vec<Uuid> = selected
+        .get(world)
+        .iter()
+        .map(|identity| identity.uuid)
+        .filter(|uuid| states.iter().any(|(restored, _)| restored == uuid))
+        .collect();
+
+    let entities = GraniteSnapshot::restore_entities(world, states);
+    for uuid in was_selected {
+        if let Some(target) = entities.get(&uuid) {
+            if !world.entity(*target).contains::<Selected>() {
+                world.trigger(EntityEvents::Select {
+                    target: *target,
+                    additive: true,
+                });
+            }
+        }
+    }
+}
+
+/// Undo and redo stacks of the editor, `Ctrl + Z` and `Ctrl + Shift + Z`. The History tab lists the last steps
+/// Custom operations join the same stack by implementing UndoableCommand and pushing them here
+#[derive(Resource)]
+pub struct EditorHistory {
+    undo: VecDeque<Box<dyn UndoableCommand>>,
+    redo: Vec<Box<dyn UndoableCommand>>,
+    /// Last known state of every serializable entity
+    known: HashMap<Uuid, SnapshotEntity>,
+    uuids: HashMap<Entity, Uuid>,
+    /// States from before the changes of the step being collected
+    step: HashMap<Uuid, Option<SnapshotEntity>>,
+    step_age: f32,
+    quiet_frames: u32,
+    /// Known states are out of date, i.e. the editor was closed while the game ran
+    stale: bool,
+}
+
+impl Default for EditorHistory {
+    fn default() -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            known: HashMap::new(),
+            uuids: HashMap::new(),
+            step: HashMap::new(),
+            step_age: 0.0,
+            quiet_frames: 0,
+            stale: true,
+        }
+    }
+}
+
+impl EditorHistory {
+    /// Record a command that already ran. Clears the redo steps
+    pub fn push(&mut self, command: Box<dyn UndoableCommand>) {
+        self.undo.push_back(command);
+        if self.undo.len() > HISTORY_LIMIT {
+            self.undo.pop_front();
+        }
+        self.redo.clear();
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Undo step labels, newest first
+    pub fn undo_labels(&self) -> Vec<String> {
+        self.undo
+            .iter()
+            .rev()
+            .map(|c| c.label().to_string())
+            .collect()
+    }
+
+    /// Redo step labels, next first
+    pub fn redo_labels(&self) -> Vec<String> {
+        self.redo
+            .iter()
+            .rev()
+            .map(|c| c.label().to_string())
+            .collect()
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.step.clear();
+    }
+
+    /// Take what changed since the last known states as the new known states, without recording it
+    fn remember(&mut self, states: Vec<SnapshotEntity>, entities: &[Entity]) {
+        for (entity, state) in entities.iter().zip(states) {
+            self.uuids.insert(*entity, state.data.identity.uuid);
+            self.known.insert(state.data.identity.uuid, state);
+        }
+    }
+
+    fn forget(&mut self, entity: Entity) {
+        if let Some(uuid) = self.uuids.remove(&entity) {
+            self.known.remove(&uuid);
+        }
+    }
+
+    /// Turn the collected changes into an undo step, dropping entities that ended where they started
+    fn finish_step(&mut self) {
+        let step = std::mem::take(&mut self.step);
+        self.step_age = 0.0;
+        let mut before = Vec::new();
+        let mut after = Vec::new();
+        for (uuid, old) in step {
+            let new = self.known.get(&uuid).cloned();
+            if old != new {
+                before.push((uuid, old));
+                after.push((uuid, new));
+            }
+        }
+        if before.is_empty() {
+            return;
+        }
+        let label = step_label(&before, &after);
+        log!(
+            LogType::Editor,
+            LogLevel::Info,
+            LogCategory::System,
+            "(history) {}",
+            label
+        );
+        self.push(Box::new(EntityStateCommand {
+            label,
+            before,
+            after,
+        }));
+    }
+}
+
+/// Describes a step by its biggest change, i.e. "Delete 'Crate'" or "Transform 3 entities"
+fn step_label(before: &StateList, after: &StateList) -> String {
+    let name = |states: &StateList| {
+        states
+            .iter()
+            .find_map(|(_, state)| state.as_ref())
+            .map(|state| state.data.identity.name.clone())
+            .unwrap_or_default()
+    };
+    let target = |count: usize, name: String| {
+        if count == 1 {
+            format!("'{}'", name)
+        } else {
+            format!("{} entities", count)
+        }
+    };
+
+    let spawned = before.iter().filter(|(_, state)| state.is_none()).count();
+    if spawned > 0 {
+        return format!("Spawn {}", target(spawned, name(after)));
+    }
+    let deleted = after.iter().filter(|(_, state)| state.is_none()).count();
+    if deleted > 0 {
+        return format!("Delete {}", target(deleted, name(before)));
+    }
+
+    let count = before.len();
+    let changed = |check: fn(&SnapshotEntity, &SnapshotEntity) -> bool| {
+        before
+            .iter()
+            .zip(after)
+            .any(|((_, old), (_, new))| match (old, new) {
+                (Some(old), Some(new)) => check(old, new),
+                _ => false,
+            })
+    };
+    let action = if changed(|old, new| old.data.parent != new.data.parent) {
+        "Reparent"
+    } else if changed(|old, new| old.data.identity.class != new.data.identity.class) {
+        "Edit"
+    } else if changed(|old, new| old.data.identity != new.data.identity) {
+        "Rename"
+    } else if changed(|old, new| old.data.components != new.data.components) {
+        "Edit components of"
+    } else {
+        "Transform"
+    };
+    format!("{} {}", action, target(count, name(before)))
+}
+
+type ChangedEntitiesFilter = (
+    With<IdentityData>,
+    Or<(Changed<IdentityData>, Changed<Transform>, Changed<ChildOf>)>,
+);
+
+type HistoryParams<'w, 's> = (
+    Query<'w, 's, Entity, ChangedEntitiesFilter>,
+    RemovedComponents<'w, 's, IdentityData>,
+    RemovedComponents<'w, 's, ChildOf>,
+    MessageReader<'w, 's, UserUpdatedComponentsEvent>,
+    HistoryQuietReaders<'w, 's>,
+);
+
+#[derive(SystemParam)]
+pub struct HistoryQuietReaders<'w, 's> {
+    load: MessageReader<'w, 's, RequestLoadEvent>,
+    loaded: MessageReader<'w, 's, WorldLoadSuccessEvent>,
+    reload: MessageReader<'w, 's, RequestReloadEvent>,
+    despawn_all: MessageReader<'w, 's, RequestDespawnSerializableEntities>,
+    despawn_source: MessageReader<'w, 's, RequestDespawnBySource>,
+}
+
+impl HistoryQuietReaders<'_, '_> {
+    pub(crate) fn any(&mut self) -> bool {
+        // Read every reader, so none is left holding old messages
+        [
+            self.load.read().count(),
+            self.loaded.read().count(),
+            self.reload.read().count(),
+            self.despawn_all.read().count(),
+            self.despawn_source.read().count(),
+        ]
+        .iter()
+        .any(|count| *count > 0)
+    }
+}
+
+/// Collects changed entities into undo steps. A step ends once nothing changed for a frame and no mouse button is held
+pub fn record_history_system(world: &mut World, state: &mut SystemState<HistoryParams>) {
+    let (changed, removed, quiet) = {
+        let (changed_query, mut removed_identity, mut removed_parent, mut components, mut quiet) =
+            state.get_mut(world);
+        let mut changed: Vec<Entity> = changed_query.iter().collect();
+        changed.extend(removed_parent.read());
+        changed.extend(components.read().map(|event| event.entity));
+        let removed: Vec<Entity> = removed_identity.read().collect();
+        (changed, removed, quiet.any())
+    };
+
+    let active = world.resource::<EditorState>().active;
+    let delta = world.resource::<Time<Real>>().delta_secs();
+    let held = world
+        .get_resource::<ButtonInput<MouseButton>>()
+        .is_some_and(|buttons| buttons.get_pressed().next().is_some());
+
+    world.resource_scope(|world, mut history: Mut<EditorHistory>| {
+        if !active {
+            history.stale = true;
+            return;
+        }
+        if history.stale {
+            let snapshot = GraniteSnapshot::capture_quiet(world);
+            let mut query = world.query::<(Entity, &IdentityData)>();
+            history.uuids = query
+                .iter(world)
+                .map(|(entity, identity)| (entity, identity.uuid))
+                .collect();
+            history.known = snapshot
+                .entities
+                .into_iter()
+                .map(|entity| (entity.data.identity.uuid, entity))
+                .collect();
+            history.step.clear();
+            history.stale = false;
+            return;
+        }
+
+        if quiet {
+            history.quiet_frames = QUIET_FRAMES;
+            if !history.step.is_empty() {
+                history.finish_step();
+            }
+        }
+        let recording = history.quiet_frames == 0;
+        history.quiet_frames = history.quiet_frames.saturating_sub(1);
+
+        let mut changed = changed;
+        changed.sort();
+        changed.dedup();
+        let touched = !changed.is_empty() || !removed.is_empty();
+
+        for entity in removed {
+            let uuid = history.uuids.get(&entity).copied();
+            let old = uuid.and_then(|uuid| history.known.get(&uuid).cloned());
+            if let (true, Some(uuid)) = (recording, uuid) {
+                history.step.entry(uuid).or_insert(old);
+            }
+            history.forget(entity);
+        }
+
+        let states = GraniteSnapshot::capture_entities(world, &changed);
+        if recording {
+            for state in states.iter() {
+                let uuid = state.data.identity.uuid;
+                let old = history.known.get(&uuid).cloned();
+                history.step.entry(uuid).or_insert(old);
+            }
+        }
+        let entities: Vec<Entity> = changed
+            .iter()
+            .copied()
+            .filter(|entity| world.get::<IdentityData>(*entity).is_some())
+            .collect();
+        history.remember(states, &entities);
+
+        if history.step.is_empty() {
+            return;
+        }
+        history.step_age += delta;
+        if !held && (!touched || history.step_age > MAX_STEP_AGE) {
+            history.finish_step();
+        }
+    });
+}
+
+/// Runs undo and redo requests
+pub fn apply_history_system(
+    world: &mut World,
+    state: &mut SystemState<(MessageReader<RequestUndo>, MessageReader<RequestRedo>)>,
+) {
+    let (undo, redo) = {
+        let (mut undo, mut redo) = state.get_mut(world);
+        (undo.read().count(), redo.read().count())
+    };
+    if undo == 0 && redo == 0 {
+        return;
+    }
+    // Undo and redo can change, spawn and despawn entities, so they count as edits
+    let action = if undo > 0 { "undo" } else { "redo" };
+    if !world
+        .resource::<EditorCapabilities>()
+        .can_edit_components(action)
+    {
+        return;
+    }
+
+    world.resource_scope(|world, mut history: Mut<EditorHistory>| {
+        // Finish what's being collected first, so it's the step that gets undone
+        history.finish_step();
+        for _ in 0..undo {
+            let Some(command) = history.undo.pop_back() else {
+                break;
+            };
+            command.undo(world);
+            log!(
+                LogType::Editor,
+                LogLevel::Info,
+                LogCategory::System,
+                "(history) Undo {}",
+                command.label()
+            );
+            history.redo.push(command);
+        }
+        for _ in 0..redo {
+            let Some(command) = history.redo.pop() else {
+                break;
+            };
+            command.redo(world);
+            log!(
+                LogType::Editor,
+                LogLevel::Info,
+                LogCategory::System,
+                "(history) Redo {}",
+                command.label()
+            );
+            history.undo.push_back(command);
+        }
+        history.quiet_frames = QUIET_FRAMES;
+    });
+}
@@ -0,0 +1,325 @@
+use crate::{interface::events::RequestBakeImposters, viewport::camera::LAYER_IMPOSTER_BAKE};
+use bevy::{
+    asset::{AssetServer, Assets, Handle},
+    camera::{
+        primitives::Aabb, visibility::RenderLayers, Camera, Camera3d, ClearColorConfig,
+        OrthographicProjection, Projection, RenderTarget, ScalingMode,
+    },
+    color::Color,
+    core_pipeline::tonemapping::Tonemapping,
+    ecs::{hierarchy::Children, observer::On, resource::Resource},
+    image::Image,
+    math::Vec2,
+    mesh::Mesh3d,
+    prelude::{
+        Commands, Entity, GlobalTransform, MessageReader, Name, Query, Res, ResMut, Transform,
+        Vec3, With, Without,
+    },
+    render::{
+        render_resource::TextureFormat,
+        view::screenshot::{Screenshot, ScreenshotCaptured},
+    },
+};
+use bevy_granite_core::{
+    rel_asset_to_absolute, EditorCapabilities, EditorIgnore, GraniteJobs, IdentityData, Imposter,
+    ImposterBillboard, JobContext, JobFinishedEvent, JobId, TreeHiddenEntity,
+};
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+use std::{
+    collections::HashMap,
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+// imposter_bake.rs
+// Renders selected props from evenly spaced angles around them into one imposter atlas
+// The prop gets an Imposter component pointing at the atlas, so it is saved with the prop and drawn as a billboard far away
+
+const IMPOSTER_VIEW_SIZE: u32 = 256;
+/// Frames the bake cameras render before reading back, so shadows and late assets settle
+const BAKE_DELAY_FRAMES: u32 = 3;
+
+type CapturedViews = Arc<Mutex<Vec<Option<Image>>>>;
+type BakedMesh = (
+    &'static GlobalTransform,
+    &'static Aabb,
+    Option<&'static RenderLayers>,
+);
+
+struct PendingBake {
+    entity: Entity,
+    imposter: Imposter,
+    cameras: Vec<Entity>,
+    images: Vec<Handle<Image>>,
+    /// Meshes moved onto the bake layer, with the layers to give back
+    layered: Vec<(Entity, Option<RenderLayers>)>,
+    frames: u32,
+    views: CapturedViews,
+}
+
+#[derive(Resource, Default)]
+pub struct PendingImposterBakes {
+    bakes: Vec<PendingBake>,
+    /// Atlas writes running as jobs, the Imposter goes on the prop once written
+    writing: HashMap<JobId, (Entity, Imposter)>,
+}
+
+/// Put the meshes of each prop on the bake layer and spawn an orthographic camera per view around them
+pub fn start_imposter_bake_system(
+    mut commands: Commands,
+    mut reader: MessageReader<RequestBakeImposters>,
+    mut images: ResMut<Assets<Image>>,
+    mut pending: ResMut<PendingImposterBakes>,
+    capabilities: Res<EditorCapabilities>,
+    props: Query<(&IdentityData, &GlobalTransform, Option<&Imposter>)>,
+    children_query: Query<&Children>,
+    meshes: Query<BakedMesh, (With<Mesh3d>, Without<ImposterBillboard>)>,
+) {
+    for RequestBakeImposters { entities, views } in reader.read() {
+        for entity in entities.iter().copied() {
+            let Ok((identity, prop_transform, existing)) = props.get(entity) else {
+                continue;
+            };
+            // Writes the atlas image into the assets
+            if !capabilities.can_use_files(&Imposter::atlas_path(&identity.uuid)) {
+                continue;
+            }
+
+            let mut layered = Vec::new();
+            let (mut min, mut max) = (Vec3::splat(f32::MAX), Vec3::splat(f32::MIN));
+            for mesh_entity in
+                std::iter::once(entity).chain(children_query.iter_descendants(entity))
+            {
+                let Ok((transform, aabb, layers)) = meshes.get(mesh_entity) else {
+                    continue;
+                };
+                let (center, half) = (Vec3::from(aabb.center), Vec3::from(aabb.half_extents));
+                for corner in 0..8 {
+                    let sign = Vec3::new(
+                        if corner & 1 == 0 { -1.0 } else { 1.0 },
+                        if corner & 2 == 0 { -1.0 } else { 1.0 },
+                        if corner & 4 == 0 { -1.0 } else { 1.0 },
+                    );
+                    let point = transform.transform_point(center + half * sign);
+                    min = min.min(point);
+                    max = max.max(point);
+                }
+                layered.push((mesh_entity, layers.cloned()));
+            }
+            if layered.is_empty() {
+                log!(
+                    LogType::Editor,
+                    LogLevel::Warning,
+                    LogCategory::Entity,
+                    "'{}' has no meshes to bake into an imposter",
+                    identity.name
+                );
+                continue;
+            }
+
+            for (mesh_entity, layers) in layered.iter() {
+                let layers = layers.clone().unwrap_or_default().with(LAYER_IMPOSTER_BAKE);
+                commands.entity(*mesh_entity).insert(layers);
+            }
+
+            let center = (min + max) * 0.5;
+            let radius = ((max - min).length() * 0.5).max(0.01);
+            let (scale, rotation, _) = prop_transform.to_scale_rotation_translation();
+            let imposter = Imposter {
+                atlas: Imposter::atlas_path(&identity.uuid),
+                views: (*views).max(1),
+                size: Vec2::new(radius * 2.0 / scale.x, radius * 2.0 / scale.y),
+                center: prop_transform.affine().inverse().transform_point3(center),
+                ..existing.cloned().unwrap_or_default()
+            };
+
+            let mut cameras = Vec::with_capacity(imposter.views as usize);
+            let mut handles = Vec::with_capacity(imposter.views as usize);
+            for view in 0..imposter.views {
+                // Views go around the world up axis, starting from the prop's own back
+                let local = imposter.view_direction(view);
+                let mut direction = rotation * local;
+                direction.y = 0.0;
+                let direction = direction.try_normalize().unwrap_or(local);
+
+                let handle = images.add(Image::new_target_texture(
+                    IMPOSTER_VIEW_SIZE,
+                    IMPOSTER_VIEW_SIZE,
+                    TextureFormat::Rgba8UnormSrgb,
+                    None,
+                ));
+                let camera = commands
+                    .spawn((
+                        Transform::from_translation(center + direction * (radius + 1.0))
+                            .looking_at(center, Vec3::Y),
+                        Camera3d::default(),
+                        Camera {
+                            order: -1,
+                            clear_color: ClearColorConfig::Custom(Color::NONE),
+                            ..Default::default()
+                        },
+                        RenderTarget::Image(handle.clone().into()),
+                        Projection::Orthographic(OrthographicProjection {
+                            scaling_mode: ScalingMode::Fixed {
+                                width: radius * 2.0,
+                                height: radius * 2.0,
+                            },
+                            near: 0.0,
+                            far: radius * 2.0 + 2.0,
+                            ..OrthographicProjection::default_3d()
+                        }),
+                        Tonemapping::None,
+                        RenderLayers::layer(LAYER_IMPOSTER_BAKE),
+                        Name::new(format!("Imposter Bake Camera ({})", view)),
+                        TreeHiddenEntity,
+                        EditorIgnore::PICKING,
+                    ))
+                    .id();
+                cameras.push(camera);
+                handles.push(handle);
+            }
+
+            log!(
+                LogType::Editor,
+                LogLevel::Info,
+                LogCategory::Entity,
+                "Baking {} imposter views of '{}' to '{}'",
+                imposter.views,
+                identity.name,
+                imposter.atlas
+            );
+            pending.bakes.push(PendingBake {
+                entity,
+                views: Arc::new(Mutex::new(vec![None; imposter.views as usize])),
+                imposter,
+                cameras,
+                images: handles,
+                layered,
+                frames: 0,
+            });
+        }
+    }
+}
+
+/// Read the views back once the cameras had a few frames, then queue the atlas write and put everything back
+pub fn finish_imposter_bake_system(
+    mut commands: Commands,
+    mut pending: ResMut<PendingImposterBakes>,
+    mut jobs: ResMut<GraniteJobs>,
+) {
+    let mut writing = Vec::new();
+    pending.bakes.retain_mut(|bake| {
+        bake.frames += 1;
+        if bake.frames == BAKE_DELAY_FRAMES {
+            for (index, handle) in bake.images.iter().enumerate() {
+                let views = bake.views.clone();
+                commands.spawn(Screenshot::image(handle.clone())).observe(
+                    move |captured: On<ScreenshotCaptured>| {
+                        if let Ok(mut views) = views.lock() {
+                            views[index] = Some(captured.image.clone());
+                        }
+                    },
+                );
+            }
+            return true;
+        }
+
+        let Ok(views) = bake.views.lock() else {
+            return true;
+        };
+        if views.iter().any(Option::is_none) {
+            return true;
+        }
+
+        let views = views.clone();
+        let path = rel_asset_to_absolute(&bake.imposter.atlas).to_string();
+        let id = jobs.spawn(
+            "imposter",
+            format!("Imposter {}", bake.imposter.atlas),
+            move |context| {
+                save_atlas(&views, Path::new(&path), context)?;
+                Ok(Vec::new())
+            },
+        );
+        writing.push((id, (bake.entity, bake.imposter.clone())));
+
+        for camera in bake.cameras.iter() {
+            commands.entity(*camera).despawn();
+        }
+        for (mesh_entity, layers) in bake.layered.iter() {
+            let mut entity = commands.entity(*mesh_entity);
+            match layers {
+                Some(layers) => entity.try_insert(layers.clone()),
+                None => entity.try_remove::<RenderLayers>(),
+            };
+        }
+        false
+    });
+    pending.writing.extend(writing);
+}
+
+/// Once an atlas is on disk, give its prop the Imposter and reload the atlas if it was baked before
+pub fn apply_baked_imposters_system(
+    mut commands: Commands,
+    mut reader: MessageReader<JobFinishedEvent>,
+    mut pending: ResMut<PendingImposterBakes>,
+    asset_server: Res<AssetServer>,
+) {
+    for event in reader.read() {
+        let Some((entity, imposter)) = pending.writing.remove(&event.id) else {
+            continue;
+        };
+        match &event.result {
+            Ok(_) => {
+                asset_server.reload(imposter.atlas.clone());
+                commands.entity(entity).try_insert(imposter);
+            }
+            Err(e) => log!(
+                LogType::Editor,
+                LogLevel::Error,
+                LogCategory::Entity,
+                "Failed to bake imposter '{}': {}",
+                imposter.atlas,
+                e
+            ),
+        }
+    }
+}
+
+fn save_atlas(views: &[Option<Image>], path: &Path, context: &JobContext) -> Result<(), String> {
+    let mut atlas =
+        image::RgbaImage::new(IMPOSTER_VIEW_SIZE * views.len() as u32, IMPOSTER_VIEW_SIZE);
+    for (index, view) in views.iter().flatten().enumerate() {
+        if context.is_cancelled() {
+            return Err("Cancelled".to_string());
+        }
+        let view = view
+            .clone()
+            .try_into_dynamic()
+            .map_err(|e| e.to_string())?
+            .to_rgba8();
+        image::imageops::replace(
+            &mut atlas,
+            &view,
+            (IMPOSTER_VIEW_SIZE as usize * index) as i64,
+            0,
+        );
+        context.set_progress((index + 1) as f32 / (views.len() + 1) as f32);
+    }
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    atlas.save(path).map_err(|e| e.to_string())?;
+    log!(
+        LogType::Editor,
+        LogLevel::OK,
+        LogCategory::System,
+        "Saved imposter atlas: {}",
+        path.display()
+    );
+    Ok(())
+}
@@ -0,0 +1,231 @@
+use crate::{editor_state::EditorState, viewport::camera::LAYER_MATERIAL_PREVIEW};
+use bevy::{
+    asset::{AssetEvent, AssetId, Assets, Handle},
+    camera::{visibility::RenderLayers, Camera, Camera3d, ClearColorConfig, RenderTarget},
+    color::Color,
+    ecs::change_detection::DetectChanges,
+    image::Image,
+    light::PointLight,
+    math::primitives::Sphere,
+    mesh::{Mesh, Mesh3d, Meshable},
+    pbr::{MeshMaterial3d, StandardMaterial},
+    prelude::{Commands, Entity, MessageReader, Name, Res, ResMut, Resource, Transform, Vec3},
+    render::render_resource::TextureFormat,
+};
+use bevy_egui::{egui, EguiTextureHandle, EguiUserTextures};
+use bevy_granite_core::{AvailableEditableMaterials, EditorIgnore, TreeHiddenEntity};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+// material_previews.rs
+// Renders every editable material onto a small sphere, so the material selector and browser can show it
+// Each preview gets a camera, a sphere and a light of its own on the preview layer, far below the scene.
+// They are despawned after a few frames, the image keeps the last render. Edited materials are rendered again
+
+pub const MATERIAL_PREVIEW_SIZE: u32 = 64;
+/// Frames a preview camera renders before it is despawned, so textures can finish loading
+const PREVIEW_FRAMES: u32 = 4;
+/// Previews rendered at the same time
+const PREVIEW_SLOTS: usize = 4;
+const PREVIEW_ORIGIN: Vec3 = Vec3::new(0.0, -5000.0, 0.0);
+const PREVIEW_SLOT_SPACING: f32 = 10.0;
+
+struct PendingPreview {
+    path: String,
+    slot: usize,
+    entities: Vec<Entity>,
+    frames: u32,
+}
+
+/// Preview sphere renders of editable materials, by material path
+#[derive(Resource, Default)]
+pub struct MaterialPreviews {
+    /// Rendered at least once, ready to draw
+    pub textures: HashMap<String, egui::TextureId>,
+    images: HashMap<String, Handle<Image>>,
+    /// Material asset and version each preview was last queued for
+    rendered: HashMap<String, (AssetId<StandardMaterial>, u32)>,
+    queue: VecDeque<String>,
+    pending: Vec<PendingPreview>,
+    sphere: Option<Handle<Mesh>>,
+}
+
+impl MaterialPreviews {
+    fn request(&mut self, path: &str) {
+        if !self.queue.iter().any(|queued| queued == path) {
+            self.queue.push_back(path.to_string());
+        }
+    }
+}
+
+/// Queue previews of new, edited and changed materials, and drop the ones of removed materials
+pub fn queue_material_previews_system(
+    mut previews: ResMut<MaterialPreviews>,
+    mut user_textures: ResMut<EguiUserTextures>,
+    available_materials: Res<AvailableEditableMaterials>,
+    mut material_events: MessageReader<AssetEvent<StandardMaterial>>,
+) {
+    let modified: HashSet<AssetId<StandardMaterial>> = material_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { id } | AssetEvent::LoadedWithDependencies { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+    if !available_materials.is_changed() && modified.is_empty() {
+        return;
+    }
+
+    let materials = available_materials.materials().unwrap_or_default();
+    for material in materials.iter() {
+        let Some(handle) = material.handle.as_ref() else {
+            continue;
+        };
+        if material.path.is_empty() {
+            continue;
+        }
+        let key = (handle.id(), material.version);
+        if previews.rendered.get(&material.path) != Some(&key) || modified.contains(&key.0) {
+            previews.rendered.insert(material.path.clone(), key);
+            previews.request(&material.path);
+        }
+    }
+
+    let known: HashSet<&str> = materials
+        .iter()
+        .map(|material| material.path.as_str())
+        .collect();
+    let removed: Vec<String> = previews
+        .rendered
+        .keys()
+        .filter(|path| !known.contains(path.as_str()))
+        .cloned()
+        .collect();
+    for path in removed {
+        previews.rendered.remove(&path);
+        previews.textures.remove(&path);
+        previews.queue.retain(|queued| *queued != path);
+        if let Some(image) = previews.images.remove(&path) {
+            user_textures.remove_image(&image);
+        }
+    }
+}
+
+/// Spawn preview renders into free slots, and despawn the ones that had their frames
+pub fn render_material_previews_system(
+    mut commands: Commands,
+    editor_state: Res<EditorState>,
+    mut previews: ResMut<MaterialPreviews>,
+    mut user_textures: ResMut<EguiUserTextures>,
+    available_materials: Res<AvailableEditableMaterials>,
+    (mut images, mut meshes): (ResMut<Assets<Image>>, ResMut<Assets<Mesh>>),
+) {
+    if previews.pending.is_empty() && (previews.queue.is_empty() || !editor_state.active) {
+        return;
+    }
+
+    let mut finished = Vec::new();
+    previews.pending.retain_mut(|preview| {
+        preview.frames += 1;
+        if preview.frames < PREVIEW_FRAMES {
+            return true;
+        }
+        for entity in preview.entities.iter() {
+            commands.entity(*entity).despawn();
+        }
+        finished.push(preview.path.clone());
+        false
+    });
+    for path in finished {
+        if let Some(image) = previews.images.get(&path).cloned() {
+            let texture = user_textures.add_image(EguiTextureHandle::Strong(image));
+            previews.textures.insert(path, texture);
+        }
+    }
+
+    if !editor_state.active {
+        return;
+    }
+
+    let sphere = previews
+        .sphere
+        .get_or_insert_with(|| meshes.add(Sphere::new(0.5).mesh().uv(32, 18)))
+        .clone();
+    while previews.pending.len() < PREVIEW_SLOTS {
+        let Some(path) = previews.queue.pop_front() else {
+            break;
+        };
+        let Some(material) = available_materials
+            .find_material_by_path(&path)
+            .and_then(|material| material.handle.clone())
+        else {
+            continue;
+        };
+        let slot = (0..PREVIEW_SLOTS)
+            .find(|slot| previews.pending.iter().all(|other| other.slot != *slot))
+            .unwrap_or_default();
+        let image = previews
+            .images
+            .entry(path.clone())
+            .or_insert_with(|| {
+                images.add(Image::new_target_texture(
+                    MATERIAL_PREVIEW_SIZE,
+                    MATERIAL_PREVIEW_SIZE,
+                    TextureFormat::Rgba8UnormSrgb,
+                    None,
+                ))
+            })
+            .clone();
+
+        let center = PREVIEW_ORIGIN + Vec3::X * PREVIEW_SLOT_SPACING * slot as f32;
+        let layers = RenderLayers::layer(LAYER_MATERIAL_PREVIEW);
+        let entities = vec![
+            commands
+                .spawn((
+                    Transform::from_translation(center + Vec3::Z * 1.6).looking_at(center, Vec3::Y),
+                    Camera3d::default(),
+                    Camera {
+                        order: -1,
+                        clear_color: ClearColorConfig::Custom(Color::NONE),
+                        ..Default::default()
+                    },
+                    RenderTarget::Image(image.into()),
+                    layers.clone(),
+                    Name::new(format!("Material Preview Camera ({})", path)),
+                    TreeHiddenEntity,
+                    EditorIgnore::PICKING,
+                ))
+                .id(),
+            commands
+                .spawn((
+                    Transform::from_translation(center),
+                    Mesh3d(sphere.clone()),
+                    MeshMaterial3d(material),
+                    layers.clone(),
+                    Name::new(format!("Material Preview Sphere ({})", path)),
+                    TreeHiddenEntity,
+                    EditorIgnore::PICKING,
+                ))
+                .id(),
+            commands
+                .spawn((
+                    Transform::from_translation(center + Vec3::new(1.5, 1.5, 2.0)),
+                    PointLight {
+                        intensity: 150_000.0,
+                        range: PREVIEW_SLOT_SPACING * 0.5,
+                        ..Default::default()
+                    },
+                    layers,
+                    Name::new(format!("Material Preview Light ({})", path)),
+                    TreeHiddenEntity,
+                    EditorIgnore::PICKING,
+                ))
+                .id(),
+        ];
+        previews.pending.push(PendingPreview {
+            path,
+            slot,
+            entities,
+            frames: 0,
+        });
+    }
+}
@@ -1,13 +1,107 @@
+pub mod autosave;
+pub mod commands;
+pub mod config;
+pub mod crash_handler;
+pub mod cubemap_capture;
 pub mod dock;
 pub mod editor;
+pub mod external_changes;
+pub mod history;
+pub mod imposter_bake;
+pub mod material_previews;
+pub mod mutations;
 pub mod plugin;
-pub mod config;
+pub mod project_lock;
+pub mod project_wizard;
+pub mod randomization_preview;
+pub mod safe_delete;
+pub mod safe_mode;
+pub mod scene_gallery;
+pub mod session;
+pub mod simulation_time;
+pub mod state_recorder;
+pub mod telemetry;
+pub mod texture_library;
+pub mod trash;
 
-pub use dock::{
-    get_dock_state_str, load_dock_state, save_dock_on_window_close_system, auto_save_dock_layout_system, DockLayoutStr, DockLayoutTracker,
+pub use autosave::{
+    autosave_path_for_scene, check_autosave_recovery_system, discard_autosave, recover_autosave,
+    track_autosave_system, write_autosaves_system, Autosave, AutosaveRecovery, AUTOSAVE_EXTENSION,
+    DEFAULT_AUTOSAVE_MINUTES,
 };
+pub use commands::{EditorCommand, EditorCommandReaders};
 pub use config::*;
+pub use crash_handler::{
+    install_crash_handler_system, snapshot_crash_context_system, track_crash_context_system,
+    CrashHandler, CRASH_REPORT_DIR,
+};
+pub use cubemap_capture::{
+    finish_cubemap_capture_system, start_cubemap_capture_system, PendingCubemapCaptures,
+};
+pub use dock::{
+    auto_save_dock_layout_system, get_dock_state_str, load_dock_state,
+    save_dock_on_window_close_system, DockLayoutStr, DockLayoutTracker,
+};
 pub use editor::{
-    load_editor_settings_toml, save_editor_settings_from_widget_data, update_active_world_system, update_editor_vis_system, update_editor_config_field};
-    
-pub use plugin::{EditorState, ConfigPlugin};
+    load_editor_settings_toml, save_editor_settings_from_widget_data, sync_startup_world_system,
+    update_active_world_system, update_editor_config_field, update_editor_vis_system,
+};
+pub use external_changes::{
+    build_scene_diffs_system, check_external_scene_changes_system, ExternalSceneChanges,
+};
+pub use history::{
+    apply_history_system, record_history_system, EditorHistory, EntityStateCommand, UndoableCommand,
+};
+pub use imposter_bake::{
+    apply_baked_imposters_system, finish_imposter_bake_system, start_imposter_bake_system,
+    PendingImposterBakes,
+};
+pub use material_previews::{
+    queue_material_previews_system, render_material_previews_system, MaterialPreviews,
+    MATERIAL_PREVIEW_SIZE,
+};
+pub use mutations::{emit_editor_mutations_system, EditorMutationEvent, EditorMutations};
+pub use texture_library::{
+    prepare_texture_library_system, scan_texture_library, TextureLibrary, TEXTURE_LIBRARY_DIR,
+};
+pub use trash::{
+    trash_entities, trash_path_for_scene, trash_system, EntityTrash, TrashEntry, TRASH_EXTENSION,
+};
+
+pub use plugin::{ConfigPlugin, EditorState};
+pub use project_lock::{
+    acquire_project_lock_system, project_lock_heartbeat_system, release_project_lock_system,
+    ProjectLock, PROJECT_LOCK_FILE,
+};
+pub use project_wizard::{
+    create_project, detect_first_run_system, ProjectWizardPopupData, SceneTemplate,
+    DEFAULT_STARTER_SCENE, PROJECT_FOLDERS,
+};
+pub use randomization_preview::{
+    apply_randomization_preview_system, handle_randomization_preview_system, RandomizationPreview,
+    RandomizationPreviewHidden,
+};
+pub use safe_delete::{safe_delete_system, PendingDelete, SafeDeleteChoice};
+pub use safe_mode::{
+    detect_safe_mode_system, safe_mode_allows_icons, safe_mode_allows_visualizers,
+    PerformanceSafeMode, SafeModeReason,
+};
+pub use scene_gallery::{
+    capture_scene_thumbnail_system, format_modified, scan_scene_gallery, thumbnail_path_for_scene,
+    SceneGalleryEntry,
+};
+pub use session::{
+    apply_scene_session_system, queue_scene_session_restore_system, save_scene_session_system,
+    session_path_for_scene, SceneSessionData, SceneSessionTracker,
+};
+pub use simulation_time::{
+    handle_simulation_time_system, SimulationTime, MAX_SIMULATION_SPEED, MIN_SIMULATION_SPEED,
+    SIMULATION_SPEED_PRESETS,
+};
+pub use state_recorder::{
+    record_entity_state_system, RecordedEntity, RecordedFrame, StateRecorder,
+    DEFAULT_RECORDER_CAPACITY, MAX_RECORDER_CAPACITY,
+};
+pub use telemetry::{
+    record_telemetry_system, EditorTelemetry, TelemetryEvent, TelemetryReaders, TELEMETRY_FILE,
+};
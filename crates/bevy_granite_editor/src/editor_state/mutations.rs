@@ -0,0 +1,310 @@
+use crate::editor_state::{
+    history::{HistoryQuietReaders, QUIET_FRAMES},
+    EditorState,
+};
+use bevy::{
+    ecs::{
+        archetype::ArchetypeId,
+        change_detection::{ComponentTicks, Tick},
+        message::Message,
+        system::SystemState,
+        world::EntityRef,
+    },
+    prelude::{ChildOf, Entity, Mut, Resource, Transform, With, World},
+    reflect::TypePath,
+};
+use bevy_granite_core::{ComponentEditor, GraniteType, IdentityData};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+// mutations.rs
+// One stream of every edit made to serializable entities while the editor is open, for game plugins,
+// undo, collaboration or autosave to subscribe to. Like history, edits aren't reported by each tool:
+// the last known state of every entity is kept and compared whenever its change ticks move,
+// so gizmos, panels, undo, scripts and anything else that edits the world all end up here
+// Loads and despawns of whole scenes only refresh the known states, they are never reported
+
+/// An edit to a serializable entity, sent in Last of the frame it happened
+/// i.e. `fn on_edit(mut reader: MessageReader<EditorMutationEvent>) { for event in reader.read() { ... } }`
+#[derive(Message, Debug, Clone, PartialEq)]
+pub enum EditorMutationEvent {
+    Spawned {
+        entity: Entity,
+        uuid: Uuid,
+    },
+    Despawned {
+        entity: Entity,
+        uuid: Uuid,
+    },
+    Renamed {
+        entity: Entity,
+        old: String,
+        new: String,
+    },
+    Reparented {
+        entity: Entity,
+        old: Option<Entity>,
+        new: Option<Entity>,
+    },
+    /// Type path of a saved component that was changed, added or removed
+    /// Edits to the class data report IdentityData
+    ComponentChanged {
+        entity: Entity,
+        component: String,
+    },
+    /// Material path of the class, none when it has no material
+    MaterialAssigned {
+        entity: Entity,
+        old: Option<String>,
+        new: Option<String>,
+    },
+    TransformChanged {
+        entity: Entity,
+        transform: Transform,
+    },
+}
+
+impl EditorMutationEvent {
+    pub fn entity(&self) -> Entity {
+        match self {
+            Self::Spawned { entity, .. }
+            | Self::Despawned { entity, .. }
+            | Self::Renamed { entity, .. }
+            | Self::Reparented { entity, .. }
+            | Self::ComponentChanged { entity, .. }
+            | Self::MaterialAssigned { entity, .. }
+            | Self::TransformChanged { entity, .. } => *entity,
+        }
+    }
+}
+
+/// Last reported state of an entity
+#[derive(Debug, Clone)]
+struct KnownEntity {
+    identity: IdentityData,
+    parent: Option<Entity>,
+    transform: Transform,
+    archetype: ArchetypeId,
+    /// Type paths of the saved components
+    components: HashSet<String>,
+}
+
+impl KnownEntity {
+    fn read(entity_ref: EntityRef, world: &World, component_editor: &ComponentEditor) -> Self {
+        Self {
+            identity: entity_ref
+                .get::<IdentityData>()
+                .cloned()
+                .unwrap_or_default(),
+            parent: entity_ref
+                .get::<ChildOf>()
+                .map(|child_of| child_of.parent()),
+            transform: entity_ref.get::<Transform>().copied().unwrap_or_default(),
+            archetype: entity_ref.archetype().id(),
+            components: component_editor.saved_component_names(world, entity_ref.id()),
+        }
+    }
+}
+
+fn material_path(identity: &IdentityData) -> Option<String> {
+    identity
+        .class
+        .get_material_data()
+        .map(|material| material.current.path.clone())
+}
+
+/// Known states of serializable entities, compared against the world in Last
+#[derive(Resource, Default)]
+pub struct EditorMutations {
+    known: HashMap<Entity, KnownEntity>,
+    last_tick: Tick,
+    /// Known states match the world, false after startup or while the editor is closed
+    synced: bool,
+    quiet_frames: u32,
+}
+
+impl EditorMutations {
+    /// Takes the world as it is without reporting anything
+    fn resync(&mut self, world: &mut World) {
+        let entities: Vec<Entity> = world
+            .query_filtered::<Entity, With<IdentityData>>()
+            .iter(world)
+            .collect();
+        let component_editor = world.resource::<ComponentEditor>();
+        self.known = entities
+            .into_iter()
+            .map(|entity| {
+                let known = KnownEntity::read(world.entity(entity), world, component_editor);
+                (entity, known)
+            })
+            .collect();
+        self.synced = true;
+    }
+
+    /// Updates the known states, returning what changed since `since`
+    fn update(
+        &mut self,
+        world: &mut World,
+        since: Tick,
+        this_run: Tick,
+    ) -> Vec<EditorMutationEvent> {
+        let mut events = Vec::new();
+        self.known.retain(|entity, known| {
+            let alive = world.get::<IdentityData>(*entity).is_some();
+            if !alive {
+                events.push(EditorMutationEvent::Despawned {
+                    entity: *entity,
+                    uuid: known.identity.uuid,
+                });
+            }
+            alive
+        });
+
+        let entities: Vec<Entity> = world
+            .query_filtered::<Entity, With<IdentityData>>()
+            .iter(world)
+            .collect();
+        let component_editor = world.resource::<ComponentEditor>();
+        let registry = component_editor.type_registry.read();
+        let changed = |ticks: Option<ComponentTicks>| {
+            ticks.is_some_and(|ticks| ticks.is_changed(since, this_run))
+        };
+
+        for entity in entities {
+            let entity_ref = world.entity(entity);
+            let Some(known) = self.known.get_mut(&entity) else {
+                let known = KnownEntity::read(entity_ref, world, component_editor);
+                events.push(EditorMutationEvent::Spawned {
+                    entity,
+                    uuid: known.identity.uuid,
+                });
+                self.known.insert(entity, known);
+                continue;
+            };
+
+            if changed(entity_ref.get_change_ticks::<IdentityData>()) {
+                if let Some(identity) = entity_ref.get::<IdentityData>() {
+                    if identity.name != known.identity.name {
+                        events.push(EditorMutationEvent::Renamed {
+                            entity,
+                            old: known.identity.name.clone(),
+                            new: identity.name.clone(),
+                        });
+                    }
+                    let (old_material, new_material) =
+                        (material_path(&known.identity), material_path(identity));
+                    if old_material != new_material {
+                        events.push(EditorMutationEvent::MaterialAssigned {
+                            entity,
+                            old: old_material,
+                            new: new_material,
+                        });
+                    } else if identity.class != known.identity.class {
+                        events.push(EditorMutationEvent::ComponentChanged {
+                            entity,
+                            component: IdentityData::type_path().to_string(),
+                        });
+                    }
+                    known.identity = identity.clone();
+                }
+            }
+
+            // Removing ChildOf moves no ticks, so the parent is always compared
+            let parent = entity_ref
+                .get::<ChildOf>()
+                .map(|child_of| child_of.parent());
+            if parent != known.parent {
+                events.push(EditorMutationEvent::Reparented {
+                    entity,
+                    old: known.parent,
+                    new: parent,
+                });
+                known.parent = parent;
+            }
+
+            if changed(entity_ref.get_change_ticks::<Transform>()) {
+                if let Some(transform) = entity_ref.get::<Transform>() {
+                    if *transform != known.transform {
+                        events.push(EditorMutationEvent::TransformChanged {
+                            entity,
+                            transform: *transform,
+                        });
+                        known.transform = *transform;
+                    }
+                }
+            }
+
+            // Components added since show up as changed below, removed ones only as a new archetype
+            if entity_ref.archetype().id() != known.archetype {
+                known.archetype = entity_ref.archetype().id();
+                let components = component_editor.saved_component_names(world, entity);
+                events.extend(known.components.difference(&components).map(|component| {
+                    EditorMutationEvent::ComponentChanged {
+                        entity,
+                        component: component.clone(),
+                    }
+                }));
+                known.components = components;
+            }
+            for component_id in entity_ref.archetype().components().iter() {
+                if !changed(entity_ref.get_change_ticks_by_id(*component_id)) {
+                    continue;
+                }
+                let Some(registration) = world
+                    .components()
+                    .get_info(*component_id)
+                    .and_then(|info| info.type_id())
+                    .and_then(|type_id| registry.get(type_id))
+                else {
+                    continue;
+                };
+                let component = registration.type_info().type_path();
+                if known.components.contains(component) {
+                    events.push(EditorMutationEvent::ComponentChanged {
+                        entity,
+                        component: component.to_string(),
+                    });
+                }
+            }
+        }
+        events
+    }
+}
+
+/// Compares serializable entities against their known states and sends an EditorMutationEvent per edit
+/// Runs in Last while the editor is open, so every edit of the frame has been applied
+pub fn emit_editor_mutations_system(
+    world: &mut World,
+    state: &mut SystemState<HistoryQuietReaders>,
+) {
+    let quiet = state.get_mut(world).any();
+    let active = world.resource::<EditorState>().active;
+    let this_run = world.read_change_tick();
+
+    let events = world.resource_scope(|world, mut mutations: Mut<EditorMutations>| {
+        let since = std::mem::replace(&mut mutations.last_tick, this_run);
+        if !active {
+            mutations.synced = false;
+            return Vec::new();
+        }
+        if !mutations.synced {
+            mutations.resync(world);
+            return Vec::new();
+        }
+        if quiet {
+            mutations.quiet_frames = QUIET_FRAMES;
+        }
+        let reporting = mutations.quiet_frames == 0;
+        mutations.quiet_frames = mutations.quiet_frames.saturating_sub(1);
+
+        let events = mutations.update(world, since, this_run);
+        if reporting {
+            events
+        } else {
+            Vec::new()
+        }
+    });
+    if !events.is_empty() {
+        world.write_message_batch(events);
+    }
+}
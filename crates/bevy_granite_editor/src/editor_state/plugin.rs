@@ -1,23 +1,44 @@
 use bevy::{
     app::PostStartup,
     ecs::{resource::Resource, schedule::IntoScheduleConfigs},
-    prelude::{App, Plugin, Res, ResMut, Startup, Update},
+    prelude::{App, Last, Plugin, PostUpdate, Res, ResMut, Startup, Update},
+    transform::TransformSystems,
 };
 
 use super::editor::update_editor_vis_system;
 use crate::{
     editor_state::{
-        load_editor_settings_toml, save_dock_on_window_close_system, auto_save_dock_layout_system, 
-        update_active_world_system, DockLayoutTracker,
+        acquire_project_lock_system, apply_baked_imposters_system, apply_history_system,
+        apply_randomization_preview_system, apply_scene_session_system,
+        auto_save_dock_layout_system, build_scene_diffs_system, capture_scene_thumbnail_system,
+        check_autosave_recovery_system, check_external_scene_changes_system,
+        detect_first_run_system, detect_safe_mode_system, emit_editor_mutations_system,
+        finish_cubemap_capture_system, finish_imposter_bake_system,
+        handle_randomization_preview_system, handle_simulation_time_system,
+        install_crash_handler_system, load_editor_settings_toml, prepare_texture_library_system,
+        project_lock_heartbeat_system, queue_material_previews_system,
+        queue_scene_session_restore_system, record_entity_state_system, record_history_system,
+        record_telemetry_system, release_project_lock_system, render_material_previews_system,
+        safe_delete_system, save_dock_on_window_close_system, save_scene_session_system,
+        snapshot_crash_context_system, start_cubemap_capture_system, start_imposter_bake_system,
+        sync_startup_world_system, track_autosave_system, track_crash_context_system, trash_system,
+        update_active_world_system, write_autosaves_system, Autosave, CrashHandler,
+        DockLayoutTracker, EditorHistory, EditorMutationEvent, EditorMutations, EditorTelemetry,
+        EntityTrash, ExternalSceneChanges, MaterialPreviews, PendingCubemapCaptures, PendingDelete,
+        PendingImposterBakes, PerformanceSafeMode, ProjectLock, RandomizationPreview,
+        SceneSessionTracker, SimulationTime, StateRecorder, TextureLibrary,
     },
     interface::EditorSettingsTabData,
     setup::is_editor_active,
 };
+use bevy_granite_core::{SceneFileWatcher, SceneTarget};
 use bevy_granite_gizmos::GizmoVisibilityState;
 
 #[derive(Resource, Clone)]
 pub struct EditorState {
     pub active: bool,
+    /// Compact mode, only the toolbar and gizmos are drawn over the game view
+    pub overlay: bool,
     pub default_world: String,
     pub current_file: Option<String>,
     pub config_path: String,
@@ -43,6 +64,7 @@ impl Plugin for ConfigPlugin {
             //
             .insert_resource(EditorState {
                 active: self.editor_active,
+                overlay: false,
                 default_world: self.default_world.clone(),
                 current_file: None,
 
@@ -53,15 +75,131 @@ impl Plugin for ConfigPlugin {
                 loaded_sources: std::collections::HashSet::new(),
             })
             .insert_resource(DockLayoutTracker::default())
+            .insert_resource(ProjectLock::default())
+            .insert_resource(SceneSessionTracker::default())
+            .insert_resource(PendingCubemapCaptures::default())
+            .insert_resource(PendingImposterBakes::default())
+            .insert_resource(PerformanceSafeMode::default())
+            .insert_resource(EditorTelemetry::default())
+            .insert_resource(CrashHandler::default())
+            .insert_resource(EditorHistory::default())
+            .insert_resource(EditorMutations::default())
+            .insert_resource(Autosave::default())
+            .insert_resource(ExternalSceneChanges::default())
+            .insert_resource(PendingDelete::default())
+            .insert_resource(EntityTrash::default())
+            .insert_resource(RandomizationPreview::default())
+            .insert_resource(MaterialPreviews::default())
+            .insert_resource(TextureLibrary::default())
+            .insert_resource(SimulationTime::default())
+            .insert_resource(StateRecorder::default())
+            .insert_resource(SceneFileWatcher::enabled())
+            .add_message::<EditorMutationEvent>()
+            // Scenes load whole whatever their spawn conditions, saving would drop what was skipped
+            .insert_resource(SceneTarget::everything())
             //
             // Systems
             //
             .add_systems(Startup, sync_initial_gizmo_state)
+            .add_systems(Startup, install_crash_handler_system)
+            .add_systems(
+                PostStartup,
+                detect_first_run_system.before(load_editor_settings_toml),
+            )
             .add_systems(PostStartup, load_editor_settings_toml)
+            .add_systems(PostStartup, acquire_project_lock_system)
             .add_systems(Update, update_active_world_system.run_if(is_editor_active))
+            .add_systems(Update, sync_startup_world_system)
             .add_systems(Update, save_dock_on_window_close_system)
-            .add_systems(Update, auto_save_dock_layout_system.run_if(is_editor_active))
-            .add_systems(Update, update_editor_vis_system);
+            .add_systems(
+                Update,
+                auto_save_dock_layout_system.run_if(is_editor_active),
+            )
+            .add_systems(Update, update_editor_vis_system)
+            .add_systems(
+                Update,
+                (project_lock_heartbeat_system, release_project_lock_system),
+            )
+            .add_systems(
+                Update,
+                (
+                    queue_scene_session_restore_system,
+                    apply_scene_session_system,
+                    save_scene_session_system,
+                )
+                    .chain()
+                    .run_if(is_editor_active),
+            )
+            .add_systems(
+                Update,
+                capture_scene_thumbnail_system.run_if(is_editor_active),
+            )
+            .add_systems(
+                Update,
+                (start_cubemap_capture_system, finish_cubemap_capture_system).chain(),
+            )
+            .add_systems(
+                Update,
+                (
+                    start_imposter_bake_system,
+                    finish_imposter_bake_system,
+                    apply_baked_imposters_system,
+                )
+                    .chain(),
+            )
+            .add_systems(
+                Update,
+                (
+                    queue_material_previews_system,
+                    render_material_previews_system,
+                )
+                    .chain(),
+            )
+            .add_systems(
+                Update,
+                prepare_texture_library_system.run_if(is_editor_active),
+            )
+            .add_systems(Update, detect_safe_mode_system.run_if(is_editor_active))
+            .add_systems(
+                Update,
+                check_autosave_recovery_system.run_if(is_editor_active),
+            )
+            .add_systems(
+                Update,
+                check_external_scene_changes_system.run_if(is_editor_active),
+            )
+            .add_systems(Last, build_scene_diffs_system)
+            .add_systems(Update, safe_delete_system.run_if(is_editor_active))
+            .add_systems(Update, trash_system.after(safe_delete_system))
+            .add_systems(
+                Update,
+                (
+                    handle_randomization_preview_system,
+                    apply_randomization_preview_system,
+                )
+                    .chain(),
+            )
+            .add_systems(Update, handle_simulation_time_system)
+            .add_systems(
+                PostUpdate,
+                record_entity_state_system.after(TransformSystems::Propagate),
+            )
+            .add_systems(Update, apply_history_system.run_if(is_editor_active))
+            .add_systems(Last, record_history_system)
+            .add_systems(
+                Last,
+                (
+                    emit_editor_mutations_system,
+                    track_autosave_system,
+                    write_autosaves_system,
+                )
+                    .chain(),
+            )
+            .add_systems(Last, record_telemetry_system)
+            .add_systems(
+                Last,
+                (track_crash_context_system, snapshot_crash_context_system).chain(),
+            );
     }
 }
 
@@ -0,0 +1,182 @@
+use crate::{
+    interface::{popups::PopupType, PopupMenuRequestedEvent},
+    utils::{load_from_toml_file, save_to_toml_file},
+};
+use bevy::{
+    app::AppExit,
+    math::Vec2,
+    prelude::{MessageReader, MessageWriter, Res, ResMut, Resource},
+    time::{Real, Time},
+    window::WindowClosing,
+};
+use bevy_granite_core::{rel_asset_to_absolute, SaveLock};
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// project_lock.rs
+// Guards against two editors saving over each other in the same assets folder
+// The owning editor keeps a heartbeat in the lock file. A lock with an old heartbeat is treated as stale (crashed editor)
+
+pub const PROJECT_LOCK_FILE: &str = ".granite_editor.lock";
+const HEARTBEAT_INTERVAL: f32 = 5.0;
+const STALE_AFTER_SECS: u64 = 30;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct ProjectLockFile {
+    pid: u32,
+    heartbeat: u64,
+}
+
+#[derive(Resource, Default, Clone)]
+pub struct ProjectLock {
+    /// This editor wrote the lock file and keeps it alive
+    pub owned: bool,
+    /// Saving is disabled because another editor owns the project
+    pub read_only: bool,
+    /// Process id of the other editor, if one was detected
+    pub other_pid: Option<u32>,
+    time_since_heartbeat: f32,
+}
+
+impl ProjectLock {
+    /// Take ownership of the project, even if another editor holds the lock
+    pub fn take_over(&mut self, save_lock: &mut SaveLock) {
+        if write_lock_file() {
+            self.owned = true;
+            self.read_only = false;
+            self.other_pid = None;
+            save_lock.reason = None;
+        }
+    }
+
+    /// Keep editing without saving anything to disk
+    pub fn enter_read_only(&mut self, save_lock: &mut SaveLock) {
+        self.read_only = true;
+        save_lock.reason = Some(match self.other_pid {
+            Some(pid) => format!(
+                "project is open in another editor (pid {}), read-only mode",
+                pid
+            ),
+            None => "read-only mode".to_string(),
+        });
+    }
+}
+
+fn lock_path() -> String {
+    rel_asset_to_absolute(PROJECT_LOCK_FILE).to_string()
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Process id currently written in the lock file, if there is a readable one
+fn lock_file_pid() -> Option<u32> {
+    load_from_toml_file::<ProjectLockFile>(&lock_path())
+        .ok()
+        .map(|lock| lock.pid)
+}
+
+fn write_lock_file() -> bool {
+    let lock = ProjectLockFile {
+        pid: std::process::id(),
+        heartbeat: now_secs(),
+    };
+
+    match save_to_toml_file(&lock, &lock_path()) {
+        Ok(_) => true,
+        Err(e) => {
+            log!(
+                LogType::Editor,
+                LogLevel::Error,
+                LogCategory::System,
+                "Failed to write project lock: {}",
+                e
+            );
+            false
+        }
+    }
+}
+
+pub fn acquire_project_lock_system(
+    mut project_lock: ResMut<ProjectLock>,
+    mut save_lock: ResMut<SaveLock>,
+    mut popup_writer: MessageWriter<PopupMenuRequestedEvent>,
+) {
+    if let Ok(existing) = load_from_toml_file::<ProjectLockFile>(&lock_path()) {
+        let alive = now_secs().saturating_sub(existing.heartbeat) < STALE_AFTER_SECS;
+        if alive && existing.pid != std::process::id() {
+            log!(
+                LogType::Editor,
+                LogLevel::Warning,
+                LogCategory::System,
+                "Another Granite editor (pid {}) is using this project. Saves would overwrite each other",
+                existing.pid
+            );
+            project_lock.other_pid = Some(existing.pid);
+            // Stay read-only until the user picks take over or read-only in the popup
+            project_lock.enter_read_only(&mut save_lock);
+            popup_writer.write(PopupMenuRequestedEvent {
+                popup: PopupType::ProjectLocked,
+                mouse_pos: Vec2::ZERO,
+            });
+            return;
+        }
+    }
+
+    project_lock.owned = write_lock_file();
+}
+
+pub fn project_lock_heartbeat_system(
+    time: Res<Time<Real>>,
+    mut project_lock: ResMut<ProjectLock>,
+    mut save_lock: ResMut<SaveLock>,
+) {
+    if !project_lock.owned {
+        return;
+    }
+
+    project_lock.time_since_heartbeat += time.delta_secs();
+    if project_lock.time_since_heartbeat >= HEARTBEAT_INTERVAL {
+        project_lock.time_since_heartbeat = 0.0;
+
+        // Another editor took over since our last heartbeat, stop writing over its lock
+        if let Some(pid) = lock_file_pid().filter(|pid| *pid != std::process::id()) {
+            log!(
+                LogType::Editor,
+                LogLevel::Warning,
+                LogCategory::System,
+                "Another Granite editor (pid {}) took over this project. Switching to read-only",
+                pid
+            );
+            project_lock.owned = false;
+            project_lock.other_pid = Some(pid);
+            project_lock.enter_read_only(&mut save_lock);
+            return;
+        }
+
+        write_lock_file();
+    }
+}
+
+pub fn release_project_lock_system(
+    mut window_close_events: MessageReader<WindowClosing>,
+    mut exit_events: MessageReader<AppExit>,
+    mut project_lock: ResMut<ProjectLock>,
+) {
+    let closing = window_close_events.read().count() > 0 || exit_events.read().count() > 0;
+    if closing && project_lock.owned {
+        // Only remove the lock if it is still ours, another editor may have taken over
+        if lock_file_pid() == Some(std::process::id()) {
+            let _ = std::fs::remove_file(lock_path());
+        }
+        project_lock.owned = false;
+    }
+}
@@ -0,0 +1,183 @@
+use crate::{
+    editor_state::EditorState,
+    interface::{popups::PopupType, PopupMenuRequestedEvent},
+    utils::save_to_toml_file,
+};
+use bevy::{
+    asset::io::file::FileAssetReader,
+    math::{EulerRot, Quat, Vec2, Vec3},
+    prelude::{MessageWriter, Res, Transform},
+};
+use bevy_granite_core::{
+    rel_asset_to_absolute, write_scene_file, Camera3D, DirLight, EntitySaveReadyData, GraniteTypes,
+    IdentityData, RectBrush, TransformData,
+};
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+use std::fs;
+use uuid::Uuid;
+
+// project_wizard.rs
+// First run flow. When the assets folder has no editor config yet we offer to lay out a project:
+// the expected folders, a starter scene built from a template, and a default editor config
+
+pub const PROJECT_FOLDERS: [&str; 3] = ["scenes", "materials", "config"];
+pub const DEFAULT_STARTER_SCENE: &str = "scenes/starter.scene";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SceneTemplate {
+    Empty,
+    #[default]
+    Basic,
+}
+
+impl SceneTemplate {
+    pub fn all() -> [SceneTemplate; 2] {
+        [SceneTemplate::Empty, SceneTemplate::Basic]
+    }
+
+    pub fn get_friendly_name(&self) -> &'static str {
+        match self {
+            SceneTemplate::Empty => "Empty",
+            SceneTemplate::Basic => "Basic (camera, light, ground)",
+        }
+    }
+
+    /// Entities the template scene starts with
+    pub fn entities(&self) -> Vec<EntitySaveReadyData> {
+        match self {
+            SceneTemplate::Empty => vec![],
+            SceneTemplate::Basic => vec![
+                template_entity(
+                    "Main Camera",
+                    GraniteTypes::Camera3D(Camera3D::default()),
+                    Transform::from_xyz(0., 4., 10.).looking_at(Vec3::ZERO, Vec3::Y),
+                ),
+                template_entity(
+                    "Sun",
+                    GraniteTypes::DirLight(DirLight::default()),
+                    Transform::from_rotation(Quat::from_euler(
+                        EulerRot::XYZ,
+                        -45f32.to_radians(),
+                        30f32.to_radians(),
+                        0.,
+                    )),
+                ),
+                template_entity(
+                    "Ground",
+                    GraniteTypes::RectBrush(RectBrush {
+                        size: Vec3::new(20., 0.2, 20.),
+                        uv_scale: Vec2::splat(10.),
+                        ..Default::default()
+                    }),
+                    Transform::from_xyz(0., -0.1, 0.),
+                ),
+            ],
+        }
+    }
+}
+
+fn template_entity(name: &str, class: GraniteTypes, transform: Transform) -> EntitySaveReadyData {
+    EntitySaveReadyData {
+        identity: IdentityData {
+            uuid: Uuid::new_v4(),
+            name: name.to_string(),
+            class,
+        },
+        transform: TransformData {
+            position: transform.translation,
+            rotation: transform.rotation,
+            scale: transform.scale,
+        },
+        parent: None,
+        components: None,
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ProjectWizardPopupData {
+    pub template: SceneTemplate,
+    /// Relative to the assets folder
+    pub scene_path: String,
+    pub create_folders: bool,
+}
+
+impl Default for ProjectWizardPopupData {
+    fn default() -> Self {
+        Self {
+            template: SceneTemplate::default(),
+            scene_path: DEFAULT_STARTER_SCENE.to_string(),
+            create_folders: true,
+        }
+    }
+}
+
+fn editor_config_path(editor_state: &EditorState) -> String {
+    FileAssetReader::get_base_path()
+        .join("assets")
+        .join(&editor_state.config_path)
+        .display()
+        .to_string()
+}
+
+/// Lay out the project on disk. Returns the relative path of the written starter scene
+pub fn create_project(
+    data: &ProjectWizardPopupData,
+    editor_state: &EditorState,
+) -> std::io::Result<String> {
+    if data.create_folders {
+        for folder in PROJECT_FOLDERS {
+            fs::create_dir_all(rel_asset_to_absolute(folder).as_ref())?;
+        }
+    }
+
+    let scene_path = if data.scene_path.ends_with(".scene") {
+        data.scene_path.clone()
+    } else {
+        format!("{}.scene", data.scene_path)
+    };
+    write_scene_file(
+        rel_asset_to_absolute(&scene_path).as_ref(),
+        data.template.entities(),
+    )?;
+
+    save_to_toml_file(&editor_state.config, &editor_config_path(editor_state))?;
+
+    log!(
+        LogType::Editor,
+        LogLevel::OK,
+        LogCategory::System,
+        "Created project with '{}' template scene: {}",
+        data.template.get_friendly_name(),
+        scene_path
+    );
+
+    Ok(scene_path)
+}
+
+/// Runs before the editor config is loaded (which would write a default one), so a missing config means first run
+pub fn detect_first_run_system(
+    editor_state: Res<EditorState>,
+    mut popup_writer: MessageWriter<PopupMenuRequestedEvent>,
+) {
+    if !editor_state.active {
+        return;
+    }
+
+    if std::path::Path::new(&editor_config_path(&editor_state)).exists() {
+        return;
+    }
+
+    log!(
+        LogType::Editor,
+        LogLevel::Info,
+        LogCategory::System,
+        "No editor config found, starting project wizard"
+    );
+    popup_writer.write(PopupMenuRequestedEvent {
+        popup: PopupType::ProjectWizard,
+        mouse_pos: Vec2::ZERO,
+    });
+}
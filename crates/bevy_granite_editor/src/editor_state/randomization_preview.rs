@@ -0,0 +1,143 @@
+use crate::{editor_state::EditorState, interface::events::RequestRandomizationPreview};
+use bevy::{
+    color::Color,
+    prelude::{
+        Children, Commands, Component, Entity, Gizmos, GlobalTransform, MessageReader, Query, Res,
+        ResMut, Resource, Visibility,
+    },
+};
+use bevy_granite_core::{IdentityData, SpawnRandomization};
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+// randomization_preview.rs
+// The editor loads scenes whole, so SpawnRandomization is never applied while editing. The preview rolls it
+// with a seed of its own and hides what would be skipped. Visibility isn't saved, so the scene is left as authored
+// Rolled rotations aren't applied either, the transform would be saved, they are drawn as arrows instead
+
+/// Seed the viewport previews SpawnRandomization with, none while the preview is off
+/// The same seed in `SceneTarget::with_seed` gives the same rolls in game
+#[derive(Resource, Default, Debug, Clone)]
+pub struct RandomizationPreview {
+    pub seed: Option<u64>,
+}
+
+/// Visibility of an entity before the preview hid it
+#[derive(Component)]
+pub struct RandomizationPreviewHidden(Visibility);
+
+fn new_seed() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|since| since.as_nanos() as u64)
+        .unwrap_or_default()
+}
+
+pub fn handle_randomization_preview_system(
+    mut preview: ResMut<RandomizationPreview>,
+    mut reader: MessageReader<RequestRandomizationPreview>,
+) {
+    for request in reader.read() {
+        preview.seed = match (request, preview.seed) {
+            (RequestRandomizationPreview::Toggle, Some(_)) => None,
+            _ => Some(new_seed()),
+        };
+        match preview.seed {
+            Some(seed) => log!(
+                LogType::Editor,
+                LogLevel::Info,
+                LogCategory::System,
+                "Previewing randomization with seed {}",
+                seed
+            ),
+            None => log!(
+                LogType::Editor,
+                LogLevel::Info,
+                LogCategory::System,
+                "Randomization preview off"
+            ),
+        }
+    }
+}
+
+/// Hides entities and children the preview seed skips, and puts back the ones it no longer does
+/// Runs while the editor is closed too, so nothing stays hidden in game
+pub fn apply_randomization_preview_system(
+    mut commands: Commands,
+    mut gizmos: Gizmos,
+    (preview, editor_state): (Res<RandomizationPreview>, Res<EditorState>),
+    randomized: Query<(
+        Entity,
+        &IdentityData,
+        &SpawnRandomization,
+        &GlobalTransform,
+        Option<&Children>,
+    )>,
+    identities: Query<&IdentityData>,
+    mut visibilities: Query<(&mut Visibility, Option<&RandomizationPreviewHidden>)>,
+    hidden: Query<(Entity, &RandomizationPreviewHidden)>,
+) {
+    let mut hide: HashSet<Entity> = HashSet::new();
+    if let (Some(seed), true) = (preview.seed, editor_state.active) {
+        for (entity, identity, randomization, global_transform, children) in randomized.iter() {
+            let children: Vec<(Entity, Uuid, &str)> = children
+                .into_iter()
+                .flatten()
+                .filter_map(|child| {
+                    let identity = identities.get(*child).ok()?;
+                    Some((*child, identity.uuid, identity.name.as_str()))
+                })
+                .collect();
+            let named: Vec<(Uuid, &str)> = children
+                .iter()
+                .map(|(_, uuid, name)| (*uuid, *name))
+                .collect();
+            let roll = randomization.roll(seed, identity.uuid, &named);
+            if !roll.spawn {
+                hide.insert(entity);
+                continue;
+            }
+            if let Some(kept) = roll.kept_child {
+                hide.extend(
+                    children
+                        .iter()
+                        .filter(|(_, uuid, _)| *uuid != kept)
+                        .map(|(child, _, _)| *child),
+                );
+            }
+            if randomization.rotation_step > 0.0 {
+                let position = global_transform.translation();
+                let forward = roll.rotation * global_transform.forward().as_vec3();
+                gizmos.arrow(
+                    position,
+                    position + forward * 1.5,
+                    Color::srgb(1.0, 0.8, 0.2),
+                );
+            }
+        }
+    }
+
+    for entity in hide.iter() {
+        if let Ok((mut visibility, None)) = visibilities.get_mut(*entity) {
+            commands
+                .entity(*entity)
+                .insert(RandomizationPreviewHidden(*visibility));
+            *visibility = Visibility::Hidden;
+        }
+    }
+    for (entity, RandomizationPreviewHidden(previous)) in hidden.iter() {
+        if hide.contains(&entity) {
+            continue;
+        }
+        if let Ok((mut visibility, _)) = visibilities.get_mut(entity) {
+            *visibility = *previous;
+        }
+        commands
+            .entity(entity)
+            .remove::<RandomizationPreviewHidden>();
+    }
+}
@@ -0,0 +1,221 @@
+use crate::{
+    editor_state::trash::trash_entities,
+    interface::{popups::PopupType, PopupMenuRequestedEvent, RequestSafeDeleteEvent},
+};
+use bevy::{
+    ecs::system::SystemState,
+    math::Vec2,
+    prelude::{Children, Entity, MessageReader, Name, Resource, World},
+};
+use bevy_granite_core::{
+    find_entity_references, retarget_entity_references, EntityReference, IdentityData,
+};
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+// safe_delete.rs
+// Deletes from the editor check first whether other entities reference what goes away, i.e. a joint to a deleted body
+// or a waypoint link. When they do, the user picks between clearing those references, pointing them at another
+// entity, or not deleting. Deletes nothing references go through right away
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SafeDeleteChoice {
+    /// Clear the references, then delete
+    Clear,
+    /// Point the references at this entity, then delete
+    Reassign(Entity),
+    Cancel,
+}
+
+/// Delete waiting on the user, because other entities reference the entities in it
+#[derive(Resource, Default, Clone)]
+pub struct PendingDelete {
+    /// Entities to delete, with their descendants
+    pub entities: Vec<Entity>,
+    pub references: Vec<EntityReference>,
+    /// Names of the entities involved, for the prompt
+    pub names: HashMap<Entity, String>,
+    /// Names of the deleted entities by uuid, to tell what each reference points at
+    pub target_names: HashMap<Uuid, String>,
+    /// Entities that can take over the references, sorted by name
+    pub candidates: Vec<(Entity, String)>,
+    pub reassign_to: Option<Entity>,
+    pub choice: Option<SafeDeleteChoice>,
+}
+
+impl PendingDelete {
+    pub fn is_pending(&self) -> bool {
+        !self.entities.is_empty()
+    }
+
+    pub fn name(&self, entity: Entity) -> String {
+        self.names
+            .get(&entity)
+            .cloned()
+            .unwrap_or_else(|| format!("{}", entity))
+    }
+}
+
+/// Checks requested deletes for references, and applies the choice made in the prompt
+pub fn safe_delete_system(
+    world: &mut World,
+    state: &mut SystemState<MessageReader<RequestSafeDeleteEvent>>,
+) {
+    let requested: Vec<Entity> = state
+        .get_mut(world)
+        .read()
+        .flat_map(|RequestSafeDeleteEvent(entities)| entities.iter().copied())
+        .collect();
+
+    let choice = world.resource_mut::<PendingDelete>().choice.take();
+    if let Some(choice) = choice {
+        let pending = std::mem::take(&mut *world.resource_mut::<PendingDelete>());
+        apply_choice(world, pending, choice);
+    }
+
+    if requested.is_empty() {
+        return;
+    }
+    // One prompt at a time, a delete asked for while one is open is dropped
+    if world.resource::<PendingDelete>().is_pending() {
+        log!(
+            LogType::Editor,
+            LogLevel::Warning,
+            LogCategory::Entity,
+            "Finish the pending delete first"
+        );
+        return;
+    }
+    check_references(world, requested);
+}
+
+fn check_references(world: &mut World, roots: Vec<Entity>) {
+    let mut entities: Vec<Entity> = vec![];
+    for root in roots {
+        if world.get_entity(root).is_err() || entities.contains(&root) {
+            continue;
+        }
+        entities.push(root);
+        let mut stack = vec![root];
+        while let Some(entity) = stack.pop() {
+            let Some(children) = world.get::<Children>(entity) else {
+                continue;
+            };
+            for &child in children.iter() {
+                if !entities.contains(&child) {
+                    entities.push(child);
+                    stack.push(child);
+                }
+            }
+        }
+    }
+
+    let target_names: HashMap<Uuid, String> = entities
+        .iter()
+        .filter_map(|entity| world.get::<IdentityData>(*entity))
+        .map(|identity| (identity.uuid, identity.name.clone()))
+        .collect();
+    let uuids: Vec<Uuid> = target_names.keys().copied().collect();
+    let references = find_entity_references(world, &uuids);
+
+    if references.is_empty() {
+        despawn(world, &entities);
+        return;
+    }
+
+    let mut names = HashMap::new();
+    let mut candidates = vec![];
+    let mut query = world.query::<(Entity, &IdentityData, Option<&Name>)>();
+    for (entity, identity, name) in query.iter(world) {
+        let name = name.map_or_else(|| identity.name.clone(), |name| name.to_string());
+        if !entities.contains(&entity) {
+            candidates.push((entity, name.clone()));
+        }
+        names.insert(entity, name);
+    }
+    candidates.sort_by(|a, b| a.1.cmp(&b.1));
+
+    log!(
+        LogType::Editor,
+        LogLevel::Warning,
+        LogCategory::Entity,
+        "{} references to the entities being deleted, asking what to do with them",
+        references.len()
+    );
+    *world.resource_mut::<PendingDelete>() = PendingDelete {
+        entities,
+        references,
+        names,
+        target_names,
+        candidates,
+        reassign_to: None,
+        choice: None,
+    };
+    world.write_message(PopupMenuRequestedEvent {
+        popup: PopupType::SafeDelete,
+        mouse_pos: Vec2::ZERO,
+    });
+}
+
+fn apply_choice(world: &mut World, pending: PendingDelete, choice: SafeDeleteChoice) {
+    let replacement = match choice {
+        SafeDeleteChoice::Cancel => {
+            log!(
+                LogType::Editor,
+                LogLevel::Info,
+                LogCategory::Entity,
+                "Delete cancelled"
+            );
+            return;
+        }
+        SafeDeleteChoice::Clear => None,
+        SafeDeleteChoice::Reassign(entity) => {
+            let Some(identity) = world.get::<IdentityData>(entity) else {
+                log!(
+                    LogType::Editor,
+                    LogLevel::Error,
+                    LogCategory::Entity,
+                    "Could not reassign references, {} is gone",
+                    entity
+                );
+                return;
+            };
+            Some(identity.uuid)
+        }
+    };
+
+    // Referencing entities may have been deleted while the prompt was open
+    let references: Vec<EntityReference> = pending
+        .references
+        .into_iter()
+        .filter(|reference| world.get_entity(reference.entity).is_ok())
+        .collect();
+    let changed = retarget_entity_references(world, &references, replacement);
+    log!(
+        LogType::Editor,
+        LogLevel::OK,
+        LogCategory::Entity,
+        "{} references on {} components before deleting {} entities",
+        if replacement.is_some() {
+            "Reassigned"
+        } else {
+            "Cleared"
+        },
+        changed,
+        pending.entities.len()
+    );
+    despawn(world, &pending.entities);
+}
+
+fn despawn(world: &mut World, entities: &[Entity]) {
+    trash_entities(world, entities);
+    for entity in entities {
+        if let Ok(entity) = world.get_entity_mut(*entity) {
+            entity.despawn();
+        }
+    }
+}
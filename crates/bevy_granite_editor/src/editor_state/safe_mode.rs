@@ -0,0 +1,179 @@
+use bevy::{
+    diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin},
+    ecs::{query::With, resource::Resource},
+    prelude::{Query, Res, ResMut},
+};
+use bevy_granite_core::IdentityData;
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+
+// safe_mode.rs
+// Degrades editor-only costs once a scene gets too big or too slow to edit comfortably
+// Nothing here touches the saved editor config, the degraded features come back when safe mode ends
+
+/// Leave safe mode once the entity count drops below this share of the threshold
+const EXIT_RATIO: f32 = 0.8;
+/// Frames the average frame time has to stay slow for, so loading hitches don't count
+const SLOW_FRAMES_BEFORE_SAFE_MODE: u32 = 120;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SafeModeReason {
+    EntityCount(usize),
+    /// Average frame time in milliseconds
+    FrameTime(f64),
+}
+
+impl SafeModeReason {
+    pub fn describe(&self) -> String {
+        match self {
+            SafeModeReason::EntityCount(count) => format!("{} entities in the scene", count),
+            SafeModeReason::FrameTime(ms) => format!("frames averaging {:.0} ms", ms),
+        }
+    }
+}
+
+/// Turns on past `entity_threshold` serializable entities or `frame_time_threshold_ms`, and cuts the editor costs below
+/// A banner under the top bar says why, each cut can be turned back on from there
+#[derive(Resource, Debug, Clone)]
+pub struct PerformanceSafeMode {
+    /// Serializable entities before safe mode turns on
+    pub entity_threshold: usize,
+    /// Average frame time in milliseconds before safe mode turns on
+    pub frame_time_threshold_ms: f64,
+    /// None follows detection, Some forces safe mode on or off
+    pub override_mode: Option<bool>,
+    /// Which editor costs safe mode cuts, each can be turned back on from the banner
+    pub disable_icons: bool,
+    pub disable_visualizers: bool,
+    pub throttle_tree: bool,
+    pub simple_selection_bounds: bool,
+    reason: Option<SafeModeReason>,
+    slow_frames: u32,
+}
+
+impl Default for PerformanceSafeMode {
+    fn default() -> Self {
+        Self {
+            entity_threshold: 20_000,
+            frame_time_threshold_ms: 50.0,
+            override_mode: None,
+            disable_icons: true,
+            disable_visualizers: true,
+            throttle_tree: true,
+            simple_selection_bounds: true,
+            reason: None,
+            slow_frames: 0,
+        }
+    }
+}
+
+impl PerformanceSafeMode {
+    pub fn is_active(&self) -> bool {
+        self.override_mode.unwrap_or(self.reason.is_some())
+    }
+
+    /// Why detection turned safe mode on, None when it didn't
+    pub fn reason(&self) -> Option<SafeModeReason> {
+        self.reason
+    }
+
+    pub fn icons_disabled(&self) -> bool {
+        self.is_active() && self.disable_icons
+    }
+
+    pub fn visualizers_disabled(&self) -> bool {
+        self.is_active() && self.disable_visualizers
+    }
+
+    pub fn tree_throttled(&self) -> bool {
+        self.is_active() && self.throttle_tree
+    }
+
+    pub fn selection_bounds_simplified(&self) -> bool {
+        self.is_active() && self.simple_selection_bounds
+    }
+
+    /// Back to following detection, clears a latched frame time reason
+    pub fn reset(&mut self) {
+        self.override_mode = None;
+        self.reason = None;
+        self.slow_frames = 0;
+    }
+
+    /// Names of the features safe mode is cutting right now
+    pub fn disabled_features(&self) -> Vec<&'static str> {
+        [
+            (self.icons_disabled(), "class icons"),
+            (self.visualizers_disabled(), "debug visualizers"),
+            (self.tree_throttled(), "live entity tree updates"),
+            (self.selection_bounds_simplified(), "mesh selection bounds"),
+        ]
+        .into_iter()
+        .filter_map(|(disabled, name)| disabled.then_some(name))
+        .collect()
+    }
+}
+
+/// Run condition for class icon spawning
+pub fn safe_mode_allows_icons(safe_mode: Res<PerformanceSafeMode>) -> bool {
+    !safe_mode.icons_disabled()
+}
+
+/// Run condition for the debug visualizer gizmos
+pub fn safe_mode_allows_visualizers(safe_mode: Res<PerformanceSafeMode>) -> bool {
+    !safe_mode.visualizers_disabled()
+}
+
+/// Turn safe mode on when the scene gets too big or too slow, and off again once it shrinks
+/// A slow frame time stays latched until `reset`, since safe mode itself makes frames faster
+pub fn detect_safe_mode_system(
+    mut safe_mode: ResMut<PerformanceSafeMode>,
+    entities: Query<(), With<IdentityData>>,
+    diagnostics: Res<DiagnosticsStore>,
+) {
+    let count = entities.iter().count();
+    let frame_time = diagnostics
+        .get(&FrameTimeDiagnosticsPlugin::FRAME_TIME)
+        .and_then(|frame_time| frame_time.average());
+
+    let slow = frame_time.is_some_and(|ms| ms > safe_mode.frame_time_threshold_ms);
+    let slow_frames = if slow { safe_mode.slow_frames + 1 } else { 0 };
+    if safe_mode.slow_frames != slow_frames {
+        safe_mode.slow_frames = slow_frames;
+    }
+
+    let exit_threshold = (safe_mode.entity_threshold as f32 * EXIT_RATIO) as usize;
+    let reason = match safe_mode.reason {
+        _ if count > safe_mode.entity_threshold => Some(SafeModeReason::EntityCount(count)),
+        Some(SafeModeReason::EntityCount(_)) if count > exit_threshold => {
+            Some(SafeModeReason::EntityCount(count))
+        }
+        Some(SafeModeReason::FrameTime(ms)) => Some(SafeModeReason::FrameTime(ms)),
+        _ => frame_time
+            .filter(|_| slow_frames >= SLOW_FRAMES_BEFORE_SAFE_MODE)
+            .map(SafeModeReason::FrameTime),
+    };
+
+    if reason.is_some() != safe_mode.reason.is_some() {
+        match reason {
+            Some(reason) => log!(
+                LogType::Editor,
+                LogLevel::Warning,
+                LogCategory::System,
+                "Editor safe mode on: {}",
+                reason.describe()
+            ),
+            None => log!(
+                LogType::Editor,
+                LogLevel::Info,
+                LogCategory::System,
+                "Editor safe mode off"
+            ),
+        }
+    }
+    if safe_mode.reason != reason {
+        safe_mode.reason = reason;
+    }
+}
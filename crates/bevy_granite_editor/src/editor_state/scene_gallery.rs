@@ -0,0 +1,217 @@
+use crate::{interface::events::RequestSceneThumbnail, viewport::ViewportCameraState};
+use bevy::{
+    asset::io::file::FileAssetReader,
+    camera::Camera,
+    ecs::observer::On,
+    image::Image,
+    prelude::{Commands, MessageReader, Query, Res, ResMut},
+    render::view::screenshot::{Screenshot, ScreenshotCaptured},
+};
+use bevy_granite_core::{
+    absolute_asset_to_rel, entities::SceneData, is_scene_file, read_scene_file,
+    strip_scene_extension, GraniteJobs, WorldSaveSuccessEvent,
+};
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+// scene_gallery.rs
+// Thumbnails for scene files and the scan behind the scene gallery popup
+// A thumbnail is a small PNG of the viewport stored next to its scene, i.e. scenes/starter.thumb.png
+
+const THUMBNAIL_EXTENSION: &str = "thumb.png";
+const THUMBNAIL_WIDTH: u32 = 256;
+const THUMBNAIL_HEIGHT: u32 = 144;
+/// Folders never scanned for scenes
+const IGNORED_DIRS: [&str; 2] = ["config", "target"];
+
+#[derive(Debug, Clone)]
+pub struct SceneGalleryEntry {
+    /// Scene path relative to assets
+    pub path: String,
+    pub name: String,
+    /// From the scene metadata, None if the file could not be read
+    pub entity_count: Option<usize>,
+    pub modified: Option<SystemTime>,
+    /// Thumbnail path relative to assets, if one was captured
+    pub thumbnail: Option<String>,
+}
+
+/// Relative path of the thumbnail for a scene source
+pub fn thumbnail_path_for_scene(source: &str) -> String {
+    let source = source.replace('\\', "/");
+    format!("{}.{}", strip_scene_extension(&source), THUMBNAIL_EXTENSION)
+}
+
+/// Every scene file under the assets folder, compressed or not, most recently modified first
+pub fn scan_scene_gallery() -> Vec<SceneGalleryEntry> {
+    let assets = FileAssetReader::get_base_path().join("assets");
+    let mut scenes = vec![];
+    collect_scene_files(&assets, &mut scenes);
+
+    let mut entries: Vec<SceneGalleryEntry> = scenes
+        .into_iter()
+        .map(|path| {
+            let rel = absolute_asset_to_rel(path.display().to_string()).to_string();
+            let thumbnail = thumbnail_path_for_scene(&rel);
+            SceneGalleryEntry {
+                name: path
+                    .file_name()
+                    .map(|name| strip_scene_extension(&name.to_string_lossy()).to_string())
+                    .unwrap_or_else(|| rel.clone()),
+                entity_count: read_scene_file(&path.to_string_lossy())
+                    .ok()
+                    .and_then(|contents| ron::de::from_str::<SceneData>(&contents).ok())
+                    .map(|scene| scene.metadata.entity_count),
+                modified: fs::metadata(&path).and_then(|meta| meta.modified()).ok(),
+                thumbnail: assets.join(&thumbnail).exists().then_some(thumbnail),
+                path: rel,
+            }
+        })
+        .collect();
+
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.modified));
+    entries
+}
+
+fn collect_scene_files(dir: &Path, scenes: &mut Vec<PathBuf>) {
+    let Ok(read_dir) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in read_dir.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            let ignored = path
+                .file_name()
+                .is_some_and(|name| IGNORED_DIRS.iter().any(|ignored| name == *ignored));
+            if !ignored {
+                collect_scene_files(&path, scenes);
+            }
+        } else if is_scene_file(&path.to_string_lossy()) {
+            scenes.push(path);
+        }
+    }
+}
+
+/// How long ago a scene was modified, i.e. "5 min ago"
+pub fn format_modified(modified: Option<SystemTime>) -> String {
+    let Some(elapsed) = modified.and_then(|time| time.elapsed().ok()) else {
+        return "Unknown".to_string();
+    };
+
+    let secs = elapsed.as_secs();
+    match secs {
+        0..60 => "Just now".to_string(),
+        60..3600 => format!("{} min ago", secs / 60),
+        3600..86400 => format!("{} h ago", secs / 3600),
+        _ => format!("{} days ago", secs / 86400),
+    }
+}
+
+/// Capture the viewport into a thumbnail after a scene is saved, or when requested from the File menu
+pub fn capture_scene_thumbnail_system(
+    mut commands: Commands,
+    mut readers: (
+        MessageReader<WorldSaveSuccessEvent>,
+        MessageReader<RequestSceneThumbnail>,
+    ),
+    viewport_camera_state: Res<ViewportCameraState>,
+    camera_query: Query<&Camera>,
+) {
+    let mut sources: Vec<String> = readers
+        .0
+        .read()
+        .map(|WorldSaveSuccessEvent(path)| path.clone())
+        .chain(
+            readers
+                .1
+                .read()
+                .map(|RequestSceneThumbnail(path)| path.clone()),
+        )
+        .map(|path| absolute_asset_to_rel(path).to_string())
+        .collect();
+    sources.sort();
+    sources.dedup();
+    if sources.is_empty() {
+        return;
+    }
+
+    // Crop the editor panels out, only the viewport ends up in the thumbnail
+    let viewport = viewport_camera_state
+        .active_camera()
+        .and_then(|camera| camera_query.get(camera).ok())
+        .and_then(|camera| camera.viewport.clone())
+        .map(|viewport| (viewport.physical_position, viewport.physical_size));
+
+    let targets: Vec<PathBuf> = sources
+        .iter()
+        .map(|source| {
+            FileAssetReader::get_base_path()
+                .join("assets")
+                .join(thumbnail_path_for_scene(source))
+        })
+        .collect();
+
+    commands.spawn(Screenshot::primary_window()).observe(
+        move |captured: On<ScreenshotCaptured>, mut jobs: ResMut<GraniteJobs>| {
+            // Cropping, scaling and encoding run as a background job, saving stays hitch free
+            let image = captured.image.clone();
+            let targets = targets.clone();
+            jobs.spawn("thumbnail", "Scene thumbnail", move |context| {
+                for (index, target) in targets.iter().enumerate() {
+                    if context.is_cancelled() {
+                        break;
+                    }
+                    save_thumbnail(&image, viewport, target);
+                    context.set_progress((index + 1) as f32 / targets.len() as f32);
+                }
+                Ok(Vec::new())
+            });
+        },
+    );
+}
+
+fn save_thumbnail(
+    image: &Image,
+    viewport: Option<(bevy::math::UVec2, bevy::math::UVec2)>,
+    target: &Path,
+) {
+    let result = image
+        .clone()
+        .try_into_dynamic()
+        .map_err(|e| e.to_string())
+        .and_then(|mut dynamic| {
+            if let Some((position, size)) = viewport {
+                dynamic = dynamic.crop_imm(position.x, position.y, size.x, size.y);
+            }
+            dynamic
+                .thumbnail(THUMBNAIL_WIDTH, THUMBNAIL_HEIGHT)
+                .to_rgb8()
+                .save(target)
+                .map_err(|e| e.to_string())
+        });
+
+    match result {
+        Ok(()) => log!(
+            LogType::Editor,
+            LogLevel::Info,
+            LogCategory::System,
+            "Saved scene thumbnail: {}",
+            target.display()
+        ),
+        Err(e) => log!(
+            LogType::Editor,
+            LogLevel::Error,
+            LogCategory::System,
+            "Failed to save scene thumbnail '{}': {}",
+            target.display(),
+            e
+        ),
+    }
+}
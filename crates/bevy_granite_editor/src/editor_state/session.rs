@@ -0,0 +1,274 @@
+use crate::{
+    editor_state::EditorState,
+    interface::{SideDockState, SideTab},
+    utils::{load_from_toml_file, save_to_toml_file},
+    viewport::{CameraTarget, VisualizationConfig},
+};
+use bevy::{
+    asset::io::file::FileAssetReader,
+    prelude::{
+        Commands, Entity, MessageReader, Query, Res, ResMut, Resource, Transform, With, Without,
+    },
+    time::{Real, Time},
+    window::WindowClosing,
+};
+use bevy_granite_core::{
+    absolute_asset_to_rel, IdentityData, SpawnSource, UICamera, WorldLoadSuccessEvent,
+    WorldSaveSuccessEvent,
+};
+use bevy_granite_gizmos::{selection::events::EntityEvents, ActiveSelection, Selected};
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+// session.rs
+// Per-scene editor session sidecar. Stores where the user left off (camera, expanded tree nodes, selection, visualizers)
+// Sidecars live in config/sessions so they don't clutter the scene folders
+
+const SESSION_DIR: &str = "config/sessions";
+/// Frames to wait for a loaded scene's entities to show up in the node tree before restoring anyway
+const RESTORE_MAX_FRAMES: u32 = 30;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct SceneSessionData {
+    pub camera_position: [f32; 3],
+    pub camera_rotation: [f32; 4],
+    pub camera_target: [f32; 3],
+    pub expanded: Vec<Uuid>,
+    pub selection: Vec<Uuid>,
+    pub active: Option<Uuid>,
+    pub visualizers: Option<VisualizationConfig>,
+}
+
+#[derive(Resource, Clone)]
+pub struct SceneSessionTracker {
+    pub time_since_last_save: f32,
+    pub save_interval: f32,
+    /// Scene source and session waiting for its entities to spawn
+    pub pending_restore: Option<(String, SceneSessionData, u32)>,
+}
+
+impl Default for SceneSessionTracker {
+    fn default() -> Self {
+        Self {
+            time_since_last_save: 0.0,
+            save_interval: 30.0,
+            pending_restore: None,
+        }
+    }
+}
+
+/// Absolute path of the session sidecar for a scene source
+pub fn session_path_for_scene(source: &str) -> String {
+    let file_name = source.replace(['/', '\\'], "_") + ".toml";
+    FileAssetReader::get_base_path()
+        .join("assets")
+        .join(SESSION_DIR)
+        .join(file_name)
+        .display()
+        .to_string()
+}
+
+/// Snapshot the editor's current session for the active scene and write it to disk
+pub fn save_scene_session_system(
+    time: Res<Time<Real>>,
+    mut tracker: ResMut<SceneSessionTracker>,
+    mut readers: (
+        MessageReader<WorldSaveSuccessEvent>,
+        MessageReader<WindowClosing>,
+    ),
+    editor_state: Res<EditorState>,
+    side_dock: Res<SideDockState>,
+    camera: (Res<CameraTarget>, Query<&Transform, With<UICamera>>),
+    identity_query: Query<(
+        &IdentityData,
+        &SpawnSource,
+        Option<&Selected>,
+        Option<&ActiveSelection>,
+    )>,
+) {
+    tracker.time_since_last_save += time.delta_secs();
+
+    let saved = readers.0.read().count() > 0;
+    let closing = readers.1.read().count() > 0;
+    let interval_elapsed = tracker.time_since_last_save >= tracker.save_interval;
+    if !(saved || closing || (interval_elapsed && editor_state.active)) {
+        return;
+    }
+    tracker.time_since_last_save = 0.0;
+
+    // Don't overwrite a session we haven't restored yet
+    if tracker.pending_restore.is_some() {
+        return;
+    }
+
+    let Some(source) = editor_state.current_file.clone() else {
+        return;
+    };
+
+    let mut session = SceneSessionData {
+        camera_target: camera.0.position.to_array(),
+        visualizers: Some(editor_state.config.viewport.visualizers),
+        ..Default::default()
+    };
+
+    if let Ok(transform) = camera.1.single() {
+        session.camera_position = transform.translation.to_array();
+        session.camera_rotation = transform.rotation.to_array();
+    }
+
+    for (identity, spawn_source, selected, active) in identity_query.iter() {
+        if spawn_source.str_ref() != source {
+            continue;
+        }
+        if active.is_some() {
+            session.active = Some(identity.uuid);
+        } else if selected.is_some() {
+            session.selection.push(identity.uuid);
+        }
+    }
+
+    for (_, tab) in side_dock.dock_state.iter_all_tabs() {
+        if let SideTab::NodeTree { data, .. } = tab {
+            session.expanded = data
+                .hierarchy
+                .iter()
+                .filter(|entry| entry.is_expanded)
+                .filter_map(|entry| identity_query.get(entry.entity).ok())
+                .filter(|(_, spawn_source, _, _)| spawn_source.str_ref() == source)
+                .map(|(identity, _, _, _)| identity.uuid)
+                .collect();
+        }
+    }
+
+    let path = session_path_for_scene(&source);
+    if let Err(e) = save_to_toml_file(&session, &path) {
+        log!(
+            LogType::Editor,
+            LogLevel::Error,
+            LogCategory::System,
+            "Failed to save editor session for '{}': {}",
+            source,
+            e
+        );
+    }
+}
+
+/// Queue a session restore for freshly loaded scenes that have a sidecar
+pub fn queue_scene_session_restore_system(
+    mut load_reader: MessageReader<WorldLoadSuccessEvent>,
+    mut tracker: ResMut<SceneSessionTracker>,
+) {
+    for WorldLoadSuccessEvent(path) in load_reader.read() {
+        let source = absolute_asset_to_rel(path.to_string()).to_string();
+        if let Ok(session) =
+            load_from_toml_file::<SceneSessionData>(&session_path_for_scene(&source))
+        {
+            tracker.pending_restore = Some((source, session, 0));
+        }
+    }
+}
+
+/// Applies a pending session once the scene entities exist and the node tree knows about them
+pub fn apply_scene_session_system(
+    mut commands: Commands,
+    mut tracker: ResMut<SceneSessionTracker>,
+    mut editor_state: ResMut<EditorState>,
+    mut camera_target: ResMut<CameraTarget>,
+    mut side_dock: ResMut<SideDockState>,
+    mut ui_camera: Query<&mut Transform, (With<UICamera>, Without<IdentityData>)>,
+    identity_query: Query<(Entity, &IdentityData, &SpawnSource)>,
+) {
+    let Some((source, _, frames)) = tracker.pending_restore.as_mut() else {
+        return;
+    };
+    *frames += 1;
+
+    let by_uuid: HashMap<Uuid, Entity> = identity_query
+        .iter()
+        .filter(|(_, _, spawn_source)| spawn_source.str_ref() == source.as_str())
+        .map(|(entity, identity, _)| (identity.uuid, entity))
+        .collect();
+
+    let tree_ready = side_dock
+        .dock_state
+        .iter_all_tabs()
+        .any(|(_, tab)| match tab {
+            SideTab::NodeTree { data, .. } => {
+                let in_tree: HashSet<Entity> =
+                    data.hierarchy.iter().map(|entry| entry.entity).collect();
+                by_uuid.values().all(|entity| in_tree.contains(entity))
+            }
+            _ => false,
+        });
+    if (by_uuid.is_empty() || !tree_ready) && *frames < RESTORE_MAX_FRAMES {
+        return;
+    }
+
+    let Some((source, session, _)) = tracker.pending_restore.take() else {
+        return;
+    };
+
+    if let Ok(mut transform) = ui_camera.single_mut() {
+        transform.translation = session.camera_position.into();
+        transform.rotation = bevy::math::Quat::from_array(session.camera_rotation);
+    }
+    camera_target.position = session.camera_target.into();
+
+    let expanded: HashSet<Entity> = session
+        .expanded
+        .iter()
+        .filter_map(|uuid| by_uuid.get(uuid).copied())
+        .collect();
+    for (_, tab) in side_dock.dock_state.iter_all_tabs_mut() {
+        match tab {
+            SideTab::NodeTree { data, .. } => {
+                for entry in data.hierarchy.iter_mut() {
+                    if expanded.contains(&entry.entity) {
+                        entry.is_expanded = true;
+                    }
+                }
+                data.tree_cache_dirty = true;
+            }
+            SideTab::EditorSettings { data, .. } => {
+                if let Some(visualizers) = session.visualizers {
+                    data.viewport.visualizers = visualizers;
+                    data.viewport.changed = true;
+                }
+            }
+            _ => {}
+        }
+    }
+    if let Some(visualizers) = session.visualizers {
+        editor_state.config.viewport.visualizers = visualizers;
+    }
+
+    commands.trigger(EntityEvents::DeselectAll);
+    for uuid in session.selection.iter() {
+        if let Some(entity) = by_uuid.get(uuid) {
+            commands.trigger(EntityEvents::Select {
+                target: *entity,
+                additive: true,
+            });
+        }
+    }
+    // Selecting the active entity last makes it the active selection
+    if let Some(entity) = session.active.and_then(|uuid| by_uuid.get(&uuid)) {
+        commands.trigger(EntityEvents::Select {
+            target: *entity,
+            additive: true,
+        });
+    }
+
+    log!(
+        LogType::Editor,
+        LogLevel::Info,
+        LogCategory::System,
+        "Restored editor session for '{}'",
+        source
+    );
+}
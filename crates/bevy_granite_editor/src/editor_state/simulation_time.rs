@@ -0,0 +1,85 @@
+use crate::{editor_state::EditorState, interface::events::RequestSimulationTime};
+use bevy::prelude::{MessageReader, Res, ResMut, Resource, Time, Virtual};
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+
+// simulation_time.rs
+// Pause, single step and time scale for the game, from the editor toolbar. They only touch Time<Virtual>,
+// editor systems read Time<Real> so the camera, autosave and the rest keep going while the game is paused.
+// Whatever the editor changed is put back when it closes, a pause the game set itself is left alone
+
+pub const MIN_SIMULATION_SPEED: f32 = 0.1;
+pub const MAX_SIMULATION_SPEED: f32 = 4.0;
+/// Speeds offered in the toolbar
+pub const SIMULATION_SPEED_PRESETS: [f32; 6] = [0.1, 0.25, 0.5, 1.0, 2.0, 4.0];
+
+/// What the editor did to Time<Virtual>, see RequestSimulationTime
+#[derive(Resource, Default, Debug, Clone)]
+pub struct SimulationTime {
+    /// Virtual time was paused or scaled from the editor
+    pub controlled: bool,
+    /// Unpaused for a single step, paused again next frame
+    stepping: bool,
+}
+
+impl SimulationTime {
+    pub fn stepping(&self) -> bool {
+        self.stepping
+    }
+}
+
+/// Pauses, steps and scales virtual time, and hands it back to the game when the editor closes
+pub fn handle_simulation_time_system(
+    mut simulation: ResMut<SimulationTime>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    editor_state: Res<EditorState>,
+    mut reader: MessageReader<RequestSimulationTime>,
+) {
+    // Virtual time advanced once since the step was asked for
+    if simulation.stepping {
+        simulation.stepping = false;
+        virtual_time.pause();
+    }
+
+    if !editor_state.active {
+        reader.clear();
+        if simulation.controlled {
+            simulation.controlled = false;
+            virtual_time.unpause();
+            virtual_time.set_relative_speed(1.0);
+        }
+        return;
+    }
+
+    for request in reader.read() {
+        simulation.controlled = true;
+        match request {
+            RequestSimulationTime::TogglePause => {
+                if virtual_time.is_paused() {
+                    virtual_time.unpause();
+                } else {
+                    virtual_time.pause();
+                }
+            }
+            RequestSimulationTime::Step => {
+                if virtual_time.is_paused() {
+                    virtual_time.unpause();
+                    simulation.stepping = true;
+                }
+            }
+            RequestSimulationTime::SetSpeed(speed) => {
+                let speed = speed.clamp(MIN_SIMULATION_SPEED, MAX_SIMULATION_SPEED);
+                virtual_time.set_relative_speed(speed);
+                log!(
+                    LogType::Editor,
+                    LogLevel::Info,
+                    LogCategory::System,
+                    "Simulation speed {:.2}x",
+                    speed
+                );
+            }
+        }
+    }
+}
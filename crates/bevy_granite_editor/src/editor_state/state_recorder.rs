@@ -0,0 +1,132 @@
+use bevy::prelude::{Entity, GlobalTransform, Resource, Time, Transform, Virtual, World};
+use bevy_granite_core::ComponentEditor;
+use std::collections::{BTreeMap, VecDeque};
+
+// state_recorder.rs
+// Samples the transforms and saved components of the entities selected when recording started, once per frame
+// of game time, into a ring buffer. The Debug tab scrubs through it and the viewport draws the trails and a ghost
+// at the reviewed frame. Frames where virtual time is paused aren't sampled, so pausing keeps the buffer as is
+
+/// Frames kept by default, about ten seconds at 60 fps
+pub const DEFAULT_RECORDER_CAPACITY: usize = 600;
+pub const MAX_RECORDER_CAPACITY: usize = 6000;
+
+/// One entity as it was in a recorded frame
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecordedEntity {
+    pub entity: Entity,
+    pub transform: Transform,
+    /// Serialized like the scene file saves them, by type path
+    pub components: BTreeMap<String, String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecordedFrame {
+    /// Elapsed virtual time
+    pub time: f32,
+    pub entities: Vec<RecordedEntity>,
+}
+
+/// Ring buffer of `capacity` frames, sampled while `recording`. Filled from the Debug tab's `Record Selection`
+#[derive(Resource, Debug)]
+pub struct StateRecorder {
+    pub recording: bool,
+    pub capacity: usize,
+    /// Entities sampled each frame, with their names
+    pub tracked: Vec<(Entity, String)>,
+    pub frames: VecDeque<RecordedFrame>,
+    /// Frame under review, none follows the latest
+    pub scrub: Option<usize>,
+    /// Draw trails and the reviewed ghost in the viewport
+    pub trails: bool,
+}
+
+impl Default for StateRecorder {
+    fn default() -> Self {
+        Self {
+            recording: false,
+            capacity: DEFAULT_RECORDER_CAPACITY,
+            tracked: Vec::new(),
+            frames: VecDeque::new(),
+            scrub: None,
+            trails: true,
+        }
+    }
+}
+
+impl StateRecorder {
+    /// Start over with new entities
+    pub fn start(&mut self, tracked: Vec<(Entity, String)>) {
+        self.clear();
+        self.tracked = tracked;
+        self.recording = !self.tracked.is_empty();
+    }
+
+    pub fn clear(&mut self) {
+        self.frames.clear();
+        self.scrub = None;
+    }
+
+    /// Frame under review, or the latest one
+    pub fn reviewed_frame(&self) -> Option<&RecordedFrame> {
+        match self.scrub {
+            Some(index) => self.frames.get(index),
+            None => self.frames.back(),
+        }
+    }
+
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.clamp(1, MAX_RECORDER_CAPACITY);
+        self.trim();
+    }
+
+    fn push(&mut self, frame: RecordedFrame) {
+        self.frames.push_back(frame);
+        self.trim();
+    }
+
+    /// Drop the oldest frames past capacity, keeping the reviewed frame under review
+    fn trim(&mut self) {
+        while self.frames.len() > self.capacity {
+            self.frames.pop_front();
+            self.scrub = self.scrub.map(|index| index.saturating_sub(1));
+        }
+    }
+}
+
+/// Samples the tracked entities while recording and game time is running
+pub fn record_entity_state_system(world: &mut World) {
+    let Some(recorder) = world.get_resource::<StateRecorder>() else {
+        return;
+    };
+    if !recorder.recording {
+        return;
+    }
+    let time = world.resource::<Time<Virtual>>();
+    if time.delta_secs() <= 0.0 {
+        return;
+    }
+
+    let component_editor = world.resource::<ComponentEditor>();
+    let entities: Vec<RecordedEntity> = recorder
+        .tracked
+        .iter()
+        .filter_map(|(entity, _)| {
+            let transform = world.get::<GlobalTransform>(*entity)?.compute_transform();
+            let components = component_editor
+                .serialize_entity_components(world, *entity)
+                .into_iter()
+                .collect();
+            Some(RecordedEntity {
+                entity: *entity,
+                transform,
+                components,
+            })
+        })
+        .collect();
+    let frame = RecordedFrame {
+        time: time.elapsed_secs(),
+        entities,
+    };
+    world.resource_mut::<StateRecorder>().push(frame);
+}
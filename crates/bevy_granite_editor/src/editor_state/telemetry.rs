@@ -0,0 +1,249 @@
+use crate::editor_state::{EditorCommand, EditorCommandReaders, EditorState};
+use bevy::{
+    app::AppExit,
+    ecs::system::SystemParam,
+    platform::collections::HashMap,
+    prelude::{MessageReader, Query, Res, ResMut, Resource, With},
+    time::{Real, Time},
+};
+use bevy_granite_core::{
+    absolute_asset_to_rel, rel_asset_to_absolute, IdentityData, SpawnSource, WorldLoadSuccessEvent,
+    WorldSaveSuccessEvent,
+};
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+use serde::Serialize;
+use std::{
+    fs::OpenOptions,
+    io::Write,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+// telemetry.rs
+// Opt-in local usage telemetry, off unless turned on in the editor settings
+// Records are appended as RON lines to a local file, nothing leaves the machine
+// Records are anonymized: a random id per session, no paths, names or entity data
+
+pub const TELEMETRY_FILE: &str = "config/editor_telemetry.log";
+const FLUSH_INTERVAL: f32 = 10.0;
+
+#[derive(Serialize, Debug, Clone)]
+pub enum TelemetryEvent {
+    SessionStart,
+    Command {
+        name: &'static str,
+    },
+    Save {
+        duration_ms: u64,
+        entities: usize,
+    },
+    Load {
+        duration_ms: u64,
+        entities: usize,
+    },
+    /// Command totals of the session, written when the editor closes or telemetry is turned off
+    SessionEnd {
+        duration_secs: u64,
+        commands: Vec<(&'static str, u32)>,
+    },
+}
+
+#[derive(Serialize, Debug, Clone)]
+struct TelemetryRecord {
+    session: String,
+    timestamp: u64,
+    event: TelemetryEvent,
+}
+
+/// Opt-in usage records: command counts, save and load durations and scene entity counts
+/// Written to assets/config/editor_telemetry.log, one RON record per line, while `Usage Telemetry` is on in the editor settings
+#[derive(Resource)]
+pub struct EditorTelemetry {
+    session: String,
+    started: Option<Instant>,
+    command_counts: HashMap<&'static str, u32>,
+    /// Scene path to when its save or load was requested
+    pending_saves: HashMap<String, Instant>,
+    pending_loads: HashMap<String, Instant>,
+    /// Loads that finished this frame, counted next frame once their entities are spawned
+    finished_loads: Vec<(String, Instant)>,
+    buffer: Vec<TelemetryRecord>,
+    since_flush: f32,
+}
+
+impl Default for EditorTelemetry {
+    fn default() -> Self {
+        Self {
+            session: uuid::Uuid::new_v4().to_string(),
+            started: None,
+            command_counts: HashMap::default(),
+            pending_saves: HashMap::default(),
+            pending_loads: HashMap::default(),
+            finished_loads: Vec::new(),
+            buffer: Vec::new(),
+            since_flush: 0.0,
+        }
+    }
+}
+
+impl EditorTelemetry {
+    /// Recording right now, only true while the settings toggle is on
+    pub fn is_recording(&self) -> bool {
+        self.started.is_some()
+    }
+
+    /// Commands used so far this session
+    pub fn command_counts(&self) -> impl Iterator<Item = (&'static str, u32)> + '_ {
+        self.command_counts
+            .iter()
+            .map(|(name, count)| (*name, *count))
+    }
+
+    fn record(&mut self, event: TelemetryEvent) {
+        self.buffer.push(TelemetryRecord {
+            session: self.session.clone(),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .unwrap_or(0),
+            event,
+        });
+    }
+
+    fn command(&mut self, name: &'static str) {
+        *self.command_counts.entry(name).or_insert(0) += 1;
+        self.record(TelemetryEvent::Command { name });
+    }
+
+    fn end_session(&mut self) {
+        let Some(started) = self.started.take() else {
+            return;
+        };
+        let mut commands: Vec<(&'static str, u32)> = self.command_counts().collect();
+        commands.sort();
+        self.record(TelemetryEvent::SessionEnd {
+            duration_secs: started.elapsed().as_secs(),
+            commands,
+        });
+        self.command_counts.clear();
+        self.pending_saves.clear();
+        self.pending_loads.clear();
+        self.finished_loads.clear();
+    }
+
+    fn flush(&mut self) {
+        self.since_flush = 0.0;
+        if self.buffer.is_empty() {
+            return;
+        }
+        let path = rel_asset_to_absolute(TELEMETRY_FILE).to_string();
+        let lines: String = self
+            .buffer
+            .drain(..)
+            .filter_map(|record| ron::to_string(&record).ok())
+            .map(|line| line + "\n")
+            .collect();
+        let written = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| file.write_all(lines.as_bytes()));
+        if let Err(e) = written {
+            log!(
+                LogType::Editor,
+                LogLevel::Warning,
+                LogCategory::System,
+                "Failed to write telemetry to '{}': {}",
+                path,
+                e
+            );
+        }
+    }
+}
+
+/// Messages telemetry reads besides the editor commands
+#[derive(SystemParam)]
+pub struct TelemetryReaders<'w, 's> {
+    commands: EditorCommandReaders<'w, 's>,
+    saved: MessageReader<'w, 's, WorldSaveSuccessEvent>,
+    loaded: MessageReader<'w, 's, WorldLoadSuccessEvent>,
+    exit: MessageReader<'w, 's, AppExit>,
+}
+
+fn scene_entity_count(sources: &Query<&SpawnSource, With<IdentityData>>, rel: &str) -> usize {
+    sources
+        .iter()
+        .filter(|source| source.str_ref() == rel)
+        .count()
+}
+
+/// Counts editor commands and times saves and loads while telemetry is turned on
+/// Readers are drained either way, so turning it on never picks up old messages
+pub fn record_telemetry_system(
+    editor_state: Res<EditorState>,
+    mut telemetry: ResMut<EditorTelemetry>,
+    mut readers: TelemetryReaders,
+    sources: Query<&SpawnSource, With<IdentityData>>,
+    time: Res<Time<Real>>,
+) {
+    let rel = |path: &str| absolute_asset_to_rel(path.to_string()).to_string();
+    let now = Instant::now();
+    let commands = readers.commands.read();
+    let saved: Vec<String> = readers.saved.read().map(|event| rel(&event.0)).collect();
+    let loaded: Vec<String> = readers.loaded.read().map(|event| rel(&event.0)).collect();
+    let exiting = readers.exit.read().count() > 0;
+
+    let enabled = editor_state.config.telemetry_enabled;
+    if !enabled {
+        if telemetry.is_recording() {
+            telemetry.end_session();
+            telemetry.flush();
+        }
+        return;
+    }
+    if !telemetry.is_recording() {
+        telemetry.started = Some(now);
+        telemetry.record(TelemetryEvent::SessionStart);
+    }
+
+    // Entities of a load are spawned through commands, so they are counted the frame after
+    for (path, requested) in std::mem::take(&mut telemetry.finished_loads) {
+        telemetry.record(TelemetryEvent::Load {
+            duration_ms: requested.elapsed().as_millis() as u64,
+            entities: scene_entity_count(&sources, &path),
+        });
+    }
+    for EditorCommand { name, scene } in commands {
+        telemetry.command(name);
+        match (name, scene) {
+            ("save", Some(scene)) => {
+                telemetry.pending_saves.entry(scene).or_insert(now);
+            }
+            ("load", Some(scene)) => {
+                telemetry.pending_loads.entry(scene).or_insert(now);
+            }
+            _ => {}
+        }
+    }
+    for path in saved {
+        let requested = telemetry.pending_saves.remove(&path).unwrap_or(now);
+        telemetry.record(TelemetryEvent::Save {
+            duration_ms: requested.elapsed().as_millis() as u64,
+            entities: scene_entity_count(&sources, &path),
+        });
+    }
+    for path in loaded {
+        let requested = telemetry.pending_loads.remove(&path).unwrap_or(now);
+        telemetry.finished_loads.push((path, requested));
+    }
+
+    telemetry.since_flush += time.delta_secs();
+    if exiting {
+        telemetry.end_session();
+        telemetry.flush();
+    } else if telemetry.since_flush >= FLUSH_INTERVAL {
+        telemetry.flush();
+    }
+}
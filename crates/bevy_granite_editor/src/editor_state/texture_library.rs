@@ -0,0 +1,105 @@
+use bevy::{
+    asset::{io::file::FileAssetReader, AssetServer, Handle},
+    image::Image,
+    prelude::{Res, ResMut, Resource},
+};
+use bevy_egui::{egui, EguiTextureHandle, EguiUserTextures};
+use bevy_granite_core::absolute_asset_to_rel;
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+// texture_library.rs
+// Every image under assets/textures with an egui thumbnail, for the texture picker of material fields
+// Scanned when a picker is opened, so textures added while the editor runs show up next time
+
+pub const TEXTURE_LIBRARY_DIR: &str = "textures";
+/// Same as the texture file dialogs used to accept
+const TEXTURE_EXTENSIONS: [&str; 3] = ["png", "jpg", "jpeg"];
+
+/// Textures the picker of material texture fields offers, recently picked first
+#[derive(Resource, Default)]
+pub struct TextureLibrary {
+    /// Image paths relative to assets, sorted
+    pub textures: Vec<String>,
+    /// Thumbnails by texture path
+    pub thumbnails: HashMap<String, egui::TextureId>,
+    images: HashMap<String, Handle<Image>>,
+    pub needs_scan: bool,
+}
+
+/// Every texture image under assets/textures, relative to assets
+pub fn scan_texture_library() -> Vec<String> {
+    let dir = FileAssetReader::get_base_path()
+        .join("assets")
+        .join(TEXTURE_LIBRARY_DIR);
+    let mut files = vec![];
+    collect_texture_files(&dir, &mut files);
+
+    let mut textures: Vec<String> = files
+        .into_iter()
+        .map(|path| absolute_asset_to_rel(path.display().to_string()).replace('\\', "/"))
+        .collect();
+    textures.sort();
+    textures
+}
+
+fn collect_texture_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(read_dir) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in read_dir.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_texture_files(&path, files);
+        } else if path.extension().is_some_and(|extension| {
+            TEXTURE_EXTENSIONS
+                .iter()
+                .any(|known| extension.eq_ignore_ascii_case(known))
+        }) {
+            files.push(path);
+        }
+    }
+}
+
+/// Rescans the texture folder when a picker asks, registering a thumbnail for new images and dropping removed ones
+pub fn prepare_texture_library_system(
+    mut library: ResMut<TextureLibrary>,
+    asset_server: Res<AssetServer>,
+    mut user_textures: ResMut<EguiUserTextures>,
+) {
+    if !library.needs_scan {
+        return;
+    }
+    library.needs_scan = false;
+    let textures = scan_texture_library();
+
+    let removed: Vec<String> = library
+        .images
+        .keys()
+        .filter(|path| !textures.contains(path))
+        .cloned()
+        .collect();
+    for path in removed {
+        library.thumbnails.remove(&path);
+        if let Some(image) = library.images.remove(&path) {
+            user_textures.remove_image(&image);
+        }
+    }
+
+    for path in textures.iter() {
+        if library.images.contains_key(path) {
+            continue;
+        }
+        let handle = asset_server.load::<Image>(path.clone());
+        let texture = user_textures.add_image(EguiTextureHandle::Strong(handle.clone()));
+        library.thumbnails.insert(path.clone(), texture);
+        library.images.insert(path.clone(), handle);
+    }
+
+    if library.textures != textures {
+        library.textures = textures;
+    }
+}
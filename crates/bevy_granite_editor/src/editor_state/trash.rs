@@ -0,0 +1,295 @@
+use crate::{
+    editor_state::EditorState,
+    interface::events::{RequestEmptyTrash, RequestRestoreFromTrash},
+};
+use bevy::{
+    ecs::system::SystemState,
+    prelude::{Entity, MessageReader, Resource, World},
+};
+use bevy_granite_core::{
+    absolute_asset_to_rel, read_scene_entities, rel_asset_to_absolute, scene_to_string,
+    DirtyScenes, EditorCapabilities, EntitySaveReadyData, GraniteSnapshot, SaveSettings,
+    SnapshotEntity, SpawnSource, WorldLoadSuccessEvent, WorldSaveSuccessEvent,
+};
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+use std::{collections::BTreeSet, fs};
+
+// trash.rs
+// Deleted entities go to a trash instead of being lost, so an accidental delete of many entities can be brought back
+// Each delete is one entry. Saving a scene purges the entries of that scene, and emptying the trash purges everything
+// With trash persistence on, each scene's entries are also written next to it as .scene.trash and read back on load
+
+pub const TRASH_EXTENSION: &str = "trash";
+/// Most deletes kept before the oldest are purged
+const TRASH_LIMIT: usize = 50;
+
+/// i.e. scenes/level_1.scene becomes <assets>/scenes/level_1.scene.trash
+pub fn trash_path_for_scene(scene: &str) -> String {
+    format!("{}.{}", rel_asset_to_absolute(scene), TRASH_EXTENSION)
+}
+
+/// Entities removed by one delete, parents before children
+#[derive(Debug, Clone)]
+pub struct TrashEntry {
+    pub label: String,
+    pub entities: Vec<SnapshotEntity>,
+}
+
+impl TrashEntry {
+    /// Scenes the entities were deleted from, relative to assets
+    pub fn sources(&self) -> BTreeSet<String> {
+        self.entities
+            .iter()
+            .filter_map(|entity| entity.source.as_ref())
+            .map(|source| source.str_ref().to_string())
+            .collect()
+    }
+}
+
+/// Deletes made in the editor, kept for the session and restored with their uuids, parents and components
+/// Shown in the Trash tab, RequestRestoreFromTrash and RequestEmptyTrash do the same from code
+#[derive(Resource, Default, Debug, Clone)]
+pub struct EntityTrash {
+    /// Oldest first
+    entries: Vec<TrashEntry>,
+}
+
+impl EntityTrash {
+    /// Entries, newest first
+    pub fn iter(&self) -> impl Iterator<Item = &TrashEntry> {
+        self.entries.iter().rev()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn push(&mut self, entry: TrashEntry) {
+        if entry.entities.is_empty() {
+            return;
+        }
+        self.entries.push(entry);
+        if self.entries.len() > TRASH_LIMIT {
+            self.entries.remove(0);
+        }
+    }
+
+    /// Takes an entry out by its place in `iter`, newest first
+    pub fn take(&mut self, index: usize) -> Option<TrashEntry> {
+        let index = self.entries.len().checked_sub(index + 1)?;
+        Some(self.entries.remove(index))
+    }
+
+    /// Drops the entities of a scene from every entry. Returns how many were dropped
+    pub fn purge_source(&mut self, source: &str) -> usize {
+        let mut purged = 0;
+        for entry in self.entries.iter_mut() {
+            let before = entry.entities.len();
+            entry.entities.retain(|entity| {
+                entity
+                    .source
+                    .as_ref()
+                    .is_none_or(|entity_source| entity_source.str_ref() != source)
+            });
+            purged += before - entry.entities.len();
+        }
+        self.entries.retain(|entry| !entry.entities.is_empty());
+        purged
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Every trashed entity of a scene, oldest delete first
+    fn scene_entities(&self, source: &str) -> Vec<EntitySaveReadyData> {
+        self.entries
+            .iter()
+            .flat_map(|entry| entry.entities.iter())
+            .filter(|entity| {
+                entity
+                    .source
+                    .as_ref()
+                    .is_some_and(|s| s.str_ref() == source)
+            })
+            .map(|entity| entity.data.clone())
+            .collect()
+    }
+}
+
+/// Captures the entities into the trash, called right before they are despawned
+pub fn trash_entities(world: &mut World, entities: &[Entity]) {
+    let captured = GraniteSnapshot::capture_entities(world, entities);
+    if captured.is_empty() {
+        return;
+    }
+    let label = match captured.as_slice() {
+        [only] => only.data.identity.name.clone(),
+        [first, rest @ ..] => format!("{} and {} more", first.data.identity.name, rest.len()),
+        [] => unreachable!(),
+    };
+    let entry = TrashEntry {
+        label,
+        entities: captured,
+    };
+    let sources = entry.sources();
+    world.resource_mut::<EntityTrash>().push(entry);
+    persist_trash(world, sources);
+}
+
+/// Writes the .scene.trash of each scene, or removes it once the scene has nothing in the trash
+fn persist_trash(world: &World, sources: BTreeSet<String>) {
+    if !world.resource::<EditorState>().config.persist_trash {
+        return;
+    }
+    let trash = world.resource::<EntityTrash>();
+    let capabilities = world.resource::<EditorCapabilities>();
+    for source in sources {
+        let path = trash_path_for_scene(&source);
+        if !capabilities.can_save(&path) {
+            continue;
+        }
+        let entities = trash.scene_entities(&source);
+        let result = if entities.is_empty() {
+            match fs::remove_file(&path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.to_string()),
+                _ => Ok(()),
+            }
+        } else {
+            scene_to_string(entities, vec![])
+                .map_err(|e| e.to_string())
+                .and_then(|contents| fs::write(&path, contents).map_err(|e| e.to_string()))
+        };
+        if let Err(e) = result {
+            log!(
+                LogType::Editor,
+                LogLevel::Error,
+                LogCategory::System,
+                "Failed to write trash '{}': {}",
+                path,
+                e
+            );
+        }
+    }
+}
+
+/// Puts every entity of an entry back by uuid, and marks their scenes as changed
+fn restore(world: &mut World, entry: TrashEntry) {
+    let states: Vec<_> = entry
+        .entities
+        .iter()
+        .map(|entity| (entity.data.identity.uuid, Some(entity.clone())))
+        .collect();
+    let restored = GraniteSnapshot::restore_entities(world, &states);
+    let sources = entry.sources();
+    for source in sources.iter() {
+        world.resource_mut::<DirtyScenes>().mark(source.clone());
+    }
+    persist_trash(world, sources);
+    log!(
+        LogType::Editor,
+        LogLevel::OK,
+        LogCategory::Entity,
+        "Restored '{}' from the trash, {} entities",
+        entry.label,
+        restored.len()
+    );
+}
+
+type TrashReaders<'w, 's> = (
+    MessageReader<'w, 's, RequestRestoreFromTrash>,
+    MessageReader<'w, 's, RequestEmptyTrash>,
+    MessageReader<'w, 's, WorldSaveSuccessEvent>,
+    MessageReader<'w, 's, WorldLoadSuccessEvent>,
+);
+
+pub fn trash_system(world: &mut World, state: &mut SystemState<TrashReaders>) {
+    let (mut restore_reader, mut empty_reader, mut save_reader, mut load_reader) =
+        state.get_mut(world);
+    let mut restores: Vec<usize> = restore_reader
+        .read()
+        .map(|RequestRestoreFromTrash(index)| *index)
+        .collect();
+    let empty = empty_reader.read().count() > 0;
+    let saved: Vec<String> = save_reader
+        .read()
+        .map(|WorldSaveSuccessEvent(path)| absolute_asset_to_rel(path.clone()).to_string())
+        .collect();
+    let loaded: Vec<String> = load_reader
+        .read()
+        .map(|WorldLoadSuccessEvent(path)| absolute_asset_to_rel(path.clone()).to_string())
+        .collect();
+
+    // Indices are newest first, take the higher ones first so the rest stay valid
+    restores.sort_unstable();
+    restores.dedup();
+    for index in restores.into_iter().rev() {
+        let entry = world.resource_mut::<EntityTrash>().take(index);
+        if let Some(entry) = entry {
+            restore(world, entry);
+        }
+    }
+
+    // Emptying drops the last copy of the entities, so it needs delete rights
+    if empty && world.resource::<EditorCapabilities>().can_delete("trash") {
+        let sources: BTreeSet<String> = world
+            .resource::<EntityTrash>()
+            .iter()
+            .flat_map(TrashEntry::sources)
+            .collect();
+        world.resource_mut::<EntityTrash>().clear();
+        persist_trash(world, sources);
+        log!(
+            LogType::Editor,
+            LogLevel::OK,
+            LogCategory::Entity,
+            "Emptied the trash"
+        );
+    }
+
+    // The scene on disk no longer has them, so the trash is the last copy and is let go
+    for source in saved {
+        let purged = world.resource_mut::<EntityTrash>().purge_source(&source);
+        if purged > 0 {
+            persist_trash(world, BTreeSet::from([source.clone()]));
+            log!(
+                LogType::Editor,
+                LogLevel::Info,
+                LogCategory::Entity,
+                "Saving '{}' purged {} entities from the trash",
+                source,
+                purged
+            );
+        }
+    }
+
+    if world.resource::<EditorState>().config.persist_trash {
+        for source in loaded {
+            let entities = read_scene_entities(&trash_path_for_scene(&source));
+            if entities.is_empty() {
+                continue;
+            }
+            // Already in memory when the scene is reloaded within a session
+            world.resource_mut::<EntityTrash>().purge_source(&source);
+            let entry = TrashEntry {
+                label: format!("Trash of '{}'", source),
+                entities: entities
+                    .into_iter()
+                    .map(|data| SnapshotEntity {
+                        data,
+                        source: Some(SpawnSource::new(source.clone(), SaveSettings::Runtime)),
+                        persistent: false,
+                    })
+                    .collect(),
+            };
+            world.resource_mut::<EntityTrash>().push(entry);
+        }
+    }
+}
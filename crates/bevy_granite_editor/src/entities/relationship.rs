@@ -1,6 +1,7 @@
+use crate::editor_state::EditorState;
 use crate::interface::{
     events::{
-        RequestNewParent, RequestRemoveChildren, RequestRemoveParents,
+        ReparentMode, RequestNewParent, RequestRemoveChildren, RequestRemoveParents,
         RequestRemoveParentsFromEntities,
     },
     tabs::RequestReparentEntityEvent,
@@ -10,34 +11,51 @@ use bevy::{
         entity::Entity,
         message::MessageReader,
         query::{With, Without},
-        system::{Commands, Query},
+        system::{Commands, Query, Res},
     },
     prelude::{ChildOf, Children},
     transform::commands::BuildChildrenTransformExt,
 };
-use bevy_granite_core::IconProxy;
+use bevy_granite_core::{EditorCapabilities, IconProxy};
 use bevy_granite_gizmos::{selection::events::EntityEvents, ActiveSelection, Selected};
 use bevy_granite_logging::*;
 
+/// Parents an entity with the given mode
+fn set_parent(commands: &mut Commands, entity: Entity, parent: Entity, mode: ReparentMode) {
+    match mode {
+        ReparentMode::KeepWorld => {
+            commands.entity(entity).set_parent_in_place(parent);
+        }
+        ReparentMode::KeepLocal => {
+            commands.entity(entity).insert(ChildOf(parent));
+        }
+    }
+}
+
 pub fn parent_from_node_tree_system(
     mut parent_request: MessageReader<RequestReparentEntityEvent>,
+    editor_state: Res<EditorState>,
+    capabilities: Res<EditorCapabilities>,
     mut commands: Commands,
 ) {
     for request in parent_request.read() {
+        if !capabilities.can_edit_components("hierarchy") {
+            continue;
+        }
+        let mode = request.mode.unwrap_or(editor_state.config.reparent_mode);
         log!(
             LogType::Editor,
             LogLevel::Info,
             LogCategory::Entity,
-            "Re-parenting {} entities to Entity {:?}",
+            "Re-parenting {} entities to Entity {:?} ({})",
             request.entities.len(),
-            request.new_parent
+            request.new_parent,
+            mode.name()
         );
 
         // Set the new parent for all entities in the request
         for &entity in &request.entities {
-            commands
-                .entity(entity)
-                .set_parent_in_place(request.new_parent);
+            set_parent(&mut commands, entity, request.new_parent, mode);
 
             log!(
                 LogType::Editor,
@@ -62,20 +80,25 @@ pub fn parent_system(
     mut parent_request: MessageReader<RequestNewParent>,
     active_selection: Query<Entity, With<ActiveSelection>>,
     selection: Query<Entity, (With<Selected>, Without<ActiveSelection>)>,
+    editor_state: Res<EditorState>,
+    capabilities: Res<EditorCapabilities>,
     mut commands: Commands,
 ) {
-    for _request in parent_request.read() {
+    for request in parent_request.read() {
+        if !capabilities.can_edit_components("hierarchy") {
+            continue;
+        }
+        let mode = request.mode.unwrap_or(editor_state.config.reparent_mode);
         if let Ok(active_entity) = active_selection.single() {
             for selected_entity in selection.iter() {
-                commands
-                    .entity(selected_entity)
-                    .set_parent_in_place(active_entity);
+                set_parent(&mut commands, selected_entity, active_entity, mode);
             }
             log!(
                 LogType::Editor,
                 LogLevel::OK,
                 LogCategory::Entity,
-                "New parent applied to selection"
+                "New parent applied to selection ({})",
+                mode.name()
             );
         } else {
             log!(
@@ -92,9 +115,13 @@ pub fn parent_removal_system(
     mut parent_request: MessageReader<RequestRemoveParents>,
     active_selection: Query<Entity, (With<ActiveSelection>, With<ChildOf>)>,
     selection: Query<Entity, (With<Selected>, With<ChildOf>)>,
+    capabilities: Res<EditorCapabilities>,
     mut commands: Commands,
 ) {
     for _request in parent_request.read() {
+        if !capabilities.can_edit_components("hierarchy") {
+            continue;
+        }
         for entity in selection.iter() {
             commands.entity(entity).remove_parent_in_place();
         }
@@ -114,9 +141,13 @@ pub fn parent_removal_system(
 
 pub fn parent_removal_from_entities_system(
     mut parent_request: MessageReader<RequestRemoveParentsFromEntities>,
+    capabilities: Res<EditorCapabilities>,
     mut commands: Commands,
 ) {
     for request in parent_request.read() {
+        if !capabilities.can_edit_components("hierarchy") {
+            continue;
+        }
         for &entity in &request.entities {
             commands.entity(entity).remove_parent_in_place();
 
@@ -145,9 +176,13 @@ pub fn child_removal_system(
     active_selection: Query<Entity, (With<ActiveSelection>, With<ChildOf>)>,
     children_query: Query<&Children>,
     icon_proxy_query: Query<(), With<IconProxy>>,
+    capabilities: Res<EditorCapabilities>,
     mut commands: Commands,
 ) {
     for _request in child_request.read() {
+        if !capabilities.can_edit_components("hierarchy") {
+            continue;
+        }
         for entity in active_selection.iter() {
             if let Ok(children) = children_query.get(entity) {
                 for &child in children.iter() {
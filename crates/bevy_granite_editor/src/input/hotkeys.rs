@@ -0,0 +1,99 @@
+use bevy::input::keyboard::KeyCode;
+use serde::{Deserialize, Serialize};
+
+// hotkeys.rs
+// Keys for the editor toggles that can be rebound from the interface settings
+// Only keys unlikely to clash with game input are offered
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EditorHotkey {
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+    Backquote,
+    Insert,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Pause,
+    ScrollLock,
+}
+
+impl EditorHotkey {
+    pub fn all() -> Vec<Self> {
+        vec![
+            Self::F1,
+            Self::F2,
+            Self::F3,
+            Self::F4,
+            Self::F5,
+            Self::F6,
+            Self::F7,
+            Self::F8,
+            Self::F9,
+            Self::F10,
+            Self::F11,
+            Self::F12,
+            Self::Backquote,
+            Self::Insert,
+            Self::Home,
+            Self::End,
+            Self::PageUp,
+            Self::PageDown,
+            Self::Pause,
+            Self::ScrollLock,
+        ]
+    }
+
+    pub fn key_code(&self) -> KeyCode {
+        match self {
+            Self::F1 => KeyCode::F1,
+            Self::F2 => KeyCode::F2,
+            Self::F3 => KeyCode::F3,
+            Self::F4 => KeyCode::F4,
+            Self::F5 => KeyCode::F5,
+            Self::F6 => KeyCode::F6,
+            Self::F7 => KeyCode::F7,
+            Self::F8 => KeyCode::F8,
+            Self::F9 => KeyCode::F9,
+            Self::F10 => KeyCode::F10,
+            Self::F11 => KeyCode::F11,
+            Self::F12 => KeyCode::F12,
+            Self::Backquote => KeyCode::Backquote,
+            Self::Insert => KeyCode::Insert,
+            Self::Home => KeyCode::Home,
+            Self::End => KeyCode::End,
+            Self::PageUp => KeyCode::PageUp,
+            Self::PageDown => KeyCode::PageDown,
+            Self::Pause => KeyCode::Pause,
+            Self::ScrollLock => KeyCode::ScrollLock,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HotkeySettings {
+    /// Show/hide the whole editor
+    pub toggle_editor: EditorHotkey,
+    /// Switch between the full editor and the compact overlay
+    pub toggle_overlay: EditorHotkey,
+}
+
+impl Default for HotkeySettings {
+    fn default() -> Self {
+        Self {
+            toggle_editor: EditorHotkey::F2,
+            toggle_overlay: EditorHotkey::F4,
+        }
+    }
+}
@@ -1,5 +1,7 @@
-pub mod shortcuts;
+pub mod hotkeys;
 pub mod plugin;
+pub mod shortcuts;
 
+pub use hotkeys::{EditorHotkey, HotkeySettings};
+pub use plugin::InputPlugin;
 pub use shortcuts::shortcuts_system;
-pub use plugin::InputPlugin;
\ No newline at end of file
@@ -1,9 +1,11 @@
 use bevy::{
+    input::{keyboard::KeyCode, ButtonInput},
     math::Vec2,
-    prelude::{Children, Commands, Entity, Query, Res},
+    prelude::{Commands, Entity, Query, Res},
 };
 use bevy_granite_core::{
-    entities::SaveSettings, RequestLoadEvent, RequestReloadEvent, RequestSaveEvent, UserInput,
+    entities::SaveSettings, EditorCapabilities, RequestLoadEvent, RequestReloadEvent,
+    RequestSaveEvent, UserInput, SCENE_FILE_EXTENSIONS,
 };
 use bevy_granite_gizmos::{selection::events::EntityEvents, Selected};
 use bevy_granite_logging::{log, LogCategory, LogLevel, LogType};
@@ -13,8 +15,9 @@ use crate::{
     editor_state::EditorState,
     interface::{
         events::{
-            PopupMenuRequestedEvent, RequestCameraEntityFrame, RequestEditorToggle,
-            RequestToggleCameraSync,
+            PopupMenuRequestedEvent, RequestCameraEntityFrame, RequestEditorOverlayToggle,
+            RequestEditorToggle, RequestRedo, RequestSafeDeleteEvent, RequestToggleCameraSync,
+            RequestUndo,
         },
         popups::PopupType,
         EditorEvents,
@@ -24,23 +27,37 @@ use crate::{
 pub fn shortcuts_system(
     mut commands: Commands,
     input: Res<UserInput>,
-    query: Query<(Entity, &Selected, Option<&Children>)>,
+    keys: Res<ButtonInput<KeyCode>>,
+    query: Query<(Entity, &Selected)>,
     mut events: EditorEvents,
     editor_state: Res<EditorState>,
+    capabilities: Res<EditorCapabilities>,
 ) {
-    handle_shortcuts(&input, &editor_state, &mut commands, &query, &mut events);
+    handle_shortcuts(
+        &input,
+        &keys,
+        &editor_state,
+        &capabilities,
+        &mut commands,
+        &query,
+        &mut events,
+    );
 }
 
 fn handle_shortcuts(
     input: &UserInput,
+    keys: &ButtonInput<KeyCode>,
     editor_state: &EditorState,
+    capabilities: &EditorCapabilities,
     commands: &mut Commands,
-    query: &Query<(Entity, &Selected, Option<&Children>)>,
+    query: &Query<(Entity, &Selected)>,
     events: &mut EditorEvents,
 ) {
-    // F2
+    let hotkeys = &editor_state.config.hotkeys;
+
+    // F2 by default
     // Toggle editor on/off
-    if input.key_f2.just_pressed {
+    if keys.just_pressed(hotkeys.toggle_editor.key_code()) {
         log!(
             LogType::Editor,
             LogLevel::Info,
@@ -50,6 +67,18 @@ fn handle_shortcuts(
         events.toggle_editor.write(RequestEditorToggle);
     }
 
+    // F4 by default
+    // Toggle compact overlay, also opens the editor when closed
+    if keys.just_pressed(hotkeys.toggle_overlay.key_code()) {
+        log!(
+            LogType::Editor,
+            LogLevel::Info,
+            LogCategory::Input,
+            "(shortcut) Toggling editor overlay"
+        );
+        events.toggle_overlay.write(RequestEditorOverlayToggle);
+    }
+
     if !editor_state.active {
         return;
     }
@@ -68,21 +97,16 @@ fn handle_shortcuts(
 
     // Delete Key
     // Delete Active Entity
-    if input.key_delete.just_pressed {
+    if input.key_delete.just_pressed && capabilities.can_delete("selected entities") {
         log!(
             LogType::Editor,
             LogLevel::Info,
             LogCategory::Input,
             "(shortcut) Deleting active entity"
         );
-        for (entity, _, children) in query.iter() {
-            if let Some(children) = children {
-                for &child in children.iter() {
-                    commands.entity(child).try_despawn();
-                }
-            }
-            commands.entity(entity).try_despawn();
-        }
+        events.safe_delete.write(RequestSafeDeleteEvent(
+            query.iter().map(|(entity, _)| entity).collect(),
+        ));
     }
 
     // F key
@@ -153,7 +177,7 @@ fn handle_shortcuts(
             "(shortcut) Opening load world dialog"
         );
         if let Some(path) = FileDialog::new()
-            .add_filter("Granite Scene", &["scene"])
+            .add_filter("Granite Scene", &SCENE_FILE_EXTENSIONS)
             .show_open_single_file()
             .unwrap()
         {
@@ -229,6 +253,33 @@ fn handle_shortcuts(
         }
     }
 
+    // Ctrl-Z, Ctrl-Shft-Z
+    // Undo and redo
+    if input.ctrl_left.pressed
+        && input.key_z.just_pressed
+        && !input.mouse_over_egui
+        && !input.mouse_right.any
+        && !input.mouse_left.any
+    {
+        if input.shift_left.pressed {
+            log!(
+                LogType::Editor,
+                LogLevel::Info,
+                LogCategory::Input,
+                "(shortcut) Redo"
+            );
+            events.redo.write(RequestRedo);
+        } else {
+            log!(
+                LogType::Editor,
+                LogLevel::Info,
+                LogCategory::Input,
+                "(shortcut) Undo"
+            );
+            events.undo.write(RequestUndo);
+        }
+    }
+
     // Shft-P
     // Relationship menu
     if input.shift_left.pressed
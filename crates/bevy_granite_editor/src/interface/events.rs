@@ -8,7 +8,15 @@ use bevy::prelude::{Entity, Message, Vec2};
 use bevy_granite_core::RequestDespawnBySource;
 use bevy_granite_core::RequestDespawnSerializableEntities;
 use bevy_granite_core::{EditableMaterial, GraniteTypes};
-use bevy_granite_core::{RequestLoadEvent, RequestReloadEvent, RequestSaveEvent};
+use bevy_granite_core::{
+    RequestApplyTransformEvent, RequestAssetMoveEvent, RequestConnectWaypointsEvent,
+    RequestCreateJointEvent, RequestCreatePrefabEvent, RequestExportEntityCsv, RequestExportGltf,
+    RequestExportSchema, RequestFlattenHierarchyEvent, RequestImportEntityCsv, RequestImportGltf,
+    RequestLoadEvent, RequestReassignSpawnSource, RequestReloadEvent, RequestRevertPrefabEvent,
+    RequestSaveEvent, RequestSceneDependencyReportEvent,
+};
+use bevy_granite_gizmos::{RequestDuplicateAllSelectionEvent, RequestDuplicateEntityEvent};
+use serde::{Deserialize, Serialize};
 
 #[derive(SystemParam)]
 pub struct EditorEvents<'w> {
@@ -17,6 +25,7 @@ pub struct EditorEvents<'w> {
     pub reload: MessageWriter<'w, RequestReloadEvent>,
     pub load: MessageWriter<'w, RequestLoadEvent>,
     pub toggle_editor: MessageWriter<'w, RequestEditorToggle>,
+    pub toggle_overlay: MessageWriter<'w, RequestEditorOverlayToggle>,
     pub toggle_cam_sync: MessageWriter<'w, RequestToggleCameraSync>,
     pub viewport_camera: MessageWriter<'w, RequestViewportCameraOverride>, // From #78
     pub frame: MessageWriter<'w, RequestCameraEntityFrame>,
@@ -24,9 +33,33 @@ pub struct EditorEvents<'w> {
     pub remove_parent: MessageWriter<'w, RequestRemoveParents>,
     pub remove_parent_entities: MessageWriter<'w, RequestRemoveParentsFromEntities>,
     pub remove_children: MessageWriter<'w, RequestRemoveChildren>,
+    pub safe_delete: MessageWriter<'w, RequestSafeDeleteEvent>,
+    pub duplicate_entity: MessageWriter<'w, RequestDuplicateEntityEvent>,
+    pub duplicate_selection: MessageWriter<'w, RequestDuplicateAllSelectionEvent>,
     pub despawn_all: MessageWriter<'w, RequestDespawnSerializableEntities>,
     pub despawn_by_source: MessageWriter<'w, RequestDespawnBySource>,
     pub set_active_world: MessageWriter<'w, SetActiveWorld>,
+    pub dependency_report: MessageWriter<'w, RequestSceneDependencyReportEvent>,
+    pub export_gltf: MessageWriter<'w, RequestExportGltf>,
+    pub import_gltf: MessageWriter<'w, RequestImportGltf>,
+    pub export_schema: MessageWriter<'w, RequestExportSchema>,
+    pub export_csv: MessageWriter<'w, RequestExportEntityCsv>,
+    pub import_csv: MessageWriter<'w, RequestImportEntityCsv>,
+    pub move_asset: MessageWriter<'w, RequestAssetMoveEvent>,
+    pub scene_thumbnail: MessageWriter<'w, RequestSceneThumbnail>,
+    pub cubemap_capture: MessageWriter<'w, RequestCubemapCapture>,
+    pub bake_imposters: MessageWriter<'w, RequestBakeImposters>,
+    pub randomization_preview: MessageWriter<'w, RequestRandomizationPreview>,
+    pub simulation_time: MessageWriter<'w, RequestSimulationTime>,
+    pub reassign_source: MessageWriter<'w, RequestReassignSpawnSource>,
+    pub connect_waypoints: MessageWriter<'w, RequestConnectWaypointsEvent>,
+    pub create_joint: MessageWriter<'w, RequestCreateJointEvent>,
+    pub create_prefab: MessageWriter<'w, RequestCreatePrefabEvent>,
+    pub revert_prefab: MessageWriter<'w, RequestRevertPrefabEvent>,
+    pub flatten_hierarchy: MessageWriter<'w, RequestFlattenHierarchyEvent>,
+    pub apply_transform: MessageWriter<'w, RequestApplyTransformEvent>,
+    pub undo: MessageWriter<'w, RequestUndo>,
+    pub redo: MessageWriter<'w, RequestRedo>,
 }
 
 // Internal Events
@@ -85,6 +118,11 @@ pub struct MaterialDeleteEvent {
 #[derive(Message)]
 pub struct RequestEditorToggle;
 
+/// Switch between the full editor and a compact overlay of only the toolbar and gizmos
+/// Opens the editor in overlay mode when it is closed
+#[derive(Message)]
+pub struct RequestEditorOverlayToggle;
+
 #[derive(Message)]
 pub struct RequestCameraEntityFrame;
 
@@ -96,8 +134,83 @@ pub struct RequestViewportCameraOverride {
     pub camera: Option<Entity>,
 }
 
+/// Capture the viewport as the gallery thumbnail of a scene source
+#[derive(Message)]
+pub struct RequestSceneThumbnail(pub String);
+
+/// Capture a cubemap around the active selection, or the viewport camera, to a png path
+#[derive(Message)]
+pub struct RequestCubemapCapture(pub String);
+
+/// Render each prop from `views` angles into an imposter atlas and give it an Imposter billboard
+#[derive(Message)]
+pub struct RequestBakeImposters {
+    pub entities: Vec<Entity>,
+    pub views: u32,
+}
+
+/// Show what SpawnRandomization rolls in the viewport without changing the scene, see RandomizationPreview
+#[derive(Message)]
+pub enum RequestRandomizationPreview {
+    /// Turn the preview on with a new seed, or off
+    Toggle,
+    /// Preview with a new seed
+    Reroll,
+}
+
+/// Control virtual time from the editor, editor systems keep running on real time
+#[derive(Message, Clone, Copy)]
+pub enum RequestSimulationTime {
+    TogglePause,
+    /// Advance a single frame while paused
+    Step,
+    /// Relative speed, clamped to 0.1x - 4x
+    SetSpeed(f32),
+}
+
+/// Undo the last editor step, see EditorHistory
 #[derive(Message)]
-pub struct RequestNewParent;
+pub struct RequestUndo;
+
+#[derive(Message)]
+pub struct RequestRedo;
+
+/// What stays put when an entity gets a new parent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ReparentMode {
+    /// The entity stays where it is, its local transform is recomputed under the parent
+    #[default]
+    KeepWorld,
+    /// The local transform is kept, so the entity moves with the parent
+    KeepLocal,
+}
+
+impl ReparentMode {
+    pub fn all() -> [ReparentMode; 2] {
+        [ReparentMode::KeepWorld, ReparentMode::KeepLocal]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ReparentMode::KeepWorld => "Keep World Transform",
+            ReparentMode::KeepLocal => "Keep Local Transform",
+        }
+    }
+
+    pub fn other(&self) -> ReparentMode {
+        match self {
+            ReparentMode::KeepWorld => ReparentMode::KeepLocal,
+            ReparentMode::KeepLocal => ReparentMode::KeepWorld,
+        }
+    }
+}
+
+/// Set the active entity as parent of the other selected entities
+/// None for the mode uses the editor setting
+#[derive(Message, Default)]
+pub struct RequestNewParent {
+    pub mode: Option<ReparentMode>,
+}
 
 #[derive(Message)]
 pub struct RequestRemoveParents;
@@ -109,3 +222,15 @@ pub struct RequestRemoveParentsFromEntities {
 
 #[derive(Message)]
 pub struct RequestRemoveChildren;
+
+/// Delete these entities and their children, asking first when other entities reference them
+#[derive(Message)]
+pub struct RequestSafeDeleteEvent(pub Vec<Entity>);
+
+/// Put back a trashed delete, by its place in the trash with the newest first
+#[derive(Message)]
+pub struct RequestRestoreFromTrash(pub usize);
+
+/// Purge every trashed entity for good
+#[derive(Message)]
+pub struct RequestEmptyTrash;
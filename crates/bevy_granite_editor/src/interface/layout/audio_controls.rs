@@ -0,0 +1,104 @@
+use bevy::ecs::entity::Entity;
+use bevy_egui::egui;
+use bevy_granite_core::AudioMonitor;
+
+/// One audio emitter in the scene, as listed in the audio menu
+pub struct AudioEmitterRow {
+    pub entity: Entity,
+    pub name: String,
+    /// Preview button is on, whether or not it is muted
+    pub previewing: bool,
+    pub selected: bool,
+}
+
+/// Mute, solo and master volume of the audio previews, the game's own sounds are not affected
+pub fn audio_controls_ui(
+    ui: &mut egui::Ui,
+    monitor: &mut AudioMonitor,
+    emitters: &[AudioEmitterRow],
+) {
+    let label = if monitor.mute_all {
+        "🔇 Audio"
+    } else {
+        "🔊 Audio"
+    };
+    ui.menu_button(label, |ui| {
+        ui.checkbox(&mut monitor.mute_all, "Mute All")
+            .on_hover_text("Silence every preview");
+        ui.horizontal(|ui| {
+            ui.label("Volume:");
+            ui.add(egui::Slider::new(&mut monitor.volume, 0.0..=1.0))
+                .on_hover_text("Master volume of every preview");
+        });
+
+        let selected = emitters.iter().find(|emitter| emitter.selected);
+        let solo_label = if selected.is_some_and(|emitter| monitor.is_soloed(emitter.entity)) {
+            "Unsolo Selected"
+        } else {
+            "Solo Selected"
+        };
+        if ui
+            .add_enabled(selected.is_some(), egui::Button::new(solo_label))
+            .on_hover_text("Only hear the selected audio emitter")
+            .clicked()
+        {
+            if let Some(emitter) = selected {
+                monitor.toggle_solo(emitter.entity);
+            }
+        }
+        if ui
+            .add_enabled(
+                monitor.mute_all || monitor.solo.is_some() || !monitor.muted.is_empty(),
+                egui::Button::new("Reset"),
+            )
+            .on_hover_text("Clear every mute and solo")
+            .clicked()
+        {
+            monitor.reset();
+        }
+
+        ui.separator();
+        if emitters.is_empty() {
+            ui.weak("No audio emitters in the scene");
+            return;
+        }
+        egui::ScrollArea::vertical()
+            .max_height(300.0)
+            .show(ui, |ui| {
+                for emitter in emitters {
+                    ui.horizontal(|ui| {
+                        if ui
+                            .selectable_label(monitor.is_muted(emitter.entity), "M")
+                            .on_hover_text("Mute")
+                            .clicked()
+                        {
+                            monitor.toggle_mute(emitter.entity);
+                        }
+                        if ui
+                            .selectable_label(monitor.is_soloed(emitter.entity), "S")
+                            .on_hover_text("Solo")
+                            .clicked()
+                        {
+                            monitor.toggle_solo(emitter.entity);
+                        }
+                        let name = egui::RichText::new(&emitter.name);
+                        let name = if emitter.selected {
+                            name.strong()
+                        } else {
+                            name
+                        };
+                        if monitor.is_audible(emitter.entity) {
+                            ui.label(name);
+                        } else {
+                            ui.label(name.weak());
+                        }
+                        if emitter.previewing {
+                            ui.weak("▶");
+                        }
+                    });
+                }
+            });
+    })
+    .response
+    .on_hover_text("Monitor audio previews in the editor");
+}
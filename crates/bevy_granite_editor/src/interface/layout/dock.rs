@@ -1,11 +1,17 @@
 use crate::{
-    editor_state::{DockLayoutStr, EditorState},
+    editor_state::{DockLayoutStr, EditorState, PerformanceSafeMode, ProjectLock, SimulationTime},
     get_interface_config_float,
     interface::{
-        layout::top_bar::top_bar_ui,
+        layout::{
+            audio_controls::AudioEmitterRow,
+            safe_mode_banner::safe_mode_banner_ui,
+            status_bar::job_status_bar_ui,
+            top_bar::{overlay_bar_ui, top_bar_ui},
+        },
         panels::{
             bottom_panel::{BottomDockState, BottomTabViewer},
             right_panel::{SideDockState, SideTabViewer},
+            EditorTabRestrictions,
         },
         EditorEvents, SettingsTab,
     },
@@ -14,12 +20,17 @@ use crate::{
 
 use bevy::{
     camera::{Camera, Camera3d, RenderTarget},
-    ecs::system::{Commands, Query},
-    prelude::{Entity, Name, Res, ResMut},
+    ecs::{
+        query::Has,
+        system::{Commands, Query},
+    },
+    prelude::{Entity, Name, Res, ResMut, Time, Virtual},
 };
 use bevy_egui::{egui, EguiContexts};
-use bevy_granite_core::{UICamera, UserInput};
-use bevy_granite_gizmos::GizmoCamera;
+use bevy_granite_core::{
+    AudioEmitters, AudioMonitor, GraniteJobs, IdentityData, StartupWorlds, UICamera, UserInput,
+};
+use bevy_granite_gizmos::{ActiveSelection, GizmoCamera};
 use egui_dock::DockArea;
 use serde::{Deserialize, Serialize};
 
@@ -68,11 +79,31 @@ pub fn dock_ui_system(
         Option<&GizmoCamera>,
     )>,
     viewport_camera_state: Res<ViewportCameraState>,
+    project_lock: Res<ProjectLock>,
+    tab_restrictions: Res<EditorTabRestrictions>,
+    mut startup_worlds: ResMut<StartupWorlds>,
+    mut safe_mode: ResMut<PerformanceSafeMode>,
+    mut jobs: ResMut<GraniteJobs>,
+    (virtual_time, simulation): (Res<Time<Virtual>>, Res<SimulationTime>),
+    (mut audio_monitor, audio_emitters, identity_query): (
+        ResMut<AudioMonitor>,
+        Res<AudioEmitters>,
+        Query<(&IdentityData, Has<ActiveSelection>)>,
+    ),
 ) {
     let mut camera_options: Vec<(Entity, String)> = camera_query
         .iter()
         .filter_map(
-            |(entity, name, camera, render_target, camera3d, editor_camera, ui_camera, gizmo_camera)| {
+            |(
+                entity,
+                name,
+                camera,
+                render_target,
+                camera3d,
+                editor_camera,
+                ui_camera,
+                gizmo_camera,
+            )| {
                 if camera3d.is_some()
                     && editor_camera.is_none()
                     && ui_camera.is_none()
@@ -91,15 +122,52 @@ pub fn dock_ui_system(
         .collect();
     camera_options.sort_by(|a, b| a.1.cmp(&b.1));
 
+    let mut audio_emitter_rows: Vec<AudioEmitterRow> = audio_emitters
+        .iter()
+        .filter_map(|state| {
+            let (identity, selected) = identity_query.get(state.entity).ok()?;
+            Some(AudioEmitterRow {
+                entity: state.entity,
+                name: identity.name.clone(),
+                previewing: state.emitter.previewing,
+                selected,
+            })
+        })
+        .collect();
+    audio_emitter_rows.sort_by(|a, b| a.name.cmp(&b.name));
+
     let ctx = contexts.ctx_mut().expect("Egui context to exist");
+
+    // Overlay mode keeps the game view fullscreen, the viewport fills whatever egui leaves free
+    if editor_state.overlay {
+        egui::TopBottomPanel::top("overlay_tool_panel")
+            .resizable(false)
+            .show(ctx, |ui| {
+                overlay_bar_ui(
+                    ui,
+                    &mut events,
+                    &user_input,
+                    &editor_state,
+                    &mut commands,
+                    (&virtual_time, &simulation),
+                    (&mut audio_monitor, &audio_emitter_rows),
+                );
+            });
+        return;
+    }
+
     let screen_rect = ctx.screen_rect();
     let screen_width = screen_rect.width();
     let screen_height = screen_rect.height();
 
-    let default_side_panel_width = side_dock.width.unwrap_or((screen_width * 0.10).clamp(200., 1000.));
+    let default_side_panel_width = side_dock
+        .width
+        .unwrap_or((screen_width * 0.10).clamp(200., 1000.));
     // we need a way to calculate the minimum size the bottom panel can be so if we change it in the future it wont start crashing again
     let max_side_panel_width = screen_width - 270.; // 270 is the minimum size to fit bottom panel it will crash if smaller than this
-    let default_bottom_panel_height = bottom_dock.height.unwrap_or((screen_height * 0.05).clamp(100., 400.));
+    let default_bottom_panel_height = bottom_dock
+        .height
+        .unwrap_or((screen_height * 0.05).clamp(100., 400.));
 
     let space = get_interface_config_float("ui.spacing");
     egui::TopBottomPanel::top("tool_panel")
@@ -116,34 +184,43 @@ pub fn dock_ui_system(
                     &mut commands,
                     &camera_options,
                     viewport_camera_state.as_ref(),
+                    project_lock.read_only,
+                    &tab_restrictions,
+                    &mut startup_worlds,
+                    (&virtual_time, &simulation),
+                    (&mut audio_monitor, &audio_emitter_rows),
                 );
             });
         });
 
+    if safe_mode.is_active() {
+        egui::TopBottomPanel::top("safe_mode_banner")
+            .resizable(false)
+            .show(ctx, |ui| {
+                safe_mode_banner_ui(ui, &mut safe_mode);
+            });
+    }
+
     let side_panel_position = editor_state.config.dock.side_panel_position;
     let panel_response = match side_panel_position {
-        SidePanelPosition::Left => {
-            egui::SidePanel::left("left_dock_panel")
-                .resizable(true)
-                .default_width(default_side_panel_width)
-                .width_range(250.0..=max_side_panel_width)
-                .show(ctx, |ui| {
-                    DockArea::new(&mut side_dock.dock_state)
-                        .id(egui::Id::new("left_dock_area"))
-                        .show_inside(ui, &mut SideTabViewer);
-                })
-        }
-        SidePanelPosition::Right => {
-            egui::SidePanel::right("right_dock_panel")
-                .resizable(true)
-                .default_width(default_side_panel_width)
-                .width_range(250.0..=max_side_panel_width)
-                .show(ctx, |ui| {
-                    DockArea::new(&mut side_dock.dock_state)
-                        .id(egui::Id::new("right_dock_area"))
-                        .show_inside(ui, &mut SideTabViewer);
-                })
-        }
+        SidePanelPosition::Left => egui::SidePanel::left("left_dock_panel")
+            .resizable(true)
+            .default_width(default_side_panel_width)
+            .width_range(250.0..=max_side_panel_width)
+            .show(ctx, |ui| {
+                DockArea::new(&mut side_dock.dock_state)
+                    .id(egui::Id::new("left_dock_area"))
+                    .show_inside(ui, &mut SideTabViewer);
+            }),
+        SidePanelPosition::Right => egui::SidePanel::right("right_dock_panel")
+            .resizable(true)
+            .default_width(default_side_panel_width)
+            .width_range(250.0..=max_side_panel_width)
+            .show(ctx, |ui| {
+                DockArea::new(&mut side_dock.dock_state)
+                    .id(egui::Id::new("right_dock_area"))
+                    .show_inside(ui, &mut SideTabViewer);
+            }),
     };
 
     let new_width = panel_response.response.rect.width();
@@ -151,6 +228,14 @@ pub fn dock_ui_system(
         side_dock.width = Some(new_width);
     }
 
+    if jobs.is_busy() || jobs.finished().next().is_some() {
+        egui::TopBottomPanel::bottom("job_status_bar")
+            .resizable(false)
+            .show(ctx, |ui| {
+                job_status_bar_ui(ui, &mut jobs);
+            });
+    }
+
     let bottom_response = egui::TopBottomPanel::bottom("bottom_dock_panel")
         .resizable(true)
         .default_height(default_bottom_panel_height)
@@ -1,5 +1,13 @@
+pub mod audio_controls;
 pub mod dock;
+pub mod safe_mode_banner;
+pub mod status_bar;
+pub mod time_controls;
 pub mod top_bar;
 
+pub use audio_controls::*;
 pub use dock::*;
-pub use top_bar::*;
\ No newline at end of file
+pub use safe_mode_banner::*;
+pub use status_bar::*;
+pub use time_controls::*;
+pub use top_bar::*;
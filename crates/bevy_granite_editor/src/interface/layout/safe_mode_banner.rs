@@ -0,0 +1,49 @@
+use crate::editor_state::PerformanceSafeMode;
+use bevy_egui::egui;
+
+/// Strip under the top bar while safe mode is on, saying why and what it cut, with manual overrides
+pub fn safe_mode_banner_ui(ui: &mut egui::Ui, safe_mode: &mut PerformanceSafeMode) {
+    ui.horizontal(|ui| {
+        let reason = match (safe_mode.override_mode, safe_mode.reason()) {
+            (Some(true), _) => "turned on manually".to_string(),
+            (_, Some(reason)) => reason.describe(),
+            (_, None) => String::new(),
+        };
+        ui.colored_label(ui.visuals().warn_fg_color, "SAFE MODE");
+        ui.label(reason);
+        ui.separator();
+
+        let disabled = safe_mode.disabled_features();
+        if disabled.is_empty() {
+            ui.weak("Nothing is degraded");
+        } else {
+            ui.weak(format!("Paused: {}", disabled.join(", ")));
+        }
+        ui.separator();
+
+        ui.menu_button("Overrides", |ui| {
+            ui.checkbox(&mut safe_mode.disable_icons, "Hide class icons");
+            ui.checkbox(&mut safe_mode.disable_visualizers, "Hide debug visualizers");
+            ui.checkbox(&mut safe_mode.throttle_tree, "Throttle entity tree updates");
+            ui.checkbox(
+                &mut safe_mode.simple_selection_bounds,
+                "Selection bounds from origins only",
+            );
+        });
+        if ui
+            .button("Turn Off")
+            .on_hover_text("Keep safe mode off for this session")
+            .clicked()
+        {
+            safe_mode.override_mode = Some(false);
+        }
+        if safe_mode.reason().is_some()
+            && ui
+                .button("Check Again")
+                .on_hover_text("Clear the detected reason and measure again")
+                .clicked()
+        {
+            safe_mode.reset();
+        }
+    });
+}
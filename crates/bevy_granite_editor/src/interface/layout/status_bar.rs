@@ -0,0 +1,64 @@
+use bevy_egui::egui;
+use bevy_granite_core::{GraniteJobs, JobStatus};
+
+/// Strip at the bottom of the editor while background jobs run, with their progress and a cancel button
+/// Stays up after the last job with what finished, until dismissed
+pub fn job_status_bar_ui(ui: &mut egui::Ui, jobs: &mut GraniteJobs) {
+    let mut cancel = Vec::new();
+    ui.horizontal(|ui| {
+        let active: Vec<_> = jobs.active().cloned().collect();
+        if active.is_empty() {
+            let summary: Vec<String> = jobs
+                .finished()
+                .take(3)
+                .map(|job| match &job.status {
+                    JobStatus::Failed(e) => format!("{} failed: {}", job.label, e),
+                    JobStatus::Cancelled => format!("{} cancelled", job.label),
+                    _ => format!("{} done", job.label),
+                })
+                .collect();
+            ui.weak(summary.join(", "));
+            if ui.small_button("Dismiss").clicked() {
+                jobs.clear_finished();
+            }
+            return;
+        }
+
+        ui.label(format!("Jobs ({})", active.len()));
+        ui.separator();
+        for job in active.iter() {
+            let text = if job.message.is_empty() {
+                job.label.clone()
+            } else {
+                format!("{}: {}", job.label, job.message)
+            };
+            match job.status {
+                JobStatus::Queued => {
+                    ui.weak(format!("{} (queued)", text));
+                }
+                _ => {
+                    ui.add(
+                        egui::ProgressBar::new(job.progress)
+                            .desired_width(140.0)
+                            .text(text),
+                    );
+                }
+            }
+            if ui
+                .small_button("x")
+                .on_hover_text(format!("Cancel {}", job.label))
+                .clicked()
+            {
+                cancel.push(job.id);
+            }
+            ui.separator();
+        }
+        if active.len() > 1 && ui.small_button("Cancel All").clicked() {
+            cancel.extend(active.iter().map(|job| job.id));
+        }
+    });
+
+    for id in cancel {
+        jobs.cancel(id);
+    }
+}
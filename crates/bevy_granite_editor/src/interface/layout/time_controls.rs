@@ -0,0 +1,53 @@
+use crate::{
+    editor_state::{SimulationTime, SIMULATION_SPEED_PRESETS},
+    interface::{events::RequestSimulationTime, EditorEvents},
+};
+use bevy::prelude::{Time, Virtual};
+use bevy_egui::egui;
+
+/// Pause, step and speed of the game's virtual time, the editor itself keeps running
+pub fn time_controls_ui(
+    ui: &mut egui::Ui,
+    events: &mut EditorEvents,
+    virtual_time: &Time<Virtual>,
+    simulation: &SimulationTime,
+) {
+    let paused = virtual_time.is_paused() || simulation.stepping();
+    let (label, hover) = if paused {
+        ("▶ Play", "Resume game time")
+    } else {
+        ("⏸ Pause", "Freeze game time, the editor keeps running")
+    };
+    if ui.button(label).on_hover_text(hover).clicked() {
+        events
+            .simulation_time
+            .write(RequestSimulationTime::TogglePause);
+    }
+    if ui
+        .add_enabled(paused, egui::Button::new("⏭ Step"))
+        .on_hover_text("Advance game time by a single frame")
+        .clicked()
+    {
+        events.simulation_time.write(RequestSimulationTime::Step);
+    }
+
+    let speed = virtual_time.relative_speed();
+    ui.menu_button(format!("{:.2}x", speed), |ui| {
+        for preset in SIMULATION_SPEED_PRESETS {
+            if ui
+                .selectable_label(
+                    (speed - preset).abs() < f32::EPSILON,
+                    format!("{}x", preset),
+                )
+                .clicked()
+            {
+                events
+                    .simulation_time
+                    .write(RequestSimulationTime::SetSpeed(preset));
+                ui.close();
+            }
+        }
+    })
+    .response
+    .on_hover_text("Game time scale");
+}
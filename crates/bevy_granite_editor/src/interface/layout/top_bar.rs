@@ -1,12 +1,20 @@
 use crate::{
-    editor_state::EditorState,
+    editor_state::{EditorState, SimulationTime},
     interface::{
         events::{
-            PopupMenuRequestedEvent, RequestCameraEntityFrame, RequestEditorToggle,
-            RequestToggleCameraSync, RequestViewportCameraOverride, SetActiveWorld,
+            PopupMenuRequestedEvent, RequestCameraEntityFrame, RequestCubemapCapture,
+            RequestEditorOverlayToggle, RequestEditorToggle, RequestRandomizationPreview,
+            RequestSceneThumbnail, RequestToggleCameraSync, RequestViewportCameraOverride,
+            SetActiveWorld,
+        },
+        layout::{
+            audio_controls::{audio_controls_ui, AudioEmitterRow},
+            time_controls::time_controls_ui,
         },
         panels::{
-            bottom_panel::{BottomDockState, BottomTab}, right_panel::{SideDockState, SideTab}, BottomTabType, SideTabType
+            bottom_panel::{BottomDockState, BottomTab},
+            right_panel::{SideDockState, SideTab},
+            BottomTabType, EditorTabRestrictions, SideTabType,
         },
         popups::PopupType,
         tabs::{
@@ -18,11 +26,16 @@ use crate::{
     viewport::ViewportCameraState,
     UI_CONFIG,
 };
-use bevy::{ecs::{entity::Entity, system::Commands}, prelude::ResMut};
+use bevy::{
+    ecs::{entity::Entity, system::Commands},
+    prelude::{ResMut, Time, Virtual},
+};
 use bevy_egui::egui;
 use bevy_granite_core::{
-    absolute_asset_to_rel, entities::SaveSettings, RequestDespawnBySource,
-    RequestDespawnSerializableEntities, RequestLoadEvent, RequestSaveEvent, UserInput,
+    absolute_asset_to_rel, entities::SaveSettings, AudioMonitor, RequestDespawnBySource,
+    RequestDespawnSerializableEntities, RequestExportGltf, RequestExportSchema, RequestImportGltf,
+    RequestLoadEvent, RequestSaveEvent, RequestSceneDependencyReportEvent, StartupWorlds,
+    UserInput, SCENE_FILE_EXTENSIONS,
 };
 use bevy_granite_gizmos::selection::events::EntityEvents;
 use native_dialog::FileDialog;
@@ -37,6 +50,11 @@ pub fn top_bar_ui(
     commands: &mut Commands,
     camera_options: &[(Entity, String)],
     viewport_camera_state: &ViewportCameraState,
+    read_only: bool,
+    tab_restrictions: &EditorTabRestrictions,
+    startup_worlds: &mut ResMut<StartupWorlds>,
+    (virtual_time, simulation): (&Time<Virtual>, &SimulationTime),
+    (audio_monitor, audio_emitters): (&mut AudioMonitor, &[AudioEmitterRow]),
 ) {
     let active_camera_label = if viewport_camera_state.is_using_editor() {
         "Editor Camera".to_string()
@@ -58,7 +76,7 @@ pub fn top_bar_ui(
             ui.menu_button("File", |ui| {
                 if ui.button("Save as").clicked() {
                     if let Some(path) = FileDialog::new()
-                        .add_filter("Granite Scene", &["scene"])
+                        .add_filter("Granite Scene", &SCENE_FILE_EXTENSIONS)
                         .show_save_single_file()
                         .unwrap()
                     {
@@ -81,7 +99,7 @@ pub fn top_bar_ui(
 
                 if ui.button("Open (Ctrl + O)").clicked() {
                     if let Some(path) = FileDialog::new()
-                        .add_filter("Granite Scene", &["scene"])
+                        .add_filter("Granite Scene", &SCENE_FILE_EXTENSIONS)
                         .show_open_single_file()
                         .unwrap()
                     {
@@ -94,11 +112,49 @@ pub fn top_bar_ui(
                     ui.close();
                 }
 
+                if ui.button("Scene Gallery").clicked() {
+                    events.popup.write(PopupMenuRequestedEvent {
+                        popup: PopupType::SceneGallery,
+                        mouse_pos: user_input.mouse_pos,
+                    });
+                    ui.close();
+                }
+
+                if let Some(current_file) = editor_state.current_file.as_ref() {
+                    if ui
+                        .button("Capture Scene Thumbnail")
+                        .on_hover_text("Use the current viewport as this scene's gallery thumbnail")
+                        .clicked()
+                    {
+                        events
+                            .scene_thumbnail
+                            .write(RequestSceneThumbnail(current_file.clone()));
+                        ui.close();
+                    }
+                }
+
+                if ui
+                    .button("Capture Cubemap")
+                    .on_hover_text("Render a cubemap around the active selection, or the viewport camera when nothing is selected")
+                    .clicked()
+                {
+                    if let Some(path) = FileDialog::new()
+                        .add_filter("PNG Cubemap", &["png"])
+                        .show_save_single_file()
+                        .unwrap()
+                    {
+                        events
+                            .cubemap_capture
+                            .write(RequestCubemapCapture(path.display().to_string()));
+                    }
+                    ui.close();
+                }
+
                 ui.separator();
 
                 ui.menu_button("Despawn", |ui| {
                     if ui.button("Despawn All Entities").clicked() {
-                        events.despawn_all.write(RequestDespawnSerializableEntities);
+                        events.despawn_all.write(RequestDespawnSerializableEntities::default());
                         ui.close();
                     }
 
@@ -118,7 +174,7 @@ pub fn top_bar_ui(
                             if ui.button(format!("{}", source)).clicked() {
                                 events
                                     .despawn_by_source
-                                    .write(RequestDespawnBySource(source));
+                                    .write(RequestDespawnBySource::new(source));
                                 ui.close();
                             }
                         }
@@ -157,6 +213,146 @@ pub fn top_bar_ui(
                     }
                 });
 
+                ui.menu_button("Dependencies", |ui| {
+                    if editor_state.loaded_sources.is_empty() {
+                        ui.label("  (No sources loaded)");
+                    } else {
+                        let sources: Vec<String> =
+                            editor_state.loaded_sources.iter().cloned().collect();
+                        for source in sources {
+                            ui.menu_button(source.clone(), |ui| {
+                                if ui.button("Report").clicked() {
+                                    events
+                                        .dependency_report
+                                        .write(RequestSceneDependencyReportEvent(source.clone(), None));
+                                    ui.close();
+                                }
+
+                                if ui.button("Package into folder").clicked() {
+                                    if let Some(folder) =
+                                        FileDialog::new().show_open_single_dir().unwrap()
+                                    {
+                                        events.dependency_report.write(
+                                            RequestSceneDependencyReportEvent(
+                                                source.clone(),
+                                                Some(folder.display().to_string()),
+                                            ),
+                                        );
+                                    }
+                                    ui.close();
+                                }
+                            });
+                        }
+                    }
+                });
+
+                ui.menu_button("Export glTF", |ui| {
+                    let mut export = |source: Option<String>| {
+                        if let Some(path) = FileDialog::new()
+                            .add_filter("glTF Binary", &["glb"])
+                            .add_filter("glTF", &["gltf"])
+                            .show_save_single_file()
+                            .unwrap()
+                        {
+                            events.export_gltf.write(RequestExportGltf {
+                                path: path.display().to_string(),
+                                source,
+                            });
+                        }
+                    };
+
+                    if ui.button("All Loaded Scenes").clicked() {
+                        export(None);
+                        ui.close();
+                    }
+
+                    if !editor_state.loaded_sources.is_empty() {
+                        ui.separator();
+                        let sources: Vec<String> =
+                            editor_state.loaded_sources.iter().cloned().collect();
+                        for source in sources {
+                            if ui.button(source.clone()).clicked() {
+                                export(Some(source));
+                                ui.close();
+                            }
+                        }
+                    }
+                });
+
+                if ui.button("Import glTF...").clicked() {
+                    if let Some(path) = FileDialog::new()
+                        .add_filter("glTF", &["gltf", "glb"])
+                        .show_open_single_file()
+                        .unwrap()
+                    {
+                        events.import_gltf.write(RequestImportGltf {
+                            path: path.display().to_string(),
+                            source: editor_state
+                                .current_file
+                                .clone()
+                                .unwrap_or_else(|| "user".to_string()),
+                        });
+                    }
+                    ui.close();
+                }
+
+                if ui.button("Export Schema...").clicked() {
+                    if let Some(path) = FileDialog::new()
+                        .add_filter("JSON", &["json"])
+                        .set_filename("granite_schema.json")
+                        .show_save_single_file()
+                        .unwrap()
+                    {
+                        events.export_schema.write(RequestExportSchema {
+                            path: path.display().to_string(),
+                        });
+                    }
+                    ui.close();
+                }
+
+                if ui.button("Move / Rename Asset").clicked() {
+                    events.popup.write(PopupMenuRequestedEvent {
+                        popup: PopupType::MoveAsset,
+                        mouse_pos: user_input.mouse_pos,
+                    });
+                    ui.close();
+                }
+
+                if ui.button("Move Entities Between Scenes").clicked() {
+                    events.popup.write(PopupMenuRequestedEvent {
+                        popup: PopupType::ReassignSource,
+                        mouse_pos: user_input.mouse_pos,
+                    });
+                    ui.close();
+                }
+
+                if ui.button("CSV Bulk Edit...").clicked() {
+                    events.popup.write(PopupMenuRequestedEvent {
+                        popup: PopupType::CsvBulkEdit,
+                        mouse_pos: user_input.mouse_pos,
+                    });
+                    ui.close();
+                }
+
+                ui.menu_button("Randomization Preview", |ui| {
+                    if ui
+                        .button("Show / Hide")
+                        .on_hover_text("Hide what SpawnRandomization would skip at load and show rolled rotations as arrows")
+                        .clicked()
+                    {
+                        events
+                            .randomization_preview
+                            .write(RequestRandomizationPreview::Toggle);
+                        ui.close();
+                    }
+                    if ui.button("Re-roll").clicked() {
+                        events
+                            .randomization_preview
+                            .write(RequestRandomizationPreview::Reroll);
+                        ui.close();
+                    }
+                });
+
                 ui.separator();
 
                 if ui.button("Open Default World").clicked() {
@@ -175,6 +371,23 @@ pub fn top_bar_ui(
 
                     ui.close();
                 }
+
+                if startup_worlds.worlds.len() > 1 {
+                    ui.menu_button("Startup World", |ui| {
+                        let active = startup_worlds.active_name().map(|name| name.to_string());
+                        let worlds = startup_worlds.worlds.clone();
+                        for (name, path) in worlds.iter() {
+                            if ui
+                                .radio(active.as_ref() == Some(name), name)
+                                .on_hover_text(path)
+                                .clicked()
+                            {
+                                startup_worlds.select(name);
+                                ui.close();
+                            }
+                        }
+                    });
+                }
             });
 
             ui.menu_button("Panels", |ui| {
@@ -183,6 +396,9 @@ pub fn top_bar_ui(
                     (SideTabType::NodeTree, "Entities"),
                     (SideTabType::EditorSettings, "Editor Settings"),
                 ] {
+                    if !tab_restrictions.is_side_allowed(&tab_type) {
+                        continue;
+                    }
                     let tab = side_dock.dock_state.find_tab_from(|tab| tab.get_type() == tab_type);
                     let mut show = tab.is_some();
                     let checkbox = ui.checkbox(&mut show, label);
@@ -203,10 +419,17 @@ pub fn top_bar_ui(
                 ui.separator();
 
                 for (tab_type, label) in vec![
-                    (BottomTabType::Log, "Log"),
+                    (BottomTabType::Log, "Console"),
                     (BottomTabType::Debug, "Debug"),
                     (BottomTabType::Events, "Events"),
+                    (BottomTabType::History, "History"),
+                    (BottomTabType::Resources, "Resources"),
+                    (BottomTabType::Issues, "Issues"),
+                    (BottomTabType::Trash, "Trash"),
                 ] {
+                    if !tab_restrictions.is_bottom_allowed(&tab_type) {
+                        continue;
+                    }
                     let tab = bottom_dock.dock_state.find_tab_from(|tab| tab.get_type() == tab_type);
                     let mut show = tab.is_some();
                     let checkbox = ui.checkbox(&mut show, label);
@@ -230,6 +453,11 @@ pub fn top_bar_ui(
 
         // Buttons
         ui.horizontal(|ui| {
+            if read_only {
+                ui.colored_label(ui.visuals().warn_fg_color, "READ ONLY")
+                    .on_hover_text("Another editor owns this project. Saving is disabled");
+                ui.separator();
+            }
             ui.separator();
             if ui.button("Add Entity (Shft + A) ").clicked() {
                 events.popup.write(PopupMenuRequestedEvent {
@@ -252,9 +480,20 @@ pub fn top_bar_ui(
                 });
             }
             ui.separator();
-            if ui.button("Toggle Editor (F2) ").clicked() {
+            let hotkeys = &editor_state.config.hotkeys;
+            if ui
+                .button(format!("Toggle Editor ({:?}) ", hotkeys.toggle_editor))
+                .clicked()
+            {
                 events.toggle_editor.write(RequestEditorToggle);
             }
+            ui.separator();
+            if ui
+                .button(format!("Overlay ({:?}) ", hotkeys.toggle_overlay))
+                .clicked()
+            {
+                events.toggle_overlay.write(RequestEditorOverlayToggle);
+            }
 
             ui.separator();
             if ui.button("Toggle Camera Control (F3) ").clicked() {
@@ -301,8 +540,67 @@ pub fn top_bar_ui(
                 commands.trigger(EntityEvents::DeselectAll);
             }
             ui.separator();
+            time_controls_ui(ui, events, virtual_time, simulation);
+            ui.separator();
+            audio_controls_ui(ui, audio_monitor, audio_emitters);
+            ui.separator();
         });
 
         ui.add_space(spacing);
     });
 }
+
+/// Thin toolbar drawn instead of the full editor while in overlay mode
+pub fn overlay_bar_ui(
+    ui: &mut egui::Ui,
+    events: &mut EditorEvents,
+    user_input: &UserInput,
+    editor_state: &EditorState,
+    commands: &mut Commands,
+    (virtual_time, simulation): (&Time<Virtual>, &SimulationTime),
+    (audio_monitor, audio_emitters): (&mut AudioMonitor, &[AudioEmitterRow]),
+) {
+    let hotkeys = &editor_state.config.hotkeys;
+    ui.horizontal(|ui| {
+        if ui
+            .button(format!("Expand Editor ({:?}) ", hotkeys.toggle_overlay))
+            .clicked()
+        {
+            events.toggle_overlay.write(RequestEditorOverlayToggle);
+        }
+        ui.separator();
+        if ui.button("Add Entity (Shft + A) ").clicked() {
+            events.popup.write(PopupMenuRequestedEvent {
+                popup: PopupType::AddEntity,
+                mouse_pos: user_input.mouse_pos,
+            });
+        }
+        ui.separator();
+        if ui.button("Camera Control (F3) ").clicked() {
+            events.toggle_cam_sync.write(RequestToggleCameraSync);
+        }
+        ui.separator();
+        if ui.button("Frame Active (F) ").clicked() {
+            events.frame.write(RequestCameraEntityFrame);
+        }
+        ui.separator();
+        if ui.button("Deselect All (U) ").clicked() {
+            commands.trigger(EntityEvents::DeselectAll);
+        }
+        ui.separator();
+        time_controls_ui(ui, events, virtual_time, simulation);
+        ui.separator();
+        audio_controls_ui(ui, audio_monitor, audio_emitters);
+        ui.separator();
+        if let Some(current_file) = &editor_state.current_file {
+            ui.label(current_file);
+            ui.separator();
+        }
+        if ui
+            .button(format!("Close ({:?}) ", hotkeys.toggle_editor))
+            .clicked()
+        {
+            events.toggle_editor.write(RequestEditorToggle);
+        }
+    });
+}
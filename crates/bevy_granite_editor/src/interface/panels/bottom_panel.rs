@@ -4,7 +4,9 @@ use egui_dock::{DockState, NodeIndex, TabViewer};
 use serde::{Deserialize, Serialize};
 
 use crate::interface::tabs::{
-    debug_tab_ui, events_tab_ui, log_tab_ui, DebugTabData, EventsTabData, LogTabData,
+    debug_tab_ui, events_tab_ui, history_tab_ui, issues_tab_ui, log_tab_ui, resources_tab_ui,
+    trash_tab_ui, DebugTabData, EventsTabData, HistoryTabData, IssuesTabData, LogTabData,
+    ResourcesTabData, TrashTabData,
 };
 
 #[derive(Resource, Clone)]
@@ -24,24 +26,52 @@ impl Default for BottomDockState {
         let events_tab = BottomTab::Events {
             data: EventsTabData::default(),
         };
+        let history_tab = BottomTab::History {
+            data: HistoryTabData::default(),
+        };
+        let resources_tab = BottomTab::Resources {
+            data: ResourcesTabData::default(),
+        };
+        let issues_tab = BottomTab::Issues {
+            data: IssuesTabData::default(),
+        };
+        let trash_tab = BottomTab::Trash {
+            data: TrashTabData::default(),
+        };
 
         let mut dock_state = DockState::new(vec![debug_tab]);
 
         let surface = dock_state.main_surface_mut();
 
-        let [_debug_node, remaining] =
-            surface.split_right(NodeIndex::root(), 0.33, vec![events_tab]);
+        let [_debug_node, remaining] = surface.split_right(
+            NodeIndex::root(),
+            0.33,
+            vec![
+                events_tab,
+                history_tab,
+                resources_tab,
+                issues_tab,
+                trash_tab,
+            ],
+        );
         let [_events_node, _log_node] = surface.split_right(remaining, 0.5, vec![log_tab]);
 
-        Self { dock_state, height: None }
+        Self {
+            dock_state,
+            height: None,
+        }
     }
 }
 
-#[derive(PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum BottomTabType {
     Log,
     Debug,
     Events,
+    History,
+    Resources,
+    Issues,
+    Trash,
 }
 
 #[derive(Clone, PartialEq, Serialize, Deserialize)]
@@ -58,6 +88,22 @@ pub enum BottomTab {
         #[serde(skip)]
         data: EventsTabData,
     },
+    History {
+        #[serde(skip)]
+        data: HistoryTabData,
+    },
+    Resources {
+        #[serde(skip)]
+        data: ResourcesTabData,
+    },
+    Issues {
+        #[serde(skip)]
+        data: IssuesTabData,
+    },
+    Trash {
+        #[serde(skip)]
+        data: TrashTabData,
+    },
 }
 
 impl BottomTab {
@@ -66,6 +112,10 @@ impl BottomTab {
             BottomTab::Log { .. } => BottomTabType::Log,
             BottomTab::Debug { .. } => BottomTabType::Debug,
             BottomTab::Events { .. } => BottomTabType::Events,
+            BottomTab::History { .. } => BottomTabType::History,
+            BottomTab::Resources { .. } => BottomTabType::Resources,
+            BottomTab::Issues { .. } => BottomTabType::Issues,
+            BottomTab::Trash { .. } => BottomTabType::Trash,
         }
     }
 
@@ -80,6 +130,18 @@ impl BottomTab {
             BottomTabType::Events => BottomTab::Events {
                 data: Default::default(),
             },
+            BottomTabType::History => BottomTab::History {
+                data: Default::default(),
+            },
+            BottomTabType::Resources => BottomTab::Resources {
+                data: Default::default(),
+            },
+            BottomTabType::Issues => BottomTab::Issues {
+                data: Default::default(),
+            },
+            BottomTabType::Trash => BottomTab::Trash {
+                data: Default::default(),
+            },
         }
     }
 }
@@ -94,14 +156,22 @@ impl TabViewer for BottomTabViewer {
             BottomTab::Log { data, .. } => log_tab_ui(ui, data),
             BottomTab::Debug { data, .. } => debug_tab_ui(ui, data),
             BottomTab::Events { data, .. } => events_tab_ui(ui, data),
+            BottomTab::History { data, .. } => history_tab_ui(ui, data),
+            BottomTab::Resources { data, .. } => resources_tab_ui(ui, data),
+            BottomTab::Issues { data, .. } => issues_tab_ui(ui, data),
+            BottomTab::Trash { data, .. } => trash_tab_ui(ui, data),
         }
     }
 
     fn title(&mut self, tab: &mut Self::Tab) -> egui::WidgetText {
         match tab {
-            BottomTab::Log { .. } => "Log".into(),
+            BottomTab::Log { .. } => "Console".into(),
             BottomTab::Debug { .. } => "Debug".into(),
             BottomTab::Events { .. } => "Events".into(),
+            BottomTab::History { .. } => "History".into(),
+            BottomTab::Resources { .. } => "Resources".into(),
+            BottomTab::Issues { .. } => "Issues".into(),
+            BottomTab::Trash { .. } => "Trash".into(),
         }
     }
 }
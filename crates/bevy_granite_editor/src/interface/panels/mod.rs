@@ -1,5 +1,7 @@
 pub mod bottom_panel;
+pub mod restrictions;
 pub mod right_panel;
 
 pub use bottom_panel::*;
-pub use right_panel::*;
\ No newline at end of file
+pub use restrictions::*;
+pub use right_panel::*;
@@ -0,0 +1,62 @@
+use super::{BottomDockState, BottomTabType, SideDockState, SideTabType};
+use bevy::prelude::{Res, ResMut, Resource};
+
+/// Limits which tabs the editor offers, i.e. for shipped dev builds that only need the log and events
+/// None allows every tab of that dock. Insert before adding the editor plugin:
+/// app.insert_resource(EditorTabRestrictions::default().allow_bottom_tabs([BottomTabType::Log]))
+#[derive(Resource, Default, Clone)]
+pub struct EditorTabRestrictions {
+    pub side: Option<Vec<SideTabType>>,
+    pub bottom: Option<Vec<BottomTabType>>,
+}
+
+impl EditorTabRestrictions {
+    pub fn allow_side_tabs(mut self, tabs: impl IntoIterator<Item = SideTabType>) -> Self {
+        self.side = Some(tabs.into_iter().collect());
+        self
+    }
+
+    pub fn allow_bottom_tabs(mut self, tabs: impl IntoIterator<Item = BottomTabType>) -> Self {
+        self.bottom = Some(tabs.into_iter().collect());
+        self
+    }
+
+    pub fn is_side_allowed(&self, tab: &SideTabType) -> bool {
+        self.side
+            .as_ref()
+            .is_none_or(|allowed| allowed.contains(tab))
+    }
+
+    pub fn is_bottom_allowed(&self, tab: &BottomTabType) -> bool {
+        self.bottom
+            .as_ref()
+            .is_none_or(|allowed| allowed.contains(tab))
+    }
+}
+
+/// Remove restricted tabs, including ones brought back by a saved layout
+pub fn enforce_tab_restrictions_system(
+    restrictions: Res<EditorTabRestrictions>,
+    mut side_dock: ResMut<SideDockState>,
+    mut bottom_dock: ResMut<BottomDockState>,
+) {
+    if side_dock
+        .dock_state
+        .iter_all_tabs()
+        .any(|(_, tab)| !restrictions.is_side_allowed(&tab.get_type()))
+    {
+        side_dock
+            .dock_state
+            .retain_tabs(|tab| restrictions.is_side_allowed(&tab.get_type()));
+    }
+
+    if bottom_dock
+        .dock_state
+        .iter_all_tabs()
+        .any(|(_, tab)| !restrictions.is_bottom_allowed(&tab.get_type()))
+    {
+        bottom_dock
+            .dock_state
+            .retain_tabs(|tab| restrictions.is_bottom_allowed(&tab.get_type()));
+    }
+}
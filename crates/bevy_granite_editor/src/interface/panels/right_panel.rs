@@ -4,11 +4,9 @@ use egui::{Ui, WidgetText};
 use egui_dock::{DockState, NodeIndex, TabViewer};
 use serde::{Deserialize, Serialize};
 
-use crate::interface::{
-    tabs::{
-        editor_settings::ui::editor_settings_tab_ui, entity_editor::tab::entity_editor_tab_ui,
-        node_tree::node_tree_tab_ui, EditorSettingsTabData, EntityEditorTabData, NodeTreeTabData
-    },
+use crate::interface::tabs::{
+    editor_settings::ui::editor_settings_tab_ui, entity_editor::tab::entity_editor_tab_ui,
+    node_tree::node_tree_tab_ui, EditorSettingsTabData, EntityEditorTabData, NodeTreeTabData,
 };
 
 #[derive(Resource, Clone)]
@@ -34,11 +32,14 @@ impl Default for SideDockState {
         let [_old_node, _entity_editor_node] =
             surface.split_below(NodeIndex::root(), 0.3, vec![entity_editor_tab]);
 
-        Self { dock_state, width: None }
+        Self {
+            dock_state,
+            width: None,
+        }
     }
 }
 
-#[derive(PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum SideTabType {
     EntityEditor,
     NodeTree,
@@ -2,19 +2,30 @@ use super::{
     cache::update_entity_cache_system,
     events::{
         MaterialDeleteEvent, MaterialHandleUpdateEvent, PopupMenuRequestedEvent,
-        RequestCameraEntityFrame, RequestEditorToggle, RequestNewParent, RequestRemoveChildren,
-        RequestRemoveParents, RequestToggleCameraSync, RequestViewportCameraOverride,
+        RequestBakeImposters, RequestCameraEntityFrame, RequestCubemapCapture,
+        RequestEditorOverlayToggle, RequestEditorToggle, RequestEmptyTrash, RequestNewParent,
+        RequestRandomizationPreview, RequestRedo, RequestRemoveChildren, RequestRemoveParents,
+        RequestRestoreFromTrash, RequestSafeDeleteEvent, RequestSceneThumbnail,
+        RequestSimulationTime, RequestToggleCameraSync, RequestUndo, RequestViewportCameraOverride,
         SetActiveWorld, UserRequestGraniteTypeViaPopup, UserUpdatedComponentsEvent,
         UserUpdatedIdentityEvent, UserUpdatedTransformEvent,
     },
     layout::dock_ui_system,
-    popups::{handle_popup_requests_system, show_active_popups_system},
+    panels::{enforce_tab_restrictions_system, EditorTabRestrictions},
+    popups::{
+        handle_popup_requests_system, prepare_scene_gallery_system, show_active_popups_system,
+    },
     tabs::{
-        handle_material_deletion_system, send_queued_events_system, update_debug_tab_ui_system,
-        update_editor_settings_tab_system, update_entity_editor_tab_system,
+        collect_event_feedback_system, handle_material_deletion_system, send_queued_events_system,
+        update_camera_debug_system, update_debug_tab_ui_system, update_editor_settings_tab_system,
+        update_entity_editor_tab_system, update_entity_mesh_stats_system,
         update_entity_with_new_components_system, update_entity_with_new_identity_system,
-        update_entity_with_new_transform_system, update_log_tab_system,
-        update_material_handle_system, update_node_tree_tabs_system, RequestReparentEntityEvent,
+        update_entity_with_new_transform_system, update_history_tab_system,
+        update_issues_tab_system, update_log_tab_system, update_material_handle_system,
+        update_material_users_system, update_node_tree_tabs_system, update_resources_tab_system,
+        update_scene_profile_debug_system, update_state_recorder_debug_system,
+        update_trash_tab_system, update_uv_tiling_preview_source_system,
+        RequestReparentEntityEvent, UICallableEventFeedback,
     },
     BottomDockState, EntityUIDataCache, PopupState, SideDockState,
 };
@@ -41,13 +52,25 @@ impl Plugin for InterfacePlugin {
             .add_message::<UserRequestGraniteTypeViaPopup>()
             .add_message::<PopupMenuRequestedEvent>()
             .add_message::<RequestEditorToggle>()
+            .add_message::<RequestEditorOverlayToggle>()
             .add_message::<RequestCameraEntityFrame>()
             .add_message::<RequestToggleCameraSync>()
+            .add_message::<RequestUndo>()
+            .add_message::<RequestRedo>()
             .add_message::<RequestNewParent>()
             .add_message::<RequestRemoveChildren>()
+            .add_message::<RequestSafeDeleteEvent>()
+            .add_message::<RequestRestoreFromTrash>()
+            .add_message::<RequestEmptyTrash>()
             .add_message::<RequestRemoveParents>()
             .add_message::<SetActiveWorld>()
             .add_message::<RequestViewportCameraOverride>()
+            .add_message::<RequestSceneThumbnail>()
+            .add_message::<RequestCubemapCapture>()
+            .add_message::<RequestBakeImposters>()
+            .add_message::<RequestRandomizationPreview>()
+            .add_message::<RequestSimulationTime>()
+            .add_message::<UICallableEventFeedback>()
             // need to rework
             .add_message::<RequestReparentEntityEvent>()
             .add_message::<RequestRemoveParentsFromEntities>()
@@ -64,6 +87,7 @@ impl Plugin for InterfacePlugin {
             .insert_resource(PopupState::default())
             .insert_resource(SideDockState::default())
             .insert_resource(BottomDockState::default())
+            .init_resource::<EditorTabRestrictions>()
             //
             // Schedule systems
             //
@@ -91,9 +115,23 @@ impl Plugin for InterfacePlugin {
                     //
                     update_node_tree_tabs_system,
                     update_entity_editor_tab_system,
+                    (
+                        update_entity_mesh_stats_system,
+                        update_material_users_system,
+                    ),
+                    update_uv_tiling_preview_source_system,
                     update_editor_settings_tab_system,
                     update_log_tab_system,
                     update_debug_tab_ui_system,
+                    update_camera_debug_system,
+                    update_state_recorder_debug_system,
+                    update_scene_profile_debug_system,
+                    update_history_tab_system,
+                    (
+                        update_resources_tab_system,
+                        update_issues_tab_system,
+                        update_trash_tab_system,
+                    ),
                     update_node_tree_tabs_system,
                 )
                     .chain()
@@ -103,7 +141,15 @@ impl Plugin for InterfacePlugin {
                 EguiPrimaryContextPass,
                 (show_active_popups_system, dock_ui_system).run_if(is_editor_active),
             )
-            .add_systems(Update, send_queued_events_system.run_if(is_editor_active));
+            .add_systems(
+                Update,
+                (
+                    send_queued_events_system,
+                    collect_event_feedback_system,
+                    enforce_tab_restrictions_system.after(update_editor_settings_tab_system),
+                    prepare_scene_gallery_system.after(handle_popup_requests_system),
+                )
+                    .run_if(is_editor_active),
+            );
     }
 }
-
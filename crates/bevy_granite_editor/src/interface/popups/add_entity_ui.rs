@@ -9,7 +9,7 @@ use bevy_egui::{
     egui::{self, Window},
     EguiContexts,
 };
-use bevy_granite_core::{ClassCategory, GraniteType, GraniteTypes};
+use bevy_granite_core::{ClassCategory, GraniteClassRegistry, GraniteType, GraniteTypes};
 
 // We dont need EntityClassType. Its the same list as the data sister struct. just keep one struct - the data one
 
@@ -17,6 +17,7 @@ pub fn add_entity_ui(
     contexts: &mut EguiContexts,
     position: Vec2,
     mut entity_add_request: MessageWriter<UserRequestGraniteTypeViaPopup>,
+    class_registry: &GraniteClassRegistry,
 ) -> bool {
     let mut should_close = false;
 
@@ -33,7 +34,7 @@ pub fn add_entity_ui(
         ))
         .show(contexts.ctx_mut().expect("Egui context to exist"), |ui| {
             ui.horizontal(|ui| {
-                let categories = get_all_categories();
+                let categories = class_registry.categories();
 
                 // Get or initialize the last hovered category from memory
                 let popup_id = egui::Id::new("add_entity_last_category");
@@ -52,7 +53,7 @@ pub fn add_entity_ui(
 
                     for category in categories {
                         let category_name = category.get_friendly_name();
-                        let entities_in_category = GraniteTypes::all_by_category(category);
+                        let entities_in_category = class_registry.by_category(category);
 
                         if entities_in_category.is_empty()
                             || (entities_in_category.len() == 1
@@ -111,15 +112,3 @@ pub fn add_entity_ui(
     }
     should_close
 }
-
-fn get_all_categories() -> Vec<ClassCategory> {
-    let mut categories = GraniteTypes::all()
-        .into_iter()
-        .map(|entity_type| entity_type.category())
-        .collect::<std::collections::HashSet<_>>()
-        .into_iter()
-        .collect::<Vec<_>>();
-
-    categories.sort();
-    categories
-}
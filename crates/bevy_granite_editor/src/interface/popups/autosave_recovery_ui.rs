@@ -0,0 +1,102 @@
+use crate::{
+    editor_state::{discard_autosave, format_modified, recover_autosave, Autosave},
+    interface::{shared::widgets::make_frame_solid_via_context, EditorEvents},
+    UI_CONFIG,
+};
+use bevy::prelude::ResMut;
+use bevy_egui::{
+    egui::{self, Window},
+    EguiContexts,
+};
+use bevy_granite_core::{RequestDespawnBySource, RequestLoadEvent, SaveSettings};
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+
+pub fn autosave_recovery_ui(
+    contexts: &mut EguiContexts,
+    mut autosave: ResMut<Autosave>,
+    mut events: EditorEvents,
+) -> bool {
+    let spacing = UI_CONFIG.spacing;
+    let large_spacing = UI_CONFIG.large_spacing;
+    let mut resolved: Vec<(String, bool)> = Vec::new();
+
+    let _response = Window::new("Recover Autosave")
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .frame(make_frame_solid_via_context(
+            egui::Frame::window(&contexts.ctx_mut().expect("Egui context to exist").style()),
+            contexts.ctx_mut().expect("Egui context to exist"),
+        ))
+        .show(contexts.ctx_mut().expect("Egui context to exist"), |ui| {
+            ui.set_max_width(450.);
+            ui.label("These scenes have autosaved edits newer than the saved file, the editor may not have closed cleanly.");
+            ui.add_space(spacing);
+            ui.label("Recovering writes the autosave over the scene file and reloads it.");
+            ui.add_space(large_spacing);
+
+            egui::Grid::new("autosave_recovery_grid")
+                .num_columns(4)
+                .spacing([large_spacing, spacing])
+                .show(ui, |ui| {
+                    for recovery in autosave.recoveries.iter() {
+                        ui.label(&recovery.scene);
+                        ui.weak(format_modified(Some(recovery.modified)));
+                        if ui.button("Recover").clicked() {
+                            resolved.push((recovery.scene.clone(), true));
+                        }
+                        if ui
+                            .button("Discard")
+                            .on_hover_text("Deletes the autosave, the saved file is kept")
+                            .clicked()
+                        {
+                            resolved.push((recovery.scene.clone(), false));
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+
+    for (scene, recover) in resolved {
+        autosave
+            .recoveries
+            .retain(|recovery| recovery.scene != scene);
+        let result = if recover {
+            recover_autosave(&scene)
+        } else {
+            discard_autosave(&scene)
+        };
+        match result {
+            Ok(()) if recover => {
+                log!(
+                    LogType::Editor,
+                    LogLevel::OK,
+                    LogCategory::System,
+                    "Recovered '{}' from its autosave",
+                    scene
+                );
+                events
+                    .despawn_by_source
+                    .write(RequestDespawnBySource::new(scene.clone()));
+                events
+                    .load
+                    .write(RequestLoadEvent(scene, SaveSettings::Runtime, None));
+            }
+            Ok(()) => {}
+            Err(e) => log!(
+                LogType::Editor,
+                LogLevel::Error,
+                LogCategory::System,
+                "Failed to {} the autosave of '{}': {}",
+                if recover { "recover" } else { "discard" },
+                scene,
+                e
+            ),
+        }
+    }
+
+    autosave.recoveries.is_empty()
+}
@@ -0,0 +1,109 @@
+use crate::{
+    interface::{shared::widgets::make_frame_solid_via_context, EditorEvents},
+    UI_CONFIG,
+};
+use bevy::ecs::entity::Entity;
+use bevy_egui::{
+    egui::{self, Window},
+    EguiContexts,
+};
+use bevy_granite_core::{CsvColumns, RequestExportEntityCsv, RequestImportEntityCsv};
+use native_dialog::FileDialog;
+
+// csv_bulk_edit_ui.rs
+// Export the selected entities to a spreadsheet and read the edited sheet back in
+
+#[derive(Clone, Default)]
+pub struct CsvBulkEditPopupData {
+    pub columns: CsvColumns,
+}
+
+pub fn csv_bulk_edit_ui(
+    contexts: &mut EguiContexts,
+    data: &mut CsvBulkEditPopupData,
+    selected: &[Entity],
+    component_names: &[String],
+    mut events: EditorEvents,
+) -> bool {
+    let spacing = UI_CONFIG.spacing;
+    let large_spacing = UI_CONFIG.large_spacing;
+    let mut should_close = false;
+
+    let _response = Window::new("CSV Bulk Edit")
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .frame(make_frame_solid_via_context(
+            egui::Frame::window(&contexts.ctx_mut().expect("Egui context to exist").style()),
+            contexts.ctx_mut().expect("Egui context to exist"),
+        ))
+        .show(contexts.ctx_mut().expect("Egui context to exist"), |ui| {
+            ui.set_min_width(400.);
+            ui.label(format!("{} selected entities", selected.len()));
+            ui.add_space(spacing);
+
+            let columns = &mut data.columns;
+            ui.checkbox(&mut columns.name, "Name");
+            ui.checkbox(&mut columns.transform, "Position, rotation and scale");
+            ui.checkbox(&mut columns.properties, "Custom properties");
+            ui.horizontal(|ui| {
+                ui.label("Component:");
+                let selected_text = columns
+                    .component
+                    .as_deref()
+                    .map(|name| name.rsplit("::").next().unwrap_or(name))
+                    .unwrap_or("None");
+                egui::ComboBox::from_id_salt("csv_bulk_edit_component")
+                    .selected_text(selected_text)
+                    .width(260.)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut columns.component, None, "None");
+                        for name in component_names {
+                            let short = name.rsplit("::").next().unwrap_or(name);
+                            ui.selectable_value(&mut columns.component, Some(name.clone()), short)
+                                .on_hover_text(name);
+                        }
+                    });
+            });
+
+            ui.add_space(spacing);
+            ui.label("Rows are matched back to entities by the id column.");
+            ui.label("Only changed cells are applied, empty cells are left alone.");
+            ui.add_space(large_spacing);
+
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(!selected.is_empty(), egui::Button::new("Export..."))
+                    .clicked()
+                {
+                    if let Ok(Some(path)) = FileDialog::new()
+                        .add_filter("CSV", &["csv"])
+                        .set_filename("entities.csv")
+                        .show_save_single_file()
+                    {
+                        events.export_csv.write(RequestExportEntityCsv {
+                            path: path.display().to_string(),
+                            entities: selected.to_vec(),
+                            columns: data.columns.clone(),
+                        });
+                    }
+                }
+                if ui.button("Import...").clicked() {
+                    if let Ok(Some(path)) = FileDialog::new()
+                        .add_filter("CSV", &["csv"])
+                        .show_open_single_file()
+                    {
+                        events.import_csv.write(RequestImportEntityCsv {
+                            path: path.display().to_string(),
+                        });
+                        should_close = true;
+                    }
+                }
+                if ui.button("Close").clicked() {
+                    should_close = true;
+                }
+            });
+        });
+
+    should_close
+}
@@ -1,9 +1,27 @@
 pub mod add_entity_ui;
-pub mod relationship_ui;
-pub mod popup_requested_system;
+pub mod autosave_recovery_ui;
+pub mod csv_bulk_edit_ui;
 pub mod help_ui;
+pub mod move_asset_ui;
+pub mod popup_requested_system;
+pub mod project_locked_ui;
+pub mod project_wizard_ui;
+pub mod reassign_source_ui;
+pub mod relationship_ui;
+pub mod safe_delete_ui;
+pub mod scene_file_changed_ui;
+pub mod scene_gallery_ui;
 
 pub use add_entity_ui::*;
-pub use relationship_ui::*;
+pub use autosave_recovery_ui::*;
+pub use csv_bulk_edit_ui::*;
+pub use help_ui::*;
+pub use move_asset_ui::*;
 pub use popup_requested_system::*;
-pub use help_ui::*;
\ No newline at end of file
+pub use project_locked_ui::*;
+pub use project_wizard_ui::*;
+pub use reassign_source_ui::*;
+pub use relationship_ui::*;
+pub use safe_delete_ui::*;
+pub use scene_file_changed_ui::*;
+pub use scene_gallery_ui::*;
@@ -0,0 +1,110 @@
+use crate::{
+    interface::{shared::widgets::make_frame_solid_via_context, EditorEvents},
+    UI_CONFIG,
+};
+use bevy_egui::{
+    egui::{self, Window},
+    EguiContexts,
+};
+use bevy_granite_core::{
+    absolute_asset_to_rel, find_asset_references, shared::asset_file_browser, RequestAssetMoveEvent,
+};
+
+#[derive(Default, Clone)]
+pub struct MoveAssetPopupData {
+    pub from: String,
+    pub to: String,
+    /// Scene and material files that reference `from`. None until previewed
+    pub preview: Option<Vec<String>>,
+}
+
+pub fn move_asset_ui(
+    contexts: &mut EguiContexts,
+    data: &mut MoveAssetPopupData,
+    mut events: EditorEvents,
+) -> bool {
+    let spacing = UI_CONFIG.spacing;
+    let large_spacing = UI_CONFIG.large_spacing;
+    let mut should_close = false;
+
+    let _response = Window::new("Move / Rename Asset")
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .frame(make_frame_solid_via_context(
+            egui::Frame::window(&contexts.ctx_mut().expect("Egui context to exist").style()),
+            contexts.ctx_mut().expect("Egui context to exist"),
+        ))
+        .show(contexts.ctx_mut().expect("Egui context to exist"), |ui| {
+            ui.set_min_width(400.);
+            egui::Grid::new("move_asset_grid")
+                .num_columns(3)
+                .spacing([large_spacing, large_spacing])
+                .show(ui, |ui| {
+                    ui.label("From:");
+                    if ui.text_edit_singleline(&mut data.from).changed() {
+                        data.preview = None;
+                    }
+                    if ui.button("Browse").clicked() {
+                        if let Some(path) = asset_file_browser("".to_string(), vec!["*"]) {
+                            data.from = absolute_asset_to_rel(path).to_string();
+                            data.to = data.from.clone();
+                            data.preview = None;
+                        }
+                    }
+                    ui.end_row();
+
+                    ui.label("To:");
+                    ui.text_edit_singleline(&mut data.to);
+                    ui.end_row();
+                });
+
+            ui.add_space(spacing);
+            ui.label("Paths are relative to the assets folder. Folders can be moved too.");
+            ui.add_space(large_spacing);
+
+            match &data.preview {
+                None => {
+                    if ui
+                        .add_enabled(!data.from.is_empty(), egui::Button::new("Preview"))
+                        .clicked()
+                    {
+                        data.preview = Some(find_asset_references(&data.from));
+                    }
+                }
+                Some(affected) => {
+                    ui.label(format!("{} files will be updated:", affected.len()));
+                    egui::ScrollArea::vertical()
+                        .max_height(200.)
+                        .show(ui, |ui| {
+                            for file in affected {
+                                ui.label(file);
+                            }
+                        });
+                }
+            }
+
+            ui.add_space(large_spacing);
+            ui.horizontal(|ui| {
+                let can_move =
+                    data.preview.is_some() && !data.to.is_empty() && data.to != data.from;
+                if ui
+                    .add_enabled(can_move, egui::Button::new("Move"))
+                    .clicked()
+                {
+                    events
+                        .move_asset
+                        .write(RequestAssetMoveEvent(data.from.clone(), data.to.clone()));
+                    should_close = true;
+                }
+                if ui.button("Cancel").clicked() {
+                    should_close = true;
+                }
+            });
+        });
+
+    if should_close {
+        *data = MoveAssetPopupData::default();
+    }
+    should_close
+}
@@ -1,23 +1,34 @@
 use bevy::{
     ecs::{
+        entity::Entity,
         message::{MessageReader, MessageWriter},
         query::With,
-        system::{Query, ResMut},
+        system::{Query, Res, ResMut, SystemParam},
     },
     math::Vec2,
     prelude::Resource,
     window::{PrimaryWindow, Window},
 };
 use bevy_egui::EguiContexts;
+use bevy_granite_core::{GraniteClassRegistry, RegisteredTypeNames, SaveLock, SceneFileWatcher};
+use bevy_granite_gizmos::Selected;
 use bevy_granite_logging::{
     config::{LogCategory, LogLevel, LogType},
     log,
 };
 
 use crate::{
-    editor_state::EditorState,
+    editor_state::{
+        Autosave, EditorState, ExternalSceneChanges, PendingDelete, ProjectLock,
+        ProjectWizardPopupData,
+    },
     interface::{
-        popups::{add_entity_ui, help_ui, relationship_ui},
+        popups::{
+            add_entity_ui, autosave_recovery_ui, csv_bulk_edit_ui, help_ui, move_asset_ui,
+            project_locked_ui, project_wizard_ui, reassign_source_ui, relationship_ui,
+            safe_delete_ui, scene_file_changed_ui, scene_gallery_ui, CsvBulkEditPopupData,
+            MoveAssetPopupData, ReassignSourcePopupData, SceneGalleryPopupData,
+        },
         EditorEvents, PopupMenuRequestedEvent, UserRequestGraniteTypeViaPopup,
     },
 };
@@ -27,12 +38,45 @@ pub enum PopupType {
     AddRelationship,
     AddEntity,
     Help,
+    MoveAsset,
+    ProjectLocked,
+    AutosaveRecovery,
+    SceneFileChanged,
+    ProjectWizard,
+    SceneGallery,
+    ReassignSource,
+    CsvBulkEdit,
+    SafeDelete,
 }
 
 #[derive(Default, Resource)]
 pub struct PopupState {
     pub active_popup: Option<PopupType>,
     pub popup_position: Vec2,
+    pub move_asset: MoveAssetPopupData,
+    pub project_wizard: ProjectWizardPopupData,
+    pub scene_gallery: SceneGalleryPopupData,
+    pub reassign_source: ReassignSourcePopupData,
+    pub csv_bulk_edit: CsvBulkEditPopupData,
+}
+
+/// World data popups read from
+#[derive(SystemParam)]
+pub struct PopupLookups<'w, 's> {
+    window: Query<'w, 's, &'static Window, With<PrimaryWindow>>,
+    selected: Query<'w, 's, Entity, With<Selected>>,
+    type_names: Res<'w, RegisteredTypeNames>,
+}
+
+/// Project state popups change
+#[derive(SystemParam)]
+pub struct PopupProject<'w> {
+    project_lock: ResMut<'w, ProjectLock>,
+    save_lock: ResMut<'w, SaveLock>,
+    autosave: ResMut<'w, Autosave>,
+    watcher: ResMut<'w, SceneFileWatcher>,
+    external_changes: ResMut<'w, ExternalSceneChanges>,
+    pending_delete: ResMut<'w, PendingDelete>,
 }
 
 pub fn handle_popup_requests_system(
@@ -47,6 +91,9 @@ pub fn handle_popup_requests_system(
             "Popup menu requested: {:?}",
             popup
         );
+        if matches!(popup, PopupType::SceneGallery) {
+            popup_state.scene_gallery.needs_scan = true;
+        }
         popup_state.active_popup = Some(popup.clone());
         popup_state.popup_position = *mouse_pos;
     }
@@ -56,25 +103,82 @@ pub fn show_active_popups_system(
     mut contexts: EguiContexts,
     mut popup_state: ResMut<PopupState>,
     events: EditorEvents,
-    entity_add_writer: MessageWriter<UserRequestGraniteTypeViaPopup>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    entity_add: (
+        MessageWriter<UserRequestGraniteTypeViaPopup>,
+        Res<GraniteClassRegistry>,
+    ),
+    lookups: PopupLookups,
     editor_state: ResMut<EditorState>,
+    project: PopupProject,
 ) {
-    if let Some(popup_type) = &popup_state.active_popup {
+    if let Some(popup_type) = popup_state.active_popup.clone() {
         let should_close = match popup_type {
-            PopupType::AddEntity => {
-                add_entity_ui(&mut contexts, popup_state.popup_position, entity_add_writer)
-            }
+            PopupType::AddEntity => add_entity_ui(
+                &mut contexts,
+                popup_state.popup_position,
+                entity_add.0,
+                &entity_add.1,
+            ),
             PopupType::AddRelationship => {
                 relationship_ui(&mut contexts, popup_state.popup_position, events)
             }
             PopupType::Help => {
-                if let Ok(window) = window_query.single() {
+                if let Ok(window) = lookups.window.single() {
                     help_ui(&mut contexts, window, editor_state)
                 } else {
                     false
                 }
             }
+            PopupType::MoveAsset => {
+                move_asset_ui(&mut contexts, &mut popup_state.move_asset, events)
+            }
+            PopupType::ProjectLocked => {
+                project_locked_ui(&mut contexts, project.project_lock, project.save_lock)
+            }
+            PopupType::AutosaveRecovery => {
+                autosave_recovery_ui(&mut contexts, project.autosave, events)
+            }
+            PopupType::SceneFileChanged => scene_file_changed_ui(
+                &mut contexts,
+                project.watcher,
+                project.external_changes,
+                events,
+            ),
+            PopupType::ProjectWizard => project_wizard_ui(
+                &mut contexts,
+                &mut popup_state.project_wizard,
+                editor_state,
+                events,
+            ),
+            PopupType::SceneGallery => scene_gallery_ui(
+                &mut contexts,
+                &mut popup_state.scene_gallery,
+                &editor_state,
+                events,
+            ),
+            PopupType::ReassignSource => reassign_source_ui(
+                &mut contexts,
+                &mut popup_state.reassign_source,
+                &editor_state,
+                events,
+            ),
+            PopupType::CsvBulkEdit => {
+                let selected: Vec<Entity> = lookups.selected.iter().collect();
+                let component_names: Vec<String> = lookups
+                    .type_names
+                    .names
+                    .iter()
+                    .map(|name| name.to_string())
+                    .collect();
+                csv_bulk_edit_ui(
+                    &mut contexts,
+                    &mut popup_state.csv_bulk_edit,
+                    &selected,
+                    &component_names,
+                    events,
+                )
+            }
+            PopupType::SafeDelete => safe_delete_ui(&mut contexts, project.pending_delete),
         };
 
         if should_close {
@@ -0,0 +1,59 @@
+use crate::{
+    editor_state::ProjectLock, interface::shared::widgets::make_frame_solid_via_context, UI_CONFIG,
+};
+use bevy::prelude::ResMut;
+use bevy_egui::{
+    egui::{self, Window},
+    EguiContexts,
+};
+use bevy_granite_core::SaveLock;
+
+pub fn project_locked_ui(
+    contexts: &mut EguiContexts,
+    mut project_lock: ResMut<ProjectLock>,
+    mut save_lock: ResMut<SaveLock>,
+) -> bool {
+    let spacing = UI_CONFIG.spacing;
+    let large_spacing = UI_CONFIG.large_spacing;
+    let mut should_close = false;
+
+    let _response = Window::new("Project In Use")
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .frame(make_frame_solid_via_context(
+            egui::Frame::window(&contexts.ctx_mut().expect("Egui context to exist").style()),
+            contexts.ctx_mut().expect("Egui context to exist"),
+        ))
+        .show(contexts.ctx_mut().expect("Egui context to exist"), |ui| {
+            ui.set_max_width(400.);
+            let owner = project_lock
+                .other_pid
+                .map(|pid| format!("(pid {})", pid))
+                .unwrap_or_default();
+            ui.label(format!(
+                "Another Granite editor {} is already using this project.",
+                owner
+            ));
+            ui.add_space(spacing);
+            ui.label("Saving from both editors will silently overwrite each other's changes.");
+            ui.add_space(large_spacing);
+
+            ui.horizontal(|ui| {
+                if ui.button("Open Read-Only").clicked() {
+                    project_lock.enter_read_only(&mut save_lock);
+                    should_close = true;
+                }
+                if ui
+                    .button("Take Over")
+                    .on_hover_text("Only do this if the other editor has crashed or is closed")
+                    .clicked()
+                {
+                    project_lock.take_over(&mut save_lock);
+                    should_close = true;
+                }
+            });
+        });
+
+    should_close
+}
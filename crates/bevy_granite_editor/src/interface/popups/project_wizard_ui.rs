@@ -0,0 +1,102 @@
+use crate::{
+    editor_state::{create_project, EditorState, ProjectWizardPopupData, SceneTemplate},
+    interface::{shared::widgets::make_frame_solid_via_context, EditorEvents},
+    UI_CONFIG,
+};
+use bevy::prelude::ResMut;
+use bevy_egui::{
+    egui::{self, Window},
+    EguiContexts,
+};
+use bevy_granite_core::{RequestLoadEvent, SaveSettings};
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+
+pub fn project_wizard_ui(
+    contexts: &mut EguiContexts,
+    data: &mut ProjectWizardPopupData,
+    editor_state: ResMut<EditorState>,
+    mut events: EditorEvents,
+) -> bool {
+    let spacing = UI_CONFIG.spacing;
+    let large_spacing = UI_CONFIG.large_spacing;
+    let mut should_close = false;
+
+    let _response = Window::new("New Project")
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .frame(make_frame_solid_via_context(
+            egui::Frame::window(&contexts.ctx_mut().expect("Egui context to exist").style()),
+            contexts.ctx_mut().expect("Egui context to exist"),
+        ))
+        .show(contexts.ctx_mut().expect("Egui context to exist"), |ui| {
+            ui.set_min_width(400.);
+            ui.label("No editor config was found in this assets folder. Set up a new project?");
+            ui.add_space(large_spacing);
+
+            egui::Grid::new("project_wizard_grid")
+                .num_columns(2)
+                .spacing([large_spacing, large_spacing])
+                .show(ui, |ui| {
+                    ui.label("Template:");
+                    egui::ComboBox::from_id_salt("project_wizard_template")
+                        .selected_text(data.template.get_friendly_name())
+                        .show_ui(ui, |ui| {
+                            for template in SceneTemplate::all() {
+                                ui.selectable_value(
+                                    &mut data.template,
+                                    template,
+                                    template.get_friendly_name(),
+                                );
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label("Starter scene:");
+                    ui.text_edit_singleline(&mut data.scene_path);
+                    ui.end_row();
+                });
+
+            ui.add_space(spacing);
+            ui.checkbox(
+                &mut data.create_folders,
+                "Create scenes, materials and config folders",
+            );
+            ui.add_space(large_spacing);
+
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(!data.scene_path.is_empty(), egui::Button::new("Create"))
+                    .clicked()
+                {
+                    match create_project(data, &editor_state) {
+                        Ok(scene_path) => {
+                            events.load.write(RequestLoadEvent(
+                                scene_path,
+                                SaveSettings::Runtime,
+                                None,
+                            ));
+                        }
+                        Err(e) => {
+                            log!(
+                                LogType::Editor,
+                                LogLevel::Error,
+                                LogCategory::System,
+                                "Failed to create project: {}",
+                                e
+                            );
+                        }
+                    }
+                    should_close = true;
+                }
+                if ui.button("Skip").clicked() {
+                    should_close = true;
+                }
+            });
+        });
+
+    should_close
+}
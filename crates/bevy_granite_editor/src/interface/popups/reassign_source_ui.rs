@@ -0,0 +1,120 @@
+use crate::{
+    editor_state::EditorState,
+    interface::{shared::widgets::make_frame_solid_via_context, EditorEvents},
+    UI_CONFIG,
+};
+use bevy_egui::{
+    egui::{self, Window},
+    EguiContexts,
+};
+use bevy_granite_core::{
+    absolute_asset_to_rel, ReassignEntities, RequestReassignSpawnSource, SCENE_FILE_EXTENSIONS,
+};
+use native_dialog::FileDialog;
+
+#[derive(Clone)]
+pub struct ReassignSourcePopupData {
+    pub from: String,
+    pub to: String,
+    /// Save both scenes right away, otherwise they are only marked dirty
+    pub save: bool,
+}
+
+impl Default for ReassignSourcePopupData {
+    fn default() -> Self {
+        Self {
+            from: String::new(),
+            to: String::new(),
+            save: true,
+        }
+    }
+}
+
+pub fn reassign_source_ui(
+    contexts: &mut EguiContexts,
+    data: &mut ReassignSourcePopupData,
+    editor_state: &EditorState,
+    mut events: EditorEvents,
+) -> bool {
+    let spacing = UI_CONFIG.spacing;
+    let large_spacing = UI_CONFIG.large_spacing;
+    let mut should_close = false;
+
+    let mut loaded: Vec<String> = editor_state.loaded_sources.iter().cloned().collect();
+    loaded.sort();
+
+    let _response = Window::new("Move Entities Between Scenes")
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .frame(make_frame_solid_via_context(
+            egui::Frame::window(&contexts.ctx_mut().expect("Egui context to exist").style()),
+            contexts.ctx_mut().expect("Egui context to exist"),
+        ))
+        .show(contexts.ctx_mut().expect("Egui context to exist"), |ui| {
+            ui.set_min_width(400.);
+            egui::Grid::new("reassign_source_grid")
+                .num_columns(3)
+                .spacing([large_spacing, large_spacing])
+                .show(ui, |ui| {
+                    ui.label("From:");
+                    egui::ComboBox::from_id_salt("reassign_source_from")
+                        .selected_text(&data.from)
+                        .width(260.)
+                        .show_ui(ui, |ui| {
+                            for source in loaded.iter() {
+                                ui.selectable_value(&mut data.from, source.clone(), source);
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label("To:");
+                    egui::ComboBox::from_id_salt("reassign_source_to")
+                        .selected_text(&data.to)
+                        .width(260.)
+                        .show_ui(ui, |ui| {
+                            for source in loaded.iter().filter(|source| **source != data.from) {
+                                ui.selectable_value(&mut data.to, source.clone(), source);
+                            }
+                        });
+                    if ui.button("New File").clicked() {
+                        if let Ok(Some(path)) = FileDialog::new()
+                            .add_filter("Granite Scene", &SCENE_FILE_EXTENSIONS)
+                            .show_save_single_file()
+                        {
+                            data.to = absolute_asset_to_rel(path.display().to_string()).to_string();
+                        }
+                    }
+                    ui.end_row();
+                });
+
+            ui.add_space(spacing);
+            ui.checkbox(&mut data.save, "Save both scenes");
+            ui.label("Every entity spawned from the first scene moves to the second.");
+            ui.label("To move only some entities, right click them in the Entities tab.");
+            ui.add_space(large_spacing);
+
+            ui.horizontal(|ui| {
+                let can_move = !data.from.is_empty() && !data.to.is_empty() && data.from != data.to;
+                if ui
+                    .add_enabled(can_move, egui::Button::new("Move"))
+                    .clicked()
+                {
+                    events.reassign_source.write(RequestReassignSpawnSource {
+                        entities: ReassignEntities::Source(data.from.clone()),
+                        target: data.to.clone(),
+                        save: data.save,
+                    });
+                    should_close = true;
+                }
+                if ui.button("Cancel").clicked() {
+                    should_close = true;
+                }
+            });
+        });
+
+    if should_close {
+        *data = ReassignSourcePopupData::default();
+    }
+    should_close
+}
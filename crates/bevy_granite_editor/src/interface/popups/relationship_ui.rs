@@ -1,6 +1,6 @@
 use crate::{
     interface::{
-        events::{RequestNewParent, RequestRemoveChildren, RequestRemoveParents},
+        events::{ReparentMode, RequestNewParent, RequestRemoveChildren, RequestRemoveParents},
         shared::widgets::make_frame_solid_via_context,
         EditorEvents,
     },
@@ -34,9 +34,14 @@ pub fn relationship_ui(
                 ui.label("Relationship:");
                 ui.add_space(spacing);
 
-                if ui.button("Set as Parent").clicked() {
-                    events.parent.write(RequestNewParent);
-                    should_close = true;
+                for mode in ReparentMode::all() {
+                    if ui
+                        .button(format!("Set as Parent, {}", mode.name()))
+                        .clicked()
+                    {
+                        events.parent.write(RequestNewParent { mode: Some(mode) });
+                        should_close = true;
+                    }
                 }
 
                 if ui.button("Remove Parent").clicked() {
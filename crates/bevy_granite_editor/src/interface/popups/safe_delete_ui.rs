@@ -0,0 +1,96 @@
+use crate::{
+    editor_state::{PendingDelete, SafeDeleteChoice},
+    interface::shared::widgets::make_frame_solid_via_context,
+    UI_CONFIG,
+};
+use bevy::prelude::ResMut;
+use bevy_egui::{
+    egui::{self, Window},
+    EguiContexts,
+};
+
+pub fn safe_delete_ui(contexts: &mut EguiContexts, mut pending: ResMut<PendingDelete>) -> bool {
+    let spacing = UI_CONFIG.spacing;
+    let large_spacing = UI_CONFIG.large_spacing;
+    let mut choice = None;
+
+    let _response = Window::new("Delete Referenced Entities")
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .frame(make_frame_solid_via_context(
+            egui::Frame::window(&contexts.ctx_mut().expect("Egui context to exist").style()),
+            contexts.ctx_mut().expect("Egui context to exist"),
+        ))
+        .show(contexts.ctx_mut().expect("Egui context to exist"), |ui| {
+            ui.set_max_width(500.);
+            ui.label(format!(
+                "Other entities reference the {} entities being deleted.",
+                pending.entities.len()
+            ));
+            ui.add_space(large_spacing);
+
+            egui::ScrollArea::vertical()
+                .id_salt("safe_delete_references")
+                .max_height(200.)
+                .show(ui, |ui| {
+                    for reference in pending.references.iter() {
+                        let target = pending
+                            .target_names
+                            .get(&reference.target)
+                            .cloned()
+                            .unwrap_or_else(|| reference.target.to_string());
+                        ui.horizontal(|ui| {
+                            ui.label(pending.name(reference.entity));
+                            ui.weak(&reference.component);
+                            ui.label(format!("-> {}", target));
+                        });
+                    }
+                });
+            ui.add_space(large_spacing);
+
+            if ui
+                .button("Clear References & Delete")
+                .on_hover_text(
+                    "Optional references become empty, a component that needs its reference is removed",
+                )
+                .clicked()
+            {
+                choice = Some(SafeDeleteChoice::Clear);
+            }
+            ui.add_space(spacing);
+
+            ui.horizontal(|ui| {
+                let selected = pending
+                    .reassign_to
+                    .map(|entity| pending.name(entity))
+                    .unwrap_or_else(|| "Select entity".to_string());
+                let mut reassign_to = pending.reassign_to;
+                egui::ComboBox::from_id_salt("safe_delete_reassign")
+                    .selected_text(selected)
+                    .show_ui(ui, |ui| {
+                        for (entity, name) in pending.candidates.iter() {
+                            ui.selectable_value(&mut reassign_to, Some(*entity), name);
+                        }
+                    });
+                pending.reassign_to = reassign_to;
+                if ui
+                    .add_enabled(reassign_to.is_some(), egui::Button::new("Reassign & Delete"))
+                    .on_hover_text("Points the references at this entity instead")
+                    .clicked()
+                {
+                    choice = reassign_to.map(SafeDeleteChoice::Reassign);
+                }
+            });
+            ui.add_space(spacing);
+
+            if ui.button("Cancel").clicked() {
+                choice = Some(SafeDeleteChoice::Cancel);
+            }
+        });
+
+    if choice.is_some() {
+        pending.choice = choice;
+    }
+    choice.is_some()
+}
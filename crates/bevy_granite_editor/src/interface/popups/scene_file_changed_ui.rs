@@ -0,0 +1,136 @@
+use crate::{
+    editor_state::ExternalSceneChanges,
+    interface::{shared::widgets::make_frame_solid_via_context, EditorEvents},
+    UI_CONFIG,
+};
+use bevy::prelude::ResMut;
+use bevy_egui::{
+    egui::{self, Window},
+    EguiContexts,
+};
+use bevy_granite_core::{
+    RequestDespawnBySource, RequestLoadEvent, SaveSettings, SceneEntityChange, SceneFileWatcher,
+};
+
+enum Choice {
+    Reload,
+    KeepMine,
+    Diff,
+}
+
+pub fn scene_file_changed_ui(
+    contexts: &mut EguiContexts,
+    mut watcher: ResMut<SceneFileWatcher>,
+    mut changes: ResMut<ExternalSceneChanges>,
+    mut events: EditorEvents,
+) -> bool {
+    let spacing = UI_CONFIG.spacing;
+    let large_spacing = UI_CONFIG.large_spacing;
+    let changed: Vec<String> = watcher.changed().map(str::to_string).collect();
+    let mut choices: Vec<(String, Choice)> = Vec::new();
+
+    let _response = Window::new("Scene Changed On Disk")
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .frame(make_frame_solid_via_context(
+            egui::Frame::window(&contexts.ctx_mut().expect("Egui context to exist").style()),
+            contexts.ctx_mut().expect("Egui context to exist"),
+        ))
+        .show(contexts.ctx_mut().expect("Egui context to exist"), |ui| {
+            ui.set_max_width(500.);
+            ui.label(
+                "These scenes were changed on disk by another program since they were loaded.",
+            );
+            ui.add_space(spacing);
+            ui.label("Saving them is refused until you reload them or keep your loaded version.");
+            ui.add_space(large_spacing);
+
+            for scene in changed.iter() {
+                ui.horizontal(|ui| {
+                    ui.label(scene);
+                    if ui
+                        .button("Reload")
+                        .on_hover_text(
+                            "Loads the file from disk, unsaved edits to this scene are lost",
+                        )
+                        .clicked()
+                    {
+                        choices.push((scene.clone(), Choice::Reload));
+                    }
+                    if ui
+                        .button("Keep mine")
+                        .on_hover_text("Keeps the loaded scene, the next save overwrites the file")
+                        .clicked()
+                    {
+                        choices.push((scene.clone(), Choice::KeepMine));
+                    }
+                    if ui
+                        .button("Diff")
+                        .on_hover_text("Compares the entities of the file with the loaded ones")
+                        .clicked()
+                    {
+                        choices.push((scene.clone(), Choice::Diff));
+                    }
+                });
+
+                let Some(diffs) = changes.diffs.get(scene) else {
+                    continue;
+                };
+                ui.add_space(spacing);
+                if diffs.is_empty() {
+                    ui.weak("No entity differs from the loaded scene.");
+                }
+                egui::ScrollArea::vertical()
+                    .id_salt(("scene_file_diff", scene))
+                    .max_height(200.)
+                    .show(ui, |ui| {
+                        for diff in diffs {
+                            let (marker, detail, color) = match &diff.change {
+                                SceneEntityChange::OnDisk => {
+                                    ("+", "only on disk".to_string(), egui::Color32::GREEN)
+                                }
+                                SceneEntityChange::Loaded => {
+                                    ("-", "only loaded".to_string(), egui::Color32::RED)
+                                }
+                                SceneEntityChange::Changed(parts) => {
+                                    ("~", parts.join(", "), egui::Color32::YELLOW)
+                                }
+                            };
+                            ui.horizontal(|ui| {
+                                ui.colored_label(color, marker);
+                                ui.label(&diff.name);
+                                ui.weak(detail);
+                            });
+                        }
+                    });
+                ui.add_space(large_spacing);
+            }
+        });
+
+    for (scene, choice) in choices {
+        match choice {
+            Choice::Reload => {
+                changes.diffs.remove(&scene);
+                watcher.acknowledge(&scene);
+                events
+                    .despawn_by_source
+                    .write(RequestDespawnBySource::new(scene.clone()));
+                events
+                    .load
+                    .write(RequestLoadEvent(scene, SaveSettings::Runtime, None));
+            }
+            Choice::KeepMine => {
+                changes.diffs.remove(&scene);
+                watcher.acknowledge(&scene);
+            }
+            Choice::Diff => changes.diff_requests.push(scene),
+        }
+    }
+
+    let done = watcher.changed().next().is_none();
+    if done {
+        changes.diffs.clear();
+    }
+    done
+}
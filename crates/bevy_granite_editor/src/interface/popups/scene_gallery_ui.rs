@@ -0,0 +1,169 @@
+use crate::{
+    editor_state::{format_modified, scan_scene_gallery, EditorState, SceneGalleryEntry},
+    interface::{
+        popups::{PopupState, PopupType},
+        shared::widgets::make_frame_solid_via_context,
+        EditorEvents,
+    },
+    UI_CONFIG,
+};
+use bevy::{
+    asset::AssetServer,
+    image::Image,
+    prelude::{Res, ResMut},
+};
+use bevy_egui::{
+    egui::{self, Window},
+    EguiContexts, EguiTextureHandle, EguiUserTextures,
+};
+use bevy_granite_core::{entities::SaveSettings, RequestLoadEvent};
+use std::collections::HashMap;
+
+const GALLERY_COLUMNS: usize = 3;
+const CARD_IMAGE_SIZE: [f32; 2] = [192., 108.];
+
+#[derive(Default, Clone)]
+pub struct SceneGalleryPopupData {
+    pub entries: Vec<SceneGalleryEntry>,
+    /// Thumbnail path -> egui texture
+    pub textures: HashMap<String, egui::TextureId>,
+    /// Rescan the assets folder next frame
+    pub needs_scan: bool,
+}
+
+/// Scans for scenes and registers their thumbnails with egui while the gallery is open
+pub fn prepare_scene_gallery_system(
+    mut popup_state: ResMut<PopupState>,
+    asset_server: Res<AssetServer>,
+    mut user_textures: ResMut<EguiUserTextures>,
+) {
+    if !matches!(popup_state.active_popup, Some(PopupType::SceneGallery))
+        || !popup_state.scene_gallery.needs_scan
+    {
+        return;
+    }
+
+    let data = &mut popup_state.scene_gallery;
+    data.needs_scan = false;
+    data.entries = scan_scene_gallery();
+    data.textures.clear();
+
+    for thumbnail in data
+        .entries
+        .iter()
+        .filter_map(|entry| entry.thumbnail.clone())
+    {
+        let handle = asset_server.load::<Image>(thumbnail.clone());
+        // Thumbnails are overwritten on save, so make sure we don't show a stale one
+        if asset_server.is_loaded(&handle) {
+            asset_server.reload(thumbnail.clone());
+        }
+        let texture = user_textures.add_image(EguiTextureHandle::Strong(handle));
+        data.textures.insert(thumbnail, texture);
+    }
+}
+
+pub fn scene_gallery_ui(
+    contexts: &mut EguiContexts,
+    data: &mut SceneGalleryPopupData,
+    editor_state: &EditorState,
+    mut events: EditorEvents,
+) -> bool {
+    let spacing = UI_CONFIG.spacing;
+    let large_spacing = UI_CONFIG.large_spacing;
+    let mut should_close = false;
+
+    let _response = Window::new("Scene Gallery")
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .frame(make_frame_solid_via_context(
+            egui::Frame::window(&contexts.ctx_mut().expect("Egui context to exist").style()),
+            contexts.ctx_mut().expect("Egui context to exist"),
+        ))
+        .show(contexts.ctx_mut().expect("Egui context to exist"), |ui| {
+            ui.set_min_width(640.);
+
+            if data.entries.is_empty() {
+                ui.label("No scenes found in the assets folder.");
+            }
+
+            egui::ScrollArea::vertical()
+                .max_height(480.)
+                .show(ui, |ui| {
+                    egui::Grid::new("scene_gallery_grid")
+                        .spacing([large_spacing, large_spacing])
+                        .show(ui, |ui| {
+                            for (index, entry) in data.entries.iter().enumerate() {
+                                let is_default = entry.path == editor_state.default_world;
+                                let is_loaded = editor_state.loaded_sources.contains(&entry.path);
+                                let texture = entry
+                                    .thumbnail
+                                    .as_ref()
+                                    .and_then(|thumbnail| data.textures.get(thumbnail));
+
+                                ui.vertical(|ui| {
+                                    match texture {
+                                        Some(texture) => {
+                                            ui.image((*texture, CARD_IMAGE_SIZE.into()));
+                                        }
+                                        None => {
+                                            ui.add_sized(
+                                                CARD_IMAGE_SIZE,
+                                                egui::Label::new(
+                                                    egui::RichText::new("No thumbnail").weak(),
+                                                ),
+                                            );
+                                        }
+                                    }
+
+                                    ui.horizontal(|ui| {
+                                        ui.strong(&entry.name);
+                                        if is_default {
+                                            ui.label(egui::RichText::new("Default").weak());
+                                        }
+                                    });
+                                    ui.label(egui::RichText::new(&entry.path).small().weak());
+                                    ui.label(format!(
+                                        "{} entities · {}",
+                                        entry
+                                            .entity_count
+                                            .map(|count| count.to_string())
+                                            .unwrap_or_else(|| "?".to_string()),
+                                        format_modified(entry.modified)
+                                    ));
+                                    ui.add_space(spacing);
+
+                                    let open = ui
+                                        .add_enabled(!is_loaded, egui::Button::new("Open"))
+                                        .on_disabled_hover_text("Already loaded");
+                                    if open.clicked() {
+                                        events.load.write(RequestLoadEvent(
+                                            entry.path.clone(),
+                                            SaveSettings::Runtime,
+                                            None,
+                                        ));
+                                        should_close = true;
+                                    }
+                                });
+
+                                if (index + 1) % GALLERY_COLUMNS == 0 {
+                                    ui.end_row();
+                                }
+                            }
+                        });
+                });
+
+            ui.add_space(large_spacing);
+            ui.horizontal(|ui| {
+                if ui.button("Refresh").clicked() {
+                    data.needs_scan = true;
+                }
+                if ui.button("Close").clicked() {
+                    should_close = true;
+                }
+            });
+        });
+
+    should_close
+}
@@ -7,7 +7,7 @@ use bevy_granite_logging::{
 use egui::{Align2, Rect, Response, Shape, Stroke, Ui, Vec2};
 use std::{
     borrow::Cow,
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
 };
 
 // Generic trait for items that can be displayed in selectors
@@ -75,13 +75,7 @@ impl SelectableItem for EditableMaterial {
     }
 
     fn group_key(&self) -> String {
-        if self.path.is_empty() || self.path == "None" {
-            "materials/internal".to_string()
-        } else if let Some(last_separator) = self.path.rfind('/') {
-            self.path[..last_separator].to_string()
-        } else {
-            "Root".to_string()
-        }
+        self.folder()
     }
 }
 
@@ -95,6 +89,25 @@ fn generic_selector_popup<T: SelectableItem>(
     no_items_message: &str,
     no_matches_message: &str,
     render_item: impl FnMut(&mut egui::Ui, &T) -> bool,
+) -> bool {
+    selector_popup_frame(ui, popup_id, button_response, |ui| {
+        render_popup_content(
+            ui,
+            search_filter,
+            items,
+            search_id_suffix,
+            no_items_message,
+            no_matches_message,
+            render_item,
+        )
+    })
+}
+
+fn selector_popup_frame(
+    ui: &mut egui::Ui,
+    popup_id: egui::Id,
+    button_response: &egui::Response,
+    add_contents: impl FnOnce(&mut egui::Ui) -> bool,
 ) -> bool {
     let mut popup_changed = false;
 
@@ -115,15 +128,7 @@ fn generic_selector_popup<T: SelectableItem>(
                     egui::ScrollArea::vertical()
                         .auto_shrink([false, true])
                         .show(ui, |ui: &mut egui::Ui| {
-                            popup_changed = render_popup_content(
-                                ui,
-                                search_filter,
-                                items,
-                                search_id_suffix,
-                                no_items_message,
-                                no_matches_message,
-                                render_item,
-                            );
+                            popup_changed = add_contents(ui);
                         });
                 })
             });
@@ -400,16 +405,47 @@ pub fn component_selector_combo(
     )
 }
 
+/// Bulk operation requested on a folder of the material selector
+#[derive(Debug, Clone, PartialEq)]
+pub enum MaterialFolderAction {
+    /// Write `category` into every material of the folder. Empty clears it so the directory is used again
+    SetCategory { folder: String, category: String },
+    /// Ask to delete the folder, listing the entities still using its materials first
+    RequestDelete { folder: String },
+    /// Delete every material of the folder from disk, once confirmed
+    Delete { folder: String },
+}
+
+#[derive(Default)]
+struct MaterialFolderNode<'a> {
+    materials: Vec<&'a EditableMaterial>,
+    children: BTreeMap<String, MaterialFolderNode<'a>>,
+}
+
+impl MaterialFolderNode<'_> {
+    fn count(&self) -> usize {
+        self.materials.len()
+            + self
+                .children
+                .values()
+                .map(|child| child.count())
+                .sum::<usize>()
+    }
+}
+
 pub fn material_selector_combo(
     ui: &mut egui::Ui,
     search_filter: &mut String,
     available_materials: &AvailableEditableMaterials,
     class_materal_path: &mut String,
     current_material: &mut EditableMaterial,
+    folder_action: &mut Option<MaterialFolderAction>,
+    previews: &HashMap<String, egui::TextureId>,
+    where_used: &mut Option<String>,
 ) -> bool {
     let popup_id = egui::Id::new("material_selector_popup");
 
-    let dropdown_text = if available_materials.materials.is_some() {
+    let dropdown_text = if available_materials.materials().is_some() {
         if current_material.is_empty() {
             "None"
         } else {
@@ -421,8 +457,55 @@ pub fn material_selector_combo(
 
     let button_response = handle_popup_button(ui, popup_id, dropdown_text, search_filter);
 
-    if let Some(ref obj_materials) = available_materials.materials {
-        generic_selector_popup(
+    let Some(obj_materials) = available_materials.materials() else {
+        return false;
+    };
+
+    let mut render_item = |ui: &mut egui::Ui, new_material: &EditableMaterial| {
+        let is_selected = *current_material.friendly_name == new_material.friendly_name
+            && *current_material.path == new_material.path;
+
+        let clicked = ui
+            .horizontal(|ui| {
+                // Materials still rendering get a blank square, so names stay lined up
+                let size = egui::vec2(16.0, 16.0);
+                match previews.get(&new_material.path) {
+                    Some(texture) => ui.image((*texture, size)),
+                    None => ui.allocate_response(size, egui::Sense::hover()),
+                };
+                let response = ui.selectable_label(is_selected, &new_material.friendly_name);
+                if !new_material.is_empty() {
+                    response.context_menu(|ui| {
+                        if ui.button("Where Used").clicked() {
+                            *where_used = Some(new_material.path.clone());
+                            ui.close();
+                        }
+                    });
+                }
+                response.clicked()
+            })
+            .inner;
+        if clicked {
+            *current_material = new_material.clone();
+            *class_materal_path = new_material.path.clone();
+
+            log!(
+                LogType::Editor,
+                LogLevel::OK,
+                LogCategory::UI,
+                "User selected new material: {}",
+                new_material.friendly_name
+            );
+
+            Popup::close_id(ui.ctx(), popup_id);
+            return true;
+        }
+        false
+    };
+
+    // Searching shows the flat list, browsing shows the folder tree
+    if !search_filter.is_empty() {
+        return generic_selector_popup(
             ui,
             popup_id,
             &button_response,
@@ -431,35 +514,247 @@ pub fn material_selector_combo(
             "material",
             "None",
             "No materials match your search",
-            |ui, new_material| {
-                let is_selected = *current_material.friendly_name == new_material.friendly_name
-                    && *current_material.path == new_material.path;
-
-                if ui
-                    .selectable_label(is_selected, &new_material.friendly_name)
-                    .clicked()
-                {
-                    current_material.friendly_name = new_material.friendly_name.clone();
-                    current_material.path = new_material.path.clone();
-
-                    *current_material = new_material.clone();
-                    *class_materal_path = new_material.path.clone();
-
-                    log!(
-                        LogType::Editor,
-                        LogLevel::OK,
-                        LogCategory::UI,
-                        "User selected new material: {}",
-                        new_material.friendly_name
-                    );
+            render_item,
+        );
+    }
 
-                    Popup::close_id(ui.ctx(), popup_id);
-                    return true;
+    selector_popup_frame(ui, popup_id, &button_response, |ui| {
+        render_search_box(ui, search_filter, "material");
+        if obj_materials.is_empty() {
+            ui.label("None");
+            return false;
+        }
+
+        let mut root = MaterialFolderNode::default();
+        for material in obj_materials {
+            let folder = material.folder();
+            let mut node = &mut root;
+            for part in folder.split('/').filter(|part| !part.is_empty()) {
+                node = node.children.entry(part.to_string()).or_default();
+            }
+            node.materials.push(material);
+        }
+
+        render_material_folder(ui, &root, "", &mut render_item, folder_action)
+    })
+}
+
+fn render_material_folder(
+    ui: &mut egui::Ui,
+    node: &MaterialFolderNode,
+    path: &str,
+    render_item: &mut impl FnMut(&mut egui::Ui, &EditableMaterial) -> bool,
+    folder_action: &mut Option<MaterialFolderAction>,
+) -> bool {
+    let mut changed = false;
+
+    for (name, child) in node.children.iter() {
+        let child_path = if path.is_empty() {
+            name.clone()
+        } else {
+            format!("{}/{}", path, name)
+        };
+        let id = ui.make_persistent_id(("material_folder", &child_path));
+        let tools_id = id.with("tools");
+        let mut tools_open = ui.data(|d| d.get_temp::<bool>(tools_id).unwrap_or(false));
+        // Engine materials have no file to rewrite or delete
+        let editable = child_path != "internal";
+
+        egui::collapsing_header::CollapsingState::load_with_default_open(ui.ctx(), id, false)
+            .show_header(ui, |ui| {
+                ui.label(format!("{} ({})", name, child.count()));
+                if editable {
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui
+                            .small_button("⚙")
+                            .on_hover_text("Folder operations")
+                            .clicked()
+                        {
+                            tools_open = !tools_open;
+                        }
+                    });
                 }
-                false
-            },
-        )
-    } else {
-        false
+            })
+            .body(|ui| {
+                if tools_open {
+                    render_material_folder_tools(ui, &child_path, child.count(), folder_action);
+                }
+                changed |=
+                    render_material_folder(ui, child, &child_path, render_item, folder_action);
+            });
+
+        ui.data_mut(|d| d.insert_temp(tools_id, tools_open));
+    }
+
+    let mut materials = node.materials.clone();
+    materials.sort_by(|a, b| a.display_name().cmp(b.display_name()));
+    for material in materials {
+        changed |= render_item(ui, material);
+    }
+
+    changed
+}
+
+fn render_material_folder_tools(
+    ui: &mut egui::Ui,
+    folder: &str,
+    count: usize,
+    folder_action: &mut Option<MaterialFolderAction>,
+) {
+    let category_id = egui::Id::new(("material_folder_category", folder));
+    let mut category = ui.data(|d| {
+        d.get_temp::<String>(category_id)
+            .unwrap_or_else(|| folder.to_string())
+    });
+
+    egui::Frame::group(ui.style()).show(ui, |ui| {
+        ui.horizontal(|ui| {
+            ui.label("Category:");
+            ui.add(egui::TextEdit::singleline(&mut category).desired_width(120.0))
+                .on_hover_text("Folder to list these materials under. Empty uses their directory");
+            if ui.button("Apply").clicked() {
+                *folder_action = Some(MaterialFolderAction::SetCategory {
+                    folder: folder.to_string(),
+                    category: category.trim().trim_matches('/').to_string(),
+                });
+            }
+        });
+        if ui
+            .button(format!("Delete All ({})", count))
+            .on_hover_text(
+                "Delete every material in this folder from disk, their users are listed first",
+            )
+            .clicked()
+        {
+            *folder_action = Some(MaterialFolderAction::RequestDelete {
+                folder: folder.to_string(),
+            });
+        }
+    });
+
+    ui.data_mut(|d| d.insert_temp(category_id, category));
+}
+
+/// Recently picked textures kept at the top of the texture picker
+const RECENT_TEXTURES: usize = 8;
+const TEXTURE_THUMBNAIL_SIZE: f32 = 24.0;
+
+/// Images under assets/textures and the state every texture picker shares
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TexturePickerData {
+    /// Image paths relative to assets
+    pub textures: Vec<String>,
+    /// Thumbnails by texture path, see TextureLibrary
+    pub thumbnails: HashMap<String, egui::TextureId>,
+    /// Most recently picked first
+    pub recent: Vec<String>,
+    pub search_filter: String,
+    /// Set when a picker is opened, so the texture folder is scanned again
+    pub scan_requested: bool,
+}
+
+impl TexturePickerData {
+    fn use_texture(&mut self, path: &str) {
+        self.recent.retain(|recent| recent != path);
+        self.recent.insert(0, path.to_string());
+        self.recent.truncate(RECENT_TEXTURES);
+    }
+}
+
+/// Dropdown listing every texture with its thumbnail, searchable, with the recently used ones first.
+/// `id_salt` keeps the popups of several texture fields apart
+pub fn texture_picker_combo(
+    ui: &mut egui::Ui,
+    id_salt: &str,
+    value: &mut String,
+    data: &mut TexturePickerData,
+) -> bool {
+    let popup_id = egui::Id::new(("texture_picker_popup", id_salt));
+
+    let button_response = ui
+        .horizontal(|ui| {
+            let size = egui::vec2(TEXTURE_THUMBNAIL_SIZE, TEXTURE_THUMBNAIL_SIZE);
+            match data.thumbnails.get(value.as_str()) {
+                Some(texture) => ui.image((*texture, size)),
+                None => ui.allocate_response(size, egui::Sense::hover()),
+            };
+            let text = if value.is_empty() {
+                "None"
+            } else {
+                value.as_str()
+            };
+            let was_open = Popup::is_id_open(ui.ctx(), popup_id);
+            let response = handle_popup_button(ui, popup_id, text, &mut data.search_filter);
+            if !was_open && Popup::is_id_open(ui.ctx(), popup_id) {
+                data.scan_requested = true;
+            }
+            response.on_hover_text(value.as_str())
+        })
+        .inner;
+
+    let mut render_item =
+        |ui: &mut egui::Ui, path: &str, thumbnails: &HashMap<String, egui::TextureId>| {
+            let clicked = ui
+                .horizontal(|ui| {
+                    // Thumbnails still loading get a blank square, so names stay lined up
+                    let size = egui::vec2(TEXTURE_THUMBNAIL_SIZE, TEXTURE_THUMBNAIL_SIZE);
+                    match thumbnails.get(path) {
+                        Some(texture) => ui.image((*texture, size)),
+                        None => ui.allocate_response(size, egui::Sense::hover()),
+                    };
+                    let name = path.strip_prefix("textures/").unwrap_or(path);
+                    ui.selectable_label(value == path, name)
+                        .on_hover_text(path)
+                        .clicked()
+                })
+                .inner;
+            if clicked {
+                *value = path.to_string();
+                log!(
+                    LogType::Editor,
+                    LogLevel::OK,
+                    LogCategory::UI,
+                    "User selected texture: {}",
+                    path
+                );
+                Popup::close_id(ui.ctx(), popup_id);
+            }
+            clicked
+        };
+
+    let recent = data.recent.clone();
+    let search_filter = data.search_filter.to_lowercase();
+    let textures = &data.textures;
+    let thumbnails = &data.thumbnails;
+    let picked = selector_popup_frame(ui, popup_id, &button_response, |ui| {
+        render_search_box(ui, &mut data.search_filter, "texture");
+        let mut picked = false;
+
+        if search_filter.is_empty() && !recent.is_empty() {
+            ui.label(egui::RichText::new("Recent").weak());
+            for path in recent.iter() {
+                picked |= render_item(ui, path, thumbnails);
+            }
+            ui.separator();
+        }
+
+        let matches: Vec<&String> = textures
+            .iter()
+            .filter(|path| search_filter.is_empty() || path.to_lowercase().contains(&search_filter))
+            .collect();
+        if textures.is_empty() {
+            ui.label("No textures found in assets/textures");
+        } else if matches.is_empty() {
+            ui.label("No textures match your search");
+        }
+        for path in matches {
+            picked |= render_item(ui, path, thumbnails);
+        }
+        picked
+    });
+
+    if picked {
+        data.use_texture(value);
     }
+    picked
 }
@@ -1,12 +1,21 @@
+use bevy::camera::{visibility::RenderLayers, Camera, NormalizedRenderTarget, RenderTarget};
 use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
 use bevy::platform::collections::HashMap;
-use bevy::prelude::{Entity, Name, Query, Res, ResMut, With};
-use bevy_granite_core::{AvailableEditableMaterials, IdentityData, SpawnSource, UserInput};
+use bevy::prelude::{DetectChanges, Entity, Has, MessageWriter, Name, Query, Res, ResMut, With};
+use bevy::window::{PrimaryWindow, WindowRef};
+use bevy_granite_core::{
+    AssetLeakReport, AvailableEditableMaterials, ComponentSchemaReport, IdentityData, MainCamera,
+    RequestAssetLeakScanEvent, RequestPurgeUnusedAssetsEvent, RequestReloadComponentSchemasEvent,
+    SceneProfileReport, SpawnSource, UICamera, UserInput,
+};
 use bevy_granite_gizmos::{ActiveSelection, Selected};
 
-use super::{ActiveObjectDetails, SelectionInfo};
+use super::{
+    ActiveObjectDetails, CameraDebugInfo, CameraFix, RecorderAction, RecorderDebugInfo,
+    SelectionInfo,
+};
 use crate::{
-    editor_state::EditorState,
+    editor_state::{EditorState, StateRecorder},
     interface::{BottomDockState, BottomTab},
 };
 pub fn update_debug_tab_ui_system(
@@ -20,6 +29,11 @@ pub fn update_debug_tab_ui_system(
     identity_query: Query<&IdentityData>,
     spawn_source_query: Query<&SpawnSource>,
     diagnostics: Res<DiagnosticsStore>,
+    leak_report: Res<AssetLeakReport>,
+    mut leak_scan: MessageWriter<RequestAssetLeakScanEvent>,
+    mut leak_purge: MessageWriter<RequestPurgeUnusedAssetsEvent>,
+    schema_report: Res<ComponentSchemaReport>,
+    mut schema_reload: MessageWriter<RequestReloadComponentSchemasEvent>,
 ) {
     for (_, tab) in bottom_dock.dock_state.iter_all_tabs_mut() {
         if let BottomTab::Debug { ref mut data, .. } = tab {
@@ -64,6 +78,25 @@ pub fn update_debug_tab_ui_system(
 
             data.available_materials = available_materials.clone();
 
+            if data.leak_scan_requested {
+                leak_scan.write(RequestAssetLeakScanEvent);
+                data.leak_scan_requested = false;
+            }
+            if data.leak_purge_requested {
+                leak_purge.write(RequestPurgeUnusedAssetsEvent);
+                data.leak_purge_requested = false;
+            }
+            if leak_report.is_changed() {
+                data.leak_report = leak_report.clone();
+            }
+            if data.schema_reload_requested {
+                schema_reload.write(RequestReloadComponentSchemasEvent);
+                data.schema_reload_requested = false;
+            }
+            if schema_report.is_changed() {
+                data.schema_report = schema_report.clone();
+            }
+
             if let Some(fps) = diagnostics.get(&FrameTimeDiagnosticsPlugin::FPS) {
                 if let Some(value) = fps.value() {
                     let fps_value_display = format!("Current FPS: {:.0}", value);
@@ -88,3 +121,192 @@ pub fn update_debug_tab_ui_system(
         }
     }
 }
+
+type CameraDebugItem<'a> = (
+    Entity,
+    &'a mut Camera,
+    &'a RenderTarget,
+    Option<&'a RenderLayers>,
+    Option<&'a Name>,
+    Has<MainCamera>,
+    Has<UICamera>,
+);
+
+/// Applies the fixes picked in the Cameras section, then lists every camera and flags conflicts
+pub fn update_camera_debug_system(
+    mut bottom_dock: ResMut<BottomDockState>,
+    mut camera_query: Query<CameraDebugItem>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+) {
+    let primary_window = primary_window.iter().next();
+    for (_, tab) in bottom_dock.dock_state.iter_all_tabs_mut() {
+        let BottomTab::Debug { ref mut data, .. } = tab else {
+            continue;
+        };
+
+        for fix in std::mem::take(&mut data.camera_fixes) {
+            match fix {
+                CameraFix::Deactivate(entity) => {
+                    if let Ok((_, mut camera, ..)) = camera_query.get_mut(entity) {
+                        camera.is_active = false;
+                    }
+                }
+                CameraFix::OrderLast(entity) => {
+                    let Ok((_, _, target, ..)) = camera_query.get(entity) else {
+                        continue;
+                    };
+                    let target = target.normalize(primary_window);
+                    let last = camera_query
+                        .iter()
+                        .filter(|(other, _, other_target, ..)| {
+                            *other != entity && other_target.normalize(primary_window) == target
+                        })
+                        .map(|(_, camera, ..)| camera.order)
+                        .max()
+                        .unwrap_or_default();
+                    if let Ok((_, mut camera, ..)) = camera_query.get_mut(entity) {
+                        camera.order = last + 1;
+                    }
+                }
+            }
+        }
+
+        let mut cameras: Vec<(Option<NormalizedRenderTarget>, CameraDebugInfo)> = camera_query
+            .iter()
+            .map(|(entity, camera, target, layers, name, main, ui)| {
+                let layers = match layers {
+                    Some(layers) => format!("{:?}", layers.iter().collect::<Vec<_>>()),
+                    None => "[0] (default)".to_string(),
+                };
+                let viewport = camera.viewport.as_ref().map(|viewport| {
+                    format!(
+                        "{}x{} at {},{}",
+                        viewport.physical_size.x,
+                        viewport.physical_size.y,
+                        viewport.physical_position.x,
+                        viewport.physical_position.y
+                    )
+                });
+                let role = match (main, ui) {
+                    (true, _) => Some("MainCamera"),
+                    (false, true) => Some("UICamera"),
+                    _ => None,
+                };
+                let info = CameraDebugInfo {
+                    entity,
+                    name: name.map(|name| name.to_string()),
+                    order: camera.order,
+                    target: describe_render_target(target),
+                    active: camera.is_active,
+                    layers,
+                    viewport,
+                    role,
+                    conflict: false,
+                };
+                (target.normalize(primary_window), info)
+            })
+            .collect();
+
+        let mut seen: HashMap<(NormalizedRenderTarget, isize), usize> = HashMap::new();
+        for (target, info) in cameras.iter() {
+            if let (Some(target), true) = (target, info.active) {
+                *seen.entry((target.clone(), info.order)).or_default() += 1;
+            }
+        }
+        for (target, info) in cameras.iter_mut() {
+            if let (Some(target), true) = (target, info.active) {
+                info.conflict = seen
+                    .get(&(target.clone(), info.order))
+                    .is_some_and(|count| *count > 1);
+            }
+        }
+        cameras.sort_by_key(|(_, info)| (!info.conflict, info.order, info.entity));
+
+        let cameras: Vec<CameraDebugInfo> = cameras.into_iter().map(|(_, info)| info).collect();
+        if data.cameras != cameras {
+            data.cameras = cameras;
+        }
+    }
+}
+
+/// Applies what the State Recorder section asked for, then copies the recorder's state and reviewed frame into it
+pub fn update_state_recorder_debug_system(
+    mut bottom_dock: ResMut<BottomDockState>,
+    mut recorder: ResMut<StateRecorder>,
+    selection_query: Query<(Entity, Option<&Name>), With<Selected>>,
+) {
+    for (_, tab) in bottom_dock.dock_state.iter_all_tabs_mut() {
+        let BottomTab::Debug { ref mut data, .. } = tab else {
+            continue;
+        };
+
+        for action in std::mem::take(&mut data.recorder_actions) {
+            match action {
+                RecorderAction::Start => recorder.start(
+                    selection_query
+                        .iter()
+                        .map(|(entity, name)| {
+                            let name = name
+                                .map(|name| name.to_string())
+                                .unwrap_or_else(|| format!("Entity {}", entity.index()));
+                            (entity, name)
+                        })
+                        .collect(),
+                ),
+                RecorderAction::Stop => recorder.recording = false,
+                RecorderAction::Clear => recorder.clear(),
+                RecorderAction::Scrub(scrub) => {
+                    recorder.scrub = scrub.filter(|index| *index < recorder.frames.len())
+                }
+                RecorderAction::Capacity(capacity) => recorder.set_capacity(capacity),
+                RecorderAction::Trails(trails) => recorder.trails = trails,
+            }
+        }
+
+        let latest = recorder.frames.back().map(|frame| frame.time);
+        let oldest = recorder.frames.front().map(|frame| frame.time);
+        let info = RecorderDebugInfo {
+            recording: recorder.recording,
+            capacity: recorder.capacity,
+            tracked: recorder.tracked.clone(),
+            frames: recorder.frames.len(),
+            duration: latest
+                .zip(oldest)
+                .map(|(latest, oldest)| latest - oldest)
+                .unwrap_or_default(),
+            scrub: recorder.scrub,
+            trails: recorder.trails,
+            reviewed: recorder
+                .reviewed_frame()
+                .map(|frame| (latest.unwrap_or_default() - frame.time, frame.clone())),
+        };
+        if data.recorder != info {
+            data.recorder = info;
+        }
+    }
+}
+
+/// Copies the profiles of the last scene load and save into the Load/Save Profile section
+pub fn update_scene_profile_debug_system(
+    mut bottom_dock: ResMut<BottomDockState>,
+    profile_report: Res<SceneProfileReport>,
+) {
+    if !profile_report.is_changed() {
+        return;
+    }
+    for (_, tab) in bottom_dock.dock_state.iter_all_tabs_mut() {
+        if let BottomTab::Debug { ref mut data, .. } = tab {
+            data.profile_report = profile_report.clone();
+        }
+    }
+}
+
+fn describe_render_target(target: &RenderTarget) -> String {
+    match target {
+        RenderTarget::Window(WindowRef::Primary) => "Primary window".to_string(),
+        RenderTarget::Window(WindowRef::Entity(entity)) => format!("Window {:?}", entity),
+        RenderTarget::Image(image) => format!("Image {:?}", image.handle.id()),
+        RenderTarget::TextureView(handle) => format!("Texture view {:?}", handle),
+        RenderTarget::None { size } => format!("None ({}x{})", size.x, size.y),
+    }
+}
@@ -1,7 +1,12 @@
+use crate::editor_state::{RecordedFrame, MAX_RECORDER_CAPACITY};
 use bevy::platform::collections::HashMap;
 use bevy::prelude::{default, Entity};
 use bevy_egui::egui;
-use bevy_granite_core::{AvailableEditableMaterials, IdentityData, SpawnSource, UserInput};
+use bevy_granite_core::{
+    AssetLeakReport, AvailableEditableMaterials, ComponentSchemaReport, IdentityData, LeakedAsset,
+    ProfileTotals, SceneProfile, SceneProfileReport, SchemaChangeKind, SpawnSource, UserInput,
+    SCENE_PROFILE_TOP,
+};
 
 #[derive(Clone, PartialEq, Default)]
 pub struct SelectionInfo {
@@ -17,6 +22,58 @@ pub struct ActiveObjectDetails {
     pub spawned_from: Option<SpawnSource>,
 }
 
+/// One Camera in the world as the Cameras section shows it
+#[derive(Clone, PartialEq, Debug)]
+pub struct CameraDebugInfo {
+    pub entity: Entity,
+    pub name: Option<String>,
+    pub order: isize,
+    pub target: String,
+    pub active: bool,
+    pub layers: String,
+    pub viewport: Option<String>,
+    /// MainCamera, UICamera or none
+    pub role: Option<&'static str>,
+    /// Another active camera renders to the same target with the same order
+    pub conflict: bool,
+}
+
+/// One-click fixes of the Cameras section
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum CameraFix {
+    Deactivate(Entity),
+    /// Order after every other camera of the same target
+    OrderLast(Entity),
+}
+
+/// The State Recorder section's copy of StateRecorder
+#[derive(Clone, PartialEq, Default, Debug)]
+pub struct RecorderDebugInfo {
+    pub recording: bool,
+    pub capacity: usize,
+    pub tracked: Vec<(Entity, String)>,
+    pub frames: usize,
+    /// Seconds of game time from the oldest to the latest frame
+    pub duration: f32,
+    pub scrub: Option<usize>,
+    pub trails: bool,
+    /// Frame under review, with its seconds before the latest one
+    pub reviewed: Option<(f32, RecordedFrame)>,
+}
+
+/// Changes the State Recorder section asks for
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum RecorderAction {
+    /// Record the current selection
+    Start,
+    Stop,
+    Clear,
+    /// Review a frame, none follows the latest
+    Scrub(Option<usize>),
+    Capacity(usize),
+    Trails(bool),
+}
+
 #[derive(PartialEq, Clone)]
 pub struct DebugTabData {
     pub fps_info: (String, String),
@@ -25,6 +82,16 @@ pub struct DebugTabData {
     pub current_file: String,
     pub active_object_details: ActiveObjectDetails,
     pub available_materials: AvailableEditableMaterials,
+    pub leak_report: AssetLeakReport,
+    pub leak_scan_requested: bool,
+    pub leak_purge_requested: bool,
+    pub schema_report: ComponentSchemaReport,
+    pub schema_reload_requested: bool,
+    pub cameras: Vec<CameraDebugInfo>,
+    pub camera_fixes: Vec<CameraFix>,
+    pub recorder: RecorderDebugInfo,
+    pub recorder_actions: Vec<RecorderAction>,
+    pub profile_report: SceneProfileReport,
 }
 
 impl Default for DebugTabData {
@@ -38,6 +105,16 @@ impl Default for DebugTabData {
                 selection: None,
             },
             available_materials: AvailableEditableMaterials::default(),
+            leak_report: AssetLeakReport::default(),
+            leak_scan_requested: false,
+            leak_purge_requested: false,
+            schema_report: ComponentSchemaReport::default(),
+            schema_reload_requested: false,
+            cameras: Vec::new(),
+            camera_fixes: Vec::new(),
+            recorder: RecorderDebugInfo::default(),
+            recorder_actions: Vec::new(),
+            profile_report: SceneProfileReport::default(),
             active_object_details: ActiveObjectDetails::default(),
         }
     }
@@ -157,10 +234,380 @@ pub fn debug_tab_ui(ui: &mut egui::Ui, data: &mut DebugTabData) {
         ui.vertical(|ui| {
             ui.weak("(These are ALL the loaded materials of your project. And on startup, we grab your whole material folder to load.)");
             ui.weak("Editable Materials:");
-            ui.label(format!("{:#?}", data.available_materials.materials));
+            ui.label(format!("{:#?}", data.available_materials.materials()));
             ui.add_space(small_spacing);
             ui.weak("Image Paths:");
             ui.label(format!("{:#?}", data.available_materials.image_paths));
         });
     });
+
+    ui.collapsing("Leaked Assets", |ui| {
+        ui.vertical(|ui| {
+            ui.weak("(Assets still in memory that no entity or material definition uses, e.g. textures of deleted materials.)");
+            ui.horizontal(|ui| {
+                if ui.button("Scan").clicked() {
+                    data.leak_scan_requested = true;
+                }
+                let can_purge = data.leak_report.total() > 0;
+                if ui
+                    .add_enabled(can_purge, egui::Button::new("Purge Unused"))
+                    .clicked()
+                {
+                    data.leak_purge_requested = true;
+                }
+            });
+            ui.add_space(small_spacing);
+            if !data.leak_report.scanned {
+                ui.label("Not scanned yet");
+                return;
+            }
+            leaked_assets_ui(ui, "Materials", &data.leak_report.materials);
+            leaked_assets_ui(ui, "Meshes", &data.leak_report.meshes);
+            leaked_assets_ui(ui, "Textures", &data.leak_report.images);
+        });
+    });
+
+    ui.collapsing("Component Schemas", |ui| {
+        ui.vertical(|ui| component_schemas_ui(ui, data));
+    });
+
+    ui.collapsing("Cameras", |ui| {
+        ui.vertical(|ui| cameras_ui(ui, data));
+    });
+
+    ui.collapsing("State Recorder", |ui| {
+        ui.vertical(|ui| recorder_ui(ui, data));
+    });
+
+    ui.collapsing("Load/Save Profile", |ui| {
+        ui.vertical(|ui| {
+            ui.weak("(Where the time of the last scene load and save went, by entity, class and component, with the size of their saved data.)");
+            ui.add_space(small_spacing);
+            scene_profile_ui(ui, "Last Load", data.profile_report.load.as_ref());
+            ui.add_space(small_spacing);
+            scene_profile_ui(ui, "Last Save", data.profile_report.save.as_ref());
+        });
+    });
+}
+
+fn scene_profile_ui(ui: &mut egui::Ui, title: &str, profile: Option<&SceneProfile>) {
+    let Some(profile) = profile else {
+        ui.label(format!("{}: none yet", title));
+        return;
+    };
+    ui.label(format!(
+        "{}: '{}' in {:.1?}, file {:.1?}, {} entities, {}",
+        title,
+        profile.scene,
+        profile.total,
+        profile.file,
+        profile.entities.len(),
+        format_bytes(profile.bytes())
+    ));
+    ui.indent(title, |ui| {
+        ui.collapsing(format!("Slowest Entities ({})", SCENE_PROFILE_TOP), |ui| {
+            egui::Grid::new((title, "entities"))
+                .striped(true)
+                .show(ui, |ui| {
+                    for entity in profile.top_entities(SCENE_PROFILE_TOP) {
+                        ui.label(&entity.name)
+                            .on_hover_text(entity.uuid.to_string());
+                        ui.weak(&entity.class);
+                        ui.label(format!("{:.1?}", entity.time));
+                        ui.label(format_bytes(entity.bytes));
+                        ui.end_row();
+                    }
+                });
+        });
+        ui.collapsing("Classes", |ui| {
+            profile_totals_ui(ui, (title, "classes"), profile.classes().iter());
+        });
+        ui.collapsing(
+            format!("Slowest Components ({})", SCENE_PROFILE_TOP),
+            |ui| {
+                profile_totals_ui(
+                    ui,
+                    (title, "components"),
+                    profile.top_components(SCENE_PROFILE_TOP).into_iter(),
+                );
+            },
+        );
+    });
+}
+
+fn profile_totals_ui<'a>(
+    ui: &mut egui::Ui,
+    id_salt: impl std::hash::Hash,
+    totals: impl Iterator<Item = &'a ProfileTotals>,
+) {
+    egui::Grid::new(id_salt).striped(true).show(ui, |ui| {
+        for total in totals {
+            let short_name = total.name.rsplit("::").next().unwrap_or(&total.name);
+            ui.label(short_name).on_hover_text(&total.name);
+            ui.weak(format!("x{}", total.count));
+            ui.label(format!("{:.1?}", total.time));
+            ui.label(format_bytes(total.bytes));
+            ui.end_row();
+        }
+    });
+}
+
+fn format_bytes(bytes: usize) -> String {
+    if bytes >= 1024 * 1024 {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    } else if bytes >= 1024 {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    } else {
+        format!("{} B", bytes)
+    }
+}
+
+fn recorder_ui(ui: &mut egui::Ui, data: &mut DebugTabData) {
+    let small_spacing = crate::UI_CONFIG.small_spacing;
+    let recorder = &data.recorder;
+    let actions = &mut data.recorder_actions;
+    ui.weak("(Records the selected entities' transforms and saved components every frame of game time. Pause the game to look back through them.)");
+    ui.horizontal(|ui| {
+        if recorder.recording {
+            if ui.button("Stop").clicked() {
+                actions.push(RecorderAction::Stop);
+            }
+        } else if ui
+            .button("Record Selection")
+            .on_hover_text("Starts over with the entities selected now")
+            .clicked()
+        {
+            actions.push(RecorderAction::Start);
+        }
+        if ui
+            .add_enabled(recorder.frames > 0, egui::Button::new("Clear"))
+            .clicked()
+        {
+            actions.push(RecorderAction::Clear);
+        }
+    });
+    ui.horizontal(|ui| {
+        ui.label("Keep frames:");
+        let mut capacity = recorder.capacity;
+        if ui
+            .add(egui::DragValue::new(&mut capacity).range(1..=MAX_RECORDER_CAPACITY))
+            .changed()
+        {
+            actions.push(RecorderAction::Capacity(capacity));
+        }
+        let mut trails = recorder.trails;
+        if ui.checkbox(&mut trails, "Trails").changed() {
+            actions.push(RecorderAction::Trails(trails));
+        }
+    });
+    ui.label(format!(
+        "{} entities, {} frames over {:.2}s{}",
+        recorder.tracked.len(),
+        recorder.frames,
+        recorder.duration,
+        if recorder.recording {
+            ", recording"
+        } else {
+            ""
+        }
+    ));
+    if recorder.frames == 0 {
+        return;
+    }
+    ui.add_space(small_spacing);
+
+    let last = recorder.frames - 1;
+    let mut index = recorder.scrub.unwrap_or(last);
+    ui.horizontal(|ui| {
+        if ui.add_enabled(index > 0, egui::Button::new("◀")).clicked() {
+            actions.push(RecorderAction::Scrub(Some(index - 1)));
+        }
+        if ui
+            .add(egui::Slider::new(&mut index, 0..=last).text("frame"))
+            .changed()
+        {
+            actions.push(RecorderAction::Scrub(Some(index)));
+        }
+        if ui
+            .add_enabled(index < last, egui::Button::new("▶"))
+            .clicked()
+        {
+            actions.push(RecorderAction::Scrub(Some(index + 1)));
+        }
+        if ui
+            .selectable_label(recorder.scrub.is_none(), "Live")
+            .on_hover_text("Follow the latest frame")
+            .clicked()
+        {
+            actions.push(RecorderAction::Scrub(None));
+        }
+    });
+
+    let Some((before_latest, frame)) = &recorder.reviewed else {
+        return;
+    };
+    ui.label(format!(
+        "t = {:.3}s ({:.3}s before the latest)",
+        frame.time, before_latest
+    ));
+    for entity in frame.entities.iter() {
+        let name = recorder
+            .tracked
+            .iter()
+            .find(|(tracked, _)| *tracked == entity.entity)
+            .map(|(_, name)| name.as_str())
+            .unwrap_or("(Unnamed)");
+        ui.collapsing(format!("{} {:?}", name, entity.entity), |ui| {
+            let transform = entity.transform;
+            let (x, y, z) = transform.rotation.to_euler(bevy::math::EulerRot::XYZ);
+            ui.label(format!("Translation: {:.3}", transform.translation));
+            ui.label(format!(
+                "Rotation: [{:.1}, {:.1}, {:.1}]",
+                x.to_degrees(),
+                y.to_degrees(),
+                z.to_degrees()
+            ));
+            ui.label(format!("Scale: {:.3}", transform.scale));
+            for (type_path, value) in entity.components.iter() {
+                let short_name = type_path.rsplit("::").next().unwrap_or(type_path);
+                ui.label(format!("{}: {}", short_name, value))
+                    .on_hover_text(type_path);
+            }
+        });
+    }
+}
+
+fn cameras_ui(ui: &mut egui::Ui, data: &mut DebugTabData) {
+    let small_spacing = crate::UI_CONFIG.small_spacing;
+    ui.weak("(Two active cameras rendering to the same target with the same order draw over each other in no set order.)");
+    let conflicts = data.cameras.iter().filter(|camera| camera.conflict).count();
+    if conflicts > 0 {
+        ui.colored_label(
+            egui::Color32::YELLOW,
+            format!("{} cameras conflict", conflicts),
+        );
+    } else {
+        ui.label("No conflicts");
+    }
+    ui.add_space(small_spacing);
+
+    for camera in data.cameras.iter() {
+        let title = match (&camera.name, camera.role) {
+            (Some(name), Some(role)) => format!("{} ({}) {:?}", name, role, camera.entity),
+            (Some(name), None) => format!("{} {:?}", name, camera.entity),
+            (None, Some(role)) => format!("{} {:?}", role, camera.entity),
+            (None, None) => format!("Camera {:?}", camera.entity),
+        };
+        if camera.conflict {
+            ui.colored_label(egui::Color32::YELLOW, title);
+        } else {
+            ui.label(title);
+        }
+        ui.indent(camera.entity, |ui| {
+            ui.label(format!("Order: {}", camera.order));
+            ui.label(format!("Target: {}", camera.target));
+            ui.label(format!("Active: {}", camera.active));
+            ui.label(format!("Render layers: {}", camera.layers));
+            ui.label(format!(
+                "Viewport: {}",
+                camera.viewport.as_deref().unwrap_or("Full target")
+            ));
+            if camera.conflict {
+                ui.horizontal(|ui| {
+                    if ui
+                        .button("Deactivate")
+                        .on_hover_text("Stops this camera rendering")
+                        .clicked()
+                    {
+                        data.camera_fixes.push(CameraFix::Deactivate(camera.entity));
+                    }
+                    if ui
+                        .button("Order last")
+                        .on_hover_text("Renders this camera after the others of its target")
+                        .clicked()
+                    {
+                        data.camera_fixes.push(CameraFix::OrderLast(camera.entity));
+                    }
+                });
+            }
+        });
+        ui.add_space(small_spacing);
+    }
+}
+
+fn component_schemas_ui(ui: &mut egui::Ui, data: &mut DebugTabData) {
+    let small_spacing = crate::UI_CONFIG.small_spacing;
+    ui.weak("(After a hot reload of your game library, picks up the new component types and moves entities onto them.)");
+    if ui.button("Reload Schemas").clicked() {
+        data.schema_reload_requested = true;
+    }
+    ui.add_space(small_spacing);
+    let report = &data.schema_report;
+    if !report.reloaded {
+        ui.label("Not reloaded yet");
+        return;
+    }
+    if report.changes.is_empty() {
+        ui.label("No component changed");
+    }
+    for change in report.changes.iter() {
+        let short_name = change
+            .type_path
+            .rsplit("::")
+            .next()
+            .unwrap_or(&change.type_path);
+        let title = match change.kind {
+            SchemaChangeKind::Added => format!("{} (new)", short_name),
+            SchemaChangeKind::Removed => format!("{} (gone)", short_name),
+            SchemaChangeKind::Changed => short_name.to_string(),
+        };
+        let response = if change.is_compatible() {
+            ui.label(title)
+        } else {
+            ui.colored_label(egui::Color32::YELLOW, title)
+        };
+        response.on_hover_text(&change.type_path);
+        ui.indent(&change.type_path, |ui| {
+            for (label, fields) in [
+                ("Added", &change.added_fields),
+                ("Removed", &change.removed_fields),
+                ("Retyped", &change.retyped_fields),
+            ] {
+                if !fields.is_empty() {
+                    ui.label(format!("{}: {}", label, fields.join(", ")));
+                }
+            }
+        });
+    }
+
+    ui.add_space(small_spacing);
+    ui.weak(format!("Rebound components ({}):", report.rebinds.len()));
+    for rebind in report.flagged() {
+        let short_name = rebind
+            .type_path
+            .rsplit("::")
+            .next()
+            .unwrap_or(&rebind.type_path);
+        let text = if rebind.failed {
+            format!(
+                "{} on {:?}: kept the old component",
+                short_name, rebind.entity
+            )
+        } else {
+            format!(
+                "{} on {:?}: dropped {}",
+                short_name,
+                rebind.entity,
+                rebind.incompatible.join(", ")
+            )
+        };
+        ui.colored_label(egui::Color32::YELLOW, text);
+    }
+}
+
+fn leaked_assets_ui(ui: &mut egui::Ui, title: &str, assets: &[LeakedAsset]) {
+    ui.weak(format!("{} ({}):", title, assets.len()));
+    for asset in assets {
+        ui.label(&asset.label);
+    }
 }
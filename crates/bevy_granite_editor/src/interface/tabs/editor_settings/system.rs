@@ -2,21 +2,23 @@ use crate::{
     editor_state::{
         dock::{load_dock_state, DockLayoutStr},
         editor::save_editor_settings_from_widget_data,
-        EditorState,
+        EditorState, DEFAULT_AUTOSAVE_MINUTES,
     },
+    input::HotkeySettings,
     interface::{
         layout::{DockState, SidePanelPosition},
         panels::{BottomDockState, SideDockState, SideTab},
         themes::{SerializableTextStyle, ThemeState},
-        EditorEvents, PopupMenuRequestedEvent, PopupType,
+        EditorEvents, PopupMenuRequestedEvent, PopupType, ReparentMode,
     },
-    viewport::{DebugRenderer, SelectionRenderer, ViewportState},
+    viewport::{DebugRenderer, SelectionRenderer, SnapSettings, ViewportState},
 };
 
 use bevy::{gizmos::config::GizmoConfigStore, math::Vec2, prelude::ResMut};
 use bevy_egui::egui::{self};
 use bevy_egui::EguiContexts;
 use bevy_granite_core::PromptImportSettings;
+use bevy_granite_gizmos::{GizmoPickSettings, GizmoSnap};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
@@ -52,11 +54,29 @@ pub struct EditorSettingsTabData {
     pub import_state: ImportState,
     pub dock: DockState,
     pub show_help_on_start: bool,
+    #[serde(default)]
+    pub hotkeys: HotkeySettings,
+    /// Opt-in local usage telemetry, see editor_state/telemetry.rs
+    #[serde(default)]
+    pub telemetry_enabled: bool,
+    /// Minutes between autosaves of edited scenes, zero turns it off. See editor_state/autosave.rs
+    #[serde(default = "default_autosave_minutes")]
+    pub autosave_minutes: f32,
+    /// Whether reparented entities keep their world or their local transform
+    #[serde(default)]
+    pub reparent_mode: ReparentMode,
+    /// Also write trashed entities next to their scene, so they survive a restart. See editor_state/trash.rs
+    #[serde(default)]
+    pub persist_trash: bool,
 
     #[serde(skip)]
     pub save_requested: bool,
 }
 
+fn default_autosave_minutes() -> f32 {
+    DEFAULT_AUTOSAVE_MINUTES
+}
+
 impl Default for EditorSettingsTabData {
     fn default() -> Self {
         Self {
@@ -72,6 +92,11 @@ impl Default for EditorSettingsTabData {
                 changed: true,
             },
             show_help_on_start: true,
+            hotkeys: HotkeySettings::default(),
+            telemetry_enabled: false,
+            autosave_minutes: DEFAULT_AUTOSAVE_MINUTES,
+            reparent_mode: ReparentMode::default(),
+            persist_trash: false,
             viewport: ViewportState::default(),
         }
     }
@@ -82,7 +107,11 @@ pub fn update_editor_settings_tab_system(
     mut side_dock: ResMut<SideDockState>,
     mut bottom_dock: ResMut<BottomDockState>,
     mut editor_state: ResMut<EditorState>,
-    mut gizmo_config_store: ResMut<GizmoConfigStore>,
+    (mut gizmo_config_store, mut gizmo_snap, mut gizmo_pick): (
+        ResMut<GizmoConfigStore>,
+        ResMut<GizmoSnap>,
+        ResMut<GizmoPickSettings>,
+    ),
     mut prompt_import_settings: ResMut<PromptImportSettings>,
     mut scene_light_state: ResMut<crate::viewport::SceneLightState>,
     mut events: EditorEvents,
@@ -164,6 +193,45 @@ pub fn update_editor_settings_tab_system(
                 sel_config.line.width = data.viewport.visualizers.selection_line_thickness;
                 let (debug_config, _) = gizmo_config_store.config_mut::<DebugRenderer>();
                 debug_config.line.width = data.viewport.visualizers.debug_line_thickness;
+                let snap = data.viewport.snap;
+                gizmo_snap.transform_value = snap.translate;
+                gizmo_snap.rotate_value = snap.rotate;
+                gizmo_snap.scale_value = snap.scale;
+                gizmo_snap.always = snap.always;
+                gizmo_pick.enabled = data.viewport.gizmo_picking.screen_space;
+                gizmo_pick.radius = data.viewport.gizmo_picking.radius;
+            } else {
+                // Snap edits from the gizmo toolbar flow back so they persist
+                let toolbar_snap = SnapSettings {
+                    translate: gizmo_snap.transform_value,
+                    rotate: gizmo_snap.rotate_value,
+                    scale: gizmo_snap.scale_value,
+                    always: gizmo_snap.always,
+                };
+                if toolbar_snap != data.viewport.snap {
+                    data.viewport.snap = toolbar_snap;
+                    editor_state.config.viewport.snap = toolbar_snap;
+                }
+            }
+
+            if data.hotkeys != editor_state.config.hotkeys {
+                editor_state.config.hotkeys = data.hotkeys;
+            }
+
+            if data.telemetry_enabled != editor_state.config.telemetry_enabled {
+                editor_state.config.telemetry_enabled = data.telemetry_enabled;
+            }
+
+            if data.autosave_minutes != editor_state.config.autosave_minutes {
+                editor_state.config.autosave_minutes = data.autosave_minutes;
+            }
+
+            if data.reparent_mode != editor_state.config.reparent_mode {
+                editor_state.config.reparent_mode = data.reparent_mode;
+            }
+
+            if data.persist_trash != editor_state.config.persist_trash {
+                editor_state.config.persist_trash = data.persist_trash;
             }
 
             if data.save_requested {
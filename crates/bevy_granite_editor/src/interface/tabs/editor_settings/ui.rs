@@ -1,9 +1,11 @@
 use super::{EditorSettingsTabData, SettingsTab};
 use crate::{
+    input::{EditorHotkey, HotkeySettings},
     interface::{
-        layout::SidePanelPosition, tabs::editor_settings::ImportState, themes::ThemeState,
+        events::ReparentMode, layout::SidePanelPosition, tabs::editor_settings::ImportState,
+        themes::ThemeState,
     },
-    viewport::ViewportState,
+    viewport::{MeshDebugMode, ViewportState},
 };
 use bevy_egui::egui::{self, SliderClamping, UiBuilder};
 use bevy_granite_core::MaterialNameSource;
@@ -218,6 +220,104 @@ fn build_dock_section(ui: &mut egui::Ui, dock: &mut crate::interface::layout::Do
     });
 }
 
+fn build_hotkeys_section(ui: &mut egui::Ui, hotkeys: &mut HotkeySettings) {
+    let spacing = crate::UI_CONFIG.spacing;
+    let large_spacing = crate::UI_CONFIG.large_spacing;
+    ui.vertical(|ui| {
+        ui.group(|ui| {
+            ui.add_space(spacing);
+            labeled_combo_columns(
+                ui,
+                "Toggle Editor Key:",
+                &mut hotkeys.toggle_editor,
+                &EditorHotkey::all(),
+                "toggle_editor_hotkey_selector",
+                Some("Shows or hides the whole editor"),
+            );
+
+            ui.add_space(large_spacing);
+            labeled_combo_columns(
+                ui,
+                "Toggle Overlay Key:",
+                &mut hotkeys.toggle_overlay,
+                &EditorHotkey::all(),
+                "toggle_overlay_hotkey_selector",
+                Some("Switches between the full editor and a compact toolbar over the game view"),
+            );
+        });
+    });
+}
+
+fn build_telemetry_section(ui: &mut egui::Ui, telemetry_enabled: &mut bool) {
+    let spacing = crate::UI_CONFIG.spacing;
+    ui.vertical(|ui| {
+        ui.group(|ui| {
+            ui.add_space(spacing);
+            labeled_checkbox_columns(
+                ui,
+                "Usage Telemetry:",
+                telemetry_enabled,
+                Some("Records anonymized usage (command counts, save and load times, scene sizes) to config/editor_telemetry.log. Nothing is sent over the network"),
+            );
+        });
+    });
+}
+
+fn build_autosave_section(ui: &mut egui::Ui, autosave_minutes: &mut f32) {
+    let spacing = crate::UI_CONFIG.spacing;
+    ui.vertical(|ui| {
+        ui.group(|ui| {
+            ui.add_space(spacing);
+            labeled_slider_columns(
+                ui,
+                "Autosave Interval:",
+                autosave_minutes,
+                0.0..=30.0,
+                0.5,
+                1,
+                Some(" min"),
+                Some("Writes edited scenes to .scene.autosave files next to them, offered for recovery after a crash. 0 turns autosave off"),
+            );
+        });
+    });
+}
+
+fn build_trash_section(ui: &mut egui::Ui, persist_trash: &mut bool) {
+    let spacing = crate::UI_CONFIG.spacing;
+    ui.vertical(|ui| {
+        ui.group(|ui| {
+            ui.add_space(spacing);
+            labeled_checkbox_columns(
+                ui,
+                "Keep Trash On Disk:",
+                persist_trash,
+                Some("Writes deleted entities to .scene.trash files next to their scene, so they can still be restored after a restart. Saving the scene purges them"),
+            );
+        });
+    });
+}
+
+fn build_reparent_section(ui: &mut egui::Ui, reparent_mode: &mut ReparentMode) {
+    let spacing = crate::UI_CONFIG.spacing;
+    ui.vertical(|ui| {
+        ui.group(|ui| {
+            ui.add_space(spacing);
+            ui.columns(2, |columns| {
+                columns[0]
+                    .label("Reparenting:")
+                    .on_hover_text("What stays put when an entity gets a new parent. Hold Alt while dropping in the Entities tab for the other one");
+                egui::ComboBox::from_id_salt("reparent_mode")
+                    .selected_text(reparent_mode.name())
+                    .show_ui(&mut columns[1], |ui| {
+                        for mode in ReparentMode::all() {
+                            ui.selectable_value(reparent_mode, mode, mode.name());
+                        }
+                    });
+            });
+        });
+    });
+}
+
 fn build_scene_light_section(ui: &mut egui::Ui, scene_light_enabled: &mut bool) {
     let spacing = crate::UI_CONFIG.spacing;
     let large_spacing = crate::UI_CONFIG.large_spacing;
@@ -274,6 +374,24 @@ fn build_debug_gizmos_section(ui: &mut egui::Ui, viewport: &mut ViewportState) {
                         Some("Show lines between Parent/Child relationships"),
                     );
 
+                    ui.add_space(spacing);
+                    changed |= labeled_checkbox_columns(
+                        ui,
+                        "Culled Entities:",
+                        &mut vis.debug_culled_entities,
+                        Some("Outline entities no scene camera is rendering"),
+                    );
+
+                    ui.add_space(spacing);
+                    changed |= labeled_combo_columns(
+                        ui,
+                        "Mesh Data:",
+                        &mut vis.debug_mesh_mode,
+                        &MeshDebugMode::ALL,
+                        "debug_mesh_mode",
+                        Some("Show vertex colors, a UV checkerboard, normals or tangents of the selected meshes. Meshes without that data show up magenta"),
+                    );
+
                     ui.add_space(spacing);
                     changed |= labeled_color_picker_columns(
                         ui,
@@ -387,6 +505,76 @@ fn build_debug_icons_section(ui: &mut egui::Ui, viewport: &mut ViewportState) {
     });
 }
 
+fn build_entity_labels_section(ui: &mut egui::Ui, viewport: &mut ViewportState) {
+    let spacing = crate::UI_CONFIG.spacing;
+    let large_spacing = crate::UI_CONFIG.large_spacing;
+    ui.vertical(|ui| {
+        ui.group(|ui| {
+            ui.add_space(large_spacing);
+
+            let labels = &mut viewport.labels;
+            let mut changed = false;
+
+            changed |= labeled_checkbox_columns(
+                ui,
+                "Entity Labels:",
+                &mut labels.enabled,
+                Some("Show entity names above entities near the camera"),
+            );
+
+            if labels.enabled {
+                ui.indent("entity_labels_options", |ui| {
+                    ui.add_space(large_spacing);
+
+                    changed |= labeled_checkbox_columns(
+                        ui,
+                        "Names:",
+                        &mut labels.show_names,
+                        Some("Show the entity name"),
+                    );
+
+                    ui.add_space(spacing);
+                    changed |= labeled_checkbox_columns(
+                        ui,
+                        "Class Badges:",
+                        &mut labels.show_class_badges,
+                        Some("Show the class abbreviation in front of the name"),
+                    );
+
+                    ui.add_space(spacing);
+                    changed |= labeled_slider_columns(
+                        ui,
+                        "Max Distance:",
+                        &mut labels.max_distance,
+                        1.0..=250.0,
+                        1.0,
+                        0,
+                        None,
+                        Some("Maximum camera distance to show labels"),
+                    );
+
+                    ui.add_space(spacing);
+                    ui.columns(2, |columns| {
+                        columns[0]
+                            .label("Classes:")
+                            .on_hover_text("Comma separated class names to label, i.e. \"Point Light, Camera\". Empty labels every class");
+                        changed |= columns[1]
+                            .add(
+                                egui::TextEdit::singleline(&mut labels.class_filter)
+                                    .hint_text("All classes"),
+                            )
+                            .changed();
+                    });
+                });
+            }
+
+            if changed {
+                viewport.changed = true;
+            }
+        });
+    });
+}
+
 fn build_selection_bounds_section(ui: &mut egui::Ui, viewport: &mut ViewportState) {
     let spacing = crate::UI_CONFIG.spacing;
     let large_spacing = crate::UI_CONFIG.large_spacing;
@@ -539,7 +727,105 @@ fn build_grid_section(ui: &mut egui::Ui, viewport: &mut ViewportState) {
         });
     });
 }
-// only obj right now, so a single section
+fn build_snapping_section(ui: &mut egui::Ui, viewport: &mut ViewportState) {
+    let spacing = crate::UI_CONFIG.spacing;
+    let large_spacing = crate::UI_CONFIG.large_spacing;
+    ui.vertical(|ui| {
+        ui.group(|ui| {
+            ui.add_space(large_spacing);
+
+            let mut changed = false;
+            let snap = &mut viewport.snap;
+
+            changed |= labeled_checkbox_columns(
+                ui,
+                "Always Snap:",
+                &mut snap.always,
+                Some("Snap gizmo drags without holding Ctrl. Holding Ctrl then drags freely"),
+            );
+
+            ui.add_space(spacing);
+            changed |= labeled_slider_columns(
+                ui,
+                "Move:",
+                &mut snap.translate,
+                0.01..=10.0,
+                0.01,
+                2,
+                Some(" m"),
+                Some("World grid for global moves, step for local moves"),
+            );
+
+            ui.add_space(spacing);
+            changed |= labeled_slider_columns(
+                ui,
+                "Rotate:",
+                &mut snap.rotate,
+                1.0..=90.0,
+                1.0,
+                0,
+                Some("°"),
+                Some("Rotation increment"),
+            );
+
+            ui.add_space(spacing);
+            changed |= labeled_slider_columns(
+                ui,
+                "Scale:",
+                &mut snap.scale,
+                0.01..=1.0,
+                0.01,
+                2,
+                None,
+                Some("Scale increment for the entity editor fields"),
+            );
+
+            if changed {
+                viewport.changed = true;
+            }
+        });
+    });
+}
+
+fn build_gizmo_picking_section(ui: &mut egui::Ui, viewport: &mut ViewportState) {
+    let spacing = crate::UI_CONFIG.spacing;
+    let large_spacing = crate::UI_CONFIG.large_spacing;
+    ui.vertical(|ui| {
+        ui.group(|ui| {
+            ui.add_space(large_spacing);
+
+            let mut changed = false;
+            let picking = &mut viewport.gizmo_picking;
+
+            changed |= labeled_checkbox_columns(
+                ui,
+                "Screen Space Gizmo Picking:",
+                &mut picking.screen_space,
+                Some("Grab the gizmo handle nearest the cursor, axes before planes before rings. Tab picks the next handle under the cursor"),
+            );
+
+            ui.add_space(spacing);
+            ui.add_enabled_ui(picking.screen_space, |ui| {
+                changed |= labeled_slider_columns(
+                    ui,
+                    "Pick Radius:",
+                    &mut picking.radius,
+                    0.0..=32.0,
+                    1.0,
+                    0,
+                    Some(" px"),
+                    Some("How far from a handle the cursor can be and still grab it"),
+                );
+            });
+
+            if changed {
+                viewport.changed = true;
+            }
+        });
+    });
+}
+
+// OBJ settings, glTF imports share the material toggle
 fn build_import_settings_section(ui: &mut egui::Ui, data: &mut ImportState) {
     let large_spacing = crate::UI_CONFIG.large_spacing;
     let spacing = crate::UI_CONFIG.spacing;
@@ -556,7 +842,7 @@ fn build_import_settings_section(ui: &mut egui::Ui, data: &mut ImportState) {
                     ui,
                     "Apply materials on import",
                     &mut data.import_settings.create_mat_on_import,
-                    Some("Should we create scene materials when importing OBJs")
+                    Some("Should we create scene materials when importing OBJs and glTFs")
                 );
 
                 if data.import_settings.create_mat_on_import {
@@ -572,6 +858,13 @@ fn build_import_settings_section(ui: &mut egui::Ui, data: &mut ImportState) {
                 };
             });
 
+            ui.add_space(large_spacing);
+            ui.label("glTF:");
+            ui.indent("gltf_options", |ui| {
+                ui.add_space(spacing);
+                ui.label("File > Import glTF... writes meshes to models/imported/ and, with materials applied on import, PBR materials to materials/imported/");
+            });
+
             if changed {
                 data.changed = true;
             }
@@ -590,19 +883,31 @@ fn build_interface_tab(ui: &mut egui::Ui, data: &mut EditorSettingsTabData) {
         .show(ui, |ui| {
             build_theme_section(ui, &mut data.theme_state);
             build_dock_section(ui, &mut data.dock);
+            build_hotkeys_section(ui, &mut data.hotkeys);
+            build_telemetry_section(ui, &mut data.telemetry_enabled);
+            build_autosave_section(ui, &mut data.autosave_minutes);
+            build_reparent_section(ui, &mut data.reparent_mode);
+            build_trash_section(ui, &mut data.persist_trash);
         });
 }
 
 // Viewport tab content
-fn build_viewport_tab(ui: &mut egui::Ui, viewport: &mut ViewportState, scene_light_enabled: &mut bool) {
+fn build_viewport_tab(
+    ui: &mut egui::Ui,
+    viewport: &mut ViewportState,
+    scene_light_enabled: &mut bool,
+) {
     egui::ScrollArea::vertical()
         .auto_shrink([true; 2])
         .show(ui, |ui| {
             build_scene_light_section(ui, scene_light_enabled);
             build_debug_gizmos_section(ui, viewport);
             build_debug_icons_section(ui, viewport);
+            build_entity_labels_section(ui, viewport);
             build_selection_bounds_section(ui, viewport);
             build_grid_section(ui, viewport);
+            build_snapping_section(ui, viewport);
+            build_gizmo_picking_section(ui, viewport);
         });
 }
 
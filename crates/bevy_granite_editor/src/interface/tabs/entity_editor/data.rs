@@ -3,8 +3,33 @@ use std::borrow::Cow;
 use super::widgets::{
     EntityGlobalTransformData, EntityIdentityData, EntityRegisteredData, MaterialTab,
 };
-use bevy::prelude::Entity;
-use bevy_granite_core::{AvailableEditableMaterials, ComponentEditor, NewEditableMaterial};
+use crate::interface::shared::{MaterialFolderAction, TexturePickerData};
+use bevy::{asset::AssetId, mesh::Mesh, prelude::Entity};
+use bevy_egui::egui;
+use bevy_granite_core::{
+    AvailableEditableMaterials, ComponentEditor, MeshStats, NewEditableMaterial,
+};
+use std::collections::HashMap;
+
+/// "Where used" list of a material, filled in by the tab update systems
+#[derive(PartialEq, Clone, Default)]
+pub struct MaterialUsersData {
+    /// Material asked about, none while the list is closed
+    pub path: Option<String>,
+    /// Folder asked to be deleted, lists the users of all its materials instead of `path`
+    pub folder: Option<String>,
+    /// Entities in loaded scenes using it, with their names
+    pub users: Vec<(Entity, String)>,
+    /// Entity clicked in the list, to select
+    pub select: Option<Entity>,
+    /// Delete was clicked, waiting for the user to confirm with the users in view
+    pub confirm_delete: bool,
+    /// Select every user at once
+    pub select_all: bool,
+    /// Material every user is swapped to on replace
+    pub replace_with: Option<String>,
+    pub replace_requested: bool,
+}
 
 #[derive(PartialEq, Clone)]
 pub struct EntityEditorTabData {
@@ -25,6 +50,17 @@ pub struct EntityEditorTabData {
     pub component_search_filter: String,
     pub available_materials: AvailableEditableMaterials,
     pub material_delete_requested: bool,
+    pub material_folder_action: Option<MaterialFolderAction>,
+    /// Preview sphere textures by material path, see MaterialPreviews
+    pub material_previews: HashMap<String, egui::TextureId>,
+    pub material_browser_open: bool,
+    pub material_users: MaterialUsersData,
+    /// Textures and recently used ones for the texture fields, see TextureLibrary
+    pub texture_picker: TexturePickerData,
+    pub mesh_stats: Option<MeshStats>,
+    pub mesh_stats_source: Option<(Entity, AssetId<Mesh>)>,
+    pub recalculate_normals_requested: bool,
+    pub uv_preview: bool,
     pub init: bool, //FIX:, proper on init not bool
 }
 
@@ -48,6 +84,15 @@ impl Default for EntityEditorTabData {
             component_search_filter: String::new(),
             available_materials: Default::default(),
             material_delete_requested: false,
+            material_folder_action: None,
+            material_previews: HashMap::new(),
+            material_browser_open: false,
+            material_users: Default::default(),
+            texture_picker: Default::default(),
+            mesh_stats: None,
+            mesh_stats_source: None,
+            recalculate_normals_requested: false,
+            uv_preview: false,
             init: false,
         }
     }
@@ -21,14 +21,15 @@ use bevy::{
     asset::Assets,
     ecs::{
         message::{MessageReader, MessageWriter},
-        query::Without,
+        query::{Or, Without},
         system::{Query, Res, ResMut},
     },
 };
 use bevy_granite_core::{
     entities::{editable::RequestEntityUpdateFromClass, GraniteType, Unknown},
-    AvailableEditableMaterials, ComponentEditor, EditableMaterial, EditableMaterialError,
-    EditableMaterialField, IdentityData, StandardMaterialDef,
+    AvailableEditableMaterials, ComponentEditor, CustomMaterialState, EditableMaterial,
+    EditableMaterialError, EditableMaterialField, EditorCapabilities, ExtendedMaterialState,
+    IdentityData, StandardMaterialDef,
 };
 use bevy_granite_gizmos::GizmoChildren;
 use bevy_granite_logging::{
@@ -56,8 +57,12 @@ pub fn update_entity_with_new_transform_system(
     >,
     mut g_query: Query<(Entity, &mut Transform), (With<GizmoChildren>, Without<IdentityData>)>,
     parent_query: Query<&GlobalTransform, (With<IdentityData>, Without<GizmoChildren>)>,
+    capabilities: Res<EditorCapabilities>,
 ) {
     for UserUpdatedTransformEvent { entity, data } in transform_updated_reader.read() {
+        if !capabilities.can_edit_components(&format!("transform of {}", entity)) {
+            continue;
+        }
         if let Ok((_, mut transform, _current_global, parent)) = e_query.get_mut(*entity) {
             let target_global = Transform {
                 translation: data.global_transform_data.position,
@@ -103,6 +108,9 @@ pub fn update_entity_with_new_transform_system(
     }
 }
 
+/// Entities moved off their StandardMaterial onto an extended or custom one
+type SwappedMaterial = Or<(With<ExtendedMaterialState>, With<CustomMaterialState>)>;
+
 pub fn update_entity_with_new_identity_system(
     mut commands: Commands,
     mut identity_updated_reader: MessageReader<UserUpdatedIdentityEvent>,
@@ -112,12 +120,17 @@ pub fn update_entity_with_new_identity_system(
     mut materials: ResMut<Assets<StandardMaterial>>,
     mut available_obj_materials: ResMut<AvailableEditableMaterials>,
     asset_server: Res<AssetServer>,
+    capabilities: Res<EditorCapabilities>,
+    extended: Query<(), SwappedMaterial>,
 ) {
     for UserUpdatedIdentityEvent {
         entity: updated_entity,
         data: update_data,
     } in identity_updated_reader.read()
     {
+        if !capabilities.can_edit_components(&format!("identity of {}", updated_entity)) {
+            continue;
+        }
         for (
             entity,
             name,
@@ -239,6 +252,13 @@ pub fn update_entity_with_new_identity_system(
                         &asset_server,
                         &mut material_handle_update_writer,
                     );
+
+                    // Extended and custom entities are off their StandardMaterial, give them the standard handle back to pick from
+                    if material_handle.is_none() && extended.contains(entity) {
+                        if let Some(handle) = target_data.current.handle.clone() {
+                            commands.entity(entity).insert(MeshMaterial3d(handle));
+                        }
+                    }
                 }
             }
 
@@ -372,8 +392,7 @@ fn handle_material_update(
             }
         }
 
-        if let Some(ref mut materials_list) = available_obj_materials.materials {
-            materials_list.push(target_material.clone());
+        if available_obj_materials.add_material(target_material.clone()) {
             log!(
                 LogType::Editor,
                 LogLevel::Info,
@@ -430,8 +449,12 @@ fn handle_material_update(
 pub fn update_entity_with_new_components_system(
     mut components_updated_reader: MessageReader<UserUpdatedComponentsEvent>,
     mut commands: Commands,
+    capabilities: Res<EditorCapabilities>,
 ) {
     for UserUpdatedComponentsEvent { entity, data } in components_updated_reader.read() {
+        if !capabilities.can_edit_components(&format!("components of {}", entity)) {
+            continue;
+        }
         let entity = *entity;
         let data = data.clone();
         commands.queue(move |world: &mut World| {
@@ -503,7 +526,7 @@ pub fn handle_material_deletion_system(
             path
         );
 
-        let none_material = if let Some(materials) = &available_materials.materials {
+        let none_material = if let Some(materials) = available_materials.materials() {
             if !materials.is_empty() {
                 materials[0].clone()
             } else {
@@ -525,9 +548,11 @@ pub fn handle_material_deletion_system(
             continue;
         };
 
+        let mut reset = 0;
         for (entity, mut identity) in identity_query.iter_mut() {
             if let Some(material_data) = identity.class.get_mut_material_data() {
                 if material_data.current.path == *path {
+                    reset += 1;
                     log!(
                         LogType::Editor,
                         LogLevel::Info,
@@ -546,6 +571,16 @@ pub fn handle_material_deletion_system(
             }
         }
 
+        if reset > 0 {
+            log!(
+                LogType::Editor,
+                LogLevel::Warning,
+                LogCategory::Asset,
+                "Deleted material '{}' was still used by {} entities, they now use 'None'",
+                path,
+                reset
+            );
+        }
         log!(
             LogType::Editor,
             LogLevel::OK,
@@ -0,0 +1,96 @@
+use crate::interface::panels::right_panel::{SideDockState, SideTab};
+use bevy::{
+    ecs::{
+        entity::Entity,
+        message::MessageWriter,
+        query::With,
+        system::{Commands, Query, Res, ResMut},
+    },
+    prelude::Name,
+};
+use bevy_granite_core::{
+    AvailableEditableMaterials, IdentityData, RequestReplaceMaterialEvent, SpawnSource,
+};
+use bevy_granite_gizmos::EntityEvents;
+
+/// Keeps the "where used" list of the material editor current, for one material or a folder being deleted,
+/// selects the entities clicked in it
+/// and sends the batch replace asked for
+pub fn update_material_users_system(
+    mut right_dock: ResMut<SideDockState>,
+    available_materials: Res<AvailableEditableMaterials>,
+    scene_entities: Query<(Entity, &IdentityData, Option<&Name>), With<SpawnSource>>,
+    mut replace_writer: MessageWriter<RequestReplaceMaterialEvent>,
+    mut commands: Commands,
+) {
+    for (_, tab) in right_dock.dock_state.iter_all_tabs_mut() {
+        let SideTab::EntityEditor { ref mut data } = tab else {
+            continue;
+        };
+        let material_users = &mut data.material_users;
+
+        if let Some(target) = material_users.select.take() {
+            commands.trigger(EntityEvents::Select {
+                target,
+                additive: false,
+            });
+        }
+        if std::mem::take(&mut material_users.select_all) {
+            for (index, (target, _)) in material_users.users.iter().enumerate() {
+                commands.trigger(EntityEvents::Select {
+                    target: *target,
+                    additive: index > 0,
+                });
+            }
+        }
+        if std::mem::take(&mut material_users.replace_requested) {
+            if let (Some(from), Some(to)) = (
+                material_users.path.clone(),
+                material_users.replace_with.take(),
+            ) {
+                replace_writer.write(RequestReplaceMaterialEvent { from, to });
+            }
+        }
+
+        // A folder delete lists the users of every material in it
+        let paths: Vec<&str> = match (&material_users.folder, &material_users.path) {
+            (Some(folder), _) => available_materials
+                .materials_in_folder(folder)
+                .into_iter()
+                .map(|material| material.path.as_str())
+                .collect(),
+            (None, Some(path)) => vec![path.as_str()],
+            (None, None) => {
+                if !material_users.users.is_empty() {
+                    material_users.users.clear();
+                }
+                continue;
+            }
+        };
+        let mut users: Vec<Entity> = paths
+            .into_iter()
+            .flat_map(|path| {
+                available_materials.material_users(
+                    path,
+                    scene_entities
+                        .iter()
+                        .map(|(entity, identity, _)| (entity, identity)),
+                )
+            })
+            .collect();
+        users.sort();
+        users.dedup();
+        let mut users: Vec<(Entity, String)> = users
+            .into_iter()
+            .filter_map(|entity| {
+                let (_, identity, name) = scene_entities.get(entity).ok()?;
+                let name = name.map_or_else(|| identity.name.clone(), |name| name.to_string());
+                Some((entity, name))
+            })
+            .collect();
+        users.sort_by(|a, b| a.1.cmp(&b.1));
+        if material_users.users != users {
+            material_users.users = users;
+        }
+    }
+}
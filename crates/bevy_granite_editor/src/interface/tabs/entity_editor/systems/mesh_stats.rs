@@ -0,0 +1,59 @@
+use crate::interface::panels::right_panel::{SideDockState, SideTab};
+use bevy::{
+    asset::{AssetEvent, Assets},
+    ecs::{
+        entity::Entity,
+        message::{MessageReader, MessageWriter},
+        query::With,
+        system::{Query, Res, ResMut},
+    },
+    mesh::{Mesh, Mesh3d},
+};
+use bevy_granite_core::{MeshStats, RequestRecalculateMeshNormals};
+use bevy_granite_gizmos::ActiveSelection;
+
+/// Keeps the mesh stats of the selected entity current, recounting when the selection or its mesh changes
+pub fn update_entity_mesh_stats_system(
+    mut right_dock: ResMut<SideDockState>,
+    mut mesh_events: MessageReader<AssetEvent<Mesh>>,
+    mut recalculate_writer: MessageWriter<RequestRecalculateMeshNormals>,
+    meshes: Res<Assets<Mesh>>,
+    active: Query<(Entity, &Mesh3d), With<ActiveSelection>>,
+) {
+    let modified: Vec<_> = mesh_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { id } | AssetEvent::LoadedWithDependencies { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+    let selected = active
+        .single()
+        .ok()
+        .map(|(entity, mesh)| (entity, mesh.0.id()));
+
+    for (_, tab) in right_dock.dock_state.iter_all_tabs_mut() {
+        let SideTab::EntityEditor { ref mut data } = tab else {
+            continue;
+        };
+
+        if data.recalculate_normals_requested {
+            data.recalculate_normals_requested = false;
+            if let Some((entity, _)) = selected {
+                recalculate_writer.write(RequestRecalculateMeshNormals(entity));
+            }
+        }
+
+        let stale = data.mesh_stats_source != selected
+            || selected.is_some_and(|(_, mesh)| modified.contains(&mesh))
+            || (data.mesh_stats.is_none() && selected.is_some());
+        if !stale {
+            continue;
+        }
+        data.mesh_stats = selected
+            .and_then(|(_, mesh)| meshes.get(mesh))
+            .map(MeshStats::from_mesh);
+        // Not loaded yet, the load event brings us back here
+        data.mesh_stats_source = data.mesh_stats.as_ref().and(selected);
+    }
+}
@@ -1,7 +1,13 @@
 pub mod entity_updates;
 pub mod material_sync;
+pub mod material_users;
+pub mod mesh_stats;
 pub mod tab_updates;
+pub mod uv_preview;
 
 pub use entity_updates::*;
 pub use material_sync::*;
-pub use tab_updates::*;
\ No newline at end of file
+pub use material_users::*;
+pub use mesh_stats::*;
+pub use tab_updates::*;
+pub use uv_preview::*;
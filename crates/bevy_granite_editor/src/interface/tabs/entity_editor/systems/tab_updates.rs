@@ -1,14 +1,18 @@
-use crate::interface::{
-    cache::{
-        entity_cache::EntityUIDataCache, sync::update_identity_from_cache,
-        update_components_from_cache, update_transform_from_cache,
+use crate::{
+    editor_state::{MaterialPreviews, TextureLibrary},
+    interface::{
+        cache::{
+            entity_cache::EntityUIDataCache, sync::update_identity_from_cache,
+            update_components_from_cache, update_transform_from_cache,
+        },
+        events::{
+            MaterialDeleteEvent, UserUpdatedComponentsEvent, UserUpdatedIdentityEvent,
+            UserUpdatedTransformEvent,
+        },
+        panels::right_panel::{SideDockState, SideTab},
+        shared::MaterialFolderAction,
+        tabs::entity_editor::EntityIdentityData,
     },
-    events::{
-        MaterialDeleteEvent, UserUpdatedComponentsEvent, UserUpdatedIdentityEvent,
-        UserUpdatedTransformEvent,
-    },
-    panels::right_panel::{SideDockState, SideTab},
-    tabs::entity_editor::EntityIdentityData,
 };
 use bevy::ecs::{
     change_detection::DetectChanges,
@@ -18,9 +22,14 @@ use bevy::ecs::{
     system::{Query, Res, ResMut},
 };
 use bevy_granite_core::{
-    entities::GraniteType, AvailableEditableMaterials, ComponentEditor, RegisteredTypeNames,
+    entities::GraniteType, AvailableEditableMaterials, ComponentEditor, EditableMaterial,
+    EditorCapabilities, RegisteredTypeNames,
+};
+use bevy_granite_gizmos::{ActiveSelection, GizmoSnap};
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
 };
-use bevy_granite_gizmos::ActiveSelection;
 
 // Every frame we check the tab for staleness
 pub fn update_entity_editor_tab_system(
@@ -34,6 +43,10 @@ pub fn update_entity_editor_tab_system(
     mut component_updated_writer: MessageWriter<UserUpdatedComponentsEvent>,
     mut material_delete_writer: MessageWriter<MaterialDeleteEvent>,
     global_component_editor: ResMut<ComponentEditor>,
+    capabilities: Res<EditorCapabilities>,
+    gizmo_snap: Res<GizmoSnap>,
+    material_previews: Res<MaterialPreviews>,
+    mut texture_library: ResMut<TextureLibrary>,
 ) {
     for (_, tab) in right_dock.dock_state.iter_all_tabs_mut() {
         if let SideTab::EntityEditor { ref mut data } = tab {
@@ -48,7 +61,7 @@ pub fn update_entity_editor_tab_system(
                         String::new()
                     };
 
-                if !current_path.is_empty() {
+                if !current_path.is_empty() && capabilities.can_delete(&current_path) {
                     // Delete from disk and memory using a temporary clone
                     let temp_material =
                         if let Some(mat_data) = data.identity_data.class_data.get_material_data() {
@@ -60,7 +73,7 @@ pub fn update_entity_editor_tab_system(
                     temp_material.delete_from_disk_and_memory(&mut available_materials);
 
                     // Reset current material to "None" (index 0)
-                    if let Some(materials) = &available_materials.materials {
+                    if let Some(materials) = available_materials.materials() {
                         if !materials.is_empty() {
                             if let Some(mat_data) =
                                 data.identity_data.class_data.get_mut_material_data()
@@ -77,12 +90,45 @@ pub fn update_entity_editor_tab_system(
                 }
             }
 
+            // Folder deletes are confirmed in the where used list first, like single materials
+            if let Some(MaterialFolderAction::RequestDelete { folder }) = data
+                .material_folder_action
+                .take_if(|action| matches!(action, MaterialFolderAction::RequestDelete { .. }))
+            {
+                data.material_users.folder = Some(folder);
+                data.material_users.confirm_delete = true;
+                data.material_users.replace_with = None;
+            }
+
+            if let Some(action) = data.material_folder_action.take() {
+                let touched = apply_material_folder_action(
+                    action,
+                    &mut available_materials,
+                    &capabilities,
+                    &mut material_delete_writer,
+                );
+                if let Some(mat_data) = data.identity_data.class_data.get_mut_material_data() {
+                    if touched.contains(&mat_data.current.path) {
+                        let replacement = available_materials
+                            .find_material_by_path(&mat_data.current.path)
+                            .or_else(|| available_materials.materials()?.first())
+                            .cloned();
+                        if let Some(replacement) = replacement {
+                            *mat_data.path = replacement.path.clone();
+                            *mat_data.current = replacement;
+                        }
+                    }
+                }
+            }
+
             let has_selected = cache.data.entity;
             let identity_data = &mut data.identity_data;
             let components_data = &mut data.registered_data;
             let active = &mut data.active_entity;
 
             let global_transform_data = &mut data.global_transform_data;
+            global_transform_data.scale_snap = gizmo_snap.scale_value;
+            global_transform_data.snap_always = gizmo_snap.always;
 
             // Only clear data when entity selection changes from some entity to none
             if has_selected != data.last_selected_entity {
@@ -107,11 +153,21 @@ pub fn update_entity_editor_tab_system(
             }
 
             if available_materials.is_changed()
-                || (data.available_materials.materials.is_none()
-                    && available_materials.materials.is_some())
+                || (data.available_materials.materials().is_none()
+                    && available_materials.materials().is_some())
             {
                 data.available_materials = available_materials.as_ref().clone();
             }
+            if material_previews.is_changed() {
+                data.material_previews = material_previews.textures.clone();
+            }
+            if std::mem::take(&mut data.texture_picker.scan_requested) {
+                texture_library.needs_scan = true;
+            }
+            if texture_library.is_changed() {
+                data.texture_picker.textures = texture_library.textures.clone();
+                data.texture_picker.thumbnails = texture_library.thumbnails.clone();
+            }
 
             update_identity_from_cache(identity_data, &mut cache, &mut identity_updated_writer);
             update_transform_from_cache(
@@ -126,10 +182,79 @@ pub fn update_entity_editor_tab_system(
             );
 
             // FIX: Do this on init, not here. this is dirty?
-            if !data.init {
+            // Also refreshed when a component schema reload registers new types
+            if !data.init || type_names.is_changed() {
                 data.registered_type_names = type_names.names.clone();
                 data.init = true;
             }
         }
     }
 }
+
+/// Runs a bulk folder operation from the material selector. Returns the paths it saved or deleted
+fn apply_material_folder_action(
+    action: MaterialFolderAction,
+    available_materials: &mut ResMut<AvailableEditableMaterials>,
+    capabilities: &EditorCapabilities,
+    material_delete_writer: &mut MessageWriter<MaterialDeleteEvent>,
+) -> Vec<String> {
+    let mut touched = Vec::new();
+    match action {
+        MaterialFolderAction::SetCategory { folder, category } => {
+            let materials: Vec<EditableMaterial> = available_materials
+                .materials_in_folder(&folder)
+                .into_iter()
+                .cloned()
+                .collect();
+            for mut material in materials {
+                if !capabilities.can_save(&material.path) {
+                    continue;
+                }
+                let Some(def) = material.def.as_mut() else {
+                    continue;
+                };
+                def.category = (!category.is_empty()).then(|| category.clone());
+                material.save_to_file();
+                available_materials.update_material(&material);
+                touched.push(material.path);
+            }
+            log!(
+                LogType::Editor,
+                LogLevel::OK,
+                LogCategory::Asset,
+                "Moved {} materials from '{}' to category '{}'",
+                touched.len(),
+                folder,
+                category
+            );
+        }
+        MaterialFolderAction::RequestDelete { .. } => {}
+        MaterialFolderAction::Delete { folder } => {
+            let materials: Vec<EditableMaterial> = available_materials
+                .materials_in_folder(&folder)
+                .into_iter()
+                .cloned()
+                .collect();
+            for material in materials {
+                if !capabilities.can_delete(&material.path) {
+                    continue;
+                }
+                if material.delete_from_disk_and_memory(available_materials) {
+                    material_delete_writer.write(MaterialDeleteEvent {
+                        path: material.path.clone(),
+                    });
+                    touched.push(material.path);
+                }
+            }
+            log!(
+                LogType::Editor,
+                LogLevel::OK,
+                LogCategory::Asset,
+                "Deleted {} materials in '{}'",
+                touched.len(),
+                folder
+            );
+        }
+    }
+    touched
+}
@@ -0,0 +1,38 @@
+use crate::{
+    interface::panels::right_panel::{SideDockState, SideTab},
+    viewport::UvTilingPreview,
+};
+use bevy::{
+    ecs::system::{Res, ResMut},
+    math::Affine2,
+};
+use bevy_granite_core::GraniteType;
+
+/// Hands the UV transform of the edited material to the viewport while its preview is on
+pub fn update_uv_tiling_preview_source_system(
+    right_dock: Res<SideDockState>,
+    mut preview: ResMut<UvTilingPreview>,
+) {
+    let uv_transform = right_dock
+        .dock_state
+        .iter_all_tabs()
+        .find_map(|(_, tab)| match tab {
+            SideTab::EntityEditor { data } if data.uv_preview => data
+                .identity_data
+                .class_data
+                .get_material_data()
+                .and_then(|material| material.current.def.as_ref())
+                .map(|def| data.available_materials.resolve_def(def))
+                .map(|def| {
+                    def.uv_transform.map_or(Affine2::IDENTITY, |m| {
+                        Affine2::from_cols_array(&[
+                            m[0][0], m[0][1], m[1][0], m[1][1], m[2][0], m[2][1],
+                        ])
+                    })
+                }),
+            _ => None,
+        });
+    if preview.uv_transform != uv_transform {
+        preview.uv_transform = uv_transform;
+    }
+}
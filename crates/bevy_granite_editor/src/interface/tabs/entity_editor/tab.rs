@@ -3,7 +3,7 @@ use bevy_egui::egui;
 use super::{
     data::EntityEditorTabData,
     widgets::{
-        entity_component_widget, entity_identity_widget,
+        entity_component_widget, entity_identity_widget, entity_mesh_stats_widget,
         entity_name_widget, entity_transform_widget,
     },
 };
@@ -12,5 +12,6 @@ pub fn entity_editor_tab_ui(ui: &mut egui::Ui, data: &mut EntityEditorTabData) {
     entity_name_widget(ui, data);
     entity_transform_widget(ui, data);
     entity_identity_widget(ui, data);
+    entity_mesh_stats_widget(ui, data);
     entity_component_widget(ui, data);
 }
@@ -1,10 +1,15 @@
 use bevy_egui::egui;
-use bevy_granite_core::{entities::GraniteType, GraniteTypes, EditableMaterial, NewEditableMaterial, ClassCategory};
+use bevy_granite_core::{
+    entities::GraniteType, AvailableEditableMaterials, ClassCategory, EditableMaterial,
+    GraniteTypes, NewEditableMaterial,
+};
 use bevy_granite_logging::{
     config::{LogCategory, LogLevel, LogType},
     log,
 };
 
+use crate::interface::shared::MaterialFolderAction;
+use crate::interface::tabs::entity_editor::data::MaterialUsersData;
 use crate::interface::tabs::{
     entity_editor::widgets::material_editor::{
         display_material_creation, display_material_edit, display_material_selector_field,
@@ -54,10 +59,7 @@ fn display_class_specific_data(ui: &mut egui::Ui, tab_data: &mut EntityEditorTab
         let mut changed = class_data.edit_via_ui(ui, spacing);
 
         // Display material editor
-        if matches!(
-            class_data.category(),
-            ClassCategory::Mesh
-        ) {
+        if matches!(class_data.category(), ClassCategory::Mesh) {
             if let Some(material_data) = class_data.get_mut_material_data() {
                 ui.add_space(spacing.1);
                 changed |= display_material_data(
@@ -77,6 +79,148 @@ fn display_class_specific_data(ui: &mut egui::Ui, tab_data: &mut EntityEditorTab
     });
 }
 
+/// "Where used" list with click-to-select, and the delete confirmation of a material or a folder
+/// Returns true when the delete is confirmed
+fn display_material_users(
+    ui: &mut egui::Ui,
+    material_users: &mut MaterialUsersData,
+    current_path: &str,
+    available_materials: &AvailableEditableMaterials,
+) -> bool {
+    let spacing = crate::UI_CONFIG.spacing;
+    let folder = material_users.folder.clone();
+    let Some(path) = folder
+        .as_ref()
+        .map(|folder| format!("{}/ (every material)", folder))
+        .or_else(|| material_users.path.clone())
+    else {
+        return false;
+    };
+    // Picking another material cancels a pending delete
+    if material_users.confirm_delete && folder.is_none() && path != current_path {
+        material_users.confirm_delete = false;
+    }
+    let mut confirmed = false;
+
+    ui.add_space(spacing);
+    egui::Frame::group(ui.style()).show(ui, |ui| {
+        ui.set_width(ui.available_width());
+        ui.horizontal(|ui| {
+            ui.label(format!(
+                "Used by {} entities: {}",
+                material_users.users.len(),
+                path
+            ));
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.small_button("✖").clicked() {
+                    material_users.path = None;
+                    material_users.folder = None;
+                    material_users.confirm_delete = false;
+                    material_users.replace_with = None;
+                }
+            });
+        });
+        egui::ScrollArea::vertical()
+            .id_salt("material_users")
+            .max_height(120.)
+            .show(ui, |ui| {
+                for (entity, name) in material_users.users.iter() {
+                    if ui
+                        .selectable_label(false, name)
+                        .on_hover_text(format!("{:?}, click to select", entity))
+                        .clicked()
+                    {
+                        material_users.select = Some(*entity);
+                    }
+                }
+            });
+
+        if !material_users.users.is_empty() && !material_users.confirm_delete && folder.is_none() {
+            ui.add_space(spacing);
+            display_material_replace(ui, material_users, &path, available_materials);
+        }
+
+        if material_users.confirm_delete {
+            ui.add_space(spacing);
+            if !material_users.users.is_empty() {
+                ui.colored_label(
+                    egui::Color32::YELLOW,
+                    "Still in use, these entities will be reset to None",
+                );
+            }
+            ui.horizontal(|ui| {
+                if ui.button("Confirm Delete").clicked() {
+                    confirmed = true;
+                    material_users.path = None;
+                    material_users.folder = None;
+                    material_users.confirm_delete = false;
+                }
+                if ui.button("Cancel").clicked() {
+                    material_users.folder = None;
+                    material_users.confirm_delete = false;
+                }
+            });
+        }
+    });
+    confirmed
+}
+
+/// Select every user, or swap them all to another material across the loaded scenes
+fn display_material_replace(
+    ui: &mut egui::Ui,
+    material_users: &mut MaterialUsersData,
+    path: &str,
+    available_materials: &AvailableEditableMaterials,
+) {
+    ui.horizontal(|ui| {
+        if ui
+            .button("Select All")
+            .on_hover_text("Select every entity using this material")
+            .clicked()
+        {
+            material_users.select_all = true;
+        }
+        ui.separator();
+        ui.label("Replace with");
+        egui::ComboBox::from_id_salt("material_replace_with")
+            .selected_text(
+                material_users
+                    .replace_with
+                    .as_deref()
+                    .unwrap_or("Pick a material"),
+            )
+            .width(160.)
+            .show_ui(ui, |ui| {
+                for material in available_materials.materials().unwrap_or_default() {
+                    if material.is_empty() || material.path.is_empty() || material.path == path {
+                        continue;
+                    }
+                    let selected = material_users.replace_with.as_deref() == Some(&material.path);
+                    if ui
+                        .selectable_label(selected, &material.path)
+                        .on_hover_text(&material.friendly_name)
+                        .clicked()
+                    {
+                        material_users.replace_with = Some(material.path.clone());
+                    }
+                }
+            });
+        if ui
+            .add_enabled(
+                material_users.replace_with.is_some(),
+                egui::Button::new("Replace All"),
+            )
+            .on_hover_text(format!(
+                "Swap all {} entities to the picked material, in every loaded scene",
+                material_users.users.len()
+            ))
+            .clicked()
+        {
+            material_users.replace_requested = true;
+        }
+    });
+}
+
 fn display_material_data(
     ui: &mut egui::Ui,
     current_material: &mut EditableMaterial,
@@ -92,6 +236,7 @@ fn display_material_data(
     let surface_collapsed_state = &mut tab_data.surface_collapsed_state;
     let settings_collapsed_state = &mut tab_data.settings_collapsed_state;
     let available_materials = &mut tab_data.available_materials;
+    let uv_preview = &mut tab_data.uv_preview;
 
     let mut changed = false;
     let mut edit_changes = false;
@@ -112,17 +257,41 @@ fn display_material_data(
                 material_search_filter,
                 class_material_path,
                 current_material,
+                &mut tab_data.material_folder_action,
+                &tab_data.material_previews,
+                &mut tab_data.material_browser_open,
+                &mut tab_data.material_users.path,
             );
             changed = material_changed;
-            
+
             // Handle delete request
-            if delete_clicked && !current_material.is_empty() && current_material.friendly_name != "None" {
-                // Set a flag to indicate deletion was requested
-                // We'll handle this in the tab update system
-                tab_data.material_delete_requested = true;
+            if delete_clicked
+                && !current_material.is_empty()
+                && current_material.friendly_name != "None"
+            {
+                // Ask first, with the entities still using it listed
+                // Confirmed deletes are handled in the tab update system
+                tab_data.material_users.path = Some(current_material.path.clone());
+                tab_data.material_users.folder = None;
+                tab_data.material_users.confirm_delete = true;
             }
         });
 
+        let folder = tab_data.material_users.folder.clone();
+        if display_material_users(
+            ui,
+            &mut tab_data.material_users,
+            &current_material.path,
+            available_materials,
+        ) {
+            match folder {
+                Some(folder) => {
+                    tab_data.material_folder_action = Some(MaterialFolderAction::Delete { folder })
+                }
+                None => tab_data.material_delete_requested = true,
+            }
+        }
+
         ui.add_space(large_spacing);
         ui.vertical(|ui| {
             if *material_builder_open {
@@ -140,7 +309,13 @@ fn display_material_data(
                 .open(Some(surface_open))
                 .show(ui, |ui| {
                     ui.set_max_width(ui.available_width());
-                    edit_changes |= display_material_edit(ui, current_material);
+                    edit_changes |= display_material_edit(
+                        ui,
+                        current_material,
+                        available_materials,
+                        uv_preview,
+                        &mut tab_data.texture_picker,
+                    );
                     if edit_changes {
                         current_material.disk_changes = edit_changes;
                         changed = true;
@@ -151,7 +326,8 @@ fn display_material_data(
             let collapsing_settings_response = egui::CollapsingHeader::new("Metadata")
                 .open(Some(settings_open))
                 .show(ui, |ui| {
-                    edit_changes |= display_material_settings(ui, current_material);
+                    edit_changes |=
+                        display_material_settings(ui, current_material, available_materials);
                     if edit_changes {
                         current_material.disk_changes = edit_changes;
                         changed = true;
@@ -183,12 +359,12 @@ fn display_material_data(
                     + ".mat";
 
                 *class_material_path = file_rel_path.clone();
-                
+
                 // If current material is None, create a new material instead of modifying it
                 if current_material.friendly_name == "None" || current_material.is_empty() {
                     *current_material = EditableMaterial::get_new_unnamed_base_color();
                 }
-                
+
                 current_material.new_material = true;
                 current_material.path = file_rel_path.clone();
                 current_material.friendly_name = material_to_build.friendly_name.clone();
@@ -1,15 +1,19 @@
-use crate::interface::shared::material_selector_combo;
+use crate::interface::shared::{
+    material_selector_combo, texture_picker_combo, MaterialFolderAction, TexturePickerData,
+};
 use bevy::pbr::StandardMaterial;
 use bevy_egui::egui;
 use bevy_granite_core::{
-    AvailableEditableMaterials, EditableMaterial, EditableMaterialField, NewEditableMaterial,
-    StandardMaterialDef,
+    AvailableEditableMaterials, CustomMaterialDef, CustomUniform, CustomUniformValue,
+    DetailSettings, EditableMaterial, EditableMaterialField, NewEditableMaterial,
+    StandardMaterialDef, TriplanarSettings, WindSettings,
 };
 use bevy_granite_logging::{
     config::{LogCategory, LogLevel, LogType},
     log,
 };
 use native_dialog::FileDialog;
+use std::collections::HashMap;
 
 pub fn display_add_material_field_dropdown(
     ui: &mut egui::Ui,
@@ -61,6 +65,9 @@ pub fn display_add_material_field_dropdown(
 
 fn init_default_field(field: &EditableMaterialField, def: &mut StandardMaterialDef) {
     let defaults = StandardMaterial::default();
+    let wind_defaults = WindSettings::default();
+    let triplanar_defaults = TriplanarSettings::default();
+    let detail_defaults = DetailSettings::default();
     let material = def;
     match field {
         EditableMaterialField::Emissive => {
@@ -101,6 +108,18 @@ fn init_default_field(field: &EditableMaterialField, def: &mut StandardMaterialD
         EditableMaterialField::OcclusionMap => {
             material.occlusion_map = Some(String::new());
         }
+        EditableMaterialField::DepthMap => {
+            material.depth_map = Some(String::new());
+        }
+        EditableMaterialField::ParallaxDepthScale => {
+            material.parallax_depth_scale = Some(defaults.parallax_depth_scale);
+        }
+        EditableMaterialField::ParallaxMappingMethod => {
+            material.parallax_mapping_method = Some("Occlusion".to_string());
+        }
+        EditableMaterialField::MaxParallaxLayerCount => {
+            material.max_parallax_layer_count = Some(defaults.max_parallax_layer_count);
+        }
         EditableMaterialField::Thickness => {
             material.thickness = Some(defaults.thickness);
         }
@@ -114,6 +133,15 @@ fn init_default_field(field: &EditableMaterialField, def: &mut StandardMaterialD
         EditableMaterialField::AttenuationDistance => {
             material.attenuation_distance = Some(defaults.attenuation_distance);
         }
+        EditableMaterialField::SpecularTransmission => {
+            material.specular_transmission = Some(defaults.specular_transmission);
+        }
+        EditableMaterialField::DiffuseTransmission => {
+            material.diffuse_transmission = Some(defaults.diffuse_transmission);
+        }
+        EditableMaterialField::Ior => {
+            material.ior = Some(defaults.ior);
+        }
         EditableMaterialField::Clearcoat => {
             material.clearcoat = Some(defaults.clearcoat);
         }
@@ -147,6 +175,42 @@ fn init_default_field(field: &EditableMaterialField, def: &mut StandardMaterialD
         EditableMaterialField::UvTransform => {
             material.uv_transform = Some([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]);
         }
+        EditableMaterialField::WindStrength => {
+            material.wind_strength = Some(wind_defaults.strength);
+        }
+        EditableMaterialField::WindFrequency => {
+            material.wind_frequency = Some(wind_defaults.frequency);
+        }
+        EditableMaterialField::WindMask => {
+            material.wind_mask = Some(wind_defaults.mask.name().to_string());
+        }
+        EditableMaterialField::WindMaskHeight => {
+            material.wind_mask_height = Some(wind_defaults.mask_height);
+        }
+        EditableMaterialField::Triplanar => {
+            material.triplanar = Some(true);
+        }
+        EditableMaterialField::TriplanarScale => {
+            material.triplanar_scale = Some(triplanar_defaults.scale);
+        }
+        EditableMaterialField::TriplanarSharpness => {
+            material.triplanar_sharpness = Some(triplanar_defaults.sharpness);
+        }
+        EditableMaterialField::DetailAlbedoTexture => {
+            material.detail_albedo_texture = Some(String::new());
+        }
+        EditableMaterialField::DetailNormalTexture => {
+            material.detail_normal_texture = Some(String::new());
+        }
+        EditableMaterialField::DetailTiling => {
+            material.detail_tiling = Some(detail_defaults.tiling);
+        }
+        EditableMaterialField::DetailStrength => {
+            material.detail_strength = Some(detail_defaults.strength);
+        }
+        EditableMaterialField::CustomShader => {
+            material.custom = Some(CustomMaterialDef::default());
+        }
         _ => {}
     }
 }
@@ -158,7 +222,11 @@ pub enum MaterialTab {
     Create,
 }
 
-pub fn display_material_settings(ui: &mut egui::Ui, material: &mut EditableMaterial) -> bool {
+pub fn display_material_settings(
+    ui: &mut egui::Ui,
+    material: &mut EditableMaterial,
+    available_materials: &AvailableEditableMaterials,
+) -> bool {
     let large_spacing = crate::UI_CONFIG.large_spacing;
     let small_spacing = crate::UI_CONFIG.small_spacing;
     let mut changed = false;
@@ -198,8 +266,14 @@ pub fn display_material_settings(ui: &mut egui::Ui, material: &mut EditableMater
 
                     ui.label("Path");
                     ui.label(material.path.to_string());
+                    ui.end_row();
+
+                    ui.label("Parent");
+                    let parent_changed =
+                        display_parent_selector(ui, &material.path, def, available_materials);
+                    ui.end_row();
 
-                    changed = field_changed;
+                    changed = field_changed || parent_changed;
                 }
             });
 
@@ -208,7 +282,69 @@ pub fn display_material_settings(ui: &mut egui::Ui, material: &mut EditableMater
     changed
 }
 
-pub fn display_material_edit(ui: &mut egui::Ui, material: &mut EditableMaterial) -> bool {
+/// Material this one can inherit from. Skips itself and its descendants so the chain can't loop
+fn display_parent_selector(
+    ui: &mut egui::Ui,
+    path: &str,
+    def: &mut StandardMaterialDef,
+    available_materials: &AvailableEditableMaterials,
+) -> bool {
+    let mut changed = false;
+    let selected_text = def
+        .parent
+        .as_deref()
+        .map(|parent| {
+            available_materials
+                .find_material_by_path(parent)
+                .map_or_else(
+                    || format!("{} (missing)", parent),
+                    |m| m.friendly_name.clone(),
+                )
+        })
+        .unwrap_or_else(|| "None".to_string());
+
+    egui::ComboBox::from_id_salt("material_parent_dropdown")
+        .selected_text(selected_text)
+        .width(ui.available_width())
+        .show_ui(ui, |ui| {
+            if ui.selectable_label(def.parent.is_none(), "None").clicked() && def.parent.is_some() {
+                def.parent = None;
+                changed = true;
+            }
+            for candidate in available_materials.materials().unwrap_or_default() {
+                if candidate.is_empty()
+                    || candidate.path == path
+                    || available_materials.inherits_from(&candidate.path, path)
+                {
+                    continue;
+                }
+                let is_selected = def.parent.as_deref() == Some(candidate.path.as_str());
+                let response = ui
+                    .selectable_label(is_selected, &candidate.friendly_name)
+                    .on_hover_text(&candidate.path);
+                if response.clicked() && !is_selected {
+                    def.parent = Some(candidate.path.clone());
+                    log!(
+                        LogType::Editor,
+                        LogLevel::Info,
+                        LogCategory::UI,
+                        "User set material parent: {}",
+                        candidate.path
+                    );
+                    changed = true;
+                }
+            }
+        });
+    changed
+}
+
+pub fn display_material_edit(
+    ui: &mut egui::Ui,
+    material: &mut EditableMaterial,
+    available_materials: &AvailableEditableMaterials,
+    uv_preview: &mut bool,
+    texture_picker: &mut TexturePickerData,
+) -> bool {
     let large_spacing = crate::UI_CONFIG.large_spacing;
     let small_spacing = crate::UI_CONFIG.small_spacing;
     let mut changed = false;
@@ -230,6 +366,8 @@ pub fn display_material_edit(ui: &mut egui::Ui, material: &mut EditableMaterial)
                                 field,
                                 def,
                                 &StandardMaterial::default(),
+                                uv_preview,
+                                texture_picker,
                             );
                         }
                     }
@@ -240,11 +378,108 @@ pub fn display_material_edit(ui: &mut egui::Ui, material: &mut EditableMaterial)
                 }
             });
 
+        let parent = material.def.as_ref().and_then(|def| {
+            let path = def.parent.as_deref()?;
+            let parent = available_materials.find_material_by_path(path)?;
+            Some((
+                parent.friendly_name.clone(),
+                available_materials.resolved_def(parent)?,
+            ))
+        });
+        if let Some((parent_name, parent_def)) = parent {
+            ui.add_space(large_spacing);
+            if display_inherited_fields(ui, material, &parent_name, &parent_def, texture_picker) {
+                material.clean_fields();
+                changed = true;
+            }
+        }
+
         ui.add_space(large_spacing);
     });
     changed
 }
 
+/// Read-only rows for the fields the parent provides, with dropdowns to override one or revert it to the parent
+fn display_inherited_fields(
+    ui: &mut egui::Ui,
+    material: &mut EditableMaterial,
+    parent_name: &str,
+    parent_def: &StandardMaterialDef,
+    texture_picker: &mut TexturePickerData,
+) -> bool {
+    let large_spacing = crate::UI_CONFIG.large_spacing;
+    let small_spacing = crate::UI_CONFIG.small_spacing;
+    let mut changed = false;
+    let Some(def) = material.def.as_mut() else {
+        return false;
+    };
+
+    let (overridden, inherited): (Vec<_>, Vec<_>) = EditableMaterialField::all()
+        .into_iter()
+        .filter(|field| parent_def.has_field(field))
+        .partition(|field| def.has_field(field));
+
+    ui.label(egui::RichText::new(format!("Inherited from {}", parent_name)).weak());
+    ui.add_enabled_ui(false, |ui| {
+        egui::Grid::new("material_inherited_grid")
+            .num_columns(3)
+            .spacing([large_spacing, small_spacing])
+            .striped(true)
+            .min_col_width(ui.available_width() / 4.0 - large_spacing * 4.)
+            .max_col_width(ui.available_width() / 3.0 - large_spacing * 3.)
+            .show(ui, |ui| {
+                let mut shown = parent_def.clone();
+                for field in &inherited {
+                    display_standard_material_field(
+                        ui,
+                        field,
+                        &mut shown,
+                        &StandardMaterial::default(),
+                        &mut false,
+                        texture_picker,
+                    );
+                }
+            });
+    });
+
+    ui.add_space(small_spacing);
+    ui.horizontal(|ui| {
+        let width = ui.available_width() / 2.0 - small_spacing;
+        if !inherited.is_empty() {
+            egui::ComboBox::from_id_salt("override_material_field_dropdown")
+                .selected_text("Override field...")
+                .width(width)
+                .show_ui(ui, |ui| {
+                    for field in &inherited {
+                        if ui.button(format!("{:?}", field)).clicked() {
+                            def.copy_field(field, parent_def);
+                            let fields = material.fields.get_or_insert_with(Vec::new);
+                            if !fields.contains(field) {
+                                fields.push(field.clone());
+                            }
+                            changed = true;
+                        }
+                    }
+                });
+        }
+        if !overridden.is_empty() {
+            egui::ComboBox::from_id_salt("revert_material_field_dropdown")
+                .selected_text("Revert to parent...")
+                .width(width)
+                .show_ui(ui, |ui| {
+                    for field in &overridden {
+                        if ui.button(format!("{:?}", field)).clicked() {
+                            // Clearing the field lets clean_fields drop it, so the parent's shows through
+                            def.copy_field(field, &StandardMaterialDef::default());
+                            changed = true;
+                        }
+                    }
+                });
+        }
+    });
+    changed
+}
+
 pub fn display_material_creation(ui: &mut egui::Ui, new: &mut NewEditableMaterial) -> (bool, bool) {
     let spacing = crate::UI_CONFIG.spacing;
     let large_spacing = crate::UI_CONFIG.large_spacing;
@@ -306,6 +541,7 @@ pub fn display_material_creation(ui: &mut egui::Ui, new: &mut NewEditableMateria
     (save_clicked, cancel_clicked)
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn display_material_selector_field(
     ui: &mut egui::Ui,
     available_materials: &AvailableEditableMaterials,
@@ -313,19 +549,33 @@ pub fn display_material_selector_field(
     material_search_filter: &mut String,
     class_material_path: &mut String,
     current_material: &mut EditableMaterial,
+    folder_action: &mut Option<MaterialFolderAction>,
+    previews: &HashMap<String, egui::TextureId>,
+    browser_open: &mut bool,
+    where_used: &mut Option<String>,
 ) -> (bool, bool) {
     let mut changed = false;
     let mut delete_clicked = false;
     let search_filter = material_search_filter;
 
     ui.vertical(|ui| {
-        let combo_response = material_selector_combo(
-            ui,
-            search_filter,
-            available_materials,
-            class_material_path,
-            current_material,
-        );
+        let combo_response = ui
+            .horizontal(|ui| {
+                if let Some(texture) = previews.get(&current_material.path) {
+                    ui.image((*texture, egui::vec2(48.0, 48.0)));
+                }
+                material_selector_combo(
+                    ui,
+                    search_filter,
+                    available_materials,
+                    class_material_path,
+                    current_material,
+                    folder_action,
+                    previews,
+                    where_used,
+                )
+            })
+            .inner;
 
         ui.separator();
         ui.horizontal(|ui| {
@@ -333,6 +583,18 @@ pub fn display_material_selector_field(
             if create_button.clicked() {
                 *material_builder_open = true;
             }
+            ui.toggle_value(browser_open, "Browse")
+                .on_hover_text("Pick from a grid of material previews");
+            if ui
+                .add_enabled(
+                    !current_material.is_empty(),
+                    egui::Button::new("Where Used"),
+                )
+                .on_hover_text("List the entities of loaded scenes using this material")
+                .clicked()
+            {
+                *where_used = Some(current_material.path.clone());
+            }
 
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                 let delete_button = ui.button("Delete");
@@ -342,11 +604,84 @@ pub fn display_material_selector_field(
             });
         });
 
+        if *browser_open {
+            changed |= display_material_browser(
+                ui,
+                available_materials,
+                class_material_path,
+                current_material,
+                previews,
+                where_used,
+            );
+        }
+
         changed |= combo_response;
     });
     (changed, delete_clicked)
 }
 
+/// Grid of material preview tiles, clicking one selects it like the selector does
+fn display_material_browser(
+    ui: &mut egui::Ui,
+    available_materials: &AvailableEditableMaterials,
+    class_material_path: &mut String,
+    current_material: &mut EditableMaterial,
+    previews: &HashMap<String, egui::TextureId>,
+    where_used: &mut Option<String>,
+) -> bool {
+    const TILE_SIZE: f32 = 56.0;
+    let mut changed = false;
+    let Some(materials) = available_materials.materials() else {
+        ui.label("No materials available");
+        return false;
+    };
+
+    ui.add_space(crate::UI_CONFIG.spacing);
+    egui::ScrollArea::vertical()
+        .id_salt("material_browser")
+        .max_height(TILE_SIZE * 4.0)
+        .show(ui, |ui| {
+            ui.horizontal_wrapped(|ui| {
+                for material in materials
+                    .iter()
+                    .filter(|material| !material.path.is_empty())
+                {
+                    let is_selected = current_material.path == material.path;
+                    let size = egui::vec2(TILE_SIZE, TILE_SIZE);
+                    let button = match previews.get(&material.path) {
+                        Some(texture) => egui::Button::image((*texture, size)),
+                        None => egui::Button::new(if material.is_empty() { "None" } else { "…" })
+                            .min_size(size),
+                    };
+                    let response = ui
+                        .add(button.selected(is_selected))
+                        .on_hover_text(&material.path);
+                    if !material.is_empty() {
+                        response.context_menu(|ui| {
+                            if ui.button("Where Used").clicked() {
+                                *where_used = Some(material.path.clone());
+                                ui.close();
+                            }
+                        });
+                    }
+                    if response.clicked() && !is_selected {
+                        *current_material = material.clone();
+                        *class_material_path = material.path.clone();
+                        log!(
+                            LogType::Editor,
+                            LogLevel::OK,
+                            LogCategory::UI,
+                            "User selected new material: {}",
+                            material.friendly_name
+                        );
+                        changed = true;
+                    }
+                }
+            });
+        });
+    changed
+}
+
 // -------------------------------------------------------------------------------------------------------------
 // Material Input Types
 // -------------------------------------------------------------------------------------------------------------
@@ -702,31 +1037,74 @@ fn display_text_field(
     changed
 }
 
-fn display_uv_scale_field(
+/// Texture path picked from the texture library, see texture_picker_combo
+fn display_texture_field(
+    ui: &mut egui::Ui,
+    name: &str,
+    value: &mut Option<String>,
+    texture_picker: &mut TexturePickerData,
+) -> bool {
+    let mut changed = false;
+    let large_spacing = crate::UI_CONFIG.large_spacing;
+    ui.label(name);
+
+    let mut path = value.clone().unwrap_or_default();
+    ui.horizontal(|ui| {
+        ui.set_max_width(ui.available_width() - large_spacing * 3.);
+        if texture_picker_combo(ui, name, &mut path, texture_picker) {
+            *value = Some(path);
+            changed = true;
+        }
+    });
+
+    if value.is_some() {
+        ui.horizontal(|ui| {
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.button("❌").on_hover_text("Clear value").clicked() {
+                    log!(
+                        LogType::Editor,
+                        LogLevel::Info,
+                        LogCategory::UI,
+                        "User Removed: {:?}",
+                        name
+                    );
+                    *value = None;
+                    changed = true;
+                }
+            });
+        });
+    }
+
+    ui.end_row();
+    changed
+}
+
+/// Most tile lines drawn per axis in the preview pad, past this the pad only shades
+const UV_PAD_MAX_LINES: i32 = 64;
+
+fn display_uv_transform_field(
     ui: &mut egui::Ui,
     uv_transform: &mut Option<[[f32; 3]; 3]>,
     default: Option<(f32, f32)>,
+    uv_preview: &mut bool,
 ) -> bool {
     let mut changed = false;
 
-    // Extract current scale from matrix or use default (1.0, 1.0)
-    let (mut scale_x, mut scale_y) = if let Some(matrix) = uv_transform {
-        (matrix[0][0], matrix[1][1])
-    } else if let Some((dx, dy)) = default {
-        (dx, dy)
-    } else {
-        (1.0, 1.0)
-    };
+    // Columns are [x_axis, y_axis, translation], rotation terms are kept as they are
+    let mut matrix = uv_transform.unwrap_or_else(|| {
+        let (dx, dy) = default.unwrap_or((1.0, 1.0));
+        [[dx, 0.0, 0.0], [0.0, dy, 0.0], [0.0, 0.0, 1.0]]
+    });
+    let mut edited = false;
 
     ui.label("UV Scale");
-    let mut scale_changed = false;
     ui.horizontal(|ui| {
-        scale_changed |= ui
-            .add(egui::DragValue::new(&mut scale_x).speed(0.01))
+        edited |= ui
+            .add(egui::DragValue::new(&mut matrix[0][0]).speed(0.01))
             .changed();
         ui.label("x");
-        scale_changed |= ui
-            .add(egui::DragValue::new(&mut scale_y).speed(0.01))
+        edited |= ui
+            .add(egui::DragValue::new(&mut matrix[1][1]).speed(0.01))
             .changed();
         ui.label("y");
     });
@@ -746,14 +1124,216 @@ fn display_uv_scale_field(
             }
         });
     });
+    ui.end_row();
+
+    ui.label("UV Offset");
+    ui.horizontal(|ui| {
+        edited |= ui
+            .add(egui::DragValue::new(&mut matrix[2][0]).speed(0.01))
+            .changed();
+        ui.label("x");
+        edited |= ui
+            .add(egui::DragValue::new(&mut matrix[2][1]).speed(0.01))
+            .changed();
+        ui.label("y");
+    });
+    ui.checkbox(uv_preview, "Preview")
+        .on_hover_text("Show the tiling on the selected mesh in the viewport");
+    ui.end_row();
+
+    if *uv_preview {
+        ui.label("");
+        edited |= display_uv_tiling_pad(ui, &mut matrix);
+        ui.label("");
+        ui.end_row();
+    }
 
-    if scale_changed {
-        // Only scale, so identity matrix with scale
-        *uv_transform = Some([[scale_x, 0.0, 0.0], [0.0, scale_y, 0.0], [0.0, 0.0, 1.0]]);
+    if edited && !changed {
+        *uv_transform = Some(matrix);
+        changed = true;
+    }
+    changed
+}
+
+/// Square standing for the mesh's 0..1 UV space, with a line wherever a texture repeat starts
+/// Dragging moves the offset, shift dragging scales around the center of the pad
+fn display_uv_tiling_pad(ui: &mut egui::Ui, matrix: &mut [[f32; 3]; 3]) -> bool {
+    let size = ui.available_width().clamp(60.0, 140.0);
+    let (rect, response) =
+        ui.allocate_exact_size(egui::vec2(size, size), egui::Sense::click_and_drag());
+    let response = response.on_hover_text("Drag to offset, Shift + drag to scale");
+
+    let mut changed = false;
+    if response.dragged() {
+        let delta = response.drag_delta() / rect.size();
+        if ui.input(|input| input.modifiers.shift) {
+            for (axis, amount) in [(0, delta.x), (1, -delta.y)] {
+                let scale = matrix[axis][axis];
+                let center = scale * 0.5 + matrix[2][axis];
+                let new_scale = scale * (amount * 2.0).exp();
+                matrix[axis][axis] = new_scale;
+                matrix[2][axis] = center - new_scale * 0.5;
+            }
+        } else {
+            matrix[2][0] -= matrix[0][0] * delta.x;
+            matrix[2][1] -= matrix[1][1] * delta.y;
+        }
+        changed = delta != egui::Vec2::ZERO;
+    }
+
+    let visuals = ui.visuals();
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 2.0, visuals.extreme_bg_color);
+    let line = egui::Stroke::new(1.0, visuals.widgets.inactive.fg_stroke.color);
+    let origin = egui::Stroke::new(2.0, visuals.selection.stroke.color);
+
+    // A texture repeat starts where scale * uv + offset is a whole number
+    for (axis, (scale, offset)) in [(matrix[0][0], matrix[2][0]), (matrix[1][1], matrix[2][1])]
+        .into_iter()
+        .enumerate()
+    {
+        let (low, high) = if scale >= 0.0 {
+            (offset, scale + offset)
+        } else {
+            (scale + offset, offset)
+        };
+        let (first, last) = (low.ceil() as i32, high.floor() as i32);
+        if scale.abs() <= f32::EPSILON || last - first > UV_PAD_MAX_LINES {
+            continue;
+        }
+        for k in first..=last {
+            let t = (k as f32 - offset) / scale;
+            let stroke = if k == 0 { origin } else { line };
+            let (a, b) = if axis == 0 {
+                let x = egui::lerp(rect.left()..=rect.right(), t);
+                (egui::pos2(x, rect.top()), egui::pos2(x, rect.bottom()))
+            } else {
+                let y = egui::lerp(rect.top()..=rect.bottom(), t);
+                (egui::pos2(rect.left(), y), egui::pos2(rect.right(), y))
+            };
+            painter.line_segment([a, b], stroke);
+        }
+    }
+    painter.rect_stroke(rect, 2.0, line, egui::StrokeKind::Inside);
+    changed
+}
+
+fn display_custom_shader_field(ui: &mut egui::Ui, custom: &mut Option<CustomMaterialDef>) -> bool {
+    let mut changed = false;
+    let mut clear = false;
+    let Some(def) = custom.as_mut() else {
+        return false;
+    };
+
+    ui.label("Custom Shader");
+    ui.horizontal(|ui| {
+        changed |= ui.text_edit_singleline(&mut def.shader).changed();
+        ui.spacing_mut().button_padding = egui::Vec2::new(2.0, 2.0);
+        if ui.button("📁").clicked() {
+            let assets_dir = std::env::current_dir().unwrap_or_default().join("assets");
+            if let Ok(Some(path)) = FileDialog::new()
+                .add_filter("WGSL Shaders", &["wgsl"])
+                .set_location(&assets_dir)
+                .show_open_single_file()
+            {
+                def.shader = path
+                    .strip_prefix(&assets_dir)
+                    .unwrap_or(&path)
+                    .to_string_lossy()
+                    .replace('\\', "/");
+                changed = true;
+            }
+        }
+    });
+    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+        if ui.button("❌").on_hover_text("Clear value").clicked() {
+            clear = true;
+        }
+    });
+    ui.end_row();
+
+    ui.label("Animated");
+    changed |= ui
+        .checkbox(&mut def.animated, "")
+        .on_hover_text("Update the time uniform every frame")
+        .changed();
+    ui.end_row();
+    ui.label("Blend");
+    changed |= ui.checkbox(&mut def.blend, "").changed();
+    ui.end_row();
+
+    // Slots as the shader reads them, see custom.wgsl
+    let slots = def.slots();
+    let mut removed = None;
+    for (index, (uniform, slot)) in def.uniforms.iter_mut().zip(slots).enumerate() {
+        changed |= ui
+            .add(egui::TextEdit::singleline(&mut uniform.name).desired_width(80.0))
+            .changed();
+        ui.horizontal(|ui| {
+            match &mut uniform.value {
+                CustomUniformValue::Color(color) => {
+                    let mut rgba = [color.0, color.1, color.2, color.3];
+                    if ui.color_edit_button_rgba_unmultiplied(&mut rgba).changed() {
+                        *color = (rgba[0], rgba[1], rgba[2], rgba[3]);
+                        changed = true;
+                    }
+                }
+                CustomUniformValue::Float(value) => {
+                    changed |= ui.add(egui::DragValue::new(value).speed(0.01)).changed();
+                }
+                CustomUniformValue::Texture(path) => {
+                    changed |= ui.text_edit_singleline(path).changed();
+                }
+            }
+            let binding = match (&uniform.value, slot) {
+                (_, None) => "no slot left".to_string(),
+                (CustomUniformValue::Color(_), Some(slot)) => format!("colors[{}]", slot),
+                (CustomUniformValue::Float(_), Some(slot)) => format!("custom_float({})", slot),
+                (CustomUniformValue::Texture(_), Some(slot)) => format!("texture_{}", slot),
+            };
+            ui.weak(binding);
+        });
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            if ui.button("❌").on_hover_text("Remove uniform").clicked() {
+                removed = Some(index);
+            }
+        });
+        ui.end_row();
+    }
+    if let Some(index) = removed {
+        def.uniforms.remove(index);
         changed = true;
     }
 
+    ui.label("Add Uniform");
+    ui.horizontal(|ui| {
+        for value in [
+            CustomUniformValue::Color((1.0, 1.0, 1.0, 1.0)),
+            CustomUniformValue::Float(0.0),
+            CustomUniformValue::Texture(String::new()),
+        ] {
+            if ui.small_button(value.kind_name()).clicked() {
+                def.uniforms.push(CustomUniform {
+                    name: value.kind_name().to_lowercase(),
+                    value,
+                });
+                changed = true;
+            }
+        }
+    });
     ui.end_row();
+
+    if clear {
+        log!(
+            LogType::Editor,
+            LogLevel::Info,
+            LogCategory::UI,
+            "User Removed: {:?}",
+            "Custom Shader"
+        );
+        *custom = None;
+        changed = true;
+    }
     changed
 }
 
@@ -764,8 +1344,13 @@ pub fn display_standard_material_field(
     field: &EditableMaterialField,
     def: &mut StandardMaterialDef,
     defaults: &StandardMaterial,
+    uv_preview: &mut bool,
+    texture_picker: &mut TexturePickerData,
 ) -> bool {
     let mut changed = false;
+    let wind_defaults = WindSettings::default();
+    let triplanar_defaults = TriplanarSettings::default();
+    let detail_defaults = DetailSettings::default();
 
     match field {
         EditableMaterialField::BaseColor => {
@@ -779,13 +1364,11 @@ pub fn display_standard_material_field(
         }
 
         EditableMaterialField::BaseColorTexture => {
-            changed |= display_text_field(
+            changed |= display_texture_field(
                 ui,
                 "Base Color Texture",
                 &mut def.base_color_texture,
-                Some(""),
-                true,
-                true,
+                texture_picker,
             );
         }
 
@@ -812,13 +1395,11 @@ pub fn display_standard_material_field(
         }
 
         EditableMaterialField::MetallicRoughnessTexture => {
-            changed |= display_text_field(
+            changed |= display_texture_field(
                 ui,
                 "Metallic Roughness Texture",
                 &mut def.metallic_roughness_texture,
-                Some(""),
-                true,
-                true,
+                texture_picker,
             );
         }
 
@@ -836,13 +1417,11 @@ pub fn display_standard_material_field(
         }
 
         EditableMaterialField::EmissiveTexture => {
-            changed |= display_text_field(
+            changed |= display_texture_field(
                 ui,
                 "Emissive Texture",
                 &mut def.emissive_texture,
-                Some(""),
-                true,
-                true,
+                texture_picker,
             );
         }
 
@@ -862,27 +1441,56 @@ pub fn display_standard_material_field(
         //        display_text_field(ui, "Normal Map", &mut def.normal_map, Some(""), true, true);
         //}
         EditableMaterialField::NormalMapTexture => {
-            changed |= display_text_field(
+            changed |= display_texture_field(
                 ui,
                 "Normal Map Texture",
                 &mut def.normal_map_texture,
-                Some(""),
-                true,
-                true,
+                texture_picker,
             );
         }
 
         EditableMaterialField::OcclusionMap => {
+            changed |=
+                display_texture_field(ui, "Occlusion Map", &mut def.occlusion_map, texture_picker);
+        }
+
+        EditableMaterialField::DepthMap => {
+            changed |= display_texture_field(ui, "Depth Map", &mut def.depth_map, texture_picker);
+        }
+
+        EditableMaterialField::ParallaxDepthScale => {
+            changed |= display_slider_field(
+                ui,
+                "Parallax Depth Scale",
+                &mut def.parallax_depth_scale,
+                0.0,
+                0.5,
+                Some(defaults.parallax_depth_scale),
+            );
+        }
+
+        EditableMaterialField::ParallaxMappingMethod => {
             changed |= display_text_field(
                 ui,
-                "Occlusion Map",
-                &mut def.occlusion_map,
-                Some(""),
-                true,
+                "Parallax Mapping Method",
+                &mut def.parallax_mapping_method,
+                Some("Occlusion"),
+                false,
                 true,
             );
         }
 
+        EditableMaterialField::MaxParallaxLayerCount => {
+            changed |= display_slider_field(
+                ui,
+                "Max Parallax Layer Count",
+                &mut def.max_parallax_layer_count,
+                1.0,
+                64.0,
+                Some(defaults.max_parallax_layer_count),
+            );
+        }
+
         EditableMaterialField::Thickness => {
             changed |= display_drag_field(
                 ui,
@@ -914,6 +1522,32 @@ pub fn display_standard_material_field(
             );
         }
 
+        EditableMaterialField::SpecularTransmission => {
+            changed |= display_slider_field(
+                ui,
+                "Specular Transmission",
+                &mut def.specular_transmission,
+                0.0,
+                1.0,
+                Some(defaults.specular_transmission),
+            );
+        }
+
+        EditableMaterialField::DiffuseTransmission => {
+            changed |= display_slider_field(
+                ui,
+                "Diffuse Transmission",
+                &mut def.diffuse_transmission,
+                0.0,
+                1.0,
+                Some(defaults.diffuse_transmission),
+            );
+        }
+
+        EditableMaterialField::Ior => {
+            changed |= display_slider_field(ui, "IOR", &mut def.ior, 1.0, 3.0, Some(defaults.ior));
+        }
+
         EditableMaterialField::Clearcoat => {
             changed |= display_slider_field(
                 ui,
@@ -1012,7 +1646,116 @@ pub fn display_standard_material_field(
         }
 
         EditableMaterialField::UvTransform => {
-            changed |= display_uv_scale_field(ui, &mut def.uv_transform, Some((1.0, 1.0)));
+            changed |=
+                display_uv_transform_field(ui, &mut def.uv_transform, Some((1.0, 1.0)), uv_preview);
+        }
+
+        EditableMaterialField::WindStrength => {
+            changed |= display_slider_field(
+                ui,
+                "Wind Strength",
+                &mut def.wind_strength,
+                0.0,
+                2.0,
+                Some(wind_defaults.strength),
+            );
+        }
+
+        EditableMaterialField::WindFrequency => {
+            changed |= display_slider_field(
+                ui,
+                "Wind Frequency",
+                &mut def.wind_frequency,
+                0.0,
+                4.0,
+                Some(wind_defaults.frequency),
+            );
+        }
+
+        EditableMaterialField::WindMask => {
+            changed |= display_text_field(
+                ui,
+                "Wind Mask",
+                &mut def.wind_mask,
+                Some("VertexColor"),
+                false,
+                true,
+            );
+        }
+
+        EditableMaterialField::WindMaskHeight => {
+            changed |= display_drag_field(
+                ui,
+                "Wind Mask Height",
+                &mut def.wind_mask_height,
+                Some(wind_defaults.mask_height),
+            );
+        }
+
+        EditableMaterialField::Triplanar => {
+            changed |= display_toggle_field(ui, "Triplanar", &mut def.triplanar, Some(true));
+        }
+
+        EditableMaterialField::TriplanarScale => {
+            changed |= display_drag_field(
+                ui,
+                "Triplanar Scale",
+                &mut def.triplanar_scale,
+                Some(triplanar_defaults.scale),
+            );
+        }
+
+        EditableMaterialField::TriplanarSharpness => {
+            changed |= display_slider_field(
+                ui,
+                "Triplanar Sharpness",
+                &mut def.triplanar_sharpness,
+                1.0,
+                16.0,
+                Some(triplanar_defaults.sharpness),
+            );
+        }
+
+        EditableMaterialField::DetailAlbedoTexture => {
+            changed |= display_texture_field(
+                ui,
+                "Detail Albedo Texture",
+                &mut def.detail_albedo_texture,
+                texture_picker,
+            );
+        }
+
+        EditableMaterialField::DetailNormalTexture => {
+            changed |= display_texture_field(
+                ui,
+                "Detail Normal Texture",
+                &mut def.detail_normal_texture,
+                texture_picker,
+            );
+        }
+
+        EditableMaterialField::DetailTiling => {
+            changed |= display_drag_field(
+                ui,
+                "Detail Tiling",
+                &mut def.detail_tiling,
+                Some(detail_defaults.tiling),
+            );
+        }
+
+        EditableMaterialField::DetailStrength => {
+            changed |= display_slider_field(
+                ui,
+                "Detail Strength",
+                &mut def.detail_strength,
+                0.0,
+                1.0,
+                Some(detail_defaults.strength),
+            );
+        }
+
+        EditableMaterialField::CustomShader => {
+            changed |= display_custom_shader_field(ui, &mut def.custom);
         }
 
         _ => {
@@ -0,0 +1,82 @@
+use crate::interface::tabs::EntityEditorTabData;
+use bevy_egui::egui;
+
+pub fn entity_mesh_stats_widget(ui: &mut egui::Ui, data: &mut EntityEditorTabData) {
+    let large_spacing = crate::UI_CONFIG.large_spacing;
+    let spacing = crate::UI_CONFIG.spacing;
+    // --------------------------------------------------------------------
+    // MESH STATS
+    // --------------------------------------------------------------------
+    let Some(stats) = data.mesh_stats.clone() else {
+        return;
+    };
+    let yes_no = |value: bool| if value { "Yes" } else { "No" };
+
+    ui.group(|ui| {
+        ui.set_min_width(ui.available_width());
+        ui.add_space(large_spacing);
+        ui.horizontal(|ui| {
+            ui.add_space(large_spacing);
+            ui.vertical(|ui| {
+                egui::CollapsingHeader::new("Mesh")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        egui::Grid::new("mesh_stats_grid")
+                            .num_columns(2)
+                            .spacing([large_spacing, spacing])
+                            .striped(true)
+                            .show(ui, |ui| {
+                                ui.label("Vertices:");
+                                ui.label(stats.vertices.to_string());
+                                ui.end_row();
+                                ui.label("Triangles:");
+                                ui.label(stats.triangles.to_string());
+                                ui.end_row();
+                                ui.label("Indexed:");
+                                ui.label(yes_no(stats.indexed));
+                                ui.end_row();
+                                ui.label("Normals:");
+                                ui.label(yes_no(stats.has_normals));
+                                ui.end_row();
+                                ui.label("Tangents:");
+                                ui.label(yes_no(stats.has_tangents));
+                                ui.end_row();
+                                ui.label("UV0 / UV1:");
+                                ui.label(format!(
+                                    "{} / {}",
+                                    yes_no(stats.has_uv0),
+                                    yes_no(stats.has_uv1)
+                                ));
+                                ui.end_row();
+                                ui.label("Vertex Colors:");
+                                ui.label(yes_no(stats.has_colors));
+                                ui.end_row();
+                            });
+
+                        let warnings = stats.warnings();
+                        if !warnings.is_empty() {
+                            ui.add_space(spacing);
+                            for warning in warnings {
+                                ui.colored_label(ui.visuals().warn_fg_color, warning);
+                            }
+                        }
+
+                        ui.add_space(spacing);
+                        let button = ui
+                            .add_enabled(
+                                stats.can_recalculate(),
+                                egui::Button::new("Recalculate Normals/Tangents"),
+                            )
+                            .on_hover_text(
+                                "Rebuilds normals (smooth when indexed) and tangents. OBJ meshes are written back to their file",
+                            );
+                        if button.clicked() {
+                            data.recalculate_normals_requested = true;
+                        }
+                    });
+            });
+            ui.add_space(large_spacing);
+        });
+        ui.add_space(large_spacing);
+    });
+}
@@ -1,11 +1,13 @@
 pub mod component_editor;
 pub mod identity_editor;
 pub mod material_editor;
+pub mod mesh_editor;
 pub mod name_editor;
 pub mod transform_editor;
 
 pub use component_editor::*;
 pub use identity_editor::*;
 pub use material_editor::*;
+pub use mesh_editor::*;
 pub use name_editor::*;
-pub use transform_editor::*;
\ No newline at end of file
+pub use transform_editor::*;
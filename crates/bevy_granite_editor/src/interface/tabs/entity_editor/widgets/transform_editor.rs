@@ -18,6 +18,9 @@ pub struct EntityGlobalTransformData {
     pub euler_radians: Vec3,
     pub last_synced_quat: Quat,
     // Not sure all this is needed for euler stability
+    /// Scale step and always-snap flag, mirrored from GizmoSnap
+    pub scale_snap: f32,
+    pub snap_always: bool,
 }
 
 impl EntityGlobalTransformData {
@@ -68,6 +71,12 @@ fn display_transform_data(ui: &mut egui::Ui, data: &mut EntityEditorTabData) {
     let euler_radians = &mut transform.euler_radians;
     let last_synced_quat = &mut transform.last_synced_quat;
     let gizmo_locked_axis = transform.gizmo_axis;
+    // Scale snaps the same way gizmo drags do, flipped by holding Ctrl
+    let scale_step = if transform.snap_always != ui.input(|i| i.modifiers.ctrl) {
+        transform.scale_snap.max(0.0)
+    } else {
+        0.0
+    };
     let large_spacing = crate::UI_CONFIG.large_spacing;
     let small_spacing = crate::UI_CONFIG.small_spacing;
     let spacing = crate::UI_CONFIG.spacing;
@@ -114,7 +123,7 @@ fn display_transform_data(ui: &mut egui::Ui, data: &mut EntityEditorTabData) {
 
                 // Scale
                 ui.vertical(|ui| {
-                    display_scale_ui(ui, scale, changed, drag_size);
+                    display_scale_ui(ui, scale, scale_step, changed, drag_size);
                 });
                 ui.end_row();
             });
@@ -391,7 +400,13 @@ fn display_position_ui(ui: &mut egui::Ui, pos: &mut Vec3, changed: &mut bool, dr
     });
 }
 
-fn display_scale_ui(ui: &mut egui::Ui, scale: &mut Vec3, changed: &mut bool, drag_size: [f32; 2]) {
+fn display_scale_ui(
+    ui: &mut egui::Ui,
+    scale: &mut Vec3,
+    step: f32,
+    changed: &mut bool,
+    drag_size: [f32; 2],
+) {
     let spacing = crate::UI_CONFIG.large_spacing;
     ui.horizontal(|ui| {
         let label_width = (ui.available_width() / 5.) + spacing;
@@ -405,6 +420,7 @@ fn display_scale_ui(ui: &mut egui::Ui, scale: &mut Vec3, changed: &mut bool, dra
             ui.visuals().text_color(),
         );
 
+        let speed = if step > 0.0 { step } else { 0.01 };
         egui::Grid::new("scale_grid")
             .num_columns(4)
             .spacing([1.0, 0.0])
@@ -417,7 +433,7 @@ fn display_scale_ui(ui: &mut egui::Ui, scale: &mut Vec3, changed: &mut bool, dra
                 let x = ui.add_sized(
                     drag_size,
                     egui::DragValue::new(&mut scale_x)
-                        .speed(0.01)
+                        .speed(speed)
                         .fixed_decimals(2),
                 );
                 x.context_menu(|ui| {
@@ -432,7 +448,7 @@ fn display_scale_ui(ui: &mut egui::Ui, scale: &mut Vec3, changed: &mut bool, dra
                 let y = ui.add_sized(
                     drag_size,
                     egui::DragValue::new(&mut scale_y)
-                        .speed(0.01)
+                        .speed(speed)
                         .fixed_decimals(2),
                 );
                 y.context_menu(|ui| {
@@ -447,7 +463,7 @@ fn display_scale_ui(ui: &mut egui::Ui, scale: &mut Vec3, changed: &mut bool, dra
                 let z = ui.add_sized(
                     drag_size,
                     egui::DragValue::new(&mut scale_z)
-                        .speed(0.01)
+                        .speed(speed)
                         .fixed_decimals(2),
                 );
                 z.context_menu(|ui| {
@@ -470,6 +486,9 @@ fn display_scale_ui(ui: &mut egui::Ui, scale: &mut Vec3, changed: &mut bool, dra
                     scale.x = scale_x;
                     scale.y = scale_y;
                     scale.z = scale_z;
+                    if step > 0.0 {
+                        *scale = (*scale / step).round() * step;
+                    }
                     *changed = true;
                 }
             });
@@ -1,24 +1,74 @@
+use super::ui::{
+    push_event_feedback, EventFeedback, UICallableEventFeedback, EVENT_REGISTRY,
+    EVENT_REQUEST_QUEUE,
+};
 use bevy::prelude::*;
-use super::ui::{EVENT_REQUEST_QUEUE, EVENT_REGISTRY};
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
 
 pub fn send_queued_events_system(world: &mut World) {
     let mut queue = EVENT_REQUEST_QUEUE.lock().unwrap();
-    
+
     if !queue.is_empty() {
         let registry = EVENT_REGISTRY.lock().unwrap();
-        
+
         for request in queue.drain(..) {
             for event_info in registry.iter() {
                 if event_info.struct_name == request.struct_name {
-                    if let Some(index) = event_info.event_names.iter().position(|&name| name == request.event_name) {
-                        if let Some(sender) = event_info.event_senders.get(index) {
-                            sender(world);
-                            println!("Successfully sent event: {}", request.event_name);
-                            break;
-                        }
+                    if let Some(index) = event_info
+                        .event_names
+                        .iter()
+                        .position(|&name| name == request.event_name)
+                    {
+                        let sent = match (&request.args, event_info.event_args.get(index)) {
+                            (Some(values), Some(Some(args))) => (args.send)(world, values.as_ref()),
+                            _ => match event_info.event_senders.get(index) {
+                                Some(sender) => {
+                                    sender(world);
+                                    true
+                                }
+                                None => false,
+                            },
+                        };
+
+                        let message = if sent {
+                            format!("Sent {}", request.event_name)
+                        } else {
+                            format!("Failed to build arguments for {}", request.event_name)
+                        };
+                        log!(
+                            LogType::Editor,
+                            if sent {
+                                LogLevel::Info
+                            } else {
+                                LogLevel::Error
+                            },
+                            LogCategory::UI,
+                            "{}",
+                            message
+                        );
+                        push_event_feedback(EventFeedback {
+                            event_name: request.event_name.clone(),
+                            message,
+                            success: sent,
+                        });
+                        break;
                     }
                 }
             }
         }
     }
 }
+
+/// Collects results reported by game code for UI fired events
+pub fn collect_event_feedback_system(mut feedback_reader: MessageReader<UICallableEventFeedback>) {
+    for feedback in feedback_reader.read() {
+        push_event_feedback(EventFeedback {
+            event_name: feedback.event_name.clone(),
+            message: feedback.message.clone(),
+            success: feedback.success,
+        });
+    }
+}
@@ -1,16 +1,83 @@
-use bevy::prelude::*;
+use bevy::{
+    prelude::*,
+    reflect::{FromReflect, GetTypeRegistration, PartialReflect, TypeRegistry},
+};
 use bevy_egui::egui;
 use std::sync::Mutex;
 
+/// Most recent feedback entries kept for the events tab
+const MAX_EVENT_FEEDBACK: usize = 50;
+
+/// Sends a unit or default valued event
+pub type UICallableEventSender = Box<dyn Fn(&mut World) + Send + Sync>;
+
 pub struct EventInfo {
     pub struct_name: &'static str,
     pub event_names: &'static [&'static str],
-    pub event_senders: Vec<Box<dyn Fn(&mut World) + Send + Sync>>,
+    pub event_senders: Vec<UICallableEventSender>,
+    /// Per event, Some if the event carries arguments edited in the tab before firing
+    pub event_args: Vec<Option<UICallableEventArgs>>,
+}
+
+/// Editable argument values for an event marked with #[args]
+/// Values are drawn via reflection, so the event needs Reflect alongside Message and Default
+pub struct UICallableEventArgs {
+    pub value: Box<dyn PartialReflect>,
+    pub type_registry: TypeRegistry,
+    pub send: fn(&mut World, &dyn PartialReflect) -> bool,
+}
+
+impl UICallableEventArgs {
+    pub fn new<T>() -> Self
+    where
+        T: Message + Reflect + FromReflect + GetTypeRegistration + Default,
+    {
+        let mut type_registry = TypeRegistry::default();
+        bevy_inspector_egui::inspector_egui_impls::register_std_impls(&mut type_registry);
+        bevy_inspector_egui::inspector_egui_impls::register_glam_impls(&mut type_registry);
+        type_registry.register::<T>();
+
+        Self {
+            value: Box::new(T::default()),
+            type_registry,
+            send: send_with_args::<T>,
+        }
+    }
+}
+
+fn send_with_args<T: Message + FromReflect>(world: &mut World, value: &dyn PartialReflect) -> bool {
+    match T::from_reflect(value) {
+        Some(message) => {
+            world.write_message(message);
+            true
+        }
+        None => false,
+    }
 }
 
 pub struct EventRequest {
     pub struct_name: String,
     pub event_name: String,
+    /// Snapshot of the argument form when the event was fired
+    pub args: Option<Box<dyn PartialReflect>>,
+}
+
+/// Result of a fired event, shown under its button
+/// The editor adds one when the event is sent, games can add their own via UICallableEventFeedback
+#[derive(Clone)]
+pub struct EventFeedback {
+    pub event_name: String,
+    pub message: String,
+    pub success: bool,
+}
+
+/// Write this from game code to report how a UI fired event went, i.e. "Spawned 5 enemies"
+/// event_name is the field name in the #[ui_callable_events] struct
+#[derive(Message, Clone)]
+pub struct UICallableEventFeedback {
+    pub event_name: String,
+    pub message: String,
+    pub success: bool,
 }
 
 lazy_static::lazy_static! {
@@ -21,18 +88,42 @@ lazy_static::lazy_static! {
     pub static ref EVENT_REGISTRY: Mutex<Vec<EventInfo>> = Mutex::new(Vec::new());
 }
 
+lazy_static::lazy_static! {
+    pub static ref EVENT_FEEDBACK: Mutex<Vec<EventFeedback>> = Mutex::new(Vec::new());
+}
+
 pub fn register_ui_callable_events_with_senders(
     struct_name: &'static str,
     event_names: &'static [&'static str],
-    event_senders: Vec<Box<dyn Fn(&mut World) + Send + Sync>>,
+    event_senders: Vec<UICallableEventSender>,
+) {
+    let event_args = event_names.iter().map(|_| None).collect();
+    register_ui_callable_events_with_args(struct_name, event_names, event_senders, event_args);
+}
+
+pub fn register_ui_callable_events_with_args(
+    struct_name: &'static str,
+    event_names: &'static [&'static str],
+    event_senders: Vec<UICallableEventSender>,
+    event_args: Vec<Option<UICallableEventArgs>>,
 ) {
     EVENT_REGISTRY.lock().unwrap().push(EventInfo {
         struct_name,
         event_names,
         event_senders,
+        event_args,
     });
 }
 
+pub fn push_event_feedback(feedback: EventFeedback) {
+    let mut all_feedback = EVENT_FEEDBACK.lock().unwrap();
+    all_feedback.push(feedback);
+    if all_feedback.len() > MAX_EVENT_FEEDBACK {
+        let overflow = all_feedback.len() - MAX_EVENT_FEEDBACK;
+        all_feedback.drain(..overflow);
+    }
+}
+
 #[derive(PartialEq, Clone, Default)]
 pub struct EventsTabData {
     pub button_clicked: Option<String>,
@@ -41,29 +132,73 @@ pub struct EventsTabData {
 pub fn events_tab_ui(ui: &mut egui::Ui, data: &mut EventsTabData) {
     let small_spacing = crate::UI_CONFIG.small_spacing;
     let spacing = crate::UI_CONFIG.spacing;
-    let registry = EVENT_REGISTRY.lock().unwrap();
+    let mut registry = EVENT_REGISTRY.lock().unwrap();
+    let feedback = EVENT_FEEDBACK.lock().unwrap().clone();
     if registry.is_empty() {
         ui.label("Events will appear here when structs with #[ui_callable_events] are processed.");
     } else {
-        for event_info in registry.iter() {
+        for event_info in registry.iter_mut() {
+            let struct_name = event_info.struct_name;
             ui.group(|ui| {
-                ui.label(format!("{}:", clean_name(event_info.struct_name)));
+                ui.label(format!("{}:", clean_name(struct_name)));
                 ui.add_space(spacing);
                 ui.set_width(ui.available_width());
-                for event_name in event_info.event_names.iter() {
+                for (index, event_name) in event_info.event_names.iter().enumerate() {
                     let clean_event_name = clean_name(event_name);
-                    if ui.button(&clean_event_name).clicked() {
+                    let args = event_info
+                        .event_args
+                        .get_mut(index)
+                        .and_then(|args| args.as_mut());
+
+                    let fired = match args {
+                        Some(args) => {
+                            let mut fired = None;
+                            ui.collapsing(&clean_event_name, |ui| {
+                                bevy_inspector_egui::reflect_inspector::ui_for_value(
+                                    args.value.as_mut(),
+                                    ui,
+                                    &args.type_registry,
+                                );
+                                ui.add_space(small_spacing);
+                                if ui.button("Send").clicked() {
+                                    fired = Some(Some(args.value.to_dynamic()));
+                                }
+                            });
+                            fired
+                        }
+                        None => ui.button(&clean_event_name).clicked().then_some(None),
+                    };
+
+                    if let Some(args) = fired {
                         EVENT_REQUEST_QUEUE.lock().unwrap().push(EventRequest {
-                            struct_name: event_info.struct_name.to_string(),
+                            struct_name: struct_name.to_string(),
                             event_name: event_name.to_string(),
+                            args,
                         });
                         data.button_clicked = Some(clean_event_name);
                     }
+
+                    if let Some(last) = feedback
+                        .iter()
+                        .rev()
+                        .find(|entry| entry.event_name == *event_name)
+                    {
+                        let color = if last.success {
+                            egui::Color32::LIGHT_GREEN
+                        } else {
+                            egui::Color32::LIGHT_RED
+                        };
+                        ui.colored_label(color, &last.message);
+                    }
                     ui.add_space(small_spacing);
                 }
                 ui.add_space(small_spacing);
             });
         }
+
+        if !feedback.is_empty() && ui.small_button("Clear results").clicked() {
+            EVENT_FEEDBACK.lock().unwrap().clear();
+        }
     }
 }
 
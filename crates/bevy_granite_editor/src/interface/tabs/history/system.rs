@@ -0,0 +1,35 @@
+use crate::{
+    editor_state::EditorHistory,
+    interface::{
+        events::{RequestRedo, RequestUndo},
+        BottomDockState, BottomTab,
+    },
+};
+use bevy::prelude::{MessageWriter, ResMut};
+
+pub fn update_history_tab_system(
+    mut bottom_dock: ResMut<BottomDockState>,
+    mut history: ResMut<EditorHistory>,
+    mut undo_writer: MessageWriter<RequestUndo>,
+    mut redo_writer: MessageWriter<RequestRedo>,
+) {
+    for (_, tab) in bottom_dock.dock_state.iter_all_tabs_mut() {
+        if let BottomTab::History { ref mut data, .. } = tab {
+            for _ in 0..data.undo_requested {
+                undo_writer.write(RequestUndo);
+            }
+            for _ in 0..data.redo_requested {
+                redo_writer.write(RequestRedo);
+            }
+            if data.clear_requested {
+                history.clear();
+            }
+            data.undo_requested = 0;
+            data.redo_requested = 0;
+            data.clear_requested = false;
+
+            data.undo = history.undo_labels();
+            data.redo = history.redo_labels();
+        }
+    }
+}
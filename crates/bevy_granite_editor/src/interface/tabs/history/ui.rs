@@ -0,0 +1,66 @@
+use bevy_egui::egui;
+
+#[derive(Clone, PartialEq, Default)]
+pub struct HistoryTabData {
+    /// Undo step labels, newest first
+    pub undo: Vec<String>,
+    /// Redo step labels, next first
+    pub redo: Vec<String>,
+    pub undo_requested: usize,
+    pub redo_requested: usize,
+    pub clear_requested: bool,
+}
+
+pub fn history_tab_ui(ui: &mut egui::Ui, data: &mut HistoryTabData) {
+    let small_spacing = crate::UI_CONFIG.small_spacing;
+
+    ui.horizontal(|ui| {
+        if ui
+            .add_enabled(!data.undo.is_empty(), egui::Button::new("Undo (Ctrl + Z)"))
+            .clicked()
+        {
+            data.undo_requested = 1;
+        }
+        if ui
+            .add_enabled(
+                !data.redo.is_empty(),
+                egui::Button::new("Redo (Ctrl + Shft + Z)"),
+            )
+            .clicked()
+        {
+            data.redo_requested = 1;
+        }
+        if ui
+            .add_enabled(
+                !data.undo.is_empty() || !data.redo.is_empty(),
+                egui::Button::new("Clear"),
+            )
+            .clicked()
+        {
+            data.clear_requested = true;
+        }
+    });
+    ui.add_space(small_spacing);
+
+    if data.undo.is_empty() && data.redo.is_empty() {
+        ui.weak("Nothing to undo yet");
+        return;
+    }
+
+    // Newest at the top. Clicking a step goes back or forward to right after it
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        for (index, label) in data.redo.iter().enumerate().rev() {
+            if ui
+                .selectable_label(false, egui::RichText::new(label).weak())
+                .clicked()
+            {
+                data.redo_requested = index + 1;
+            }
+        }
+        for (index, label) in data.undo.iter().enumerate() {
+            if ui.selectable_label(index == 0, label).clicked() {
+                data.undo_requested = index;
+            }
+        }
+    });
+}
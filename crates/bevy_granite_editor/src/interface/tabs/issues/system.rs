@@ -0,0 +1,56 @@
+use super::ui::{IssueRow, IssuesTabData};
+use crate::interface::{BottomDockState, BottomTab};
+use bevy::prelude::{Mut, World};
+use bevy_granite_core::{apply_issue_fix, run_scene_validators, SceneIssues, SceneValidators};
+use bevy_granite_gizmos::EntityEvents;
+
+/// Runs validation and quick fixes asked for in the Issues tab, selects clicked entities, then copies the issues in
+pub fn update_issues_tab_system(world: &mut World) {
+    world.resource_scope(|world, mut bottom_dock: Mut<BottomDockState>| {
+        for (_, tab) in bottom_dock.dock_state.iter_all_tabs_mut() {
+            if let BottomTab::Issues { ref mut data } = tab {
+                update_issues_tab(world, data);
+            }
+        }
+    });
+}
+
+fn update_issues_tab(world: &mut World, data: &mut IssuesTabData) {
+    if let Some(target) = data.select.take() {
+        // Despawned since the last validation
+        if world.get_entity(target).is_ok() {
+            world.trigger(EntityEvents::Select {
+                target,
+                additive: false,
+            });
+        }
+    }
+    if let Some(index) = data.fix_requested.take() {
+        apply_issue_fix(world, index);
+    } else if data.validate_requested {
+        run_scene_validators(world);
+    }
+    data.validate_requested = false;
+
+    let validators = world.resource::<SceneValidators>().names();
+    if data.validators != validators {
+        data.validators = validators;
+    }
+    let issues = world.resource::<SceneIssues>();
+    if data.runs == issues.runs {
+        return;
+    }
+    data.runs = issues.runs;
+    data.issues = issues
+        .issues
+        .iter()
+        .map(|issue| IssueRow {
+            severity: issue.severity,
+            validator: issue.validator.clone(),
+            message: issue.message.clone(),
+            scene: issue.scene.clone(),
+            entity: issue.entity,
+            fix: issue.fix.as_ref().map(|fix| fix.label.clone()),
+        })
+        .collect();
+}
@@ -0,0 +1,123 @@
+use bevy::prelude::Entity;
+use bevy_egui::egui;
+use bevy_granite_core::IssueSeverity;
+
+/// Copy of one SceneIssue, in the order of the SceneIssues resource
+#[derive(Clone, PartialEq)]
+pub struct IssueRow {
+    pub severity: IssueSeverity,
+    pub validator: String,
+    pub message: String,
+    pub scene: Option<String>,
+    pub entity: Option<Entity>,
+    /// Label of the quick fix, if the validator offers one
+    pub fix: Option<String>,
+}
+
+#[derive(Clone, PartialEq, Default)]
+pub struct IssuesTabData {
+    pub issues: Vec<IssueRow>,
+    /// Names of the registered validators
+    pub validators: Vec<String>,
+    /// Validations seen, to copy issues only when a new one ran
+    pub runs: u32,
+    pub hide_info: bool,
+    pub validate_requested: bool,
+    /// Index of the issue whose fix was clicked
+    pub fix_requested: Option<usize>,
+    pub select: Option<Entity>,
+}
+
+pub fn issues_tab_ui(ui: &mut egui::Ui, data: &mut IssuesTabData) {
+    let small_spacing = crate::UI_CONFIG.small_spacing;
+
+    ui.horizontal(|ui| {
+        if ui
+            .add_enabled(!data.validators.is_empty(), egui::Button::new("Validate"))
+            .on_hover_text(data.validators.join("\n"))
+            .clicked()
+        {
+            data.validate_requested = true;
+        }
+        ui.checkbox(&mut data.hide_info, "Hide info");
+        ui.separator();
+        for severity in [
+            IssueSeverity::Error,
+            IssueSeverity::Warning,
+            IssueSeverity::Info,
+        ] {
+            let count = data
+                .issues
+                .iter()
+                .filter(|issue| issue.severity == severity)
+                .count();
+            ui.colored_label(
+                severity_color(ui, severity),
+                format!("{} {}", count, severity.label()),
+            );
+        }
+    });
+    ui.add_space(small_spacing);
+
+    if data.validators.is_empty() {
+        ui.weak("No validators registered. Game crates add them with add_scene_validator");
+        return;
+    }
+    if data.runs == 0 {
+        ui.weak("Not validated yet");
+        return;
+    }
+    if data.issues.is_empty() {
+        ui.weak("No issues found");
+        return;
+    }
+
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        egui::Grid::new("scene_issues")
+            .num_columns(5)
+            .striped(true)
+            .show(ui, |ui| {
+                for (index, issue) in data.issues.iter().enumerate() {
+                    if data.hide_info && issue.severity == IssueSeverity::Info {
+                        continue;
+                    }
+                    ui.colored_label(severity_color(ui, issue.severity), issue.severity.label());
+                    ui.label(&issue.validator);
+                    ui.weak(issue.scene.as_deref().unwrap_or("-"));
+                    match issue.entity {
+                        Some(entity) => {
+                            if ui
+                                .link(&issue.message)
+                                .on_hover_text("Select the entity")
+                                .clicked()
+                            {
+                                data.select = Some(entity);
+                            }
+                        }
+                        None => {
+                            ui.label(&issue.message);
+                        }
+                    }
+                    match &issue.fix {
+                        Some(fix) => {
+                            if ui.button("Fix").on_hover_text(fix).clicked() {
+                                data.fix_requested = Some(index);
+                            }
+                        }
+                        None => {
+                            ui.label("");
+                        }
+                    }
+                    ui.end_row();
+                }
+            });
+    });
+}
+
+fn severity_color(ui: &egui::Ui, severity: IssueSeverity) -> egui::Color32 {
+    match severity {
+        IssueSeverity::Error => ui.visuals().error_fg_color,
+        IssueSeverity::Warning => ui.visuals().warn_fg_color,
+        IssueSeverity::Info => ui.visuals().weak_text_color(),
+    }
+}
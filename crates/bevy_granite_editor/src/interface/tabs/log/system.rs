@@ -4,9 +4,13 @@ use crate::interface::panels::bottom_panel::{BottomDockState, BottomTab};
 use bevy::prelude::ResMut;
 use bevy_egui::{egui, EguiContexts};
 use bevy_granite_logging::LOG_BUFFER;
+use std::hash::{DefaultHasher, Hash, Hasher};
 
+/// Reformats the console only when entries come in, the buffer is cleared, or the filter or search changes
 pub fn update_log_tab_system(mut bottom_dock: ResMut<BottomDockState>, mut contexts: EguiContexts) {
-    let log_entries = LOG_BUFFER.lock().unwrap().clone();
+    let Ok(log_entries) = LOG_BUFFER.lock() else {
+        return;
+    };
     let ctx = contexts.ctx_mut().expect("Egui context to exist");
     let style = (*ctx.style()).clone();
 
@@ -19,6 +23,18 @@ pub fn update_log_tab_system(mut bottom_dock: ResMut<BottomDockState>, mut conte
 
     for (_, tab) in bottom_dock.dock_state.iter_all_tabs_mut() {
         if let BottomTab::Log { ref mut data, .. } = tab {
+            let mut hasher = DefaultHasher::new();
+            (
+                log_entries.pushed(),
+                log_entries.len(),
+                &data.filter,
+                &data.search_query,
+            )
+                .hash(&mut hasher);
+            let formatted_for = hasher.finish();
+            if data.formatted_for == Some(formatted_for) {
+                continue;
+            }
             data.formatted_log_cache = filter_and_format_logs_rich(
                 &log_entries,
                 &data.filter,
@@ -26,6 +42,7 @@ pub fn update_log_tab_system(mut bottom_dock: ResMut<BottomDockState>, mut conte
                 monospace_font_id.clone(),
             );
             data.last_log_count = log_entries.len();
+            data.formatted_for = Some(formatted_for);
         }
     }
 }
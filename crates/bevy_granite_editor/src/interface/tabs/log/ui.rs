@@ -1,7 +1,8 @@
 use bevy_egui::egui::{self, Color32, FontId, Margin};
 use bevy_granite_logging::{
+    clear_logs,
     config::{LogCategory, LogLevel, LogType},
-    LogEntry, RgbaColor,
+    LogBuffer, RgbaColor,
 };
 use egui::Frame;
 
@@ -12,7 +13,7 @@ fn to_color32(color: RgbaColor) -> Color32 {
 use egui::{text::LayoutJob, text::TextFormat};
 
 pub fn filter_and_format_logs_rich(
-    entries: &[LogEntry],
+    entries: &LogBuffer,
     filter: &LogFilter,
     search: &str,
     font_id: FontId,
@@ -114,8 +115,12 @@ pub struct LogFilter {
 pub struct LogTabData {
     pub filter: LogFilter,
     pub formatted_log_cache: Vec<LayoutJob>,
+    /// Hash of the entry count, buffer length, filter and search the cache was formatted with
+    pub formatted_for: Option<u64>,
     pub last_log_count: usize,
     pub search_query: String,
+    /// Follow new entries. Scrolling up or unticking it holds the view
+    pub auto_scroll: bool,
 }
 
 impl Default for LogTabData {
@@ -129,8 +134,10 @@ impl Default for LogTabData {
                 enabled_categories: LogCategory::all(),
             },
             formatted_log_cache: Vec::new(),
+            formatted_for: None,
             last_log_count: 0,
             search_query: String::new(),
+            auto_scroll: true,
         }
     }
 }
@@ -158,6 +165,25 @@ pub fn log_tab_ui(ui: &mut egui::Ui, data: &mut LogTabData) {
                         ui.label("Search:");
                         ui.add_space(large_spacing);
                         ui.text_edit_singleline(&mut data.search_query);
+                        ui.add_space(large_spacing);
+                        ui.checkbox(&mut data.auto_scroll, "Auto-scroll");
+                        ui.add_space(small_spacing);
+                        if ui
+                            .button("Clear")
+                            .on_hover_text("Empty the console. Log files keep everything")
+                            .clicked()
+                        {
+                            clear_logs();
+                        }
+                        ui.add_space(large_spacing);
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "{} of {}",
+                                data.formatted_log_cache.len(),
+                                data.last_log_count
+                            ))
+                            .weak(),
+                        );
                     });
                     ui.add_space(spacing);
                     ui.separator();
@@ -170,7 +196,7 @@ pub fn log_tab_ui(ui: &mut egui::Ui, data: &mut LogTabData) {
                         .inner_margin(Margin::same(large_spacing as i8))
                         .show(ui, |ui| {
                             egui::ScrollArea::vertical()
-                                .stick_to_bottom(true)
+                                .stick_to_bottom(data.auto_scroll)
                                 .show(ui, |ui| {
                                     ui.add_space(large_spacing);
                                     ui.set_min_height(ui.available_height());
@@ -262,7 +288,7 @@ pub fn log_tab_ui(ui: &mut egui::Ui, data: &mut LogTabData) {
                         ui.horizontal(|ui| {
                             ui.add(
                                 egui::DragValue::new(&mut data.filter.max_display_logs)
-                                    .range(50..=7_500)
+                                    .range(50..=LogBuffer::capacity())
                                     .speed(50)
                                     .suffix(" log lines"),
                             );
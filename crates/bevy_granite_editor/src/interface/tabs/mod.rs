@@ -2,16 +2,32 @@ pub mod debug;
 pub mod editor_settings;
 pub mod entity_editor;
 pub mod events;
+pub mod history;
+pub mod issues;
 pub mod log;
 pub mod node_tree;
+pub mod resources;
+pub mod trash;
 
-pub use debug::{debug_tab_ui, update_debug_tab_ui_system, DebugTabData};
+pub use debug::{
+    debug_tab_ui, update_camera_debug_system, update_debug_tab_ui_system,
+    update_scene_profile_debug_system, update_state_recorder_debug_system, DebugTabData,
+};
 pub use editor_settings::{update_editor_settings_tab_system, EditorSettingsTabData, SettingsTab};
 pub use entity_editor::{
-    handle_material_deletion_system, update_entity_editor_tab_system, update_entity_with_new_components_system,
+    handle_material_deletion_system, update_entity_editor_tab_system,
+    update_entity_mesh_stats_system, update_entity_with_new_components_system,
     update_entity_with_new_identity_system, update_entity_with_new_transform_system,
-    update_material_handle_system, EntityEditorTabData,
+    update_material_handle_system, update_material_users_system,
+    update_uv_tiling_preview_source_system, EntityEditorTabData,
+};
+pub use events::{
+    collect_event_feedback_system, events_tab_ui, send_queued_events_system, EventsTabData,
+    UICallableEventFeedback,
 };
-pub use events::{events_tab_ui, send_queued_events_system, EventsTabData};
+pub use history::{history_tab_ui, update_history_tab_system, HistoryTabData};
+pub use issues::{issues_tab_ui, update_issues_tab_system, IssuesTabData};
 pub use log::{log_tab_ui, update_log_tab_system, LogTabData};
 pub use node_tree::{update_node_tree_tabs_system, NodeTreeTabData, RequestReparentEntityEvent};
+pub use resources::{resources_tab_ui, update_resources_tab_system, ResourcesTabData};
+pub use trash::{trash_tab_ui, update_trash_tab_system, TrashTabData};
@@ -2,7 +2,12 @@ use super::data::{HierarchyEntry, NodeTreeTabData};
 use crate::interface::tabs::node_tree::data::PendingContextAction;
 use bevy::prelude::Entity;
 use bevy_egui::egui;
+use bevy_granite_core::{
+    absolute_asset_to_rel, JointKind, WaypointLinkMode, PREFAB_FILE_EXTENSION,
+    SCENE_FILE_EXTENSIONS,
+};
 use bevy_granite_logging::{log, LogCategory, LogLevel, LogType};
+use native_dialog::FileDialog;
 
 pub fn show_entity_context_menu(
     _ui: &mut egui::Ui,
@@ -30,11 +35,265 @@ pub fn show_entity_context_menu(
 
             ui.close();
         }
+
+        // Acts on the whole selection when the clicked entity is part of it
+        let in_selection = data.selected_entities.contains(&entity);
+        let entities = if in_selection {
+            data.selected_entities.clone()
+        } else {
+            vec![entity]
+        };
+
+        if ui
+            .button("Duplicate")
+            .on_hover_text("Children are duplicated with their parent")
+            .clicked()
+        {
+            log!(
+                LogType::Editor,
+                LogLevel::Info,
+                LogCategory::UI,
+                "Context menu: Duplicate {} entities",
+                entities.len()
+            );
+            data.pending_context_actions.push(if in_selection {
+                PendingContextAction::DuplicateSelection(false)
+            } else {
+                PendingContextAction::DuplicateEntity(entity)
+            });
+            ui.close();
+        }
+        if in_selection
+            && ui
+                .button("Duplicate into Group")
+                .on_hover_text("Parent the duplicates under a new Empty at their center")
+                .clicked()
+        {
+            log!(
+                LogType::Editor,
+                LogLevel::Info,
+                LogCategory::UI,
+                "Context menu: Duplicate {} entities into a group",
+                entities.len()
+            );
+            data.pending_context_actions
+                .push(PendingContextAction::DuplicateSelection(true));
+            ui.close();
+        }
+        let scenes: Vec<String> = data
+            .hierarchy
+            .iter()
+            .filter(|entry| entry.is_dummy_parent)
+            .map(|entry| entry.name.clone())
+            .collect();
+
+        ui.menu_button("Move to Scene", |ui| {
+            for scene in scenes {
+                if ui.button(&scene).clicked() {
+                    queue_move_to_scene(data, entities.clone(), scene);
+                    ui.close();
+                }
+            }
+
+            ui.separator();
+            if ui.button("New Scene File...").clicked() {
+                if let Ok(Some(path)) = FileDialog::new()
+                    .add_filter("Granite Scene", &SCENE_FILE_EXTENSIONS)
+                    .show_save_single_file()
+                {
+                    let scene = absolute_asset_to_rel(path.display().to_string()).to_string();
+                    queue_move_to_scene(data, entities.clone(), scene);
+                }
+                ui.close();
+            }
+        });
+
+        if entry.entity_type == "Waypoint" {
+            ui.menu_button("Waypoints", |ui| {
+                let can_link = entities.len() > 1;
+                for (label, mode) in [
+                    ("Connect Both Ways", WaypointLinkMode::Bidirectional),
+                    ("Connect One Way", WaypointLinkMode::OneWay),
+                    ("Disconnect", WaypointLinkMode::Disconnect),
+                ] {
+                    if ui.add_enabled(can_link, egui::Button::new(label)).clicked() {
+                        log!(
+                            LogType::Editor,
+                            LogLevel::Info,
+                            LogCategory::UI,
+                            "Context menu: {:?} {} waypoints",
+                            mode,
+                            entities.len()
+                        );
+                        data.pending_context_actions
+                            .push(PendingContextAction::ConnectWaypoints(
+                                entities.clone(),
+                                mode,
+                            ));
+                        ui.close();
+                    }
+                }
+                if !can_link {
+                    ui.label("Select waypoints in the order to link them");
+                }
+            });
+        }
+
+        ui.menu_button("Prefab", |ui| {
+            if ui.button("Save as Prefab...").clicked() {
+                if let Ok(Some(path)) = FileDialog::new()
+                    .add_filter("Granite Prefab", &[PREFAB_FILE_EXTENSION])
+                    .show_save_single_file()
+                {
+                    let path = absolute_asset_to_rel(path.display().to_string()).to_string();
+                    log!(
+                        LogType::Editor,
+                        LogLevel::Info,
+                        LogCategory::UI,
+                        "Context menu: Save '{}' as prefab '{}'",
+                        entry.name,
+                        path
+                    );
+                    data.pending_context_actions
+                        .push(PendingContextAction::CreatePrefab(entity, path));
+                }
+                ui.close();
+            }
+
+            if let Some(prefab) = entry.prefab.as_ref() {
+                ui.separator();
+                if ui
+                    .button("Open Prefab")
+                    .on_hover_text("Edit the prefab file. Saving it updates every instance")
+                    .clicked()
+                {
+                    data.pending_context_actions
+                        .push(PendingContextAction::OpenPrefab(prefab.clone()));
+                    ui.close();
+                }
+                if ui
+                    .button("Revert Overrides")
+                    .on_hover_text("Drop the edits made to this instance")
+                    .clicked()
+                {
+                    data.pending_context_actions
+                        .push(PendingContextAction::RevertPrefab(entity));
+                    ui.close();
+                }
+            }
+        });
+
+        ui.menu_button("Imposter", |ui| {
+            for views in [8, 12, 16] {
+                if ui
+                    .button(format!("Bake {} Views", views))
+                    .on_hover_text(
+                        "Render around the prop into an atlas, drawn as a billboard far away",
+                    )
+                    .clicked()
+                {
+                    log!(
+                        LogType::Editor,
+                        LogLevel::Info,
+                        LogCategory::UI,
+                        "Context menu: Bake {} imposters with {} views",
+                        entities.len(),
+                        views
+                    );
+                    data.pending_context_actions
+                        .push(PendingContextAction::BakeImposters(entities.clone(), views));
+                    ui.close();
+                }
+            }
+        });
+
+        ui.menu_button("Transform", |ui| {
+            if ui
+                .button("Flatten Hierarchy")
+                .on_hover_text(
+                    "Move every child directly under this, removing the groups in between",
+                )
+                .clicked()
+            {
+                log!(
+                    LogType::Editor,
+                    LogLevel::Info,
+                    LogCategory::UI,
+                    "Context menu: Flatten hierarchy of {} entities",
+                    entities.len()
+                );
+                data.pending_context_actions
+                    .push(PendingContextAction::FlattenHierarchy(entities.clone()));
+                ui.close();
+            }
+
+            ui.separator();
+            for (label, scale, rotation) in [
+                ("Apply Scale", true, false),
+                ("Apply Rotation", false, true),
+                ("Apply Rotation & Scale", true, true),
+            ] {
+                if ui
+                    .button(label)
+                    .on_hover_text("Bake into the mesh and children, then reset it on the entity")
+                    .clicked()
+                {
+                    log!(
+                        LogType::Editor,
+                        LogLevel::Info,
+                        LogCategory::UI,
+                        "Context menu: {} of {} entities",
+                        label,
+                        entities.len()
+                    );
+                    data.pending_context_actions
+                        .push(PendingContextAction::ApplyTransform(
+                            entities.clone(),
+                            scale,
+                            rotation,
+                        ));
+                    ui.close();
+                }
+            }
+        });
+
+        if entities.len() > 1 {
+            ui.menu_button("Physics Joint", |ui| {
+                for kind in JointKind::all() {
+                    if ui.button(kind.name()).clicked() {
+                        log!(
+                            LogType::Editor,
+                            LogLevel::Info,
+                            LogCategory::UI,
+                            "Context menu: {} joint between {} entities",
+                            kind.name(),
+                            entities.len()
+                        );
+                        data.pending_context_actions
+                            .push(PendingContextAction::CreateJoint(entities.clone(), kind));
+                        ui.close();
+                    }
+                }
+            });
+        }
     });
 
     menu_shown
 }
 
+fn queue_move_to_scene(data: &mut NodeTreeTabData, entities: Vec<Entity>, scene: String) {
+    log!(
+        LogType::Editor,
+        LogLevel::Info,
+        LogCategory::UI,
+        "Context menu: Move {} entities to scene '{}'",
+        entities.len(),
+        scene
+    );
+    data.pending_context_actions
+        .push(PendingContextAction::MoveToScene(entities, scene));
+}
+
 pub fn show_scene_context_menu(
     _ui: &mut egui::Ui,
     scene_path: &str,
@@ -60,9 +319,7 @@ pub fn show_scene_context_menu(
                 scene_path
             );
             data.pending_context_actions
-                .push(PendingContextAction::SetActiveScene(
-                    scene_path.to_string(),
-                ));
+                .push(PendingContextAction::SetActiveScene(scene_path.to_string()));
 
             ui.close();
         }
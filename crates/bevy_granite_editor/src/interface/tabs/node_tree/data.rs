@@ -2,14 +2,39 @@ use bevy::{
     ecs::message::Message,
     prelude::{Entity, Event},
 };
+use bevy_egui::egui;
+use bevy_granite_core::{JointKind, WaypointLinkMode};
+
+use crate::interface::events::ReparentMode;
 
 /// Pending actions from context menus to be processed by the system
 #[derive(Debug, Clone, PartialEq)]
 pub enum PendingContextAction {
     DeleteEntity(Entity),
+    DuplicateEntity(Entity),
+    /// Duplicate the selection, optionally under a new group
+    DuplicateSelection(bool),
     SetActiveScene(String),
     ReloadScene(String),
     DespawnScene(String),
+    /// Move entities and their children to another scene file
+    MoveToScene(Vec<Entity>, String),
+    /// Link, one way link or unlink waypoints in selection order
+    ConnectWaypoints(Vec<Entity>, WaypointLinkMode),
+    /// Joint each entity to the one before it in selection order
+    CreateJoint(Vec<Entity>, JointKind),
+    /// Save the entity and its children as a prefab file and put an instance in their place
+    CreatePrefab(Entity, String),
+    /// Open the prefab file of an instance for editing
+    OpenPrefab(String),
+    /// Drop the overrides of a prefab instance
+    RevertPrefab(Entity),
+    /// Bake the entities into imposter atlases with this many views
+    BakeImposters(Vec<Entity>, u32),
+    /// Move every editable descendant directly under the entities, removing the groups in between
+    FlattenHierarchy(Vec<Entity>),
+    /// Bake scale and/or rotation into the mesh and children of the entities
+    ApplyTransform(Vec<Entity>, bool, bool),
 }
 
 /// Core data structures for the node tree system
@@ -32,8 +57,10 @@ pub struct NodeTreeTabData {
     pub search_filter: String,
     pub drag_payload: Option<Vec<Entity>>, // Entities being dragged
     pub drop_target: Option<Entity>,       // Entity being dropped onto
+    pub drop_other_mode: bool,             // alt held on drop, reparent with the other ReparentMode
     pub active_scene_file: Option<String>, // Currently active scene file path
     pub pending_context_actions: Vec<PendingContextAction>, // Actions from context menus
+    pub dirty_scenes: Vec<String>,         // Scene files with unsaved changes
 
     // Virtual scrolling fields
     pub virtual_scroll_state: VirtualScrollState,
@@ -91,6 +118,7 @@ pub struct FlattenedTreeNode {
     pub is_dummy_parent: bool,
     pub is_preserve_disk: bool,
     pub is_preserve_disk_transform: bool,
+    pub icon: Option<egui::TextureId>,
 }
 
 impl Default for NodeTreeTabData {
@@ -113,8 +141,10 @@ impl Default for NodeTreeTabData {
             search_filter: String::new(),
             drag_payload: None,
             drop_target: None,
+            drop_other_mode: false,
             active_scene_file: None,
             pending_context_actions: Vec::new(),
+            dirty_scenes: Vec::new(),
             virtual_scroll_state: VirtualScrollState::default(),
             flattened_tree_cache: Vec::new(),
             tree_cache_dirty: true,
@@ -132,6 +162,10 @@ pub struct HierarchyEntry {
     pub is_dummy_parent: bool,
     pub is_preserve_disk: bool,
     pub is_preserve_disk_transform: bool,
+    /// Class icon shown next to the type column
+    pub icon: Option<egui::TextureId>,
+    /// Prefab file, for prefab instance roots
+    pub prefab: Option<String>,
 }
 
 /// Events for node tree operations
@@ -139,6 +173,8 @@ pub struct HierarchyEntry {
 pub struct RequestReparentEntityEvent {
     pub entities: Vec<Entity>,
     pub new_parent: Entity,
+    /// None uses the editor setting
+    pub mode: Option<ReparentMode>,
 }
 
 /// Visual state for rendering a single tree row
@@ -155,6 +191,7 @@ pub struct RowVisualState {
     pub is_expanded: bool,
     pub has_children: bool,
     pub is_active_scene: bool,
+    pub is_dirty_scene: bool,
 }
 
 impl RowVisualState {
@@ -204,6 +241,7 @@ impl RowVisualState {
             is_expanded: entry.is_expanded,
             has_children,
             is_active_scene,
+            is_dirty_scene: entry.is_dummy_parent && data.dirty_scenes.contains(&entry.name),
         }
     }
 
@@ -249,6 +287,7 @@ impl RowVisualState {
             is_expanded: node.is_expanded,
             has_children: node.has_children,
             is_active_scene,
+            is_dirty_scene: node.is_dummy_parent && data.dirty_scenes.contains(&node.name),
         }
     }
 }
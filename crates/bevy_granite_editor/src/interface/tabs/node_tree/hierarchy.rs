@@ -1,6 +1,9 @@
 use super::data::{HierarchyEntry, NodeTreeTabData};
 use bevy::prelude::{ChildOf, Entity, Name};
-use bevy_granite_core::{GraniteType, IdentityData, SaveSettings, SpawnSource};
+use bevy_egui::egui;
+use bevy_granite_core::{
+    GraniteClassRegistry, GraniteTypes, IdentityData, SaveSettings, SpawnSource,
+};
 use bevy_granite_logging::{log, LogCategory, LogLevel, LogType};
 use std::collections::HashMap;
 
@@ -59,6 +62,9 @@ pub fn update_hierarchy_data<'a>(
         ),
     >,
     hierarchy_changed: bool,
+    class_registry: &GraniteClassRegistry,
+    icon_for: &mut impl FnMut(&GraniteTypes) -> Option<egui::TextureId>,
+    prefab_for: &impl Fn(Entity) -> Option<String>,
 ) {
     if hierarchy_changed {
         log!(
@@ -90,17 +96,23 @@ pub fn update_hierarchy_data<'a>(
             )
         });
 
+        // Prefab instance roots show as prefabs, whatever class carries them
+        let prefab = prefab_for(entity);
         let entry = HierarchyEntry {
             entity,
             name: name.to_string(),
-            entity_type: identity
-                .map(|id| id.class.type_abv())
-                .unwrap_or_else(|| "Unknown".to_string()),
+            entity_type: match (&prefab, identity) {
+                (Some(_), _) => "Prefab".to_string(),
+                (None, Some(id)) => class_registry.type_abv(&id.class),
+                (None, None) => "Unknown".to_string(),
+            },
             parent: relation.map(|r| r.parent()),
             is_expanded: existing_expanded.get(&entity).copied().unwrap_or(false),
             is_dummy_parent: false,
             is_preserve_disk,
             is_preserve_disk_transform,
+            icon: identity.and_then(|id| icon_for(&id.class)),
+            prefab,
         };
 
         if let Some(spawn_source) = spawn_source {
@@ -143,6 +155,8 @@ fn create_file_grouped_hierarchy(
                 is_dummy_parent: true,
                 is_preserve_disk: false,
                 is_preserve_disk_transform: false,
+                icon: None,
+                prefab: None,
             };
 
             hierarchy_entries.push(dummy_entry);
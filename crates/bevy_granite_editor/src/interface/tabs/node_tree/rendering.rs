@@ -200,6 +200,7 @@ fn flatten_tree_recursive(
             is_dummy_parent: entry.is_dummy_parent,
             is_preserve_disk: entry.is_preserve_disk,
             is_preserve_disk_transform: entry.is_preserve_disk_transform,
+            icon: entry.icon,
         });
 
         // If expanded and has children, recursively add children
@@ -344,6 +345,10 @@ fn render_virtual_type_column(
         }
 
         ui.label(&node.entity_type);
+        if let Some(icon) = node.icon {
+            let icon_size = ui.text_style_height(&egui::TextStyle::Body);
+            ui.add(egui::Image::new((icon, egui::Vec2::splat(icon_size))));
+        }
     });
 }
 
@@ -645,6 +650,12 @@ pub mod styling {
         name_text: &'a egui::RichText,
         visual_state: &RowVisualState,
     ) -> egui::Button<'a> {
+        // Unsaved scenes get a trailing marker
+        let name_text = &if visual_state.is_dirty_scene {
+            egui::RichText::new(format!("{} *", name_text.text()))
+        } else {
+            name_text.clone()
+        };
         if visual_state.is_active_scene {
             egui::Button::new(
                 name_text
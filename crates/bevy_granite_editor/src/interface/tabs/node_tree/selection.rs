@@ -166,6 +166,7 @@ pub fn handle_drag_drop(
                         entity
                     );
                     data.drop_target = Some(entity);
+                    data.drop_other_mode = response.ctx.input(|i| i.modifiers.alt);
                 }
             }
         }
@@ -2,29 +2,53 @@ use super::{
     data::NodeTreeTabData,
     hierarchy::{detect_changes, update_hierarchy_data},
     selection::{
-        handle_external_selection_change, process_selection_changes, update_tree_click_protection,
-        update_scroll_delay, validation::is_valid_drop,
+        handle_external_selection_change, process_selection_changes, update_scroll_delay,
+        update_tree_click_protection, validation::is_valid_drop,
     },
     RequestReparentEntityEvent,
 };
-use crate::interface::events::RequestRemoveParentsFromEntities;
+use crate::interface::events::{
+    ReparentMode, RequestBakeImposters, RequestRemoveParentsFromEntities, RequestSafeDeleteEvent,
+};
 use crate::interface::{SideDockState, SideTab};
 use crate::{
-    editor_state::EditorState,
+    editor_state::{EditorState, PerformanceSafeMode},
     interface::{tabs::node_tree::data::PendingContextAction, EditorEvents, SetActiveWorld},
 };
+use bevy::asset::AssetServer;
 use bevy::ecs::query::Has;
 use bevy::ecs::system::Commands;
 use bevy::{
     ecs::query::{Changed, Or},
-    prelude::{ChildOf, Entity, MessageWriter, Name, Query, Res, ResMut, With, RemovedComponents},
+    prelude::{
+        ChildOf, Entity, Local, MessageWriter, Name, Query, Real, RemovedComponents, Res, ResMut,
+        Time, With,
+    },
 };
+use bevy_egui::{EguiTextureHandle, EguiUserTextures};
 use bevy_granite_core::{
-    IdentityData, RequestDespawnBySource, RequestReloadEvent, SpawnSource, TreeHiddenEntity,
+    DirtyScenes, EditorCapabilities, GraniteClassRegistry, GraniteTypes, IdentityData,
+    PrefabInstance, ReassignEntities, RequestApplyTransformEvent, RequestConnectWaypointsEvent,
+    RequestCreateJointEvent, RequestCreatePrefabEvent, RequestDespawnBySource,
+    RequestFlattenHierarchyEvent, RequestLoadEvent, RequestReassignSpawnSource, RequestReloadEvent,
+    RequestRevertPrefabEvent, SaveSettings, SpawnSource, TreeHiddenEntity,
+};
+use bevy_granite_gizmos::{
+    ActiveSelection, GizmoChildren, GizmoMesh, RequestDuplicateAllSelectionEvent,
+    RequestDuplicateEntityEvent, Selected,
 };
-use bevy_granite_gizmos::{ActiveSelection, GizmoChildren, GizmoMesh, Selected};
 use bevy_granite_logging::{log, LogCategory, LogLevel, LogType};
 
+/// Seconds between tree rebuilds while safe mode throttles the tree
+const SAFE_MODE_TREE_REBUILD_INTERVAL: f32 = 1.0;
+
+/// Hierarchy changes waiting for the next throttled rebuild, and when the tree was last rebuilt
+#[derive(Default)]
+pub struct TreeRebuildThrottle {
+    pending: bool,
+    last_rebuild: f32,
+}
+
 pub fn update_node_tree_tabs_system(
     mut right_dock: ResMut<SideDockState>,
     active_selection: Query<Entity, With<ActiveSelection>>,
@@ -40,23 +64,67 @@ pub fn update_node_tree_tabs_system(
     )>,
     changed_hierarchy: Query<
         (Has<GizmoChildren>, Has<GizmoMesh>, Has<TreeHiddenEntity>),
-        Or<(Changed<Name>, Changed<IdentityData>, Changed<SpawnSource>, Changed<ChildOf>)>,
+        Or<(
+            Changed<Name>,
+            Changed<IdentityData>,
+            Changed<SpawnSource>,
+            Changed<ChildOf>,
+            Changed<PrefabInstance>,
+        )>,
     >,
+    prefab_query: Query<&PrefabInstance>,
     mut removed_child_of: RemovedComponents<ChildOf>,
     mut commands: Commands,
     mut editor_events: EditorEvents,
     mut reparent_event_writer: MessageWriter<RequestReparentEntityEvent>,
+    classes: (
+        Res<GraniteClassRegistry>,
+        Res<AssetServer>,
+        ResMut<EguiUserTextures>,
+    ),
+    capabilities: Res<EditorCapabilities>,
+    dirty_scenes: Res<DirtyScenes>,
+    throttle: (
+        Res<PerformanceSafeMode>,
+        Res<Time<Real>>,
+        Local<TreeRebuildThrottle>,
+    ),
 ) {
+    let (class_registry, asset_server, mut user_textures) = classes;
+    let (safe_mode, time, mut throttle) = throttle;
+
+    // Safe mode batches hierarchy changes into one rebuild per interval
+    throttle.pending |= !changed_hierarchy.is_empty() || !removed_child_of.is_empty();
+    removed_child_of.clear();
+    let now = time.elapsed_secs();
+    let throttled =
+        safe_mode.tree_throttled() && now - throttle.last_rebuild < SAFE_MODE_TREE_REBUILD_INTERVAL;
+    let has_changes = throttle.pending && !throttled;
+    if has_changes {
+        throttle.pending = false;
+        throttle.last_rebuild = now;
+    }
+
+    let prefab_for = |entity: Entity| {
+        prefab_query
+            .get(entity)
+            .ok()
+            .map(|instance| instance.prefab.clone())
+    };
+    let mut icon_for = |class: &GraniteTypes| {
+        class_registry
+            .icon_handle(class, &asset_server)
+            .map(|handle| user_textures.add_image(EguiTextureHandle::Strong(handle)))
+    };
+
     for (_, tab) in right_dock.dock_state.iter_all_tabs_mut() {
         if let SideTab::NodeTree { ref mut data, .. } = tab {
             let previous_selection = data.active_selection;
             data.active_selection = active_selection.single().ok();
             data.selected_entities = all_selected.iter().collect();
             data.active_scene_file = editor_state.current_file.clone();
+            data.dirty_scenes = dirty_scenes.iter().cloned().collect();
 
-            let has_changes = !changed_hierarchy.is_empty() || !removed_child_of.is_empty();
-            for _ in removed_child_of.read() {}
-            
             if !has_changes && !data.hierarchy.is_empty() {
                 // No changes
             } else {
@@ -72,12 +140,19 @@ pub fn update_node_tree_tabs_system(
                         .map(|(a, b, c, d, e, _)| (a, b, c, d, e))
                         .collect()
                 };
-                
-                let (entities_changed, data_changed, hierarchy_changed) = 
+
+                let (entities_changed, data_changed, hierarchy_changed) =
                     detect_changes(filtered_entities.iter().cloned(), has_changes, data);
 
                 if entities_changed || data_changed || hierarchy_changed {
-                    update_hierarchy_data(data, filtered_entities, hierarchy_changed);
+                    update_hierarchy_data(
+                        data,
+                        filtered_entities,
+                        hierarchy_changed,
+                        &class_registry,
+                        &mut icon_for,
+                        &prefab_for,
+                    );
                     data.tree_cache_dirty = true;
                 }
             }
@@ -90,8 +165,9 @@ pub fn update_node_tree_tabs_system(
                 data,
                 &mut reparent_event_writer,
                 &mut editor_events.remove_parent_entities,
+                editor_state.config.reparent_mode,
             );
-            process_context_actions(data, &mut editor_events, &mut commands);
+            process_context_actions(data, &mut editor_events, &capabilities);
         }
     }
 }
@@ -100,6 +176,7 @@ fn handle_drag_drop_events(
     data: &mut crate::interface::tabs::NodeTreeTabData,
     reparent_event_writer: &mut MessageWriter<RequestReparentEntityEvent>,
     remove_parents_event_writer: &mut MessageWriter<RequestRemoveParentsFromEntities>,
+    default_mode: ReparentMode,
 ) {
     if let Some(dragged_entities) = data.drag_payload.clone() {
         if let Some(drop_target) = data.drop_target {
@@ -122,14 +199,18 @@ fn handle_drag_drop_events(
                     dragged_entities.len(),
                     drop_target
                 );
+                // Alt swaps the configured mode for this drop
+                let mode = data.drop_other_mode.then(|| default_mode.other());
                 reparent_event_writer.write(RequestReparentEntityEvent {
                     entities: dragged_entities,
                     new_parent: drop_target,
+                    mode,
                 });
             }
 
             data.drag_payload = None;
             data.drop_target = None;
+            data.drop_other_mode = false;
         }
     }
 }
@@ -138,12 +219,26 @@ fn handle_drag_drop_events(
 fn process_context_actions(
     data: &mut NodeTreeTabData,
     events: &mut EditorEvents,
-    commands: &mut Commands,
+    capabilities: &EditorCapabilities,
 ) {
     for action in data.pending_context_actions.drain(..) {
         match action {
             PendingContextAction::DeleteEntity(entity) => {
-                commands.entity(entity).try_despawn();
+                if capabilities.can_delete(&format!("{}", entity)) {
+                    events
+                        .safe_delete
+                        .write(RequestSafeDeleteEvent(vec![entity]));
+                }
+            }
+            PendingContextAction::DuplicateEntity(entity) => {
+                events
+                    .duplicate_entity
+                    .write(RequestDuplicateEntityEvent { entity });
+            }
+            PendingContextAction::DuplicateSelection(group) => {
+                events
+                    .duplicate_selection
+                    .write(RequestDuplicateAllSelectionEvent { group });
             }
             PendingContextAction::SetActiveScene(scene_path) => {
                 events.set_active_world.write(SetActiveWorld(scene_path));
@@ -154,7 +249,54 @@ fn process_context_actions(
             PendingContextAction::DespawnScene(scene_path) => {
                 events
                     .despawn_by_source
-                    .write(RequestDespawnBySource(scene_path));
+                    .write(RequestDespawnBySource::new(scene_path));
+            }
+            PendingContextAction::MoveToScene(entities, scene_path) => {
+                events.reassign_source.write(RequestReassignSpawnSource {
+                    entities: ReassignEntities::Entities(entities),
+                    target: scene_path,
+                    save: true,
+                });
+            }
+            PendingContextAction::ConnectWaypoints(entities, mode) => {
+                events
+                    .connect_waypoints
+                    .write(RequestConnectWaypointsEvent { entities, mode });
+            }
+            PendingContextAction::CreateJoint(entities, kind) => {
+                events
+                    .create_joint
+                    .write(RequestCreateJointEvent { entities, kind });
+            }
+            PendingContextAction::CreatePrefab(entity, path) => {
+                events
+                    .create_prefab
+                    .write(RequestCreatePrefabEvent { entity, path });
+            }
+            PendingContextAction::OpenPrefab(path) => {
+                events
+                    .load
+                    .write(RequestLoadEvent(path, SaveSettings::Runtime, None));
+            }
+            PendingContextAction::RevertPrefab(entity) => {
+                events.revert_prefab.write(RequestRevertPrefabEvent(entity));
+            }
+            PendingContextAction::BakeImposters(entities, views) => {
+                events
+                    .bake_imposters
+                    .write(RequestBakeImposters { entities, views });
+            }
+            PendingContextAction::FlattenHierarchy(entities) => {
+                events
+                    .flatten_hierarchy
+                    .write(RequestFlattenHierarchyEvent(entities));
+            }
+            PendingContextAction::ApplyTransform(entities, scale, rotation) => {
+                events.apply_transform.write(RequestApplyTransformEvent {
+                    entities,
+                    scale,
+                    rotation,
+                });
             }
         }
     }
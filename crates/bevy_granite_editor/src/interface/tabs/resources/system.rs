@@ -0,0 +1,119 @@
+use super::ui::{ResourceEntry, ResourcesTabData};
+use crate::interface::{BottomDockState, BottomTab};
+use bevy::{
+    ecs::reflect::ReflectResource,
+    prelude::{AppTypeRegistry, Mut, World},
+};
+use bevy_granite_core::{save_project_resources, ProjectResources};
+use bevy_granite_logging::{
+    config::{LogCategory, LogLevel, LogType},
+    log,
+};
+use std::collections::HashMap;
+
+/// Applies edits and project toggles from the Resources tab, then refreshes it from the world
+/// Only expanded resources are copied, so large engine resources cost nothing until opened
+pub fn update_resources_tab_system(world: &mut World) {
+    world.resource_scope(|world, mut bottom_dock: Mut<BottomDockState>| {
+        for (_, tab) in bottom_dock.dock_state.iter_all_tabs_mut() {
+            if let BottomTab::Resources { ref mut data } = tab {
+                update_resources_tab(world, data);
+            }
+        }
+    });
+}
+
+fn update_resources_tab(world: &mut World, data: &mut ResourcesTabData) {
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    data.type_registry = Some(type_registry.clone());
+    let registry = type_registry.read();
+
+    for entry in data.resources.iter_mut() {
+        if entry.saved_toggled {
+            entry.saved_toggled = false;
+            world
+                .resource_mut::<ProjectResources>()
+                .set_saved(&entry.type_path, entry.saved);
+        }
+        if !entry.edited {
+            continue;
+        }
+        entry.edited = false;
+        let (Some(value), Some(reflect_resource)) = (
+            entry.value.as_ref(),
+            registry
+                .get_with_type_path(&entry.type_path)
+                .and_then(|registration| registration.data::<ReflectResource>()),
+        ) else {
+            continue;
+        };
+        // Removed since the copy was taken
+        if reflect_resource.reflect(&*world).is_ok() {
+            reflect_resource.apply(world, value.as_ref());
+        }
+    }
+
+    if data.save_requested {
+        data.save_requested = false;
+        let path = world.resource::<ProjectResources>().path.clone();
+        data.save_status = Some(match save_project_resources(world) {
+            Ok(count) => {
+                log!(
+                    LogType::Editor,
+                    LogLevel::OK,
+                    LogCategory::System,
+                    "Saved {} resources to {}",
+                    count,
+                    path
+                );
+                (format!("Saved {}", count), true)
+            }
+            Err(e) => {
+                log!(
+                    LogType::Editor,
+                    LogLevel::Error,
+                    LogCategory::System,
+                    "Failed to save resources to {}: {}",
+                    path,
+                    e
+                );
+                (format!("Failed: {}", e), false)
+            }
+        });
+    }
+
+    let project = world.resource::<ProjectResources>();
+    data.project_path = project.path.clone();
+    let mut previous: HashMap<String, ResourceEntry> = data
+        .resources
+        .drain(..)
+        .map(|entry| (entry.type_path.clone(), entry))
+        .collect();
+
+    data.resources = registry
+        .iter()
+        .filter_map(|registration| {
+            let value = registration
+                .data::<ReflectResource>()?
+                .reflect(&*world)
+                .ok()?;
+            let type_path = registration.type_info().type_path();
+            let open = previous.remove(type_path).is_some_and(|entry| entry.open);
+            Some(ResourceEntry {
+                type_path: type_path.to_string(),
+                name: registration
+                    .type_info()
+                    .type_path_table()
+                    .short_path()
+                    .to_string(),
+                engine: type_path.starts_with("bevy"),
+                saved: project.is_saved(type_path),
+                open,
+                value: open.then(|| value.to_dynamic()),
+                edited: false,
+                saved_toggled: false,
+            })
+        })
+        .collect();
+    data.resources.sort_by(|a, b| a.name.cmp(&b.name));
+}
@@ -0,0 +1,162 @@
+use bevy::{prelude::AppTypeRegistry, reflect::PartialReflect};
+use bevy_egui::egui;
+
+pub struct ResourceEntry {
+    pub type_path: String,
+    pub name: String,
+    /// From Bevy, Granite or another bevy_ crate, hidden unless asked for
+    pub engine: bool,
+    /// Kept in the project config file
+    pub saved: bool,
+    pub open: bool,
+    /// Copy of the resource while expanded, edited in place and applied by update_resources_tab_system
+    pub value: Option<Box<dyn PartialReflect>>,
+    pub edited: bool,
+    pub saved_toggled: bool,
+}
+
+impl Clone for ResourceEntry {
+    fn clone(&self) -> Self {
+        Self {
+            type_path: self.type_path.clone(),
+            name: self.name.clone(),
+            engine: self.engine,
+            saved: self.saved,
+            open: self.open,
+            value: self.value.as_ref().map(|value| value.to_dynamic()),
+            edited: self.edited,
+            saved_toggled: self.saved_toggled,
+        }
+    }
+}
+
+impl PartialEq for ResourceEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.type_path == other.type_path && self.saved == other.saved && self.open == other.open
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct ResourcesTabData {
+    /// Reflected resources in the world, sorted by name
+    pub resources: Vec<ResourceEntry>,
+    pub type_registry: Option<AppTypeRegistry>,
+    pub search_filter: String,
+    pub show_engine: bool,
+    /// Project config file, relative to assets
+    pub project_path: String,
+    pub save_requested: bool,
+    /// Result of the last save, and whether it worked
+    pub save_status: Option<(String, bool)>,
+}
+
+impl PartialEq for ResourcesTabData {
+    fn eq(&self, other: &Self) -> bool {
+        self.resources == other.resources
+            && self.search_filter == other.search_filter
+            && self.show_engine == other.show_engine
+            && self.save_status == other.save_status
+    }
+}
+
+pub fn resources_tab_ui(ui: &mut egui::Ui, data: &mut ResourcesTabData) {
+    let small_spacing = crate::UI_CONFIG.small_spacing;
+    let large_spacing = crate::UI_CONFIG.large_spacing;
+
+    ui.horizontal(|ui| {
+        ui.add(
+            egui::TextEdit::singleline(&mut data.search_filter)
+                .desired_width(160.0)
+                .hint_text("Search..."),
+        );
+        ui.checkbox(&mut data.show_engine, "Engine resources")
+            .on_hover_text("Resources from Bevy, Granite and other bevy_ crates");
+        ui.separator();
+        let saved = data.resources.iter().filter(|entry| entry.saved).count();
+        if ui
+            .button(format!("Save to project ({})", saved))
+            .on_hover_text(format!(
+                "Writes the checked resources to {}",
+                data.project_path
+            ))
+            .clicked()
+        {
+            data.save_requested = true;
+        }
+        if let Some((message, success)) = &data.save_status {
+            let color = if *success {
+                egui::Color32::LIGHT_GREEN
+            } else {
+                egui::Color32::LIGHT_RED
+            };
+            ui.colored_label(color, message);
+        }
+    });
+    ui.add_space(small_spacing);
+
+    let Some(type_registry) = data.type_registry.clone() else {
+        ui.label("Resources not loaded yet");
+        return;
+    };
+    let type_registry = type_registry.read();
+    let search = data.search_filter.to_lowercase();
+
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        let mut shown = 0;
+        for entry in data.resources.iter_mut() {
+            if entry.engine && !data.show_engine {
+                continue;
+            }
+            if !search.is_empty() && !entry.type_path.to_lowercase().contains(&search) {
+                continue;
+            }
+            shown += 1;
+
+            ui.horizontal(|ui| {
+                ui.set_width(ui.available_width() - large_spacing);
+                ui.with_layout(egui::Layout::left_to_right(egui::Align::Center), |ui| {
+                    let header = egui::CollapsingHeader::new(&entry.name)
+                        .id_salt(&entry.type_path)
+                        .show_background(false)
+                        .show(ui, |_ui| {});
+                    header.header_response.on_hover_text(&entry.type_path);
+                    entry.open = header.openness > 0.0;
+                });
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui
+                        .checkbox(&mut entry.saved, "Project")
+                        .on_hover_text("Keep in the project config file, loaded on startup")
+                        .changed()
+                    {
+                        entry.saved_toggled = true;
+                    }
+                });
+            });
+
+            if !entry.open {
+                continue;
+            }
+            ui.indent(&entry.type_path, |ui| match entry.value.as_mut() {
+                Some(value) => {
+                    let original_spacing = ui.spacing().item_spacing;
+                    ui.spacing_mut().item_spacing = egui::vec2(10.0, 2.0);
+                    if bevy_inspector_egui::reflect_inspector::ui_for_value(
+                        value.as_mut(),
+                        ui,
+                        &type_registry,
+                    ) {
+                        entry.edited = true;
+                    }
+                    ui.spacing_mut().item_spacing = original_spacing;
+                }
+                None => {
+                    ui.weak("Loading...");
+                }
+            });
+        }
+
+        if shown == 0 {
+            ui.weak("No reflected resources match. Resources need #[derive(Reflect)] and #[reflect(Resource)]");
+        }
+    });
+}
@@ -0,0 +1,33 @@
+use crate::{
+    editor_state::EntityTrash,
+    interface::{
+        events::{RequestEmptyTrash, RequestRestoreFromTrash},
+        BottomDockState, BottomTab,
+    },
+};
+use bevy::prelude::{MessageWriter, Res, ResMut};
+
+pub fn update_trash_tab_system(
+    mut bottom_dock: ResMut<BottomDockState>,
+    trash: Res<EntityTrash>,
+    mut restore_writer: MessageWriter<RequestRestoreFromTrash>,
+    mut empty_writer: MessageWriter<RequestEmptyTrash>,
+) {
+    for (_, tab) in bottom_dock.dock_state.iter_all_tabs_mut() {
+        if let BottomTab::Trash { ref mut data, .. } = tab {
+            if let Some(index) = data.restore_requested.take() {
+                restore_writer.write(RequestRestoreFromTrash(index));
+            }
+            if data.empty_requested {
+                empty_writer.write(RequestEmptyTrash);
+                data.empty_requested = false;
+                data.confirm_empty = false;
+            }
+
+            data.entries = trash
+                .iter()
+                .map(|entry| (entry.label.clone(), entry.entities.len()))
+                .collect();
+        }
+    }
+}
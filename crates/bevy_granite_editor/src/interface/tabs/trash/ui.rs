@@ -0,0 +1,59 @@
+use bevy_egui::egui;
+
+#[derive(Clone, PartialEq, Default)]
+pub struct TrashTabData {
+    /// Trashed deletes as label and entity count, newest first
+    pub entries: Vec<(String, usize)>,
+    pub restore_requested: Option<usize>,
+    /// Empty Trash was clicked once, waiting on the second click
+    pub confirm_empty: bool,
+    pub empty_requested: bool,
+}
+
+pub fn trash_tab_ui(ui: &mut egui::Ui, data: &mut TrashTabData) {
+    let small_spacing = crate::UI_CONFIG.small_spacing;
+
+    ui.horizontal(|ui| {
+        if data.confirm_empty {
+            ui.label("Purge every trashed entity for good?");
+            if ui.button("Empty").clicked() {
+                data.empty_requested = true;
+            }
+            if ui.button("Cancel").clicked() {
+                data.confirm_empty = false;
+            }
+        } else if ui
+            .add_enabled(!data.entries.is_empty(), egui::Button::new("Empty Trash"))
+            .on_hover_text("Saving a scene also purges what was deleted from it")
+            .clicked()
+        {
+            data.confirm_empty = true;
+        }
+    });
+    ui.add_space(small_spacing);
+
+    if data.entries.is_empty() {
+        ui.weak("Nothing deleted");
+        return;
+    }
+
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        egui::Grid::new("trash_grid")
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                for (index, (label, count)) in data.entries.iter().enumerate() {
+                    ui.label(label);
+                    ui.weak(if *count == 1 {
+                        "1 entity".to_string()
+                    } else {
+                        format!("{} entities", count)
+                    });
+                    if ui.button("Restore").clicked() {
+                        data.restore_requested = Some(index);
+                    }
+                    ui.end_row();
+                }
+            });
+    });
+}
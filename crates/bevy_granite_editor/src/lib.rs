@@ -16,14 +16,19 @@ use interface::InterfacePlugin;
 use viewport::ViewportPlugin;
 
 pub use editor_state::{
-    get_interface_config_float, get_interface_config_str, update_editor_config_field, HELP_CONFIG,
-    UI_CONFIG,
+    get_interface_config_float, get_interface_config_str, update_editor_config_field,
+    EditorHistory, EditorMutationEvent, EditorTelemetry, EntityStateCommand, TelemetryEvent,
+    UndoableCommand, HELP_CONFIG, UI_CONFIG,
 };
 pub use entities::get_entity_bounds_or_fallback;
+pub use input::{EditorHotkey, HotkeySettings};
 pub use interface::events::{
-    RequestCameraEntityFrame, RequestEditorToggle, RequestNewParent, RequestRemoveChildren,
-    RequestRemoveParents, RequestToggleCameraSync,
+    ReparentMode, RequestBakeImposters, RequestCameraEntityFrame, RequestCubemapCapture,
+    RequestEditorOverlayToggle, RequestEditorToggle, RequestEmptyTrash, RequestNewParent,
+    RequestRedo, RequestRemoveChildren, RequestRemoveParents, RequestRestoreFromTrash,
+    RequestSafeDeleteEvent, RequestSceneThumbnail, RequestToggleCameraSync, RequestUndo,
 };
+pub use interface::panels::{BottomTabType, EditorTabRestrictions, SideTabType};
 
 pub struct BevyGraniteEditor {
     pub active: bool,
@@ -3,6 +3,10 @@ use bevy::camera::visibility::RenderLayers;
 pub const LAYER_SCENE: usize = 0;
 pub const LAYER_GRID: usize = 13;
 pub const LAYER_GIZMO: usize = 14;
+/// Props being baked into an imposter atlas, only seen by the bake cameras
+pub const LAYER_IMPOSTER_BAKE: usize = 15;
+/// Material preview spheres, only seen by their own preview cameras
+pub const LAYER_MATERIAL_PREVIEW: usize = 16;
 pub const LAYER_UI: usize = 31;
 
 pub fn scene_layers() -> RenderLayers {
@@ -16,8 +16,8 @@ use bevy::{
     input::mouse::{MouseMotion, MouseWheel},
     mesh::{Mesh, Mesh3d},
     prelude::{
-        Local, MessageReader, Query, Res, ResMut, Resource, Time, Transform, UVec2, Vec2, Vec3,
-        Window, With, Without,
+        Local, MessageReader, Query, Real, Res, ResMut, Resource, Time, Transform, UVec2, Vec2,
+        Vec3, Window, With, Without,
     },
     transform::components::GlobalTransform,
     window::{CursorGrabMode, CursorOptions, PrimaryWindow},
@@ -273,7 +273,10 @@ pub fn handle_viewport_camera_override_requests(
         &mut Transform,
         (With<Camera3d>, Without<UICamera>, Without<GizmoCamera>),
     >,
-    camera_meta_query: Query<(&Camera, &RenderTarget), (With<Camera3d>, Without<UICamera>, Without<GizmoCamera>)>,
+    camera_meta_query: Query<
+        (&Camera, &RenderTarget),
+        (With<Camera3d>, Without<UICamera>, Without<GizmoCamera>),
+    >,
     render_layers_query: Query<
         &RenderLayers,
         (With<Camera3d>, Without<UICamera>, Without<GizmoCamera>),
@@ -405,7 +408,9 @@ pub fn update_viewport_camera_viewports_system(
     primary_window_query: Query<&Window, With<PrimaryWindow>>,
 ) {
     let Ok(ctx) = contexts.ctx_mut() else { return };
-    let Ok(primary_window) = primary_window_query.single() else { return };
+    let Ok(primary_window) = primary_window_query.single() else {
+        return;
+    };
 
     let surface_width = primary_window.resolution.physical_width() as f32;
     let surface_height = primary_window.resolution.physical_height() as f32;
@@ -664,7 +669,7 @@ pub fn mouse_button_iter(
     mut mouse_wheel_events: MessageReader<MouseWheel>,
     mut query: Query<&mut Transform, With<UICamera>>,
     mut input_state: ResMut<InputState>,
-    time: Res<Time>,
+    time: Res<Time<Real>>,
     mut target_pos: ResMut<CameraTarget>,
     user_input: Res<UserInput>,
     movement_speed: Local<f32>,
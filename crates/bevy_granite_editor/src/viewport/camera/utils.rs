@@ -9,8 +9,8 @@ use bevy::{
     camera::Camera3d,
     input::mouse::{MouseMotion, MouseWheel},
     prelude::{
-        Camera, Commands, EulerRot, Local, MessageReader, Name, Quat, Query, Res, ResMut, Time,
-        Transform, Vec2, Vec3, With,
+        Camera, Commands, EulerRot, Local, MessageReader, Name, Quat, Query, Real, Res, ResMut,
+        Time, Transform, Vec2, Vec3, With,
     },
 };
 use bevy::{core_pipeline::tonemapping::Tonemapping, picking::Pickable};
@@ -118,7 +118,7 @@ pub fn handle_movement(
     mouse_motion_events: &mut MessageReader<MouseMotion>,
     mouse_wheel_events: &mut MessageReader<MouseWheel>,
     _target_pos: &mut ResMut<CameraTarget>,
-    time: Res<Time>,
+    time: Res<Time<Real>>,
     mut movement_speed: Local<f32>,
 ) {
     let delta_time = time.delta_secs();
@@ -1,5 +1,36 @@
 use serde::{Deserialize, Serialize};
 
+/// What the selected meshes show for debugging imports and lightmaps
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum MeshDebugMode {
+    #[default]
+    Off,
+    VertexColors,
+    Uv0Checker,
+    Uv1Checker,
+    Normals,
+    Tangents,
+}
+
+impl MeshDebugMode {
+    pub const ALL: [MeshDebugMode; 6] = [
+        MeshDebugMode::Off,
+        MeshDebugMode::VertexColors,
+        MeshDebugMode::Uv0Checker,
+        MeshDebugMode::Uv1Checker,
+        MeshDebugMode::Normals,
+        MeshDebugMode::Tangents,
+    ];
+
+    /// Modes drawn by overlay copies of the mesh instead of gizmo lines
+    pub fn uses_overlay(&self) -> bool {
+        matches!(
+            self,
+            MeshDebugMode::VertexColors | MeshDebugMode::Uv0Checker | MeshDebugMode::Uv1Checker
+        )
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
 pub struct VisualizationConfig {
     pub selection_enabled: bool,
@@ -12,6 +43,12 @@ pub struct VisualizationConfig {
     pub debug_enabled: bool,
     pub debug_selected_only: bool,
     pub debug_relationship_lines: bool,
+    /// Outline serializable entities no scene camera renders
+    #[serde(default)]
+    pub debug_culled_entities: bool,
+    /// Vertex colors, UV checkerboard, normals or tangents of the selected meshes
+    #[serde(default)]
+    pub debug_mesh_mode: MeshDebugMode,
     pub debug_color: [f32; 3],
     pub debug_line_thickness: f32,
 
@@ -36,6 +73,8 @@ impl Default for VisualizationConfig {
             debug_enabled: true,
             debug_selected_only: true,
             debug_relationship_lines: true,
+            debug_culled_entities: false,
+            debug_mesh_mode: MeshDebugMode::Off,
             debug_color: [0.8, 1.0, 0.0],
             debug_line_thickness: 0.75,
             icons_enabled: true,
@@ -48,3 +87,82 @@ impl Default for VisualizationConfig {
         }
     }
 }
+
+/// Names and class badges drawn above entities in the viewport
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct EntityLabelSettings {
+    pub enabled: bool,
+    pub show_names: bool,
+    /// Class abbreviation in front of the name, i.e. "OBJ" or "PL"
+    pub show_class_badges: bool,
+    /// Entities further from the viewport camera get no label
+    pub max_distance: f32,
+    /// Comma separated class names to label, i.e. "Point Light, Camera". Empty labels every class
+    pub class_filter: String,
+}
+
+impl Default for EntityLabelSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            show_names: true,
+            show_class_badges: true,
+            max_distance: 30.,
+            class_filter: String::new(),
+        }
+    }
+}
+
+impl EntityLabelSettings {
+    /// Whether the filter lets a class through, matched case insensitively on part of its name
+    pub fn shows_class(&self, type_name: &str) -> bool {
+        let type_name = type_name.to_lowercase();
+        let mut filters = self
+            .class_filter
+            .split(',')
+            .map(|filter| filter.trim().to_lowercase())
+            .filter(|filter| !filter.is_empty())
+            .peekable();
+        filters.peek().is_none() || filters.any(|filter| type_name.contains(&filter))
+    }
+}
+
+/// Gizmo snap increments, applied while Ctrl is held or always when `always` is set
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub struct SnapSettings {
+    /// Grid size for global moves, step for local moves
+    pub translate: f32,
+    /// Degrees
+    pub rotate: f32,
+    pub scale: f32,
+    pub always: bool,
+}
+
+impl Default for SnapSettings {
+    fn default() -> Self {
+        Self {
+            translate: 0.5,
+            rotate: 15.0,
+            scale: 0.1,
+            always: false,
+        }
+    }
+}
+
+/// How gizmo handles are grabbed, see GizmoPickSettings
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub struct GizmoPickingSettings {
+    /// Pick handles on screen by priority instead of by their meshes
+    pub screen_space: bool,
+    /// Pixels around a handle that still grab it
+    pub radius: f32,
+}
+
+impl Default for GizmoPickingSettings {
+    fn default() -> Self {
+        Self {
+            screen_space: true,
+            radius: 8.0,
+        }
+    }
+}
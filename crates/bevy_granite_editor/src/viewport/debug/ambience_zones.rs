@@ -0,0 +1,53 @@
+use super::DebugRenderer;
+use crate::editor_state::EditorState;
+use bevy::{
+    color::{Alpha, Color},
+    gizmos::gizmos::Gizmos,
+    prelude::{Isometry3d, Res, Transform},
+};
+use bevy_granite_core::{AmbienceZoneShape, AmbienceZones};
+
+/// Shape of every ambience zone with its blend distance around it, brighter while the listener hears it
+pub fn show_ambience_zones_system(
+    mut gizmos: Gizmos<DebugRenderer>,
+    ambience_zones: Res<AmbienceZones>,
+    editor_state: Res<EditorState>,
+) {
+    if !editor_state.active {
+        return;
+    }
+    let config = editor_state.config.viewport.visualizers;
+    if !config.debug_enabled {
+        return;
+    }
+
+    let base = Color::srgb_from_array(config.debug_color);
+    for state in ambience_zones.iter() {
+        let zone = &state.zone;
+        let color = base.with_alpha(0.4 + 0.6 * state.weight);
+        let blend_color = base.with_alpha(0.15 + 0.25 * state.weight);
+        let (_, rotation, translation) = state.transform.to_scale_rotation_translation();
+        let isometry = Isometry3d::new(translation, rotation);
+
+        match zone.shape {
+            AmbienceZoneShape::Sphere => {
+                gizmos.sphere(isometry, zone.radius, color);
+                if zone.blend_distance > 0.0 {
+                    gizmos.sphere(isometry, zone.radius + zone.blend_distance, blend_color);
+                }
+            }
+            AmbienceZoneShape::Box => {
+                let cube = |half_extents| Transform {
+                    translation,
+                    rotation,
+                    scale: half_extents * 2.0,
+                };
+                gizmos.cube(cube(zone.half_extents), color);
+                // Corners of the real blend edge are rounded, the box around it is close enough
+                if zone.blend_distance > 0.0 {
+                    gizmos.cube(cube(zone.half_extents + zone.blend_distance), blend_color);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use super::DebugRenderer;
+use crate::editor_state::EditorState;
+use bevy::{
+    color::{Alpha, Color},
+    gizmos::gizmos::Gizmos,
+    prelude::{Isometry3d, Res},
+};
+use bevy_granite_core::AudioEmitters;
+
+/// Min and max distance of every audio emitter, brighter while its preview plays and isn't muted
+pub fn show_audio_emitters_system(
+    mut gizmos: Gizmos<DebugRenderer>,
+    audio_emitters: Res<AudioEmitters>,
+    editor_state: Res<EditorState>,
+) {
+    if !editor_state.active {
+        return;
+    }
+    let config = editor_state.config.viewport.visualizers;
+    if !config.debug_enabled {
+        return;
+    }
+
+    let base = Color::srgb_from_array(config.debug_color);
+    for state in audio_emitters.iter() {
+        let emitter = &state.emitter;
+        let strength = if state.previewing() { 1.0 } else { 0.0 };
+        let isometry = Isometry3d::from_translation(state.position);
+        if emitter.min_distance > 0.0 {
+            gizmos.sphere(
+                isometry,
+                emitter.min_distance,
+                base.with_alpha(0.4 + 0.6 * strength),
+            );
+        }
+        gizmos.sphere(
+            isometry,
+            emitter.max_distance,
+            base.with_alpha(0.15 + 0.25 * strength),
+        );
+    }
+}
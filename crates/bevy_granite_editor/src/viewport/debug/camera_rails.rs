@@ -0,0 +1,82 @@
+use super::DebugRenderer;
+use crate::editor_state::EditorState;
+use bevy::{
+    color::{Alpha, Color},
+    ecs::system::Query,
+    gizmos::gizmos::Gizmos,
+    prelude::{Isometry3d, Res, Time, Transform},
+    transform::components::GlobalTransform,
+};
+use bevy_granite_core::{GraniteTypes, IdentityData};
+
+/// Seconds the preview dolly rests on the last point before starting over
+const PREVIEW_HOLD: f32 = 1.0;
+
+/// Path of every camera rail with lines to its look at targets, and a dolly travelling it at the rail's speed and easing
+pub fn show_camera_rails_system(
+    mut gizmos: Gizmos<DebugRenderer>,
+    query: Query<(&IdentityData, &GlobalTransform)>,
+    time: Res<Time>,
+    editor_state: Res<EditorState>,
+) {
+    if !editor_state.active {
+        return;
+    }
+    let config = editor_state.config.viewport.visualizers;
+    if !config.debug_enabled {
+        return;
+    }
+
+    let color = Color::srgb_from_array(config.debug_color);
+    let faded = color.with_alpha(0.35);
+    for (identity, transform) in query.iter() {
+        let GraniteTypes::CameraRail(rail) = &identity.class else {
+            continue;
+        };
+        let path = rail.path();
+        let world = transform.affine();
+        gizmos.linestrip(
+            path.positions()
+                .map(|position| world.transform_point3(position)),
+            color,
+        );
+        for point in &rail.points {
+            let position = world.transform_point3(point.position);
+            gizmos.sphere(Isometry3d::from_translation(position), 0.15, color);
+            if let Some(target) = point.look_at {
+                let target = world.transform_point3(target);
+                gizmos.line(position, target, faded);
+                gizmos.sphere(Isometry3d::from_translation(target), 0.1, faded);
+            }
+        }
+
+        let duration = rail.duration(&path);
+        if path.is_empty() || duration <= 0.0 {
+            continue;
+        }
+        let progress = (time.elapsed_secs() % (duration + PREVIEW_HOLD) / duration).min(1.0);
+        draw_dolly(&mut gizmos, rail.pose(&path, transform, progress), color);
+    }
+}
+
+/// Small camera frustum looking down the pose's forward
+fn draw_dolly(gizmos: &mut Gizmos<DebugRenderer>, pose: Transform, color: Color) {
+    let (width, height, depth) = (0.4, 0.25, 0.6);
+    let origin = pose.translation;
+    let forward = pose.forward().as_vec3() * depth;
+    let right = pose.right().as_vec3() * width;
+    let up = pose.up().as_vec3() * height;
+    let corners = [
+        origin + forward + right + up,
+        origin + forward - right + up,
+        origin + forward - right - up,
+        origin + forward + right - up,
+    ];
+    for (index, corner) in corners.iter().enumerate() {
+        gizmos.line(origin, *corner, color);
+        gizmos.line(*corner, corners[(index + 1) % corners.len()], color);
+    }
+    // Marks the top of the view
+    gizmos.line(corners[0], origin + forward + up * 1.6, color);
+    gizmos.line(corners[1], origin + forward + up * 1.6, color);
+}
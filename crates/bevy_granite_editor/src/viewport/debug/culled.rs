@@ -0,0 +1,81 @@
+use super::DebugRenderer;
+use crate::editor_state::EditorState;
+use crate::get_entity_bounds_or_fallback;
+use bevy::{
+    asset::Assets,
+    color::Color,
+    gizmos::gizmos::Gizmos,
+    mesh::{Mesh, Mesh3d},
+    prelude::{Camera, Commands, Entity, GlobalTransform, Local, Query, Res, Transform, With},
+};
+use bevy_granite_core::{IdentityData, VisibleSerializableEntities};
+
+const CULLED_COLOR: Color = Color::srgb(1.0, 0.25, 0.1);
+
+/// Visible entity tracking is opt-in, the overlay turns it on while it's shown
+/// Only removed again if the overlay inserted it, a game may be using it too
+pub fn track_visible_entities_for_culled_system(
+    mut commands: Commands,
+    mut inserted: Local<bool>,
+    visible: Option<Res<VisibleSerializableEntities>>,
+    editor_state: Res<EditorState>,
+) {
+    let config = editor_state.config.viewport.visualizers;
+    let wanted = editor_state.active && config.debug_enabled && config.debug_culled_entities;
+    if wanted && visible.is_none() {
+        commands.init_resource::<VisibleSerializableEntities>();
+        *inserted = true;
+    } else if !wanted && *inserted {
+        commands.remove_resource::<VisibleSerializableEntities>();
+        *inserted = false;
+    }
+}
+
+/// Bounds of every serializable entity no scene camera is rendering, i.e. frustum culled, hidden or on another render layer
+/// Only the cameras saved in the scene count, the editor camera is ignored
+pub fn show_culled_entities_system(
+    mut gizmos: Gizmos<DebugRenderer>,
+    visible: Option<Res<VisibleSerializableEntities>>,
+    scene_cameras: Query<(Entity, &Camera), With<IdentityData>>,
+    entities: Query<(Entity, &GlobalTransform, &IdentityData)>,
+    mesh_query: Query<&Mesh3d>,
+    meshes: Res<Assets<Mesh>>,
+    editor_state: Res<EditorState>,
+) {
+    if !editor_state.active {
+        return;
+    }
+    let config = editor_state.config.viewport.visualizers;
+    if !config.debug_enabled || !config.debug_culled_entities {
+        return;
+    }
+    let Some(visible) = visible else {
+        return;
+    };
+
+    let cameras: Vec<Entity> = scene_cameras
+        .iter()
+        .filter(|(_, camera)| camera.is_active)
+        .map(|(entity, _)| entity)
+        .collect();
+    if cameras.is_empty() {
+        return;
+    }
+
+    for (entity, transform, identity) in entities.iter() {
+        if cameras.contains(&entity)
+            || cameras
+                .iter()
+                .any(|camera| visible.is_visible_to(*camera, entity))
+        {
+            continue;
+        }
+        let Some((min, max)) =
+            get_entity_bounds_or_fallback(entity, identity, &meshes, &mesh_query)
+        else {
+            continue;
+        };
+        let bounds = Transform::from_translation((min + max) * 0.5).with_scale(max - min);
+        gizmos.cube(transform.mul_transform(bounds), CULLED_COLOR);
+    }
+}
@@ -32,7 +32,10 @@ pub fn show_empty_origin_system(
             }
         }
 
-        if matches!(identity_data.class, GraniteTypes::Empty(_)) {
+        if matches!(
+            identity_data.class,
+            GraniteTypes::Empty(_) | GraniteTypes::Anchor(_) | GraniteTypes::InputBinding(_)
+        ) {
             let f_size = 0.7;
 
             let size = Vec3::new(f_size, f_size, f_size);
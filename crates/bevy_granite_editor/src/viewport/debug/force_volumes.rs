@@ -0,0 +1,50 @@
+use super::DebugRenderer;
+use crate::editor_state::EditorState;
+use bevy::{
+    color::Color,
+    gizmos::gizmos::Gizmos,
+    prelude::{Isometry3d, Res, Vec3},
+};
+use bevy_granite_core::ForceVolumes;
+
+/// Radius of every force volume, with arrows showing the force inside it
+pub fn show_force_volumes_system(
+    mut gizmos: Gizmos<DebugRenderer>,
+    force_volumes: Res<ForceVolumes>,
+    editor_state: Res<EditorState>,
+) {
+    if !editor_state.active {
+        return;
+    }
+    let config = editor_state.config.viewport.visualizers;
+    if !config.debug_enabled {
+        return;
+    }
+
+    let color = Color::srgb_from_array(config.debug_color);
+    for (_, volume, transform) in force_volumes.iter() {
+        let center = transform.translation();
+        gizmos.sphere(Isometry3d::from_translation(center), volume.radius, color);
+
+        // Sample halfway to the edge around the volume's own axes
+        let half = volume.radius * 0.5;
+        let samples = [
+            Vec3::ZERO,
+            transform.right().as_vec3() * half,
+            transform.left().as_vec3() * half,
+            transform.forward().as_vec3() * half,
+            transform.back().as_vec3() * half,
+        ];
+        for offset in samples {
+            let position = center + offset;
+            let force = volume.force_at(transform, position);
+            if force == Vec3::ZERO {
+                continue;
+            }
+            let arrow = force.normalize() * volume.radius * 0.25;
+            gizmos
+                .arrow(position, position + arrow, color)
+                .with_tip_length(volume.radius * 0.05);
+        }
+    }
+}
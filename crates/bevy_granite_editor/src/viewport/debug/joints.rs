@@ -0,0 +1,50 @@
+use super::DebugRenderer;
+use crate::editor_state::EditorState;
+use bevy::{
+    color::Color,
+    gizmos::gizmos::Gizmos,
+    prelude::{GlobalTransform, Isometry3d, Query, Res},
+};
+use bevy_granite_core::{GraniteJoint, JointBodies};
+
+/// Anchors of every physics joint, linked to the connected body, with the hinge or slider axis
+pub fn show_joints_system(
+    mut gizmos: Gizmos<DebugRenderer>,
+    joints: Query<(&GlobalTransform, &GraniteJoint, Option<&JointBodies>)>,
+    bodies: Query<&GlobalTransform>,
+    editor_state: Res<EditorState>,
+) {
+    if !editor_state.active {
+        return;
+    }
+    let config = editor_state.config.viewport.visualizers;
+    if !config.debug_enabled {
+        return;
+    }
+
+    let color = Color::srgb_from_array(config.debug_color);
+    for (transform, joint, joint_bodies) in joints.iter() {
+        let anchor = transform.transform_point(joint.anchor);
+        gizmos.sphere(Isometry3d::from_translation(anchor), 0.08, color);
+
+        if let Some(connected) =
+            joint_bodies.and_then(|bodies_of| bodies.get(bodies_of.connected).ok())
+        {
+            let connected_anchor = connected.transform_point(joint.connected_anchor);
+            gizmos.sphere(Isometry3d::from_translation(connected_anchor), 0.05, color);
+            gizmos.line(transform.translation(), anchor, color);
+            gizmos.line(connected.translation(), connected_anchor, color);
+        }
+
+        if joint.kind.uses_axis() {
+            let axis = transform
+                .affine()
+                .transform_vector3(joint.axis)
+                .normalize_or_zero()
+                * 0.5;
+            gizmos
+                .arrow(anchor - axis, anchor + axis, color)
+                .with_tip_length(0.1);
+        }
+    }
+}
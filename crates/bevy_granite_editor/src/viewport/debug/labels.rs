@@ -0,0 +1,143 @@
+use crate::{editor_state::EditorState, viewport::ViewportCameraState};
+use bevy::{
+    camera::{primitives::Aabb, Camera},
+    prelude::{Entity, GlobalTransform, Name, Query, Res, Vec3, With},
+};
+use bevy_egui::{egui, EguiContexts};
+use bevy_granite_core::{GraniteType, IdentityData, TreeHiddenEntity};
+use bevy_granite_gizmos::Selected;
+
+const LABEL_FONT_SIZE: f32 = 11.0;
+// Entities without bounds, i.e. lights and empties, get their label this far above their origin
+const LABEL_FALLBACK_HEIGHT: f32 = 0.25;
+
+/// Names and class badges of entities near the viewport camera, painted over the viewport
+/// Farther labels are painted first so the closest stay readable in crowded scenes
+pub fn show_entity_labels_system(
+    mut contexts: EguiContexts,
+    editor_state: Res<EditorState>,
+    viewport_camera_state: Res<ViewportCameraState>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    entities: Query<(
+        Entity,
+        &GlobalTransform,
+        &IdentityData,
+        Option<&Name>,
+        Option<&Aabb>,
+    )>,
+    hidden_query: Query<(), With<TreeHiddenEntity>>,
+    selected_query: Query<(), With<Selected>>,
+) {
+    if !editor_state.active {
+        return;
+    }
+    let settings = &editor_state.config.viewport.labels;
+    if !settings.enabled || (!settings.show_names && !settings.show_class_badges) {
+        return;
+    }
+    let Some((camera, camera_transform)) = viewport_camera_state
+        .active_camera()
+        .and_then(|entity| cameras.get(entity).ok())
+    else {
+        return;
+    };
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    // The viewport is whatever the editor panels leave free
+    let viewport_rect = ctx.available_rect();
+    if viewport_rect.width() <= 1.0 || viewport_rect.height() <= 1.0 {
+        return;
+    }
+
+    let camera_position = camera_transform.translation();
+    let camera_forward = camera_transform.forward();
+    let mut labels: Vec<(f32, egui::Pos2, String, String, bool)> = Vec::new();
+    for (entity, transform, identity, name, aabb) in entities.iter() {
+        if hidden_query.contains(entity) || !settings.shows_class(&identity.class.type_name()) {
+            continue;
+        }
+        let anchor = match aabb {
+            Some(aabb) => {
+                transform.transform_point(Vec3::from(aabb.center) + Vec3::Y * aabb.half_extents.y)
+            }
+            None => transform.translation() + Vec3::Y * LABEL_FALLBACK_HEIGHT,
+        };
+        let distance = anchor.distance(camera_position);
+        if distance > settings.max_distance
+            || (anchor - camera_position).dot(*camera_forward) <= 0.0
+        {
+            continue;
+        }
+        let Some(ndc) = camera.world_to_ndc(camera_transform, anchor) else {
+            continue;
+        };
+        if ndc.x.abs() > 1.0 || ndc.y.abs() > 1.0 {
+            continue;
+        }
+        let position = egui::pos2(
+            viewport_rect.min.x + (ndc.x + 1.0) * 0.5 * viewport_rect.width(),
+            viewport_rect.min.y + (1.0 - ndc.y) * 0.5 * viewport_rect.height(),
+        );
+        let name = name
+            .map(|name| name.as_str().to_string())
+            .unwrap_or_else(|| identity.name.clone());
+        labels.push((
+            distance,
+            position,
+            name,
+            identity.class.type_abv(),
+            selected_query.contains(entity),
+        ));
+    }
+    if labels.is_empty() {
+        return;
+    }
+    labels.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    let painter = ctx
+        .layer_painter(egui::LayerId::new(
+            egui::Order::Background,
+            egui::Id::new("viewport_entity_labels"),
+        ))
+        .with_clip_rect(viewport_rect);
+    let font = egui::FontId::proportional(LABEL_FONT_SIZE);
+    let padding = egui::vec2(4.0, 2.0);
+    for (_, position, name, badge, selected) in labels {
+        let text_color = if selected {
+            egui::Color32::from_rgb(255, 210, 90)
+        } else {
+            egui::Color32::from_gray(230)
+        };
+        let badge = settings
+            .show_class_badges
+            .then(|| painter.layout_no_wrap(badge, font.clone(), egui::Color32::BLACK));
+        let name = settings
+            .show_names
+            .then(|| painter.layout_no_wrap(name, font.clone(), text_color));
+
+        let badge_width = badge
+            .as_ref()
+            .map_or(0.0, |galley| galley.size().x + padding.x * 2.0);
+        let name_width = name
+            .as_ref()
+            .map_or(0.0, |galley| galley.size().x + padding.x * 2.0);
+        let height = LABEL_FONT_SIZE + padding.y * 2.0;
+        let min = position - egui::vec2((badge_width + name_width) * 0.5, height);
+
+        if let Some(galley) = badge {
+            let rect = egui::Rect::from_min_size(min, egui::vec2(badge_width, height));
+            painter.rect_filled(rect, 3.0, egui::Color32::from_rgb(200, 200, 200));
+            painter.galley(rect.min + padding, galley, egui::Color32::BLACK);
+        }
+        if let Some(galley) = name {
+            let rect = egui::Rect::from_min_size(
+                min + egui::vec2(badge_width, 0.0),
+                egui::vec2(name_width, height),
+            );
+            painter.rect_filled(rect, 3.0, egui::Color32::from_black_alpha(170));
+            painter.galley(rect.min + padding, galley, text_color);
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use super::DebugRenderer;
+use crate::editor_state::EditorState;
+use bevy::{
+    color::{Alpha, Color},
+    gizmos::gizmos::Gizmos,
+    prelude::{GlobalTransform, Query, Res},
+};
+use bevy_granite_core::LightProbeVolume;
+
+/// Box of every light probe, faded while its textures aren't lighting it yet
+pub fn show_light_probes_system(
+    mut gizmos: Gizmos<DebugRenderer>,
+    volumes: Query<(&LightProbeVolume, &GlobalTransform)>,
+    editor_state: Res<EditorState>,
+) {
+    if !editor_state.active {
+        return;
+    }
+    let config = editor_state.config.viewport.visualizers;
+    if !config.debug_enabled {
+        return;
+    }
+
+    let base = Color::srgb_from_array(config.debug_color);
+    for (volume, transform) in volumes.iter() {
+        let color = if volume.is_applied() {
+            base
+        } else {
+            base.with_alpha(0.3)
+        };
+        // Bevy light probes cover a unit cube, the volume transform scales it to the box
+        gizmos.cube(transform.compute_transform(), color);
+    }
+}
@@ -0,0 +1,338 @@
+use super::DebugRenderer;
+use crate::{
+    editor_state::{EditorState, PerformanceSafeMode},
+    viewport::config::MeshDebugMode,
+};
+use bevy::{
+    asset::{Assets, Handle, RenderAssetUsages},
+    camera::primitives::MeshAabb,
+    color::Color,
+    ecs::{
+        component::Component,
+        entity::Entity,
+        hierarchy::Children,
+        query::{With, Without},
+        resource::Resource,
+        system::{Commands, Query, Res, ResMut, SystemParam},
+    },
+    gizmos::gizmos::Gizmos,
+    image::{ImageAddressMode, ImageFilterMode, ImageSampler, ImageSamplerDescriptor},
+    light::NotShadowCaster,
+    math::{Mat3, Vec3},
+    mesh::{Mesh, Mesh3d, MeshVertexAttribute, VertexAttributeValues},
+    pbr::{MeshMaterial3d, StandardMaterial, UvChannel},
+    prelude::{Image, Name, Transform},
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+    transform::components::GlobalTransform,
+};
+use bevy_granite_core::EditorIgnore;
+use bevy_granite_gizmos::Selected;
+use std::collections::HashMap;
+
+// mesh_data.rs
+// Debug views of the selected meshes' data: vertex colors, a UV checkerboard per UV set, normals and tangents
+// Colors and checkers are drawn by overlay copies of the mesh with an unlit debug material, so the entity's own material is never touched
+// Meshes missing the data a mode needs show up magenta
+
+const MISSING_COLOR: Color = Color::srgb(1.0, 0.0, 1.0);
+const NORMAL_COLOR: Color = Color::srgb(0.2, 0.6, 1.0);
+const TANGENT_COLOR: Color = Color::srgb(1.0, 0.2, 0.2);
+const BITANGENT_COLOR: Color = Color::srgb(0.2, 1.0, 0.2);
+
+/// Vertex lines drawn per frame over every selected mesh, meshes above it are sampled
+const MAX_DEBUG_VERTICES: usize = 20_000;
+/// Line length as a fraction of the mesh bounds diagonal
+const LINE_LENGTH_FACTOR: f32 = 0.04;
+/// Draws the overlay over the mesh it copies instead of fighting it for depth
+const OVERLAY_DEPTH_BIAS: f32 = 1000.0;
+
+const CHECKER_SIZE: u32 = 256;
+const CHECKER_CELLS: u32 = 8;
+
+/// Overlay entity per debugged mesh, and the debug materials they share
+#[derive(Resource, Default)]
+pub struct MeshDebugOverlays {
+    overlays: HashMap<Entity, Entity>,
+    materials: Option<MeshDebugMaterials>,
+}
+
+struct MeshDebugMaterials {
+    vertex_colors: Handle<StandardMaterial>,
+    uv_checkers: [Handle<StandardMaterial>; 2],
+    missing: Handle<StandardMaterial>,
+}
+
+impl MeshDebugMaterials {
+    fn new(materials: &mut Assets<StandardMaterial>, images: &mut Assets<Image>) -> Self {
+        let checker = images.add(uv_checker_image());
+        let debug_material = |base_color: Color| StandardMaterial {
+            base_color,
+            unlit: true,
+            depth_bias: OVERLAY_DEPTH_BIAS,
+            ..Default::default()
+        };
+        let uv_checker = |channel: UvChannel| StandardMaterial {
+            base_color_texture: Some(checker.clone()),
+            base_color_channel: channel,
+            ..debug_material(Color::WHITE)
+        };
+        Self {
+            // StandardMaterial multiplies vertex colors into white
+            vertex_colors: materials.add(debug_material(Color::WHITE)),
+            uv_checkers: [
+                materials.add(uv_checker(UvChannel::Uv0)),
+                materials.add(uv_checker(UvChannel::Uv1)),
+            ],
+            missing: materials.add(debug_material(MISSING_COLOR)),
+        }
+    }
+
+    fn for_mode(&self, mode: MeshDebugMode, mesh: &Mesh) -> Handle<StandardMaterial> {
+        let (attribute, material) = match mode {
+            MeshDebugMode::Uv0Checker => (Mesh::ATTRIBUTE_UV_0, &self.uv_checkers[0]),
+            MeshDebugMode::Uv1Checker => (Mesh::ATTRIBUTE_UV_1, &self.uv_checkers[1]),
+            _ => (Mesh::ATTRIBUTE_COLOR, &self.vertex_colors),
+        };
+        if mesh.contains_attribute(attribute) {
+            material.clone()
+        } else {
+            self.missing.clone()
+        }
+    }
+}
+
+/// Checkerboard that brightens red along U and green along V, so flipped or rotated UVs stand out
+fn uv_checker_image() -> Image {
+    let cell = CHECKER_SIZE / CHECKER_CELLS;
+    let mut data = Vec::with_capacity((CHECKER_SIZE * CHECKER_SIZE * 4) as usize);
+    for y in 0..CHECKER_SIZE {
+        for x in 0..CHECKER_SIZE {
+            let dark = (x / cell + y / cell).is_multiple_of(2);
+            let u = x as f32 / CHECKER_SIZE as f32;
+            let v = y as f32 / CHECKER_SIZE as f32;
+            let shade = if dark { 0.35 } else { 1.0 };
+            data.extend_from_slice(&[
+                ((0.3 + 0.7 * u) * shade * 255.0) as u8,
+                ((0.3 + 0.7 * v) * shade * 255.0) as u8,
+                (0.6 * shade * 255.0) as u8,
+                255,
+            ]);
+        }
+    }
+    let mut image = Image::new(
+        Extent3d {
+            width: CHECKER_SIZE,
+            height: CHECKER_SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    image.sampler = ImageSampler::Descriptor(ImageSamplerDescriptor {
+        address_mode_u: ImageAddressMode::Repeat,
+        address_mode_v: ImageAddressMode::Repeat,
+        mag_filter: ImageFilterMode::Nearest,
+        min_filter: ImageFilterMode::Linear,
+        ..Default::default()
+    });
+    image
+}
+
+#[derive(SystemParam)]
+pub struct MeshDebugTargets<'w, 's> {
+    selected: Query<'w, 's, Entity, With<Selected>>,
+    children: Query<'w, 's, &'static Children>,
+    mesh_entities:
+        Query<'w, 's, (&'static Mesh3d, &'static GlobalTransform), Without<MeshDebugOverlay>>,
+    meshes: Res<'w, Assets<Mesh>>,
+}
+
+impl MeshDebugTargets<'_, '_> {
+    /// Selected entities and their descendants that have a mesh
+    pub(crate) fn targets(&self) -> Vec<Entity> {
+        let mut targets = Vec::new();
+        for entity in self.selected.iter() {
+            for target in std::iter::once(entity).chain(self.children.iter_descendants(entity)) {
+                if self.mesh_entities.contains(target) && !targets.contains(&target) {
+                    targets.push(target);
+                }
+            }
+        }
+        targets
+    }
+
+    /// The target's mesh handle, loaded mesh and transform
+    pub(crate) fn get(&self, target: Entity) -> Option<(&Mesh3d, &Mesh, &GlobalTransform)> {
+        let (mesh, transform) = self.mesh_entities.get(target).ok()?;
+        Some((mesh, self.meshes.get(&mesh.0)?, transform))
+    }
+}
+
+/// Marks overlay copies of a debugged mesh
+#[derive(Component)]
+pub struct MeshDebugOverlay;
+
+/// Spawns, follows and removes the overlays of the vertex color and UV checker modes
+pub fn update_mesh_debug_overlays_system(
+    mut commands: Commands,
+    mut overlays: ResMut<MeshDebugOverlays>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+    editor_state: Res<EditorState>,
+    safe_mode: Res<PerformanceSafeMode>,
+    debug_targets: MeshDebugTargets,
+) {
+    let config = editor_state.config.viewport.visualizers;
+    let mode = config.debug_mesh_mode;
+    let active = editor_state.active
+        && config.debug_enabled
+        && mode.uses_overlay()
+        && !safe_mode.visualizers_disabled();
+    let targets = if active {
+        debug_targets.targets()
+    } else {
+        Vec::new()
+    };
+
+    overlays.overlays.retain(|target, overlay| {
+        let keep = targets.contains(target);
+        if !keep {
+            if let Ok(mut overlay) = commands.get_entity(*overlay) {
+                overlay.despawn();
+            }
+        }
+        keep
+    });
+    if targets.is_empty() {
+        return;
+    }
+
+    let overlays = overlays.as_mut();
+    let debug_materials = overlays
+        .materials
+        .get_or_insert_with(|| MeshDebugMaterials::new(&mut materials, &mut images));
+
+    for target in targets {
+        let Some((mesh, mesh_asset, global_transform)) = debug_targets.get(target) else {
+            continue;
+        };
+        let material = debug_materials.for_mode(mode, mesh_asset);
+        // Both transforms, so the overlay doesn't trail a frame behind while dragging
+        let overlay_components = (
+            Mesh3d(mesh.0.clone()),
+            MeshMaterial3d(material),
+            global_transform.compute_transform(),
+            *global_transform,
+        );
+
+        match overlays.overlays.get(&target) {
+            Some(overlay) => {
+                if let Ok(mut overlay) = commands.get_entity(*overlay) {
+                    overlay.insert(overlay_components);
+                }
+            }
+            None => {
+                let overlay = commands
+                    .spawn((
+                        overlay_components,
+                        MeshDebugOverlay,
+                        NotShadowCaster,
+                        Name::new("Mesh Debug Overlay"),
+                        EditorIgnore::SERIALIZE | EditorIgnore::PICKING,
+                    ))
+                    .id();
+                overlays.overlays.insert(target, overlay);
+            }
+        }
+    }
+}
+
+fn vec3_attribute(mesh: &Mesh, attribute: MeshVertexAttribute) -> Option<&[[f32; 3]]> {
+    match mesh.attribute(attribute)? {
+        VertexAttributeValues::Float32x3(values) => Some(values),
+        _ => None,
+    }
+}
+
+/// Normals, or tangents with their bitangents, of the selected meshes as lines
+pub fn show_mesh_vectors_system(
+    mut gizmos: Gizmos<DebugRenderer>,
+    editor_state: Res<EditorState>,
+    debug_targets: MeshDebugTargets,
+) {
+    let config = editor_state.config.viewport.visualizers;
+    let mode = config.debug_mesh_mode;
+    if !editor_state.active
+        || !config.debug_enabled
+        || !matches!(mode, MeshDebugMode::Normals | MeshDebugMode::Tangents)
+    {
+        return;
+    }
+
+    let targets: Vec<(&Mesh, &GlobalTransform)> = debug_targets
+        .targets()
+        .into_iter()
+        .filter_map(|target| {
+            let (_, mesh, transform) = debug_targets.get(target)?;
+            Some((mesh, transform))
+        })
+        .collect();
+    let total: usize = targets.iter().map(|(mesh, _)| mesh.count_vertices()).sum();
+    let stride = total.div_ceil(MAX_DEBUG_VERTICES).max(1);
+
+    for (mesh, transform) in targets {
+        let Some(aabb) = mesh.compute_aabb() else {
+            continue;
+        };
+        let affine = transform.affine();
+        let size = Vec3::from(aabb.half_extents) * 2.0;
+        let bounds = Transform::from_translation(aabb.center.into()).with_scale(size);
+        let length = (size * transform.scale()).length() * LINE_LENGTH_FACTOR;
+        let Some(positions) = vec3_attribute(mesh, Mesh::ATTRIBUTE_POSITION) else {
+            continue;
+        };
+
+        match mode {
+            MeshDebugMode::Normals => {
+                let Some(normals) = vec3_attribute(mesh, Mesh::ATTRIBUTE_NORMAL) else {
+                    gizmos.cube(transform.mul_transform(bounds), MISSING_COLOR);
+                    continue;
+                };
+                // Normals follow the inverse transpose, so non uniform scale doesn't skew them
+                let normal_matrix = Mat3::from(affine.matrix3).inverse().transpose();
+                for (position, normal) in positions.iter().zip(normals).step_by(stride) {
+                    let start = affine.transform_point3(Vec3::from(*position));
+                    let direction = (normal_matrix * Vec3::from(*normal)).normalize_or_zero();
+                    gizmos.line(start, start + direction * length, NORMAL_COLOR);
+                }
+            }
+            _ => {
+                let (Some(normals), Some(VertexAttributeValues::Float32x4(tangents))) = (
+                    vec3_attribute(mesh, Mesh::ATTRIBUTE_NORMAL),
+                    mesh.attribute(Mesh::ATTRIBUTE_TANGENT),
+                ) else {
+                    gizmos.cube(transform.mul_transform(bounds), MISSING_COLOR);
+                    continue;
+                };
+                for ((position, normal), tangent) in
+                    positions.iter().zip(normals).zip(tangents).step_by(stride)
+                {
+                    let start = affine.transform_point3(Vec3::from(*position));
+                    let tangent_direction = Vec3::new(tangent[0], tangent[1], tangent[2]);
+                    let bitangent_direction =
+                        Vec3::from(*normal).cross(tangent_direction) * tangent[3];
+                    let tangent_direction = affine
+                        .transform_vector3(tangent_direction)
+                        .normalize_or_zero();
+                    let bitangent_direction = affine
+                        .transform_vector3(bitangent_direction)
+                        .normalize_or_zero();
+                    gizmos.line(start, start + tangent_direction * length, TANGENT_COLOR);
+                    gizmos.line(start, start + bitangent_direction * length, BITANGENT_COLOR);
+                }
+            }
+        }
+    }
+}
@@ -1,13 +1,24 @@
-pub mod selection;
-pub mod relationships;
-pub mod lights;
+pub mod ambience_zones;
+pub mod audio_emitters;
+pub mod camera_rails;
 pub mod cameras;
+pub mod culled;
 pub mod entities;
+pub mod force_volumes;
+pub mod joints;
+pub mod labels;
+pub mod light_probes;
+pub mod lights;
+pub mod mesh_data;
+pub mod relationships;
+pub mod selection;
+pub mod sockets;
+pub mod splines;
+pub mod state_recorder;
+pub mod uv_tiling;
+pub mod waypoints;
 
-use bevy::{
-    gizmos::config::GizmoConfigGroup,
-    reflect::Reflect,
-};
+use bevy::{gizmos::config::GizmoConfigGroup, reflect::Reflect};
 
 // Renamed from CustomSelectionGizmos to be more descriptive
 #[derive(GizmoConfigGroup, Default, Reflect)]
@@ -18,8 +29,22 @@ pub struct SelectionRenderer;
 pub struct DebugRenderer;
 
 // Re-export all debug visualization functions
-pub use selection::*;
-pub use relationships::*;
-pub use lights::*;
+pub use ambience_zones::*;
+pub use audio_emitters::*;
+pub use camera_rails::*;
 pub use cameras::*;
+pub use culled::*;
 pub use entities::*;
+pub use force_volumes::*;
+pub use joints::*;
+pub use labels::*;
+pub use light_probes::*;
+pub use lights::*;
+pub use mesh_data::*;
+pub use relationships::*;
+pub use selection::*;
+pub use sockets::*;
+pub use splines::*;
+pub use state_recorder::*;
+pub use uv_tiling::*;
+pub use waypoints::*;
@@ -1,4 +1,7 @@
-use crate::{editor_state::EditorState, get_entity_bounds_or_fallback};
+use crate::{
+    editor_state::{EditorState, PerformanceSafeMode},
+    get_entity_bounds_or_fallback,
+};
 
 use super::SelectionRenderer;
 use bevy::{
@@ -6,8 +9,8 @@ use bevy::{
     ecs::{entity::Entity, system::Query},
     gizmos::gizmos::Gizmos,
     math::Vec3,
-    prelude::{Assets, Mesh, Res, With},
     mesh::Mesh3d,
+    prelude::{Assets, Mesh, Res, With},
     transform::components::GlobalTransform,
 };
 use bevy_granite_core::IdentityData;
@@ -54,11 +57,13 @@ pub fn show_selected_entities_bounds_system(
     mut gizmos: Gizmos<SelectionRenderer>,
     meshes: Res<Assets<Mesh>>,
     editor_state: Res<EditorState>,
+    safe_mode: Res<PerformanceSafeMode>,
 ) {
     if should_skip_bounds_rendering(&editor_state) {
         return;
     }
     let config = editor_state.config.viewport.visualizers;
+    let simplified = safe_mode.selection_bounds_simplified();
 
     let selected_entities: Vec<_> = query.iter().collect();
     if selected_entities.is_empty() {
@@ -75,7 +80,10 @@ pub fn show_selected_entities_bounds_system(
     for (entity, global_transform, identity_data) in selected_entities {
         valid_count += 1;
 
-        let (local_min, local_max) = if let Some((min, max)) =
+        let (local_min, local_max) = if simplified {
+            // Entity origins only, mesh bounds of huge selections are too costly in safe mode
+            (Vec3::ZERO, Vec3::ZERO)
+        } else if let Some((min, max)) =
             get_entity_bounds_or_fallback(entity, identity_data, &meshes, &mesh_query)
         {
             (min, max)
@@ -0,0 +1,35 @@
+use super::DebugRenderer;
+use crate::editor_state::EditorState;
+use bevy::{
+    color::Color,
+    gizmos::gizmos::Gizmos,
+    prelude::{GlobalTransform, Query, Res},
+};
+use bevy_granite_core::KitSockets;
+
+/// Arrow out of every kit socket along the direction it connects
+pub fn show_kit_sockets_system(
+    mut gizmos: Gizmos<DebugRenderer>,
+    pieces: Query<(&GlobalTransform, &KitSockets)>,
+    editor_state: Res<EditorState>,
+) {
+    if !editor_state.active {
+        return;
+    }
+    let config = editor_state.config.viewport.visualizers;
+    if !config.debug_enabled {
+        return;
+    }
+
+    let color = Color::srgb_from_array(config.debug_color);
+    for (transform, sockets) in pieces.iter() {
+        let transform = transform.compute_transform();
+        for socket in sockets.sockets.iter() {
+            let socket_world = transform.mul_transform(socket.local_transform());
+            let start = socket_world.translation;
+            gizmos
+                .arrow(start, start + socket_world.forward() * 0.5, color)
+                .with_tip_length(0.15);
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use super::DebugRenderer;
+use crate::editor_state::EditorState;
+use bevy::{
+    color::{Alpha, Color},
+    gizmos::gizmos::Gizmos,
+    prelude::{Isometry3d, Query, Res, With},
+    transform::components::GlobalTransform,
+};
+use bevy_granite_core::{GraniteTypes, IdentityData, Splines};
+use bevy_granite_gizmos::Selected;
+
+/// Path of every Spline3D with its control points, the first point drawn larger to show the direction
+/// Unselected splines are faded so the one being edited stands out
+pub fn show_splines_system(
+    mut gizmos: Gizmos<DebugRenderer>,
+    splines: Res<Splines>,
+    query: Query<(&IdentityData, &GlobalTransform)>,
+    selected_query: Query<(), With<Selected>>,
+    editor_state: Res<EditorState>,
+) {
+    if !editor_state.active {
+        return;
+    }
+    let config = editor_state.config.viewport.visualizers;
+    if !config.debug_enabled {
+        return;
+    }
+
+    let base = Color::srgb_from_array(config.debug_color);
+    for (entity, path) in splines.iter() {
+        let Ok((identity, transform)) = query.get(entity) else {
+            continue;
+        };
+        let GraniteTypes::Spline3D(spline) = &identity.class else {
+            continue;
+        };
+        let color = if selected_query.contains(entity) {
+            base
+        } else {
+            base.with_alpha(0.45)
+        };
+        gizmos.linestrip(path.positions().iter().copied(), color);
+
+        let world = transform.affine();
+        for (index, point) in spline.points.iter().enumerate() {
+            let radius = if index == 0 { 0.15 } else { 0.08 };
+            gizmos.sphere(
+                Isometry3d::from_translation(world.transform_point3(*point)),
+                radius,
+                color,
+            );
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use super::DebugRenderer;
+use crate::editor_state::{EditorState, StateRecorder};
+use bevy::{
+    color::{Alpha, Color},
+    gizmos::gizmos::Gizmos,
+    prelude::{Isometry3d, Res},
+};
+
+/// Path each recorded entity took, fading into the past, and a ghost of it at the frame under review
+pub fn show_recorded_state_system(
+    mut gizmos: Gizmos<DebugRenderer>,
+    recorder: Res<StateRecorder>,
+    editor_state: Res<EditorState>,
+) {
+    if !recorder.trails || recorder.frames.is_empty() {
+        return;
+    }
+
+    let color = Color::srgb_from_array(editor_state.config.viewport.visualizers.debug_color);
+    let count = recorder.frames.len() as f32;
+    for (entity, _) in recorder.tracked.iter() {
+        gizmos.linestrip_gradient(recorder.frames.iter().enumerate().filter_map(
+            |(index, frame)| {
+                let recorded = frame
+                    .entities
+                    .iter()
+                    .find(|recorded| recorded.entity == *entity)?;
+                let fade = 0.15 + 0.85 * (index as f32 + 1.0) / count;
+                Some((recorded.transform.translation, color.with_alpha(fade)))
+            },
+        ));
+    }
+
+    let Some(frame) = recorder.reviewed_frame() else {
+        return;
+    };
+    for recorded in frame.entities.iter() {
+        let transform = recorded.transform;
+        gizmos.axes(transform, 0.5 * transform.scale.max_element().max(1.0));
+        gizmos.sphere(
+            Isometry3d::new(transform.translation, transform.rotation),
+            0.15,
+            color,
+        );
+    }
+}
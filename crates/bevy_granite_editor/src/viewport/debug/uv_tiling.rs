@@ -0,0 +1,159 @@
+use super::{MeshDebugOverlay, MeshDebugTargets};
+use crate::editor_state::EditorState;
+use bevy::{
+    asset::{Assets, Handle, RenderAssetUsages},
+    ecs::{
+        entity::Entity,
+        resource::Resource,
+        system::{Commands, Res, ResMut},
+    },
+    image::{ImageAddressMode, ImageFilterMode, ImageSampler, ImageSamplerDescriptor},
+    light::NotShadowCaster,
+    math::Affine2,
+    mesh::Mesh3d,
+    pbr::{MeshMaterial3d, StandardMaterial},
+    prelude::{AlphaMode, Color, Image, Name},
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+use bevy_granite_core::EditorIgnore;
+use std::collections::HashMap;
+
+// uv_tiling.rs
+// Preview of the UV transform being edited in the material editor
+// The selected meshes get an overlay copy with see through tiles: an outline per texture repeat
+// and a corner marker where each tile starts, so tiling and offset read at a glance
+
+const TILE_SIZE: u32 = 128;
+const BORDER: u32 = 2;
+const MARKER: u32 = 16;
+const OVERLAY_DEPTH_BIAS: f32 = 1000.0;
+
+/// The UV transform previewed on the selected meshes, set while the material editor's preview is on
+#[derive(Resource, Default)]
+pub struct UvTilingPreview {
+    pub uv_transform: Option<Affine2>,
+    overlays: HashMap<Entity, Entity>,
+    material: Option<Handle<StandardMaterial>>,
+    applied: Option<Affine2>,
+}
+
+/// Transparent tile with an outline and a marker in the corner at UV (0, 0)
+fn tile_image() -> Image {
+    let mut data = Vec::with_capacity((TILE_SIZE * TILE_SIZE * 4) as usize);
+    for y in 0..TILE_SIZE {
+        for x in 0..TILE_SIZE {
+            let border =
+                x < BORDER || y < BORDER || x >= TILE_SIZE - BORDER || y >= TILE_SIZE - BORDER;
+            let marker = x < MARKER && y < MARKER;
+            let pixel: [u8; 4] = if border {
+                [255, 170, 0, 255]
+            } else if marker {
+                [255, 170, 0, 140]
+            } else {
+                [0, 0, 0, 0]
+            };
+            data.extend_from_slice(&pixel);
+        }
+    }
+    let mut image = Image::new(
+        Extent3d {
+            width: TILE_SIZE,
+            height: TILE_SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    image.sampler = ImageSampler::Descriptor(ImageSamplerDescriptor {
+        address_mode_u: ImageAddressMode::Repeat,
+        address_mode_v: ImageAddressMode::Repeat,
+        mag_filter: ImageFilterMode::Linear,
+        min_filter: ImageFilterMode::Linear,
+        ..Default::default()
+    });
+    image
+}
+
+/// Spawns, follows and removes the tile overlays, and keeps their UV transform in sync with the editor
+pub fn update_uv_tiling_preview_system(
+    mut commands: Commands,
+    mut preview: ResMut<UvTilingPreview>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+    editor_state: Res<EditorState>,
+    debug_targets: MeshDebugTargets,
+) {
+    let uv_transform = preview.uv_transform.filter(|_| editor_state.active);
+    let targets = if uv_transform.is_some() {
+        debug_targets.targets()
+    } else {
+        Vec::new()
+    };
+
+    let preview = preview.as_mut();
+    preview.overlays.retain(|target, overlay| {
+        let keep = targets.contains(target);
+        if !keep {
+            if let Ok(mut overlay) = commands.get_entity(*overlay) {
+                overlay.despawn();
+            }
+        }
+        keep
+    });
+    let Some(uv_transform) = uv_transform.filter(|_| !targets.is_empty()) else {
+        return;
+    };
+
+    let material = preview
+        .material
+        .get_or_insert_with(|| {
+            materials.add(StandardMaterial {
+                base_color: Color::WHITE,
+                base_color_texture: Some(images.add(tile_image())),
+                alpha_mode: AlphaMode::Blend,
+                unlit: true,
+                depth_bias: OVERLAY_DEPTH_BIAS,
+                ..Default::default()
+            })
+        })
+        .clone();
+    if preview.applied != Some(uv_transform) {
+        if let Some(material) = materials.get_mut(&material) {
+            material.uv_transform = uv_transform;
+        }
+        preview.applied = Some(uv_transform);
+    }
+
+    for target in targets {
+        let Some((mesh, _, global_transform)) = debug_targets.get(target) else {
+            continue;
+        };
+        let overlay_components = (
+            Mesh3d(mesh.0.clone()),
+            MeshMaterial3d(material.clone()),
+            global_transform.compute_transform(),
+            *global_transform,
+        );
+        match preview.overlays.get(&target) {
+            Some(overlay) => {
+                if let Ok(mut overlay) = commands.get_entity(*overlay) {
+                    overlay.insert(overlay_components);
+                }
+            }
+            None => {
+                let overlay = commands
+                    .spawn((
+                        overlay_components,
+                        MeshDebugOverlay,
+                        NotShadowCaster,
+                        Name::new("UV Tiling Preview"),
+                        EditorIgnore::SERIALIZE | EditorIgnore::PICKING,
+                    ))
+                    .id();
+                preview.overlays.insert(target, overlay);
+            }
+        }
+    }
+}
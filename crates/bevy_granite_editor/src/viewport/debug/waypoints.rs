@@ -0,0 +1,40 @@
+use super::DebugRenderer;
+use crate::editor_state::EditorState;
+use bevy::{color::Color, gizmos::gizmos::Gizmos, prelude::Res};
+use bevy_granite_core::WaypointNetworks;
+
+/// Lines between linked waypoints, arrows for one way links
+pub fn show_waypoint_links_system(
+    mut gizmos: Gizmos<DebugRenderer>,
+    networks: Res<WaypointNetworks>,
+    editor_state: Res<EditorState>,
+) {
+    if !editor_state.active {
+        return;
+    }
+    let config = editor_state.config.viewport.visualizers;
+    if !config.debug_enabled {
+        return;
+    }
+
+    let color = Color::srgb_from_array(config.debug_color);
+    for name in networks.names() {
+        let Some(graph) = networks.get(name) else {
+            continue;
+        };
+        for (from, targets) in graph.edges.iter().enumerate() {
+            let from_pos = graph.nodes[from].position;
+            for to in targets.iter().copied() {
+                let to_pos = graph.nodes[to].position;
+                if graph.edges[to].contains(&from) {
+                    // Both ways, draw it once
+                    if from < to {
+                        gizmos.line(from_pos, to_pos, color);
+                    }
+                } else {
+                    gizmos.arrow(from_pos, to_pos, color).with_tip_length(0.3);
+                }
+            }
+        }
+    }
+}
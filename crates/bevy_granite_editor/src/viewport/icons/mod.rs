@@ -4,13 +4,13 @@ pub mod updating;
 
 use bevy::{
     asset::Assets,
-    ecs::system::ResMut,
+    ecs::system::{Res, ResMut},
     image::Image,
+    mesh::Mesh3d,
     pbr::{MeshMaterial3d, StandardMaterial},
     prelude::{Bundle, Name},
-    mesh::Mesh3d,
 };
-use bevy_granite_core::{GraniteType, GraniteTypes, IconEntity};
+use bevy_granite_core::{GraniteClassRegistry, GraniteType, IconEntity};
 use bevy_granite_logging::{log, LogCategory, LogLevel, LogType};
 
 #[derive(Bundle)]
@@ -27,8 +27,11 @@ pub use spawning::*;
 pub use updating::*;
 
 // Register each classes embedded icon
-pub fn register_embedded_class_icons(mut images: ResMut<Assets<Image>>) {
-    for class in GraniteTypes::all() {
+pub fn register_embedded_class_icons(
+    mut images: ResMut<Assets<Image>>,
+    class_registry: Res<GraniteClassRegistry>,
+) {
+    for class in class_registry.iter() {
         class.register_embedded_icon(&mut images);
     }
     log!(
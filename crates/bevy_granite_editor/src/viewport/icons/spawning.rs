@@ -6,11 +6,14 @@ use bevy::{
     mesh::{Indices, Mesh3d},
     pbr::{MeshMaterial3d, StandardMaterial},
     prelude::{
-        Assets, Commands, Entity, Handle, Image, Mesh, Name, Query, Res, ResMut, Transform, Without,
+        AssetServer, Assets, Commands, Entity, Handle, Image, Mesh, Name, Query, Res, ResMut,
+        Transform, Without,
     },
     render::alpha::AlphaMode,
 };
-use bevy_granite_core::{GraniteType, IconProxy, IdentityData, TreeHiddenEntity};
+use bevy_granite_core::{
+    GraniteClassRegistry, GraniteType, IconProxy, IdentityData, TreeHiddenEntity,
+};
 
 pub fn spawn_icon_entities_system(
     mut commands: Commands,
@@ -19,6 +22,7 @@ pub fn spawn_icon_entities_system(
     entity_query: Query<(Entity, &IdentityData), Without<IconEntity>>,
     existing_icons_query: Query<&IconEntity>,
     editor_state: Res<EditorState>,
+    classes: (Res<GraniteClassRegistry>, Res<AssetServer>),
 ) {
     if !editor_state.active {
         return;
@@ -39,7 +43,7 @@ pub fn spawn_icon_entities_system(
         if !entities_with_icons.contains(&entity) {
             let class = &identity_data.class;
 
-            let handle = class.get_icon_handle();
+            let handle = classes.0.icon_handle(class, &classes.1);
             let name = class.type_name() + "_icon";
 
             if let Some(handle) = handle {
@@ -1,5 +1,5 @@
 use super::IconEntity;
-use crate::editor_state::EditorState;
+use crate::editor_state::{EditorState, PerformanceSafeMode};
 use bevy::{
     camera::visibility::Visibility,
     color::Color,
@@ -29,6 +29,7 @@ pub fn update_icon_entities_system(
     camera_query: Query<&GlobalTransform, (With<UICamera>, Without<IconEntity>)>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     editor_state: Res<EditorState>,
+    safe_mode: Res<PerformanceSafeMode>,
 ) {
     let rotating = drag_state.dragging && matches!(**selected_gizmo, GizmoType::Rotate);
     if !editor_state.active {
@@ -48,7 +49,7 @@ pub fn update_icon_entities_system(
     for (icon_entity, mut icon_transform, mut visibility, material_handle) in icon_query.iter_mut()
     {
         // Hide icons if disabled
-        if !config.icons_enabled {
+        if !config.icons_enabled || safe_mode.icons_disabled() {
             *visibility = Visibility::Hidden;
         } else {
             *visibility = Visibility::Visible;
@@ -4,34 +4,36 @@ pub mod debug;
 pub mod grid;
 pub mod icons;
 pub mod plugin;
+pub mod spline_points;
 pub mod state;
 pub mod viewmode;
 
 pub use camera::{
-    add_editor_camera,
-    add_ui_camera,
-    camera_frame_system,
-    camera_sync_toggle_system,
-    enforce_viewport_camera_state,
-    mouse_button_iter,
-    sync_cameras_system,
-    CameraSyncState,
-    CameraTarget,
-    EditorViewportCamera,
-    InputState,
-    ViewportCameraState,
+    add_editor_camera, add_ui_camera, camera_frame_system, camera_sync_toggle_system,
+    enforce_viewport_camera_state, mouse_button_iter, sync_cameras_system, CameraSyncState,
+    CameraTarget, EditorViewportCamera, InputState, ViewportCameraState,
 };
 pub use state::ViewportState;
 
-pub use config::VisualizationConfig;
+pub use config::{
+    EntityLabelSettings, GizmoPickingSettings, MeshDebugMode, SnapSettings, VisualizationConfig,
+};
 pub use debug::{
-    relationship_line_system, show_active_selection_bounds_system, show_camera_forward_system,
-    show_directional_light_forward_system, show_empty_origin_system, show_point_light_range_system,
-    show_selected_entities_bounds_system, DebugRenderer, SelectionRenderer,
+    relationship_line_system, show_active_selection_bounds_system, show_ambience_zones_system,
+    show_audio_emitters_system, show_camera_forward_system, show_camera_rails_system,
+    show_culled_entities_system, show_directional_light_forward_system, show_empty_origin_system,
+    show_entity_labels_system, show_force_volumes_system, show_joints_system,
+    show_kit_sockets_system, show_light_probes_system, show_mesh_vectors_system,
+    show_point_light_range_system, show_recorded_state_system,
+    show_selected_entities_bounds_system, show_splines_system, show_waypoint_links_system,
+    track_visible_entities_for_culled_system, update_mesh_debug_overlays_system,
+    update_uv_tiling_preview_system, DebugRenderer, MeshDebugOverlays, SelectionRenderer,
+    UvTilingPreview,
 };
 pub use grid::update_grid_system;
 pub use icons::{
     cleanup_icon_entities_system, spawn_icon_entities_system, update_icon_entities_system,
 };
 pub use plugin::ViewportPlugin;
+pub use spline_points::{sync_spline_point_handles_system, SplinePointHandle};
 pub use viewmode::{cleanup_scene_light_system, scene_light_system, SceneLightState};
@@ -7,14 +7,23 @@ use super::camera::{
 };
 use super::viewmode::{cleanup_scene_light_system, scene_light_system, SceneLightState};
 use crate::{
+    editor_state::{safe_mode_allows_icons, safe_mode_allows_visualizers},
     setup::is_editor_active,
     viewport::{
-        cleanup_icon_entities_system, grid::{spawn_viewport_grid, update_grid_system},
-        icons::register_embedded_class_icons, relationship_line_system,
-        show_active_selection_bounds_system, show_camera_forward_system,
-        show_directional_light_forward_system, show_empty_origin_system,
-        show_point_light_range_system, show_selected_entities_bounds_system,
-        spawn_icon_entities_system, update_icon_entities_system, DebugRenderer, SelectionRenderer,
+        cleanup_icon_entities_system,
+        grid::{spawn_viewport_grid, update_grid_system},
+        icons::register_embedded_class_icons,
+        relationship_line_system, show_active_selection_bounds_system, show_ambience_zones_system,
+        show_audio_emitters_system, show_camera_forward_system, show_camera_rails_system,
+        show_culled_entities_system, show_directional_light_forward_system,
+        show_empty_origin_system, show_entity_labels_system, show_force_volumes_system,
+        show_joints_system, show_kit_sockets_system, show_light_probes_system,
+        show_mesh_vectors_system, show_point_light_range_system, show_recorded_state_system,
+        show_selected_entities_bounds_system, show_splines_system, show_waypoint_links_system,
+        spawn_icon_entities_system, sync_spline_point_handles_system,
+        track_visible_entities_for_culled_system, update_icon_entities_system,
+        update_mesh_debug_overlays_system, update_uv_tiling_preview_system, DebugRenderer,
+        MeshDebugOverlays, SelectionRenderer, UvTilingPreview,
     },
 };
 use bevy::{
@@ -41,6 +50,8 @@ impl Plugin for ViewportPlugin {
             .insert_resource(InputState::default()) // FIX: Use UserInput
             .insert_resource(ViewportCameraState::default())
             .insert_resource(SceneLightState::default())
+            .init_resource::<MeshDebugOverlays>()
+            .init_resource::<UvTilingPreview>()
             //
             // Debug gizmo groups/config
             //
@@ -90,10 +101,18 @@ impl Plugin for ViewportPlugin {
             .add_systems(Update, camera_frame_system.run_if(is_editor_active))
             .add_systems(Update, camera_sync_toggle_system.run_if(is_editor_active))
             .add_systems(Update, scene_light_system.run_if(is_editor_active))
-            .add_systems(Update, cleanup_scene_light_system.run_if(not(is_editor_active)))
+            // Not gated on the editor, so handles are removed once it closes
+            .add_systems(Update, sync_spline_point_handles_system)
             .add_systems(
                 Update,
-                (handle_viewport_camera_override_requests, enforce_viewport_camera_state)
+                cleanup_scene_light_system.run_if(not(is_editor_active)),
+            )
+            .add_systems(
+                Update,
+                (
+                    handle_viewport_camera_override_requests,
+                    enforce_viewport_camera_state,
+                )
                     .chain()
                     .run_if(is_editor_active),
             )
@@ -101,6 +120,13 @@ impl Plugin for ViewportPlugin {
                 EguiPrimaryContextPass,
                 update_viewport_camera_viewports_system.run_if(is_editor_active),
             )
+            .add_systems(
+                EguiPrimaryContextPass,
+                show_entity_labels_system
+                    .after(update_viewport_camera_viewports_system)
+                    .run_if(is_editor_active)
+                    .run_if(safe_mode_allows_visualizers),
+            )
             .add_systems(
                 Update,
                 restore_runtime_camera_state.run_if(not(is_editor_active)),
@@ -109,7 +135,11 @@ impl Plugin for ViewportPlugin {
             .add_systems(Update, update_icon_entities_system)
             .add_systems(
                 Update,
-                (spawn_icon_entities_system, cleanup_icon_entities_system).run_if(is_editor_active),
+                (
+                    spawn_icon_entities_system.run_if(safe_mode_allows_icons),
+                    cleanup_icon_entities_system,
+                )
+                    .run_if(is_editor_active),
             )
             .add_systems(
                 // Different gizmo visualizers per type
@@ -120,8 +150,38 @@ impl Plugin for ViewportPlugin {
                     relationship_line_system,
                     show_point_light_range_system,
                     show_empty_origin_system,
+                    show_waypoint_links_system,
+                    show_kit_sockets_system,
+                    show_joints_system,
+                    show_force_volumes_system,
+                    show_ambience_zones_system,
+                    show_audio_emitters_system,
+                    show_light_probes_system,
+                    show_camera_rails_system,
+                    show_splines_system,
+                    show_culled_entities_system,
+                    show_mesh_vectors_system,
+                )
+                    .after(TransformSystems::Propagate)
+                    .run_if(is_editor_active)
+                    .run_if(safe_mode_allows_visualizers),
+            )
+            // Not gated on the editor or safe mode, so it can remove its overlays once either turns it off
+            .add_systems(
+                PostUpdate,
+                (
+                    update_mesh_debug_overlays_system,
+                    update_uv_tiling_preview_system,
+                    track_visible_entities_for_culled_system,
+                )
+                    .after(TransformSystems::Propagate),
+            )
+            .add_systems(
+                PostUpdate,
+                (
                     show_active_selection_bounds_system,
                     show_selected_entities_bounds_system,
+                    show_recorded_state_system,
                 )
                     .after(TransformSystems::Propagate)
                     .run_if(is_editor_active),
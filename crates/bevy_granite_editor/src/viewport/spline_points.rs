@@ -0,0 +1,235 @@
+use crate::{
+    editor_state::EditorState,
+    viewport::{camera::gizmo_layers, ViewportCameraState},
+};
+use bevy::{
+    asset::{Assets, Handle},
+    camera::Camera,
+    ecs::{component::Component, observer::On},
+    light::{NotShadowCaster, NotShadowReceiver},
+    math::primitives::InfinitePlane3d,
+    mesh::{Mesh, Mesh3d},
+    pbr::{MeshMaterial3d, StandardMaterial},
+    picking::{
+        events::{Drag, Pointer, Press},
+        pointer::PointerButton,
+        Pickable,
+    },
+    prelude::{
+        AlphaMode, Color, Commands, Entity, GlobalTransform, Local, Meshable, Name, Query, Res,
+        ResMut, Sphere, Transform, Vec3, Visibility, With,
+    },
+};
+use bevy_granite_core::{EditorIgnore, GraniteTypes, IdentityData, TreeHiddenEntity, UserInput};
+use bevy_granite_gizmos::{ActiveSelection, GizmoCamera, GizmoSnap};
+use bevy_granite_logging::{log, LogCategory, LogLevel, LogType};
+
+// spline_points.rs
+// Control points of the active Spline3D get a pickable handle each, drawn on the gizmo layer
+// Drag a handle to move its point across the plane facing the camera, Shift + click inserts a point after it
+// and Alt + click deletes it. Edits go straight to IdentityData, so history and the entity editor pick them up
+
+/// Handle radius one unit away from the camera, scaled with distance to keep its size on screen
+const HANDLE_SIZE: f32 = 0.012;
+
+/// Drag target for one control point of the active spline
+#[derive(Component)]
+pub struct SplinePointHandle {
+    pub spline: Entity,
+    pub index: usize,
+}
+
+/// Keeps one handle per control point of the active spline, and none once it is deselected
+pub fn sync_spline_point_handles_system(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut handle_assets: Local<Option<(Handle<Mesh>, Handle<StandardMaterial>)>>,
+    editor_state: Res<EditorState>,
+    viewport_camera_state: Res<ViewportCameraState>,
+    camera_query: Query<&GlobalTransform, With<Camera>>,
+    active_query: Query<(Entity, &IdentityData, &GlobalTransform), With<ActiveSelection>>,
+    mut handles: Query<(Entity, &SplinePointHandle, &mut Transform)>,
+) {
+    let active = active_query
+        .single()
+        .ok()
+        .filter(|_| editor_state.active)
+        .and_then(|(entity, identity, transform)| match &identity.class {
+            GraniteTypes::Spline3D(spline) => Some((entity, spline, transform)),
+            _ => None,
+        });
+    let camera_position = viewport_camera_state
+        .active_camera()
+        .and_then(|camera| camera_query.get(camera).ok())
+        .map(|transform| transform.translation());
+    // Scaled with camera distance so handles keep their size on screen
+    let place = |spline_transform: &GlobalTransform, point: Vec3| {
+        let position = spline_transform.transform_point(point);
+        let distance = camera_position.map_or(1.0, |camera| position.distance(camera));
+        Transform::from_translation(position).with_scale(Vec3::splat(distance.max(0.1)))
+    };
+
+    let mut has_handle = vec![false; active.map_or(0, |(_, spline, _)| spline.points.len())];
+    for (handle_entity, handle, mut transform) in handles.iter_mut() {
+        let Some((spline_entity, spline, spline_transform)) = active else {
+            commands.entity(handle_entity).despawn();
+            continue;
+        };
+        if handle.spline != spline_entity
+            || handle.index >= spline.points.len()
+            || has_handle[handle.index]
+        {
+            commands.entity(handle_entity).despawn();
+            continue;
+        }
+        has_handle[handle.index] = true;
+        *transform = place(spline_transform, spline.points[handle.index]);
+    }
+
+    let Some((spline_entity, spline, spline_transform)) = active else {
+        return;
+    };
+    if has_handle.iter().all(|has| *has) {
+        return;
+    }
+    let (mesh, material) = handle_assets
+        .get_or_insert_with(|| {
+            (
+                meshes.add(Sphere::new(HANDLE_SIZE).mesh().ico(2).unwrap()),
+                materials.add(StandardMaterial {
+                    base_color: Color::srgb(1.0, 0.8, 0.0),
+                    unlit: true,
+                    alpha_mode: AlphaMode::Blend,
+                    ..Default::default()
+                }),
+            )
+        })
+        .clone();
+    for (index, _) in has_handle.iter().enumerate().filter(|(_, has)| !**has) {
+        commands
+            .spawn((
+                Mesh3d(mesh.clone()),
+                MeshMaterial3d(material.clone()),
+                place(spline_transform, spline.points[index]),
+                Visibility::Visible,
+                SplinePointHandle {
+                    spline: spline_entity,
+                    index,
+                },
+                Pickable {
+                    is_hoverable: true,
+                    should_block_lower: true,
+                },
+                EditorIgnore::PICKING,
+                NotShadowCaster,
+                NotShadowReceiver,
+                gizmo_layers(),
+                TreeHiddenEntity,
+                Name::new(format!("SplinePoint_{}", index)),
+            ))
+            .observe(drag_spline_point_handle)
+            .observe(press_spline_point_handle);
+    }
+}
+
+/// Moves the point across the plane through it that faces the camera, on the snap grid while snapping
+fn drag_spline_point_handle(
+    drag: On<Pointer<Drag>>,
+    handles: Query<&SplinePointHandle>,
+    camera_query: Query<(&GlobalTransform, &Camera), With<GizmoCamera>>,
+    mut splines: Query<(&mut IdentityData, &GlobalTransform)>,
+    gizmo_snap: Res<GizmoSnap>,
+    user_input: Res<UserInput>,
+) {
+    if drag.button != PointerButton::Primary {
+        return;
+    }
+    let Ok(handle) = handles.get(drag.entity) else {
+        return;
+    };
+    let Ok((camera_transform, camera)) = camera_query.single() else {
+        return;
+    };
+    let Ok((mut identity, transform)) = splines.get_mut(handle.spline) else {
+        return;
+    };
+    let GraniteTypes::Spline3D(spline) = &identity.class else {
+        return;
+    };
+    let Some(point) = spline.points.get(handle.index) else {
+        return;
+    };
+    let Ok(ray) = camera.viewport_to_world(camera_transform, drag.pointer_location.position) else {
+        return;
+    };
+
+    let world_point = transform.transform_point(*point);
+    let Some(hit) = ray.intersect_plane(
+        world_point,
+        InfinitePlane3d::new(camera_transform.forward()),
+    ) else {
+        return;
+    };
+    let mut target = ray.get_point(hit);
+    let step = gizmo_snap.transform_step(user_input.ctrl_left.any);
+    if step > 0.0 {
+        target = (target / step).round() * step;
+    }
+    let local = transform.affine().inverse().transform_point3(target);
+    if local == *point {
+        return;
+    }
+    if let GraniteTypes::Spline3D(spline) = &mut identity.class {
+        spline.points[handle.index] = local;
+    }
+}
+
+/// Shift inserts a point after the pressed one, Alt deletes it
+fn press_spline_point_handle(
+    press: On<Pointer<Press>>,
+    handles: Query<&SplinePointHandle>,
+    mut splines: Query<&mut IdentityData>,
+    user_input: Res<UserInput>,
+) {
+    if press.button != PointerButton::Primary {
+        return;
+    }
+    let Ok(handle) = handles.get(press.entity) else {
+        return;
+    };
+    if !user_input.shift_left.any && !user_input.alt_left.any {
+        return;
+    }
+    let Ok(mut identity) = splines.get_mut(handle.spline) else {
+        return;
+    };
+    let GraniteTypes::Spline3D(spline) = &mut identity.class else {
+        return;
+    };
+    if handle.index >= spline.points.len() {
+        return;
+    }
+
+    if user_input.alt_left.any {
+        spline.points.remove(handle.index);
+        log!(
+            LogType::Editor,
+            LogLevel::Info,
+            LogCategory::Entity,
+            "Deleted spline point {} of {}",
+            handle.index,
+            handle.spline
+        );
+    } else {
+        let index = spline.insert_after(handle.index);
+        log!(
+            LogType::Editor,
+            LogLevel::Info,
+            LogCategory::Entity,
+            "Inserted spline point {} of {}",
+            index,
+            handle.spline
+        );
+    }
+}
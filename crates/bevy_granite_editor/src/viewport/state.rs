@@ -1,4 +1,6 @@
-use crate::viewport::VisualizationConfig;
+use crate::viewport::{
+    EntityLabelSettings, GizmoPickingSettings, SnapSettings, VisualizationConfig,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -8,6 +10,12 @@ pub struct ViewportState {
     pub grid_distance: f32,
     pub grid_color: [f32; 4],
     pub grid_size: f32,
+    #[serde(default)]
+    pub snap: SnapSettings,
+    #[serde(default)]
+    pub gizmo_picking: GizmoPickingSettings,
+    #[serde(default)]
+    pub labels: EntityLabelSettings,
 
     #[serde(skip)]
     pub changed: bool,
@@ -19,6 +27,9 @@ impl Default for ViewportState {
             grid_distance: 100.,
             grid_color: [0.124, 0.124, 0.124, 1.0],
             grid_size: 1.,
+            snap: SnapSettings::default(),
+            gizmo_picking: GizmoPickingSettings::default(),
+            labels: EntityLabelSettings::default(),
             visualizers: VisualizationConfig::default(),
             changed: true,
         }
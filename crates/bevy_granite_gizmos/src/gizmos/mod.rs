@@ -7,6 +7,7 @@ use bevy::{
 pub mod distance_scaling;
 pub mod events;
 pub mod manager;
+pub mod picking;
 pub mod plugin;
 pub mod rotate;
 pub mod transform;
@@ -31,6 +32,22 @@ pub enum GizmoMode {
     Global,
 }
 
+impl GizmoMode {
+    pub fn toggled(&self) -> GizmoMode {
+        match self {
+            GizmoMode::Local => GizmoMode::Global,
+            GizmoMode::Global => GizmoMode::Local,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GizmoMode::Local => "Local",
+            GizmoMode::Global => "Global",
+        }
+    }
+}
+
 #[derive(Resource)]
 pub struct NewGizmoConfig {
     pub speed_scale: f32,
@@ -130,10 +147,59 @@ pub struct GizmoMesh;
 #[relationship_target(relationship = GizmoRoot)]
 pub struct GizmoChildren(Vec<Entity>);
 
+/// Snap steps for gizmo drags, used while Ctrl is held (or always, with Ctrl turning them off)
 #[derive(Resource)]
 pub struct GizmoSnap {
+    /// Degrees
     pub rotate_value: f32,
+    /// Grid size for global moves, step size for local ones
     pub transform_value: f32,
+    /// Step for scale fields
+    pub scale_value: f32,
+    /// Snap without holding Ctrl
+    pub always: bool,
+    /// Align dragged pieces to the KitSockets of nearby pieces
+    pub kit_sockets: bool,
+    /// How close two sockets have to be to snap
+    pub kit_socket_distance: f32,
+    /// Drop moved entities onto the scene mesh under the cursor
+    pub surface: bool,
+    /// Turn entities dropped onto a surface so their up axis follows its normal
+    pub surface_align: bool,
+}
+
+impl GizmoSnap {
+    /// Holding Ctrl flips whether snapping is on
+    pub fn active(&self, ctrl_held: bool) -> bool {
+        self.always != ctrl_held
+    }
+
+    /// Move step, 0 when not snapping
+    pub fn transform_step(&self, ctrl_held: bool) -> f32 {
+        if self.active(ctrl_held) {
+            self.transform_value.max(0.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// Rotation step in radians, 0 when not snapping
+    pub fn rotate_step(&self, ctrl_held: bool) -> f32 {
+        if self.active(ctrl_held) {
+            self.rotate_value.max(0.0).to_radians()
+        } else {
+            0.0
+        }
+    }
+
+    /// Scale step, 0 when not snapping
+    pub fn scale_step(&self, ctrl_held: bool) -> f32 {
+        if self.active(ctrl_held) {
+            self.scale_value.max(0.0)
+        } else {
+            0.0
+        }
+    }
 }
 
 #[derive(Component, Deref, Clone, Copy)]
@@ -176,14 +242,19 @@ pub use events::{
     SpawnGizmoEvent, TransformDraggingEvent, TransformInitDragEvent, TransformResetDragEvent,
 };
 pub use manager::{gizmo_changed_watcher, gizmo_events};
+pub use picking::{
+    cycle_gizmo_pick_system, gizmo_pick_backend_system, GizmoHandle, GizmoHandleKind,
+    GizmoHandleShape, GizmoPickCandidates, GizmoPickSettings,
+};
 pub use plugin::GizmoPlugin;
 pub use rotate::{
     despawn_rotate_gizmo, handle_init_rotate_drag, handle_rotate_dragging, handle_rotate_input,
-    handle_rotate_reset, register_embedded_rotate_gizmo_mesh, spawn_rotate_gizmo, 
-    update_gizmo_rotation_for_mode as update_rotate_gizmo_rotation_for_mode,
-    RotateGizmo, RotateGizmoParent,
+    handle_rotate_reset, register_embedded_rotate_gizmo_mesh, spawn_rotate_gizmo,
+    update_gizmo_rotation_for_mode as update_rotate_gizmo_rotation_for_mode, RotateGizmo,
+    RotateGizmoParent,
 };
 pub use transform::{
-    despawn_transform_gizmo, spawn_transform_gizmo, update_gizmo_rotation_for_mode as update_transform_gizmo_rotation_for_mode, 
+    despawn_transform_gizmo, spawn_transform_gizmo,
+    update_gizmo_rotation_for_mode as update_transform_gizmo_rotation_for_mode,
     PreviousTransformGizmo, TransformGizmo, TransformGizmoParent,
 };
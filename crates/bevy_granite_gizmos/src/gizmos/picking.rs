@@ -0,0 +1,335 @@
+use crate::GizmoCamera;
+use bevy::{
+    camera::Camera,
+    math::{Ray3d, Vec2, Vec3},
+    picking::backend::{ray::RayMap, HitData, PointerHits},
+    prelude::{
+        Component, Entity, GlobalTransform, InheritedVisibility, MessageWriter, Query, Res, ResMut,
+        Resource, With,
+    },
+};
+use bevy_granite_core::UserInput;
+
+// picking.rs
+// Gizmo handles are picked on screen instead of by their meshes. Every handle has a GizmoHandle shape, and the ones
+// within a pixel radius of the cursor are candidates. Handles drawn right under the cursor come first, then near misses,
+// each group in GizmoPickSettings priority. Overlapping handles at glancing angles no longer go to whichever mesh is in front,
+// and thin handles can be grabbed from a few pixels away. Tab cycles through the candidates under a still cursor
+
+/// Handle kinds, in the default pick priority
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GizmoHandleKind {
+    /// Free move or free rotate sphere
+    Center,
+    Axis,
+    Plane,
+    Ring,
+}
+
+/// Shape a handle is picked by, in its local space
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GizmoHandleShape {
+    Sphere {
+        radius: f32,
+    },
+    /// Line between two points, as thick as the drawn handle
+    Segment {
+        start: Vec3,
+        end: Vec3,
+        thickness: f32,
+    },
+    /// Square in the local XZ plane
+    Square {
+        half_size: f32,
+    },
+    /// Circle in the local XY plane
+    Circle {
+        radius: f32,
+        thickness: f32,
+    },
+}
+
+/// Makes a gizmo mesh pickable in screen space, on the entity that observes its drags
+#[derive(Component, Clone, Copy, Debug)]
+pub struct GizmoHandle {
+    pub kind: GizmoHandleKind,
+    pub shape: GizmoHandleShape,
+}
+
+impl GizmoHandle {
+    pub fn new(kind: GizmoHandleKind, shape: GizmoHandleShape) -> Self {
+        Self { kind, shape }
+    }
+}
+
+/// How gizmo handles are picked
+#[derive(Resource, Clone, Debug)]
+pub struct GizmoPickSettings {
+    /// Off picks handles by their meshes like any other entity
+    pub enabled: bool,
+    /// Pixels around a handle that still grab it
+    pub radius: f32,
+    /// First to last, when several handles are under the cursor
+    pub priority: [GizmoHandleKind; 4],
+}
+
+impl Default for GizmoPickSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            radius: 8.0,
+            priority: [
+                GizmoHandleKind::Center,
+                GizmoHandleKind::Axis,
+                GizmoHandleKind::Plane,
+                GizmoHandleKind::Ring,
+            ],
+        }
+    }
+}
+
+impl GizmoPickSettings {
+    fn rank(&self, kind: GizmoHandleKind) -> usize {
+        self.priority
+            .iter()
+            .position(|other| *other == kind)
+            .unwrap_or(self.priority.len())
+    }
+}
+
+/// Handles under the cursor in pick order, and how far Tab cycled through them
+#[derive(Resource, Default, Debug)]
+pub struct GizmoPickCandidates {
+    candidates: Vec<Entity>,
+    cycle: usize,
+}
+
+impl GizmoPickCandidates {
+    pub fn candidates(&self) -> &[Entity] {
+        &self.candidates
+    }
+
+    /// The handle a press would grab
+    pub fn picked(&self) -> Option<Entity> {
+        if self.candidates.is_empty() {
+            return None;
+        }
+        Some(self.candidates[self.cycle % self.candidates.len()])
+    }
+}
+
+/// Cursor ray of the gizmo camera and where it is on screen
+struct PickView<'a> {
+    camera: &'a Camera,
+    camera_transform: &'a GlobalTransform,
+    ray: Ray3d,
+    cursor: Vec2,
+}
+
+impl PickView<'_> {
+    fn project(&self, point: Vec3) -> Option<Vec2> {
+        self.camera
+            .world_to_viewport(self.camera_transform, point)
+            .ok()
+    }
+
+    /// Pixels a world length at a point covers on screen
+    fn pixels(&self, point: Vec3, length: f32) -> f32 {
+        let side = point + self.camera_transform.right().as_vec3() * length;
+        match (self.project(point), self.project(side)) {
+            (Some(a), Some(b)) => a.distance(b),
+            _ => 0.0,
+        }
+    }
+
+    /// Pixels from the cursor to the nearest edge of a polyline, with the nearest point on it
+    fn nearest_edge(&self, points: &[Vec3], closed: bool) -> Option<(f32, Vec3)> {
+        let count = if closed {
+            points.len()
+        } else {
+            points.len().saturating_sub(1)
+        };
+        let mut nearest: Option<(f32, Vec3)> = None;
+        for index in 0..count {
+            let (start, end) = (points[index], points[(index + 1) % points.len()]);
+            let (Some(a), Some(b)) = (self.project(start), self.project(end)) else {
+                continue;
+            };
+            let ab = b - a;
+            let along = if ab.length_squared() > 0.0 {
+                ((self.cursor - a).dot(ab) / ab.length_squared()).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            let distance = self.cursor.distance(a + ab * along);
+            if nearest.is_none_or(|(best, _)| distance < best) {
+                nearest = Some((distance, start.lerp(end, along)));
+            }
+        }
+        nearest
+    }
+}
+
+impl GizmoHandleShape {
+    /// Pixels from the cursor to the drawn handle, 0 over it, with the point on the handle nearest the cursor
+    fn pick(&self, view: &PickView, transform: &GlobalTransform) -> Option<(f32, Vec3)> {
+        let scale = transform.scale().max_element();
+        match *self {
+            GizmoHandleShape::Sphere { radius } => {
+                let center = transform.translation();
+                let distance = view.cursor.distance(view.project(center)?);
+                let along = (center - view.ray.origin).dot(*view.ray.direction).max(0.0);
+                Some((
+                    (distance - view.pixels(center, radius * scale)).max(0.0),
+                    view.ray.get_point(along),
+                ))
+            }
+            GizmoHandleShape::Segment {
+                start,
+                end,
+                thickness,
+            } => {
+                let points = [
+                    transform.transform_point(start),
+                    transform.transform_point(end),
+                ];
+                let (distance, nearest) = view.nearest_edge(&points, false)?;
+                let thickness = view.pixels(nearest, thickness * scale);
+                Some(((distance - thickness).max(0.0), nearest))
+            }
+            GizmoHandleShape::Square { half_size } => {
+                let points = [
+                    Vec3::new(-half_size, 0.0, -half_size),
+                    Vec3::new(half_size, 0.0, -half_size),
+                    Vec3::new(half_size, 0.0, half_size),
+                    Vec3::new(-half_size, 0.0, half_size),
+                ]
+                .map(|point| transform.transform_point(point));
+                let corners: Vec<Vec2> = points
+                    .iter()
+                    .filter_map(|point| view.project(*point))
+                    .collect();
+                let inside = corners.len() == 4 && {
+                    let sides: Vec<f32> = (0..4)
+                        .map(|index| {
+                            let (a, b) = (corners[index], corners[(index + 1) % 4]);
+                            (b - a).perp_dot(view.cursor - a)
+                        })
+                        .collect();
+                    sides.iter().all(|side| *side >= 0.0) || sides.iter().all(|side| *side <= 0.0)
+                };
+                if inside {
+                    let plane = bevy::math::primitives::InfinitePlane3d::new(transform.up());
+                    let hit = view
+                        .ray
+                        .intersect_plane(transform.translation(), plane)
+                        .map(|distance| view.ray.get_point(distance))
+                        .unwrap_or(transform.translation());
+                    return Some((0.0, hit));
+                }
+                view.nearest_edge(&points, true)
+            }
+            GizmoHandleShape::Circle { radius, thickness } => {
+                const SEGMENTS: usize = 48;
+                let points: Vec<Vec3> = (0..SEGMENTS)
+                    .map(|index| {
+                        let angle = index as f32 / SEGMENTS as f32 * std::f32::consts::TAU;
+                        transform.transform_point(Vec3::new(
+                            angle.cos() * radius,
+                            angle.sin() * radius,
+                            0.0,
+                        ))
+                    })
+                    .collect();
+                let (distance, nearest) = view.nearest_edge(&points, true)?;
+                let thickness = view.pixels(nearest, thickness * scale);
+                Some(((distance - thickness).max(0.0), nearest))
+            }
+        }
+    }
+}
+
+/// Picking backend for gizmo handles. Hits go a layer above the gizmo camera's mesh hits so they block them
+pub fn gizmo_pick_backend_system(
+    settings: Res<GizmoPickSettings>,
+    mut candidates: ResMut<GizmoPickCandidates>,
+    ray_map: Res<RayMap>,
+    cameras: Query<(&Camera, &GlobalTransform), With<GizmoCamera>>,
+    handles: Query<(Entity, &GizmoHandle, &GlobalTransform, &InheritedVisibility)>,
+    mut hits_writer: MessageWriter<PointerHits>,
+) {
+    if !settings.enabled {
+        return;
+    }
+
+    for (ray_id, ray) in ray_map.iter() {
+        let Ok((camera, camera_transform)) = cameras.get(ray_id.camera) else {
+            continue;
+        };
+        if !camera.is_active {
+            continue;
+        }
+        let Ok(cursor) = camera.world_to_viewport(camera_transform, ray.get_point(1.0)) else {
+            continue;
+        };
+        let view = PickView {
+            camera,
+            camera_transform,
+            ray: *ray,
+            cursor,
+        };
+
+        let mut picked: Vec<(bool, usize, f32, Entity, Vec3)> = handles
+            .iter()
+            .filter(|(.., visibility)| visibility.get())
+            .filter_map(|(entity, handle, transform, _)| {
+                let (distance, position) = handle.shape.pick(&view, transform)?;
+                (distance <= settings.radius).then_some((
+                    distance > 0.0,
+                    settings.rank(handle.kind),
+                    distance,
+                    entity,
+                    position,
+                ))
+            })
+            .collect();
+        picked.sort_by(|a, b| (a.0, a.1).cmp(&(b.0, b.1)).then(a.2.total_cmp(&b.2)));
+
+        let entities: Vec<Entity> = picked.iter().map(|(.., entity, _)| *entity).collect();
+        if entities != candidates.candidates {
+            candidates.candidates = entities;
+            candidates.cycle = 0;
+        }
+        let Some(entity) = candidates.picked() else {
+            continue;
+        };
+        let Some((.., position)) = picked.iter().find(|(.., other, _)| *other == entity) else {
+            continue;
+        };
+
+        let depth = ray.origin.distance(*position);
+        hits_writer.write(PointerHits::new(
+            ray_id.pointer,
+            vec![(
+                entity,
+                HitData::new(ray_id.camera, depth, Some(*position), None),
+            )],
+            camera.order as f32 + 0.5,
+        ));
+    }
+}
+
+/// Tab grabs the next handle under the cursor instead, while nothing is being dragged
+pub fn cycle_gizmo_pick_system(
+    user_input: Res<UserInput>,
+    mut candidates: ResMut<GizmoPickCandidates>,
+) {
+    if !user_input.key_tab.just_pressed
+        || user_input.mouse_over_egui
+        || user_input.mouse_left.pressed
+        || candidates.candidates.len() < 2
+    {
+        return;
+    }
+    candidates.cycle = (candidates.cycle + 1) % candidates.candidates.len();
+}
@@ -1,21 +1,23 @@
 use super::register_embedded_rotate_gizmo_mesh;
 use super::{
-    gizmo_changed_watcher, gizmo_events, handle_init_rotate_drag, handle_rotate_input,
-    handle_rotate_reset, scale_gizmo_by_camera_distance_system, DespawnGizmoEvent, GizmoSnap,
-    GizmoType, LastSelectedGizmo, NewGizmoConfig, PreviousTransformGizmo, RotateDraggingEvent,
-    RotateInitDragEvent, RotateResetDragEvent, SpawnGizmoEvent, TransformDraggingEvent,
-    TransformInitDragEvent, TransformResetDragEvent,
-    update_rotate_gizmo_rotation_for_mode, update_transform_gizmo_rotation_for_mode,
+    cycle_gizmo_pick_system, gizmo_changed_watcher, gizmo_events, gizmo_pick_backend_system,
+    handle_init_rotate_drag, handle_rotate_input, handle_rotate_reset,
+    scale_gizmo_by_camera_distance_system, update_rotate_gizmo_rotation_for_mode,
+    update_transform_gizmo_rotation_for_mode, DespawnGizmoEvent, GizmoPickCandidates,
+    GizmoPickSettings, GizmoSnap, GizmoType, LastSelectedGizmo, NewGizmoConfig,
+    PreviousTransformGizmo, RotateDraggingEvent, RotateInitDragEvent, RotateResetDragEvent,
+    SpawnGizmoEvent, TransformDraggingEvent, TransformInitDragEvent, TransformResetDragEvent,
 };
 use crate::gizmos::transform::{
-    apply_transformations, TransitionDelta,
+    apply_transformations, snap_to_kit_sockets, snap_to_surface, InitialDragOffset, TransitionDelta,
 };
 use crate::gizmos::{GizmoMode, NewGizmoType};
 use crate::is_gizmos_active;
 use bevy::ecs::schedule::common_conditions::any_with_component;
 use bevy::{
-    app::{App, Plugin, PostUpdate, Startup, Update},
+    app::{App, Plugin, PostUpdate, PreUpdate, Startup, Update},
     ecs::schedule::IntoScheduleConfigs,
+    picking::PickingSystems,
 };
 
 pub struct GizmoPlugin;
@@ -36,10 +38,18 @@ impl Plugin for GizmoPlugin {
             })
             .insert_resource(NewGizmoType(GizmoType::Pointer))
             .insert_resource(GizmoSnap {
-                transform_value: 0.,
-                rotate_value: 0.,
+                transform_value: 0.5,
+                rotate_value: 15.,
+                scale_value: 0.1,
+                always: false,
+                kit_sockets: true,
+                kit_socket_distance: 1.,
+                surface: false,
+                surface_align: false,
             })
             .insert_resource(super::transform::drag::TransformDuplicationState::default())
+            .init_resource::<GizmoPickSettings>()
+            .init_resource::<GizmoPickCandidates>()
             //
             // Events
             //
@@ -55,6 +65,12 @@ impl Plugin for GizmoPlugin {
             // Schedule system
             //
             .add_systems(Startup, register_embedded_rotate_gizmo_mesh)
+            .add_systems(
+                PreUpdate,
+                gizmo_pick_backend_system
+                    .in_set(PickingSystems::Backend)
+                    .run_if(is_gizmos_active),
+            )
             .add_systems(
                 Update,
                 (
@@ -63,6 +79,13 @@ impl Plugin for GizmoPlugin {
                     update_transform_gizmo_rotation_for_mode,
                     update_rotate_gizmo_rotation_for_mode,
                     apply_transformations.run_if(any_with_component::<TransitionDelta>),
+                    snap_to_surface
+                        .after(apply_transformations)
+                        .run_if(any_with_component::<InitialDragOffset>),
+                    // Sockets win over the surface below them
+                    snap_to_kit_sockets
+                        .after(snap_to_surface)
+                        .run_if(any_with_component::<InitialDragOffset>),
                 )
                     .run_if(is_gizmos_active),
             )
@@ -73,6 +96,7 @@ impl Plugin for GizmoPlugin {
                     handle_rotate_input,
                     handle_init_rotate_drag.after(handle_rotate_input),
                     handle_rotate_reset.after(handle_rotate_input),
+                    cycle_gizmo_pick_system,
                 )
                     .run_if(is_gizmos_active),
             )
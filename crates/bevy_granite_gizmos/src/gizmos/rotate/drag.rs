@@ -2,9 +2,9 @@
 // Children inherit rotation automatically through hierarchy
 use crate::{
     gizmos::{
-        GizmoConfig, GizmoMesh, GizmoMode, GizmoOf, GizmoRoot, GizmoSnap, GizmoType, NewGizmoConfig, NewGizmoType,
-        RotateDraggingEvent, RotateGizmo, RotateGizmoParent, RotateInitDragEvent,
-        RotateResetDragEvent,
+        GizmoConfig, GizmoMesh, GizmoMode, GizmoOf, GizmoRoot, GizmoSnap, GizmoType,
+        NewGizmoConfig, NewGizmoType, RotateDraggingEvent, RotateGizmo, RotateGizmoParent,
+        RotateInitDragEvent, RotateResetDragEvent,
     },
     input::{DragState, GizmoAxis},
     selection::{
@@ -210,7 +210,7 @@ pub fn handle_init_rotate_drag(
                     LogCategory::Input,
                     "Duplicate entity"
                 );
-                duplicate_event_writer.write(RequestDuplicateAllSelectionEvent);
+                duplicate_event_writer.write(RequestDuplicateAllSelectionEvent::default());
             }
         } else {
             return;
@@ -249,7 +249,8 @@ pub fn handle_rotate_dragging(
     active_selection: Query<Entity, With<ActiveSelection>>,
     other_selected: Query<Entity, (With<Selected>, Without<ActiveSelection>)>,
     parents: Query<&ChildOf>,
-    _gizmo_snap: Res<GizmoSnap>,
+    gizmo_snap: Res<GizmoSnap>,
+    user_input: Res<UserInput>,
     selected: Res<NewGizmoConfig>,
     gizmo_data: Query<(&GizmoAxis, &GizmoRoot)>,
     gizmo_config_query: Query<&GizmoConfig>,
@@ -262,7 +263,7 @@ pub fn handle_rotate_dragging(
     let Ok((gizmo_axis, gizmo_root)) = gizmo_data.get(event.entity) else {
         return;
     };
-    
+
     if !drag_state.dragging {
         drag_state.dragging = true;
         for (axis, mut visibility, root) in gizmo_visibility_query.iter_mut() {
@@ -275,9 +276,9 @@ pub fn handle_rotate_dragging(
             }
         }
     }
-    
+
     let config = gizmo_config_query.get(gizmo_root.0).ok();
-    
+
     let GizmoConfig::Rotate {
         speed_scale,
         distance_scale: _,
@@ -329,7 +330,7 @@ pub fn handle_rotate_dragging(
             return;
         }
     };
-    
+
     // Get target rotation for local/global mode
     let target_rotation = if let Ok(global_transform) = global_transforms.get(target.0) {
         global_transform.to_scale_rotation_translation().1
@@ -343,21 +344,21 @@ pub fn handle_rotate_dragging(
 
     let (final_rotation, local_axis) = match gizmo_axis {
         GizmoAxis::All => {
-            let snap_increment = _gizmo_snap.rotate_value.to_radians();
-            
+            let snap_increment = gizmo_snap.rotate_step(user_input.ctrl_left.any);
+
             if snap_increment > 0.0 {
                 let delta_x = event.delta.x * free_rotate_speed;
                 let delta_y = event.delta.y * free_rotate_speed;
-                
+
                 let rotation_magnitude = (delta_x * delta_x + delta_y * delta_y).sqrt();
-                
+
                 drag_state.accumulated_angle += rotation_magnitude;
-                
+
                 let delta_from_last_snap = drag_state.accumulated_angle - drag_state.last_snapped;
                 if delta_from_last_snap.abs() >= snap_increment {
                     let snap_count = (delta_from_last_snap / snap_increment).trunc();
                     let snapped_magnitude = snap_count * snap_increment;
-                    
+
                     let normalized_delta_x = if rotation_magnitude > f32::EPSILON {
                         delta_x / rotation_magnitude
                     } else {
@@ -368,13 +369,17 @@ pub fn handle_rotate_dragging(
                     } else {
                         0.0
                     };
-                    
+
                     let snapped_delta_x = normalized_delta_x * snapped_magnitude;
                     let snapped_delta_y = normalized_delta_y * snapped_magnitude;
-                    
+
                     drag_state.last_snapped += snapped_magnitude;
-                    let rotation = Quat::from_axis_angle(camera_transform.up().as_vec3(), snapped_delta_x)
-                        * Quat::from_axis_angle(camera_transform.right().as_vec3(), snapped_delta_y);
+                    let rotation =
+                        Quat::from_axis_angle(camera_transform.up().as_vec3(), snapped_delta_x)
+                            * Quat::from_axis_angle(
+                                camera_transform.right().as_vec3(),
+                                snapped_delta_y,
+                            );
                     (rotation, None)
                 } else {
                     return;
@@ -394,24 +399,22 @@ pub fn handle_rotate_dragging(
                 GizmoAxis::Z => Vec3::Z,
                 _ => return,
             };
-            
+
             let world_axis = match mode {
-                GizmoMode::Local => {
-                    target_rotation * axis
-                }
-                GizmoMode::Global => {
-                    axis
-                }
+                GizmoMode::Local => target_rotation * axis,
+                GizmoMode::Global => axis,
             };
 
-            let Ok(ray) = camera.viewport_to_world(camera_transform, event.pointer_location.position) else {
+            let Ok(ray) =
+                camera.viewport_to_world(camera_transform, event.pointer_location.position)
+            else {
                 return;
             };
 
             let ray_origin = ray.origin;
             let ray_direction = ray.direction;
             let plane_normal = world_axis;
-            
+
             let ray_dir_dot = ray_direction.dot(plane_normal);
             if ray_dir_dot.abs() < 1e-6 {
                 return; // Ray parallel to plane
@@ -421,54 +424,56 @@ pub fn handle_rotate_dragging(
             let hit_pos = ray_origin + ray_direction * t;
             let prev_vec = drag_state.prev_hit_dir;
             let curr_vec = (hit_pos - origin).normalize();
-            
-            if prev_vec.is_nan() || curr_vec.is_nan() || prev_vec.length_squared() < 1e-6 || curr_vec.length_squared() < 1e-6 {
+
+            if prev_vec.is_nan()
+                || curr_vec.is_nan()
+                || prev_vec.length_squared() < 1e-6
+                || curr_vec.length_squared() < 1e-6
+            {
                 drag_state.prev_hit_dir = curr_vec;
                 return;
             }
-            
+
             let dot_product = prev_vec.dot(curr_vec);
             if dot_product < 0.95 {
                 drag_state.prev_hit_dir = curr_vec;
                 return;
             }
-            
+
             let unsigned_angle = prev_vec.angle_between(curr_vec);
             if unsigned_angle.is_nan() || !unsigned_angle.is_finite() {
                 return;
             }
-            
+
             let angle_threshold = 0.001; // ~0.057 degrees
             if unsigned_angle.abs() < angle_threshold {
-                return; 
+                return;
             }
-            
+
             let direction = prev_vec.cross(curr_vec).dot(world_axis).signum();
             let signed_angle = unsigned_angle * direction * locked_rotate_speed;
-            
-            let snap_increment = _gizmo_snap.rotate_value.to_radians();
+
+            let snap_increment = gizmo_snap.rotate_step(user_input.ctrl_left.any);
             let (snapped_angle, new_accumulated, new_last_snapped) = calculate_snap_rotation(
                 signed_angle,
                 drag_state.accumulated_angle,
                 drag_state.last_snapped,
                 snap_increment,
             );
-            
+
             drag_state.accumulated_angle = new_accumulated;
             drag_state.last_snapped = new_last_snapped;
             drag_state.prev_hit_dir = curr_vec;
-            
+
             if snapped_angle.abs() < f32::EPSILON {
                 return;
             }
-            
+
             let rotation_delta = Quat::from_axis_angle(world_axis, snapped_angle);
-            
+
             (rotation_delta, Some((axis, snapped_angle)))
         }
-        GizmoAxis::None => {
-            (Quat::IDENTITY, None)
-        }
+        GizmoAxis::None => (Quat::IDENTITY, None),
     };
 
     for &entity in &root_entities {
@@ -486,19 +491,21 @@ pub fn handle_rotate_dragging(
                     // Get the current global rotation
                     // Apply the rotation in global space
                     // Convert back to local space (accounting for parent rotation)
-                    
-                    let current_global_rotation = if let Ok(global_transform) = global_transforms.get(entity) {
-                        global_transform.to_scale_rotation_translation().1
-                    } else {
-                        entity_transform.rotation
-                    };
-                    
+
+                    let current_global_rotation =
+                        if let Ok(global_transform) = global_transforms.get(entity) {
+                            global_transform.to_scale_rotation_translation().1
+                        } else {
+                            entity_transform.rotation
+                        };
+
                     let new_global_rotation = final_rotation * current_global_rotation;
-                    
+
                     if let Ok(parent) = parents.get(entity) {
                         if let Ok(parent_global) = global_transforms.get(parent.parent()) {
                             let parent_rotation = parent_global.to_scale_rotation_translation().1;
-                            entity_transform.rotation = parent_rotation.inverse() * new_global_rotation;
+                            entity_transform.rotation =
+                                parent_rotation.inverse() * new_global_rotation;
                         } else {
                             entity_transform.rotation = new_global_rotation;
                         }
@@ -525,7 +532,7 @@ fn calculate_snap_rotation(
     let new_accumulated = accumulated + raw_delta;
     let delta_from_last_snap = new_accumulated - last_snapped;
     let snap_count = (delta_from_last_snap / snap_increment).trunc();
-    
+
     if snap_count.abs() >= 1.0 {
         let snapped_angle = snap_count * snap_increment;
         let new_last_snapped = last_snapped + snapped_angle;
@@ -15,7 +15,9 @@ use bevy_granite_logging::{
     log,
 };
 
-use crate::gizmos::{GizmoConfig, GizmoMode, GizmoOf, GizmoRoot};
+use crate::gizmos::{
+    GizmoConfig, GizmoHandle, GizmoHandleKind, GizmoHandleShape, GizmoMode, GizmoOf, GizmoRoot,
+};
 use crate::{gizmos::GizmoMesh, input::GizmoAxis};
 
 #[derive(Component)]
@@ -32,6 +34,8 @@ pub struct PreviousTransformGizmo {
 const GIZMO_SCALE: f32 = 0.85;
 const ROTATE_INNER_RADIUS: f32 = 0.12 * GIZMO_SCALE; // middle sphere of gizmo (free rotate)
 const ROTATE_VISUAL_RADIUS: f32 = 0.64 * GIZMO_SCALE; // middle sphere of gizmo (visual)
+const RING_RADIUS: f32 = 0.68; // middle of Ring.obj, before GIZMO_SCALE
+const RING_THICKNESS: f32 = 0.04;
 const RING_MESH_HASH: uuid::Uuid = uuid::uuid!("3f6f4c2a-6e36-4ccf-81c4-f343f83c5f80"); // constantly random - doesnt matter the value
 
 pub fn register_embedded_rotate_gizmo_mesh(mut meshes: ResMut<Assets<Mesh>>) {
@@ -60,18 +64,14 @@ pub fn spawn_rotate_gizmo(
         let gizmo_translation = offset;
 
         let initial_rotation = match config.mode() {
-            GizmoMode::Global => {
-                parent_global_transform
-                    .to_scale_rotation_translation()
-                    .1
-                    .inverse()
-            }
-            GizmoMode::Local => {
-                Quat::IDENTITY
-            }
+            GizmoMode::Global => parent_global_transform
+                .to_scale_rotation_translation()
+                .1
+                .inverse(),
+            GizmoMode::Local => Quat::IDENTITY,
         };
 
-        // Create the gizmo parent entity 
+        // Create the gizmo parent entity
         let gizmo_entity = commands
             .spawn((
                 Transform {
@@ -224,6 +224,12 @@ fn build_free_sphere(
             GizmoAxis::All,
             RotateGizmo,
             GizmoMesh,
+            GizmoHandle::new(
+                GizmoHandleKind::Center,
+                GizmoHandleShape::Sphere {
+                    radius: ROTATE_INNER_RADIUS,
+                },
+            ),
             ChildOf(parent),
             GizmoOf(target),
             GizmoRoot(parent),
@@ -271,6 +277,13 @@ fn build_axis_ring(
             axis,
             RotateGizmo,
             GizmoMesh,
+            GizmoHandle::new(
+                GizmoHandleKind::Ring,
+                GizmoHandleShape::Circle {
+                    radius: RING_RADIUS,
+                    thickness: RING_THICKNESS,
+                },
+            ),
             GizmoOf(target),
             ChildOf(parent),
             GizmoRoot(parent),
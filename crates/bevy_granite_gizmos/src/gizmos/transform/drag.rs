@@ -1,6 +1,6 @@
 use super::TransformGizmo;
 use crate::{
-    gizmos::{GizmoConfig, GizmoOf, GizmoRoot, GizmoSnap, GizmoMode},
+    gizmos::{GizmoConfig, GizmoMode, GizmoOf, GizmoRoot, GizmoSnap},
     input::GizmoAxis,
     selection::{ActiveSelection, RequestDuplicateAllSelectionEvent, Selected},
     GizmoCamera,
@@ -8,12 +8,17 @@ use crate::{
 use bevy::{
     asset::Assets,
     ecs::{
-        component::Component, entity::ContainsEntity, hierarchy::{ChildOf, Children}, message::MessageWriter, observer::On, system::Commands
+        component::Component,
+        entity::ContainsEntity,
+        hierarchy::{ChildOf, Children},
+        message::MessageWriter,
+        observer::On,
+        system::Commands,
     },
     gizmos::{retained::Gizmo, GizmoAsset},
     picking::events::{Drag, DragEnd, DragStart, Pointer, Press},
     prelude::{
-        Entity, GlobalTransform, Query, Quat, Res, ResMut, Resource, Transform, Vec3, With, Without,
+        Entity, GlobalTransform, Quat, Query, Res, ResMut, Resource, Transform, Vec3, With, Without,
     },
 };
 use bevy_granite_core::UserInput;
@@ -61,7 +66,7 @@ pub fn drag_transform_gizmo(
         );
         return;
     };
-    
+
     let Ok(gizmo_config) = gizmo_config_query.get(gizmo_root.0) else {
         log!(
             LogType::Editor,
@@ -165,23 +170,15 @@ pub fn drag_transform_gizmo(
     let (active_axis, normal) = match typ {
         TransformGizmo::Axis => {
             let axis_vec = match gizmo_config.mode() {
-                GizmoMode::Local => {
-                    target_rotation * axis.to_vec3()
-                }
-                GizmoMode::Global => {
-                    axis.to_vec3()
-                }
+                GizmoMode::Local => target_rotation * axis.to_vec3(),
+                GizmoMode::Global => axis.to_vec3(),
             };
             (axis_vec, camera_transform.forward().as_vec3())
         }
         TransformGizmo::Plane => {
             let plane_normal = match gizmo_config.mode() {
-                GizmoMode::Local => {
-                    target_rotation * axis.to_vec3()
-                }
-                GizmoMode::Global => {
-                    axis.to_vec3()
-                }
+                GizmoMode::Local => target_rotation * axis.to_vec3(),
+                GizmoMode::Global => axis.to_vec3(),
             };
             (plane_normal, plane_normal)
         }
@@ -198,28 +195,38 @@ pub fn drag_transform_gizmo(
 
     let hit = click_ray.get_point(click_distance);
     let raw_delta = hit - current_world_pos;
-    
+    let target_position = current_world_pos + drag_offset.offset();
+    let step = gizmo_snap.transform_step(user_input.ctrl_left.any);
+    // Global moves land on the world grid, local moves go in steps along the rotated axes
+    let grid = step > 0.0 && gizmo_config.mode() == GizmoMode::Global;
+
     let world_delta = match typ {
         TransformGizmo::Axis => {
             let axis_normalized = active_axis.normalize_or_zero();
             let projection = raw_delta.dot(axis_normalized);
-            
-            let snapped_distance = if gizmo_snap.transform_value == 0.0 {
-                projection
+
+            let snapped_distance = if grid {
+                let along = target_position.dot(axis_normalized);
+                snap_value(along + projection, step) - along
             } else {
-                (projection / gizmo_snap.transform_value).round() * gizmo_snap.transform_value
+                snap_value(projection, step)
             };
-            
+
             axis_normalized * snapped_distance
         }
         TransformGizmo::Plane => {
             let plane_normal_normalized = normal.normalize_or_zero();
             let normal_component = raw_delta.dot(plane_normal_normalized);
             let projected = raw_delta - (plane_normal_normalized * normal_component);
-            snap_gizmo(projected, gizmo_snap.transform_value)
+            if grid {
+                let delta = snap_gizmo(target_position + projected, step) - target_position;
+                delta - plane_normal_normalized * delta.dot(plane_normal_normalized)
+            } else {
+                snap_gizmo(projected, step)
+            }
         }
     };
-    
+
     // Apply the delta to all root selected entities
     let mut world_delta = world_delta;
     if world_delta.length() > 0.0 {
@@ -236,7 +243,8 @@ pub fn drag_transform_gizmo(
         }
     }
 
-    if user_input.ctrl_left.any {
+    // Alt drags the camera along, Ctrl is taken by snapping
+    if user_input.alt_left.any {
         if let Ok(mut camera_transform) = objects.get_mut(c_entity) {
             camera_transform.translation += world_delta;
         }
@@ -321,10 +329,18 @@ pub fn dragstart_transform_gizmo(
         return;
     };
     log!("Attempting Drag Duplicate");
-    dispatch.write(RequestDuplicateAllSelectionEvent);
+    dispatch.write(RequestDuplicateAllSelectionEvent::default());
     duplication_state.just_duplicated = true;
 }
 
+fn snap_value(value: f32, inc: f32) -> f32 {
+    if inc == 0.0 {
+        value
+    } else {
+        (value / inc).round() * inc
+    }
+}
+
 fn snap_gizmo(value: Vec3, inc: f32) -> Vec3 {
     if inc == 0.0 {
         value
@@ -351,7 +367,7 @@ pub fn draw_axis_lines(
     if let GizmoAxis::All = axis {
         return;
     }
-    
+
     let Ok(gizmo_config) = gizmo_config_query.get(gizmo_root.0) else {
         log! {
             LogType::Editor,
@@ -362,7 +378,7 @@ pub fn draw_axis_lines(
         };
         return;
     };
-    
+
     let Ok(origin) = origin.get(root.get()) else {
         log! {
             LogType::Editor,
@@ -373,13 +389,19 @@ pub fn draw_axis_lines(
         };
         return;
     };
-    
+
     let entity_rotation = origin.to_scale_rotation_translation().1;
-    
+
     let mut asset = GizmoAsset::new();
     match transform {
         TransformGizmo::Axis => {
-            render_line(&mut asset, axis, origin, entity_rotation, gizmo_config.mode());
+            render_line(
+                &mut asset,
+                axis,
+                origin,
+                entity_rotation,
+                gizmo_config.mode(),
+            );
         }
         TransformGizmo::Plane => {
             let (a, b) = axis.plane();
@@ -400,8 +422,8 @@ pub fn draw_axis_lines(
 }
 
 fn render_line(
-    asset: &mut GizmoAsset, 
-    axis: &GizmoAxis, 
+    asset: &mut GizmoAsset,
+    axis: &GizmoAxis,
     origin: &GlobalTransform,
     entity_rotation: Quat,
     mode: GizmoMode,
@@ -409,16 +431,12 @@ fn render_line(
     let step = 10.0;
     let max_distance = 1000.0;
     let mut current = -max_distance;
-    
+
     let axis_direction = match mode {
-        GizmoMode::Local => {
-            entity_rotation * axis.to_vec3()
-        }
-        GizmoMode::Global => {
-            axis.to_vec3()
-        }
+        GizmoMode::Local => entity_rotation * axis.to_vec3(),
+        GizmoMode::Global => axis.to_vec3(),
     };
-    
+
     while current < max_distance {
         asset.line(
             origin.translation() + axis_direction * current,
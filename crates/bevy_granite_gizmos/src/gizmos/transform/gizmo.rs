@@ -16,7 +16,10 @@ use bevy_granite_logging::{
 };
 
 use crate::{
-    gizmos::{GizmoConfig, GizmoMesh, GizmoMode, GizmoOf, GizmoRoot},
+    gizmos::{
+        GizmoConfig, GizmoHandle, GizmoHandleKind, GizmoHandleShape, GizmoMesh, GizmoMode, GizmoOf,
+        GizmoRoot,
+    },
     input::GizmoAxis,
 };
 
@@ -185,6 +188,12 @@ fn build_gizmo_sphere(
             axis,
             TransformGizmo::Axis,
             GizmoMesh,
+            GizmoHandle::new(
+                GizmoHandleKind::Center,
+                GizmoHandleShape::Sphere {
+                    radius: TRANSFORM_INNER_RADIUS,
+                },
+            ),
             GizmoOf(root),
             ChildOf(parent),
         ))
@@ -238,6 +247,15 @@ fn build_axis_cylinder(
         .insert(axis)
         .insert(TransformGizmo::Axis)
         .insert(GizmoMesh)
+        // Down the line and on to the tip of the cone
+        .insert(GizmoHandle::new(
+            GizmoHandleKind::Axis,
+            GizmoHandleShape::Segment {
+                start: Vec3::NEG_Y * (TRANSFORM_LINE_LENGTH * 0.5),
+                end: Vec3::Y * ((TRANSFORM_LINE_LENGTH + TRANSFORM_HANDLE_LENGTH) * 0.5),
+                thickness: TRANSFORM_LINE_WIDTH,
+            },
+        ))
         .insert(ChildOf(parent))
         .observe(super::drag::calculate_drag_offset)
         .observe(super::drag::drag_end_cleanup)
@@ -302,6 +320,12 @@ fn build_axis_plane(
             axis,
             TransformGizmo::Plane,
             GizmoMesh,
+            GizmoHandle::new(
+                GizmoHandleKind::Plane,
+                GizmoHandleShape::Square {
+                    half_size: TRANSFORM_LINE_LENGTH * 0.33 * 0.5,
+                },
+            ),
             ChildOf(parent),
         ))
         .observe(super::drag::calculate_drag_offset)
@@ -351,7 +375,7 @@ pub fn update_gizmo_rotation_for_mode(
     for (mut gizmo_transform, gizmo_of, config) in gizmo_query.iter_mut() {
         if let Ok(parent_global_transform) = parent_query.get(gizmo_of.0) {
             let parent_rotation = parent_global_transform.to_scale_rotation_translation().1;
-            
+
             match config.mode() {
                 GizmoMode::Global => {
                     gizmo_transform.rotation = parent_rotation.inverse();
@@ -1,5 +1,9 @@
-pub mod gizmo;
 pub mod drag;
+pub mod gizmo;
+pub mod sockets;
+pub mod surface;
 
+pub use drag::*;
 pub use gizmo::*;
-pub use drag::*;
\ No newline at end of file
+pub use sockets::*;
+pub use surface::*;
@@ -0,0 +1,58 @@
+use crate::{
+    gizmos::GizmoSnap,
+    selection::{ActiveSelection, Selected},
+};
+use bevy::{
+    ecs::hierarchy::{ChildOf, Children},
+    prelude::{Entity, GlobalTransform, Query, Res, Transform, With, Without},
+};
+use bevy_granite_core::{find_kit_snap, KitSockets};
+use std::collections::HashSet;
+
+/// While dragging a single kit piece with the move gizmo, align it to the closest compatible socket of another piece
+/// The drag follows the cursor again each frame, so moving the cursor away releases the snap
+pub fn snap_to_kit_sockets(
+    gizmo_snap: Res<GizmoSnap>,
+    mut active: Query<
+        (Entity, &mut Transform, &KitSockets, Option<&ChildOf>),
+        With<ActiveSelection>,
+    >,
+    other_selected: Query<Entity, (With<Selected>, Without<ActiveSelection>)>,
+    pieces: Query<(Entity, &GlobalTransform, &KitSockets)>,
+    global_transforms: Query<&GlobalTransform>,
+    children: Query<&Children>,
+) {
+    if !gizmo_snap.kit_sockets || !other_selected.is_empty() {
+        return;
+    }
+    let Ok((entity, mut transform, sockets, child_of)) = active.single_mut() else {
+        return;
+    };
+
+    // Transform was just moved, its GlobalTransform is from last frame
+    let parent_global = child_of.and_then(|child_of| global_transforms.get(child_of.parent()).ok());
+    let world = match parent_global {
+        Some(parent_global) => parent_global.mul_transform(*transform).compute_transform(),
+        None => *transform,
+    };
+
+    // Children move with the piece, they can't be snapped to
+    let moving: HashSet<Entity> = std::iter::once(entity)
+        .chain(children.iter_descendants(entity))
+        .collect();
+    let Some(snap) = find_kit_snap(
+        &world,
+        sockets,
+        pieces
+            .iter()
+            .filter(|(candidate, ..)| !moving.contains(candidate)),
+        gizmo_snap.kit_socket_distance,
+    ) else {
+        return;
+    };
+
+    *transform = match parent_global {
+        Some(parent_global) => GlobalTransform::from(snap.transform).reparented_to(parent_global),
+        None => snap.transform,
+    };
+}
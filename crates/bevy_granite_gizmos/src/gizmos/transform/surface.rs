@@ -0,0 +1,100 @@
+use crate::{
+    gizmos::GizmoSnap,
+    selection::{ActiveSelection, Selected},
+    GizmoCamera,
+};
+use bevy::{
+    camera::Camera,
+    ecs::hierarchy::{ChildOf, Children},
+    picking::mesh_picking::ray_cast::{MeshRayCast, MeshRayCastSettings},
+    prelude::{Entity, GlobalTransform, Quat, Query, Res, Transform, Vec3, With, Without},
+};
+use bevy_granite_core::{EditorIgnore, IconProxy, UserInput};
+use std::collections::HashSet;
+
+type MovedEntity = (Entity, &'static mut Transform, Option<&'static ChildOf>);
+
+/// While dragging with the move gizmo and Surface on, drop the active entity where the cursor hits a scene mesh
+/// With Align on its up axis also turns to the hit normal, keeping its heading. The rest of the selection follows
+/// When the cursor is over empty space the drag moves along the gizmo axis as usual
+#[allow(clippy::too_many_arguments)]
+pub fn snap_to_surface(
+    gizmo_snap: Res<GizmoSnap>,
+    user_input: Res<UserInput>,
+    camera_query: Query<(&GlobalTransform, &Camera), With<GizmoCamera>>,
+    mut ray_cast: MeshRayCast,
+    mut active: Query<MovedEntity, With<ActiveSelection>>,
+    mut other_selected: Query<MovedEntity, (With<Selected>, Without<ActiveSelection>)>,
+    global_transforms: Query<&GlobalTransform>,
+    children: Query<&Children>,
+    ignored: Query<&EditorIgnore>,
+    icons: Query<(), With<IconProxy>>,
+) {
+    if !gizmo_snap.surface || user_input.mouse_over_egui {
+        return;
+    }
+    let Ok((entity, mut transform, child_of)) = active.single_mut() else {
+        return;
+    };
+    let Ok((camera_transform, camera)) = camera_query.single() else {
+        return;
+    };
+    let Ok(ray) = camera.viewport_to_world(camera_transform, user_input.mouse_pos) else {
+        return;
+    };
+
+    // The selection and its children move with the cursor, they can't be dropped onto
+    let selected: HashSet<Entity> = other_selected.iter().map(|(entity, ..)| entity).collect();
+    let mut moving: HashSet<Entity> = HashSet::new();
+    for root in std::iter::once(entity).chain(selected.iter().copied()) {
+        moving.extend(std::iter::once(root).chain(children.iter_descendants(root)));
+    }
+    let filter = |candidate: Entity| {
+        !moving.contains(&candidate)
+            && !icons.contains(candidate)
+            && !ignored
+                .get(candidate)
+                .is_ok_and(|ignore| ignore.contains(EditorIgnore::PICKING))
+    };
+    let settings = MeshRayCastSettings::default()
+        .with_filter(&filter)
+        .always_early_exit();
+    let Some((_, hit)) = ray_cast.cast_ray(ray, &settings).first() else {
+        return;
+    };
+    let (point, normal) = (hit.point, hit.normal.normalize_or(Vec3::Y));
+
+    // Transform was just moved, its GlobalTransform is from last frame
+    let parent_global = child_of.and_then(|child_of| global_transforms.get(child_of.parent()).ok());
+    let world = match parent_global {
+        Some(parent_global) => parent_global.mul_transform(*transform).compute_transform(),
+        None => *transform,
+    };
+
+    let mut placed = world.with_translation(point);
+    if gizmo_snap.surface_align {
+        placed.rotation =
+            (Quat::from_rotation_arc(world.up().as_vec3(), normal) * world.rotation).normalize();
+    }
+    let world_delta = placed.translation - world.translation;
+
+    *transform = match parent_global {
+        Some(parent_global) => GlobalTransform::from(placed).reparented_to(parent_global),
+        None => placed,
+    };
+
+    // Other roots of the selection keep their offset to the active entity
+    for (_, mut other_transform, other_child_of) in other_selected.iter_mut() {
+        let parent = other_child_of.map(|child_of| child_of.parent());
+        if parent.is_some_and(|parent| parent == entity || selected.contains(&parent)) {
+            continue;
+        }
+        let delta = match parent.and_then(|parent| global_transforms.get(parent).ok()) {
+            Some(parent_global) => {
+                parent_global.to_scale_rotation_translation().1.inverse() * world_delta
+            }
+            None => world_delta,
+        };
+        other_transform.translation += delta;
+    }
+}
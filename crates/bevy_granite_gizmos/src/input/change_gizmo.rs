@@ -1,6 +1,6 @@
-use crate::gizmos::{NewGizmoType, GizmoType};
+use crate::gizmos::{GizmoConfig, GizmoType, NewGizmoConfig, NewGizmoType};
 use bevy::{
-    ecs::system::{Res, ResMut},
+    ecs::system::{Query, Res, ResMut},
     input::keyboard::KeyCode,
 };
 use bevy_granite_core::{InputTypes, UserInput};
@@ -53,3 +53,29 @@ pub fn watch_gizmo_change(user_input: Res<UserInput>, mut selected_gizmo: ResMut
         );
     }
 }
+
+/// X flips move and rotate gizmos between the entity's own axes and the world axes
+pub fn watch_gizmo_mode_change(
+    user_input: Res<UserInput>,
+    mut config: ResMut<NewGizmoConfig>,
+    mut gizmos: Query<&mut GizmoConfig>,
+) {
+    let toggle = user_input.key_x.just_pressed
+        && user_input.current_button_inputs.len() == 1
+        && !user_input.mouse_over_egui;
+    if !toggle {
+        return;
+    }
+
+    config.mode = config.mode.toggled();
+    for mut gizmo in gizmos.iter_mut() {
+        gizmo.set_mode(config.mode);
+    }
+    log!(
+        LogType::Editor,
+        LogLevel::Info,
+        LogCategory::Input,
+        "(shortcut) Toggling gizmo mode to {}",
+        config.mode.name()
+    );
+}
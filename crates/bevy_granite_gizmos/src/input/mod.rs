@@ -2,6 +2,6 @@ pub mod change_gizmo;
 pub mod drag;
 pub mod plugin;
 
-pub use plugin::InputPlugin;
+pub use change_gizmo::{watch_gizmo_change, watch_gizmo_mode_change};
 pub use drag::{DragState, GizmoAxis};
-pub use change_gizmo::{watch_gizmo_change};
\ No newline at end of file
+pub use plugin::InputPlugin;
@@ -1,4 +1,4 @@
-use super::{watch_gizmo_change, DragState};
+use super::{watch_gizmo_change, watch_gizmo_mode_change, DragState};
 use crate::{is_gizmos_active, GizmoVisibilityState};
 use bevy::{
     app::{App, Plugin, Update},
@@ -17,6 +17,9 @@ impl Plugin for InputPlugin {
             //
             // Schedule system
             //
-            .add_systems(Update, (watch_gizmo_change,).run_if(is_gizmos_active));
+            .add_systems(
+                Update,
+                (watch_gizmo_change, watch_gizmo_mode_change).run_if(is_gizmos_active),
+            );
     }
 }
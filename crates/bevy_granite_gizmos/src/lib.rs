@@ -14,10 +14,11 @@ mod ui;
 // Re-export
 pub use camera::GizmoCamera;
 pub use gizmos::{
-    despawn_rotate_gizmo, GizmoChildren, GizmoMesh, GizmoSnap, GizmoType, NewGizmoConfig,
+    despawn_rotate_gizmo, GizmoChildren, GizmoHandle, GizmoHandleKind, GizmoHandleShape, GizmoMesh,
+    GizmoMode, GizmoPickCandidates, GizmoPickSettings, GizmoSnap, GizmoType, NewGizmoConfig,
     RotateGizmo, TransformGizmo,
 };
-pub use input::{watch_gizmo_change, DragState, GizmoAxis};
+pub use input::{watch_gizmo_change, watch_gizmo_mode_change, DragState, GizmoAxis};
 pub use selection::{
     ActiveSelection, EntityEvents, RequestDuplicateAllSelectionEvent, RequestDuplicateEntityEvent,
     Selected,
@@ -7,25 +7,35 @@ use bevy::{
         query::With,
         system::{Commands, Query},
     },
+    math::Vec3,
     mesh::{Mesh, Mesh3d},
-    prelude::{AppTypeRegistry, ChildOf, Children, MessageReader, ReflectComponent, Res, World},
+    prelude::{
+        AppTypeRegistry, ChildOf, Children, GlobalTransform, MessageReader, Name, ReflectComponent,
+        Res, Transform, World,
+    },
     render::sync_world::SyncToRenderWorld,
 };
 use bevy_granite_core::{
-    entities::GraniteType, EditorIgnore, HasRuntimeData, IconProxy, IdentityData,
+    entities::{Empty, GraniteType},
+    EditorCapabilities, EditorIgnore, HasRuntimeData, IconProxy, IdentityData, SpawnSource,
 };
 use bevy_granite_logging::{
     config::{LogCategory, LogLevel, LogType},
     log,
 };
+use std::collections::HashSet;
 use uuid::Uuid;
 
 pub fn duplicate_entity_system(
     mut commands: Commands,
     mut duplicate_event_reader: MessageReader<RequestDuplicateEntityEvent>,
     type_registry: Res<AppTypeRegistry>,
+    capabilities: Res<EditorCapabilities>,
 ) {
     for event in duplicate_event_reader.read() {
+        if !capabilities.can_edit_components("duplicate") {
+            continue;
+        }
         log!(
             LogType::Editor,
             LogLevel::Info,
@@ -51,9 +61,13 @@ pub fn duplicate_all_selection_system(
     mut commands: Commands,
     mut duplicate_event_reader: MessageReader<RequestDuplicateAllSelectionEvent>,
     type_registry: Res<AppTypeRegistry>,
+    capabilities: Res<EditorCapabilities>,
     selected: Query<Entity, With<Selected>>,
 ) {
-    for _event in duplicate_event_reader.read() {
+    for event in duplicate_event_reader.read() {
+        if !capabilities.can_edit_components("duplicate") {
+            continue;
+        }
         log!(
             LogType::Editor,
             LogLevel::Info,
@@ -61,21 +75,117 @@ pub fn duplicate_all_selection_system(
             "Duplicate All Selection Event"
         );
         let registry = type_registry.clone();
-        let entities_to_duplicate: Vec<Entity> = selected.iter().collect();
+        let group = event.group;
+        let selection: Vec<Entity> = selected.iter().collect();
         commands.queue(move |world: &mut World| {
-            for entity in entities_to_duplicate {
-                let original_parent = world
-                    .get_entity(entity)
-                    .ok()
-                    .and_then(|entity_ref| entity_ref.get::<ChildOf>())
-                    .map(|parent| parent.parent());
-
-                duplicate_entity_recursive(world, entity, original_parent, &registry);
+            // Children come along with their parent, duplicating them again would copy them twice
+            // and put the second copy under the original parent
+            let duplicates: Vec<Entity> = selection_roots(world, &selection)
+                .into_iter()
+                .filter_map(|entity| {
+                    let original_parent = world
+                        .get_entity(entity)
+                        .ok()
+                        .and_then(|entity_ref| entity_ref.get::<ChildOf>())
+                        .map(|parent| parent.parent());
+
+                    duplicate_entity_recursive(world, entity, original_parent, &registry)
+                })
+                .collect();
+
+            if group && !duplicates.is_empty() {
+                group_duplicates(world, &duplicates);
             }
         });
     }
 }
 
+/// Selected entities without a selected ancestor
+fn selection_roots(world: &World, selection: &[Entity]) -> Vec<Entity> {
+    let selected: HashSet<Entity> = selection.iter().copied().collect();
+    selection
+        .iter()
+        .copied()
+        .filter(|&entity| {
+            let mut current = entity;
+            while let Some(parent) = world.get::<ChildOf>(current).map(ChildOf::parent) {
+                if selected.contains(&parent) {
+                    return false;
+                }
+                current = parent;
+            }
+            true
+        })
+        .collect()
+}
+
+/// Parents the duplicates under a new Empty at their center, keeping where they are in the world
+/// The group goes under their shared parent, or the scene root when they have different parents
+fn group_duplicates(world: &mut World, duplicates: &[Entity]) {
+    let parents: Vec<Option<Entity>> = duplicates
+        .iter()
+        .map(|&entity| world.get::<ChildOf>(entity).map(ChildOf::parent))
+        .collect();
+    let shared_parent = parents.iter().all(|parent| *parent == parents[0]);
+
+    // Local transforms when they share a parent, world transforms otherwise
+    let transforms: Vec<Transform> = duplicates
+        .iter()
+        .map(|&entity| {
+            if shared_parent {
+                world.get::<Transform>(entity).copied().unwrap_or_default()
+            } else {
+                world
+                    .get::<GlobalTransform>(entity)
+                    .map(GlobalTransform::compute_transform)
+                    .unwrap_or_default()
+            }
+        })
+        .collect();
+    let center = transforms
+        .iter()
+        .map(|transform| transform.translation)
+        .sum::<Vec3>()
+        / transforms.len() as f32;
+
+    let mut commands = world.commands();
+    let group = Empty::default()
+        .spawn_from_new_identity(&mut commands, Transform::from_translation(center));
+    world.flush();
+
+    let source = world.get::<SpawnSource>(duplicates[0]).cloned();
+    let mut group_mut = world.entity_mut(group);
+    group_mut.insert(Name::new("Group"));
+    if let Some(mut identity) = group_mut.get_mut::<IdentityData>() {
+        identity.name = "Group".to_string();
+    }
+    if let Some(source) = source {
+        group_mut.insert(source);
+    }
+    if let Some(parent) = parents[0].filter(|_| shared_parent) {
+        group_mut.insert(ChildOf(parent));
+    }
+
+    for (&entity, transform) in duplicates.iter().zip(transforms) {
+        world.entity_mut(entity).insert((
+            ChildOf(group),
+            Transform {
+                translation: transform.translation - center,
+                ..transform
+            },
+        ));
+    }
+
+    log!(
+        LogType::Editor,
+        LogLevel::OK,
+        LogCategory::Entity,
+        "Grouped {} duplicates under {}",
+        duplicates.len(),
+        group
+    );
+}
+
 fn duplicate_entity_recursive(
     world: &mut World,
     entity_to_duplicate: Entity,
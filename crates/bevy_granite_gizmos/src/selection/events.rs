@@ -17,5 +17,9 @@ pub struct RequestDuplicateEntityEvent {
     pub entity: Entity,
 }
 
-#[derive(Message)]
-pub struct RequestDuplicateAllSelectionEvent;
+/// Duplicates the selection. Selected children of selected parents are duplicated once, with their parent
+#[derive(Message, Default)]
+pub struct RequestDuplicateAllSelectionEvent {
+    /// Parent the duplicates under a new Empty group
+    pub group: bool,
+}
@@ -242,20 +242,21 @@ pub fn editor_gizmos_ui(
                                     .range(0.0..=360.0),
                             )
                             .changed();
+                        ui.add_space(small_spacing);
+                        ui.checkbox(&mut gizmo_snap.always, "Always")
+                            .on_hover_text("Snap without holding Ctrl. Hold Ctrl while dragging to flip");
                         ui.add_space(spacing);
-                        egui::ComboBox::new("GizmoMode", "")
-                            .selected_text(match mode {
-                                GizmoMode::Local => "Local",
-                                GizmoMode::Global => "Global",
-                            })
-                            .show_ui(ui, |ui| {
-                                changed |= ui
-                                    .selectable_value(&mut mode, GizmoMode::Local, "Local")
-                                    .changed();
-                                changed |= ui
-                                    .selectable_value(&mut mode, GizmoMode::Global, "Global")
-                                    .changed();
-                            });
+                        ui.checkbox(&mut gizmo_snap.kit_sockets, "Sockets")
+                            .on_hover_text("Snap pieces with KitSockets to nearby compatible sockets while moving");
+                        ui.add_space(spacing);
+                        ui.checkbox(&mut gizmo_snap.surface, "Surface")
+                            .on_hover_text("Drop moved entities onto the mesh under the cursor, i.e. props onto terrain");
+                        ui.add_enabled_ui(gizmo_snap.surface, |ui| {
+                            ui.checkbox(&mut gizmo_snap.surface_align, "Align")
+                                .on_hover_text("Turn the entity's up axis to the surface normal");
+                        });
+                        ui.add_space(spacing);
+                        changed |= gizmo_mode_button(ui, &mut mode);
                     }
 
                     if matches!(active, GizmoType::Rotate) {
@@ -269,21 +270,12 @@ pub fn editor_gizmos_ui(
                                     .range(0.0..=360.0),
                             )
                             .changed();
+                        ui.add_space(small_spacing);
+                        ui.checkbox(&mut gizmo_snap.always, "Always")
+                            .on_hover_text("Snap without holding Ctrl. Hold Ctrl while dragging to flip");
 
                         ui.add_space(spacing);
-                        egui::ComboBox::new("GizmoMode", "")
-                            .selected_text(match mode {
-                                GizmoMode::Local => "Local",
-                                GizmoMode::Global => "Global",
-                            })
-                            .show_ui(ui, |ui| {
-                                changed |= ui
-                                    .selectable_value(&mut mode, GizmoMode::Local, "Local")
-                                    .changed();
-                                changed |= ui
-                                    .selectable_value(&mut mode, GizmoMode::Global, "Global")
-                                    .changed();
-                            });
+                        changed |= gizmo_mode_button(ui, &mut mode);
                     }
                 });
                 if changed {
@@ -309,3 +301,19 @@ pub fn editor_gizmos_ui(
             },
         );
 }
+
+/// Flips between entity and world axes, same as pressing X in the viewport
+fn gizmo_mode_button(ui: &mut egui::Ui, mode: &mut GizmoMode) -> bool {
+    let hover = match mode {
+        GizmoMode::Local => "Axes follow the entity's rotation. Click or press X for world axes",
+        GizmoMode::Global => "Axes follow the world. Click or press X for the entity's axes",
+    };
+    let clicked = ui
+        .selectable_label(*mode == GizmoMode::Local, mode.name())
+        .on_hover_text(hover)
+        .clicked();
+    if clicked {
+        *mode = mode.toggled();
+    }
+    clicked
+}
@@ -1,20 +1,61 @@
 use crate::entry::LogEntry;
 use lazy_static::lazy_static;
+use std::collections::VecDeque;
 use std::sync::{Arc, Mutex};
 
 const MAX_LOG_ENTRIES: usize = 7_500;
 
-lazy_static! {
-    pub static ref LOG_BUFFER: Arc<Mutex<Vec<LogEntry>>> = Arc::new(Mutex::new(Vec::new()));
+// Ring buffer of the most recent entries, read by the editor console and crash reports
+#[derive(Debug, Default)]
+pub struct LogBuffer {
+    entries: VecDeque<LogEntry>,
+    // Entries ever pushed, so readers can tell new ones apart after old ones are dropped or cleared
+    pushed: u64,
 }
 
-pub fn push_log(entry: LogEntry) {
-    let mut buffer = LOG_BUFFER.lock().unwrap();
-    buffer.push(entry);
+impl LogBuffer {
+    pub fn push(&mut self, entry: LogEntry) {
+        if self.entries.len() == MAX_LOG_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+        self.pushed += 1;
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &LogEntry> + ExactSizeIterator {
+        self.entries.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn pushed(&self) -> u64 {
+        self.pushed
+    }
 
-    let len = buffer.len();
-    if len > MAX_LOG_ENTRIES {
-        buffer.drain(0..(len - MAX_LOG_ENTRIES));
+    pub fn capacity() -> usize {
+        MAX_LOG_ENTRIES
     }
 }
 
+lazy_static! {
+    pub static ref LOG_BUFFER: Arc<Mutex<LogBuffer>> = Arc::new(Mutex::new(LogBuffer::default()));
+}
+
+pub fn push_log(entry: LogEntry) {
+    LOG_BUFFER.lock().unwrap().push(entry);
+}
+
+// Empties the buffer, i.e. the console's Clear button. Files and stdout keep everything
+pub fn clear_logs() {
+    LOG_BUFFER.lock().unwrap().clear();
+}
@@ -1,26 +1,102 @@
 use crate::{LogCategory, LogEntry};
-use std::fs::OpenOptions;
+use lazy_static::lazy_static;
+use std::fs::{File, OpenOptions};
 use std::io::Write;
-use std::path::PathBuf;
-use std::sync::OnceLock;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 
-static LOG_FILE_PATH: OnceLock<PathBuf> = OnceLock::new();
+// Where and how much the file sink writes. Once the file passes max_bytes it becomes app.log.1,
+// app.log.1 becomes app.log.2 and so on, keeping `rotations` old files
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileLogConfig {
+    pub path: PathBuf,
+    pub max_bytes: u64,
+    pub rotations: u32,
+}
+
+impl Default for FileLogConfig {
+    fn default() -> Self {
+        Self {
+            path: dirs::config_dir()
+                .unwrap_or_else(|| std::env::current_dir().unwrap())
+                .join("bevy_granite_logging")
+                .join("app.log"),
+            max_bytes: 5 * 1024 * 1024,
+            rotations: 3,
+        }
+    }
+}
 
-fn get_log_path() -> &'static PathBuf {
-    LOG_FILE_PATH.get_or_init(|| {
-        let path = dirs::config_dir()
-            .unwrap_or_else(|| std::env::current_dir().unwrap())
-            .join("bevy_granite_logging")
-            .join("app.log");
+#[derive(Default)]
+struct FileSink {
+    // None turns the sink off
+    config: Option<FileLogConfig>,
+    file: Option<File>,
+    size: u64,
+}
+
+impl FileSink {
+    fn open(&mut self) -> Result<&mut File, std::io::Error> {
+        if self.file.is_none() {
+            let Some(config) = &self.config else {
+                return Err(std::io::Error::other("file logging is off"));
+            };
+            if let Some(parent) = config.path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&config.path)?;
+            self.size = file.metadata().map(|meta| meta.len()).unwrap_or(0);
+            self.file = Some(file);
+        }
+        Ok(self.file.as_mut().unwrap())
+    }
 
-        if let Some(parent) = path.parent() {
-            if let Err(e) = std::fs::create_dir_all(parent) {
-                eprintln!("Failed to create log directory: {}", e);
+    fn rotate(&mut self) -> Result<(), std::io::Error> {
+        self.file = None;
+        let Some(config) = &self.config else {
+            return Ok(());
+        };
+        if config.rotations == 0 {
+            return std::fs::remove_file(&config.path);
+        }
+        for index in (1..config.rotations).rev() {
+            let from = rotated_path(&config.path, index);
+            if from.exists() {
+                std::fs::rename(&from, rotated_path(&config.path, index + 1))?;
             }
         }
+        std::fs::rename(&config.path, rotated_path(&config.path, 1))
+    }
+}
+
+lazy_static! {
+    static ref FILE_SINK: Mutex<FileSink> = Mutex::new(FileSink {
+        config: Some(FileLogConfig::default()),
+        ..Default::default()
+    });
+}
 
-        path
-    })
+fn rotated_path(path: &Path, index: u32) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{}", index));
+    PathBuf::from(name)
+}
+
+// Sends log files elsewhere or changes their rotation, None turns file logging off. Stdout and the buffer are unaffected
+// Call before adding the Granite plugins to catch the session start
+pub fn set_file_logging(config: Option<FileLogConfig>) {
+    let mut sink = FILE_SINK.lock().unwrap();
+    sink.config = config;
+    sink.file = None;
+    sink.size = 0;
+}
+
+// Current file sink settings, None when file logging is off
+pub fn file_logging() -> Option<FileLogConfig> {
+    FILE_SINK.lock().unwrap().config.clone()
 }
 
 pub fn write_to_file(entry: &LogEntry) -> Result<(), std::io::Error> {
@@ -28,16 +104,21 @@ pub fn write_to_file(entry: &LogEntry) -> Result<(), std::io::Error> {
         return Ok(()); // Skip blank log entries
     }
 
-    let mut file = OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(get_log_path())?;
+    let mut sink = FILE_SINK.lock().unwrap();
+    let Some(max_bytes) = sink.config.as_ref().map(|config| config.max_bytes) else {
+        return Ok(());
+    };
 
     let log_line = format!(
         "{}{:?} {:?} {:?} {}\n",
         entry.timestamp, entry.log_type, entry.level, entry.category, entry.message
     );
 
-    file.write_all(log_line.as_bytes())?;
+    sink.open()?;
+    if sink.size > 0 && sink.size + log_line.len() as u64 > max_bytes {
+        sink.rotate()?;
+    }
+    sink.open()?.write_all(log_line.as_bytes())?;
+    sink.size += log_line.len() as u64;
     Ok(())
 }
@@ -1,16 +1,15 @@
 pub mod buffer;
 pub mod config;
 pub mod entry;
-pub mod macros;
 pub mod file;
+pub mod macros;
 pub mod output;
 
-
-pub use buffer::{push_log, LOG_BUFFER};
+pub use buffer::{clear_logs, push_log, LogBuffer, LOG_BUFFER};
 pub use config::{
     disable_log_category, disable_log_level, disable_log_type, setup_logging, LogCategory,
     LogLevel, LogType, RgbaColor,
 };
 pub use entry::LogEntry;
+pub use file::{file_logging, set_file_logging, FileLogConfig};
 pub use output::log;
-
@@ -2,7 +2,7 @@ use once_cell::sync::Lazy;
 use proc_macro::TokenStream;
 use quote::quote;
 use std::sync::Mutex;
-use syn::{parse_macro_input, DeriveInput,};
+use syn::{parse_macro_input, DeriveInput};
 
 static REGISTERED_COMPONENTS: Lazy<Mutex<Vec<(String, bool)>>> =
     Lazy::new(|| Mutex::new(Vec::new()));
@@ -91,68 +91,165 @@ pub fn register_editor_components(input: TokenStream) -> TokenStream {
 
 #[proc_macro_attribute]
 pub fn ui_callable_events(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(item as DeriveInput);
-    let name = &input.ident;
+    let mut input = parse_macro_input!(item as DeriveInput);
+    let name = input.ident.clone();
     let name_str = name.to_string();
 
-    // Extract field names and types from the struct
-    let (field_names, field_types): (Vec<_>, Vec<_>) = if let syn::Data::Struct(ref data_struct) = input.data {
-        if let syn::Fields::Named(ref fields_named) = data_struct.fields {
-            fields_named.named.iter().map(|field| {
-                let field_name = field.ident.as_ref().unwrap().to_string();
-                let field_type = &field.ty;
-                (field_name, field_type.clone())
-            }).unzip()
-        } else {
-            (Vec::new(), Vec::new())
+    // Extract field names, types and #[args] markers from the struct
+    // #[args] is not a real attribute, so strip it before re-emitting the struct
+    let mut field_names = Vec::new();
+    let mut field_types = Vec::new();
+    let mut field_has_args = Vec::new();
+    if let syn::Data::Struct(ref mut data_struct) = input.data {
+        if let syn::Fields::Named(ref mut fields_named) = data_struct.fields {
+            for field in fields_named.named.iter_mut() {
+                let has_args = field.attrs.iter().any(|attr| attr.path().is_ident("args"));
+                field.attrs.retain(|attr| !attr.path().is_ident("args"));
+                field_names.push(field.ident.as_ref().unwrap().to_string());
+                field_types.push(field.ty.clone());
+                field_has_args.push(has_args);
+            }
         }
-    } else {
-        (Vec::new(), Vec::new())
-    };
+    }
 
     // Generate event sender closures
     let event_senders = field_types.iter().map(|field_type| {
         quote! {
             Box::new(|world: &mut bevy::prelude::World| {
-                world.send_event(#field_type::default());
+                world.write_message(<#field_type>::default());
             }) as Box<dyn Fn(&mut bevy::prelude::World) + Send + Sync>
         }
     });
 
+    // Events marked #[args] get an editable form in the events tab
+    let event_args = field_types
+        .iter()
+        .zip(field_has_args.iter())
+        .map(|(field_type, has_args)| {
+            if *has_args {
+                quote! { Some(bevy_granite::prelude::UICallableEventArgs::new::<#field_type>()) }
+            } else {
+                quote! { None }
+            }
+        });
+
     let expanded = quote! {
         #input
-        
+
         impl bevy_granite_core::UICallableEventMarker for #name {}
-        
+
         impl bevy_granite_core::UICallableEventProvider for #name {
             fn get_event_names() -> &'static [&'static str] {
                 &[#(#field_names),*]
             }
-            
+
             fn get_struct_name() -> &'static str {
                 #name_str
             }
         }
-        
+
         impl #name {
             pub fn get_event_types() -> &'static [&'static str] {
                 &[#(stringify!(#field_types)),*]
             }
-            
+
             pub fn register_ui() {
                 let event_senders = vec![#(#event_senders),*];
+                let event_args = vec![#(#event_args),*];
                 let event_names: &'static [&'static str] = &[#(#field_names),*];
-                
+
                 // Use the registration function - this will be provided by the user's import
-                bevy_granite::prelude::register_ui_callable_events_with_senders(
+                bevy_granite::prelude::register_ui_callable_events_with_args(
                     #name_str,
                     event_names,
                     event_senders,
+                    event_args,
                 );
             }
         }
     };
-    
+
     TokenStream::from(expanded)
 }
 
+/// Derive the GraniteClass boilerplate so a game struct can be spawned, edited and saved by the editor
+/// Optional attribute: #[granite_class(name = "Spawn Point", abv = "Spawn", category = Gameplay, icon = "SpawnPoint.png")]
+/// category is a built in ClassCategory variant, or a string for a project category: category = "Vehicles"
+/// icon path is relative to the file the struct is declared in, like include_bytes!
+/// icon_path = "icons/spawn.png" loads the icon from the assets folder instead
+/// The struct still needs Component, Reflect, Serialize, Deserialize, Default and Clone
+#[proc_macro_derive(GraniteClass, attributes(granite_class))]
+pub fn derive_granite_class(item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as DeriveInput);
+    let name = &input.ident;
+    let name_str = name.to_string();
+
+    let mut type_name = name_str.clone();
+    let mut type_abv: Option<String> = None;
+    let mut category = quote! { bevy_granite_core::ClassCategory::Gameplay };
+    let mut icon: Option<syn::LitStr> = None;
+    let mut icon_path: Option<String> = None;
+
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("granite_class"))
+    {
+        let result = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                type_name = meta.value()?.parse::<syn::LitStr>()?.value();
+            } else if meta.path.is_ident("abv") {
+                type_abv = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+            } else if meta.path.is_ident("category") {
+                let value = meta.value()?;
+                category = if value.peek(syn::LitStr) {
+                    let custom = value.parse::<syn::LitStr>()?;
+                    quote! { bevy_granite_core::ClassCategory::Custom(#custom) }
+                } else {
+                    let variant = value.parse::<syn::Ident>()?;
+                    quote! { bevy_granite_core::ClassCategory::#variant }
+                };
+            } else if meta.path.is_ident("icon") {
+                icon = Some(meta.value()?.parse::<syn::LitStr>()?);
+            } else if meta.path.is_ident("icon_path") {
+                icon_path = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+            } else {
+                return Err(meta.error("expected `name`, `abv`, `category`, `icon` or `icon_path`"));
+            }
+            Ok(())
+        });
+        if let Err(e) = result {
+            return e.to_compile_error().into();
+        }
+    }
+    let type_abv = type_abv.unwrap_or_else(|| type_name.clone());
+
+    let icon_tokens = match icon {
+        Some(path) => {
+            let filename = std::path::Path::new(&path.value())
+                .file_name()
+                .map(|f| f.to_string_lossy().to_string())
+                .unwrap_or_else(|| path.value());
+            quote! { Some((#filename, include_bytes!(#path))) }
+        }
+        None => quote! { None },
+    };
+    let icon_path_tokens = match icon_path {
+        Some(path) => quote! { Some(#path) },
+        None => quote! { None },
+    };
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let expanded = quote! {
+        impl #impl_generics bevy_granite_core::GraniteClass for #name #ty_generics #where_clause {
+            const CLASS_NAME: &'static str = #name_str;
+            const TYPE_NAME: &'static str = #type_name;
+            const TYPE_ABV: &'static str = #type_abv;
+            const CATEGORY: bevy_granite_core::ClassCategory = #category;
+            const ICON: Option<(&'static str, &'static [u8])> = #icon_tokens;
+            const ICON_PATH: Option<&'static str> = #icon_path_tokens;
+        }
+    };
+
+    TokenStream::from(expanded)
+}
@@ -0,0 +1,56 @@
+// Soak test: loops load, random edits, save and reload on a copy of a scene, headless and without a GPU
+// cargo run --example soak_test --no-default-features --features stress -- scenes/dungeon.scene 50
+// Exits with an error code when any round trip check failed
+use bevy::{
+    app::ScheduleRunnerPlugin,
+    prelude::*,
+    render::{
+        settings::{RenderCreation, WgpuSettings},
+        RenderPlugin,
+    },
+    window::ExitCondition,
+};
+use bevy_granite_core::{BevyGraniteCore, SoakTestPlugin};
+use std::time::Duration;
+
+fn main() -> AppExit {
+    let mut args = std::env::args().skip(1);
+    let scene = args
+        .next()
+        .unwrap_or_else(|| "scenes/dungeon.scene".to_string());
+    let iterations = args
+        .next()
+        .and_then(|iterations| iterations.parse().ok())
+        .unwrap_or(100);
+
+    App::new()
+        .add_plugins(
+            DefaultPlugins
+                .set(WindowPlugin {
+                    primary_window: None,
+                    exit_condition: ExitCondition::DontExit,
+                    ..default()
+                })
+                // No GPU needed, meshes and materials are still loaded as assets
+                .set(RenderPlugin {
+                    render_creation: RenderCreation::Automatic(WgpuSettings {
+                        backends: None,
+                        ..default()
+                    }),
+                    ..default()
+                }),
+        )
+        .add_plugins(ScheduleRunnerPlugin::run_loop(Duration::from_secs_f64(
+            1.0 / 60.0,
+        )))
+        .add_plugins(BevyGraniteCore {
+            logging: true,
+            startup_worlds: default(),
+        })
+        .add_plugins(SoakTestPlugin {
+            scene,
+            iterations,
+            ..default()
+        })
+        .run()
+}
@@ -0,0 +1,82 @@
+// Validate command: loads scenes headless and runs every registered SceneValidator on them
+// cargo run --example validate_scenes --no-default-features --features core -- scenes/dungeon.scene
+// Exits with an error code when any issue is an error
+use bevy::{
+    app::ScheduleRunnerPlugin,
+    prelude::*,
+    render::{
+        settings::{RenderCreation, WgpuSettings},
+        RenderPlugin,
+    },
+    window::ExitCondition,
+};
+use bevy_granite_core::{
+    BevyGraniteCore, GraniteType, IdentityData, SceneIssue, SceneValidationPlugin, SceneValidator,
+    SceneValidatorAppExt, SpawnSource,
+};
+use std::time::Duration;
+
+/// Example rule, register your own the same way
+struct UnnamedEntities;
+
+impl SceneValidator for UnnamedEntities {
+    fn name(&self) -> &str {
+        "Unnamed entities"
+    }
+
+    fn validate(&self, world: &mut World) -> Vec<SceneIssue> {
+        let mut query = world.query_filtered::<(Entity, &IdentityData), With<SpawnSource>>();
+        query
+            .iter(world)
+            .filter(|(_, identity)| identity.name.trim().is_empty())
+            .map(|(entity, identity)| {
+                let name = identity.class.type_name();
+                SceneIssue::warning(format!("{} has no name", name))
+                    .on(entity)
+                    .with_fix(format!("Name it '{}'", name), move |world: &mut World| {
+                        if let Some(mut identity) = world.get_mut::<IdentityData>(entity) {
+                            identity.name = name.clone();
+                        }
+                    })
+            })
+            .collect()
+    }
+}
+
+fn main() -> AppExit {
+    let mut scenes: Vec<String> = std::env::args().skip(1).collect();
+    if scenes.is_empty() {
+        scenes.push("scenes/dungeon.scene".to_string());
+    }
+
+    App::new()
+        .add_plugins(
+            DefaultPlugins
+                .set(WindowPlugin {
+                    primary_window: None,
+                    exit_condition: ExitCondition::DontExit,
+                    ..default()
+                })
+                // No GPU needed, meshes and materials are still loaded as assets
+                .set(RenderPlugin {
+                    render_creation: RenderCreation::Automatic(WgpuSettings {
+                        backends: None,
+                        ..default()
+                    }),
+                    ..default()
+                }),
+        )
+        .add_plugins(ScheduleRunnerPlugin::run_loop(Duration::from_secs_f64(
+            1.0 / 60.0,
+        )))
+        .add_plugins(BevyGraniteCore {
+            logging: true,
+            startup_worlds: default(),
+        })
+        .add_scene_validator(UnnamedEntities)
+        .add_plugins(SceneValidationPlugin {
+            scenes,
+            ..default()
+        })
+        .run()
+}
@@ -0,0 +1,42 @@
+// Visual regression: renders a scene from fixed angles and compares the PNGs with stored baselines, without a window
+// cargo run --example visual_regression --no-default-features --features visual -- scenes/dungeon.scene tests/visual
+// Needs a GPU. Missing baselines are written on the first run, pass --update to overwrite them all.
+// Exits with an error code when any angle differs past the threshold, renders and diffs land in target/visual
+use bevy::{app::ScheduleRunnerPlugin, prelude::*, window::ExitCondition};
+use bevy_granite_core::{BevyGraniteCore, VisualRegressionPlugin};
+use std::{path::PathBuf, time::Duration};
+
+fn main() -> AppExit {
+    let update_baselines = std::env::args().any(|arg| arg == "--update");
+    let mut args = std::env::args()
+        .skip(1)
+        .filter(|arg| !arg.starts_with("--"));
+    let scene = args
+        .next()
+        .unwrap_or_else(|| "scenes/dungeon.scene".to_string());
+    let baseline_dir = args
+        .next()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("tests/visual"));
+
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: None,
+            exit_condition: ExitCondition::DontExit,
+            ..default()
+        }))
+        .add_plugins(ScheduleRunnerPlugin::run_loop(Duration::from_secs_f64(
+            1.0 / 60.0,
+        )))
+        .add_plugins(BevyGraniteCore {
+            logging: true,
+            startup_worlds: default(),
+        })
+        .add_plugins(VisualRegressionPlugin {
+            scene,
+            baseline_dir,
+            update_baselines,
+            ..default()
+        })
+        .run()
+}
@@ -75,6 +75,10 @@ pub struct BevyGranite {
     pub active: bool,
     /// String relative path to the default world file for UI
     pub default_world: String,
+    /// Named startup worlds (dev, demo, benchmark...). Picked with the --granite-world launch arg or the
+    /// GRANITE_WORLD env var. When empty, default_world is the only one
+    #[cfg(feature = "core")]
+    pub startup_worlds: bevy_granite_core::StartupWorlds,
     /// Whether to enable log setup, essentially controlling the logging system
     pub logging: bool,
 }
@@ -85,6 +89,8 @@ impl Default for BevyGranite {
             active: true,
             default_world: "scenes/default.mat".to_string(),
             logging: true,
+            #[cfg(feature = "core")]
+            startup_worlds: bevy_granite_core::StartupWorlds::default(),
         }
     }
 }
@@ -94,15 +100,25 @@ impl PluginGroup for BevyGranite {
     ///
     /// This method assembles all the individual plugins in the correct order utilizing feature sets
     ///
-    fn build(self) -> PluginGroupBuilder {
+    fn build(mut self) -> PluginGroupBuilder {
         let mut builder = PluginGroupBuilder::start::<Self>()
             // Required plugins
             .add(bevy_inspector_egui::DefaultInspectorConfigPlugin);
 
         #[cfg(feature = "core")]
         {
+            let mut startup_worlds = std::mem::take(&mut self.startup_worlds);
+            if startup_worlds.worlds.is_empty() {
+                startup_worlds.add("default", self.default_world.clone());
+            }
+            startup_worlds.apply_launch_selection();
+            if let Some(path) = startup_worlds.active_path() {
+                self.default_world = path.to_string();
+            }
+
             builder = builder.add(bevy_granite_core::BevyGraniteCore {
                 logging: self.logging,
+                startup_worlds,
             });
         }
 
@@ -132,6 +148,8 @@ impl PluginGroup for BevyGranite {
 /// - **Editor**: UI events, camera management, and entity tree utilities
 /// - **Core**: World loading/saving events and serialization utilities
 /// - **Gizmos**: Entity selection, duplication, and spawning events
+///
+/// Class and feature types are reached through their crate, i.e. `bevy_granite::bevy_granite_core::Weather`
 pub mod prelude {
     pub use crate::BevyGranite;
 
@@ -139,13 +157,16 @@ pub mod prelude {
     pub use crate::{
         bevy_granite_core,
         bevy_granite_core::{
-            absolute_asset_to_rel, rel_asset_to_absolute, BridgeTag, MainCamera,
-            RequestDespawnBySource, RequestDespawnSerializableEntities, RequestLoadEvent,
-            RequestReloadEvent, RequestSaveEvent, SaveSettings, SpawnSource, TreeHiddenEntity,
-            UICamera, WorldLoadSuccessEvent, WorldSaveSuccessEvent, RequestLoadBatchEvent, WorldLoadBatchSuccessEvent
+            absolute_asset_to_rel, register_granite_class, rel_asset_to_absolute, BridgeTag,
+            GraniteClass, MainCamera, RequestDespawnBySource, RequestDespawnSerializableEntities,
+            RequestLoadBatchEvent, RequestLoadEvent, RequestReloadEvent, RequestSaveEvent,
+            SaveSettings, SpawnSource, TreeHiddenEntity, UICamera, WorldLoadBatchSuccessEvent,
+            WorldLoadSuccessEvent, WorldSaveSuccessEvent,
         },
         bevy_granite_logging::{log, LogCategory, LogLevel, LogType},
-        bevy_granite_macros::{granite_component, register_editor_components, ui_callable_events},
+        bevy_granite_macros::{
+            granite_component, register_editor_components, ui_callable_events, GraniteClass,
+        },
     };
 
     #[cfg(feature = "gizmos")]
@@ -160,5 +181,8 @@ pub mod prelude {
     };
 
     #[cfg(feature = "editor")]
-    pub use crate::bevy_granite_editor::interface::tabs::events::ui::register_ui_callable_events_with_senders;
+    pub use crate::bevy_granite_editor::interface::tabs::events::ui::{
+        register_ui_callable_events_with_args, register_ui_callable_events_with_senders,
+        UICallableEventArgs, UICallableEventFeedback,
+    };
 }